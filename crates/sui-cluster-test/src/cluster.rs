@@ -12,7 +12,7 @@ use sui_graphql_rpc::test_infra::cluster::start_graphql_server;
 use sui_indexer::test_utils::{start_test_indexer, start_test_indexer_v2};
 use sui_indexer::IndexerConfig;
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
-use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv};
+use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv, SUI_CLIENT_CONFIG_VERSION};
 use sui_sdk::wallet_context::WalletContext;
 use sui_swarm::memory::Swarm;
 use sui_swarm_config::genesis_config::GenesisConfig;
@@ -183,6 +183,7 @@ impl Cluster for LocalNewCluster {
         // Check if we already have a config directory that is passed
         if let Some(config_dir) = options.config_dir.clone() {
             assert!(options.epoch_duration_ms.is_none());
+            assert!(options.mpc_parties.is_none() && options.mpc_threshold.is_none());
             // Load the config of the Sui authority.
             let network_config_path = config_dir.join(SUI_NETWORK_CONFIG);
             let network_config: NetworkConfig = PersistedConfig::read(&network_config_path)
@@ -204,6 +205,12 @@ impl Cluster for LocalNewCluster {
             if let Some(epoch_duration_ms) = options.epoch_duration_ms {
                 cluster_builder = cluster_builder.with_epoch_duration_ms(epoch_duration_ms);
             }
+            if let Some(mpc_parties) = options.mpc_parties {
+                cluster_builder = cluster_builder.with_num_validators(mpc_parties);
+            }
+            if let Some(mpc_threshold) = options.mpc_threshold {
+                cluster_builder = cluster_builder.with_signature_mpc_threshold(mpc_threshold);
+            }
         }
 
         if let Some(rpc_port) = fullnode_port {
@@ -359,16 +366,16 @@ pub async fn new_wallet_context_from_cluster(
         .add_key(None, SuiKeyPair::Ed25519(key_pair))
         .unwrap();
     SuiClientConfig {
+        version: SUI_CLIENT_CONFIG_VERSION,
         keystore,
-        envs: vec![SuiEnv {
-            alias: "localnet".to_string(),
-            rpc: fullnode_url.into(),
-            ws: None,
-        }],
+        envs: vec![SuiEnv::builder("localnet", fullnode_url).build().unwrap()],
         active_address: Some(address),
         active_env: Some("localnet".to_string()),
         dwallets: vec![],
         active_dwallet: None,
+        address_aliases: Default::default(),
+        profiles: Default::default(),
+        active_profile: None,
     }
     .persisted(&wallet_config_path)
     .save()
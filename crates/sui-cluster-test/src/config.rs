@@ -46,6 +46,14 @@ pub struct ClusterTestOpt {
     /// URL for the indexer RPC server
     #[clap(long)]
     pub graphql_address: Option<String>,
+    /// The number of validators of a new local network, each a party of the signature MPC
+    /// protocols.
+    #[clap(long)]
+    pub mpc_parties: Option<usize>,
+    /// The number of validators of a new local network needed to decrypt in the signature MPC
+    /// protocols. More than two thirds of them by default.
+    #[clap(long)]
+    pub mpc_threshold: Option<usize>,
 }
 
 fn obfuscated_pg_address(val: &Option<String>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -76,6 +84,8 @@ impl ClusterTestOpt {
             config_dir: None,
             graphql_address: None,
             use_indexer_v2: false,
+            mpc_parties: None,
+            mpc_threshold: None,
         }
     }
 }
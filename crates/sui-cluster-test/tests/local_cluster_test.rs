@@ -35,6 +35,8 @@ async fn test_sui_cluster() {
         config_dir: None,
         graphql_address: Some(graphql_address),
         use_indexer_v2: true,
+        mpc_parties: None,
+        mpc_threshold: None,
     };
 
     let _cluster = LocalNewCluster::start(&opts).await.unwrap();
@@ -14,7 +14,7 @@ use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use std::collections::{BTreeMap, BTreeSet};
 use std::net::SocketAddr;
-use std::num::NonZeroUsize;
+use std::num::{NonZeroU32, NonZeroUsize};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
@@ -153,6 +153,9 @@ pub struct NodeConfig {
     #[serde(default)]
     pub indexer_max_subscriptions: Option<usize>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_rpc_rate_limit_config: Option<JsonRpcRateLimitConfig>,
+
     #[serde(default = "default_transaction_kv_store_config")]
     pub transaction_kv_store_read_config: TransactionKeyValueStoreReadConfig,
 
@@ -175,6 +178,47 @@ pub struct TransactionKeyValueStoreReadConfig {
     pub base_url: String,
 }
 
+/// Rate limits of the JSON-RPC server. A request over a limit is rejected with a
+/// `429 Too Many Requests` response, and a subscription over a limit is not created.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct JsonRpcRateLimitConfig {
+    /// Per-client rate-limit (in requests/sec) of all the methods together.
+    ///
+    /// If unspecified, this will default to no limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_rate_limit: Option<NonZeroU32>,
+
+    /// Rate-limits of individual methods, by method name, e.g. `dwallet_getSignSession`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub method_rate_limits: BTreeMap<String, JsonRpcMethodRateLimit>,
+
+    /// The request header holding the address of the client, e.g. `x-forwarded-for`, for a
+    /// fullnode behind a trusted proxy. Only the last address of the header is used, the one the
+    /// proxy appended: the ones before it are set by the client.
+    ///
+    /// If unspecified, or missing from a request, the client is identified by the IP address of
+    /// its connection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_ip_header: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct JsonRpcMethodRateLimit {
+    /// Rate-limit (in requests/sec) of the method, for all the clients together.
+    ///
+    /// If unspecified, this will default to no limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<NonZeroU32>,
+
+    /// Per-client rate-limit (in requests/sec) of the method.
+    ///
+    /// If unspecified, this will default to no limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_rate_limit: Option<NonZeroU32>,
+}
+
 fn default_jwk_fetch_interval_seconds() -> u64 {
     3600
 }
@@ -3592,6 +3592,29 @@ impl AuthorityState {
                     limit,
                     descending,
                 )?,
+            EventFilter::DWalletEventKind(kind) => index_store
+                .events_by_move_event_struct_name(
+                    &kind.event_type(),
+                    tx_num,
+                    event_num,
+                    limit,
+                    descending,
+                )?,
+            EventFilter::DWalletSession(session_id) => index_store.events_by_dwallet_session(
+                &session_id,
+                tx_num,
+                event_num,
+                limit,
+                descending,
+            )?,
+            // The events of a dWallet are not indexed by it.
+            EventFilter::DWallet(_) => {
+                return Err(SuiError::UserInputError {
+                    error: UserInputError::Unsupported(
+                        "Querying events by dWallet is not supported, subscribe to them or query them by DWalletEventKind instead.".to_string(),
+                    ),
+                })
+            }
             // not using "_ =>" because we want to make sure we remember to add new variants here
             EventFilter::Package(_)
             | EventFilter::MoveEventField { .. }
@@ -7,11 +7,13 @@ mod metrics;
 mod presign;
 mod sign;
 mod signature_mpc_subscriber;
+mod status;
 mod submit_to_consensus;
 
 use crate::authority::{AuthorityState, EffectsNotifyRead};
 use crate::authority_client::AuthorityAPI;
 pub use crate::signature_mpc::metrics::SignatureMPCMetrics;
+pub use crate::signature_mpc::status::SignatureMPCStatus;
 use crate::signature_mpc::submit_to_consensus::SubmitSignatureMPC;
 pub use crate::signature_mpc::submit_to_consensus::SubmitSignatureMPCToConsensus;
 use futures::FutureExt;
@@ -58,7 +60,7 @@ use typed_store::Map;
 
 use dkg::DKGState;
 use tokio_stream::StreamExt;
-use sui_types::messages_signature_mpc::{InitiateSignatureMPCProtocol, SignatureMPCMessage, SignatureMPCMessageProtocols, SignatureMPCMessageSummary, SignatureMPCOutput, SignatureMPCSessionID};
+use sui_types::messages_signature_mpc::{InitiateSignatureMPCProtocol, SignatureMPCMessage, SignatureMPCMessageProtocols, SignatureMPCMessageSummary, SignatureMPCOutput, SignatureMPCProtocol, SignatureMPCSessionID};
 
 use crate::signature_mpc::dkg::{DKGRound, DKGRoundCompletion};
 use crate::signature_mpc::presign::{PresignRound, PresignRoundCompletion, PresignState};
@@ -84,6 +86,7 @@ pub struct SignatureMPCAggregator {
     tiresias_key_share_decryption_key_share: SecretKeyShareSizedNumber,
     submit: Arc<dyn SubmitSignatureMPC>,
     metrics: Arc<SignatureMPCMetrics>,
+    status: Arc<SignatureMPCStatus>,
     exit: watch::Receiver<()>,
     /// Channel to receive protocols initiation for signature mpc from the state.
     rx_initiate_signature_mpc_protocol_sender: mpsc::Receiver<InitiateSignatureMPCProtocol>,
@@ -109,6 +112,7 @@ impl SignatureMPCAggregator {
         tiresias_key_share_decryption_key_share: SecretKeyShareSizedNumber,
         submit: Arc<dyn SubmitSignatureMPC>,
         metrics: Arc<SignatureMPCMetrics>,
+        status: Arc<SignatureMPCStatus>,
         exit: watch::Receiver<()>,
         rx_initiate_signature_mpc_protocol_sender: mpsc::Receiver<InitiateSignatureMPCProtocol>,
         rx_signature_mpc_protocol_message_sender: mpsc::Receiver<SignatureMPCMessage>,
@@ -122,6 +126,7 @@ impl SignatureMPCAggregator {
             tiresias_key_share_decryption_key_share,
             submit,
             metrics,
+            status,
             exit,
             rx_initiate_signature_mpc_protocol_sender,
             rx_signature_mpc_protocol_message_sender,
@@ -158,6 +163,7 @@ impl SignatureMPCAggregator {
                     let tiresias_public_parameters = self.tiresias_public_parameters.clone();
                     let tiresias_key_share_decryption_key_share = self.tiresias_key_share_decryption_key_share.clone();
                     let submit = self.submit.clone();
+                    let status = self.status.clone();
 
                     let session_refs = self.session_refs.clone();
 
@@ -175,6 +181,7 @@ impl SignatureMPCAggregator {
                         tiresias_public_parameters,
                         tiresias_key_share_decryption_key_share,
                         submit,
+                        status,
                         session_refs,
                         dkg_session_rounds,
                         dkg_session_states,
@@ -194,6 +201,7 @@ impl SignatureMPCAggregator {
                     let tiresias_public_parameters = self.tiresias_public_parameters.clone();
                     let tiresias_key_share_decryption_key_share = self.tiresias_key_share_decryption_key_share.clone();
                     let submit = self.submit.clone();
+                    let status = self.status.clone();
 
                     let session_refs = self.session_refs.clone();
                     let dkg_session_rounds = self.dkg_session_rounds.clone();
@@ -211,6 +219,7 @@ impl SignatureMPCAggregator {
                         tiresias_public_parameters,
                         tiresias_key_share_decryption_key_share,
                         submit,
+                        status,
                         session_refs,
                         dkg_session_rounds,
                         dkg_session_states,
@@ -233,6 +242,7 @@ impl SignatureMPCAggregator {
         tiresias_public_parameters: DecryptionPublicParameters,
         tiresias_key_share_decryption_key_share: SecretKeyShareSizedNumber,
         submit: Arc<dyn SubmitSignatureMPC>,
+        status: Arc<SignatureMPCStatus>,
         session_refs: Arc<DashMap<SignatureMPCSessionID, ObjectRef>>,
         dkg_session_rounds: Arc<DashMap<SignatureMPCSessionID, DKGRound>>,
         dkg_session_states: Arc<DashMap<SignatureMPCSessionID, DKGState>>,
@@ -245,10 +255,21 @@ impl SignatureMPCAggregator {
         let session_id = message.summary.session_id;
         let sender_party_id = (epoch_store.committee().authority_index(&message.summary.auth_sig().authority).unwrap() + 1) as PartyID;
 
-        let Some(session_ref) = session_refs.get(&session_id) else {
+        // Copy the reference out rather than holding the guard, which would deadlock the removals below.
+        let Some(session_ref) = session_refs.get(&session_id).map(|r| *r) else {
             return;
         };
-        let session_ref = session_ref.clone();
+        if status.is_aborted(&session_id) {
+            // The operator aborted the session through the admin interface, drop what is left of it.
+            session_refs.remove(&session_id);
+            dkg_session_rounds.remove(&session_id);
+            dkg_session_states.remove(&session_id);
+            presign_session_rounds.remove(&session_id);
+            presign_session_states.remove(&session_id);
+            sign_session_rounds.remove(&session_id);
+            sign_session_states.remove(&session_id);
+            return;
+        }
         match &message.summary.message {
             SignatureMPCMessageProtocols::DKG(m) => {
                 let mut state = dkg_session_states
@@ -270,6 +291,7 @@ impl SignatureMPCAggregator {
                             dkg_session_rounds.clone(),
                             dkg_session_states.clone(),
                             submit.clone(),
+                            status.clone(),
                         );
                     }
                 }
@@ -302,6 +324,7 @@ impl SignatureMPCAggregator {
                             presign_session_rounds.clone(),
                             presign_session_states.clone(),
                             submit.clone(),
+                            status.clone(),
                         );
                     }
                 }
@@ -333,6 +356,7 @@ impl SignatureMPCAggregator {
                             presign_session_rounds.clone(),
                             presign_session_states.clone(),
                             submit.clone(),
+                            status.clone(),
                         );
                     }
                 }
@@ -364,6 +388,7 @@ impl SignatureMPCAggregator {
                             sign_session_rounds.clone(),
                             sign_session_states.clone(),
                             submit.clone(),
+                            status.clone(),
                         );
                     }
                 }
@@ -381,11 +406,15 @@ impl SignatureMPCAggregator {
         dkg_session_rounds: Arc<DashMap<SignatureMPCSessionID, DKGRound>>,
         dkg_session_states: Arc<DashMap<SignatureMPCSessionID, DKGState>>,
         submit: Arc<dyn SubmitSignatureMPC>,
+        status: Arc<SignatureMPCStatus>,
     ) {
         spawn_monitored_task!(async move {
             let m = {
                 if let Some(mut round) = dkg_session_rounds.get_mut(&session_id) {
-                    round.complete_round(state.clone()).ok()
+                    round
+                        .complete_round(state.clone())
+                        .tap_err(|e| status.session_failed(SignatureMPCProtocol::DKG, session_id, e))
+                        .ok()
                 } else {
                     None
                 }
@@ -406,10 +435,11 @@ impl SignatureMPCAggregator {
                                 ),
                                 &epoch_store,
                             )
-                            .await;
+                            .await
+                            .tap_err(|e| status.session_failed(SignatureMPCProtocol::DKG, session_id, e));
                     }
                     DKGRoundCompletion::Output(secret_key_share_encryption_and_proof) => {
-                        let _ = submit
+                        let result = submit
                             .sign_and_submit_output(
                                 &SignatureMPCOutput::new_dkg(
                                     epoch,
@@ -424,6 +454,7 @@ impl SignatureMPCAggregator {
                                 &epoch_store,
                             )
                             .await;
+                        status.output_submitted(SignatureMPCProtocol::DKG, session_id, &result);
                     }
                     DKGRoundCompletion::None => {}
                 }
@@ -441,11 +472,15 @@ impl SignatureMPCAggregator {
         presign_session_rounds: Arc<DashMap<SignatureMPCSessionID, PresignRound>>,
         presign_session_states: Arc<DashMap<SignatureMPCSessionID, PresignState>>,
         submit: Arc<dyn SubmitSignatureMPC>,
+        status: Arc<SignatureMPCStatus>,
     ) {
         spawn_monitored_task!(async move {
             let m = {
                 if let Some(mut round) = presign_session_rounds.get_mut(&session_id) {
-                    round.complete_round(state.clone()).ok()
+                    round
+                        .complete_round(state.clone())
+                        .tap_err(|e| status.session_failed(SignatureMPCProtocol::Presign, session_id, e))
+                        .ok()
                 } else {
                     None
                 }
@@ -466,7 +501,8 @@ impl SignatureMPCAggregator {
                                 ),
                                 &epoch_store,
                             )
-                            .await;
+                            .await
+                            .tap_err(|e| status.session_failed(SignatureMPCProtocol::Presign, session_id, e));
                     }
                     PresignRoundCompletion::FirstRoundOutput((output, message_to_submit, individual_encrypted_nonce_shares_and_public_shares)) => {
                         {
@@ -505,7 +541,8 @@ impl SignatureMPCAggregator {
                                 ),
                                 &epoch_store,
                             )
-                            .await;
+                            .await
+                            .tap_err(|e| status.session_failed(SignatureMPCProtocol::Presign, session_id, e));
                         let _ = submit
                             .sign_and_submit_output(
                                 &SignatureMPCOutput::new_presign_output(
@@ -517,7 +554,8 @@ impl SignatureMPCAggregator {
                                 .unwrap(),
                                 &epoch_store,
                             )
-                            .await;
+                            .await
+                            .tap_err(|e| status.session_failed(SignatureMPCProtocol::Presign, session_id, e));
                     }
                     PresignRoundCompletion::SecondRoundOutput(_) => {
                         // TODO: should never happen, add error
@@ -538,11 +576,15 @@ impl SignatureMPCAggregator {
         presign_session_rounds: Arc<DashMap<SignatureMPCSessionID, PresignRound>>,
         presign_session_states: Arc<DashMap<SignatureMPCSessionID, PresignState>>,
         submit: Arc<dyn SubmitSignatureMPC>,
+        status: Arc<SignatureMPCStatus>,
     ) {
         spawn_monitored_task!(async move {
             let m = {
                 if let Some(mut round) = presign_session_rounds.get_mut(&session_id) {
-                    round.complete_round(state.clone()).ok()
+                    round
+                        .complete_round(state.clone())
+                        .tap_err(|e| status.session_failed(SignatureMPCProtocol::Presign, session_id, e))
+                        .ok()
                 } else {
                     None
                 }
@@ -563,13 +605,14 @@ impl SignatureMPCAggregator {
                                 ),
                                 &epoch_store,
                             )
-                            .await;
+                            .await
+                            .tap_err(|e| status.session_failed(SignatureMPCProtocol::Presign, session_id, e));
                     }
                     PresignRoundCompletion::FirstRoundOutput(_) => {
                         // TODO: should never happen, add error
                     }
                     PresignRoundCompletion::SecondRoundOutput(presigns) => {
-                        let _ = submit
+                        let result = submit
                             .sign_and_submit_output(
                                 &SignatureMPCOutput::new_presign(
                                     epoch,
@@ -581,6 +624,7 @@ impl SignatureMPCAggregator {
                                 &epoch_store,
                             )
                             .await;
+                        status.output_submitted(SignatureMPCProtocol::Presign, session_id, &result);
                     }
                     PresignRoundCompletion::None => {}
                 }
@@ -599,11 +643,15 @@ impl SignatureMPCAggregator {
         sign_session_rounds: Arc<DashMap<SignatureMPCSessionID, SignRound>>,
         sign_session_states: Arc<DashMap<SignatureMPCSessionID, SignState>>,
         submit: Arc<dyn SubmitSignatureMPC>,
+        status: Arc<SignatureMPCStatus>,
     ) {
         spawn_monitored_task!(async move {
             let m = {
                 if let Some(mut round) = sign_session_rounds.get_mut(&session_id) {
-                    round.complete_round(state.clone()).ok()
+                    round
+                        .complete_round(state.clone())
+                        .tap_err(|e| status.session_failed(SignatureMPCProtocol::Sign, session_id, e))
+                        .ok()
                 } else {
                     None
                 }
@@ -611,18 +659,19 @@ impl SignatureMPCAggregator {
             if let Some(m) = m {
                 match m {
                     SignRoundCompletion::Output(sigs) => {
-                        let _ = submit
-                                    .sign_and_submit_output(
-                                        &SignatureMPCOutput::new_sign(
-                                            epoch,
-                                            session_id,
-                                            session_ref,
-                                            sigs,
-                                        )
-                                        .unwrap(),
-                                        &epoch_store,
-                                    )
-                                    .await;
+                        let result = submit
+                            .sign_and_submit_output(
+                                &SignatureMPCOutput::new_sign(
+                                    epoch,
+                                    session_id,
+                                    session_ref,
+                                    sigs,
+                                )
+                                .unwrap(),
+                                &epoch_store,
+                            )
+                            .await;
+                        status.output_submitted(SignatureMPCProtocol::Sign, session_id, &result);
                     }
                     SignRoundCompletion::None => {}
                 }
@@ -638,6 +687,7 @@ impl SignatureMPCAggregator {
         tiresias_public_parameters: DecryptionPublicParameters,
         tiresias_key_share_decryption_key_share: SecretKeyShareSizedNumber,
         submit: Arc<dyn SubmitSignatureMPC>,
+        status: Arc<SignatureMPCStatus>,
         session_refs: Arc<DashMap<SignatureMPCSessionID, ObjectRef>>,
         dkg_session_rounds: Arc<DashMap<SignatureMPCSessionID, DKGRound>>,
         dkg_session_states: Arc<DashMap<SignatureMPCSessionID, DKGState>>,
@@ -661,7 +711,9 @@ impl SignatureMPCAggregator {
                     parties.clone(),
                     session_id,
                     commitment_to_centralized_party_secret_key_share.clone(),
-                ) {
+                )
+                .tap_err(|e| status.session_failed(SignatureMPCProtocol::DKG, session_id, e))
+                {
                     let mut state = dkg_session_states
                         .entry(session_id)
                         .or_insert_with(|| DKGState::new(epoch, party_id, parties.clone()));
@@ -675,8 +727,11 @@ impl SignatureMPCAggregator {
                         SignatureMPCMessageProtocols::DKG(message),
                         session_id,
                     );
-                    // TODO: Handle error
-                    let _ = submit.sign_and_submit_message(&summary, &epoch_store).await;
+                    status.session_started(SignatureMPCProtocol::DKG, session_id);
+                    let _ = submit
+                        .sign_and_submit_message(&summary, &epoch_store)
+                        .await
+                        .tap_err(|e| status.session_failed(SignatureMPCProtocol::DKG, session_id, e));
                 }
             }
             InitiateSignatureMPCProtocol::Presign {
@@ -695,7 +750,9 @@ impl SignatureMPCAggregator {
                     session_id,
                     dkg_output,
                     commitments_and_proof_to_centralized_party_nonce_shares.clone(),
-                ) {
+                )
+                .tap_err(|e| status.session_failed(SignatureMPCProtocol::Presign, session_id, e))
+                {
                     let mut state = presign_session_states.entry(session_id).or_insert_with(|| {
                         PresignState::new(
                             tiresias_public_parameters
@@ -717,8 +774,11 @@ impl SignatureMPCAggregator {
                         SignatureMPCMessageProtocols::PresignFirstRound(message),
                         session_id,
                     );
-                    // TODO: Handle error
-                    let _ = submit.sign_and_submit_message(&summary, &epoch_store).await;
+                    status.session_started(SignatureMPCProtocol::Presign, session_id);
+                    let _ = submit
+                        .sign_and_submit_message(&summary, &epoch_store)
+                        .await
+                        .tap_err(|e| status.session_failed(SignatureMPCProtocol::Presign, session_id, e));
                 }
             }
             InitiateSignatureMPCProtocol::Sign {
@@ -744,7 +804,9 @@ impl SignatureMPCAggregator {
                     public_nonce_encrypted_partial_signature_and_proofs.clone(),
                     presigns,
                     hash.into()
-                ) {
+                )
+                .tap_err(|e| status.session_failed(SignatureMPCProtocol::Sign, session_id, e))
+                {
                     let mut state = sign_session_states.entry(session_id).or_insert_with(|| {
                         SignState::new(tiresias_public_parameters, epoch, party_id, parties, session_id)
                     });
@@ -758,8 +820,11 @@ impl SignatureMPCAggregator {
                         SignatureMPCMessageProtocols::Sign(message),
                         session_id,
                     );
-                    // TODO: Handle error
-                    let _ = submit.sign_and_submit_message(&summary, &epoch_store).await;
+                    status.session_started(SignatureMPCProtocol::Sign, session_id);
+                    let _ = submit
+                        .sign_and_submit_message(&summary, &epoch_store)
+                        .await
+                        .tap_err(|e| status.session_failed(SignatureMPCProtocol::Sign, session_id, e));
                 }
             }
         }
@@ -779,6 +844,7 @@ impl SignatureMPCService {
         epoch_store: Arc<AuthorityPerEpochStore>,
         submit: Arc<dyn SubmitSignatureMPC>,
         metrics: Arc<SignatureMPCMetrics>,
+        status: Arc<SignatureMPCStatus>,
     ) -> (Arc<Self>, watch::Sender<()> /* The exit sender */) {
         info!("Starting signature mpc service.");
 
@@ -795,6 +861,7 @@ impl SignatureMPCService {
             + 1) as PartyID;
 
         let epoch = epoch_store.epoch();
+        status.start_epoch(epoch, party_id);
 
         let rx_initiate_signature_mpc_protocol_sender =
             SignatureMpcSubscriber::new(epoch_store.clone(), exit_rcv.clone());
@@ -816,6 +883,7 @@ impl SignatureMPCService {
             tiresias_key_share_decryption_key_share,
             submit,
             metrics,
+            status,
             exit_rcv,
             rx_initiate_signature_mpc_protocol_sender,
             rx_signature_mpc_protocol_message_sender,
@@ -873,13 +941,69 @@ impl SignatureMPCServiceNotify for SignatureMPCServiceNoop {
 
 #[cfg(test)]
 mod tests {
-    use crate::signature_mpc::SubmitSignatureMPC;
+    use crate::signature_mpc::{SignatureMPCAggregator, SignatureMPCStatus, SubmitSignatureMPC};
     use std::sync::Arc;
+    use std::time::Duration;
+    use dashmap::DashMap;
     use either::Either;
+    use signature_mpc::twopc_mpc_protocols::config_signature_mpc_secret_for_network_for_testing;
     use tokio::sync::mpsc;
+    use sui_types::base_types::random_object_ref;
     use sui_types::error::SuiResult;
-    use sui_types::messages_signature_mpc::{SignatureMPCMessageSummary, SignatureMPCOutput};
+    use sui_types::messages_signature_mpc::{SignatureMPCMessage, SignatureMPCMessageProtocols, SignatureMPCMessageSummary, SignatureMPCOutput, SignatureMPCProtocol, SignatureMPCSessionID, SignedSignatureMPCMessageSummary};
     use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
+    use crate::authority::test_authority_builder::TestAuthorityBuilder;
+
+    #[tokio::test]
+    async fn test_message_of_aborted_session() {
+        let state = TestAuthorityBuilder::new().build().await;
+        let epoch_store = state.epoch_store_for_testing().clone();
+        let epoch = epoch_store.epoch();
+        let (tiresias_public_parameters, key_shares) = config_signature_mpc_secret_for_network_for_testing(1);
+        let (submit, _outputs) = mpsc::channel::<Either<SignatureMPCMessageSummary, SignatureMPCOutput>>(10);
+
+        // A live sign session, which the operator aborts.
+        let session_id = SignatureMPCSessionID([1; 32]);
+        let status = SignatureMPCStatus::new(true);
+        status.start_epoch(epoch, 1);
+        status.session_started(SignatureMPCProtocol::Sign, session_id);
+        let session_refs = Arc::new(DashMap::new());
+        session_refs.insert(session_id, random_object_ref());
+        assert_eq!(status.abort_session(session_id), Some(SignatureMPCProtocol::Sign));
+
+        let summary = SignatureMPCMessageSummary {
+            epoch,
+            message: SignatureMPCMessageProtocols::Sign(vec![]),
+            session_id,
+        };
+        let message = SignatureMPCMessage {
+            summary: SignedSignatureMPCMessageSummary::new(epoch, summary, &*state.secret, state.name),
+        };
+        let insert = SignatureMPCAggregator::insert_message(
+            epoch,
+            epoch_store.clone(),
+            1,
+            [1].into(),
+            tiresias_public_parameters,
+            key_shares[&1],
+            Arc::new(submit),
+            status.clone(),
+            session_refs.clone(),
+            Arc::new(DashMap::new()),
+            Arc::new(DashMap::new()),
+            Arc::new(DashMap::new()),
+            Arc::new(DashMap::new()),
+            Arc::new(DashMap::new()),
+            Arc::new(DashMap::new()),
+            message,
+        );
+
+        // The message of the aborted session drops what is left of it.
+        tokio::time::timeout(Duration::from_secs(10), insert)
+            .await
+            .expect("a message of an aborted session must not block the service");
+        assert!(session_refs.is_empty());
+    }
 
     #[async_trait::async_trait]
     impl SubmitSignatureMPC for mpsc::Sender<Either<SignatureMPCMessageSummary, SignatureMPCOutput>> {
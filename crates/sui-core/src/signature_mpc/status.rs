@@ -0,0 +1,253 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use parking_lot::Mutex;
+use signature_mpc::twopc_mpc_protocols::PartyID;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use sui_types::base_types::{EpochId, ObjectID};
+use sui_types::error::SuiResult;
+use sui_types::messages_signature_mpc::{
+    SignatureMPCFailure, SignatureMPCProtocol, SignatureMPCSessionID, SignatureMPCSessionInFlight,
+    SignatureMPCSessionsStatus, SignatureMPCStatusSummary,
+};
+use tracing::warn;
+
+/// The failures kept for the admin interface, at most.
+const MAX_RECENT_FAILURES: usize = 64;
+
+/// The health of the signature MPC service of this node, served by the admin interface.
+///
+/// It outlives the service, which is restarted every epoch: the sessions are counted per epoch,
+/// while the recent failures are kept across epochs.
+pub struct SignatureMPCStatus {
+    key_share_present: bool,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    epoch: Option<EpochId>,
+    party_id: Option<PartyID>,
+    sessions: HashMap<SignatureMPCProtocol, SignatureMPCSessionsStatus>,
+    /// The protocol of each pending session, and when this node initiated its party of it.
+    pending: HashMap<SignatureMPCSessionID, (SignatureMPCProtocol, u64)>,
+    /// The sessions of the epoch the operator aborted, whose messages are ignored.
+    aborted: HashSet<SignatureMPCSessionID>,
+    recent_failures: VecDeque<SignatureMPCFailure>,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+impl SignatureMPCStatus {
+    pub fn new(key_share_present: bool) -> Arc<Self> {
+        Arc::new(Self {
+            key_share_present,
+            inner: Mutex::new(Inner::default()),
+        })
+    }
+
+    /// Reset the sessions of the previous epoch, for the service of `epoch` to start.
+    pub(crate) fn start_epoch(&self, epoch: EpochId, party_id: PartyID) {
+        let mut inner = self.inner.lock();
+        inner.epoch = Some(epoch);
+        inner.party_id = Some(party_id);
+        inner.sessions.clear();
+        inner.pending.clear();
+        inner.aborted.clear();
+    }
+
+    /// Record that this node initiated its party of the session `session_id`.
+    pub(crate) fn session_started(
+        &self,
+        protocol: SignatureMPCProtocol,
+        session_id: SignatureMPCSessionID,
+    ) {
+        let mut inner = self.inner.lock();
+        if inner
+            .pending
+            .insert(session_id, (protocol, now_ms()))
+            .is_none()
+        {
+            let sessions = inner.sessions.entry(protocol).or_default();
+            sessions.participated += 1;
+            sessions.pending += 1;
+        }
+    }
+
+    /// Record that this node submitted the output of the session `session_id`.
+    pub(crate) fn session_completed(&self, session_id: SignatureMPCSessionID) {
+        let mut inner = self.inner.lock();
+        if let Some((protocol, _)) = inner.pending.remove(&session_id) {
+            let sessions = inner.sessions.entry(protocol).or_default();
+            sessions.completed += 1;
+            sessions.pending -= 1;
+        }
+    }
+
+    /// Record the `result` of submitting the output of the session `session_id`.
+    pub(crate) fn output_submitted(
+        &self,
+        protocol: SignatureMPCProtocol,
+        session_id: SignatureMPCSessionID,
+        result: &SuiResult,
+    ) {
+        match result {
+            Ok(()) => self.session_completed(session_id),
+            Err(e) => self.session_failed(protocol, session_id, e),
+        }
+    }
+
+    /// Record that the session `session_id` failed on this node with `error`.
+    pub(crate) fn session_failed(
+        &self,
+        protocol: SignatureMPCProtocol,
+        session_id: SignatureMPCSessionID,
+        error: impl Debug,
+    ) {
+        let error = format!("{error:?}");
+        warn!(%protocol, ?session_id, %error, "signature mpc session failed");
+
+        let timestamp_ms = now_ms();
+        let mut inner = self.inner.lock();
+        let was_pending = inner.pending.remove(&session_id).is_some();
+        let sessions = inner.sessions.entry(protocol).or_default();
+        sessions.failed += 1;
+        if was_pending {
+            sessions.pending -= 1;
+        }
+        let epoch = inner.epoch.unwrap_or_default();
+        if inner.recent_failures.len() == MAX_RECENT_FAILURES {
+            inner.recent_failures.pop_front();
+        }
+        inner.recent_failures.push_back(SignatureMPCFailure {
+            timestamp_ms,
+            epoch,
+            protocol,
+            session_id,
+            error,
+        });
+    }
+
+    /// The sessions this node participates in which are neither completed nor failed, oldest first.
+    pub fn in_flight_sessions(&self) -> Vec<SignatureMPCSessionInFlight> {
+        let inner = self.inner.lock();
+        let mut sessions: Vec<_> = inner
+            .pending
+            .iter()
+            .map(
+                |(session_id, (protocol, started_at_ms))| SignatureMPCSessionInFlight {
+                    session_id: ObjectID::new(session_id.0),
+                    protocol: *protocol,
+                    started_at_ms: *started_at_ms,
+                },
+            )
+            .collect();
+        sessions.sort_by_key(|session| (session.started_at_ms, session.session_id));
+        sessions
+    }
+
+    /// Abort the pending session `session_id` on this node: its messages are ignored for the rest
+    /// of the epoch, and it is recorded as failed. Returns its protocol, or None if the session is
+    /// not pending.
+    ///
+    /// The other parties go on without this node, as they do without an unresponsive party.
+    pub fn abort_session(&self, session_id: SignatureMPCSessionID) -> Option<SignatureMPCProtocol> {
+        // The session is taken out of the pending ones under the same lock it is checked with, for
+        // a session completing concurrently not to be recorded as aborted.
+        let protocol = {
+            let mut inner = self.inner.lock();
+            let (protocol, _) = inner.pending.remove(&session_id)?;
+            inner.aborted.insert(session_id);
+            inner.sessions.entry(protocol).or_default().pending -= 1;
+            protocol
+        };
+        self.session_failed(protocol, session_id, "aborted by the operator");
+        Some(protocol)
+    }
+
+    /// Whether the operator aborted the session `session_id` during the epoch.
+    pub(crate) fn is_aborted(&self, session_id: &SignatureMPCSessionID) -> bool {
+        self.inner.lock().aborted.contains(session_id)
+    }
+
+    pub fn summary(&self) -> SignatureMPCStatusSummary {
+        let inner = self.inner.lock();
+        let sessions = |protocol| inner.sessions.get(&protocol).cloned().unwrap_or_default();
+        SignatureMPCStatusSummary {
+            key_share_present: self.key_share_present,
+            epoch: inner.epoch,
+            party_id: inner.party_id,
+            dkg: sessions(SignatureMPCProtocol::DKG),
+            presign: sessions(SignatureMPCProtocol::Presign),
+            sign: sessions(SignatureMPCProtocol::Sign),
+            recent_failures: inner.recent_failures.iter().cloned().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_sessions() {
+        let status = SignatureMPCStatus::new(true);
+        status.start_epoch(1, 2);
+        let session = |byte| SignatureMPCSessionID([byte; 32]);
+
+        status.session_started(SignatureMPCProtocol::Presign, session(1));
+        status.session_started(SignatureMPCProtocol::Presign, session(2));
+        status.session_started(SignatureMPCProtocol::Presign, session(3));
+        status.session_completed(session(1));
+        status.session_failed(SignatureMPCProtocol::Presign, session(2), "invalid proof");
+
+        let summary = status.summary();
+        assert_eq!(summary.epoch, Some(1));
+        assert_eq!(summary.party_id, Some(2));
+        assert_eq!(
+            summary.presign,
+            SignatureMPCSessionsStatus {
+                participated: 3,
+                completed: 1,
+                failed: 1,
+                pending: 1,
+            }
+        );
+        assert_eq!(summary.sign, SignatureMPCSessionsStatus::default());
+        assert_eq!(summary.recent_failures.len(), 1);
+        assert_eq!(summary.recent_failures[0].session_id, session(2));
+
+        let in_flight = status.in_flight_sessions();
+        assert_eq!(in_flight.len(), 1);
+        assert_eq!(in_flight[0].session_id, ObjectID::new([3; 32]));
+        assert_eq!(in_flight[0].protocol, SignatureMPCProtocol::Presign);
+
+        // Only pending sessions are aborted.
+        assert_eq!(status.abort_session(session(1)), None);
+        assert!(!status.is_aborted(&session(1)));
+        assert_eq!(
+            status.abort_session(session(3)),
+            Some(SignatureMPCProtocol::Presign)
+        );
+        assert!(status.is_aborted(&session(3)));
+        assert!(status.in_flight_sessions().is_empty());
+        let summary = status.summary();
+        assert_eq!(summary.presign.failed, 2);
+        assert_eq!(summary.presign.pending, 0);
+        assert_eq!(summary.recent_failures.len(), 2);
+
+        // The sessions are counted per epoch, the failures are kept.
+        status.start_epoch(2, 2);
+        let summary = status.summary();
+        assert_eq!(summary.presign, SignatureMPCSessionsStatus::default());
+        assert_eq!(summary.recent_failures.len(), 2);
+        assert!(!status.is_aborted(&session(3)));
+    }
+}
@@ -28,7 +28,7 @@ use sui_types::sui_system_state::SuiSystemStateTrait;
 use tokio::sync::{broadcast, mpsc};
 use tokio::time::{interval, sleep};
 use sui_types::messages_signature_mpc::{config_signature_mpc_secret_for_network_for_testing, DecryptionPublicParameters, PartyID, SecretKeyShareSizedNumber, SignatureMPCMessageSummary, SignatureMPCOutput};
-use crate::signature_mpc::{SignatureMPCService, SignatureMPCMetrics};
+use crate::signature_mpc::{SignatureMPCService, SignatureMPCMetrics, SignatureMPCStatus};
 
 async fn send_transactions(
     name: &bls12381::min_sig::BLS12381PublicKey,
@@ -104,6 +104,7 @@ pub fn signature_mpc_service_for_testing(decryption_key_share_public_parameters:
         epoch_store.clone(),
         Arc::new(submit),
         SignatureMPCMetrics::new_for_tests(),
+        SignatureMPCStatus::new(true),
     );
     signature_mpc_service
 }
@@ -0,0 +1,462 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+/// Bitcoin SPV light client.
+///
+/// Maintains a Bitcoin header chain, rooted at a trusted checkpoint, as a shared object.
+/// Every submitted header is checked for proof of work and for the difficulty retargeting rules
+/// of its network, and the chain with the most accumulated work is selected as the main chain.
+/// dWallet logic can then condition on a Bitcoin transaction being included in a main chain
+/// block with enough confirmations, by verifying a merkle inclusion proof against it.
+///
+/// All hashes (block hashes, merkle roots and txids) are in internal byte order, i.e. the raw
+/// double-SHA256 output, which is the reverse of the hex shown by block explorers.
+#[allow(unused_field)]
+module dwallet_system::bitcoin_spv {
+    use std::hash;
+    use std::vector;
+    use dwallet::object::{Self, ID, UID};
+    use dwallet::table::{Self, Table};
+    use dwallet::tx_context::TxContext;
+    use dwallet::transfer;
+    use dwallet::event;
+
+    const EInvalidHeaderLength: u64 = 0;
+    const EUnknownParent: u64 = 1;
+    const EHeaderAlreadyKnown: u64 = 2;
+    const EInvalidDifficulty: u64 = 3;
+    const EInsufficientProofOfWork: u64 = 4;
+    const EUnknownBlock: u64 = 5;
+    const EInsufficientConfirmations: u64 = 6;
+    const EInvalidMerkleProof: u64 = 7;
+    const EInvalidNetwork: u64 = 8;
+    const EInvalidCheckpoint: u64 = 9;
+    const EInvalidTransaction: u64 = 10;
+
+    const NETWORK_MAINNET: u8 = 0;
+    const NETWORK_TESTNET: u8 = 1;
+    const NETWORK_REGTEST: u8 = 2;
+
+    const HEADER_LENGTH: u64 = 80;
+    const HASH_LENGTH: u64 = 32;
+    const DIFFICULTY_ADJUSTMENT_INTERVAL: u64 = 2016;
+    /// Two weeks, in seconds.
+    const TARGET_TIMESPAN: u64 = 1209600;
+    /// Ten minutes, in seconds.
+    const TARGET_SPACING: u64 = 600;
+
+    const MAX_U256: u256 = 0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff;
+    const MAINNET_POW_LIMIT: u256 = 0x00000000ffffffffffffffffffffffffffffffffffffffffffffffffffffffff;
+    const REGTEST_POW_LIMIT: u256 = 0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff;
+
+    /// A parsed 80-byte Bitcoin block header.
+    struct BlockHeader has store, copy, drop {
+        version: u32,
+        prev_block_hash: vector<u8>,
+        merkle_root: vector<u8>,
+        timestamp: u32,
+        bits: u32,
+        nonce: u32,
+    }
+
+    /// A header known to the light client, together with its position in the header tree.
+    struct HeaderEntry has store, copy, drop {
+        header: BlockHeader,
+        height: u64,
+        /// Work accumulated from the checkpoint up to and including this header.
+        chainwork: u256,
+        /// Timestamp of the first block of the difficulty period this header belongs to.
+        period_start_timestamp: u32,
+        /// `bits` of the last block in this period that was not mined at minimum difficulty (testnet).
+        last_regular_bits: u32,
+    }
+
+    struct BitcoinLightClient has key {
+        id: UID,
+        network: u8,
+        pow_limit: u256,
+        checkpoint_height: u64,
+        headers: Table<vector<u8>, HeaderEntry>,
+        /// Block hashes of the main chain, by height.
+        main_chain: Table<u64, vector<u8>>,
+        tip: vector<u8>,
+        tip_height: u64,
+    }
+
+    struct BitcoinHeaderSubmitted has copy, drop {
+        light_client_id: ID,
+        block_hash: vector<u8>,
+        height: u64,
+        is_new_tip: bool,
+    }
+
+    /// Create and share a light client for `network` (0 = mainnet, 1 = testnet, 2 = regtest),
+    /// starting from the trusted `checkpoint_header` at `checkpoint_height`.
+    /// `period_start_timestamp` is the timestamp of the first block of the checkpoint's difficulty period.
+    public fun create_light_client(
+        network: u8,
+        checkpoint_header: vector<u8>,
+        checkpoint_height: u64,
+        period_start_timestamp: u32,
+        ctx: &mut TxContext,
+    ) {
+        assert!(
+            network == NETWORK_MAINNET || network == NETWORK_TESTNET || network == NETWORK_REGTEST,
+            EInvalidNetwork
+        );
+        let pow_limit = if (network == NETWORK_REGTEST) { REGTEST_POW_LIMIT } else { MAINNET_POW_LIMIT };
+
+        let header = parse_header(&checkpoint_header);
+        let block_hash = double_sha256(checkpoint_header);
+        let target = verify_proof_of_work(&header, &block_hash, pow_limit);
+        assert!(
+            checkpoint_height % DIFFICULTY_ADJUSTMENT_INTERVAL != 0 || period_start_timestamp == header.timestamp,
+            EInvalidCheckpoint
+        );
+
+        let headers = table::new(ctx);
+        let main_chain = table::new(ctx);
+        let bits = header.bits;
+        table::add(&mut headers, block_hash, HeaderEntry {
+            header,
+            height: checkpoint_height,
+            chainwork: work(target),
+            period_start_timestamp,
+            last_regular_bits: bits,
+        });
+        table::add(&mut main_chain, checkpoint_height, block_hash);
+
+        let light_client = BitcoinLightClient {
+            id: object::new(ctx),
+            network,
+            pow_limit,
+            checkpoint_height,
+            headers,
+            main_chain,
+            tip: block_hash,
+            tip_height: checkpoint_height,
+        };
+
+        event::emit(BitcoinHeaderSubmitted {
+            light_client_id: object::id(&light_client),
+            block_hash,
+            height: checkpoint_height,
+            is_new_tip: true,
+        });
+
+        transfer::share_object(light_client);
+    }
+
+    /// Submit a batch of raw headers, in order.
+    public fun submit_headers(light_client: &mut BitcoinLightClient, raw_headers: vector<vector<u8>>) {
+        vector::reverse(&mut raw_headers);
+        while (!vector::is_empty(&raw_headers)) {
+            submit_header(light_client, vector::pop_back(&mut raw_headers));
+        };
+    }
+
+    /// Submit a single raw header extending any known header.
+    /// The main chain is switched to the new header if it now has the most accumulated work.
+    public fun submit_header(light_client: &mut BitcoinLightClient, raw_header: vector<u8>) {
+        let header = parse_header(&raw_header);
+        let block_hash = double_sha256(raw_header);
+        assert!(!table::contains(&light_client.headers, block_hash), EHeaderAlreadyKnown);
+        assert!(table::contains(&light_client.headers, header.prev_block_hash), EUnknownParent);
+
+        let prev = *table::borrow(&light_client.headers, header.prev_block_hash);
+        let height = prev.height + 1;
+
+        assert!(header.bits == next_work_required(light_client, &prev, &header, height), EInvalidDifficulty);
+        let target = verify_proof_of_work(&header, &block_hash, light_client.pow_limit);
+
+        let is_period_start = height % DIFFICULTY_ADJUSTMENT_INTERVAL == 0;
+        let period_start_timestamp = if (is_period_start) { header.timestamp } else { prev.period_start_timestamp };
+        let last_regular_bits = if (is_period_start || header.bits != target_to_bits(light_client.pow_limit)) {
+            header.bits
+        } else {
+            prev.last_regular_bits
+        };
+        let chainwork = prev.chainwork + work(target);
+
+        table::add(&mut light_client.headers, block_hash, HeaderEntry {
+            header,
+            height,
+            chainwork,
+            period_start_timestamp,
+            last_regular_bits,
+        });
+
+        let tip_chainwork = table::borrow(&light_client.headers, light_client.tip).chainwork;
+        let is_new_tip = chainwork > tip_chainwork;
+        if (is_new_tip) {
+            update_main_chain(light_client, block_hash, height);
+        };
+
+        event::emit(BitcoinHeaderSubmitted {
+            light_client_id: object::id(light_client),
+            block_hash,
+            height,
+            is_new_tip,
+        });
+    }
+
+    /// Number of confirmations of `block_hash`, or 0 if it is not part of the main chain.
+    public fun confirmations(light_client: &BitcoinLightClient, block_hash: vector<u8>): u64 {
+        if (!table::contains(&light_client.headers, block_hash)) {
+            return 0
+        };
+        let height = table::borrow(&light_client.headers, block_hash).height;
+        if (height > light_client.tip_height || *table::borrow(&light_client.main_chain, height) != block_hash) {
+            return 0
+        };
+        light_client.tip_height - height + 1
+    }
+
+    /// Abort unless the transaction `txid` is included at `tx_index` of the main chain block `block_hash`,
+    /// which has at least `min_confirmations` confirmations.
+    /// `merkle_proof` holds the sibling hashes from the transaction up to (but excluding) the merkle root.
+    public fun assert_transaction_confirmed(
+        light_client: &BitcoinLightClient,
+        block_hash: vector<u8>,
+        txid: vector<u8>,
+        merkle_proof: vector<vector<u8>>,
+        tx_index: u64,
+        min_confirmations: u64,
+    ) {
+        assert!(table::contains(&light_client.headers, block_hash), EUnknownBlock);
+        assert!(confirmations(light_client, block_hash) >= min_confirmations, EInsufficientConfirmations);
+        let merkle_root = table::borrow(&light_client.headers, block_hash).header.merkle_root;
+        assert!(verify_merkle_proof(merkle_root, txid, merkle_proof, tx_index), EInvalidMerkleProof);
+    }
+
+    /// Check that `txid` is the leaf at `tx_index` of the merkle tree with root `merkle_root`.
+    public fun verify_merkle_proof(
+        merkle_root: vector<u8>,
+        txid: vector<u8>,
+        merkle_proof: vector<vector<u8>>,
+        tx_index: u64,
+    ): bool {
+        if (vector::length(&txid) != HASH_LENGTH) {
+            return false
+        };
+        let current = txid;
+        let index = tx_index;
+        let i = 0;
+        while (i < vector::length(&merkle_proof)) {
+            let sibling = *vector::borrow(&merkle_proof, i);
+            if (vector::length(&sibling) != HASH_LENGTH) {
+                return false
+            };
+            if (index & 1 == 0) {
+                vector::append(&mut current, sibling);
+                current = double_sha256(current);
+            } else {
+                vector::append(&mut sibling, current);
+                current = double_sha256(sibling);
+            };
+            index = index >> 1;
+            i = i + 1;
+        };
+        index == 0 && current == merkle_root
+    }
+
+    /// Compute the txid of a raw (non-witness serialized) transaction.
+    /// 64-byte transactions are rejected, as they can be confused with inner merkle tree nodes.
+    public fun compute_txid(raw_transaction: vector<u8>): vector<u8> {
+        assert!(vector::length(&raw_transaction) != 64, EInvalidTransaction);
+        double_sha256(raw_transaction)
+    }
+
+    public fun tip(light_client: &BitcoinLightClient): vector<u8> {
+        light_client.tip
+    }
+
+    public fun tip_height(light_client: &BitcoinLightClient): u64 {
+        light_client.tip_height
+    }
+
+    public fun network(light_client: &BitcoinLightClient): u8 {
+        light_client.network
+    }
+
+    public fun contains_header(light_client: &BitcoinLightClient, block_hash: vector<u8>): bool {
+        table::contains(&light_client.headers, block_hash)
+    }
+
+    public fun header_height(light_client: &BitcoinLightClient, block_hash: vector<u8>): u64 {
+        assert!(table::contains(&light_client.headers, block_hash), EUnknownBlock);
+        table::borrow(&light_client.headers, block_hash).height
+    }
+
+    public fun main_chain_block_hash(light_client: &BitcoinLightClient, height: u64): vector<u8> {
+        assert!(height >= light_client.checkpoint_height && height <= light_client.tip_height, EUnknownBlock);
+        *table::borrow(&light_client.main_chain, height)
+    }
+
+    fun update_main_chain(light_client: &mut BitcoinLightClient, new_tip: vector<u8>, new_tip_height: u64) {
+        // Drop heights of the old main chain that are above the new tip.
+        let height = light_client.tip_height;
+        while (height > new_tip_height) {
+            table::remove(&mut light_client.main_chain, height);
+            height = height - 1;
+        };
+
+        // Walk back from the new tip until we reach a block that is already on the main chain.
+        // The checkpoint is always on the main chain, so this terminates.
+        let current = new_tip;
+        let height = new_tip_height;
+        loop {
+            if (table::contains(&light_client.main_chain, height)) {
+                let entry = table::borrow_mut(&mut light_client.main_chain, height);
+                if (*entry == current) {
+                    break
+                };
+                *entry = current;
+            } else {
+                table::add(&mut light_client.main_chain, height, current);
+            };
+            if (height == light_client.checkpoint_height) {
+                break
+            };
+            current = table::borrow(&light_client.headers, current).header.prev_block_hash;
+            height = height - 1;
+        };
+
+        light_client.tip = new_tip;
+        light_client.tip_height = new_tip_height;
+    }
+
+    fun next_work_required(
+        light_client: &BitcoinLightClient,
+        prev: &HeaderEntry,
+        header: &BlockHeader,
+        height: u64,
+    ): u32 {
+        if (height % DIFFICULTY_ADJUSTMENT_INTERVAL != 0) {
+            if (light_client.network == NETWORK_TESTNET) {
+                // Testnet allows a minimum difficulty block if no block was found for twice the target spacing.
+                if ((header.timestamp as u64) > (prev.header.timestamp as u64) + TARGET_SPACING * 2) {
+                    return target_to_bits(light_client.pow_limit)
+                };
+                return prev.last_regular_bits
+            };
+            return prev.header.bits
+        };
+        if (light_client.network == NETWORK_REGTEST) {
+            return prev.header.bits
+        };
+
+        let last_timestamp = (prev.header.timestamp as u64);
+        let first_timestamp = (prev.period_start_timestamp as u64);
+        let timespan = if (last_timestamp > first_timestamp) { last_timestamp - first_timestamp } else { 0 };
+        if (timespan < TARGET_TIMESPAN / 4) {
+            timespan = TARGET_TIMESPAN / 4;
+        };
+        if (timespan > TARGET_TIMESPAN * 4) {
+            timespan = TARGET_TIMESPAN * 4;
+        };
+
+        let new_target = bits_to_target(prev.header.bits) * (timespan as u256) / (TARGET_TIMESPAN as u256);
+        if (new_target > light_client.pow_limit) {
+            new_target = light_client.pow_limit;
+        };
+        target_to_bits(new_target)
+    }
+
+    fun verify_proof_of_work(header: &BlockHeader, block_hash: &vector<u8>, pow_limit: u256): u256 {
+        let target = bits_to_target(header.bits);
+        assert!(target != 0 && target <= pow_limit, EInvalidDifficulty);
+        assert!(hash_to_u256(block_hash) <= target, EInsufficientProofOfWork);
+        target
+    }
+
+    /// Expected number of hashes needed to find a block with the given target.
+    fun work(target: u256): u256 {
+        (MAX_U256 - target) / (target + 1) + 1
+    }
+
+    fun bits_to_target(bits: u32): u256 {
+        let exponent = ((bits >> 24) as u8);
+        let mantissa = bits & 0x007fffff;
+        // Negative and overflowing targets are invalid.
+        assert!(bits & 0x00800000 == 0 && exponent <= 32, EInvalidDifficulty);
+        if (exponent <= 3) {
+            ((mantissa >> (8 * (3 - exponent))) as u256)
+        } else {
+            (mantissa as u256) << (8 * (exponent - 3))
+        }
+    }
+
+    fun target_to_bits(target: u256): u32 {
+        let size = 0u8;
+        let remaining = target;
+        while (remaining > 0) {
+            remaining = remaining >> 8;
+            size = size + 1;
+        };
+        let mantissa = if (size <= 3) {
+            ((target << (8 * (3 - size))) as u32)
+        } else {
+            ((target >> (8 * (size - 3))) as u32)
+        };
+        // The mantissa is signed, so move a set sign bit into the exponent.
+        if (mantissa & 0x00800000 != 0) {
+            mantissa = mantissa >> 8;
+            size = size + 1;
+        };
+        mantissa | ((size as u32) << 24)
+    }
+
+    fun parse_header(raw_header: &vector<u8>): BlockHeader {
+        assert!(vector::length(raw_header) == HEADER_LENGTH, EInvalidHeaderLength);
+        BlockHeader {
+            version: read_u32_le(raw_header, 0),
+            prev_block_hash: slice(raw_header, 4, 36),
+            merkle_root: slice(raw_header, 36, 68),
+            timestamp: read_u32_le(raw_header, 68),
+            bits: read_u32_le(raw_header, 72),
+            nonce: read_u32_le(raw_header, 76),
+        }
+    }
+
+    fun double_sha256(data: vector<u8>): vector<u8> {
+        hash::sha2_256(hash::sha2_256(data))
+    }
+
+    /// Interpret a hash in internal byte order as a little-endian number.
+    fun hash_to_u256(hash: &vector<u8>): u256 {
+        let value = 0u256;
+        let i = vector::length(hash);
+        while (i > 0) {
+            i = i - 1;
+            value = (value << 8) | (*vector::borrow(hash, i) as u256);
+        };
+        value
+    }
+
+    fun read_u32_le(bytes: &vector<u8>, offset: u64): u32 {
+        (*vector::borrow(bytes, offset) as u32)
+            | ((*vector::borrow(bytes, offset + 1) as u32) << 8)
+            | ((*vector::borrow(bytes, offset + 2) as u32) << 16)
+            | ((*vector::borrow(bytes, offset + 3) as u32) << 24)
+    }
+
+    fun slice(bytes: &vector<u8>, start: u64, end: u64): vector<u8> {
+        let result = vector::empty<u8>();
+        while (start < end) {
+            vector::push_back(&mut result, *vector::borrow(bytes, start));
+            start = start + 1;
+        };
+        result
+    }
+
+    #[test_only]
+    public fun bits_to_target_for_testing(bits: u32): u256 {
+        bits_to_target(bits)
+    }
+
+    #[test_only]
+    public fun target_to_bits_for_testing(target: u256): u32 {
+        target_to_bits(target)
+    }
+}
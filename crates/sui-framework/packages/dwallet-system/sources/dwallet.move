@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
 module dwallet_system::dwallet {
+    use std::bcs;
     use std::vector;
+    use dwallet::event;
     use dwallet::object::{Self, UID, ID};
     use dwallet::transfer;
     use dwallet::tx_context;
@@ -12,6 +14,11 @@ module dwallet_system::dwallet {
 
     const ENotSystemAddress: u64 = 0;
     const EMesssageApprovalDWalletMismatch: u64 = 1;
+    const EEmptyAttestationSchema: u64 = 2;
+
+    /// The domain separator of attestations, which no chain transaction or message signed by a
+    /// dWallet for a chain starts with.
+    const ATTESTATION_DOMAIN: vector<u8> = b"dwallet-attestation:v1";
 
     struct DWalletCap has key, store {
         id: UID,
@@ -54,12 +61,36 @@ module dwallet_system::dwallet {
         sender: address,
     }
 
+    /// Emitted when the owner of a dWallet requests the signing of messages.
+    struct SignSessionCreated has copy, drop {
+        session_id: ID,
+        dwallet_id: ID,
+        dwallet_cap_id: ID,
+        messages: vector<vector<u8>>,
+        sender: address,
+    }
+
+    /// Emitted when the network signed the messages of a sign session.
+    struct SignOutputCreated has copy, drop {
+        session_id: ID,
+        sign_output_id: ID,
+        dwallet_id: ID,
+        dwallet_cap_id: ID,
+        signatures: vector<vector<u8>>,
+        sender: address,
+    }
+
     public(friend) fun create_dwallet_cap(ctx: &mut TxContext): DWalletCap {
         DWalletCap {
             id: object::new(ctx),
         }
     }
 
+    #[test_only]
+    public fun create_dwallet_cap_for_testing(ctx: &mut TxContext): DWalletCap {
+        create_dwallet_cap(ctx)
+    }
+
     public fun create_approvals_holder(message_approvals: vector<MessageApproval>, ctx: &mut TxContext) {
         let holder = ApprovalsHolder {
             id: object::new(ctx),
@@ -77,6 +108,15 @@ module dwallet_system::dwallet {
         message_approvals
     }
 
+    /// Cancel the approvals of `holder`, which can then no longer be used to sign its messages.
+    public fun cancel_approvals_holder(holder: ApprovalsHolder) {
+        let message_approvals = remove_approvals_holder(holder);
+        while (vector::length(&message_approvals) > 0) {
+            let (_, _) = remove(vector::pop_back(&mut message_approvals));
+        };
+        vector::destroy_empty(message_approvals);
+    }
+
     public fun approve_messages(dwallet_cap: &DWalletCap, messages: vector<vector<u8>>): vector<MessageApproval> {
         let dwallet_cap_id = object::id(dwallet_cap);
         let message_approvals = vector::empty<MessageApproval>();
@@ -90,6 +130,28 @@ module dwallet_system::dwallet {
         message_approvals
     }
 
+    /// Approve signing `payloads` as attestations of schema `schema`, e.g. `b"price-feed:v1"`.
+    /// Each payload is wrapped by `attestation_message` before approval, so that the signature of an
+    /// attestation can never be a valid signature of a chain transaction.
+    public fun approve_attestations(dwallet_cap: &DWalletCap, schema: vector<u8>, payloads: vector<vector<u8>>): vector<MessageApproval> {
+        let messages = vector::empty<vector<u8>>();
+        while (vector::length(&payloads) > 0) {
+            let payload = vector::remove(&mut payloads, 0);
+            vector::push_back(&mut messages, attestation_message(schema, payload));
+        };
+        approve_messages(dwallet_cap, messages)
+    }
+
+    /// The message signed for the attestation `payload` of schema `schema`:
+    /// `ATTESTATION_DOMAIN || bcs(schema) || payload`, with the schema prefixed by its length.
+    public fun attestation_message(schema: vector<u8>, payload: vector<u8>): vector<u8> {
+        assert!(!vector::is_empty(&schema), EEmptyAttestationSchema);
+        let message = ATTESTATION_DOMAIN;
+        vector::append(&mut message, bcs::to_bytes(&schema));
+        vector::append(&mut message, payload);
+        message
+    }
+
     public fun dwallet_cap_id(message_approval: &MessageApproval): ID {
         message_approval.dwallet_cap_id
     }
@@ -162,6 +224,13 @@ module dwallet_system::dwallet {
             sign_data,
             sender: tx_context::sender(ctx),
         };
+        event::emit(SignSessionCreated {
+            session_id: object::id(&sign_session),
+            dwallet_id,
+            dwallet_cap_id,
+            messages: sign_session.messages,
+            sender: sign_session.sender,
+        });
         transfer::freeze_object(sign_session);
     }
 
@@ -177,6 +246,14 @@ module dwallet_system::dwallet {
             signatures,
             sender: session.sender,
         };
+        event::emit(SignOutputCreated {
+            session_id: sign_output.session_id,
+            sign_output_id: object::id(&sign_output),
+            dwallet_id: sign_output.dwallet_id,
+            dwallet_cap_id: sign_output.dwallet_cap_id,
+            signatures: sign_output.signatures,
+            sender: sign_output.sender,
+        });
         transfer::transfer(sign_output, session.sender);
     }
 }
@@ -3,10 +3,11 @@
 
 #[allow(unused_const)]
 module dwallet_system::dwallet_2pc_mpc_ecdsa_k1 {
+    use dwallet::event;
     use dwallet::object::{Self, UID, ID};
     use dwallet::transfer;
     use dwallet::tx_context::{Self, TxContext};
-    use dwallet_system::dwallet::{create_dwallet_cap, SignMessages};
+    use dwallet_system::dwallet::{create_dwallet_cap, DWalletCap, SignMessages};
     use dwallet_system::dwallet;
 
     const ENotSystemAddress: u64 = 0;
@@ -14,6 +15,7 @@ module dwallet_system::dwallet_2pc_mpc_ecdsa_k1 {
     const EPresignOutputAndPresignMismatch: u64 = 2;
     const ESignInvalidSignatureParts: u64 = 3;
     const ENotSupported: u64 = 4;
+    const EDWalletCapMismatch: u64 = 5;
 
     const KECCAK256: u8 = 0;
     const SHA256: u8 = 1;
@@ -95,6 +97,47 @@ module dwallet_system::dwallet_2pc_mpc_ecdsa_k1 {
     //     sender: address,
     // }
 
+    /// Emitted when a dWallet is created.
+    struct DWalletCreated has copy, drop {
+        dwallet_id: ID,
+        dwallet_cap_id: ID,
+        sender: address,
+    }
+
+    /// Emitted when the owner of a dWallet starts a presign session.
+    struct PresignSessionCreated has copy, drop {
+        session_id: ID,
+        dwallet_id: ID,
+        dwallet_cap_id: ID,
+        sender: address,
+    }
+
+    /// Emitted when the network completed the presign of a presign session.
+    struct PresignCreated has copy, drop {
+        session_id: ID,
+        presign_id: ID,
+        dwallet_id: ID,
+        dwallet_cap_id: ID,
+        sender: address,
+    }
+
+    /// The public key of a secp256k1 key published by its owner, for the user shares of the
+    /// dWallets transferred to them to be encrypted to.
+    struct EncryptionKey has key {
+        id: UID,
+        encryption_key: vector<u8>,
+    }
+
+    /// The user share of a dWallet, encrypted to the `EncryptionKey` of the recipient of the
+    /// dWallet, and owned by them.
+    struct EncryptedUserShare has key {
+        id: UID,
+        dwallet_id: ID,
+        encryption_key_id: ID,
+        encrypted_user_share: vector<u8>,
+        sender: address,
+    }
+
     public fun create_dkg_session(commitment_to_centralized_party_secret_key_share: vector<u8>, ctx: &mut TxContext) {
         let cap = create_dwallet_cap(ctx);
         let session = DKGSession {
@@ -140,9 +183,40 @@ module dwallet_system::dwallet_2pc_mpc_ecdsa_k1 {
             public_key,
             encrypted_secret_key_share,
         };
+        event::emit(DWalletCreated {
+            dwallet_id: object::id(&result),
+            dwallet_cap_id,
+            sender: tx_context::sender(ctx),
+        });
         transfer::freeze_object(result);
     }
 
+    public fun dwallet_cap_id(dwallet: &DWallet): ID {
+        dwallet.dwallet_cap_id
+    }
+
+    /// The BCS encoded public key of `dwallet`, its compressed SEC1 encoding prefixed by its length.
+    public fun public_key(dwallet: &DWallet): vector<u8> {
+        dwallet.public_key
+    }
+
+    #[test_only]
+    public fun create_dwallet_for_testing(dwallet_cap_id: ID, ctx: &mut TxContext): DWallet {
+        create_dwallet_with_public_key_for_testing(dwallet_cap_id, vector[], ctx)
+    }
+
+    #[test_only]
+    public fun create_dwallet_with_public_key_for_testing(dwallet_cap_id: ID, public_key: vector<u8>, ctx: &mut TxContext): DWallet {
+        DWallet {
+            id: object::new(ctx),
+            session_id: object::id_from_address(@0x0),
+            dwallet_cap_id,
+            output: vector[],
+            public_key,
+            encrypted_secret_key_share: vector[],
+        }
+    }
+
     native fun dkg_verify_decommitment_and_proof_of_centralized_party_public_key_share(commitment_to_centralized_party_secret_key_share: vector<u8>, secret_key_share_encryption_and_proof: vector<u8>, centralized_party_public_key_share_decommitment_and_proofs: vector<u8>): (vector<u8>, vector<u8>, vector<u8>);
 
     public fun create_presign_session(dwallet: &DWallet, messages: vector<vector<u8>>, commitments_and_proof_to_centralized_party_nonce_shares: vector<u8>, hash: u8, ctx: &mut TxContext) {
@@ -160,6 +234,12 @@ module dwallet_system::dwallet_2pc_mpc_ecdsa_k1 {
             messages,
             sender: tx_context::sender(ctx),
         };
+        event::emit(PresignSessionCreated {
+            session_id: object::id(&session),
+            dwallet_id,
+            dwallet_cap_id,
+            sender: session.sender,
+        });
         transfer::freeze_object(session);
     }
 
@@ -186,6 +266,13 @@ module dwallet_system::dwallet_2pc_mpc_ecdsa_k1 {
             dwallet_cap_id: session.dwallet_cap_id,
             presigns,
         };
+        event::emit(PresignCreated {
+            session_id: presign.session_id,
+            presign_id: object::id(&presign),
+            dwallet_id: presign.dwallet_id,
+            dwallet_cap_id: presign.dwallet_cap_id,
+            sender: session.sender,
+        });
         transfer::transfer(presign, session.sender);
     }
 
@@ -226,4 +313,28 @@ module dwallet_system::dwallet_2pc_mpc_ecdsa_k1 {
 
         dwallet::create_sign_messages(dwallet_id, dwallet_cap_id, session.messages, sign_data, ctx)
     }
+
+    /// Publish `encryption_key`, owned by the sender, for the senders of dWallets to encrypt their
+    /// user shares to.
+    public fun publish_encryption_key(encryption_key: vector<u8>, ctx: &mut TxContext) {
+        let key = EncryptionKey {
+            id: object::new(ctx),
+            encryption_key,
+        };
+        transfer::transfer(key, tx_context::sender(ctx));
+    }
+
+    /// Send the user share of `dwallet`, encrypted to the `EncryptionKey` `encryption_key_id` of
+    /// `recipient`, to `recipient`, along with whom `dwallet_cap` is transferred.
+    public fun send_encrypted_user_share(dwallet: &DWallet, dwallet_cap: &DWalletCap, encryption_key_id: ID, encrypted_user_share: vector<u8>, recipient: address, ctx: &mut TxContext) {
+        assert!(dwallet.dwallet_cap_id == object::id(dwallet_cap), EDWalletCapMismatch);
+        let share = EncryptedUserShare {
+            id: object::new(ctx),
+            dwallet_id: object::id(dwallet),
+            encryption_key_id,
+            encrypted_user_share,
+            sender: tx_context::sender(ctx),
+        };
+        transfer::transfer(share, recipient);
+    }
 }
@@ -0,0 +1,140 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+/// Bindings of dWallets to their addresses on external chains.
+///
+/// A `DWalletBinding` is an immutable object linking a dWallet to the address derived from its
+/// public key on an external chain, e.g. its Ethereum address, for contracts to discover which
+/// dWallet controls an address, or the address of a dWallet. Only the holder of the `DWalletCap` of
+/// the dWallet creates bindings of it.
+///
+/// Only Ethereum addresses are derived on chain from the public key of the dWallet, and checked
+/// against the bound address: the addresses on other chains are claims of the holder of the
+/// capability, which contracts relying on them trust to be derived correctly, see `is_verified`.
+module dwallet_system::dwallet_binding {
+    use std::vector;
+    use dwallet::bcs;
+    use dwallet::ecdsa_k1;
+    use dwallet::event;
+    use dwallet::hash;
+    use dwallet::hex;
+    use dwallet::object::{Self, ID, UID};
+    use dwallet::transfer;
+    use dwallet::tx_context::{Self, TxContext};
+    use dwallet_system::dwallet::DWalletCap;
+    use dwallet_system::dwallet_2pc_mpc_ecdsa_k1::{Self, DWallet};
+
+    const EDWalletCapMismatch: u64 = 0;
+    const EEmptyChain: u64 = 1;
+    const EEmptyAddress: u64 = 2;
+    const EAddressMismatch: u64 = 3;
+
+    /// The chain whose addresses are derived on chain.
+    const ETHEREUM: vector<u8> = b"ethereum";
+
+    struct DWalletBinding has key {
+        id: UID,
+        dwallet_id: ID,
+        dwallet_cap_id: ID,
+        /// The name of the address, e.g. `ethereum` or `bitcoin-mainnet`.
+        chain: vector<u8>,
+        /// The address of the dWallet on `chain`, as displayed on that chain.
+        external_address: vector<u8>,
+    }
+
+    struct DWalletBindingCreated has copy, drop {
+        binding_id: ID,
+        dwallet_id: ID,
+        chain: vector<u8>,
+        external_address: vector<u8>,
+        sender: address,
+    }
+
+    /// Bind `dwallet`, of `dwallet_cap`, to its address `external_address` on `chain`, with a new
+    /// immutable `DWalletBinding`. An `ethereum` address must be the one of the public key of
+    /// `dwallet`, in any case.
+    public fun create_binding(
+        dwallet: &DWallet,
+        dwallet_cap: &DWalletCap,
+        chain: vector<u8>,
+        external_address: vector<u8>,
+        ctx: &mut TxContext,
+    ) {
+        let dwallet_cap_id = object::id(dwallet_cap);
+        assert!(dwallet_2pc_mpc_ecdsa_k1::dwallet_cap_id(dwallet) == dwallet_cap_id, EDWalletCapMismatch);
+        assert!(!vector::is_empty(&chain), EEmptyChain);
+        assert!(!vector::is_empty(&external_address), EEmptyAddress);
+        if (chain == ETHEREUM) {
+            assert!(to_lowercase(external_address) == ethereum_address(dwallet), EAddressMismatch);
+        };
+
+        let binding = DWalletBinding {
+            id: object::new(ctx),
+            dwallet_id: object::id(dwallet),
+            dwallet_cap_id,
+            chain,
+            external_address,
+        };
+        event::emit(DWalletBindingCreated {
+            binding_id: object::id(&binding),
+            dwallet_id: binding.dwallet_id,
+            chain: binding.chain,
+            external_address: binding.external_address,
+            sender: tx_context::sender(ctx),
+        });
+        transfer::freeze_object(binding);
+    }
+
+    public fun dwallet_id(binding: &DWalletBinding): ID {
+        binding.dwallet_id
+    }
+
+    public fun chain(binding: &DWalletBinding): vector<u8> {
+        binding.chain
+    }
+
+    public fun external_address(binding: &DWalletBinding): vector<u8> {
+        binding.external_address
+    }
+
+    /// Whether the address of `binding` was derived on chain from the public key of its dWallet,
+    /// rather than claimed by the holder of its capability.
+    public fun is_verified(binding: &DWalletBinding): bool {
+        binding.chain == ETHEREUM
+    }
+
+    /// The lowercase Ethereum address of `dwallet`, `0x` and the hex of the last 20 bytes of the
+    /// Keccak-256 hash of its uncompressed public key, without its `0x04` prefix.
+    fun ethereum_address(dwallet: &DWallet): vector<u8> {
+        let public_key = bcs::peel_vec_u8(&mut bcs::new(dwallet_2pc_mpc_ecdsa_k1::public_key(dwallet)));
+        let uncompressed = ecdsa_k1::decompress_pubkey(&public_key);
+        let point = vector[];
+        let i = 1;
+        while (i < vector::length(&uncompressed)) {
+            vector::push_back(&mut point, *vector::borrow(&uncompressed, i));
+            i = i + 1;
+        };
+        let digest = hash::keccak256(&point);
+        let bytes = vector[];
+        let i = 12;
+        while (i < vector::length(&digest)) {
+            vector::push_back(&mut bytes, *vector::borrow(&digest, i));
+            i = i + 1;
+        };
+        let result = b"0x";
+        vector::append(&mut result, hex::encode(bytes));
+        result
+    }
+
+    fun to_lowercase(bytes: vector<u8>): vector<u8> {
+        let i = 0;
+        while (i < vector::length(&bytes)) {
+            let byte = vector::borrow_mut(&mut bytes, i);
+            if (*byte >= 65 && *byte <= 90) {
+                *byte = *byte + 32;
+            };
+            i = i + 1;
+        };
+        bytes
+    }
+}
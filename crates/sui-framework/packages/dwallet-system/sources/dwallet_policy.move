@@ -0,0 +1,318 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+/// Signing policies of dWallets.
+///
+/// A `SigningPolicy` takes custody of the `DWalletCap` of a dWallet, so that the messages of the
+/// dWallet are only approved within the rules of the policy, for as long as it holds the
+/// capability:
+/// - an allowlist of the addresses that may have messages approved, besides the owner of the
+///   policy,
+/// - a rate limit on the messages approved per time window,
+/// - a number of co-approvers that must co-approve each request before its messages are approved.
+///
+/// The policy is a shared object, which only its owner updates, or removes to get the capability
+/// back.
+module dwallet_system::dwallet_policy {
+    use std::vector;
+    use dwallet::clock::{Self, Clock};
+    use dwallet::event;
+    use dwallet::object::{Self, ID, UID};
+    use dwallet::transfer;
+    use dwallet::tx_context::{Self, TxContext};
+    use dwallet_system::dwallet::{Self, DWalletCap, MessageApproval};
+
+    const ENotOwner: u64 = 0;
+    const ENotAllowed: u64 = 1;
+    const ERateLimited: u64 = 2;
+    const EInvalidRateLimit: u64 = 3;
+    const EInvalidCoApprovalThreshold: u64 = 4;
+    const ECoApprovalRequired: u64 = 5;
+    const ENotCoApprover: u64 = 6;
+    const EAlreadyCoApproved: u64 = 7;
+    const EUnknownRequest: u64 = 8;
+    const ENotRequester: u64 = 9;
+    const ENotEnoughCoApprovals: u64 = 10;
+    const ETooManyRequests: u64 = 11;
+
+    /// The most requests a policy holds waiting for co-approvals.
+    const MAX_PENDING_REQUESTS: u64 = 32;
+
+    struct SigningPolicy has key {
+        id: UID,
+        owner: address,
+        dwallet_cap: DWalletCap,
+        /// The addresses that may have messages approved, besides the owner.
+        allowlist: vector<address>,
+        /// The messages approved per window of `window_ms` milliseconds at most, `0` for no limit.
+        max_messages: u64,
+        window_ms: u64,
+        window_start_ms: u64,
+        window_messages: u64,
+        /// The addresses that co-approve the requests, `co_approval_threshold` of which must
+        /// co-approve a request before its messages are approved. `0` for no co-approval.
+        co_approvers: vector<address>,
+        co_approval_threshold: u64,
+        /// The pending requests, at most `MAX_PENDING_REQUESTS`, each removed once approved or
+        /// cancelled.
+        requests: vector<ApprovalRequest>,
+        next_request_id: u64,
+    }
+
+    /// A request of messages waiting for the co-approvals of a policy.
+    struct ApprovalRequest has store, drop {
+        id: u64,
+        requester: address,
+        messages: vector<vector<u8>>,
+        co_approvals: vector<address>,
+    }
+
+    struct SigningPolicySet has copy, drop {
+        policy_id: ID,
+        dwallet_cap_id: ID,
+        owner: address,
+    }
+
+    struct SigningPolicyRemoved has copy, drop {
+        policy_id: ID,
+        dwallet_cap_id: ID,
+    }
+
+    struct ApprovalRequested has copy, drop {
+        policy_id: ID,
+        request_id: u64,
+        requester: address,
+    }
+
+    /// Put the messages approvals of `dwallet_cap` under a new shared `SigningPolicy`, owned by
+    /// the sender.
+    public fun create_policy(
+        dwallet_cap: DWalletCap,
+        allowlist: vector<address>,
+        max_messages: u64,
+        window_ms: u64,
+        co_approvers: vector<address>,
+        co_approval_threshold: u64,
+        ctx: &mut TxContext,
+    ) {
+        check_rules(max_messages, window_ms, &co_approvers, co_approval_threshold);
+        let policy = SigningPolicy {
+            id: object::new(ctx),
+            owner: tx_context::sender(ctx),
+            dwallet_cap,
+            allowlist,
+            max_messages,
+            window_ms,
+            window_start_ms: 0,
+            window_messages: 0,
+            co_approvers,
+            co_approval_threshold,
+            requests: vector::empty(),
+            next_request_id: 0,
+        };
+        emit_policy_set(&policy);
+        transfer::share_object(policy);
+    }
+
+    /// Replace the rules of `policy`, dropping its pending requests, which were co-approved under
+    /// the previous rules.
+    public fun update_policy(
+        policy: &mut SigningPolicy,
+        allowlist: vector<address>,
+        max_messages: u64,
+        window_ms: u64,
+        co_approvers: vector<address>,
+        co_approval_threshold: u64,
+        ctx: &mut TxContext,
+    ) {
+        assert!(policy.owner == tx_context::sender(ctx), ENotOwner);
+        check_rules(max_messages, window_ms, &co_approvers, co_approval_threshold);
+        policy.allowlist = allowlist;
+        policy.max_messages = max_messages;
+        policy.window_ms = window_ms;
+        policy.window_start_ms = 0;
+        policy.window_messages = 0;
+        policy.co_approvers = co_approvers;
+        policy.co_approval_threshold = co_approval_threshold;
+        policy.requests = vector::empty();
+        emit_policy_set(policy);
+    }
+
+    /// Remove `policy`, giving back the capability it holds.
+    public fun remove_policy(policy: SigningPolicy, ctx: &mut TxContext): DWalletCap {
+        assert!(policy.owner == tx_context::sender(ctx), ENotOwner);
+        let SigningPolicy {
+            id,
+            owner: _,
+            dwallet_cap,
+            allowlist: _,
+            max_messages: _,
+            window_ms: _,
+            window_start_ms: _,
+            window_messages: _,
+            co_approvers: _,
+            co_approval_threshold: _,
+            requests: _,
+            next_request_id: _,
+        } = policy;
+        event::emit(SigningPolicyRemoved {
+            policy_id: object::uid_to_inner(&id),
+            dwallet_cap_id: object::id(&dwallet_cap),
+        });
+        object::delete(id);
+        dwallet_cap
+    }
+
+    /// Approve `messages` under `policy`, which must not require co-approvals.
+    public fun approve_messages(
+        policy: &mut SigningPolicy,
+        messages: vector<vector<u8>>,
+        clock: &Clock,
+        ctx: &mut TxContext,
+    ): vector<MessageApproval> {
+        assert!(policy.co_approval_threshold == 0, ECoApprovalRequired);
+        assert!(is_allowed(policy, tx_context::sender(ctx)), ENotAllowed);
+        consume_rate_limit(policy, vector::length(&messages), clock);
+        dwallet::approve_messages(&policy.dwallet_cap, messages)
+    }
+
+    /// Request the approval of `messages` under `policy`, to be co-approved with `co_approve`.
+    /// Returns the ID of the request.
+    public fun request_approval(
+        policy: &mut SigningPolicy,
+        messages: vector<vector<u8>>,
+        ctx: &mut TxContext,
+    ): u64 {
+        let requester = tx_context::sender(ctx);
+        assert!(is_allowed(policy, requester), ENotAllowed);
+        assert!(vector::length(&policy.requests) < MAX_PENDING_REQUESTS, ETooManyRequests);
+        let request_id = policy.next_request_id;
+        policy.next_request_id = request_id + 1;
+        vector::push_back(&mut policy.requests, ApprovalRequest {
+            id: request_id,
+            requester,
+            messages,
+            co_approvals: vector::empty(),
+        });
+        event::emit(ApprovalRequested {
+            policy_id: object::id(policy),
+            request_id,
+            requester,
+        });
+        request_id
+    }
+
+    /// Co-approve the request `request_id` of `policy` as the sender, one of its co-approvers.
+    public fun co_approve(policy: &mut SigningPolicy, request_id: u64, ctx: &mut TxContext) {
+        let co_approver = tx_context::sender(ctx);
+        assert!(vector::contains(&policy.co_approvers, &co_approver), ENotCoApprover);
+        let request = vector::borrow_mut(&mut policy.requests, request_index(policy, request_id));
+        assert!(!vector::contains(&request.co_approvals, &co_approver), EAlreadyCoApproved);
+        vector::push_back(&mut request.co_approvals, co_approver);
+    }
+
+    /// Approve the messages of the request `request_id` of `policy`, made by the sender, once
+    /// enough co-approvers have co-approved it.
+    public fun approve_requested(
+        policy: &mut SigningPolicy,
+        request_id: u64,
+        clock: &Clock,
+        ctx: &mut TxContext,
+    ): vector<MessageApproval> {
+        let index = request_index(policy, request_id);
+        let ApprovalRequest {
+            id: _,
+            requester,
+            messages,
+            co_approvals,
+        } = vector::remove(&mut policy.requests, index);
+        assert!(requester == tx_context::sender(ctx), ENotRequester);
+        assert!(
+            vector::length(&co_approvals) >= policy.co_approval_threshold,
+            ENotEnoughCoApprovals
+        );
+        consume_rate_limit(policy, vector::length(&messages), clock);
+        dwallet::approve_messages(&policy.dwallet_cap, messages)
+    }
+
+    /// Cancel the request `request_id` of `policy`, as its requester or the owner of the policy.
+    public fun cancel_request(policy: &mut SigningPolicy, request_id: u64, ctx: &mut TxContext) {
+        let index = request_index(policy, request_id);
+        let sender = tx_context::sender(ctx);
+        let request = vector::remove(&mut policy.requests, index);
+        assert!(request.requester == sender || policy.owner == sender, ENotRequester);
+    }
+
+    public fun owner(policy: &SigningPolicy): address {
+        policy.owner
+    }
+
+    public fun dwallet_cap_id(policy: &SigningPolicy): ID {
+        object::id(&policy.dwallet_cap)
+    }
+
+    public fun allowlist(policy: &SigningPolicy): vector<address> {
+        policy.allowlist
+    }
+
+    public fun rate_limit(policy: &SigningPolicy): (u64, u64) {
+        (policy.max_messages, policy.window_ms)
+    }
+
+    public fun co_approvers(policy: &SigningPolicy): vector<address> {
+        policy.co_approvers
+    }
+
+    public fun co_approval_threshold(policy: &SigningPolicy): u64 {
+        policy.co_approval_threshold
+    }
+
+    /// Whether `addr` may have messages approved under `policy`.
+    public fun is_allowed(policy: &SigningPolicy, addr: address): bool {
+        addr == policy.owner || vector::contains(&policy.allowlist, &addr)
+    }
+
+    fun check_rules(
+        max_messages: u64,
+        window_ms: u64,
+        co_approvers: &vector<address>,
+        co_approval_threshold: u64,
+    ) {
+        assert!(max_messages == 0 || window_ms > 0, EInvalidRateLimit);
+        assert!(co_approval_threshold <= vector::length(co_approvers), EInvalidCoApprovalThreshold);
+    }
+
+    /// Count `messages` against the rate limit of `policy`, starting a new window once the
+    /// current one has elapsed.
+    fun consume_rate_limit(policy: &mut SigningPolicy, messages: u64, clock: &Clock) {
+        if (policy.max_messages == 0) {
+            return
+        };
+        let now = clock::timestamp_ms(clock);
+        if (now >= policy.window_start_ms + policy.window_ms) {
+            policy.window_start_ms = now;
+            policy.window_messages = 0;
+        };
+        assert!(policy.window_messages + messages <= policy.max_messages, ERateLimited);
+        policy.window_messages = policy.window_messages + messages;
+    }
+
+    fun request_index(policy: &SigningPolicy, request_id: u64): u64 {
+        let i = 0;
+        while (i < vector::length(&policy.requests)) {
+            if (vector::borrow(&policy.requests, i).id == request_id) {
+                return i
+            };
+            i = i + 1;
+        };
+        abort EUnknownRequest
+    }
+
+    fun emit_policy_set(policy: &SigningPolicy) {
+        event::emit(SigningPolicySet {
+            policy_id: object::id(policy),
+            dwallet_cap_id: object::id(&policy.dwallet_cap),
+            owner: policy.owner,
+        });
+    }
+}
@@ -0,0 +1,245 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+/// Ethereum light client.
+///
+/// Tracks the finalized execution headers of an Ethereum network as a shared object, starting from
+/// the bootstrap of a trusted beacon block. Light client updates are verified natively against the
+/// sync committees of the light client store, following the Altair light client sync protocol,
+/// and are only applied once finalized by a supermajority of the sync committee.
+/// The execution headers are kept so that dWallet logic can condition on Ethereum state by
+/// verifying proofs against their state and receipts roots, such as the receipt and log proofs
+/// gating signing on Ethereum transactions and events.
+///
+/// Forks, bootstraps, updates and the store are BCS encoded, see `sui_types::eth_light_client`.
+/// Updates are submitted by relayers, such as the `eth-state-updater` of `sui-light-client`.
+///
+/// Anyone can create a light client, from any genesis validators root and trusted block root:
+/// nothing ties a light client to the Ethereum network it claims to follow but the trust in its
+/// creator. Consumers must therefore pin the ID of the light client they trust, for instance in
+/// their dWallet policy, rather than accept any `EthereumLightClient` with the expected chain ID.
+#[allow(unused_const)]
+module dwallet_system::ethereum_light_client {
+    use dwallet::object::{Self, ID, UID};
+    use dwallet::table::{Self, Table};
+    use dwallet::tx_context::TxContext;
+    use dwallet::transfer;
+    use dwallet::event;
+
+    // Aborts of the natives.
+    const EInvalidInput: u64 = 0;
+    const EInvalidBootstrap: u64 = 1;
+    const EInvalidUpdate: u64 = 2;
+
+    const EUnknownBlock: u64 = 3;
+    const EInvalidReceiptProof: u64 = 4;
+    const EInvalidLogProof: u64 = 5;
+
+    /// What the light client retains of a finalized execution header.
+    struct ExecutionHeader has store, copy, drop {
+        block_hash: vector<u8>,
+        state_root: vector<u8>,
+        receipts_root: vector<u8>,
+        timestamp: u64,
+    }
+
+    struct EthereumLightClient has key {
+        id: UID,
+        chain_id: u64,
+        /// The BCS encoded `LightClientStore`.
+        store: vector<u8>,
+        finalized_slot: u64,
+        latest_block_number: u64,
+        /// The finalized execution headers by block number.
+        execution_headers: Table<u64, ExecutionHeader>,
+    }
+
+    struct EthereumHeaderSubmitted has copy, drop {
+        light_client_id: ID,
+        slot: u64,
+        block_number: u64,
+        block_hash: vector<u8>,
+    }
+
+    native fun ethereum_bootstrap(
+        genesis_validators_root: vector<u8>,
+        forks: vector<u8>,
+        trusted_block_root: vector<u8>,
+        bootstrap: vector<u8>,
+    ): (vector<u8>, u64, u64, vector<u8>, vector<u8>, vector<u8>, u64);
+
+    native fun ethereum_apply_update(
+        store: vector<u8>,
+        update: vector<u8>,
+    ): (vector<u8>, u64, u64, vector<u8>, vector<u8>, vector<u8>, u64);
+
+    native fun ethereum_verify_receipt(
+        receipts_root: vector<u8>,
+        transaction_index: u64,
+        proof: vector<vector<u8>>,
+    ): (bool, bool);
+
+    native fun ethereum_verify_log(
+        receipts_root: vector<u8>,
+        transaction_index: u64,
+        proof: vector<vector<u8>>,
+        log_index: u64,
+        address: vector<u8>,
+        topics: vector<vector<u8>>,
+        data: vector<u8>,
+    ): bool;
+
+    /// Create and share a light client for the network `chain_id`, with genesis validators root
+    /// `genesis_validators_root` and fork schedule `forks`, from the bootstrap of the trusted
+    /// beacon block `trusted_block_root`.
+    ///
+    /// This is permissionless and the parameters are not checked against the network `chain_id`,
+    /// see the module documentation: the light client is only as trustworthy as its creator.
+    public fun create_light_client(
+        chain_id: u64,
+        genesis_validators_root: vector<u8>,
+        forks: vector<u8>,
+        trusted_block_root: vector<u8>,
+        bootstrap: vector<u8>,
+        ctx: &mut TxContext,
+    ) {
+        let (store, slot, block_number, block_hash, state_root, receipts_root, timestamp) =
+            ethereum_bootstrap(genesis_validators_root, forks, trusted_block_root, bootstrap);
+        let execution_headers = table::new(ctx);
+        table::add(&mut execution_headers, block_number, ExecutionHeader {
+            block_hash,
+            state_root,
+            receipts_root,
+            timestamp,
+        });
+        transfer::share_object(EthereumLightClient {
+            id: object::new(ctx),
+            chain_id,
+            store,
+            finalized_slot: slot,
+            latest_block_number: block_number,
+            execution_headers,
+        });
+    }
+
+    /// Verify and apply `update`, a BCS encoded `EthUpdate`, storing its finalized execution
+    /// header if it advances the light client.
+    public fun submit_update(light_client: &mut EthereumLightClient, update: vector<u8>) {
+        let (store, slot, block_number, block_hash, state_root, receipts_root, timestamp) =
+            ethereum_apply_update(light_client.store, update);
+        light_client.store = store;
+        // An update can only teach the next sync committee without advancing the finalized header.
+        if (slot == light_client.finalized_slot) {
+            return
+        };
+
+        light_client.finalized_slot = slot;
+        light_client.latest_block_number = block_number;
+        table::add(&mut light_client.execution_headers, block_number, ExecutionHeader {
+            block_hash,
+            state_root,
+            receipts_root,
+            timestamp,
+        });
+        event::emit(EthereumHeaderSubmitted {
+            light_client_id: object::id(light_client),
+            slot,
+            block_number,
+            block_hash,
+        });
+    }
+
+    /// Whether the transaction at `transaction_index` of block `block_number` succeeded.
+    /// `proof` is the RLP encoded nodes of the receipts trie of the block on the path to the
+    /// receipt of the transaction, see `sui_types::eth_proofs`.
+    public fun verify_receipt(
+        light_client: &EthereumLightClient,
+        block_number: u64,
+        transaction_index: u64,
+        proof: vector<vector<u8>>,
+    ): bool {
+        let (valid, status) = ethereum_verify_receipt(
+            receipts_root(light_client, block_number),
+            transaction_index,
+            proof,
+        );
+        assert!(valid, EInvalidReceiptProof);
+        status
+    }
+
+    /// Whether the log at `log_index` of the receipt of the transaction at `transaction_index` of
+    /// block `block_number` was emitted by `address`, with `topics` and `data`.
+    public fun verify_log(
+        light_client: &EthereumLightClient,
+        block_number: u64,
+        transaction_index: u64,
+        proof: vector<vector<u8>>,
+        log_index: u64,
+        address: vector<u8>,
+        topics: vector<vector<u8>>,
+        data: vector<u8>,
+    ): bool {
+        ethereum_verify_log(
+            receipts_root(light_client, block_number),
+            transaction_index,
+            proof,
+            log_index,
+            address,
+            topics,
+            data,
+        )
+    }
+
+    public fun assert_log(
+        light_client: &EthereumLightClient,
+        block_number: u64,
+        transaction_index: u64,
+        proof: vector<vector<u8>>,
+        log_index: u64,
+        address: vector<u8>,
+        topics: vector<vector<u8>>,
+        data: vector<u8>,
+    ) {
+        assert!(
+            verify_log(light_client, block_number, transaction_index, proof, log_index, address, topics, data),
+            EInvalidLogProof
+        );
+    }
+
+    public fun chain_id(light_client: &EthereumLightClient): u64 {
+        light_client.chain_id
+    }
+
+    public fun finalized_slot(light_client: &EthereumLightClient): u64 {
+        light_client.finalized_slot
+    }
+
+    public fun latest_block_number(light_client: &EthereumLightClient): u64 {
+        light_client.latest_block_number
+    }
+
+    public fun contains_block(light_client: &EthereumLightClient, block_number: u64): bool {
+        table::contains(&light_client.execution_headers, block_number)
+    }
+
+    public fun block_hash(light_client: &EthereumLightClient, block_number: u64): vector<u8> {
+        execution_header(light_client, block_number).block_hash
+    }
+
+    public fun state_root(light_client: &EthereumLightClient, block_number: u64): vector<u8> {
+        execution_header(light_client, block_number).state_root
+    }
+
+    public fun receipts_root(light_client: &EthereumLightClient, block_number: u64): vector<u8> {
+        execution_header(light_client, block_number).receipts_root
+    }
+
+    public fun timestamp(light_client: &EthereumLightClient, block_number: u64): u64 {
+        execution_header(light_client, block_number).timestamp
+    }
+
+    fun execution_header(light_client: &EthereumLightClient, block_number: u64): &ExecutionHeader {
+        assert!(table::contains(&light_client.execution_headers, block_number), EUnknownBlock);
+        table::borrow(&light_client.execution_headers, block_number)
+    }
+}
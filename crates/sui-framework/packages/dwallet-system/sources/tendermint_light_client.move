@@ -0,0 +1,172 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+/// Tendermint light client.
+///
+/// Tracks the headers of a Tendermint (Cosmos) chain as a shared object, starting from a trusted
+/// consensus state. Light blocks are verified natively against the validator set trusted at an
+/// already known height, following the Tendermint light client skipping verification rules,
+/// and only while that height is within the trusting period.
+/// dWallet logic can then condition on Cosmos chain state by verifying ICS-23 (IAVL) membership
+/// proofs against the app hash of a verified header.
+///
+/// Light blocks, validator sets and proofs are BCS encoded, see `sui_types::tendermint`.
+#[allow(unused_field)]
+module dwallet_system::tendermint_light_client {
+    use dwallet::clock::{Self, Clock};
+    use dwallet::object::{Self, ID, UID};
+    use dwallet::table::{Self, Table};
+    use dwallet::tx_context::TxContext;
+    use dwallet::transfer;
+    use dwallet::event;
+
+    const EUnknownHeight: u64 = 0;
+    const ETrustingPeriodExpired: u64 = 1;
+    const EInvalidTrustedValidators: u64 = 2;
+    const EInvalidTimestamp: u64 = 3;
+    const EHeightAlreadyKnown: u64 = 4;
+    const EInvalidMembershipProof: u64 = 5;
+
+    /// What the light client retains of a verified header.
+    struct ConsensusState has store, copy, drop {
+        timestamp_ms: u64,
+        app_hash: vector<u8>,
+        next_validators_hash: vector<u8>,
+    }
+
+    struct TendermintLightClient has key {
+        id: UID,
+        chain_id: vector<u8>,
+        trusting_period_ms: u64,
+        latest_height: u64,
+        consensus_states: Table<u64, ConsensusState>,
+    }
+
+    struct TendermintHeaderSubmitted has copy, drop {
+        light_client_id: ID,
+        height: u64,
+        app_hash: vector<u8>,
+    }
+
+    native fun tendermint_verify_light_block(
+        chain_id: vector<u8>,
+        trusted_height: u64,
+        trusted_validators: vector<u8>,
+        light_block: vector<u8>,
+    ): (u64, u64, vector<u8>, vector<u8>, vector<u8>);
+
+    native fun tendermint_verify_membership(
+        app_hash: vector<u8>,
+        proofs: vector<u8>,
+        path: vector<vector<u8>>,
+        value: vector<u8>,
+    ): bool;
+
+    /// Create and share a light client for `chain_id`, starting from the trusted header at
+    /// `trusted_height`, of which the timestamp, app hash and next validators hash are given.
+    public fun create_light_client(
+        chain_id: vector<u8>,
+        trusting_period_ms: u64,
+        trusted_height: u64,
+        trusted_timestamp_ms: u64,
+        trusted_app_hash: vector<u8>,
+        trusted_next_validators_hash: vector<u8>,
+        ctx: &mut TxContext,
+    ) {
+        let consensus_states = table::new(ctx);
+        table::add(&mut consensus_states, trusted_height, ConsensusState {
+            timestamp_ms: trusted_timestamp_ms,
+            app_hash: trusted_app_hash,
+            next_validators_hash: trusted_next_validators_hash,
+        });
+        transfer::share_object(TendermintLightClient {
+            id: object::new(ctx),
+            chain_id,
+            trusting_period_ms,
+            latest_height: trusted_height,
+            consensus_states,
+        });
+    }
+
+    /// Verify and store `light_block`, using `trusted_validators`, the next validator set of the
+    /// known header at `trusted_height`.
+    public fun submit_light_block(
+        light_client: &mut TendermintLightClient,
+        trusted_height: u64,
+        trusted_validators: vector<u8>,
+        light_block: vector<u8>,
+        clock: &Clock,
+    ) {
+        assert!(table::contains(&light_client.consensus_states, trusted_height), EUnknownHeight);
+        let trusted = *table::borrow(&light_client.consensus_states, trusted_height);
+        assert!(
+            clock::timestamp_ms(clock) < trusted.timestamp_ms + light_client.trusting_period_ms,
+            ETrustingPeriodExpired
+        );
+
+        let (height, timestamp_ms, app_hash, next_validators_hash, trusted_validators_hash) =
+            tendermint_verify_light_block(light_client.chain_id, trusted_height, trusted_validators, light_block);
+        assert!(trusted_validators_hash == trusted.next_validators_hash, EInvalidTrustedValidators);
+        assert!(timestamp_ms > trusted.timestamp_ms, EInvalidTimestamp);
+        assert!(!table::contains(&light_client.consensus_states, height), EHeightAlreadyKnown);
+
+        table::add(&mut light_client.consensus_states, height, ConsensusState {
+            timestamp_ms,
+            app_hash,
+            next_validators_hash,
+        });
+        if (height > light_client.latest_height) {
+            light_client.latest_height = height;
+        };
+        event::emit(TendermintHeaderSubmitted {
+            light_client_id: object::id(light_client),
+            height,
+            app_hash,
+        });
+    }
+
+    /// Whether `value` is stored under `path` in the chain state at `height`.
+    /// For a Cosmos SDK chain, `path` is `[store_name, key]` and `proofs` are the BCS encoded
+    /// existence proofs of the key in the store, then of the store in the multistore.
+    public fun verify_membership(
+        light_client: &TendermintLightClient,
+        height: u64,
+        proofs: vector<u8>,
+        path: vector<vector<u8>>,
+        value: vector<u8>,
+    ): bool {
+        tendermint_verify_membership(app_hash(light_client, height), proofs, path, value)
+    }
+
+    public fun assert_membership(
+        light_client: &TendermintLightClient,
+        height: u64,
+        proofs: vector<u8>,
+        path: vector<vector<u8>>,
+        value: vector<u8>,
+    ) {
+        assert!(verify_membership(light_client, height, proofs, path, value), EInvalidMembershipProof);
+    }
+
+    public fun chain_id(light_client: &TendermintLightClient): vector<u8> {
+        light_client.chain_id
+    }
+
+    public fun latest_height(light_client: &TendermintLightClient): u64 {
+        light_client.latest_height
+    }
+
+    public fun contains_height(light_client: &TendermintLightClient, height: u64): bool {
+        table::contains(&light_client.consensus_states, height)
+    }
+
+    public fun app_hash(light_client: &TendermintLightClient, height: u64): vector<u8> {
+        assert!(table::contains(&light_client.consensus_states, height), EUnknownHeight);
+        table::borrow(&light_client.consensus_states, height).app_hash
+    }
+
+    public fun timestamp_ms(light_client: &TendermintLightClient, height: u64): u64 {
+        assert!(table::contains(&light_client.consensus_states, height), EUnknownHeight);
+        table::borrow(&light_client.consensus_states, height).timestamp_ms
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+#[test_only]
+module dwallet_system::bitcoin_spv_tests {
+    use dwallet::test_scenario;
+    use dwallet_system::bitcoin_spv::{Self, BitcoinLightClient};
+
+    // Bitcoin mainnet blocks 0, 1 and 2.
+    const GENESIS_HEADER: vector<u8> = x"0100000000000000000000000000000000000000000000000000000000000000000000003ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a29ab5f49ffff001d1dac2b7c";
+    const GENESIS_HASH: vector<u8> = x"6fe28c0ab6f1b372c1a6a246ae63f74f931e8365e15a089c68d6190000000000";
+    const GENESIS_TIMESTAMP: u32 = 1231006505;
+    const BLOCK_1_HEADER: vector<u8> = x"010000006fe28c0ab6f1b372c1a6a246ae63f74f931e8365e15a089c68d6190000000000982051fd1e4ba744bbbe680e1fee14677ba1a3c3540bf7b1cdb606e857233e0e61bc6649ffff001d01e36299";
+    const BLOCK_1_HASH: vector<u8> = x"4860eb18bf1b1620e37e9490fc8a427514416fd75159ab86688e9a8300000000";
+    const BLOCK_1_COINBASE_TXID: vector<u8> = x"982051fd1e4ba744bbbe680e1fee14677ba1a3c3540bf7b1cdb606e857233e0e";
+    const BLOCK_2_HEADER: vector<u8> = x"010000004860eb18bf1b1620e37e9490fc8a427514416fd75159ab86688e9a8300000000d5fdcc541e25de1c7a5addedf24858b8bb665c9f36ef744ee42c316022c90f9bb0bc6649ffff001d08d2bd61";
+    const BLOCK_2_HASH: vector<u8> = x"bddd99ccfda39da1b108ce1a5d70038d0a967bacb68b6b63065f626a00000000";
+
+    fun create_mainnet_light_client(scenario: &mut test_scenario::Scenario) {
+        bitcoin_spv::create_light_client(0, GENESIS_HEADER, 0, GENESIS_TIMESTAMP, test_scenario::ctx(scenario));
+        test_scenario::next_tx(scenario, @0x0);
+    }
+
+    #[test]
+    fun test_submit_headers_and_verify_transaction() {
+        let scenario_val = test_scenario::begin(@0x0);
+        let scenario = &mut scenario_val;
+        create_mainnet_light_client(scenario);
+
+        let light_client = test_scenario::take_shared<BitcoinLightClient>(scenario);
+        assert!(bitcoin_spv::tip(&light_client) == GENESIS_HASH, 0);
+
+        bitcoin_spv::submit_headers(&mut light_client, vector[BLOCK_1_HEADER, BLOCK_2_HEADER]);
+        assert!(bitcoin_spv::tip(&light_client) == BLOCK_2_HASH, 0);
+        assert!(bitcoin_spv::tip_height(&light_client) == 2, 0);
+        assert!(bitcoin_spv::main_chain_block_hash(&light_client, 1) == BLOCK_1_HASH, 0);
+        assert!(bitcoin_spv::confirmations(&light_client, BLOCK_1_HASH) == 2, 0);
+
+        // Block 1 only contains its coinbase, so the txid is the merkle root and the proof is empty.
+        bitcoin_spv::assert_transaction_confirmed(&light_client, BLOCK_1_HASH, BLOCK_1_COINBASE_TXID, vector[], 0, 2);
+
+        test_scenario::return_shared(light_client);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = bitcoin_spv::EInsufficientConfirmations)]
+    fun test_insufficient_confirmations() {
+        let scenario_val = test_scenario::begin(@0x0);
+        let scenario = &mut scenario_val;
+        create_mainnet_light_client(scenario);
+
+        let light_client = test_scenario::take_shared<BitcoinLightClient>(scenario);
+        bitcoin_spv::submit_header(&mut light_client, BLOCK_1_HEADER);
+        bitcoin_spv::assert_transaction_confirmed(&light_client, BLOCK_1_HASH, BLOCK_1_COINBASE_TXID, vector[], 0, 2);
+
+        test_scenario::return_shared(light_client);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = bitcoin_spv::EUnknownParent)]
+    fun test_submit_header_with_unknown_parent() {
+        let scenario_val = test_scenario::begin(@0x0);
+        let scenario = &mut scenario_val;
+        create_mainnet_light_client(scenario);
+
+        let light_client = test_scenario::take_shared<BitcoinLightClient>(scenario);
+        bitcoin_spv::submit_header(&mut light_client, BLOCK_2_HEADER);
+
+        test_scenario::return_shared(light_client);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = bitcoin_spv::EHeaderAlreadyKnown)]
+    fun test_submit_header_twice() {
+        let scenario_val = test_scenario::begin(@0x0);
+        let scenario = &mut scenario_val;
+        create_mainnet_light_client(scenario);
+
+        let light_client = test_scenario::take_shared<BitcoinLightClient>(scenario);
+        bitcoin_spv::submit_header(&mut light_client, BLOCK_1_HEADER);
+        bitcoin_spv::submit_header(&mut light_client, BLOCK_1_HEADER);
+
+        test_scenario::return_shared(light_client);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    fun test_verify_merkle_proof() {
+        let a = x"0101010101010101010101010101010101010101010101010101010101010101";
+        let c = x"0303030303030303030303030303030303030303030303030303030303030303";
+        let ab = x"39ce20bede82c96b8908bec4a157b09c549b3db90b9b474bda9ae9b9030310b4";
+        let root = x"223e023fadf1f053df26988871f893c821c28edf77d64a955e6c2a02d547bdac";
+        // The last leaf of an odd level is paired with itself.
+        assert!(bitcoin_spv::verify_merkle_proof(root, c, vector[c, ab], 2), 0);
+        assert!(!bitcoin_spv::verify_merkle_proof(root, a, vector[c, ab], 2), 0);
+        assert!(!bitcoin_spv::verify_merkle_proof(root, c, vector[c, ab], 6), 0);
+    }
+
+    #[test]
+    fun test_compact_target_round_trip() {
+        let target = bitcoin_spv::bits_to_target_for_testing(0x1d00ffff);
+        assert!(target == 0x00000000ffff0000000000000000000000000000000000000000000000000000, 0);
+        assert!(bitcoin_spv::target_to_bits_for_testing(target) == 0x1d00ffff, 0);
+        assert!(bitcoin_spv::target_to_bits_for_testing(0x0080) == 0x02008000, 0);
+    }
+}
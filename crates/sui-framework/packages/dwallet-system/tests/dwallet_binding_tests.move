@@ -0,0 +1,144 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+#[test_only]
+module dwallet_system::dwallet_binding_tests {
+    use dwallet::object;
+    use dwallet::test_scenario;
+    use dwallet::transfer;
+    use dwallet_system::dwallet;
+    use dwallet_system::dwallet_2pc_mpc_ecdsa_k1;
+    use dwallet_system::dwallet_binding::{Self, DWalletBinding};
+
+    const OWNER: address = @0xA;
+    /// The BCS encoded public key of the private key 1, whose Ethereum address is
+    /// `0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf`.
+    const PUBLIC_KEY: vector<u8> = x"210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+    #[test]
+    fun test_create_binding() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        let dwallet_cap = dwallet::create_dwallet_cap_for_testing(test_scenario::ctx(scenario));
+        let dwallet = dwallet_2pc_mpc_ecdsa_k1::create_dwallet_with_public_key_for_testing(
+            object::id(&dwallet_cap),
+            PUBLIC_KEY,
+            test_scenario::ctx(scenario),
+        );
+        let dwallet_id = object::id(&dwallet);
+        dwallet_binding::create_binding(
+            &dwallet,
+            &dwallet_cap,
+            b"ethereum",
+            b"0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf",
+            test_scenario::ctx(scenario),
+        );
+
+        test_scenario::next_tx(scenario, OWNER);
+        let binding = test_scenario::take_immutable<DWalletBinding>(scenario);
+        assert!(dwallet_binding::dwallet_id(&binding) == dwallet_id, 0);
+        assert!(dwallet_binding::chain(&binding) == b"ethereum", 1);
+        assert!(dwallet_binding::external_address(&binding) == b"0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf", 2);
+        assert!(dwallet_binding::is_verified(&binding), 3);
+        test_scenario::return_immutable(binding);
+
+        transfer::public_freeze_object(dwallet);
+        transfer::public_transfer(dwallet_cap, OWNER);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = dwallet_binding::EDWalletCapMismatch)]
+    fun test_create_binding_cap_mismatch() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        let dwallet_cap = dwallet::create_dwallet_cap_for_testing(test_scenario::ctx(scenario));
+        let other_cap = dwallet::create_dwallet_cap_for_testing(test_scenario::ctx(scenario));
+        let dwallet = dwallet_2pc_mpc_ecdsa_k1::create_dwallet_for_testing(
+            object::id(&dwallet_cap),
+            test_scenario::ctx(scenario),
+        );
+        dwallet_binding::create_binding(
+            &dwallet,
+            &other_cap,
+            b"ethereum",
+            b"0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf",
+            test_scenario::ctx(scenario),
+        );
+
+        transfer::public_freeze_object(dwallet);
+        transfer::public_transfer(dwallet_cap, OWNER);
+        transfer::public_transfer(other_cap, OWNER);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = dwallet_binding::EEmptyAddress)]
+    fun test_create_binding_empty_address() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        let dwallet_cap = dwallet::create_dwallet_cap_for_testing(test_scenario::ctx(scenario));
+        let dwallet = dwallet_2pc_mpc_ecdsa_k1::create_dwallet_for_testing(
+            object::id(&dwallet_cap),
+            test_scenario::ctx(scenario),
+        );
+        dwallet_binding::create_binding(&dwallet, &dwallet_cap, b"ethereum", b"", test_scenario::ctx(scenario));
+
+        transfer::public_freeze_object(dwallet);
+        transfer::public_transfer(dwallet_cap, OWNER);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = dwallet_binding::EAddressMismatch)]
+    fun test_create_binding_address_mismatch() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        let dwallet_cap = dwallet::create_dwallet_cap_for_testing(test_scenario::ctx(scenario));
+        let dwallet = dwallet_2pc_mpc_ecdsa_k1::create_dwallet_with_public_key_for_testing(
+            object::id(&dwallet_cap),
+            PUBLIC_KEY,
+            test_scenario::ctx(scenario),
+        );
+        // The address of the private key 2.
+        dwallet_binding::create_binding(
+            &dwallet,
+            &dwallet_cap,
+            b"ethereum",
+            b"0x2B5AD5c4795c026514f8317c7a215E218DcCD6cF",
+            test_scenario::ctx(scenario),
+        );
+
+        transfer::public_freeze_object(dwallet);
+        transfer::public_transfer(dwallet_cap, OWNER);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    fun test_create_binding_unverified_chain() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        let dwallet_cap = dwallet::create_dwallet_cap_for_testing(test_scenario::ctx(scenario));
+        let dwallet = dwallet_2pc_mpc_ecdsa_k1::create_dwallet_with_public_key_for_testing(
+            object::id(&dwallet_cap),
+            PUBLIC_KEY,
+            test_scenario::ctx(scenario),
+        );
+        dwallet_binding::create_binding(
+            &dwallet,
+            &dwallet_cap,
+            b"bitcoin-mainnet",
+            b"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            test_scenario::ctx(scenario),
+        );
+
+        test_scenario::next_tx(scenario, OWNER);
+        let binding = test_scenario::take_immutable<DWalletBinding>(scenario);
+        assert!(!dwallet_binding::is_verified(&binding), 0);
+        test_scenario::return_immutable(binding);
+
+        transfer::public_freeze_object(dwallet);
+        transfer::public_transfer(dwallet_cap, OWNER);
+        test_scenario::end(scenario_val);
+    }
+}
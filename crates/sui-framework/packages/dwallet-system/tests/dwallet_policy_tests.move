@@ -0,0 +1,310 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+#[test_only]
+module dwallet_system::dwallet_policy_tests {
+    use std::vector;
+    use dwallet::clock::{Self, Clock};
+    use dwallet::test_scenario::{Self, Scenario};
+    use dwallet::transfer;
+    use dwallet_system::dwallet::{Self, MessageApproval};
+    use dwallet_system::dwallet_policy::{Self, SigningPolicy};
+
+    const OWNER: address = @0xA;
+    const ALLOWED: address = @0xB;
+    const STRANGER: address = @0xC;
+    const CO_APPROVER_1: address = @0xD;
+    const CO_APPROVER_2: address = @0xE;
+
+    const WINDOW_MS: u64 = 60000;
+
+    fun create_policy(
+        scenario: &mut Scenario,
+        max_messages: u64,
+        co_approvers: vector<address>,
+        co_approval_threshold: u64,
+    ) {
+        let dwallet_cap = dwallet::create_dwallet_cap_for_testing(test_scenario::ctx(scenario));
+        dwallet_policy::create_policy(
+            dwallet_cap,
+            vector[ALLOWED],
+            max_messages,
+            WINDOW_MS,
+            co_approvers,
+            co_approval_threshold,
+            test_scenario::ctx(scenario),
+        );
+    }
+
+    fun approve(scenario: &mut Scenario, sender: address, messages: vector<vector<u8>>, clock: &Clock) {
+        test_scenario::next_tx(scenario, sender);
+        let policy = test_scenario::take_shared<SigningPolicy>(scenario);
+        let message_approvals = dwallet_policy::approve_messages(
+            &mut policy,
+            messages,
+            clock,
+            test_scenario::ctx(scenario),
+        );
+        destroy_approvals(message_approvals);
+        test_scenario::return_shared(policy);
+    }
+
+    fun destroy_approvals(message_approvals: vector<MessageApproval>) {
+        while (vector::length(&message_approvals) > 0) {
+            let (_, _) = dwallet::remove(vector::pop_back(&mut message_approvals));
+        };
+        vector::destroy_empty(message_approvals);
+    }
+
+    #[test]
+    fun test_approve_messages() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        create_policy(scenario, 0, vector[], 0);
+        let clock = clock::create_for_testing(test_scenario::ctx(scenario));
+
+        approve(scenario, OWNER, vector[b"owner"], &clock);
+        approve(scenario, ALLOWED, vector[b"allowed"], &clock);
+
+        clock::destroy_for_testing(clock);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = dwallet_policy::ENotAllowed)]
+    fun test_approve_messages_not_allowed() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        create_policy(scenario, 0, vector[], 0);
+        let clock = clock::create_for_testing(test_scenario::ctx(scenario));
+
+        approve(scenario, STRANGER, vector[b"stranger"], &clock);
+
+        clock::destroy_for_testing(clock);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    fun test_rate_limit_window() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        create_policy(scenario, 2, vector[], 0);
+        let clock = clock::create_for_testing(test_scenario::ctx(scenario));
+
+        approve(scenario, OWNER, vector[b"1", b"2"], &clock);
+        clock::increment_for_testing(&mut clock, WINDOW_MS);
+        approve(scenario, OWNER, vector[b"3", b"4"], &clock);
+
+        clock::destroy_for_testing(clock);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = dwallet_policy::ERateLimited)]
+    fun test_rate_limited() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        create_policy(scenario, 2, vector[], 0);
+        let clock = clock::create_for_testing(test_scenario::ctx(scenario));
+
+        approve(scenario, OWNER, vector[b"1", b"2"], &clock);
+        clock::increment_for_testing(&mut clock, WINDOW_MS - 1);
+        approve(scenario, ALLOWED, vector[b"3"], &clock);
+
+        clock::destroy_for_testing(clock);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = dwallet_policy::ECoApprovalRequired)]
+    fun test_approve_messages_co_approval_required() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        create_policy(scenario, 0, vector[CO_APPROVER_1], 1);
+        let clock = clock::create_for_testing(test_scenario::ctx(scenario));
+
+        approve(scenario, OWNER, vector[b"owner"], &clock);
+
+        clock::destroy_for_testing(clock);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    fun test_co_approval() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        create_policy(scenario, 0, vector[CO_APPROVER_1, CO_APPROVER_2], 1);
+        let clock = clock::create_for_testing(test_scenario::ctx(scenario));
+
+        test_scenario::next_tx(scenario, ALLOWED);
+        let policy = test_scenario::take_shared<SigningPolicy>(scenario);
+        let request_id = dwallet_policy::request_approval(
+            &mut policy,
+            vector[b"requested"],
+            test_scenario::ctx(scenario),
+        );
+        test_scenario::return_shared(policy);
+
+        test_scenario::next_tx(scenario, CO_APPROVER_2);
+        let policy = test_scenario::take_shared<SigningPolicy>(scenario);
+        dwallet_policy::co_approve(&mut policy, request_id, test_scenario::ctx(scenario));
+        test_scenario::return_shared(policy);
+
+        test_scenario::next_tx(scenario, ALLOWED);
+        let policy = test_scenario::take_shared<SigningPolicy>(scenario);
+        let message_approvals = dwallet_policy::approve_requested(
+            &mut policy,
+            request_id,
+            &clock,
+            test_scenario::ctx(scenario),
+        );
+        assert!(vector::length(&message_approvals) == 1, 0);
+        destroy_approvals(message_approvals);
+        test_scenario::return_shared(policy);
+
+        clock::destroy_for_testing(clock);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = dwallet_policy::ENotEnoughCoApprovals)]
+    fun test_not_enough_co_approvals() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        create_policy(scenario, 0, vector[CO_APPROVER_1, CO_APPROVER_2], 2);
+        let clock = clock::create_for_testing(test_scenario::ctx(scenario));
+
+        test_scenario::next_tx(scenario, OWNER);
+        let policy = test_scenario::take_shared<SigningPolicy>(scenario);
+        let request_id = dwallet_policy::request_approval(
+            &mut policy,
+            vector[b"requested"],
+            test_scenario::ctx(scenario),
+        );
+        test_scenario::return_shared(policy);
+
+        test_scenario::next_tx(scenario, CO_APPROVER_1);
+        let policy = test_scenario::take_shared<SigningPolicy>(scenario);
+        dwallet_policy::co_approve(&mut policy, request_id, test_scenario::ctx(scenario));
+        test_scenario::return_shared(policy);
+
+        test_scenario::next_tx(scenario, OWNER);
+        let policy = test_scenario::take_shared<SigningPolicy>(scenario);
+        let message_approvals = dwallet_policy::approve_requested(
+            &mut policy,
+            request_id,
+            &clock,
+            test_scenario::ctx(scenario),
+        );
+        destroy_approvals(message_approvals);
+        test_scenario::return_shared(policy);
+
+        clock::destroy_for_testing(clock);
+        test_scenario::end(scenario_val);
+    }
+
+    fun request(scenario: &mut Scenario, sender: address): u64 {
+        test_scenario::next_tx(scenario, sender);
+        let policy = test_scenario::take_shared<SigningPolicy>(scenario);
+        let request_id = dwallet_policy::request_approval(
+            &mut policy,
+            vector[b"requested"],
+            test_scenario::ctx(scenario),
+        );
+        test_scenario::return_shared(policy);
+        request_id
+    }
+
+    fun cancel(scenario: &mut Scenario, sender: address, request_id: u64) {
+        test_scenario::next_tx(scenario, sender);
+        let policy = test_scenario::take_shared<SigningPolicy>(scenario);
+        dwallet_policy::cancel_request(&mut policy, request_id, test_scenario::ctx(scenario));
+        test_scenario::return_shared(policy);
+    }
+
+    #[test]
+    fun test_cancel_request() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        create_policy(scenario, 0, vector[CO_APPROVER_1], 1);
+
+        let i = 0;
+        while (i < 32) {
+            request(scenario, ALLOWED);
+            i = i + 1;
+        };
+        cancel(scenario, ALLOWED, 0);
+        cancel(scenario, OWNER, 1);
+        request(scenario, ALLOWED);
+        request(scenario, ALLOWED);
+
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = dwallet_policy::ENotRequester)]
+    fun test_cancel_request_not_requester() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        create_policy(scenario, 0, vector[CO_APPROVER_1], 1);
+
+        let request_id = request(scenario, ALLOWED);
+        cancel(scenario, CO_APPROVER_1, request_id);
+
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = dwallet_policy::ETooManyRequests)]
+    fun test_too_many_requests() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        create_policy(scenario, 0, vector[CO_APPROVER_1], 1);
+
+        let i = 0;
+        while (i <= 32) {
+            request(scenario, ALLOWED);
+            i = i + 1;
+        };
+
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = dwallet_policy::EInvalidCoApprovalThreshold)]
+    fun test_invalid_co_approval_threshold() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        create_policy(scenario, 0, vector[CO_APPROVER_1], 2);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    fun test_remove_policy() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        create_policy(scenario, 0, vector[], 0);
+
+        test_scenario::next_tx(scenario, OWNER);
+        let policy = test_scenario::take_shared<SigningPolicy>(scenario);
+        let dwallet_cap = dwallet_policy::remove_policy(policy, test_scenario::ctx(scenario));
+        transfer::public_transfer(dwallet_cap, OWNER);
+
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = dwallet_policy::ENotOwner)]
+    fun test_remove_policy_not_owner() {
+        let scenario_val = test_scenario::begin(OWNER);
+        let scenario = &mut scenario_val;
+        create_policy(scenario, 0, vector[], 0);
+
+        test_scenario::next_tx(scenario, ALLOWED);
+        let policy = test_scenario::take_shared<SigningPolicy>(scenario);
+        let dwallet_cap = dwallet_policy::remove_policy(policy, test_scenario::ctx(scenario));
+        transfer::public_transfer(dwallet_cap, ALLOWED);
+
+        test_scenario::end(scenario_val);
+    }
+}
@@ -0,0 +1,21 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+#[test_only]
+module dwallet_system::dwallet_tests {
+    use dwallet_system::dwallet;
+
+    #[test]
+    fun test_attestation_message() {
+        assert!(
+            dwallet::attestation_message(b"price-feed:v1", x"0102") == x"6477616c6c65742d6174746573746174696f6e3a76310d70726963652d666565643a76310102",
+            0
+        );
+    }
+
+    #[test]
+    #[expected_failure(abort_code = dwallet::EEmptyAttestationSchema)]
+    fun test_attestation_message_empty_schema() {
+        dwallet::attestation_message(b"", x"0102");
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+#[test_only]
+module dwallet_system::ethereum_light_client_tests {
+    use dwallet::test_scenario;
+    use dwallet_system::ethereum_light_client;
+
+    const CHAIN_ID: u64 = 1;
+    const GENESIS_VALIDATORS_ROOT: vector<u8> = x"4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95";
+    const TRUSTED_BLOCK_ROOT: vector<u8> = x"0404040404040404040404040404040404040404040404040404040404040404";
+
+    #[test]
+    #[expected_failure(abort_code = ethereum_light_client::EInvalidInput)]
+    fun test_create_with_invalid_genesis_validators_root() {
+        let scenario_val = test_scenario::begin(@0x0);
+        let scenario = &mut scenario_val;
+        ethereum_light_client::create_light_client(
+            CHAIN_ID,
+            x"4b36",
+            vector[],
+            TRUSTED_BLOCK_ROOT,
+            vector[],
+            test_scenario::ctx(scenario),
+        );
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = ethereum_light_client::EInvalidInput)]
+    fun test_create_with_invalid_bootstrap() {
+        let scenario_val = test_scenario::begin(@0x0);
+        let scenario = &mut scenario_val;
+        ethereum_light_client::create_light_client(
+            CHAIN_ID,
+            GENESIS_VALIDATORS_ROOT,
+            // No forks.
+            x"00",
+            TRUSTED_BLOCK_ROOT,
+            x"0102",
+            test_scenario::ctx(scenario),
+        );
+        test_scenario::end(scenario_val);
+    }
+}
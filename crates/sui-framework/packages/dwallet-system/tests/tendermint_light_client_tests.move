@@ -0,0 +1,95 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+#[test_only]
+module dwallet_system::tendermint_light_client_tests {
+    use dwallet::clock;
+    use dwallet::test_scenario;
+    use dwallet_system::tendermint_light_client::{Self, TendermintLightClient};
+
+    const CHAIN_ID: vector<u8> = b"cosmoshub-4";
+    /// Two weeks.
+    const TRUSTING_PERIOD_MS: u64 = 1209600000;
+    const TRUSTED_HEIGHT: u64 = 100;
+    const TRUSTED_TIMESTAMP_MS: u64 = 1700000000000;
+    const APP_HASH: vector<u8> = x"0404040404040404040404040404040404040404040404040404040404040404";
+    const NEXT_VALIDATORS_HASH: vector<u8> = x"0505050505050505050505050505050505050505050505050505050505050505";
+
+    fun create_light_client(scenario: &mut test_scenario::Scenario) {
+        tendermint_light_client::create_light_client(
+            CHAIN_ID,
+            TRUSTING_PERIOD_MS,
+            TRUSTED_HEIGHT,
+            TRUSTED_TIMESTAMP_MS,
+            APP_HASH,
+            NEXT_VALIDATORS_HASH,
+            test_scenario::ctx(scenario),
+        );
+        test_scenario::next_tx(scenario, @0x0);
+    }
+
+    #[test]
+    fun test_create_light_client() {
+        let scenario_val = test_scenario::begin(@0x0);
+        let scenario = &mut scenario_val;
+        create_light_client(scenario);
+
+        let light_client = test_scenario::take_shared<TendermintLightClient>(scenario);
+        assert!(tendermint_light_client::chain_id(&light_client) == CHAIN_ID, 0);
+        assert!(tendermint_light_client::latest_height(&light_client) == TRUSTED_HEIGHT, 0);
+        assert!(tendermint_light_client::contains_height(&light_client, TRUSTED_HEIGHT), 0);
+        assert!(!tendermint_light_client::contains_height(&light_client, TRUSTED_HEIGHT + 1), 0);
+        assert!(tendermint_light_client::app_hash(&light_client, TRUSTED_HEIGHT) == APP_HASH, 0);
+        assert!(tendermint_light_client::timestamp_ms(&light_client, TRUSTED_HEIGHT) == TRUSTED_TIMESTAMP_MS, 0);
+
+        test_scenario::return_shared(light_client);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = tendermint_light_client::ETrustingPeriodExpired)]
+    fun test_trusting_period_expired() {
+        let scenario_val = test_scenario::begin(@0x0);
+        let scenario = &mut scenario_val;
+        create_light_client(scenario);
+
+        let light_client = test_scenario::take_shared<TendermintLightClient>(scenario);
+        let clock = clock::create_for_testing(test_scenario::ctx(scenario));
+        clock::set_for_testing(&mut clock, TRUSTED_TIMESTAMP_MS + TRUSTING_PERIOD_MS);
+        tendermint_light_client::submit_light_block(&mut light_client, TRUSTED_HEIGHT, vector[], vector[], &clock);
+
+        clock::destroy_for_testing(clock);
+        test_scenario::return_shared(light_client);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = tendermint_light_client::EUnknownHeight)]
+    fun test_submit_from_unknown_height() {
+        let scenario_val = test_scenario::begin(@0x0);
+        let scenario = &mut scenario_val;
+        create_light_client(scenario);
+
+        let light_client = test_scenario::take_shared<TendermintLightClient>(scenario);
+        let clock = clock::create_for_testing(test_scenario::ctx(scenario));
+        tendermint_light_client::submit_light_block(&mut light_client, TRUSTED_HEIGHT + 1, vector[], vector[], &clock);
+
+        clock::destroy_for_testing(clock);
+        test_scenario::return_shared(light_client);
+        test_scenario::end(scenario_val);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = tendermint_light_client::EUnknownHeight)]
+    fun test_verify_membership_at_unknown_height() {
+        let scenario_val = test_scenario::begin(@0x0);
+        let scenario = &mut scenario_val;
+        create_light_client(scenario);
+
+        let light_client = test_scenario::take_shared<TendermintLightClient>(scenario);
+        tendermint_light_client::verify_membership(&light_client, TRUSTED_HEIGHT + 1, vector[], vector[], vector[]);
+
+        test_scenario::return_shared(light_client);
+        test_scenario::end(scenario_val);
+    }
+}
@@ -4,6 +4,7 @@
 use super::db_backend::GenericQueryBuilder;
 use crate::{
     config::{Limits, DEFAULT_SERVER_DB_POOL_SIZE},
+    data::Db,
     error::Error,
     types::{
         address::{Address, AddressTransactionBlockRelationship},
@@ -12,6 +13,7 @@ use crate::{
         coin::Coin,
         coin_metadata::CoinMetadata,
         digest::Digest,
+        dwallet::DWallet,
         dynamic_field::{DynamicField, DynamicFieldName},
         event::{Event, EventFilter},
         move_function::MoveFunction,
@@ -55,6 +57,7 @@ use sui_types::{
     gas_coin::{GAS, TOTAL_SUPPLY_SUI},
     governance::StakedSui as NativeStakedSui,
     object::Object as NativeObject,
+    signature_mpc::DWalletCap,
     sui_system_state::sui_system_state_summary::{
         SuiSystemStateSummary as NativeSuiSystemStateSummary, SuiValidatorSummary,
     },
@@ -1069,6 +1072,65 @@ impl PgManager {
         Ok(Some(connection))
     }
 
+    /// The dWallets whose `DWalletCap` is owned by `address`, paginated by the IDs of their caps.
+    /// The dWallets whose DKG is still in progress are missing from the page of their caps.
+    pub(crate) async fn fetch_owned_dwallets(
+        &self,
+        address: SuiAddress,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Option<Connection<String, DWallet>>, Error> {
+        let obj_filter = ObjectFilter {
+            type_: Some(DWalletCap::type_().to_canonical_string(/* with_prefix */ true)),
+            owner: Some(address),
+            object_ids: None,
+            object_keys: None,
+        };
+
+        let objs = self
+            .multi_get_objs(
+                first,
+                after,
+                last,
+                before,
+                Some(obj_filter),
+                Some(OwnerType::Address),
+            )
+            .await?;
+
+        let Some((stored_objs, has_next_page)) = objs else {
+            return Ok(None);
+        };
+
+        let cap_ids: Vec<_> = stored_objs
+            .iter()
+            .map(|stored_obj| stored_obj.object_id.clone())
+            .collect();
+        let db = Db::new(self.inner.clone(), self.limits);
+        let mut dwallets: BTreeMap<_, _> = DWallet::query_by_caps(&db, cap_ids.clone())
+            .await?
+            .into_iter()
+            .map(|dwallet| (dwallet.stored.dwallet_cap_id.clone(), dwallet))
+            .collect();
+
+        let mut connection = Connection::new(false, has_next_page);
+        for cap_id in cap_ids {
+            let Some(dwallet) = dwallets.remove(&cap_id) else {
+                continue;
+            };
+
+            let cursor = ObjectID::from_bytes(&cap_id)
+                .map_err(|e| Error::Internal(format!("Failed to deserialize object ID: {e}")))?
+                .to_canonical_string(/* with_prefix */ true);
+
+            connection.edges.push(Edge::new(cursor, dwallet));
+        }
+
+        Ok(Some(connection))
+    }
+
     /// Make a request to the RPC for its representations of the staked sui we parsed out of the
     /// object.  Used to implement fields that are implemented in JSON-RPC but not GraphQL (yet).
     pub(crate) async fn fetch_rpc_staked_sui(
@@ -92,6 +92,7 @@ fn functional_groups() -> &'static BTreeMap<(&'static str, &'static str), Functi
             (("Query", "networkMetrics"), G::Analytics),
             (("Query", "protocolConfig"), G::SystemState),
             (("Query", "resolveNameServiceAddress"), G::NameService),
+            (("Subscription", "dwalletEvents"), G::Subscriptions),
             (("Subscription", "events"), G::Subscriptions),
             (("Subscription", "transactions"), G::Subscriptions),
             (("SystemStateSummary", "safeMode"), G::SystemState),
@@ -115,9 +116,9 @@ mod tests {
     use std::collections::BTreeSet;
 
     use async_graphql::registry::Registry;
-    use async_graphql::OutputType;
+    use async_graphql::{OutputType, SubscriptionType};
 
-    use crate::types::query::Query;
+    use crate::{subscription::Subscription, types::query::Query};
 
     use super::*;
 
@@ -128,6 +129,7 @@ mod tests {
     fn test_groups_match_schema() {
         let mut registry = Registry::default();
         Query::create_type_info(&mut registry);
+        Subscription::create_type_info(&mut registry);
 
         let unimplemented = BTreeSet::from_iter([
             ("Checkpoint", "addressMetrics"),
@@ -13,17 +13,19 @@ pub(crate) mod functional_group;
 mod metrics;
 mod mutation;
 pub mod server;
+mod subscription;
 pub mod test_infra;
 mod types;
 
 use async_graphql::*;
 use mutation::Mutation;
+use subscription::Subscription;
 use types::owner::ObjectOwner;
 
 use crate::types::query::Query;
 
 pub fn schema_sdl_export() -> String {
-    let schema = Schema::build(Query, Mutation, EmptySubscription)
+    let schema = Schema::build(Query, Mutation, Subscription)
         .register_output_type::<ObjectOwner>()
         .finish();
     schema.sdl()
@@ -0,0 +1,65 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use std::{collections::VecDeque, time::Duration};
+
+use async_graphql::*;
+use futures::{stream, Stream};
+
+use crate::{
+    data::Db,
+    types::dwallet_event::{DWalletEvent, DWalletEventFilter},
+};
+
+/// How often a subscription polls the database for new events.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The most events a subscription reads from the database at once.
+const EVENTS_BATCH_SIZE: i64 = 50;
+
+pub struct Subscription;
+
+/// The state of a subscription to dWallet events: its own handle to the database, its filter, the
+/// cursor of the last event it read, and the events it read but has not sent yet.
+type DWalletEventsState = (
+    Db,
+    DWalletEventFilter,
+    Option<(i64, i64)>,
+    VecDeque<DWalletEvent>,
+);
+
+#[Subscription]
+impl Subscription {
+    /// The dWallet events matching `filter`, as the indexer ingests them, from the first event
+    /// after subscribing. The subscription ends at the first error reading the events.
+    async fn dwallet_events(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<DWalletEventFilter>,
+    ) -> Result<impl Stream<Item = Result<DWalletEvent>>> {
+        let db: &Db = ctx.data_unchecked();
+        let db = Db::new(db.inner.clone(), db.limits);
+        let cursor = DWalletEvent::latest_cursor(&db).await.extend()?;
+        let state: DWalletEventsState = (db, filter.unwrap_or_default(), cursor, VecDeque::new());
+
+        Ok(stream::unfold(Some(state), |state| async move {
+            let (db, filter, mut cursor, mut pending) = state?;
+            loop {
+                if let Some(event) = pending.pop_front() {
+                    return Some((Ok(event), Some((db, filter, cursor, pending))));
+                }
+
+                match DWalletEvent::query_after(&db, cursor, filter.clone(), EVENTS_BATCH_SIZE)
+                    .await
+                {
+                    Ok(events) if events.is_empty() => tokio::time::sleep(POLL_INTERVAL).await,
+                    Ok(events) => {
+                        cursor = events.last().map(DWalletEvent::cursor);
+                        pending.extend(events);
+                    }
+                    Err(e) => return Some((Err(e.extend()), None)),
+                }
+            }
+        }))
+    }
+}
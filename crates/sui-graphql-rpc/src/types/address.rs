@@ -9,6 +9,7 @@ use crate::{context_data::db_data_provider::PgManager, error::Error};
 use super::{
     balance::Balance,
     coin::Coin,
+    dwallet::DWallet,
     dynamic_field::{DynamicField, DynamicFieldName},
     object::{Object, ObjectFilter},
     stake::StakedSui,
@@ -140,6 +141,21 @@ impl Address {
             .extend()
     }
 
+    /// The dWallets whose `DWalletCap` is owned by the given address.
+    pub async fn dwallet_connection(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Option<Connection<String, DWallet>>> {
+        ctx.data_unchecked::<PgManager>()
+            .fetch_owned_dwallets(self.address, first, after, last, before)
+            .await
+            .extend()
+    }
+
     pub async fn default_name_service_name(&self, ctx: &Context<'_>) -> Result<Option<String>> {
         ctx.data_unchecked::<PgManager>()
             .default_name_service_name(ctx.data_unchecked::<NameServiceConfig>(), self.address)
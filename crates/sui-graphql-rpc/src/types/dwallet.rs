@@ -0,0 +1,146 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use async_graphql::{connection::Connection, *};
+use diesel::{ExpressionMethods, QueryDsl};
+use sui_indexer::{models_v2::dwallets::StoredDWallet, schema_v2::dwallets};
+
+use super::{
+    address::Address,
+    base64::Base64,
+    date_time::DateTime,
+    object::Object,
+    sign_session::{self, SignSession},
+    sui_address::SuiAddress,
+};
+use crate::{
+    context_data::db_data_provider::PgManager,
+    data::{Db, QueryExecutor},
+    error::Error,
+    types::cursor::Page,
+};
+
+/// A dWallet: a key shared between its owner and the network, with which they sign messages
+/// together, as indexed from the `DWalletCreated` event of its creation.
+#[derive(Clone)]
+pub(crate) struct DWallet {
+    pub stored: StoredDWallet,
+}
+
+#[Object]
+impl DWallet {
+    /// The ID of the `DWallet` object.
+    async fn dwallet_id(&self) -> Result<SuiAddress> {
+        address_of(&self.stored.dwallet_id).extend()
+    }
+
+    /// The ID of the `DWalletCap` of the dWallet, whose owner approves the messages it signs.
+    async fn dwallet_cap_id(&self) -> Result<SuiAddress> {
+        address_of(&self.stored.dwallet_cap_id).extend()
+    }
+
+    /// The compressed SEC1 public key of the dWallet.
+    async fn public_key(&self) -> Base64 {
+        Base64::from(&self.stored.public_key)
+    }
+
+    /// The public output of the DKG that created the dWallet.
+    async fn dkg_output(&self) -> Base64 {
+        Base64::from(&self.stored.dkg_output)
+    }
+
+    /// The address that created the dWallet.
+    async fn creator(&self) -> Result<Address> {
+        Ok(Address {
+            address: address_of(&self.stored.sender).extend()?,
+        })
+    }
+
+    /// When the dWallet was created.
+    async fn created_at(&self) -> Result<DateTime> {
+        DateTime::from_ms(self.stored.timestamp_ms).extend()
+    }
+
+    /// The `DWallet` object.
+    async fn as_object(&self, ctx: &Context<'_>) -> Result<Option<Object>> {
+        let id = address_of(&self.stored.dwallet_id).extend()?;
+        ctx.data_unchecked::<PgManager>()
+            .fetch_obj(id, None)
+            .await
+            .extend()
+    }
+
+    /// The `DWalletCap` object of the dWallet, whose owner controls it.
+    async fn cap(&self, ctx: &Context<'_>) -> Result<Option<Object>> {
+        let id = address_of(&self.stored.dwallet_cap_id).extend()?;
+        ctx.data_unchecked::<PgManager>()
+            .fetch_obj(id, None)
+            .await
+            .extend()
+    }
+
+    /// The sign sessions of the dWallet, oldest first.
+    async fn sign_session_connection(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<sign_session::Cursor>,
+        last: Option<u64>,
+        before: Option<sign_session::Cursor>,
+    ) -> Result<Connection<String, SignSession>> {
+        let page = Page::from_params(ctx.data_unchecked(), first, after, last, before)?;
+        SignSession::paginate(
+            ctx.data_unchecked(),
+            page,
+            Some(self.stored.dwallet_id.clone()),
+        )
+        .await
+        .extend()
+    }
+}
+
+impl DWallet {
+    /// Look up the dWallet `id` in the database.
+    pub(crate) async fn query(db: &Db, id: SuiAddress) -> Result<Option<Self>, Error> {
+        use dwallets::dsl;
+
+        let id = id.into_vec();
+        let stored = db
+            .optional(move || {
+                dsl::dwallets
+                    .filter(dsl::dwallet_id.eq(id.clone()))
+                    .limit(1)
+                    .into_boxed()
+            })
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to fetch dWallet: {e}")))?;
+
+        Ok(stored.map(|stored| DWallet { stored }))
+    }
+
+    /// Look up the dWallets of the `DWalletCap`s `cap_ids` in the database. The dWallets whose
+    /// DKG is still in progress are missing from the result.
+    pub(crate) async fn query_by_caps(db: &Db, cap_ids: Vec<Vec<u8>>) -> Result<Vec<Self>, Error> {
+        use dwallets::dsl;
+
+        let stored: Vec<StoredDWallet> = db
+            .results(move || {
+                dsl::dwallets
+                    .filter(dsl::dwallet_cap_id.eq_any(cap_ids.clone()))
+                    .into_boxed()
+            })
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to fetch dWallets: {e}")))?;
+
+        Ok(stored
+            .into_iter()
+            .map(|stored| DWallet { stored })
+            .collect())
+    }
+}
+
+/// The address of an ID stored by the indexer.
+pub(crate) fn address_of(bytes: &[u8]) -> Result<SuiAddress, Error> {
+    SuiAddress::from_bytes(bytes)
+        .map_err(|e| Error::Internal(format!("Failed to deserialize address: {e}")))
+}
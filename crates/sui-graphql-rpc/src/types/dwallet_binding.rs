@@ -0,0 +1,67 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use async_graphql::*;
+use sui_types::signature_mpc::DWalletBinding as NativeDWalletBinding;
+
+use super::{dwallet::DWallet, move_object::MoveObject, sui_address::SuiAddress};
+
+pub(crate) enum DWalletBindingDowncastError {
+    NotADWalletBinding,
+    Bcs(bcs::Error),
+}
+
+#[derive(Clone)]
+pub(crate) struct DWalletBinding {
+    /// Representation of this DWalletBinding as a generic Move Object.
+    pub super_: MoveObject,
+
+    /// Deserialized representation of the Move Object's contents as a
+    /// `0x3::dwallet_binding::DWalletBinding`.
+    pub native: NativeDWalletBinding,
+}
+
+#[Object]
+impl DWalletBinding {
+    /// The dWallet bound to the external address.
+    async fn dwallet(&self, ctx: &Context<'_>) -> Result<Option<DWallet>> {
+        let id = SuiAddress::from(self.native.dwallet_id.bytes);
+        DWallet::query(ctx.data_unchecked(), id).await.extend()
+    }
+
+    /// The ID of the `DWalletCap` of the bound dWallet.
+    async fn dwallet_cap_id(&self) -> SuiAddress {
+        SuiAddress::from(self.native.dwallet_cap_id.bytes)
+    }
+
+    /// The chain of the external address, e.g. `bitcoin`.
+    async fn chain(&self) -> String {
+        String::from_utf8_lossy(&self.native.chain).into_owned()
+    }
+
+    /// The address on the external chain, derived from the public key of the dWallet.
+    async fn external_address(&self) -> String {
+        String::from_utf8_lossy(&self.native.external_address).into_owned()
+    }
+
+    /// The corresponding `0x3::dwallet_binding::DWalletBinding` Move object.
+    async fn as_move_object(&self) -> &MoveObject {
+        &self.super_
+    }
+}
+
+impl TryFrom<&MoveObject> for DWalletBinding {
+    type Error = DWalletBindingDowncastError;
+
+    fn try_from(move_object: &MoveObject) -> Result<Self, Self::Error> {
+        if !move_object.native.is_type(&NativeDWalletBinding::type_()) {
+            return Err(DWalletBindingDowncastError::NotADWalletBinding);
+        }
+
+        Ok(Self {
+            super_: move_object.clone(),
+            native: bcs::from_bytes(move_object.native.contents())
+                .map_err(DWalletBindingDowncastError::Bcs)?,
+        })
+    }
+}
@@ -0,0 +1,244 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use async_graphql::*;
+use diesel::{BoolExpressionMethods, ExpressionMethods, QueryDsl};
+use sui_indexer::{models_v2::dwallets::StoredDWalletEvent, schema_v2::dwallet_events};
+use sui_types::{
+    digests::TransactionDigest,
+    signature_mpc::{SignOutputCreatedEvent, SignSessionCreatedEvent},
+};
+
+use super::{
+    address::Address,
+    base64::Base64,
+    date_time::DateTime,
+    digest::Digest,
+    dwallet::{address_of, DWallet},
+    sign_session::SignSession,
+    sui_address::SuiAddress,
+    transaction_block::TransactionBlock,
+};
+use crate::{
+    context_data::db_data_provider::PgManager,
+    data::{Db, QueryExecutor},
+    error::Error,
+};
+
+/// An event of the lifecycle of a dWallet: its creation, or a presign or sign session of its
+/// owner.
+#[derive(Clone)]
+pub(crate) struct DWalletEvent {
+    pub stored: StoredDWalletEvent,
+}
+
+/// The kinds of dWallet events.
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum DWalletEventType {
+    /// The DKG of a dWallet completed, creating it.
+    #[graphql(name = "DWALLET_CREATED")]
+    DWalletCreated,
+    /// The owner of a dWallet requested presigns from the network.
+    PresignSessionCreated,
+    /// The network completed a presign for a dWallet.
+    PresignCreated,
+    /// The owner of a dWallet requested the network to sign messages.
+    SignSessionCreated,
+    /// The network signed the messages of a sign session.
+    SignOutputCreated,
+}
+
+#[derive(InputObject, Clone, Default)]
+pub(crate) struct DWalletEventFilter {
+    /// Only the events of this dWallet.
+    pub dwallet: Option<SuiAddress>,
+    /// Only the events of the dWallets, and sessions, of this address.
+    pub sender: Option<SuiAddress>,
+    /// Only the events of this kind.
+    pub event_type: Option<DWalletEventType>,
+}
+
+#[Object]
+impl DWalletEvent {
+    /// The kind of the event.
+    async fn event_type(&self) -> Result<DWalletEventType> {
+        DWalletEventType::from_name(&self.stored.event_type)
+            .ok_or_else(|| {
+                Error::Internal(format!(
+                    "Unknown dWallet event type: {}",
+                    self.stored.event_type
+                ))
+            })
+            .extend()
+    }
+
+    /// The dWallet the event is of. Absent for the events of a dWallet whose DKG is still in
+    /// progress.
+    async fn dwallet(&self, ctx: &Context<'_>) -> Result<Option<DWallet>> {
+        let id = address_of(&self.stored.dwallet_id).extend()?;
+        DWallet::query(ctx.data_unchecked(), id).await.extend()
+    }
+
+    /// The address whose dWallet, or session, the event is of.
+    async fn sender(&self) -> Result<Address> {
+        Ok(Address {
+            address: address_of(&self.stored.sender).extend()?,
+        })
+    }
+
+    /// The sign session the event is of, for the events of sign sessions.
+    async fn sign_session(&self, ctx: &Context<'_>) -> Result<Option<SignSession>> {
+        let Some(session_id) = self.sign_session_id().extend()? else {
+            return Ok(None);
+        };
+
+        SignSession::query(ctx.data_unchecked(), session_id)
+            .await
+            .extend()
+    }
+
+    /// The transaction block that emitted the event.
+    async fn transaction_block(&self, ctx: &Context<'_>) -> Result<Option<TransactionBlock>> {
+        let digest = TransactionDigest::try_from(self.stored.transaction_digest.as_slice())
+            .map_err(|e| Error::Internal(format!("Failed to deserialize digest: {e}")))
+            .extend()?;
+
+        ctx.data_unchecked::<PgManager>()
+            .fetch_tx(&Digest::from(digest))
+            .await
+            .extend()
+    }
+
+    /// UTC timestamp in milliseconds since epoch (1/1/1970)
+    async fn timestamp(&self) -> Result<DateTime> {
+        DateTime::from_ms(self.stored.timestamp_ms).extend()
+    }
+
+    /// The Base64 encoded BCS serialized bytes of the event.
+    async fn bcs(&self) -> Base64 {
+        Base64::from(&self.stored.bcs)
+    }
+}
+
+impl DWalletEventType {
+    /// The name of the Move event struct, as stored by the indexer.
+    fn name(&self) -> &'static str {
+        match self {
+            Self::DWalletCreated => "DWalletCreated",
+            Self::PresignSessionCreated => "PresignSessionCreated",
+            Self::PresignCreated => "PresignCreated",
+            Self::SignSessionCreated => "SignSessionCreated",
+            Self::SignOutputCreated => "SignOutputCreated",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [
+            Self::DWalletCreated,
+            Self::PresignSessionCreated,
+            Self::PresignCreated,
+            Self::SignSessionCreated,
+            Self::SignOutputCreated,
+        ]
+        .into_iter()
+        .find(|event_type| event_type.name() == name)
+    }
+}
+
+impl DWalletEvent {
+    /// The events matching `filter` emitted after the event at `cursor`, the sequence numbers of
+    /// its transaction and of the event in it, oldest first, up to `limit` of them.
+    pub(crate) async fn query_after(
+        db: &Db,
+        cursor: Option<(i64, i64)>,
+        filter: DWalletEventFilter,
+        limit: i64,
+    ) -> Result<Vec<Self>, Error> {
+        use dwallet_events::dsl;
+
+        let stored: Vec<StoredDWalletEvent> = db
+            .results(move || {
+                let mut query = dsl::dwallet_events
+                    .order((dsl::tx_sequence_number, dsl::event_sequence_number))
+                    .limit(limit)
+                    .into_boxed();
+
+                if let Some((tx, event)) = cursor {
+                    query = query.filter(
+                        dsl::tx_sequence_number.gt(tx).or(dsl::tx_sequence_number
+                            .eq(tx)
+                            .and(dsl::event_sequence_number.gt(event))),
+                    );
+                }
+
+                if let Some(dwallet) = &filter.dwallet {
+                    query = query.filter(dsl::dwallet_id.eq(dwallet.into_vec()));
+                }
+
+                if let Some(sender) = &filter.sender {
+                    query = query.filter(dsl::sender.eq(sender.into_vec()));
+                }
+
+                if let Some(event_type) = filter.event_type {
+                    query = query.filter(dsl::event_type.eq(event_type.name()));
+                }
+
+                query
+            })
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to fetch dWallet events: {e}")))?;
+
+        Ok(stored
+            .into_iter()
+            .map(|stored| DWalletEvent { stored })
+            .collect())
+    }
+
+    /// The sequence number of the latest dWallet event, and of its transaction, if there is one.
+    pub(crate) async fn latest_cursor(db: &Db) -> Result<Option<(i64, i64)>, Error> {
+        use dwallet_events::dsl;
+
+        let stored: Option<StoredDWalletEvent> = db
+            .optional(move || {
+                dsl::dwallet_events
+                    .order((
+                        dsl::tx_sequence_number.desc(),
+                        dsl::event_sequence_number.desc(),
+                    ))
+                    .limit(1)
+                    .into_boxed()
+            })
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to fetch dWallet events: {e}")))?;
+
+        Ok(stored.map(|stored| DWalletEvent { stored }.cursor()))
+    }
+
+    /// The sequence number of the transaction that emitted the event, and of the event in it.
+    pub(crate) fn cursor(&self) -> (i64, i64) {
+        (
+            self.stored.tx_sequence_number,
+            self.stored.event_sequence_number,
+        )
+    }
+
+    /// The ID of the sign session of the event, if it is the event of a sign session.
+    fn sign_session_id(&self) -> Result<Option<SuiAddress>, Error> {
+        let bcs_error = |e| Error::Internal(format!("Failed to deserialize dWallet event: {e}"));
+        let session_id = match DWalletEventType::from_name(&self.stored.event_type) {
+            Some(DWalletEventType::SignSessionCreated) => {
+                bcs::from_bytes::<SignSessionCreatedEvent>(&self.stored.bcs)
+                    .map_err(bcs_error)?
+                    .session_id
+            }
+            Some(DWalletEventType::SignOutputCreated) => {
+                bcs::from_bytes::<SignOutputCreatedEvent>(&self.stored.bcs)
+                    .map_err(bcs_error)?
+                    .session_id
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(SuiAddress::from(session_id.bytes)))
+    }
+}
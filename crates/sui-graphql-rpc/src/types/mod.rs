@@ -15,6 +15,9 @@ pub(crate) mod cursor;
 pub(crate) mod date_time;
 pub(crate) mod digest;
 pub(crate) mod display;
+pub(crate) mod dwallet;
+pub(crate) mod dwallet_binding;
+pub(crate) mod dwallet_event;
 pub(crate) mod dynamic_field;
 pub(crate) mod epoch;
 pub(crate) mod event;
@@ -36,6 +39,7 @@ pub(crate) mod owner;
 pub(crate) mod protocol_config;
 pub(crate) mod query;
 pub(crate) mod safe_mode;
+pub(crate) mod sign_session;
 pub(crate) mod stake;
 pub(crate) mod stake_subsidy;
 pub(crate) mod storage_fund;
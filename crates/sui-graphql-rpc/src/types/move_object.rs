@@ -3,6 +3,7 @@
 
 use super::coin::CoinDowncastError;
 use super::coin_metadata::{CoinMetadata, CoinMetadataDowncastError};
+use super::dwallet_binding::{DWalletBinding, DWalletBindingDowncastError};
 use super::move_type::MoveType;
 use super::move_value::MoveValue;
 use super::stake::StakedSuiDowncastError;
@@ -79,6 +80,17 @@ impl MoveObject {
         }
     }
 
+    /// Attempts to convert the Move object into a `0x3::dwallet_binding::DWalletBinding`.
+    async fn as_dwallet_binding(&self) -> Result<Option<DWalletBinding>, Error> {
+        match DWalletBinding::try_from(self) {
+            Ok(binding) => Ok(Some(binding)),
+            Err(DWalletBindingDowncastError::NotADWalletBinding) => Ok(None),
+            Err(DWalletBindingDowncastError::Bcs(e)) => Err(Error::Internal(format!(
+                "Failed to deserialize dWallet binding: {e}"
+            ))),
+        }
+    }
+
     /// Attempts to convert the Move object into a `0x2::coin::CoinMetadata`.
     async fn as_coin_metadata(&self) -> Result<Option<CoinMetadata>, Error> {
         match CoinMetadata::try_from(self) {
@@ -16,22 +16,24 @@ use super::{
     coin_metadata::CoinMetadata,
     cursor::Page,
     digest::Digest,
+    dwallet::DWallet,
     epoch::Epoch,
     event::{Event, EventFilter},
     move_type::MoveType,
     object::{Object, ObjectFilter},
     owner::{ObjectOwner, Owner},
     protocol_config::ProtocolConfigs,
+    sign_session::SignSession,
     sui_address::SuiAddress,
     transaction_block::{TransactionBlock, TransactionBlockFilter},
 };
 use crate::{
     config::ServiceConfig, context_data::db_data_provider::PgManager, error::Error,
-    mutation::Mutation,
+    mutation::Mutation, subscription::Subscription,
 };
 
 pub(crate) struct Query;
-pub(crate) type SuiGraphQLSchema = async_graphql::Schema<Query, Mutation, EmptySubscription>;
+pub(crate) type SuiGraphQLSchema = async_graphql::Schema<Query, Mutation, Subscription>;
 
 #[Object]
 impl Query {
@@ -122,6 +124,16 @@ impl Query {
             .extend()
     }
 
+    /// Fetch a dWallet by the ID of its `DWallet` object.
+    async fn dwallet(&self, ctx: &Context<'_>, id: SuiAddress) -> Result<Option<DWallet>> {
+        DWallet::query(ctx.data_unchecked(), id).await.extend()
+    }
+
+    /// Fetch a sign session by the ID of its `SignSession` object.
+    async fn sign_session(&self, ctx: &Context<'_>, id: SuiAddress) -> Result<Option<SignSession>> {
+        SignSession::query(ctx.data_unchecked(), id).await.extend()
+    }
+
     /// The coin objects that exist in the network.
     ///
     /// The type field is a string of the inner type of the coin by which to filter
@@ -0,0 +1,250 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use async_graphql::{
+    connection::{Connection, CursorType, Edge},
+    *,
+};
+use diesel::{BoolExpressionMethods, ExpressionMethods, QueryDsl};
+use sui_indexer::{
+    models_v2::dwallets::StoredDWalletSignSession, schema_v2::dwallet_sign_sessions,
+};
+
+use super::{
+    address::Address,
+    base64::Base64,
+    checkpoint::{Checkpoint, CheckpointId},
+    cursor::{self, Page, Target},
+    date_time::DateTime,
+    dwallet::{address_of, DWallet},
+    epoch::Epoch,
+    sui_address::SuiAddress,
+};
+use crate::{
+    data::{BoxedQuery, Db, QueryExecutor},
+    error::Error,
+};
+
+/// A request to the network to sign messages with a dWallet, approved by the owner of its
+/// `DWalletCap`.
+#[derive(Clone)]
+pub(crate) struct SignSession {
+    pub stored: StoredDWalletSignSession,
+}
+
+/// The progress of a sign session.
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum SignSessionStatus {
+    /// The network has not signed the messages yet.
+    Pending,
+    /// The network signed the messages.
+    Signed,
+    /// The epoch of the session ended before the network signed the messages.
+    Expired,
+}
+
+/// The cursor of a sign session: the sequence number of the transaction that created it, and its
+/// ID, to order the sessions created in the same transaction.
+pub(crate) type Cursor = cursor::Cursor<(i64, Vec<u8>)>;
+type Query<ST, GB> = BoxedQuery<ST, dwallet_sign_sessions::table, Db, GB>;
+
+#[Object]
+impl SignSession {
+    /// The ID of the `SignSession` object.
+    async fn session_id(&self) -> Result<SuiAddress> {
+        address_of(&self.stored.session_id).extend()
+    }
+
+    /// The dWallet that signs the messages.
+    async fn dwallet(&self, ctx: &Context<'_>) -> Result<Option<DWallet>> {
+        let id = address_of(&self.stored.dwallet_id).extend()?;
+        DWallet::query(ctx.data_unchecked(), id).await.extend()
+    }
+
+    /// The ID of the `DWalletCap` that approved the messages.
+    async fn dwallet_cap_id(&self) -> Result<SuiAddress> {
+        address_of(&self.stored.dwallet_cap_id).extend()
+    }
+
+    /// The address that created the session.
+    async fn sender(&self) -> Result<Address> {
+        Ok(Address {
+            address: address_of(&self.stored.sender).extend()?,
+        })
+    }
+
+    /// The messages to sign.
+    async fn messages(&self) -> Vec<Base64> {
+        self.stored
+            .messages
+            .iter()
+            .flatten()
+            .map(Base64::from)
+            .collect()
+    }
+
+    /// Whether the network signed the messages, or can no longer sign them.
+    async fn status(&self, ctx: &Context<'_>) -> Result<SignSessionStatus> {
+        self.status_impl(ctx.data_unchecked()).await.extend()
+    }
+
+    /// When the session was created.
+    async fn created_at(&self) -> Result<DateTime> {
+        DateTime::from_ms(self.stored.timestamp_ms).extend()
+    }
+
+    /// When the network signed the messages, if it did.
+    async fn completed_at(&self) -> Result<Option<DateTime>> {
+        self.stored
+            .completed_timestamp_ms
+            .map(DateTime::from_ms)
+            .transpose()
+            .extend()
+    }
+
+    /// The ID of the `SignOutput` object holding the signatures, if the network signed the
+    /// messages.
+    async fn sign_output_id(&self) -> Result<Option<SuiAddress>> {
+        self.stored
+            .sign_output_id
+            .as_deref()
+            .map(address_of)
+            .transpose()
+            .extend()
+    }
+
+    /// The signatures on the messages, in their order, if the network signed them.
+    async fn signatures(&self) -> Option<Vec<Base64>> {
+        let signatures = self.stored.signatures.as_ref()?;
+        Some(signatures.iter().flatten().map(Base64::from).collect())
+    }
+
+    /// The epoch the session was created in, whose committee signs the messages.
+    async fn epoch(&self, ctx: &Context<'_>) -> Result<Option<Epoch>> {
+        let db: &Db = ctx.data_unchecked();
+        let Some(epoch) = self.epoch_impl(db).await.extend()? else {
+            return Ok(None);
+        };
+
+        Epoch::query(db, Some(epoch)).await.extend()
+    }
+}
+
+impl SignSession {
+    /// Look up the sign session `id` in the database.
+    pub(crate) async fn query(db: &Db, id: SuiAddress) -> Result<Option<Self>, Error> {
+        use dwallet_sign_sessions::dsl;
+
+        let id = id.into_vec();
+        let stored = db
+            .optional(move || {
+                dsl::dwallet_sign_sessions
+                    .filter(dsl::session_id.eq(id.clone()))
+                    .limit(1)
+                    .into_boxed()
+            })
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to fetch sign session: {e}")))?;
+
+        Ok(stored.map(|stored| SignSession { stored }))
+    }
+
+    /// Query the database for a `page` of sign sessions, in the order they were created, which can
+    /// optionally be further `filter`-ed by the ID of the dWallet signing them.
+    pub(crate) async fn paginate(
+        db: &Db,
+        page: Page<(i64, Vec<u8>)>,
+        filter: Option<Vec<u8>>,
+    ) -> Result<Connection<String, SignSession>, Error> {
+        use dwallet_sign_sessions::dsl;
+
+        let (prev, next, results) = page
+            .paginate_query::<StoredDWalletSignSession, _, _, _>(db, move || {
+                let mut query = dsl::dwallet_sign_sessions.into_boxed();
+                if let Some(dwallet_id) = filter.clone() {
+                    query = query.filter(dsl::dwallet_id.eq(dwallet_id));
+                }
+
+                query
+            })
+            .await?;
+
+        let mut conn = Connection::new(prev, next);
+        for stored in results {
+            let cursor = Cursor::new(stored.cursor()).encode_cursor();
+            conn.edges.push(Edge::new(cursor, SignSession { stored }));
+        }
+
+        Ok(conn)
+    }
+
+    /// The epoch of the checkpoint that created the session, if it is indexed.
+    async fn epoch_impl(&self, db: &Db) -> Result<Option<u64>, Error> {
+        let checkpoint = CheckpointId::by_seq_num(self.stored.checkpoint_sequence_number as u64);
+        Ok(Checkpoint::query(db, checkpoint)
+            .await?
+            .map(|checkpoint| checkpoint.stored.epoch as u64))
+    }
+
+    /// Sign sessions do not outlive the epoch they were created in, so a session without a sign
+    /// output from an epoch before the latest one has expired.
+    pub(crate) async fn status_impl(&self, db: &Db) -> Result<SignSessionStatus, Error> {
+        if self.stored.sign_output_id.is_some() {
+            return Ok(SignSessionStatus::Signed);
+        }
+
+        let Some(epoch) = self.epoch_impl(db).await? else {
+            return Ok(SignSessionStatus::Pending);
+        };
+
+        let latest = Checkpoint::query(db, CheckpointId::default())
+            .await?
+            .map(|checkpoint| checkpoint.stored.epoch as u64);
+
+        Ok(match latest {
+            Some(latest) if epoch < latest => SignSessionStatus::Expired,
+            _ => SignSessionStatus::Pending,
+        })
+    }
+}
+
+impl Target<(i64, Vec<u8>)> for StoredDWalletSignSession {
+    type Source = dwallet_sign_sessions::table;
+
+    fn filter_ge<ST, GB>(cursor: &(i64, Vec<u8>), query: Query<ST, GB>) -> Query<ST, GB> {
+        use dwallet_sign_sessions::dsl;
+        let (tx, id) = cursor.clone();
+        query.filter(
+            dsl::tx_sequence_number
+                .gt(tx)
+                .or(dsl::tx_sequence_number.eq(tx).and(dsl::session_id.ge(id))),
+        )
+    }
+
+    fn filter_le<ST, GB>(cursor: &(i64, Vec<u8>), query: Query<ST, GB>) -> Query<ST, GB> {
+        use dwallet_sign_sessions::dsl;
+        let (tx, id) = cursor.clone();
+        query.filter(
+            dsl::tx_sequence_number
+                .lt(tx)
+                .or(dsl::tx_sequence_number.eq(tx).and(dsl::session_id.le(id))),
+        )
+    }
+
+    fn order<ST, GB>(asc: bool, query: Query<ST, GB>) -> Query<ST, GB> {
+        use dwallet_sign_sessions::dsl;
+        if asc {
+            query
+                .order(dsl::tx_sequence_number)
+                .then_order_by(dsl::session_id)
+        } else {
+            query
+                .order(dsl::tx_sequence_number.desc())
+                .then_order_by(dsl::session_id.desc())
+        }
+    }
+
+    fn cursor(&self) -> (i64, Vec<u8>) {
+        (self.tx_sequence_number, self.session_id.clone())
+    }
+}
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS dwallet_events;
+DROP TABLE IF EXISTS dwallet_sign_sessions;
+DROP TABLE IF EXISTS dwallet_presigns;
+DROP TABLE IF EXISTS dwallets;
@@ -0,0 +1,74 @@
+CREATE TABLE dwallets
+(
+    dwallet_id                  BYTEA        PRIMARY KEY,
+    dwallet_cap_id              BYTEA        NOT NULL,
+    -- the address that created the dWallet
+    sender                      BYTEA        NOT NULL,
+    -- the compressed SEC1 public key of the dWallet
+    public_key                  BYTEA        NOT NULL,
+    -- the public output of the DKG that created the dWallet
+    dkg_output                  BYTEA        NOT NULL,
+    tx_sequence_number          BIGINT       NOT NULL,
+    checkpoint_sequence_number  BIGINT       NOT NULL,
+    timestamp_ms                BIGINT       NOT NULL
+);
+
+CREATE INDEX dwallets_dwallet_cap_id ON dwallets (dwallet_cap_id);
+CREATE INDEX dwallets_sender ON dwallets (sender, tx_sequence_number);
+
+CREATE TABLE dwallet_presigns
+(
+    presign_id                  BYTEA        PRIMARY KEY,
+    -- the presign session that created the presign
+    session_id                  BYTEA        NOT NULL,
+    dwallet_id                  BYTEA        NOT NULL,
+    dwallet_cap_id              BYTEA        NOT NULL,
+    -- the address that started the presign session, which owns the presign
+    sender                      BYTEA        NOT NULL,
+    tx_sequence_number          BIGINT       NOT NULL,
+    checkpoint_sequence_number  BIGINT       NOT NULL,
+    timestamp_ms                BIGINT       NOT NULL
+);
+
+CREATE INDEX dwallet_presigns_dwallet_id ON dwallet_presigns (dwallet_id, tx_sequence_number);
+
+CREATE TABLE dwallet_sign_sessions
+(
+    session_id                  BYTEA        PRIMARY KEY,
+    dwallet_id                  BYTEA        NOT NULL,
+    dwallet_cap_id              BYTEA        NOT NULL,
+    sender                      BYTEA        NOT NULL,
+    -- the messages to sign
+    messages                    BYTEA[]      NOT NULL,
+    tx_sequence_number          BIGINT       NOT NULL,
+    checkpoint_sequence_number  BIGINT       NOT NULL,
+    timestamp_ms                BIGINT       NOT NULL,
+    -- the columns below are set once the network signed the messages
+    sign_output_id              BYTEA,
+    -- one 64-byte `r || s` signature per message, in order
+    signatures                  BYTEA[],
+    completed_tx_sequence_number BIGINT,
+    completed_timestamp_ms      BIGINT
+);
+
+CREATE INDEX dwallet_sign_sessions_dwallet_id ON dwallet_sign_sessions (dwallet_id, tx_sequence_number);
+CREATE INDEX dwallet_sign_sessions_sender ON dwallet_sign_sessions (sender, tx_sequence_number);
+
+CREATE TABLE dwallet_events
+(
+    tx_sequence_number          BIGINT       NOT NULL,
+    event_sequence_number       BIGINT       NOT NULL,
+    transaction_digest          BYTEA        NOT NULL,
+    checkpoint_sequence_number  BIGINT       NOT NULL,
+    dwallet_id                  BYTEA        NOT NULL,
+    -- the name of the event struct, e.g. `DWalletCreated` or `SignOutputCreated`
+    event_type                  TEXT         NOT NULL,
+    sender                      BYTEA        NOT NULL,
+    timestamp_ms                BIGINT       NOT NULL,
+    -- bcs of the Event contents (Event.contents)
+    bcs                         BYTEA        NOT NULL,
+    PRIMARY KEY(tx_sequence_number, event_sequence_number)
+);
+
+CREATE INDEX dwallet_events_dwallet_id ON dwallet_events (dwallet_id, tx_sequence_number, event_sequence_number);
+CREATE INDEX dwallet_events_event_type ON dwallet_events (event_type, tx_sequence_number, event_sequence_number);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS epoch_validator_signing_metrics;
+DROP TABLE IF EXISTS epoch_signing_metrics;
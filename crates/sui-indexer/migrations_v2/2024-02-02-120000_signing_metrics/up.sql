@@ -0,0 +1,29 @@
+-- the signing health of an epoch, aggregated once the epoch ended
+CREATE TABLE epoch_signing_metrics
+(
+    epoch                       BIGINT       PRIMARY KEY,
+    sign_sessions_completed     BIGINT       NOT NULL,
+    -- the sign sessions of the epoch that the network did not sign before the epoch ended
+    sign_sessions_failed        BIGINT       NOT NULL,
+    -- percentiles of the time from the creation of a sign session to its sign output,
+    -- NULL if no sign session of the epoch completed
+    latency_p50_ms              BIGINT,
+    latency_p90_ms              BIGINT,
+    latency_p99_ms              BIGINT,
+    presigns_created            BIGINT       NOT NULL,
+    -- each sign session consumes one presign
+    presigns_consumed           BIGINT       NOT NULL
+);
+
+CREATE TABLE epoch_validator_signing_metrics
+(
+    epoch                       BIGINT       NOT NULL,
+    -- the sui address of the validator
+    validator_address           BYTEA        NOT NULL,
+    name                        TEXT         NOT NULL,
+    -- the sign sessions of the epoch the validator was the aggregator of
+    aggregated_sessions         BIGINT       NOT NULL,
+    -- of which the validator submitted the signatures
+    completed_sessions          BIGINT       NOT NULL,
+    PRIMARY KEY(epoch, validator_address)
+);
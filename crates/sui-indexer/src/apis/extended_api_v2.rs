@@ -7,7 +7,8 @@ use sui_json_rpc::SuiRpcModule;
 use sui_json_rpc_api::{validate_limit, ExtendedApiServer, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS};
 use sui_json_rpc_types::{
     AddressMetrics, CheckpointedObjectID, EpochInfo, EpochMetrics, EpochMetricsPage, EpochPage,
-    MoveCallMetrics, NetworkMetrics, Page, QueryObjectsPage, SuiObjectResponseQuery,
+    EpochSigningMetricsPage, MoveCallMetrics, NetworkMetrics, Page, QueryObjectsPage,
+    SuiObjectResponseQuery,
 };
 use sui_open_rpc::Module;
 use sui_types::sui_serde::BigInt;
@@ -154,6 +155,34 @@ impl ExtendedApiServer for ExtendedApiV2 {
         Ok(all_epoch_address_metrics)
     }
 
+    async fn get_signing_metrics(
+        &self,
+        cursor: Option<BigInt<u64>>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<EpochSigningMetricsPage> {
+        let limit = validate_limit(limit, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS)?;
+        let mut signing_metrics = self
+            .inner
+            .spawn_blocking(move |this| {
+                this.get_epoch_signing_metrics(
+                    cursor.map(|x| *x),
+                    limit + 1,
+                    descending_order.unwrap_or(false),
+                )
+            })
+            .await?;
+
+        let has_next_page = signing_metrics.len() > limit;
+        signing_metrics.truncate(limit);
+        let next_cursor = signing_metrics.last().map(|m| m.epoch);
+        Ok(Page {
+            data: signing_metrics,
+            next_cursor: next_cursor.map(|id| id.into()),
+            has_next_page,
+        })
+    }
+
     async fn get_total_transactions(&self) -> RpcResult<BigInt<u64>> {
         let latest_checkpoint = self
             .inner
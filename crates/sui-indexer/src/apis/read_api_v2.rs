@@ -55,7 +55,7 @@ impl ReadApiV2 {
             .await
     }
 
-    async fn get_chain_identifier(&self) -> RpcResult<ChainIdentifier> {
+    pub(crate) async fn get_chain_identifier(&self) -> RpcResult<ChainIdentifier> {
         let genesis_checkpoint = self.get_checkpoint(CheckpointId::SequenceNumber(0)).await?;
         Ok(ChainIdentifier::from(genesis_checkpoint.digest))
     }
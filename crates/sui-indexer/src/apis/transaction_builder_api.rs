@@ -11,7 +11,8 @@ use sui_json::SuiJsonValue;
 use sui_json_rpc::SuiRpcModule;
 use sui_json_rpc_api::{TransactionBuilderClient, TransactionBuilderServer};
 use sui_json_rpc_types::{
-    RPCTransactionRequestParams, SuiTransactionBlockBuilderMode, SuiTypeTag, TransactionBlockBytes,
+    RPCTransactionRequestParams, SignBatch, SignRequestParams, SuiTransactionBlockBuilderMode,
+    SuiTypeTag, TransactionBlockBytes,
 };
 use sui_open_rpc::Module;
 use sui_types::base_types::{ObjectID, SuiAddress};
@@ -213,6 +214,15 @@ impl TransactionBuilderServer for TransactionBuilderApi {
             .request_withdraw_stake(signer, staked_sui, gas, gas_budget)
             .await
     }
+
+    async fn batch_sign(
+        &self,
+        signer: SuiAddress,
+        requests: Vec<SignRequestParams>,
+        gas_budget: BigInt<u64>,
+    ) -> RpcResult<SignBatch> {
+        self.fullnode.batch_sign(signer, requests, gas_budget).await
+    }
 }
 
 impl SuiRpcModule for TransactionBuilderApi {
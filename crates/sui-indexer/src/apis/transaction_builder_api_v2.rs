@@ -2,11 +2,13 @@
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
 use super::governance_api_v2::GovernanceReadApiV2;
+use super::read_api_v2::ReadApiV2;
 use crate::indexer_reader::IndexerReader;
 use async_trait::async_trait;
 use move_core_types::language_storage::StructTag;
 use sui_json_rpc::transaction_builder_api::TransactionBuilderApi;
 use sui_json_rpc_types::{SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse};
+use sui_protocol_config::ProtocolConfig;
 use sui_transaction_builder::DataReader;
 use sui_types::base_types::{ObjectID, ObjectInfo, SuiAddress};
 use sui_types::object::Object;
@@ -70,4 +72,22 @@ impl DataReader for TransactionBuilderApiV2 {
             .reference_gas_price
             .ok_or_else(|| anyhow::anyhow!("missing latest reference_gas_price"))?)
     }
+
+    async fn get_protocol_config(&self) -> Result<ProtocolConfig, anyhow::Error> {
+        let chain = ReadApiV2::new(self.inner.clone())
+            .get_chain_identifier()
+            .await?
+            .chain();
+        let latest_epoch = self
+            .inner
+            .spawn_blocking(|this| this.get_latest_epoch_info_from_db())
+            .await?;
+        let version = (latest_epoch.protocol_version as u64).into();
+        ProtocolConfig::get_for_version_if_supported(version, chain).ok_or_else(|| {
+            anyhow::anyhow!(
+                "unsupported protocol version {}",
+                latest_epoch.protocol_version
+            )
+        })
+    }
 }
@@ -28,6 +28,7 @@ use sui_types::base_types::SequenceNumber;
 use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
 use sui_types::event::SystemEpochInfoEvent;
 use sui_types::object::Owner;
+use sui_types::signature_mpc::{DWallet, DWalletEvent};
 use sui_types::transaction::TransactionDataAPI;
 use tap::tap::TapFallible;
 use tracing::{error, info, warn};
@@ -46,6 +47,7 @@ use crate::types_v2::IndexedEpochInfo;
 use crate::types_v2::{
     IndexedCheckpoint, IndexedEvent, IndexedTransaction, IndexerResult, TransactionKind, TxIndex,
 };
+use crate::types_v2::{IndexedDWallet, IndexedDWalletEvent};
 use crate::types_v2::{IndexedDeletedObject, IndexedObject, IndexedPackage};
 use crate::IndexerConfig;
 
@@ -312,6 +314,9 @@ where
         let object_history_changes: TransactionObjectChangesToCommit =
             Self::index_objects_history(data.clone(), &module_resolver);
 
+        // Index dWallets
+        let (dwallets, dwallet_events) = Self::index_dwallets(&data)?;
+
         let (checkpoint, db_transactions, db_events, db_indices, db_displays) = {
             let CheckpointData {
                 transactions,
@@ -350,6 +355,8 @@ where
             object_changes,
             object_history_changes,
             packages,
+            dwallets,
+            dwallet_events,
             epoch,
         })
     }
@@ -515,6 +522,68 @@ where
         Ok((db_transactions, db_events, db_indices, db_displays))
     }
 
+    /// Index the dWallet events of the checkpoint, and the dWallets created by its transactions.
+    fn index_dwallets(
+        data: &CheckpointData,
+    ) -> IndexerResult<(Vec<IndexedDWallet>, Vec<IndexedDWalletEvent>)> {
+        let CheckpointData {
+            transactions,
+            checkpoint_summary,
+            checkpoint_contents,
+        } = data;
+
+        let mut dwallets = Vec::new();
+        let mut dwallet_events = Vec::new();
+        let tx_sequence_numbers = checkpoint_contents
+            .enumerate_transactions(checkpoint_summary)
+            .map(|(seq, _)| seq);
+        for (tx, tx_sequence_number) in transactions.iter().zip(tx_sequence_numbers) {
+            let Some(events) = &tx.events else {
+                continue;
+            };
+            let tx_digest = *tx.transaction.digest();
+            for (idx, event) in events.data.iter().enumerate() {
+                let Some(dwallet_event) = IndexedDWalletEvent::try_from_event(
+                    tx_sequence_number,
+                    idx as u64,
+                    checkpoint_summary.sequence_number,
+                    tx_digest,
+                    event,
+                    checkpoint_summary.timestamp_ms,
+                )?
+                else {
+                    continue;
+                };
+                if let DWalletEvent::DWalletCreated(created) = &dwallet_event.event {
+                    let dwallet_id = created.dwallet_id.bytes;
+                    let dwallet = tx
+                        .output_objects
+                        .iter()
+                        .find(|o| o.id() == dwallet_id)
+                        .and_then(|o| o.data.try_as_move())
+                        .ok_or_else(|| {
+                            IndexerError::FullNodeReadingError(format!(
+                                "DWallet {dwallet_id} not found in the output objects of its creating transaction {tx_digest}"
+                            ))
+                        })?;
+                    let dwallet = DWallet::from_bcs_bytes(dwallet.contents())?;
+                    dwallets.push(IndexedDWallet {
+                        dwallet_id,
+                        dwallet_cap_id: created.dwallet_cap_id.bytes,
+                        sender: created.sender,
+                        public_key: dwallet.public_key,
+                        dkg_output: dwallet.output,
+                        tx_sequence_number,
+                        checkpoint_sequence_number: checkpoint_summary.sequence_number,
+                        timestamp_ms: checkpoint_summary.timestamp_ms,
+                    });
+                }
+                dwallet_events.push(dwallet_event);
+            }
+        }
+        Ok((dwallets, dwallet_events))
+    }
+
     fn index_objects(
         data: CheckpointData,
         metrics: &IndexerMetrics,
@@ -105,6 +105,8 @@ async fn commit_checkpoints<S>(
     let mut object_changes_batch = vec![];
     let mut object_history_changes_batch = vec![];
     let mut packages_batch = vec![];
+    let mut dwallets_batch = vec![];
+    let mut dwallet_events_batch = vec![];
 
     for indexed_checkpoint in indexed_checkpoint_batch {
         let CheckpointDataToCommit {
@@ -116,6 +118,8 @@ async fn commit_checkpoints<S>(
             object_changes,
             object_history_changes,
             packages,
+            dwallets,
+            dwallet_events,
             epoch: _,
         } = indexed_checkpoint;
         checkpoint_batch.push(checkpoint);
@@ -126,6 +130,8 @@ async fn commit_checkpoints<S>(
         object_changes_batch.push(object_changes);
         object_history_changes_batch.push(object_history_changes);
         packages_batch.push(packages);
+        dwallets_batch.push(dwallets);
+        dwallet_events_batch.push(dwallet_events);
     }
 
     let first_checkpoint_seq = checkpoint_batch.first().as_ref().unwrap().sequence_number;
@@ -136,6 +142,11 @@ async fn commit_checkpoints<S>(
     let tx_indices_batch = tx_indices_batch.into_iter().flatten().collect::<Vec<_>>();
     let events_batch = events_batch.into_iter().flatten().collect::<Vec<_>>();
     let packages_batch = packages_batch.into_iter().flatten().collect::<Vec<_>>();
+    let dwallets_batch = dwallets_batch.into_iter().flatten().collect::<Vec<_>>();
+    let dwallet_events_batch = dwallet_events_batch
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
     let checkpoint_num = checkpoint_batch.len();
     let tx_count = tx_batch.len();
 
@@ -147,6 +158,7 @@ async fn commit_checkpoints<S>(
             state.persist_events(events_batch),
             state.persist_displays(display_updates_batch),
             state.persist_packages(packages_batch),
+            state.persist_dwallet_data(dwallets_batch, dwallet_events_batch),
             state.persist_objects(object_changes_batch.clone()),
             state.persist_object_history(object_history_changes_batch.clone()),
             state.persist_object_snapshot(),
@@ -11,8 +11,8 @@ use std::collections::BTreeMap;
 use crate::{
     models_v2::display::StoredDisplay,
     types_v2::{
-        IndexedCheckpoint, IndexedDeletedObject, IndexedEpochInfo, IndexedEvent, IndexedObject,
-        IndexedPackage, IndexedTransaction, TxIndex,
+        IndexedCheckpoint, IndexedDWallet, IndexedDWalletEvent, IndexedDeletedObject,
+        IndexedEpochInfo, IndexedEvent, IndexedObject, IndexedPackage, IndexedTransaction, TxIndex,
     },
 };
 
@@ -26,6 +26,8 @@ pub struct CheckpointDataToCommit {
     pub object_changes: TransactionObjectChangesToCommit,
     pub object_history_changes: TransactionObjectChangesToCommit,
     pub packages: Vec<IndexedPackage>,
+    pub dwallets: Vec<IndexedDWallet>,
+    pub dwallet_events: Vec<IndexedDWalletEvent>,
     pub epoch: Option<EpochToCommit>,
 }
 
@@ -13,11 +13,13 @@ use crate::{
         network_metrics::StoredNetworkMetrics,
         objects::{CoinBalance, ObjectRefColumn, StoredObject},
         packages::StoredPackage,
+        signing_metrics::{StoredEpochSigningMetrics, StoredValidatorSigningMetrics},
         transactions::StoredTransaction,
         tx_indices::TxSequenceNumber,
     },
     schema_v2::{
-        address_metrics, checkpoints, display, epochs, events, move_call_metrics, objects,
+        address_metrics, checkpoints, display, epoch_signing_metrics,
+        epoch_validator_signing_metrics, epochs, events, move_call_metrics, objects,
         objects_snapshot, packages, transactions,
     },
     types_v2::{IndexerResult, OwnerType},
@@ -41,8 +43,9 @@ use std::{
 };
 use sui_json_rpc_types::DisplayFieldsResponse;
 use sui_json_rpc_types::{
-    AddressMetrics, CheckpointId, EpochInfo, EventFilter, MoveCallMetrics, MoveFunctionName,
-    NetworkMetrics, SuiEvent, SuiObjectDataFilter, SuiTransactionBlockResponse, TransactionFilter,
+    AddressMetrics, CheckpointId, EpochInfo, EpochSigningMetrics, EventFilter, MoveCallMetrics,
+    MoveFunctionName, NetworkMetrics, SuiEvent, SuiObjectDataFilter, SuiTransactionBlockResponse,
+    TransactionFilter,
 };
 use sui_json_rpc_types::{
     Balance, Coin as SuiCoin, SuiCoinMetadata, SuiTransactionBlockEffects,
@@ -1154,6 +1157,9 @@ impl IndexerReader {
                 EventFilter::MoveEventType(struct_tag) => {
                     format!("event_type = '{}'", struct_tag)
                 }
+                EventFilter::DWalletEventKind(kind) => {
+                    format!("event_type = '{}'", kind.event_type())
+                }
                 EventFilter::MoveEventModule { package, module } => {
                     let package_module_prefix = format!("{}::{}", package.to_hex_literal(), module);
                     format!("event_type LIKE '{package_module_prefix}::%'")
@@ -1166,6 +1172,11 @@ impl IndexerReader {
                     // Processed above
                     unreachable!()
                 }
+                EventFilter::DWallet(_) | EventFilter::DWalletSession(_) => {
+                    return Err(IndexerError::NotSupportedError(
+                        "Querying events by dWallet or session is not supported, subscribe to them or query them by DWalletEventKind instead.".into(),
+                    ));
+                }
                 EventFilter::MoveEventField { .. }
                 | EventFilter::All(_)
                 | EventFilter::Any(_)
@@ -1587,6 +1598,60 @@ impl IndexerReader {
             .collect())
     }
 
+    pub fn get_epoch_signing_metrics(
+        &self,
+        cursor: Option<u64>,
+        limit: usize,
+        descending_order: bool,
+    ) -> IndexerResult<Vec<EpochSigningMetrics>> {
+        let (signing_metrics, validator_signing_metrics) = self.run_query(|conn| {
+            let mut boxed_query = epoch_signing_metrics::table.into_boxed();
+            if let Some(cursor) = cursor {
+                if descending_order {
+                    boxed_query =
+                        boxed_query.filter(epoch_signing_metrics::epoch.lt(cursor as i64));
+                } else {
+                    boxed_query =
+                        boxed_query.filter(epoch_signing_metrics::epoch.gt(cursor as i64));
+                }
+            }
+            if descending_order {
+                boxed_query = boxed_query.order_by(epoch_signing_metrics::epoch.desc());
+            } else {
+                boxed_query = boxed_query.order_by(epoch_signing_metrics::epoch.asc());
+            }
+            let signing_metrics = boxed_query
+                .limit(limit as i64)
+                .load::<StoredEpochSigningMetrics>(conn)?;
+
+            let epochs: Vec<_> = signing_metrics.iter().map(|m| m.epoch).collect();
+            let validator_signing_metrics = epoch_validator_signing_metrics::table
+                .filter(epoch_validator_signing_metrics::epoch.eq_any(epochs))
+                .order_by((
+                    epoch_validator_signing_metrics::epoch,
+                    epoch_validator_signing_metrics::validator_address,
+                ))
+                .load::<StoredValidatorSigningMetrics>(conn)?;
+            Ok::<_, diesel::result::Error>((signing_metrics, validator_signing_metrics))
+        })?;
+
+        let mut validators_by_epoch: HashMap<i64, Vec<StoredValidatorSigningMetrics>> =
+            HashMap::new();
+        for validator in validator_signing_metrics {
+            validators_by_epoch
+                .entry(validator.epoch)
+                .or_default()
+                .push(validator);
+        }
+        signing_metrics
+            .into_iter()
+            .map(|m| {
+                let validators = validators_by_epoch.remove(&m.epoch).unwrap_or_default();
+                m.into_epoch_signing_metrics(validators)
+            })
+            .collect()
+    }
+
     pub(crate) async fn get_display_fields(
         &self,
         original_object: &sui_types::object::Object,
@@ -35,6 +35,7 @@ pub struct IndexerMetrics {
     pub latest_move_call_metrics_tx_seq: IntGauge,
     pub latest_address_metrics_tx_seq: IntGauge,
     pub latest_network_metrics_cp_seq: IntGauge,
+    pub latest_signing_metrics_epoch: IntGauge,
     // checkpoint E2E latency is:
     // fullnode_download_latency + checkpoint_index_latency + db_commit_latency
     pub fullnode_checkpoint_data_download_latency: Histogram,
@@ -61,6 +62,7 @@ pub struct IndexerMetrics {
     pub checkpoint_db_commit_latency_events: Histogram,
     pub checkpoint_db_commit_latency_events_chunks: Histogram,
     pub checkpoint_db_commit_latency_packages: Histogram,
+    pub checkpoint_db_commit_latency_dwallets: Histogram,
     pub checkpoint_db_commit_latency_tx_indices: Histogram,
     pub checkpoint_db_commit_latency_tx_indices_chunks: Histogram,
     pub checkpoint_db_commit_latency_checkpoints: Histogram,
@@ -191,6 +193,11 @@ impl IndexerMetrics {
                 "Latest network metrics cp seq",
                 registry,
             ).unwrap(),
+            latest_signing_metrics_epoch: register_int_gauge_with_registry!(
+                "latest_signing_metrics_epoch",
+                "Latest epoch whose signing metrics were persisted",
+                registry,
+            ).unwrap(),
             fullnode_checkpoint_data_download_latency: register_histogram_with_registry!(
                 "fullnode_checkpoint_data_download_latency",
                 "Time spent in downloading checkpoint and transation for a new checkpoint from the Full Node",
@@ -357,6 +364,13 @@ impl IndexerMetrics {
                 registry,
             )
             .unwrap(),
+            checkpoint_db_commit_latency_dwallets: register_histogram_with_registry!(
+                "checkpoint_db_commit_latency_dwallets",
+                "Time spent commiting dWallets, dWallet events, presigns and sign sessions",
+                DB_COMMIT_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
             checkpoint_db_commit_latency_tx_indices: register_histogram_with_registry!(
                 "checkpoint_db_commit_latency_tx_indices",
                 "Time spent commiting tx indices",
@@ -0,0 +1,213 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use diesel::prelude::*;
+
+use sui_types::signature_mpc::{
+    PresignCreatedEvent, SignOutputCreatedEvent, SignSessionCreatedEvent,
+};
+
+use crate::schema_v2::{dwallet_events, dwallet_presigns, dwallet_sign_sessions, dwallets};
+use crate::types_v2::{IndexedDWallet, IndexedDWalletEvent};
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = dwallets)]
+pub struct StoredDWallet {
+    pub dwallet_id: Vec<u8>,
+    pub dwallet_cap_id: Vec<u8>,
+    pub sender: Vec<u8>,
+    pub public_key: Vec<u8>,
+    pub dkg_output: Vec<u8>,
+    pub tx_sequence_number: i64,
+    pub checkpoint_sequence_number: i64,
+    pub timestamp_ms: i64,
+}
+
+impl From<IndexedDWallet> for StoredDWallet {
+    fn from(dwallet: IndexedDWallet) -> Self {
+        Self {
+            dwallet_id: dwallet.dwallet_id.to_vec(),
+            dwallet_cap_id: dwallet.dwallet_cap_id.to_vec(),
+            sender: dwallet.sender.to_vec(),
+            public_key: dwallet.public_key,
+            dkg_output: dwallet.dkg_output,
+            tx_sequence_number: dwallet.tx_sequence_number as i64,
+            checkpoint_sequence_number: dwallet.checkpoint_sequence_number as i64,
+            timestamp_ms: dwallet.timestamp_ms as i64,
+        }
+    }
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = dwallet_events)]
+pub struct StoredDWalletEvent {
+    pub tx_sequence_number: i64,
+    pub event_sequence_number: i64,
+    pub transaction_digest: Vec<u8>,
+    pub checkpoint_sequence_number: i64,
+    pub dwallet_id: Vec<u8>,
+    pub event_type: String,
+    pub sender: Vec<u8>,
+    pub timestamp_ms: i64,
+    pub bcs: Vec<u8>,
+}
+
+impl From<IndexedDWalletEvent> for StoredDWalletEvent {
+    fn from(event: IndexedDWalletEvent) -> Self {
+        Self {
+            tx_sequence_number: event.tx_sequence_number as i64,
+            event_sequence_number: event.event_sequence_number as i64,
+            transaction_digest: event.transaction_digest.into_inner().to_vec(),
+            checkpoint_sequence_number: event.checkpoint_sequence_number as i64,
+            dwallet_id: event.event.dwallet_id().to_vec(),
+            event_type: event.event_type,
+            sender: event.event.sender().to_vec(),
+            timestamp_ms: event.timestamp_ms as i64,
+            bcs: event.bcs,
+        }
+    }
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = dwallet_presigns)]
+pub struct StoredDWalletPresign {
+    pub presign_id: Vec<u8>,
+    pub session_id: Vec<u8>,
+    pub dwallet_id: Vec<u8>,
+    pub dwallet_cap_id: Vec<u8>,
+    pub sender: Vec<u8>,
+    pub tx_sequence_number: i64,
+    pub checkpoint_sequence_number: i64,
+    pub timestamp_ms: i64,
+}
+
+impl StoredDWalletPresign {
+    pub fn new(event: &IndexedDWalletEvent, created: &PresignCreatedEvent) -> Self {
+        Self {
+            presign_id: created.presign_id.bytes.to_vec(),
+            session_id: created.session_id.bytes.to_vec(),
+            dwallet_id: created.dwallet_id.bytes.to_vec(),
+            dwallet_cap_id: created.dwallet_cap_id.bytes.to_vec(),
+            sender: created.sender.to_vec(),
+            tx_sequence_number: event.tx_sequence_number as i64,
+            checkpoint_sequence_number: event.checkpoint_sequence_number as i64,
+            timestamp_ms: event.timestamp_ms as i64,
+        }
+    }
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = dwallet_sign_sessions)]
+pub struct StoredDWalletSignSession {
+    pub session_id: Vec<u8>,
+    pub dwallet_id: Vec<u8>,
+    pub dwallet_cap_id: Vec<u8>,
+    pub sender: Vec<u8>,
+    pub messages: Vec<Option<Vec<u8>>>,
+    pub tx_sequence_number: i64,
+    pub checkpoint_sequence_number: i64,
+    pub timestamp_ms: i64,
+    pub sign_output_id: Option<Vec<u8>>,
+    pub signatures: Option<Vec<Option<Vec<u8>>>>,
+    pub completed_tx_sequence_number: Option<i64>,
+    pub completed_timestamp_ms: Option<i64>,
+}
+
+impl StoredDWalletSignSession {
+    /// The session created by `event`, which the network has not signed yet.
+    pub fn new(event: &IndexedDWalletEvent, created: &SignSessionCreatedEvent) -> Self {
+        Self {
+            session_id: created.session_id.bytes.to_vec(),
+            dwallet_id: created.dwallet_id.bytes.to_vec(),
+            dwallet_cap_id: created.dwallet_cap_id.bytes.to_vec(),
+            sender: created.sender.to_vec(),
+            messages: created.messages.iter().cloned().map(Some).collect(),
+            tx_sequence_number: event.tx_sequence_number as i64,
+            checkpoint_sequence_number: event.checkpoint_sequence_number as i64,
+            timestamp_ms: event.timestamp_ms as i64,
+            sign_output_id: None,
+            signatures: None,
+            completed_tx_sequence_number: None,
+            completed_timestamp_ms: None,
+        }
+    }
+}
+
+/// The completion of a sign session, set on its row of `dwallet_sign_sessions`.
+#[derive(Debug, Clone)]
+pub struct StoredDWalletSignOutput {
+    pub session_id: Vec<u8>,
+    pub sign_output_id: Option<Vec<u8>>,
+    pub signatures: Option<Vec<Option<Vec<u8>>>>,
+    pub completed_tx_sequence_number: Option<i64>,
+    pub completed_timestamp_ms: Option<i64>,
+}
+
+impl StoredDWalletSignOutput {
+    pub fn new(event: &IndexedDWalletEvent, output: &SignOutputCreatedEvent) -> Self {
+        Self {
+            session_id: output.session_id.bytes.to_vec(),
+            sign_output_id: Some(output.sign_output_id.bytes.to_vec()),
+            signatures: Some(output.signatures.iter().cloned().map(Some).collect()),
+            completed_tx_sequence_number: Some(event.tx_sequence_number as i64),
+            completed_timestamp_ms: Some(event.timestamp_ms as i64),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::language_storage::StructTag;
+    use sui_types::base_types::{ObjectID, SuiAddress};
+    use sui_types::digests::TransactionDigest;
+    use sui_types::event::Event;
+    use sui_types::id::ID;
+    use sui_types::signature_mpc::{
+        DWalletEvent, DWALLET_MODULE_NAME, SIGN_OUTPUT_CREATED_EVENT_STRUCT_NAME,
+    };
+    use sui_types::SUI_SYSTEM_ADDRESS;
+
+    #[test]
+    fn test_sign_output_event() {
+        let output = SignOutputCreatedEvent {
+            session_id: ID::new(ObjectID::random()),
+            sign_output_id: ID::new(ObjectID::random()),
+            dwallet_id: ID::new(ObjectID::random()),
+            dwallet_cap_id: ID::new(ObjectID::random()),
+            signatures: vec![vec![1; 64], vec![2; 64]],
+            sender: SuiAddress::random_for_testing_only(),
+        };
+        let event = Event {
+            package_id: ObjectID::from(SUI_SYSTEM_ADDRESS),
+            transaction_module: DWALLET_MODULE_NAME.to_owned(),
+            sender: SuiAddress::ZERO,
+            type_: StructTag {
+                address: SUI_SYSTEM_ADDRESS,
+                module: DWALLET_MODULE_NAME.to_owned(),
+                name: SIGN_OUTPUT_CREATED_EVENT_STRUCT_NAME.to_owned(),
+                type_params: vec![],
+            },
+            contents: bcs::to_bytes(&output).unwrap(),
+        };
+        let indexed_event =
+            IndexedDWalletEvent::try_from_event(1, 0, 1, TransactionDigest::default(), &event, 100)
+                .unwrap()
+                .unwrap();
+        assert_eq!(
+            indexed_event.event,
+            DWalletEvent::SignOutputCreated(output.clone())
+        );
+
+        let sign_output = StoredDWalletSignOutput::new(&indexed_event, &output);
+        assert_eq!(sign_output.session_id, output.session_id.bytes.to_vec());
+        assert_eq!(sign_output.signatures.unwrap().len(), 2);
+        assert_eq!(sign_output.completed_timestamp_ms, Some(100));
+
+        // The event is of the sender of the session, not of the system transaction.
+        let stored_event = StoredDWalletEvent::from(indexed_event);
+        assert_eq!(stored_event.event_type, "SignOutputCreated");
+        assert_eq!(stored_event.sender, output.sender.to_vec());
+        assert_eq!(stored_event.dwallet_id, output.dwallet_id.bytes.to_vec());
+    }
+}
@@ -4,12 +4,14 @@
 pub mod address_metrics;
 pub mod checkpoints;
 pub mod display;
+pub mod dwallets;
 pub mod epoch;
 pub mod events;
 pub mod move_call_metrics;
 pub mod network_metrics;
 pub mod objects;
 pub mod packages;
+pub mod signing_metrics;
 pub mod transactions;
 pub mod tx_count_metrics;
 pub mod tx_indices;
@@ -0,0 +1,221 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use diesel::prelude::*;
+
+use sui_json_rpc_types::{EpochSigningMetrics, ValidatorSigningMetrics};
+use sui_types::base_types::SuiAddress;
+use sui_types::sui_system_state::sui_system_state_summary::SuiValidatorSummary;
+
+use crate::errors::IndexerError;
+use crate::schema_v2::{epoch_signing_metrics, epoch_validator_signing_metrics};
+
+#[derive(Clone, Debug, Queryable, QueryableByName, Insertable)]
+#[diesel(table_name = epoch_signing_metrics)]
+pub struct StoredEpochSigningMetrics {
+    pub epoch: i64,
+    pub sign_sessions_completed: i64,
+    pub sign_sessions_failed: i64,
+    pub latency_p50_ms: Option<i64>,
+    pub latency_p90_ms: Option<i64>,
+    pub latency_p99_ms: Option<i64>,
+    pub presigns_created: i64,
+    pub presigns_consumed: i64,
+}
+
+impl Default for StoredEpochSigningMetrics {
+    fn default() -> Self {
+        Self {
+            epoch: -1,
+            sign_sessions_completed: 0,
+            sign_sessions_failed: 0,
+            latency_p50_ms: None,
+            latency_p90_ms: None,
+            latency_p99_ms: None,
+            presigns_created: 0,
+            presigns_consumed: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Queryable, Insertable)]
+#[diesel(table_name = epoch_validator_signing_metrics)]
+pub struct StoredValidatorSigningMetrics {
+    pub epoch: i64,
+    pub validator_address: Vec<u8>,
+    pub name: String,
+    pub aggregated_sessions: i64,
+    pub completed_sessions: i64,
+}
+
+impl TryFrom<StoredValidatorSigningMetrics> for ValidatorSigningMetrics {
+    type Error = IndexerError;
+
+    fn try_from(stored: StoredValidatorSigningMetrics) -> Result<Self, Self::Error> {
+        let sui_address = SuiAddress::from_bytes(&stored.validator_address).map_err(|_| {
+            IndexerError::PersistentStorageDataCorruptionError(format!(
+                "Failed to parse validator address {:?} of epoch {}",
+                stored.validator_address, stored.epoch,
+            ))
+        })?;
+        let participation_rate = if stored.aggregated_sessions == 0 {
+            1.0
+        } else {
+            stored.completed_sessions as f64 / stored.aggregated_sessions as f64
+        };
+        Ok(Self {
+            sui_address,
+            name: stored.name,
+            aggregated_sessions: stored.aggregated_sessions as u64,
+            completed_sessions: stored.completed_sessions as u64,
+            participation_rate,
+        })
+    }
+}
+
+impl StoredEpochSigningMetrics {
+    pub fn into_epoch_signing_metrics(
+        self,
+        validators: Vec<StoredValidatorSigningMetrics>,
+    ) -> Result<EpochSigningMetrics, IndexerError> {
+        Ok(EpochSigningMetrics {
+            epoch: self.epoch as u64,
+            sign_sessions_completed: self.sign_sessions_completed as u64,
+            sign_sessions_failed: self.sign_sessions_failed as u64,
+            latency_p50_ms: self.latency_p50_ms.map(|v| v as u64),
+            latency_p90_ms: self.latency_p90_ms.map(|v| v as u64),
+            latency_p99_ms: self.latency_p99_ms.map(|v| v as u64),
+            presigns_created: self.presigns_created as u64,
+            presigns_consumed: self.presigns_consumed as u64,
+            validators: validators
+                .into_iter()
+                .map(ValidatorSigningMetrics::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// The metrics of the sign sessions and presigns created in the checkpoints
+/// `first_checkpoint..=last_checkpoint` of `epoch`. Sign sessions do not outlive their epoch,
+/// so a session without a sign output failed.
+pub fn build_epoch_signing_metrics_query(
+    epoch: i64,
+    first_checkpoint: i64,
+    last_checkpoint: i64,
+) -> String {
+    format!(
+        "WITH sessions AS (
+            SELECT
+              sign_output_id,
+              completed_timestamp_ms - timestamp_ms AS latency_ms
+            FROM dwallet_sign_sessions
+            WHERE checkpoint_sequence_number >= {first_checkpoint}
+              AND checkpoint_sequence_number <= {last_checkpoint}
+          )
+          SELECT
+            {epoch}::BIGINT AS epoch,
+            COUNT(*) FILTER (WHERE sign_output_id IS NOT NULL) AS sign_sessions_completed,
+            COUNT(*) FILTER (WHERE sign_output_id IS NULL) AS sign_sessions_failed,
+            percentile_disc(0.5) WITHIN GROUP (ORDER BY latency_ms) AS latency_p50_ms,
+            percentile_disc(0.9) WITHIN GROUP (ORDER BY latency_ms) AS latency_p90_ms,
+            percentile_disc(0.99) WITHIN GROUP (ORDER BY latency_ms) AS latency_p99_ms,
+            (
+              SELECT COUNT(*) FROM dwallet_presigns
+              WHERE checkpoint_sequence_number >= {first_checkpoint}
+                AND checkpoint_sequence_number <= {last_checkpoint}
+            ) AS presigns_created,
+            COUNT(*) AS presigns_consumed
+          FROM sessions;"
+    )
+}
+
+/// The sign sessions of `epoch` each of its `validators` was the aggregator of, from the
+/// `(session_id, completed)` of the sessions. The parties of the sign MPC are the validators of
+/// the committee by their index from 1, and the aggregator of a session is picked by its ID, as by
+/// the signature MPC service.
+pub fn build_validator_signing_metrics(
+    epoch: i64,
+    validators: &[SuiValidatorSummary],
+    sessions: &[(Vec<u8>, bool)],
+) -> Vec<StoredValidatorSigningMetrics> {
+    // The committee orders its authorities by their name, the protocol public key.
+    let mut validators: Vec<_> = validators.iter().collect();
+    validators.sort_by(|a, b| a.protocol_pubkey_bytes.cmp(&b.protocol_pubkey_bytes));
+    let mut metrics: Vec<_> = validators
+        .iter()
+        .map(|validator| StoredValidatorSigningMetrics {
+            epoch,
+            validator_address: validator.sui_address.to_vec(),
+            name: validator.name.clone(),
+            aggregated_sessions: 0,
+            completed_sessions: 0,
+        })
+        .collect();
+    if metrics.is_empty() {
+        return metrics;
+    }
+    for (session_id, completed) in sessions {
+        let Some(prefix) = session_id.get(..8) else {
+            continue;
+        };
+        let session_prefix = u64::from_be_bytes(prefix.try_into().unwrap());
+        let aggregator = &mut metrics[(session_prefix % validators.len() as u64) as usize];
+        aggregator.aggregated_sessions += 1;
+        if *completed {
+            aggregator.completed_sessions += 1;
+        }
+    }
+    metrics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validator_signing_metrics() {
+        let validators: Vec<_> = [3u8, 1, 2]
+            .into_iter()
+            .map(|i| SuiValidatorSummary {
+                sui_address: SuiAddress::from_bytes([i; 32]).unwrap(),
+                protocol_pubkey_bytes: vec![i; 96],
+                name: format!("validator-{i}"),
+                ..Default::default()
+            })
+            .collect();
+        let session = |prefix: u64, completed: bool| {
+            let mut session_id = prefix.to_be_bytes().to_vec();
+            session_id.extend([0; 24]);
+            (session_id, completed)
+        };
+        // Party 1 aggregates the sessions whose prefix is 0 modulo 3, party 2 those of 1, and
+        // party 3 those of 2.
+        let sessions = vec![
+            session(0, true),
+            session(3, false),
+            session(4, true),
+            session(5, true),
+        ];
+
+        let metrics = build_validator_signing_metrics(7, &validators, &sessions);
+        let counts: Vec<_> = metrics
+            .iter()
+            .map(|m| (m.name.as_str(), m.aggregated_sessions, m.completed_sessions))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                ("validator-1", 2, 1),
+                ("validator-2", 1, 1),
+                ("validator-3", 1, 1),
+            ]
+        );
+
+        let validator = ValidatorSigningMetrics::try_from(metrics[0].clone()).unwrap();
+        assert_eq!(
+            validator.sui_address,
+            SuiAddress::from_bytes([1; 32]).unwrap()
+        );
+        assert_eq!(validator.participation_rate, 0.5);
+    }
+}
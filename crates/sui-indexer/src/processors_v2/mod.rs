@@ -5,3 +5,4 @@ pub mod address_metrics_processor;
 pub mod move_call_metrics_processor;
 pub mod network_metrics_processor;
 pub mod processor_orchestrator_v2;
+pub mod signing_metrics_processor;
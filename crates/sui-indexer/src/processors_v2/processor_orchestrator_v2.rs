@@ -10,6 +10,7 @@ use crate::store::IndexerAnalyticalStore;
 use super::address_metrics_processor::AddressMetricsProcessor;
 use super::move_call_metrics_processor::MoveCallMetricsProcessor;
 use super::network_metrics_processor::NetworkMetricsProcessor;
+use super::signing_metrics_processor::SigningMetricsProcessor;
 
 pub struct ProcessorOrchestratorV2<S> {
     store: S,
@@ -71,10 +72,26 @@ where
             }
         });
 
+        let signing_metrics_processor =
+            SigningMetricsProcessor::new(self.store.clone(), self.metrics.clone());
+        let signing_metrics_handle = tokio::task::spawn(async move {
+            loop {
+                let signing_metrics_res = signing_metrics_processor.start().await;
+                if let Err(e) = signing_metrics_res {
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                    error!(
+                        "Indexer signing metrics processor failed with error {:?}, retrying in 5s...",
+                        e
+                    );
+                }
+            }
+        });
+
         try_join_all(vec![
             network_metrics_handle,
             addr_metrics_handle,
             move_call_metrics_handle,
+            signing_metrics_handle,
         ])
         .await
         .expect("Processor orchestrator should not run into errors.");
@@ -0,0 +1,53 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use tracing::info;
+
+use crate::metrics::IndexerMetrics;
+use crate::store::IndexerAnalyticalStore;
+use crate::types_v2::IndexerResult;
+
+/// Aggregates the signing metrics of each epoch once it ended, which is once a checkpoint of a
+/// later epoch is stored, as checkpoints are committed after the rest of their data.
+pub struct SigningMetricsProcessor<S> {
+    pub store: S,
+    metrics: IndexerMetrics,
+}
+
+impl<S> SigningMetricsProcessor<S>
+where
+    S: IndexerAnalyticalStore + Clone + Sync + Send + 'static,
+{
+    pub fn new(store: S, metrics: IndexerMetrics) -> SigningMetricsProcessor<S> {
+        Self { store, metrics }
+    }
+
+    pub async fn start(&self) -> IndexerResult<()> {
+        info!("Indexer signing metrics async processor started...");
+        let latest_signing_metrics = self.store.get_latest_epoch_signing_metrics().await?;
+        let mut last_processed_epoch = latest_signing_metrics.unwrap_or_default().epoch;
+        loop {
+            let mut latest_stored_checkpoint = self.store.get_latest_stored_checkpoint().await?;
+            while if let Some(cp) = &latest_stored_checkpoint {
+                cp.epoch <= last_processed_epoch + 1
+            } else {
+                true
+            } {
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                latest_stored_checkpoint = self.store.get_latest_stored_checkpoint().await?;
+            }
+
+            let current_epoch = latest_stored_checkpoint.unwrap().epoch;
+            for epoch in last_processed_epoch + 1..current_epoch {
+                self.store
+                    .calculate_and_persist_signing_metrics(epoch)
+                    .await?;
+                last_processed_epoch = epoch;
+                self.metrics
+                    .latest_signing_metrics_epoch
+                    .set(last_processed_epoch);
+                info!("Persisted signing metrics for epoch {}", epoch);
+            }
+        }
+    }
+}
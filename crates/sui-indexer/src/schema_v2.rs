@@ -63,6 +63,63 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    dwallet_events (tx_sequence_number, event_sequence_number) {
+        tx_sequence_number -> Int8,
+        event_sequence_number -> Int8,
+        transaction_digest -> Bytea,
+        checkpoint_sequence_number -> Int8,
+        dwallet_id -> Bytea,
+        event_type -> Text,
+        sender -> Bytea,
+        timestamp_ms -> Int8,
+        bcs -> Bytea,
+    }
+}
+
+diesel::table! {
+    dwallet_presigns (presign_id) {
+        presign_id -> Bytea,
+        session_id -> Bytea,
+        dwallet_id -> Bytea,
+        dwallet_cap_id -> Bytea,
+        sender -> Bytea,
+        tx_sequence_number -> Int8,
+        checkpoint_sequence_number -> Int8,
+        timestamp_ms -> Int8,
+    }
+}
+
+diesel::table! {
+    dwallet_sign_sessions (session_id) {
+        session_id -> Bytea,
+        dwallet_id -> Bytea,
+        dwallet_cap_id -> Bytea,
+        sender -> Bytea,
+        messages -> Array<Nullable<Bytea>>,
+        tx_sequence_number -> Int8,
+        checkpoint_sequence_number -> Int8,
+        timestamp_ms -> Int8,
+        sign_output_id -> Nullable<Bytea>,
+        signatures -> Nullable<Array<Nullable<Bytea>>>,
+        completed_tx_sequence_number -> Nullable<Int8>,
+        completed_timestamp_ms -> Nullable<Int8>,
+    }
+}
+
+diesel::table! {
+    dwallets (dwallet_id) {
+        dwallet_id -> Bytea,
+        dwallet_cap_id -> Bytea,
+        sender -> Bytea,
+        public_key -> Bytea,
+        dkg_output -> Bytea,
+        tx_sequence_number -> Int8,
+        checkpoint_sequence_number -> Int8,
+        timestamp_ms -> Int8,
+    }
+}
+
 diesel::table! {
     epoch_peak_tps (epoch) {
         epoch -> Int8,
@@ -71,6 +128,29 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    epoch_signing_metrics (epoch) {
+        epoch -> Int8,
+        sign_sessions_completed -> Int8,
+        sign_sessions_failed -> Int8,
+        latency_p50_ms -> Nullable<Int8>,
+        latency_p90_ms -> Nullable<Int8>,
+        latency_p99_ms -> Nullable<Int8>,
+        presigns_created -> Int8,
+        presigns_consumed -> Int8,
+    }
+}
+
+diesel::table! {
+    epoch_validator_signing_metrics (epoch, validator_address) {
+        epoch -> Int8,
+        validator_address -> Bytea,
+        name -> Text,
+        aggregated_sessions -> Int8,
+        completed_sessions -> Int8,
+    }
+}
+
 diesel::table! {
     epochs (epoch) {
         epoch -> Int8,
@@ -305,7 +385,13 @@ diesel::allow_tables_to_appear_in_same_query!(
     addresses,
     checkpoints,
     display,
+    dwallet_events,
+    dwallet_presigns,
+    dwallet_sign_sessions,
+    dwallets,
     epoch_peak_tps,
+    epoch_signing_metrics,
+    epoch_validator_signing_metrics,
     epochs,
     events,
     move_call_metrics,
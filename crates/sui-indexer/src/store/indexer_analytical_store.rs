@@ -6,6 +6,7 @@ use async_trait::async_trait;
 use crate::models_v2::checkpoints::StoredCheckpoint;
 use crate::models_v2::move_call_metrics::StoredMoveCallMetrics;
 use crate::models_v2::network_metrics::StoredEpochPeakTps;
+use crate::models_v2::signing_metrics::StoredEpochSigningMetrics;
 use crate::models_v2::transactions::{
     StoredTransaction, StoredTransactionCheckpoint, StoredTransactionSuccessCommandCount,
     StoredTransactionTimestamp, TxSeq,
@@ -73,4 +74,10 @@ pub trait IndexerAnalyticalStore {
         end_tx_seq: i64,
     ) -> IndexerResult<()>;
     async fn calculate_and_persist_move_call_metrics(&self, epoch: i64) -> IndexerResult<()>;
+
+    // for signing metrics of dWallets
+    async fn get_latest_epoch_signing_metrics(
+        &self,
+    ) -> IndexerResult<Option<StoredEpochSigningMetrics>>;
+    async fn calculate_and_persist_signing_metrics(&self, epoch: i64) -> IndexerResult<()>;
 }
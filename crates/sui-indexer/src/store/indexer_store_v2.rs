@@ -15,7 +15,8 @@ use crate::handlers::{EpochToCommit, TransactionObjectChangesToCommit};
 
 use crate::models_v2::display::StoredDisplay;
 use crate::types_v2::{
-    IndexedCheckpoint, IndexedEvent, IndexedPackage, IndexedTransaction, TxIndex,
+    IndexedCheckpoint, IndexedDWallet, IndexedDWalletEvent, IndexedEvent, IndexedPackage,
+    IndexedTransaction, TxIndex,
 };
 
 #[async_trait]
@@ -69,6 +70,14 @@ pub trait IndexerStoreV2 {
 
     async fn persist_packages(&self, packages: Vec<IndexedPackage>) -> Result<(), IndexerError>;
 
+    /// Persist the dWallets, and the dWallet events with the presigns and sign sessions they
+    /// create, in the order of the checkpoints.
+    async fn persist_dwallet_data(
+        &self,
+        dwallets: Vec<IndexedDWallet>,
+        events: Vec<IndexedDWalletEvent>,
+    ) -> Result<(), IndexerError>;
+
     async fn persist_epoch(&self, epoch: EpochToCommit) -> Result<(), IndexerError>;
 
     async fn advance_epoch(&self, epoch: EpochToCommit) -> Result<(), IndexerError>;
@@ -11,22 +11,28 @@ use diesel::dsl::count;
 use diesel::{ExpressionMethods, OptionalExtension};
 use diesel::{QueryDsl, RunQueryDsl};
 use sui_types::base_types::ObjectID;
+use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
 
 use crate::errors::{Context, IndexerError};
 use crate::models_v2::address_metrics::StoredAddressMetrics;
 use crate::models_v2::checkpoints::StoredCheckpoint;
+use crate::models_v2::epoch::StoredEpochInfo;
 use crate::models_v2::move_call_metrics::{
     build_move_call_metric_query, QueriedMoveCallMetrics, QueriedMoveMetrics, StoredMoveCallMetrics,
 };
 use crate::models_v2::network_metrics::{StoredEpochPeakTps, Tps};
+use crate::models_v2::signing_metrics::{
+    build_epoch_signing_metrics_query, build_validator_signing_metrics, StoredEpochSigningMetrics,
+};
 use crate::models_v2::transactions::{
     StoredTransaction, StoredTransactionCheckpoint, StoredTransactionSuccessCommandCount,
     StoredTransactionTimestamp, TxSeq,
 };
 use crate::models_v2::tx_count_metrics::StoredTxCountMetrics;
 use crate::schema_v2::{
-    active_addresses, address_metrics, addresses, checkpoints, epoch_peak_tps, move_call_metrics,
-    move_calls, transactions, tx_count_metrics,
+    active_addresses, address_metrics, addresses, checkpoints, dwallet_sign_sessions,
+    epoch_peak_tps, epoch_signing_metrics, epoch_validator_signing_metrics, epochs,
+    move_call_metrics, move_calls, transactions, tx_count_metrics,
 };
 use crate::store::diesel_macro::{read_only_blocking, transactional_blocking_with_retry};
 use crate::types_v2::IndexerResult;
@@ -470,6 +476,87 @@ impl IndexerAnalyticalStore for PgIndexerAnalyticalStore {
         .context("Failed persisting move call metrics to PostgresDB")?;
         Ok(())
     }
+
+    async fn get_latest_epoch_signing_metrics(
+        &self,
+    ) -> IndexerResult<Option<StoredEpochSigningMetrics>> {
+        let latest_signing_metrics = read_only_blocking!(&self.blocking_cp, |conn| {
+            epoch_signing_metrics::dsl::epoch_signing_metrics
+                .order(epoch_signing_metrics::dsl::epoch.desc())
+                .first::<StoredEpochSigningMetrics>(conn)
+                .optional()
+        })
+        .context("Failed reading latest epoch signing metrics from PostgresDB")?;
+        Ok(latest_signing_metrics)
+    }
+
+    async fn calculate_and_persist_signing_metrics(&self, epoch: i64) -> IndexerResult<()> {
+        // The system state stored with an epoch is the one it ended with, so the committee of
+        // an epoch is the validators of the previous one, except for the genesis epoch.
+        let (epoch_info, committee_epoch_info) = read_only_blocking!(&self.blocking_cp, |conn| {
+            let epoch_info = epochs::table
+                .filter(epochs::epoch.eq(epoch))
+                .first::<StoredEpochInfo>(conn)?;
+            let committee_epoch_info = epochs::table
+                .filter(epochs::epoch.eq((epoch - 1).max(0)))
+                .first::<StoredEpochInfo>(conn)?;
+            Ok::<_, diesel::result::Error>((epoch_info, committee_epoch_info))
+        })
+        .context("Failed reading epoch info for signing metrics from PostgresDB")?;
+        let first_checkpoint = epoch_info.first_checkpoint_id;
+        let last_checkpoint = epoch_info.last_checkpoint_id.ok_or_else(|| {
+            IndexerError::PostgresReadError(format!(
+                "Cannot calculate signing metrics of epoch {epoch}, which has not ended"
+            ))
+        })?;
+        let committee_system_state: SuiSystemStateSummary =
+            bcs::from_bytes(&committee_epoch_info.system_state).map_err(|_| {
+                IndexerError::PersistentStorageDataCorruptionError(format!(
+                    "Failed to deserialize `system_state` for epoch {}",
+                    committee_epoch_info.epoch,
+                ))
+            })?;
+
+        let signing_metrics_query =
+            build_epoch_signing_metrics_query(epoch, first_checkpoint, last_checkpoint);
+        let (signing_metrics, sessions) = read_only_blocking!(&self.blocking_cp, |conn| {
+            let signing_metrics = diesel::sql_query(signing_metrics_query.clone())
+                .get_result::<StoredEpochSigningMetrics>(conn)?;
+            let sessions = dwallet_sign_sessions::table
+                .filter(dwallet_sign_sessions::checkpoint_sequence_number.ge(first_checkpoint))
+                .filter(dwallet_sign_sessions::checkpoint_sequence_number.le(last_checkpoint))
+                .select((
+                    dwallet_sign_sessions::session_id,
+                    dwallet_sign_sessions::sign_output_id.is_not_null(),
+                ))
+                .load::<(Vec<u8>, bool)>(conn)?;
+            Ok::<_, diesel::result::Error>((signing_metrics, sessions))
+        })
+        .context("Failed calculating epoch signing metrics from PostgresDB")?;
+        let validator_signing_metrics = build_validator_signing_metrics(
+            epoch,
+            &committee_system_state.active_validators,
+            &sessions,
+        );
+
+        transactional_blocking_with_retry!(
+            &self.blocking_cp,
+            |conn| {
+                diesel::insert_into(epoch_validator_signing_metrics::table)
+                    .values(validator_signing_metrics.clone())
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+                diesel::insert_into(epoch_signing_metrics::table)
+                    .values(signing_metrics.clone())
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+                Ok::<(), IndexerError>(())
+            },
+            Duration::from_secs(10)
+        )
+        .context("Failed persisting epoch signing metrics to PostgresDB")?;
+        Ok(())
+    }
 }
 
 fn construct_checkpoint_tx_count_query(start_checkpoint: i64, end_checkpoint: i64) -> String {
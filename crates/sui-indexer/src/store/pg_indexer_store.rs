@@ -400,6 +400,10 @@ impl PgIndexerStore {
                 boxed_query =
                     boxed_query.filter(events::dsl::event_type.eq(struct_name.to_string()));
             }
+            EventFilter::DWalletEventKind(kind) => {
+                boxed_query =
+                    boxed_query.filter(events::dsl::event_type.eq(kind.event_type().to_string()));
+            }
             EventFilter::Sender(sender) => {
                 boxed_query = boxed_query.filter(events::dsl::sender.eq(sender.to_string()));
             }
@@ -22,6 +22,7 @@ use tracing::info;
 
 use sui_types::base_types::{ObjectID, SequenceNumber};
 use sui_types::object::ObjectRead;
+use sui_types::signature_mpc::DWalletEvent;
 
 use crate::errors::{Context, IndexerError};
 use crate::handlers::EpochToCommit;
@@ -30,6 +31,10 @@ use crate::metrics::IndexerMetrics;
 
 use crate::models_v2::checkpoints::StoredCheckpoint;
 use crate::models_v2::display::StoredDisplay;
+use crate::models_v2::dwallets::{
+    StoredDWallet, StoredDWalletEvent, StoredDWalletPresign, StoredDWalletSignOutput,
+    StoredDWalletSignSession,
+};
 use crate::models_v2::epoch::StoredEpochInfo;
 use crate::models_v2::events::StoredEvent;
 use crate::models_v2::objects::{
@@ -38,13 +43,15 @@ use crate::models_v2::objects::{
 use crate::models_v2::packages::StoredPackage;
 use crate::models_v2::transactions::StoredTransaction;
 use crate::schema_v2::{
-    checkpoints, display, epochs, events, objects, objects_history, objects_snapshot, packages,
-    transactions, tx_calls, tx_changed_objects, tx_input_objects, tx_recipients, tx_senders,
+    checkpoints, display, dwallet_events, dwallet_presigns, dwallet_sign_sessions, dwallets,
+    epochs, events, objects, objects_history, objects_snapshot, packages, transactions, tx_calls,
+    tx_changed_objects, tx_input_objects, tx_recipients, tx_senders,
 };
 use crate::store::diesel_macro::{read_only_blocking, transactional_blocking_with_retry};
 use crate::store::module_resolver_v2::IndexerStoreModuleResolver;
 use crate::types_v2::{
-    IndexedCheckpoint, IndexedEvent, IndexedPackage, IndexedTransaction, TxIndex,
+    IndexedCheckpoint, IndexedDWallet, IndexedDWalletEvent, IndexedEvent, IndexedPackage,
+    IndexedTransaction, TxIndex,
 };
 use crate::PgConnectionPool;
 
@@ -539,6 +546,107 @@ impl PgIndexerStoreV2 {
         })
     }
 
+    fn persist_dwallet_data(
+        &self,
+        dwallets: Vec<IndexedDWallet>,
+        events: Vec<IndexedDWalletEvent>,
+    ) -> Result<(), IndexerError> {
+        let guard = self
+            .metrics
+            .checkpoint_db_commit_latency_dwallets
+            .start_timer();
+        let mut presigns = vec![];
+        let mut sign_sessions = vec![];
+        let mut sign_outputs = vec![];
+        for event in &events {
+            match &event.event {
+                DWalletEvent::PresignCreated(created) => {
+                    presigns.push(StoredDWalletPresign::new(event, created))
+                }
+                DWalletEvent::SignSessionCreated(created) => {
+                    sign_sessions.push(StoredDWalletSignSession::new(event, created))
+                }
+                DWalletEvent::SignOutputCreated(output) => {
+                    sign_outputs.push(StoredDWalletSignOutput::new(event, output))
+                }
+                DWalletEvent::DWalletCreated(_) | DWalletEvent::PresignSessionCreated(_) => {}
+            }
+        }
+        let dwallets = dwallets
+            .into_iter()
+            .map(StoredDWallet::from)
+            .collect::<Vec<_>>();
+        let events = events
+            .into_iter()
+            .map(StoredDWalletEvent::from)
+            .collect::<Vec<_>>();
+
+        transactional_blocking_with_retry!(
+            &self.blocking_cp,
+            |conn| {
+                for chunk in dwallets.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    diesel::insert_into(dwallets::table)
+                        .values(chunk)
+                        .on_conflict_do_nothing()
+                        .execute(conn)
+                        .map_err(IndexerError::from)
+                        .context("Failed to write dwallets to PostgresDB")?;
+                }
+                for chunk in events.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    diesel::insert_into(dwallet_events::table)
+                        .values(chunk)
+                        .on_conflict_do_nothing()
+                        .execute(conn)
+                        .map_err(IndexerError::from)
+                        .context("Failed to write dwallet_events to PostgresDB")?;
+                }
+                for chunk in presigns.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    diesel::insert_into(dwallet_presigns::table)
+                        .values(chunk)
+                        .on_conflict_do_nothing()
+                        .execute(conn)
+                        .map_err(IndexerError::from)
+                        .context("Failed to write dwallet_presigns to PostgresDB")?;
+                }
+                for chunk in sign_sessions.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    diesel::insert_into(dwallet_sign_sessions::table)
+                        .values(chunk)
+                        .on_conflict_do_nothing()
+                        .execute(conn)
+                        .map_err(IndexerError::from)
+                        .context("Failed to write dwallet_sign_sessions to PostgresDB")?;
+                }
+                // The sessions are inserted first, as a session may be signed in the batch it
+                // was created in.
+                for output in &sign_outputs {
+                    diesel::update(dwallet_sign_sessions::table.find(output.session_id.clone()))
+                        .set((
+                            dwallet_sign_sessions::sign_output_id.eq(output.sign_output_id.clone()),
+                            dwallet_sign_sessions::signatures.eq(output.signatures.clone()),
+                            dwallet_sign_sessions::completed_tx_sequence_number
+                                .eq(output.completed_tx_sequence_number),
+                            dwallet_sign_sessions::completed_timestamp_ms
+                                .eq(output.completed_timestamp_ms),
+                        ))
+                        .execute(conn)
+                        .map_err(IndexerError::from)
+                        .context("Failed to write dwallet sign outputs to PostgresDB")?;
+                }
+                Ok::<(), IndexerError>(())
+            },
+            Duration::from_secs(60)
+        )
+        .tap(|_| {
+            let elapsed = guard.stop_and_record();
+            info!(
+                elapsed,
+                "Persisted {} dwallets and {} dwallet events",
+                dwallets.len(),
+                events.len()
+            )
+        })
+    }
+
     async fn persist_tx_indices_chunk(&self, indices: Vec<TxIndex>) -> Result<(), IndexerError> {
         let guard = self
             .metrics
@@ -1081,6 +1189,18 @@ impl IndexerStoreV2 for PgIndexerStoreV2 {
             .await
     }
 
+    async fn persist_dwallet_data(
+        &self,
+        dwallets: Vec<IndexedDWallet>,
+        events: Vec<IndexedDWalletEvent>,
+    ) -> Result<(), IndexerError> {
+        if dwallets.is_empty() && events.is_empty() {
+            return Ok(());
+        }
+        self.execute_in_blocking_worker(move |this| this.persist_dwallet_data(dwallets, events))
+            .await
+    }
+
     async fn persist_tx_indices(&self, indices: Vec<TxIndex>) -> Result<(), IndexerError> {
         if indices.is_empty() {
             return Ok(());
@@ -18,6 +18,7 @@ use sui_types::messages_checkpoint::{
 };
 use sui_types::move_package::MovePackage;
 use sui_types::object::{Object, Owner};
+use sui_types::signature_mpc::DWalletEvent;
 use sui_types::sui_serde::SuiStructTag;
 use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
 use sui_types::transaction::SenderSignedData;
@@ -201,6 +202,61 @@ impl IndexedEvent {
     }
 }
 
+/// An event of the lifecycle of a dWallet, see [DWalletEvent].
+#[derive(Debug, Clone)]
+pub struct IndexedDWalletEvent {
+    pub tx_sequence_number: u64,
+    pub event_sequence_number: u64,
+    pub checkpoint_sequence_number: u64,
+    pub transaction_digest: TransactionDigest,
+    /// The name of the event struct, e.g. `DWalletCreated`.
+    pub event_type: String,
+    pub event: DWalletEvent,
+    pub bcs: Vec<u8>,
+    pub timestamp_ms: u64,
+}
+
+impl IndexedDWalletEvent {
+    /// Index `event` if it is a dWallet event.
+    pub fn try_from_event(
+        tx_sequence_number: u64,
+        event_sequence_number: u64,
+        checkpoint_sequence_number: u64,
+        transaction_digest: TransactionDigest,
+        event: &sui_types::event::Event,
+        timestamp_ms: u64,
+    ) -> IndexerResult<Option<Self>> {
+        let Some(dwallet_event) =
+            DWalletEvent::try_from_event(&event.type_, &event.contents).transpose()?
+        else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            tx_sequence_number,
+            event_sequence_number,
+            checkpoint_sequence_number,
+            transaction_digest,
+            event_type: event.type_.name.to_string(),
+            event: dwallet_event,
+            bcs: event.contents.clone(),
+            timestamp_ms,
+        }))
+    }
+}
+
+/// A dWallet, indexed from the transaction that created it.
+#[derive(Debug, Clone)]
+pub struct IndexedDWallet {
+    pub dwallet_id: ObjectID,
+    pub dwallet_cap_id: ObjectID,
+    pub sender: SuiAddress,
+    pub public_key: Vec<u8>,
+    pub dkg_output: Vec<u8>,
+    pub tx_sequence_number: u64,
+    pub checkpoint_sequence_number: u64,
+    pub timestamp_ms: u64,
+}
+
 #[derive(Debug, Copy, Clone)]
 pub enum OwnerType {
     Immutable = 0,
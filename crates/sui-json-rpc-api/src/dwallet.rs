@@ -0,0 +1,134 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+
+use sui_json_rpc_types::{
+    DWalletInfo, DWalletPage, DWalletQuery, EncryptedUserSharePage, EthereumProof, SignEventFilter,
+    SuiDWalletTransferStatus, SuiMpcParameters, SuiPresignPoolStatus, SuiSignEvent, SuiSignSession,
+    VerifiedEthereumProof,
+};
+use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::external_address::{BitcoinAddresses, BitcoinNetwork};
+use sui_types::sui_serde::BigInt;
+
+#[open_rpc(namespace = "dwallet", tag = "dWallet Read API")]
+#[rpc(server, client, namespace = "dwallet")]
+pub trait DWalletReadApi {
+    /// Return the Bitcoin addresses controlled by a dWallet, for the given network or for all networks.
+    #[method(name = "getBitcoinAddresses")]
+    async fn get_bitcoin_addresses(
+        &self,
+        /// the ID of the dWallet object
+        dwallet_id: ObjectID,
+        /// the Bitcoin network, if None, addresses for all networks are returned
+        network: Option<BitcoinNetwork>,
+    ) -> RpcResult<Vec<BitcoinAddresses>>;
+
+    /// Return the public data of a dWallet: its DKG public output, curve and scheme, owner,
+    /// creation epoch, signing policy and addresses on external chains.
+    #[method(name = "getDWallet")]
+    async fn get_dwallet(
+        &self,
+        /// the ID of the dWallet object
+        dwallet_id: ObjectID,
+    ) -> RpcResult<DWalletInfo>;
+
+    /// Return the dWallets of an address, by the `DWalletCap`s it owns, with the public data of
+    /// the active ones. A dWallet whose capability is held by a signing policy is not listed.
+    /// A page scans a bounded number of capabilities, so it may hold fewer dWallets than the
+    /// limit and still have a next page when the query filters most of them out.
+    #[method(name = "getDWalletsByOwner")]
+    async fn get_dwallets_by_owner(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// the dWallets query criteria.
+        query: Option<DWalletQuery>,
+        /// An optional paging cursor: the `DWalletCap` of the last dWallet of the previous page, or of the last one scanned when the page was cut short. If provided, the query will start from the next item after the specified cursor. Default to start from the first item if not specified.
+        cursor: Option<ObjectID>,
+        /// Max number of items returned per page, default to [QUERY_MAX_RESULT_LIMIT] if not specified.
+        limit: Option<usize>,
+    ) -> RpcResult<DWalletPage>;
+
+    /// Return the parameters of the MPC protocols of the network in the current epoch: its
+    /// Tiresias public parameters, the supported curves and hash functions, the protocol feature
+    /// flags and the protocol version.
+    #[method(name = "getMpcParameters")]
+    async fn get_mpc_parameters(&self) -> RpcResult<SuiMpcParameters>;
+
+    /// Return the presigns of a dWallet: how many are available to its owner in a page of the
+    /// presigns it owns, and, with the first page, how many are pending generation by the network
+    /// and consumed by sign sessions this epoch.
+    #[method(name = "getPresignPoolStatus")]
+    async fn get_presign_pool_status(
+        &self,
+        /// the ID of the dWallet object
+        dwallet_id: ObjectID,
+        /// the address whose available presigns are counted, if None, the owner of the dWallet
+        owner: Option<SuiAddress>,
+        /// An optional paging cursor: the last presign of the previous page. If provided, the query will start from the next item after the specified cursor. Default to start from the first item if not specified.
+        cursor: Option<ObjectID>,
+        /// Max number of presigns of the owner scanned per page, default to [QUERY_MAX_RESULT_LIMIT] if not specified.
+        limit: Option<usize>,
+    ) -> RpcResult<SuiPresignPoolStatus>;
+
+    /// Return the status of a sign session: its message digests, parties, and its completion
+    /// time and signatures once signed.
+    #[method(name = "getSignSession")]
+    async fn get_sign_session(
+        &self,
+        /// the ID of the SignSession object, which is its SignatureMPCSessionID
+        session_id: ObjectID,
+    ) -> RpcResult<SuiSignSession>;
+
+    /// Return the encrypted user shares of a dWallet owned by an address, sent to it along with
+    /// the dWallet.
+    #[method(name = "getEncryptedUserShares")]
+    async fn get_encrypted_user_shares(
+        &self,
+        /// the ID of the dWallet object
+        dwallet_id: ObjectID,
+        /// the address whose shares are returned, if None, the owner of the dWallet
+        owner: Option<SuiAddress>,
+        /// An optional paging cursor: the last share of the previous page. If provided, the query will start from the next item after the specified cursor. Default to start from the first item if not specified.
+        cursor: Option<ObjectID>,
+        /// Max number of items returned per page, default to [QUERY_MAX_RESULT_LIMIT] if not specified.
+        limit: Option<usize>,
+    ) -> RpcResult<EncryptedUserSharePage>;
+
+    /// Return the status of the transfer of a dWallet with an encrypted user share: whether the
+    /// share is encrypted to a key of its recipient, and whether the recipient controls the
+    /// dWallet.
+    #[method(name = "getTransferStatus")]
+    async fn get_transfer_status(
+        &self,
+        /// the ID of the EncryptedUserShare object
+        share_id: ObjectID,
+    ) -> RpcResult<SuiDWalletTransferStatus>;
+
+    /// Subscribe to the completion and failure of sign sessions. A session fails when its epoch
+    /// ends before it is signed; the failure of a session is sent only if the session was created
+    /// after the subscription, or if it is selected by its ID.
+    #[subscription(name = "subscribeSignEvents", item = SuiSignEvent)]
+    fn subscribe_sign_events(
+        &self,
+        /// the sessions to notify of, by dWallet, session or owner
+        filter: SignEventFilter,
+    );
+
+    /// Verify a proof of Ethereum state against a block finalized by an Ethereum light client
+    /// object, with the verification of the Move light client, and return the proven value.
+    #[method(name = "verifyEthereumProof")]
+    async fn verify_ethereum_proof(
+        &self,
+        /// the ID of the EthereumLightClient object
+        light_client_id: ObjectID,
+        /// the number of the block the proof is against, which the light client must have finalized
+        block_number: BigInt<u64>,
+        /// the proof of a storage slot or of a receipt
+        proof: EthereumProof,
+    ) -> RpcResult<VerifiedEthereumProof>;
+}
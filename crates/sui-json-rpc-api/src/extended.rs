@@ -5,8 +5,9 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 
 use sui_json_rpc_types::{
-    AddressMetrics, CheckpointedObjectID, EpochInfo, EpochMetricsPage, EpochPage, MoveCallMetrics,
-    NetworkMetrics, QueryObjectsPage, SuiObjectResponseQuery,
+    AddressMetrics, CheckpointedObjectID, EpochInfo, EpochMetricsPage, EpochPage,
+    EpochSigningMetricsPage, MoveCallMetrics, NetworkMetrics, QueryObjectsPage,
+    SuiObjectResponseQuery,
 };
 use sui_open_rpc_macros::open_rpc;
 use sui_types::sui_serde::BigInt;
@@ -73,6 +74,19 @@ pub trait ExtendedApi {
         descending_order: Option<bool>,
     ) -> RpcResult<Vec<AddressMetrics>>;
 
+    /// Return the signing metrics of ended epochs: their sign sessions, signing latency, presign
+    /// consumption and the sessions aggregated by each validator
+    #[method(name = "getSigningMetrics")]
+    async fn get_signing_metrics(
+        &self,
+        /// optional paging cursor
+        cursor: Option<BigInt<u64>>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+        /// flag to return results in descending order
+        descending_order: Option<bool>,
+    ) -> RpcResult<EpochSigningMetricsPage>;
+
     #[method(name = "getTotalTransactions")]
     async fn get_total_transactions(&self) -> RpcResult<BigInt<u64>>;
 }
@@ -2,11 +2,14 @@
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
 use anyhow::anyhow;
-use mysten_metrics::histogram::Histogram;
+use mysten_metrics::histogram::{Histogram, HistogramVec};
 
 pub use coin::CoinReadApiClient;
 pub use coin::CoinReadApiOpenRpc;
 pub use coin::CoinReadApiServer;
+pub use dwallet::DWalletReadApiClient;
+pub use dwallet::DWalletReadApiOpenRpc;
+pub use dwallet::DWalletReadApiServer;
 pub use extended::ExtendedApiClient;
 pub use extended::ExtendedApiOpenRpc;
 pub use extended::ExtendedApiServer;
@@ -20,7 +23,10 @@ pub use move_utils::MoveUtilsClient;
 pub use move_utils::MoveUtilsOpenRpc;
 pub use move_utils::MoveUtilsServer;
 use once_cell::sync::Lazy;
-use prometheus::{register_int_counter_with_registry, IntCounter};
+use prometheus::{
+    register_int_counter_vec_with_registry, register_int_counter_with_registry,
+    register_int_gauge_with_registry, IntCounter, IntCounterVec, IntGauge,
+};
 pub use read::ReadApiClient;
 pub use read::ReadApiOpenRpc;
 pub use read::ReadApiServer;
@@ -34,6 +40,7 @@ pub use write::WriteApiOpenRpc;
 pub use write::WriteApiServer;
 
 mod coin;
+mod dwallet;
 mod extended;
 mod governance;
 mod indexer;
@@ -278,6 +285,77 @@ impl JsonRpcMetrics {
     }
 }
 
+/// Metrics of the dWallet read API, apart from the metrics of every route, to measure the load of
+/// dWallet clients on a fullnode.
+#[derive(Clone)]
+pub struct DWalletRpcMetrics {
+    pub requests_by_method: IntCounterVec,
+    pub errors_by_method: IntCounterVec,
+    pub latency_by_method: HistogramVec,
+
+    pub sign_event_subscriptions: IntGauge,
+    pub sign_event_subscriptions_rejected: IntCounter,
+    pub sign_event_notifications: IntCounterVec,
+    pub sign_event_lag_ms: Histogram,
+}
+
+impl DWalletRpcMetrics {
+    pub fn new(registry: &prometheus::Registry) -> Self {
+        Self {
+            requests_by_method: register_int_counter_vec_with_registry!(
+                "dwallet_rpc_requests_by_method",
+                "Number of dWallet read API requests by method",
+                &["method"],
+                registry
+            )
+            .unwrap(),
+            errors_by_method: register_int_counter_vec_with_registry!(
+                "dwallet_rpc_errors_by_method",
+                "Number of failed dWallet read API requests by method",
+                &["method"],
+                registry
+            )
+            .unwrap(),
+            latency_by_method: HistogramVec::new_in_registry(
+                "dwallet_rpc_latency_by_method",
+                "The latency of dWallet read API requests by method, in ms",
+                &["method"],
+                registry,
+            ),
+            sign_event_subscriptions: register_int_gauge_with_registry!(
+                "dwallet_rpc_sign_event_subscriptions",
+                "Number of active sign event subscriptions",
+                registry
+            )
+            .unwrap(),
+            sign_event_subscriptions_rejected: register_int_counter_with_registry!(
+                "dwallet_rpc_sign_event_subscriptions_rejected",
+                "Number of sign event subscriptions rejected for exceeding the subscription limit",
+                registry
+            )
+            .unwrap(),
+            sign_event_notifications: register_int_counter_vec_with_registry!(
+                "dwallet_rpc_sign_event_notifications",
+                "Number of sign event notifications sent to subscribers, by kind",
+                &["kind"],
+                registry
+            )
+            .unwrap(),
+            sign_event_lag_ms: Histogram::new_in_registry(
+                "dwallet_rpc_sign_event_lag",
+                "The time from the execution of the transaction of a sign event to its \
+                 notification to a subscriber, in ms",
+                registry,
+            ),
+        }
+    }
+
+    pub fn new_for_tests() -> Self {
+        let registry = prometheus::Registry::new();
+        Self::new(&registry)
+    }
+}
+
 pub fn read_size_from_env(var_name: &str) -> Option<usize> {
     std::env::var(var_name)
         .ok()?
@@ -300,3 +378,5 @@ pub const CLIENT_TARGET_API_VERSION_HEADER: &str = "client-target-api-version";
 
 pub const TRANSIENT_ERROR_CODE: i32 = -32050;
 pub const TRANSACTION_EXECUTION_CLIENT_ERROR_CODE: i32 = -32002;
+/// The error of a request rejected for exceeding a rate limit of the server.
+pub const RATE_LIMITED_ERROR_CODE: i32 = -32005;
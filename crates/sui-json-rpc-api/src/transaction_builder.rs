@@ -7,7 +7,8 @@ use jsonrpsee::proc_macros::rpc;
 
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    RPCTransactionRequestParams, SuiTransactionBlockBuilderMode, SuiTypeTag, TransactionBlockBytes,
+    RPCTransactionRequestParams, SignBatch, SignRequestParams, SuiTransactionBlockBuilderMode,
+    SuiTypeTag, TransactionBlockBytes,
 };
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::{ObjectID, SuiAddress};
@@ -249,4 +250,19 @@ pub trait TransactionBuilder {
         /// the gas budget, the transaction will fail if the gas cost exceed the budget
         gas_budget: BigInt<u64>,
     ) -> RpcResult<TransactionBlockBytes>;
+
+    /// Create unsigned transactions signing the messages of many presigns with their dWallets,
+    /// packed into as few transactions as the transaction limits of the current epoch allow. Each
+    /// transaction pays for its gas with a coin of its own, for the transactions to be executed
+    /// concurrently.
+    #[method(name = "batchSign")]
+    async fn batch_sign(
+        &self,
+        /// the transaction signer's Sui address, which owns the presigns and the capabilities of their dWallets
+        signer: SuiAddress,
+        /// the sign requests, by their presign, the requests of a dWallet capability are packed into the same transaction
+        requests: Vec<SignRequestParams>,
+        /// the gas budget of each transaction, the transaction will fail if the gas cost exceed the budget
+        gas_budget: BigInt<u64>,
+    ) -> RpcResult<SignBatch>;
 }
@@ -8,6 +8,7 @@ pub use balance_changes::*;
 pub use object_changes::*;
 pub use sui_checkpoint::*;
 pub use sui_coin::*;
+pub use sui_dwallet::*;
 pub use sui_event::*;
 pub use sui_extended::*;
 pub use sui_governance::*;
@@ -26,6 +27,7 @@ mod balance_changes;
 mod object_changes;
 mod sui_checkpoint;
 mod sui_coin;
+mod sui_dwallet;
 mod sui_event;
 mod sui_extended;
 mod sui_governance;
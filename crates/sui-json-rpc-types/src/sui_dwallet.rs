@@ -0,0 +1,514 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use std::collections::BTreeMap;
+
+use fastcrypto::encoding::{Base64, Hex};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sui_types::base_types::{AuthorityName, EpochId, ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use sui_types::eth_proofs::{Account, Log, Receipt};
+use sui_types::external_address::ExternalAddresses;
+use sui_types::sui_serde::BigInt;
+
+use crate::{Page, TransactionBlockBytes};
+
+/// The public data of a dWallet, as returned by `dwallet_getDWallet`.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DWalletInfo {
+    pub dwallet_id: ObjectID,
+    pub dwallet_cap_id: ObjectID,
+    /// The curve of the key of the dWallet, e.g. `secp256k1`.
+    pub curve: String,
+    /// The signature scheme of the dWallet, e.g. `ecdsa`.
+    pub scheme: String,
+    /// The public output of the DKG that created the dWallet.
+    #[schemars(with = "Base64")]
+    #[serde_as(as = "Base64")]
+    pub dkg_output: Vec<u8>,
+    /// The compressed SEC1 public key of the dWallet.
+    #[schemars(with = "Base64")]
+    #[serde_as(as = "Base64")]
+    pub public_key: Vec<u8>,
+    /// The address that controls the dWallet: the owner of its capability, or of the signing
+    /// policy holding it. None if the capability is held by an object.
+    pub owner: Option<SuiAddress>,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub creation_epoch: EpochId,
+    /// The shared `SigningPolicy` holding the capability of the dWallet, if any.
+    pub signing_policy_id: Option<ObjectID>,
+    /// The addresses of the dWallet on external chains, derived from its public key.
+    pub external_addresses: ExternalAddresses,
+}
+
+pub type DWalletPage = Page<SuiOwnedDWallet, ObjectID>;
+pub type EncryptedUserSharePage = Page<SuiEncryptedUserShare, ObjectID>;
+
+/// The status of a dWallet of `dwallet_getDWalletsByOwner`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SuiDWalletStatus {
+    /// The DKG of the dWallet is in progress, or its output awaits its owner to create the dWallet.
+    Pending,
+    /// The dWallet was created, and signs messages.
+    Active,
+}
+
+/// A dWallet of an address, by the `DWalletCap` it owns, as returned by
+/// `dwallet_getDWalletsByOwner`.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiOwnedDWallet {
+    pub dwallet_cap_id: ObjectID,
+    pub status: SuiDWalletStatus,
+    /// The curve of the key of the dWallet, e.g. `secp256k1`.
+    pub curve: String,
+    /// The public data of the dWallet, once it is active.
+    pub dwallet: Option<DWalletInfo>,
+}
+
+/// The criteria of `dwallet_getDWalletsByOwner`, all of which a listed dWallet matches.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DWalletQuery {
+    /// Only the dWallets of this curve, e.g. `secp256k1`.
+    pub curve: Option<String>,
+    /// Only the dWallets of this status.
+    pub status: Option<SuiDWalletStatus>,
+}
+
+impl DWalletQuery {
+    pub fn matches(&self, dwallet: &SuiOwnedDWallet) -> bool {
+        self.curve.iter().all(|curve| *curve == dwallet.curve)
+            && self.status.iter().all(|status| *status == dwallet.status)
+    }
+}
+
+/// The presigns of a dWallet, as returned by `dwallet_getPresignPoolStatus`, for a page of the
+/// presigns owned by `owner`.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiPresignPoolStatus {
+    pub dwallet_id: ObjectID,
+    /// The address whose presigns are available. None if the dWallet has no owner.
+    pub owner: Option<SuiAddress>,
+    /// The presigns of the dWallet in the page of presigns owned by `owner`, that no sign session
+    /// consumed yet. The presigns available in total are the sum over the pages.
+    pub available: u64,
+    /// The presign sessions of the dWallet started this epoch, whose presign the network did not
+    /// create yet. Only counted for the first page.
+    pub pending: Option<u64>,
+    /// The presigns of the dWallet consumed by the sign sessions started this epoch. Only counted
+    /// for the first page.
+    pub consumed_this_epoch: Option<u64>,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub epoch: EpochId,
+    /// The cursor of the next page: the last presign of `owner` in this page.
+    pub next_cursor: Option<ObjectID>,
+    pub has_next_page: bool,
+}
+
+/// The parameters of the MPC protocols of the network, as returned by
+/// `dwallet_getMpcParameters`, for requests to be validated before they are sent.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiMpcParameters {
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub epoch: EpochId,
+    /// The protocol version of the epoch, which versions the MPC protocols.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub protocol_version: u64,
+    /// The Tiresias public parameters of the network, hex encoded, under which the secret key
+    /// shares of the network are encrypted. None if the signature MPC is not enabled.
+    pub tiresias_public_parameters: Option<String>,
+    /// The curves of the keys of dWallets, e.g. `secp256k1`.
+    pub curves: Vec<String>,
+    /// The hash functions of the messages of sign sessions, by their code: `keccak256` is 0.
+    pub hash_functions: Vec<String>,
+    /// The feature flags of the protocol version, including `signature_mpc`.
+    pub feature_flags: BTreeMap<String, bool>,
+}
+
+/// The status of a sign session.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SuiSignSessionStatus {
+    /// The network has not signed the messages of the session yet.
+    Pending,
+    /// The network signed the messages of the session.
+    Signed,
+    /// The epoch of the session ended before the network signed its messages, which it never
+    /// will: the sign MPC sessions do not outlive their epoch.
+    Expired,
+}
+
+/// A party of the sign MPC: a validator of the committee of the epoch of a session.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiSignSessionParty {
+    pub party_id: u16,
+    pub authority: AuthorityName,
+}
+
+/// The status of a sign session, as returned by `dwallet_getSignSession`.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiSignSession {
+    /// The ID of the `SignSession` object, which is the `SignatureMPCSessionID` of the session.
+    pub session_id: ObjectID,
+    pub dwallet_id: ObjectID,
+    pub dwallet_cap_id: ObjectID,
+    pub sender: SuiAddress,
+    pub status: SuiSignSessionStatus,
+    /// The epoch the session was created in, whose committee signs its messages.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub epoch: EpochId,
+    /// The hash function of the messages, `keccak256` or `sha256`.
+    pub hash: String,
+    /// The digests of the messages, hashed with `hash`, as signed.
+    #[schemars(with = "Vec<Base64>")]
+    #[serde_as(as = "Vec<Base64>")]
+    pub message_digests: Vec<Vec<u8>>,
+    /// The parties of the session, each of which contributes a decryption share of every
+    /// signature.
+    pub parties: Vec<SuiSignSessionParty>,
+    /// The party that collects the decryption shares and submits the signatures.
+    pub aggregator_party_id: u16,
+    /// When the messages were signed, in milliseconds since the Unix epoch.
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    pub completed_at_ms: Option<u64>,
+    pub sign_output_id: Option<ObjectID>,
+    /// One 64-byte `r || s` signature per message, in order, once signed.
+    #[schemars(with = "Option<Vec<Base64>>")]
+    #[serde_as(as = "Option<Vec<Base64>>")]
+    pub signatures: Option<Vec<Vec<u8>>>,
+}
+
+/// The sign sessions whose notifications `dwallet_subscribeSignEvents` sends.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, JsonSchema, PartialEq, Eq)]
+pub enum SignEventFilter {
+    /// The sign sessions of a dWallet, by its ID.
+    DWallet(ObjectID),
+    /// A sign session, by the ID of its `SignSession` object.
+    Session(ObjectID),
+    /// The sign sessions requested by an address.
+    Owner(SuiAddress),
+}
+
+impl SignEventFilter {
+    /// Whether the session `session_id` of the dWallet `dwallet_id`, requested by `sender`, is
+    /// selected by the filter.
+    pub fn matches(
+        &self,
+        session_id: &ObjectID,
+        dwallet_id: &ObjectID,
+        sender: &SuiAddress,
+    ) -> bool {
+        match self {
+            Self::DWallet(id) => id == dwallet_id,
+            Self::Session(id) => id == session_id,
+            Self::Owner(owner) => owner == sender,
+        }
+    }
+}
+
+/// A notification of `dwallet_subscribeSignEvents`: a sign session was signed, or failed.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SuiSignEvent {
+    /// The network signed the messages of the session.
+    #[serde(rename_all = "camelCase")]
+    Completed {
+        session_id: ObjectID,
+        dwallet_id: ObjectID,
+        dwallet_cap_id: ObjectID,
+        sender: SuiAddress,
+        sign_output_id: ObjectID,
+        /// One 64-byte `r || s` signature per message, in order.
+        #[schemars(with = "Vec<Base64>")]
+        #[serde_as(as = "Vec<Base64>")]
+        signatures: Vec<Vec<u8>>,
+        /// The transaction that created the sign output.
+        tx_digest: TransactionDigest,
+        /// When the sign output was created, in milliseconds since the Unix epoch.
+        #[schemars(with = "Option<BigInt<u64>>")]
+        #[serde_as(as = "Option<BigInt<u64>>")]
+        timestamp_ms: Option<u64>,
+    },
+    /// The epoch of the session ended before the network signed its messages, which it never
+    /// will: the sign MPC sessions do not outlive their epoch.
+    #[serde(rename_all = "camelCase")]
+    Failed {
+        session_id: ObjectID,
+        dwallet_id: ObjectID,
+        dwallet_cap_id: ObjectID,
+        sender: SuiAddress,
+        /// The epoch that ended.
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "BigInt<u64>")]
+        epoch: EpochId,
+    },
+}
+
+impl SuiSignEvent {
+    pub fn session_id(&self) -> &ObjectID {
+        match self {
+            Self::Completed { session_id, .. } | Self::Failed { session_id, .. } => session_id,
+        }
+    }
+}
+
+/// The user share of a dWallet, encrypted to the `EncryptionKey` of the recipient of the dWallet,
+/// as returned by `dwallet_getEncryptedUserShares`.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiEncryptedUserShare {
+    /// The ID of the `EncryptedUserShare` object.
+    pub share_id: ObjectID,
+    pub dwallet_id: ObjectID,
+    /// The `EncryptionKey` the share is encrypted to.
+    pub encryption_key_id: ObjectID,
+    #[schemars(with = "Base64")]
+    #[serde_as(as = "Base64")]
+    pub encrypted_user_share: Vec<u8>,
+    /// The address that sent the share, along with the capability of the dWallet.
+    pub sender: SuiAddress,
+    /// The recipient of the share, which owns it.
+    pub owner: SuiAddress,
+}
+
+/// How far the transfer of a dWallet with its encrypted user share can be verified.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SuiDWalletTransferState {
+    /// The share is not encrypted to an `EncryptionKey` owned by its recipient, who cannot
+    /// decrypt it.
+    InvalidEncryptionKey,
+    /// The share is encrypted to the key of its recipient, who does not control the dWallet.
+    AwaitingCap,
+    /// The share is encrypted to the key of its recipient, who controls the dWallet: the
+    /// recipient completes the transfer by decrypting the share, and checking it against the DKG
+    /// output of the dWallet.
+    Verified,
+}
+
+/// The transfer of a dWallet to the recipient of its encrypted user share, as returned by
+/// `dwallet_getTransferStatus`.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiDWalletTransferStatus {
+    pub share: SuiEncryptedUserShare,
+    pub state: SuiDWalletTransferState,
+    /// The address that controls the dWallet: the owner of its capability, or of the signing
+    /// policy holding it. None if the capability is held by an object.
+    pub dwallet_owner: Option<SuiAddress>,
+    /// The public key of the `EncryptionKey` of the share, if it is owned by the recipient.
+    #[schemars(with = "Option<Base64>")]
+    #[serde_as(as = "Option<Base64>")]
+    pub encryption_key: Option<Vec<u8>>,
+}
+
+/// A request of `unsafe_batchSign`: the signature of the messages of a presign by its dWallet,
+/// the last round of the sign flow, once the network created the presign.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SignRequestParams {
+    /// The `Presign` of the messages, owned by the signer.
+    pub presign_id: ObjectID,
+    /// The `PresignSessionOutput` of the presign session of the presign, owned by the signer.
+    pub presign_session_output_id: ObjectID,
+    /// The BCS serialized partial signatures of the user, one per message of the presign
+    /// session, in order.
+    #[schemars(with = "Base64")]
+    #[serde_as(as = "Base64")]
+    pub public_nonce_encrypted_partial_signature_and_proofs: Vec<u8>,
+}
+
+/// An unsigned transaction of `unsafe_batchSign`, signing the messages of some of its requests.
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SignBatchTransaction {
+    pub transaction: TransactionBlockBytes,
+    /// The indices of the requests signed by the transaction, in the order of its sign sessions:
+    /// once executed, the transaction emits a `SignSessionCreated` event per request, in order,
+    /// holding the ID of its `SignSession`.
+    pub requests: Vec<u64>,
+}
+
+/// The transactions of `unsafe_batchSign`, each of which is signed and executed on its own.
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SignBatch {
+    pub transactions: Vec<SignBatchTransaction>,
+}
+
+/// A proof of Ethereum state to verify with `dwallet_verifyEthereumProof`, as served by an
+/// Ethereum RPC provider, with bytes in hex.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum EthereumProof {
+    /// The value of a storage slot of an account: the `accountProof` and a `storageProof` of
+    /// `eth_getProof`.
+    #[serde(rename_all = "camelCase")]
+    Storage {
+        #[schemars(with = "String")]
+        #[serde_as(as = "Hex")]
+        address: [u8; 20],
+        /// The RLP encoded nodes of the state trie on the path to the account.
+        #[schemars(with = "Vec<String>")]
+        #[serde_as(as = "Vec<Hex>")]
+        account_proof: Vec<Vec<u8>>,
+        #[schemars(with = "String")]
+        #[serde_as(as = "Hex")]
+        slot: [u8; 32],
+        /// The RLP encoded nodes of the storage trie of the account on the path to the slot.
+        #[schemars(with = "Vec<String>")]
+        #[serde_as(as = "Vec<Hex>")]
+        storage_proof: Vec<Vec<u8>>,
+    },
+    /// The receipt of a transaction.
+    #[serde(rename_all = "camelCase")]
+    Receipt {
+        /// The index of the transaction in its block.
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "BigInt<u64>")]
+        transaction_index: u64,
+        /// The RLP encoded nodes of the receipts trie of the block on the path to the receipt.
+        #[schemars(with = "Vec<String>")]
+        #[serde_as(as = "Vec<Hex>")]
+        proof: Vec<Vec<u8>>,
+    },
+}
+
+/// An Ethereum account, as proven by an account proof.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiEthereumAccount {
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub nonce: u64,
+    /// The balance in wei, big-endian.
+    #[schemars(with = "String")]
+    #[serde_as(as = "Hex")]
+    pub balance: [u8; 32],
+    #[schemars(with = "String")]
+    #[serde_as(as = "Hex")]
+    pub storage_root: [u8; 32],
+    #[schemars(with = "String")]
+    #[serde_as(as = "Hex")]
+    pub code_hash: [u8; 32],
+}
+
+impl From<Account> for SuiEthereumAccount {
+    fn from(account: Account) -> Self {
+        Self {
+            nonce: account.nonce,
+            balance: account.balance,
+            storage_root: account.storage_root,
+            code_hash: account.code_hash,
+        }
+    }
+}
+
+/// A log emitted by an Ethereum transaction.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiEthereumLog {
+    #[schemars(with = "String")]
+    #[serde_as(as = "Hex")]
+    pub address: [u8; 20],
+    #[schemars(with = "Vec<String>")]
+    #[serde_as(as = "Vec<Hex>")]
+    pub topics: Vec<[u8; 32]>,
+    #[schemars(with = "String")]
+    #[serde_as(as = "Hex")]
+    pub data: Vec<u8>,
+}
+
+impl From<Log> for SuiEthereumLog {
+    fn from(log: Log) -> Self {
+        Self {
+            address: log.address,
+            topics: log.topics,
+            data: log.data,
+        }
+    }
+}
+
+/// The value proven by an [EthereumProof].
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum EthereumProvenValue {
+    #[serde(rename_all = "camelCase")]
+    Storage {
+        /// None if the account does not exist.
+        account: Option<SuiEthereumAccount>,
+        /// The value of the slot, big-endian. Zero if the slot is not set.
+        #[schemars(with = "String")]
+        #[serde_as(as = "Hex")]
+        value: [u8; 32],
+    },
+    #[serde(rename_all = "camelCase")]
+    Receipt {
+        /// The EIP-2718 type of the transaction, 0 for legacy transactions.
+        transaction_type: u8,
+        /// Whether the transaction succeeded.
+        status: bool,
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "BigInt<u64>")]
+        cumulative_gas_used: u64,
+        logs: Vec<SuiEthereumLog>,
+    },
+}
+
+impl From<Receipt> for EthereumProvenValue {
+    fn from(receipt: Receipt) -> Self {
+        Self::Receipt {
+            transaction_type: receipt.transaction_type,
+            status: receipt.status,
+            cumulative_gas_used: receipt.cumulative_gas_used,
+            logs: receipt.logs.into_iter().map(SuiEthereumLog::from).collect(),
+        }
+    }
+}
+
+/// An Ethereum proof verified against a block finalized by a light client, as returned by
+/// `dwallet_verifyEthereumProof`.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiedEthereumProof {
+    pub light_client_id: ObjectID,
+    /// The ID of the Ethereum network of the light client.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub chain_id: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub block_number: u64,
+    #[schemars(with = "String")]
+    #[serde_as(as = "Hex")]
+    pub block_hash: Vec<u8>,
+    pub value: EthereumProvenValue,
+}
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
+use anyhow::anyhow;
 use fastcrypto::encoding::{Base58, Base64};
 use move_core_types::annotated_value::MoveStructLayout;
 use move_core_types::identifier::Identifier;
@@ -13,8 +14,12 @@ use serde_with::{serde_as, DisplayFromStr};
 use std::fmt;
 use std::fmt::Display;
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
-use sui_types::error::SuiResult;
+use sui_types::error::{SuiError, SuiResult};
 use sui_types::event::{Event, EventEnvelope, EventID};
+use sui_types::signature_mpc::{
+    DWalletCreatedEvent, DWalletEvent, DWalletEventKind, PresignCreatedEvent,
+    PresignSessionCreatedEvent, SignOutputCreatedEvent, SignSessionCreatedEvent,
+};
 use sui_types::sui_serde::BigInt;
 
 use json_to_table::json_to_table;
@@ -93,6 +98,49 @@ impl From<SuiEvent> for Event {
     }
 }
 
+impl TryFrom<SuiEvent> for DWalletEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(event: SuiEvent) -> Result<Self, Self::Error> {
+        DWalletEvent::try_from_event(&event.type_, &event.bcs)
+            .ok_or_else(|| anyhow!("Event of type {} is not a dWallet event", event.type_))?
+            .map_err(|e| {
+                anyhow!(
+                    "Cannot deserialize dWallet event of type {}: {e}",
+                    event.type_
+                )
+            })
+    }
+}
+
+/// Implement `TryFrom<SuiEvent>` for the event of each variant of [DWalletEvent], failing for
+/// events of other types.
+macro_rules! impl_try_from_sui_event {
+    ($($variant:ident($event:ty)),* $(,)?) => {$(
+        impl TryFrom<SuiEvent> for $event {
+            type Error = anyhow::Error;
+
+            fn try_from(event: SuiEvent) -> Result<Self, Self::Error> {
+                match DWalletEvent::try_from(event)? {
+                    DWalletEvent::$variant(event) => Ok(event),
+                    event => Err(anyhow!(
+                        "Expected a {} event, got {event:?}",
+                        stringify!($variant)
+                    )),
+                }
+            }
+        }
+    )*};
+}
+
+impl_try_from_sui_event!(
+    DWalletCreated(DWalletCreatedEvent),
+    PresignSessionCreated(PresignSessionCreatedEvent),
+    PresignCreated(PresignCreatedEvent),
+    SignSessionCreated(SignSessionCreatedEvent),
+    SignOutputCreated(SignOutputCreatedEvent),
+);
+
 impl SuiEvent {
     pub fn try_from(
         event: Event,
@@ -260,6 +308,20 @@ pub enum EventFilter {
         #[serde_as(as = "BigInt<u64>")]
         end_time: u64,
     },
+    /// Return the lifecycle events of a dWallet: its creation, and the presign and sign sessions
+    /// of its owner. Only supported by event subscriptions.
+    DWallet(
+        /// the ID of the dWallet object
+        ObjectID,
+    ),
+    /// Return the events of a presign or sign session of a dWallet. Supported by event
+    /// subscriptions and the event queries of full nodes.
+    DWalletSession(
+        /// the ID of the session object
+        ObjectID,
+    ),
+    /// Return the dWallet events of the given kind.
+    DWalletEventKind(DWalletEventKind),
 
     All(Vec<EventFilter>),
     Any(Vec<EventFilter>),
@@ -302,6 +364,13 @@ impl EventFilter {
             EventFilter::MoveEventModule { package, module } => {
                 &item.type_.module == module && &ObjectID::from(item.type_.address) == package
             }
+            EventFilter::DWallet(dwallet_id) => {
+                matches!(dwallet_event(item)?, Some(event) if event.dwallet_id() == dwallet_id)
+            }
+            EventFilter::DWalletSession(session_id) => {
+                matches!(dwallet_event(item)?, Some(event) if event.session_id() == Some(session_id))
+            }
+            EventFilter::DWalletEventKind(kind) => item.type_ == kind.event_type(),
         })
     }
 
@@ -313,6 +382,18 @@ impl EventFilter {
     }
 }
 
+/// The dWallet event of `item`, if it is one.
+fn dwallet_event(item: &SuiEvent) -> SuiResult<Option<DWalletEvent>> {
+    DWalletEvent::try_from_event(&item.type_, &item.bcs)
+        .transpose()
+        .map_err(|e| SuiError::ObjectDeserializationError {
+            error: format!(
+                "Failed to deserialize the dWallet event {}: {e}",
+                item.type_
+            ),
+        })
+}
+
 impl Filter<SuiEvent> for EventFilter {
     fn matches(&self, item: &SuiEvent) -> bool {
         let _scope = monitored_scope("EventFilter::matches");
@@ -12,7 +12,7 @@ use serde_with::serde_as;
 use serde_with::DisplayFromStr;
 
 use sui_types::base_types::AuthorityName;
-use sui_types::base_types::{EpochId, ObjectID};
+use sui_types::base_types::{EpochId, ObjectID, SuiAddress};
 use sui_types::committee::Committee;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::sui_serde::BigInt;
@@ -22,6 +22,7 @@ use crate::Page;
 
 pub type EpochPage = Page<EpochInfo, BigInt<u64>>;
 pub type EpochMetricsPage = Page<EpochMetrics, BigInt<u64>>;
+pub type EpochSigningMetricsPage = Page<EpochSigningMetrics, BigInt<u64>>;
 
 #[serde_as]
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -194,3 +195,63 @@ pub struct AddressMetrics {
     pub cumulative_active_addresses: u64,
     pub daily_active_addresses: u64,
 }
+
+/// The signing health of an ended epoch: its sign sessions, how long the network took to sign
+/// them, and how each validator of the committee of the epoch aggregated the sessions assigned
+/// to it.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochSigningMetrics {
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub epoch: EpochId,
+    /// Sign sessions of the epoch whose messages the network signed
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub sign_sessions_completed: u64,
+    /// Sign sessions of the epoch the network did not sign before the epoch ended
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub sign_sessions_failed: u64,
+    /// Percentiles of the time from the creation of a sign session to its sign output, None if
+    /// no sign session of the epoch completed
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    pub latency_p50_ms: Option<u64>,
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    pub latency_p90_ms: Option<u64>,
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    pub latency_p99_ms: Option<u64>,
+    /// Presigns the network created in the epoch
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub presigns_created: u64,
+    /// Presigns consumed by the sign sessions of the epoch, one per session
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub presigns_consumed: u64,
+    /// The validators of the committee of the epoch
+    pub validators: Vec<ValidatorSigningMetrics>,
+}
+
+/// The sign sessions of an epoch a validator was the aggregator of, which collects the
+/// decryption shares of the committee and submits the signatures.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorSigningMetrics {
+    pub sui_address: SuiAddress,
+    pub name: String,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub aggregated_sessions: u64,
+    /// Aggregated sessions whose messages were signed
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub completed_sessions: u64,
+    /// `completed_sessions / aggregated_sessions`, 1 if the validator aggregated no session
+    pub participation_rate: f64,
+}
@@ -4,6 +4,7 @@
 use std::str::FromStr;
 
 use anyhow::anyhow;
+use fastcrypto::encoding::{Base64, Encoding, Hex};
 use move_core_types::annotated_value::{MoveStruct, MoveValue};
 use move_core_types::ident_str;
 use move_core_types::identifier::Identifier;
@@ -12,11 +13,23 @@ use serde_json::json;
 
 use sui_types::base_types::{ObjectDigest, SequenceNumber};
 use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::external_address::ExternalAddresses;
 use sui_types::gas_coin::GasCoin;
+use sui_types::id::ID;
 use sui_types::object::{MoveObject, Owner};
-use sui_types::{parse_sui_struct_tag, MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+use sui_types::signature_mpc::{
+    DWalletCreatedEvent, DWalletEvent, DWalletEventKind, SignOutputCreatedEvent,
+    DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME, DWALLET_CREATED_EVENT_STRUCT_NAME,
+};
+use sui_types::{
+    parse_sui_struct_tag, MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_ADDRESS,
+};
 
-use crate::{ObjectChange, SuiMoveStruct, SuiMoveValue};
+use crate::{
+    DWalletInfo, DWalletQuery, EventFilter, Filter, ObjectChange, SignEventFilter,
+    SuiDWalletStatus, SuiEvent, SuiMoveStruct, SuiMoveValue, SuiMpcParameters, SuiOwnedDWallet,
+    SuiSignEvent, SuiSignSession, SuiSignSessionStatus,
+};
 
 #[test]
 fn test_move_value_to_sui_coin() {
@@ -196,3 +209,193 @@ fn test_type_tag_struct_tag_devnet_inc_222() {
         assert_eq!(oc, deser);
     }
 }
+
+#[test]
+fn test_dwallet_event_try_from_sui_event() {
+    let created = DWalletCreatedEvent {
+        dwallet_id: ID::new(ObjectID::random()),
+        dwallet_cap_id: ID::new(ObjectID::random()),
+        sender: SuiAddress::random_for_testing_only(),
+    };
+    let event = SuiEvent {
+        type_: StructTag {
+            address: SUI_SYSTEM_ADDRESS,
+            module: DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME.to_owned(),
+            name: DWALLET_CREATED_EVENT_STRUCT_NAME.to_owned(),
+            type_params: vec![],
+        },
+        bcs: bcs::to_bytes(&created).unwrap(),
+        ..SuiEvent::random_for_testing()
+    };
+    assert_eq!(
+        DWalletEvent::try_from(event.clone()).unwrap(),
+        DWalletEvent::DWalletCreated(created.clone())
+    );
+    assert_eq!(
+        DWalletCreatedEvent::try_from(event.clone()).unwrap(),
+        created
+    );
+
+    // An event of another dWallet event type, or of a type which is not a dWallet event.
+    assert!(SignOutputCreatedEvent::try_from(event).is_err());
+    assert!(DWalletEvent::try_from(SuiEvent::random_for_testing()).is_err());
+}
+
+#[test]
+fn test_dwallet_event_filters() {
+    let output = SignOutputCreatedEvent {
+        session_id: ID::new(ObjectID::random()),
+        sign_output_id: ID::new(ObjectID::random()),
+        dwallet_id: ID::new(ObjectID::random()),
+        dwallet_cap_id: ID::new(ObjectID::random()),
+        signatures: vec![vec![1, 2, 3]],
+        sender: SuiAddress::random_for_testing_only(),
+    };
+    let event = SuiEvent {
+        type_: DWalletEventKind::SignOutputCreated.event_type(),
+        bcs: bcs::to_bytes(&output).unwrap(),
+        ..SuiEvent::random_for_testing()
+    };
+
+    assert!(EventFilter::DWallet(output.dwallet_id.bytes).matches(&event));
+    assert!(EventFilter::DWalletSession(output.session_id.bytes).matches(&event));
+    assert!(EventFilter::DWalletEventKind(DWalletEventKind::SignOutputCreated).matches(&event));
+
+    assert!(!EventFilter::DWallet(output.session_id.bytes).matches(&event));
+    assert!(!EventFilter::DWalletSession(output.dwallet_id.bytes).matches(&event));
+    assert!(!EventFilter::DWalletEventKind(DWalletEventKind::SignSessionCreated).matches(&event));
+    // Events which are not dWallet events do not match.
+    let other = SuiEvent::random_for_testing();
+    assert!(!EventFilter::DWallet(output.dwallet_id.bytes).matches(&other));
+}
+
+#[test]
+fn test_dwallet_info_serde() {
+    let public_key =
+        Hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap();
+    let dwallet_id = ObjectID::random();
+    let dwallet_cap_id = ObjectID::random();
+    let info = DWalletInfo {
+        dwallet_id,
+        dwallet_cap_id,
+        curve: "secp256k1".to_string(),
+        scheme: "ecdsa".to_string(),
+        dkg_output: vec![1, 2, 3],
+        public_key: public_key.clone(),
+        owner: None,
+        creation_epoch: 7,
+        signing_policy_id: None,
+        external_addresses: ExternalAddresses::new(&public_key).unwrap(),
+    };
+
+    let json = serde_json::to_value(&info).unwrap();
+    assert_eq!(json["dwalletCapId"], json!(dwallet_cap_id));
+    assert_eq!(json["dkgOutput"], json!(Base64::encode([1, 2, 3])));
+    assert_eq!(json["publicKey"], json!(Base64::encode(&public_key)));
+    assert_eq!(json["creationEpoch"], json!("7"));
+    assert_eq!(json["owner"], serde_json::Value::Null);
+    assert_eq!(
+        json["externalAddresses"]["ethereum"],
+        json!("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf")
+    );
+    assert_eq!(serde_json::from_value::<DWalletInfo>(json).unwrap(), info);
+}
+
+#[test]
+fn test_mpc_parameters_serde() {
+    let parameters = SuiMpcParameters {
+        epoch: 3,
+        protocol_version: 2,
+        tiresias_public_parameters: Some("97431848".to_string()),
+        curves: vec!["secp256k1".to_string()],
+        hash_functions: vec!["keccak256".to_string(), "sha256".to_string()],
+        feature_flags: [("signature_mpc".to_string(), true)].into(),
+    };
+
+    let json = serde_json::to_value(&parameters).unwrap();
+    assert_eq!(json["protocolVersion"], json!("2"));
+    assert_eq!(json["tiresiasPublicParameters"], json!("97431848"));
+    assert_eq!(json["hashFunctions"], json!(["keccak256", "sha256"]));
+    assert_eq!(json["featureFlags"]["signature_mpc"], json!(true));
+    assert_eq!(
+        serde_json::from_value::<SuiMpcParameters>(json).unwrap(),
+        parameters
+    );
+}
+
+#[test]
+fn test_sign_session_serde() {
+    let session = SuiSignSession {
+        session_id: ObjectID::random(),
+        dwallet_id: ObjectID::random(),
+        dwallet_cap_id: ObjectID::random(),
+        sender: SuiAddress::random_for_testing_only(),
+        status: SuiSignSessionStatus::Signed,
+        epoch: 3,
+        hash: "keccak256".to_string(),
+        message_digests: vec![vec![7; 32]],
+        parties: vec![],
+        aggregator_party_id: 2,
+        completed_at_ms: Some(1_700_000_000_000),
+        sign_output_id: Some(ObjectID::random()),
+        signatures: Some(vec![vec![9; 64]]),
+    };
+
+    let json = serde_json::to_value(&session).unwrap();
+    assert_eq!(json["status"], json!("signed"));
+    assert_eq!(json["epoch"], json!("3"));
+    assert_eq!(json["messageDigests"], json!([Base64::encode([7; 32])]));
+    assert_eq!(json["aggregatorPartyId"], json!(2));
+    assert_eq!(json["completedAtMs"], json!("1700000000000"));
+    assert_eq!(json["signatures"], json!([Base64::encode([9; 64])]));
+    assert_eq!(
+        serde_json::from_value::<SuiSignSession>(json).unwrap(),
+        session
+    );
+}
+
+#[test]
+fn test_sign_event_serde() {
+    let session_id = ObjectID::random();
+    let filter: SignEventFilter = serde_json::from_value(json!({ "Session": session_id })).unwrap();
+    assert_eq!(filter, SignEventFilter::Session(session_id));
+
+    let event = SuiSignEvent::Failed {
+        session_id,
+        dwallet_id: ObjectID::random(),
+        dwallet_cap_id: ObjectID::random(),
+        sender: SuiAddress::random_for_testing_only(),
+        epoch: 4,
+    };
+    let json = serde_json::to_value(&event).unwrap();
+    assert_eq!(json["type"], json!("failed"));
+    assert_eq!(json["sessionId"], json!(session_id));
+    assert_eq!(json["epoch"], json!("4"));
+    assert_eq!(serde_json::from_value::<SuiSignEvent>(json).unwrap(), event);
+}
+
+#[test]
+fn test_dwallet_query_matches() {
+    let pending = SuiOwnedDWallet {
+        dwallet_cap_id: ObjectID::random(),
+        status: SuiDWalletStatus::Pending,
+        curve: "secp256k1".to_string(),
+        dwallet: None,
+    };
+    assert!(DWalletQuery::default().matches(&pending));
+
+    let query: DWalletQuery =
+        serde_json::from_value(json!({ "curve": "secp256k1", "status": "active" })).unwrap();
+    assert_eq!(query.status, Some(SuiDWalletStatus::Active));
+    assert!(!query.matches(&pending));
+    let active = SuiOwnedDWallet {
+        status: SuiDWalletStatus::Active,
+        ..pending.clone()
+    };
+    assert!(query.matches(&active));
+    let query = DWalletQuery {
+        curve: Some("ed25519".to_string()),
+        status: None,
+    };
+    assert!(!query.matches(&active));
+}
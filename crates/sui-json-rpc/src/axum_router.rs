@@ -1,8 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
+use std::net::SocketAddr;
 use std::sync::Arc;
 
+use axum::extract::ConnectInfo;
 use axum::extract::Json;
 use axum::extract::State;
 use futures::StreamExt;
@@ -18,8 +20,9 @@ use jsonrpsee::types::{ErrorObject, Id, InvalidRequest, Params, Request};
 use jsonrpsee::{core::server::rpc_module::Methods, server::logger::Logger};
 use serde_json::value::RawValue;
 
+use crate::rate_limiter::RpcRateLimiter;
 use crate::routing_layer::RpcRouter;
-use sui_json_rpc_api::CLIENT_TARGET_API_VERSION_HEADER;
+use sui_json_rpc_api::{CLIENT_TARGET_API_VERSION_HEADER, RATE_LIMITED_ERROR_CODE};
 
 pub const MAX_RESPONSE_SIZE: u32 = 2 << 30;
 
@@ -32,25 +35,34 @@ pub struct JsonRpcService<L> {
     /// Registered server methods.
     methods: Methods,
     rpc_router: RpcRouter,
+    rate_limiter: Arc<RpcRateLimiter>,
 }
 
 impl<L> JsonRpcService<L> {
-    pub fn new(methods: Methods, rpc_router: RpcRouter, logger: L) -> Self {
+    pub fn new(
+        methods: Methods,
+        rpc_router: RpcRouter,
+        logger: L,
+        rate_limiter: RpcRateLimiter,
+    ) -> Self {
         Self {
             methods,
             rpc_router,
             logger,
             id_provider: Arc::new(RandomIntegerIdProvider),
+            rate_limiter: Arc::new(rate_limiter),
         }
     }
 }
 
 impl<L: Logger> JsonRpcService<L> {
-    fn call_data(&self) -> CallData<'_, L> {
+    fn call_data<'a>(&'a self, client: Option<&'a str>) -> CallData<'a, L> {
         CallData {
             logger: &self.logger,
             methods: &self.methods,
             rpc_router: &self.rpc_router,
+            rate_limiter: &self.rate_limiter,
+            client,
             max_response_body_size: MAX_RESPONSE_SIZE,
             request_start: self.logger.on_request(TransportProtocol::Http),
         }
@@ -60,10 +72,13 @@ impl<L: Logger> JsonRpcService<L> {
         &'a self,
         bounded_subscriptions: BoundedSubscriptions,
         sink: &'b MethodSink,
+        client: Option<&'b str>,
     ) -> ws::WsCallData<'c, L> {
         ws::WsCallData {
             logger: &self.logger,
             methods: &self.methods,
+            rate_limiter: &self.rate_limiter,
+            client,
             max_response_body_size: MAX_RESPONSE_SIZE,
             request_start: self.logger.on_request(TransportProtocol::Http),
             bounded_subscriptions,
@@ -97,8 +112,15 @@ pub(crate) fn ok_response(body: String) -> hyper::Response<hyper::Body> {
     from_template(hyper::StatusCode::OK, body, JSON)
 }
 
+/// Create the JSON response of a request rejected for exceeding a rate limit.
+pub(crate) fn too_many_requests_response(body: String) -> hyper::Response<hyper::Body> {
+    const JSON: &str = "application/json; charset=utf-8";
+    from_template(hyper::StatusCode::TOO_MANY_REQUESTS, body, JSON)
+}
+
 pub async fn json_rpc_handler<L: Logger>(
     State(service): State<JsonRpcService<L>>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
     headers: HeaderMap,
     Json(raw_request): Json<Box<RawValue>>,
 ) -> impl axum::response::IntoResponse {
@@ -106,18 +128,26 @@ pub async fn json_rpc_handler<L: Logger>(
     let api_version = headers
         .get(CLIENT_TARGET_API_VERSION_HEADER)
         .and_then(|h| h.to_str().ok());
-    let response = process_raw_request(&service, api_version, raw_request.get()).await;
-
+    let client = service
+        .rate_limiter
+        .client(&headers, connect_info.map(|ConnectInfo(addr)| addr));
+    let response =
+        process_raw_request(&service, api_version, client.as_deref(), raw_request.get()).await;
+
+    if response.error_code == Some(RATE_LIMITED_ERROR_CODE) {
+        return too_many_requests_response(response.result);
+    }
     ok_response(response.result)
 }
 
 async fn process_raw_request<L: Logger>(
     service: &JsonRpcService<L>,
     api_version: Option<&str>,
+    client: Option<&str>,
     raw_request: &str,
 ) -> MethodResponse {
     if let Ok(request) = serde_json::from_str::<Request>(raw_request) {
-        process_request(request, api_version, service.call_data()).await
+        process_request(request, api_version, service.call_data(client)).await
     } else if let Ok(_batch) = serde_json::from_str::<Vec<&RawValue>>(raw_request) {
         MethodResponse::error(
             Id::Null,
@@ -137,6 +167,8 @@ async fn process_request<L: Logger>(
     let CallData {
         methods,
         rpc_router,
+        rate_limiter,
+        client,
         logger,
         max_response_body_size,
         request_start,
@@ -147,6 +179,24 @@ async fn process_request<L: Logger>(
     let name = rpc_router.route(&req.method, api_version);
     let id = req.id;
 
+    if let Err(error) = rate_limiter.check(name, client) {
+        logger.on_call(
+            name,
+            params,
+            logger::MethodKind::Unknown,
+            TransportProtocol::Http,
+        );
+        let response = MethodResponse::error(id, error);
+        logger.on_result(
+            name,
+            response.success,
+            response.error_code,
+            request_start,
+            TransportProtocol::Http,
+        );
+        return response;
+    }
+
     let response = match methods.method_with_name(name) {
         None => {
             logger.on_call(
@@ -217,6 +267,8 @@ pub(crate) struct CallData<'a, L: Logger> {
     logger: &'a L,
     methods: &'a Methods,
     rpc_router: &'a RpcRouter,
+    rate_limiter: &'a RpcRateLimiter,
+    client: Option<&'a str>,
     max_response_body_size: u32,
     request_start: L::Instant,
 }
@@ -246,6 +298,8 @@ pub mod ws {
         pub bounded_subscriptions: BoundedSubscriptions,
         pub id_provider: &'a dyn IdProvider,
         pub methods: &'a Methods,
+        pub rate_limiter: &'a RpcRateLimiter,
+        pub client: Option<&'a str>,
         pub max_response_body_size: u32,
         pub sink: &'a MethodSink,
         pub logger: &'a L,
@@ -258,11 +312,20 @@ pub mod ws {
     pub async fn ws_json_rpc_upgrade<L: Logger>(
         ws: WebSocketUpgrade,
         State(service): State<JsonRpcService<L>>,
+        connect_info: Option<ConnectInfo<SocketAddr>>,
+        headers: HeaderMap,
     ) -> Response {
-        ws.on_upgrade(|ws| ws_json_rpc_handler(ws, service))
+        let client = service
+            .rate_limiter
+            .client(&headers, connect_info.map(|ConnectInfo(addr)| addr));
+        ws.on_upgrade(|ws| ws_json_rpc_handler(ws, service, client))
     }
 
-    async fn ws_json_rpc_handler<L: Logger>(mut socket: WebSocket, service: JsonRpcService<L>) {
+    async fn ws_json_rpc_handler<L: Logger>(
+        mut socket: WebSocket,
+        service: JsonRpcService<L>,
+        client: Option<String>,
+    ) {
         #[allow(clippy::disallowed_methods)]
         let (tx, mut rx) = mpsc::unbounded::<String>();
         let sink = MethodSink::new_with_limit(tx, MAX_RESPONSE_SIZE, MAX_RESPONSE_SIZE);
@@ -274,7 +337,7 @@ pub mod ws {
                     if let Some(Ok(message)) = maybe_message {
                         if let Message::Text(msg) = message {
                             let response =
-                                process_raw_request(&service, &msg, bounded_subscriptions.clone(), &sink).await;
+                                process_raw_request(&service, &msg, bounded_subscriptions.clone(), &sink, client.as_deref()).await;
                             if let Some(response) = response {
                                 let _ = sink.send_raw(response.result);
                             }
@@ -297,9 +360,11 @@ pub mod ws {
         raw_request: &str,
         bounded_subscriptions: BoundedSubscriptions,
         sink: &MethodSink,
+        client: Option<&str>,
     ) -> Option<MethodResponse> {
         if let Ok(request) = serde_json::from_str::<Request>(raw_request) {
-            process_request(request, service.ws_call_data(bounded_subscriptions, sink)).await
+            let call = service.ws_call_data(bounded_subscriptions, sink, client);
+            process_request(request, call).await
         } else if let Ok(_batch) = serde_json::from_str::<Vec<&RawValue>>(raw_request) {
             Some(MethodResponse::error(
                 Id::Null,
@@ -317,6 +382,8 @@ pub mod ws {
     ) -> Option<MethodResponse> {
         let WsCallData {
             methods,
+            rate_limiter,
+            client,
             logger,
             max_response_body_size,
             request_start,
@@ -330,6 +397,24 @@ pub mod ws {
         let name = &req.method;
         let id = req.id;
 
+        if let Err(error) = rate_limiter.check(name, client) {
+            logger.on_call(
+                name,
+                params,
+                logger::MethodKind::Unknown,
+                TransportProtocol::WebSocket,
+            );
+            let response = MethodResponse::error(id, error);
+            logger.on_result(
+                name,
+                response.success,
+                response.error_code,
+                request_start,
+                TransportProtocol::WebSocket,
+            );
+            return Some(response);
+        }
+
         let response = match methods.method_with_name(name) {
             None => {
                 logger.on_call(
@@ -0,0 +1,1481 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail};
+use async_trait::async_trait;
+use fastcrypto::hash::{HashFunction, Keccak256, Sha256};
+use futures::stream::{self, StreamExt};
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::types::SubscriptionResult;
+use jsonrpsee::{RpcModule, SubscriptionSink};
+use move_core_types::ident_str;
+use move_core_types::identifier::IdentStr;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use serde::de::DeserializeOwned;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::{instrument, warn};
+
+use sui_core::authority::AuthorityState;
+use sui_json_rpc_api::{
+    validate_limit, DWalletReadApiOpenRpc, DWalletReadApiServer, DWalletRpcMetrics,
+    QUERY_MAX_RESULT_LIMIT,
+};
+use sui_json_rpc_types::{
+    DWalletInfo, DWalletPage, DWalletQuery, EncryptedUserSharePage, EthereumProof,
+    EthereumProvenValue, EventFilter, Page, SignEventFilter, SuiDWalletStatus,
+    SuiDWalletTransferState, SuiDWalletTransferStatus, SuiEncryptedUserShare, SuiEthereumAccount,
+    SuiEvent, SuiMpcParameters, SuiObjectDataFilter, SuiOwnedDWallet, SuiPresignPoolStatus,
+    SuiSignEvent, SuiSignSession, SuiSignSessionParty, SuiSignSessionStatus, TransactionFilter,
+    VerifiedEthereumProof,
+};
+use sui_open_rpc::Module;
+use sui_storage::key_value_store::TransactionKeyValueStore;
+use sui_types::base_types::{EpochId, ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use sui_types::dynamic_field::Field;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::error::UserInputError;
+use sui_types::eth_light_client::{EthereumLightClient, ExecutionHeader, Root};
+use sui_types::eth_proofs::{self, EthProofError};
+use sui_types::event::SystemEpochInfoEvent;
+use sui_types::external_address::{
+    dwallet_public_key_to_sec1, BitcoinAddresses, BitcoinNetwork, ExternalAddresses,
+};
+use sui_types::object::{Object, ObjectRead, Owner, PastObjectRead};
+use sui_types::signature_mpc::{
+    DKGSession, DKGSessionOutput, DWallet, DWalletCap, DWalletEvent, EncryptedUserShare,
+    EncryptionKey, Presign, SignData, SignOutput, SignSession, SigningPolicy,
+    SigningPolicyRemovedEvent, SigningPolicySetEvent, DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+    DWALLET_MODULE_NAME, DWALLET_POLICY_MODULE_NAME, SIGNING_POLICY_REMOVED_EVENT_STRUCT_NAME,
+    SIGNING_POLICY_SET_EVENT_STRUCT_NAME, SIGN_OUTPUT_CREATED_EVENT_STRUCT_NAME,
+    SIGN_SESSION_CREATED_EVENT_STRUCT_NAME,
+};
+use sui_types::sui_serde::BigInt;
+use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
+use sui_types::{SUI_SYSTEM_ADDRESS, SUI_SYSTEM_PACKAGE_ID};
+
+use crate::authority_state::StateRead;
+use crate::error::{Error, SuiRpcInputError};
+use crate::indexer_api::{spawn_subscription, DEFAULT_MAX_SUBSCRIPTIONS};
+use crate::{with_tracing, SuiRpcModule};
+
+/// The most events of the `dwallet_policy` module scanned for the signing policy of a dWallet.
+const MAX_POLICY_EVENTS: usize = 10_000;
+/// The most events of a filter scanned for the presigns of a dWallet in the current epoch.
+const MAX_EPOCH_EVENTS: usize = 1_000;
+/// The events of a sign session: its creation, and its sign output.
+const MAX_SIGN_SESSION_EVENTS: usize = 2;
+/// The most `DWalletCap`s of an owner scanned for a page of its dWallets.
+const MAX_DWALLET_CAPS_SCANNED: usize = 1_000;
+/// The curves of the keys of dWallets.
+const CURVES: [&str; 1] = ["secp256k1"];
+/// The hash functions of the messages of sign sessions, by their code in `SignData`.
+const HASH_FUNCTIONS: [&str; 2] = ["keccak256", "sha256"];
+
+#[derive(Clone)]
+pub struct DWalletReadApi {
+    state: Arc<dyn StateRead>,
+    transaction_kv_store: Arc<TransactionKeyValueStore>,
+    subscription_semaphore: Arc<Semaphore>,
+    metrics: Arc<DWalletRpcMetrics>,
+}
+
+impl DWalletReadApi {
+    pub fn new(
+        state: Arc<AuthorityState>,
+        transaction_kv_store: Arc<TransactionKeyValueStore>,
+        max_subscriptions: Option<usize>,
+        metrics: Arc<DWalletRpcMetrics>,
+    ) -> Self {
+        let max_subscriptions = max_subscriptions.unwrap_or(DEFAULT_MAX_SUBSCRIPTIONS);
+        Self {
+            state,
+            transaction_kv_store,
+            subscription_semaphore: Arc::new(Semaphore::new(max_subscriptions)),
+            metrics,
+        }
+    }
+
+    /// Run the request of `method`, recording it, its latency and its failure in the dWallet
+    /// metrics.
+    async fn observe<T>(
+        &self,
+        method: &str,
+        request: impl Future<Output = Result<T, Error>>,
+    ) -> Result<T, Error> {
+        self.metrics
+            .requests_by_method
+            .with_label_values(&[method])
+            .inc();
+        let latency = self.metrics.latency_by_method.with_label_values(&[method]);
+        let _timer = latency.start_timer();
+        let result = request.await;
+        if result.is_err() {
+            self.metrics
+                .errors_by_method
+                .with_label_values(&[method])
+                .inc();
+        }
+        result
+    }
+
+    fn get_dwallet(&self, dwallet_id: ObjectID) -> Result<DWallet, Error> {
+        let object = self.get_dwallet_object(dwallet_id)?;
+        object.to_rust::<DWallet>().ok_or_else(|| {
+            Error::UnexpectedError(format!("Failed to deserialize dWallet {dwallet_id}"))
+        })
+    }
+
+    fn get_dwallet_object(&self, dwallet_id: ObjectID) -> Result<Object, Error> {
+        let object = self.get_live_object(dwallet_id)?;
+        if !object
+            .struct_tag()
+            .is_some_and(|struct_tag| DWallet::is_type(&struct_tag))
+        {
+            return Err(SuiRpcInputError::GenericInvalid(format!(
+                "Object {dwallet_id} is not a dWallet"
+            ))
+            .into());
+        }
+        Ok(object)
+    }
+
+    async fn get_dwallet_info(&self, dwallet_id: ObjectID) -> Result<DWalletInfo, Error> {
+        let object = self.get_dwallet_object(dwallet_id)?;
+        let dwallet = object.to_rust::<DWallet>().ok_or_else(|| {
+            Error::UnexpectedError(format!("Failed to deserialize dWallet {dwallet_id}"))
+        })?;
+        let public_key = dwallet_public_key_to_sec1(&dwallet.public_key)
+            .map_err(|e| Error::UnexpectedError(e.to_string()))?;
+        let external_addresses = ExternalAddresses::new(&public_key)
+            .map_err(|e| Error::UnexpectedError(e.to_string()))?;
+
+        let creation_epoch = self.creation_epoch(&object)?;
+
+        // A capability that is not a live object is wrapped, e.g. in a signing policy.
+        let dwallet_cap_id = dwallet.dwallet_cap_id.bytes;
+        let (owner, signing_policy_id) = match self.state.get_object_read(&dwallet_cap_id)? {
+            ObjectRead::Exists(_, cap, _) => match cap.owner {
+                Owner::AddressOwner(owner) => (Some(owner), None),
+                _ => (None, None),
+            },
+            _ => match self.get_signing_policy(dwallet_cap_id).await? {
+                Some(policy) => (Some(policy.owner), Some(*policy.id())),
+                None => (None, None),
+            },
+        };
+
+        Ok(DWalletInfo {
+            dwallet_id,
+            dwallet_cap_id,
+            curve: "secp256k1".to_string(),
+            scheme: "ecdsa".to_string(),
+            dkg_output: dwallet.output,
+            public_key,
+            owner,
+            creation_epoch,
+            signing_policy_id,
+            external_addresses,
+        })
+    }
+
+    async fn get_dwallets_by_owner(
+        &self,
+        owner: SuiAddress,
+        query: Option<DWalletQuery>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<DWalletPage, Error> {
+        let limit =
+            validate_limit(limit, *QUERY_MAX_RESULT_LIMIT).map_err(SuiRpcInputError::from)?;
+        let query = query.unwrap_or_default();
+        let filter = SuiObjectDataFilter::StructType(DWalletCap::type_());
+
+        // The page holds one more dWallet than the limit, to know whether there is a next page,
+        // read from as many pages of capabilities as the query filters out, up to
+        // `MAX_DWALLET_CAPS_SCANNED` of them.
+        let mut data = vec![];
+        let mut cap_cursor = cursor;
+        let mut scanned = 0;
+        let mut last_page = false;
+        while data.len() <= limit && !last_page && scanned < MAX_DWALLET_CAPS_SCANNED {
+            let page_limit = (limit + 1).min(MAX_DWALLET_CAPS_SCANNED - scanned);
+            let caps = self.state.get_owner_objects_with_limit(
+                owner,
+                cap_cursor,
+                page_limit,
+                Some(filter.clone()),
+            )?;
+            last_page = caps.len() < page_limit;
+            for cap in caps {
+                scanned += 1;
+                cap_cursor = Some(cap.object_id);
+                let dwallet = self.get_owned_dwallet(cap.object_id).await?;
+                if query.matches(&dwallet) {
+                    data.push(dwallet);
+                    if data.len() > limit {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if data.len() > limit {
+            data.truncate(limit);
+            let next_cursor = data.last().map(|dwallet| dwallet.dwallet_cap_id);
+            return Ok(Page {
+                data,
+                next_cursor,
+                has_next_page: true,
+            });
+        }
+        // A page cut short by the scan continues after the last capability scanned.
+        let next_cursor = if last_page {
+            data.last()
+                .map_or(cursor, |dwallet| Some(dwallet.dwallet_cap_id))
+        } else {
+            cap_cursor
+        };
+        Ok(Page {
+            data,
+            next_cursor,
+            has_next_page: !last_page,
+        })
+    }
+
+    /// The dWallet of the `DWalletCap` `dwallet_cap_id`, found by following its DKG: the DKG
+    /// session created with the capability, the DKG output of the session, and the dWallet created
+    /// from the output, each by the transaction index.
+    async fn get_owned_dwallet(&self, dwallet_cap_id: ObjectID) -> Result<SuiOwnedDWallet, Error> {
+        let dwallet_id = self.dwallet_id_of_cap(dwallet_cap_id).await?;
+        let dwallet = match dwallet_id {
+            Some(dwallet_id) => Some(self.get_dwallet_info(dwallet_id).await?),
+            None => None,
+        };
+        Ok(SuiOwnedDWallet {
+            dwallet_cap_id,
+            status: match dwallet {
+                Some(_) => SuiDWalletStatus::Active,
+                None => SuiDWalletStatus::Pending,
+            },
+            curve: "secp256k1".to_string(),
+            dwallet,
+        })
+    }
+
+    async fn dwallet_id_of_cap(&self, dwallet_cap_id: ObjectID) -> Result<Option<ObjectID>, Error> {
+        let session_id = self
+            .first_created(TransactionFilter::ChangedObject(dwallet_cap_id), |object| {
+                let session = object_as::<DKGSession>(object, DKGSession::is_type)?;
+                (session.dwallet_cap_id.bytes == dwallet_cap_id).then_some(object.id())
+            })
+            .await?;
+        let Some(session_id) = session_id else {
+            return Ok(None);
+        };
+        let output_id = self
+            .first_created(TransactionFilter::InputObject(session_id), |object| {
+                let output = object_as::<DKGSessionOutput>(object, DKGSessionOutput::is_type)?;
+                (output.session_id.bytes == session_id).then_some(object.id())
+            })
+            .await?;
+        let Some(output_id) = output_id else {
+            return Ok(None);
+        };
+        self.first_created(TransactionFilter::InputObject(output_id), |object| {
+            let dwallet = object_as::<DWallet>(object, DWallet::is_type)?;
+            (dwallet.dwallet_cap_id.bytes == dwallet_cap_id).then_some(object.id())
+        })
+        .await
+    }
+
+    /// The first of the objects created by the first transaction of `filter` matched by
+    /// `matches`, even if it was since deleted.
+    async fn first_created<T>(
+        &self,
+        filter: TransactionFilter,
+        matches: impl Fn(&Object) -> Option<T>,
+    ) -> Result<Option<T>, Error> {
+        let digests = self
+            .state
+            .get_transactions(
+                &self.transaction_kv_store,
+                Some(filter),
+                None,
+                Some(1),
+                false,
+            )
+            .await?;
+        let Some(digest) = digests.first() else {
+            return Ok(None);
+        };
+        let Some(effects) = self.state.get_db().get_executed_effects(digest)? else {
+            return Ok(None);
+        };
+        for ((object_id, version, _), _) in effects.created() {
+            if let PastObjectRead::VersionFound(_, object, _) =
+                self.state.get_past_object_read(&object_id, version)?
+            {
+                if let Some(matched) = matches(&object) {
+                    return Ok(Some(matched));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn get_mpc_parameters(&self) -> SuiMpcParameters {
+        let epoch_store = self.state.load_epoch_store_one_call_per_task();
+        let protocol_config = epoch_store.protocol_config();
+        SuiMpcParameters {
+            epoch: epoch_store.epoch(),
+            protocol_version: protocol_config.version.as_u64(),
+            tiresias_public_parameters: protocol_config
+                .signature_mpc_tiresias_public_parameters()
+                .map(str::to_owned),
+            curves: CURVES.map(str::to_owned).to_vec(),
+            hash_functions: HASH_FUNCTIONS.map(str::to_owned).to_vec(),
+            feature_flags: protocol_config.feature_map(),
+        }
+    }
+
+    async fn get_presign_pool_status(
+        &self,
+        dwallet_id: ObjectID,
+        owner: Option<SuiAddress>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<SuiPresignPoolStatus, Error> {
+        let limit =
+            validate_limit(limit, *QUERY_MAX_RESULT_LIMIT).map_err(SuiRpcInputError::from)?;
+        let owner = match owner {
+            Some(owner) => Some(owner),
+            None => self.get_dwallet_info(dwallet_id).await?.owner,
+        };
+
+        let mut available = 0;
+        let mut next_cursor = cursor;
+        let mut has_next_page = false;
+        if let Some(owner) = owner {
+            // One more presign than the limit, to know whether there is a next page.
+            let mut presigns = self.state.get_owner_objects_with_limit(
+                owner,
+                cursor,
+                limit + 1,
+                Some(SuiObjectDataFilter::StructType(Presign::type_())),
+            )?;
+            has_next_page = presigns.len() > limit;
+            presigns.truncate(limit);
+            next_cursor = presigns.last().map_or(cursor, |info| Some(info.object_id));
+            for info in presigns {
+                if let ObjectRead::Exists(_, object, _) =
+                    self.state.get_object_read(&info.object_id)?
+                {
+                    available += u64::from(
+                        object
+                            .to_rust::<Presign>()
+                            .is_some_and(|presign| presign.dwallet_id.bytes == dwallet_id),
+                    );
+                }
+            }
+        }
+
+        // The sessions of the epoch are counted once, with the first page.
+        let (pending, consumed_this_epoch) = match cursor {
+            None => {
+                let (pending, consumed) = self.count_epoch_presign_sessions(dwallet_id).await?;
+                (Some(pending), Some(consumed))
+            }
+            Some(_) => (None, None),
+        };
+
+        Ok(SuiPresignPoolStatus {
+            dwallet_id,
+            owner,
+            available,
+            pending,
+            consumed_this_epoch,
+            epoch: self.state.load_epoch_store_one_call_per_task().epoch(),
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    /// The presign sessions of `dwallet_id` started this epoch whose presign is not created yet,
+    /// and the presigns consumed by its sign sessions started this epoch.
+    async fn count_epoch_presign_sessions(
+        &self,
+        dwallet_id: ObjectID,
+    ) -> Result<(u64, u64), Error> {
+        // A presign session is pending until the network creates its presign, or fails at the end
+        // of the epoch; the events are scanned from the most recent, so presigns come first.
+        let mut started = HashSet::new();
+        let mut presigned = HashSet::new();
+        self.visit_epoch_events(
+            EventFilter::MoveEventModule {
+                package: SUI_SYSTEM_PACKAGE_ID,
+                module: DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME.to_owned(),
+            },
+            |event| match event {
+                DWalletEvent::PresignSessionCreated(created)
+                    if created.dwallet_id.bytes == dwallet_id =>
+                {
+                    started.insert(created.session_id.bytes);
+                }
+                DWalletEvent::PresignCreated(created) if created.dwallet_id.bytes == dwallet_id => {
+                    presigned.insert(created.session_id.bytes);
+                }
+                _ => {}
+            },
+        )
+        .await?;
+        // Every sign session consumes a presign.
+        let mut consumed = 0;
+        self.visit_epoch_events(
+            EventFilter::MoveEventType(dwallet_event_type(SIGN_SESSION_CREATED_EVENT_STRUCT_NAME)),
+            |event| {
+                if let DWalletEvent::SignSessionCreated(created) = event {
+                    consumed += u64::from(created.dwallet_id.bytes == dwallet_id);
+                }
+            },
+        )
+        .await?;
+        Ok((started.difference(&presigned).count() as u64, consumed))
+    }
+
+    /// Visit the dWallet events of `filter` emitted in the current epoch, from the most recent, up
+    /// to `MAX_EPOCH_EVENTS` of them.
+    async fn visit_epoch_events(
+        &self,
+        filter: EventFilter,
+        mut visit: impl FnMut(DWalletEvent),
+    ) -> Result<(), Error> {
+        let epoch_start_timestamp_ms = self
+            .state
+            .load_epoch_store_one_call_per_task()
+            .epoch_start_state()
+            .epoch_start_timestamp_ms();
+        let mut cursor = None;
+        let mut scanned = 0;
+        while scanned < MAX_EPOCH_EVENTS {
+            let events = self
+                .state
+                .query_events(
+                    &self.transaction_kv_store,
+                    filter.clone(),
+                    cursor,
+                    *QUERY_MAX_RESULT_LIMIT,
+                    true,
+                )
+                .await?;
+            for event in &events {
+                // Events are timestamped once checkpointed, so an event without one is recent.
+                if event
+                    .timestamp_ms
+                    .is_some_and(|timestamp_ms| timestamp_ms < epoch_start_timestamp_ms)
+                {
+                    return Ok(());
+                }
+                if let Some(event) = DWalletEvent::try_from_event(&event.type_, &event.bcs) {
+                    visit(event?);
+                }
+            }
+            if events.len() < *QUERY_MAX_RESULT_LIMIT {
+                break;
+            }
+            scanned += events.len();
+            cursor = events.last().map(|event| event.id);
+        }
+        Ok(())
+    }
+
+    fn get_sign_session_object(
+        &self,
+        session_id: ObjectID,
+    ) -> Result<(SignSession<SignData>, Object), Error> {
+        let object = self.get_live_object(session_id)?;
+        let session_type =
+            SignSession::<SignData>::type_(TypeTag::Struct(Box::new(SignData::type_())));
+        if object.struct_tag().as_ref() != Some(&session_type) {
+            return Err(SuiRpcInputError::GenericInvalid(format!(
+                "Object {session_id} is not a sign session"
+            ))
+            .into());
+        }
+        let session = object.to_rust::<SignSession<SignData>>().ok_or_else(|| {
+            Error::UnexpectedError(format!("Failed to deserialize sign session {session_id}"))
+        })?;
+        Ok((session, object))
+    }
+
+    async fn get_sign_session(&self, session_id: ObjectID) -> Result<SuiSignSession, Error> {
+        let (session, object) = self.get_sign_session_object(session_id)?;
+        let epoch = self.creation_epoch(&object)?;
+
+        let (hash, message_digests) = match session.sign_data.hash {
+            0 => (
+                HASH_FUNCTIONS[0],
+                session
+                    .messages
+                    .iter()
+                    .map(|message| Keccak256::digest(message).to_vec())
+                    .collect(),
+            ),
+            1 => (
+                HASH_FUNCTIONS[1],
+                session
+                    .messages
+                    .iter()
+                    .map(|message| Sha256::digest(message).to_vec())
+                    .collect(),
+            ),
+            hash => {
+                return Err(Error::UnexpectedError(format!(
+                    "Unknown hash function {hash} of sign session {session_id}"
+                )))
+            }
+        };
+
+        // The parties are the validators of the committee of the epoch, by their index, and the
+        // aggregator is picked by the session ID, as by the signature MPC service.
+        let committee = self
+            .state
+            .get_or_latest_committee(Some(BigInt::from(epoch)))?;
+        let parties: Vec<_> = committee
+            .voting_rights
+            .iter()
+            .enumerate()
+            .map(|(index, (authority, _))| SuiSignSessionParty {
+                party_id: (index + 1) as u16,
+                authority: *authority,
+            })
+            .collect();
+        let session_prefix = u64::from_be_bytes(session_id.into_bytes()[..8].try_into().unwrap());
+        let aggregator_party_id = (session_prefix % parties.len() as u64 + 1) as u16;
+
+        let (status, sign_output) = match self.find_sign_output(session_id).await? {
+            Some(output) => (SuiSignSessionStatus::Signed, Some(output)),
+            None if epoch < self.state.load_epoch_store_one_call_per_task().epoch() => {
+                (SuiSignSessionStatus::Expired, None)
+            }
+            None => (SuiSignSessionStatus::Pending, None),
+        };
+        let completed_at_ms = match &sign_output {
+            Some((_, object)) => self.checkpoint_timestamp_ms(&object.previous_transaction)?,
+            None => None,
+        };
+
+        Ok(SuiSignSession {
+            session_id,
+            dwallet_id: session.dwallet_id.bytes,
+            dwallet_cap_id: session.dwallet_cap_id.bytes,
+            sender: session.sender,
+            status,
+            epoch,
+            hash: hash.to_string(),
+            message_digests,
+            parties,
+            aggregator_party_id,
+            completed_at_ms,
+            sign_output_id: sign_output
+                .as_ref()
+                .map(|(output, _)| *output.id.object_id()),
+            signatures: sign_output.map(|(output, _)| output.signatures),
+        })
+    }
+
+    async fn get_encrypted_user_shares(
+        &self,
+        dwallet_id: ObjectID,
+        owner: Option<SuiAddress>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<EncryptedUserSharePage, Error> {
+        let limit =
+            validate_limit(limit, *QUERY_MAX_RESULT_LIMIT).map_err(SuiRpcInputError::from)?;
+        let owner = match owner {
+            Some(owner) => owner,
+            None => match self.get_dwallet_info(dwallet_id).await?.owner {
+                Some(owner) => owner,
+                None => {
+                    return Ok(Page {
+                        data: vec![],
+                        next_cursor: None,
+                        has_next_page: false,
+                    })
+                }
+            },
+        };
+        let filter = SuiObjectDataFilter::StructType(EncryptedUserShare::type_());
+
+        // The page holds one more share than the limit, to know whether there is a next page,
+        // read from as many pages of the owner's shares as belong to other dWallets.
+        let mut data = vec![];
+        let mut share_cursor = cursor;
+        while data.len() <= limit {
+            let infos = self.state.get_owner_objects_with_limit(
+                owner,
+                share_cursor,
+                limit + 1,
+                Some(filter.clone()),
+            )?;
+            let last_page = infos.len() <= limit;
+            for info in infos {
+                share_cursor = Some(info.object_id);
+                if let ObjectRead::Exists(_, object, _) =
+                    self.state.get_object_read(&info.object_id)?
+                {
+                    if let Some(share) = encrypted_user_share(&object) {
+                        if share.dwallet_id == dwallet_id {
+                            data.push(share);
+                            if data.len() > limit {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+            if last_page {
+                break;
+            }
+        }
+
+        let has_next_page = data.len() > limit;
+        data.truncate(limit);
+        let next_cursor = data.last().map_or(cursor, |share| Some(share.share_id));
+        Ok(Page {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    /// The transfer of a dWallet to the owner of the encrypted user share `share_id`, verified
+    /// as the recipient would before decrypting the share: by its `EncryptionKey`, and the owner
+    /// of the dWallet.
+    async fn get_transfer_status(
+        &self,
+        share_id: ObjectID,
+    ) -> Result<SuiDWalletTransferStatus, Error> {
+        let object = self.get_live_object(share_id)?;
+        let share = encrypted_user_share(&object).ok_or_else(|| {
+            SuiRpcInputError::GenericInvalid(format!(
+                "Object {share_id} is not an encrypted user share owned by an address"
+            ))
+        })?;
+        let dwallet_owner = self.get_dwallet_info(share.dwallet_id).await?.owner;
+
+        let encryption_key = match self.state.get_object_read(&share.encryption_key_id)? {
+            ObjectRead::Exists(_, key, _) if key.owner == Owner::AddressOwner(share.owner) => {
+                object_as::<EncryptionKey>(&key, EncryptionKey::is_type)
+                    .map(|key| key.encryption_key)
+            }
+            _ => None,
+        };
+        let state = match (&encryption_key, dwallet_owner) {
+            (None, _) => SuiDWalletTransferState::InvalidEncryptionKey,
+            (Some(_), Some(owner)) if owner == share.owner => SuiDWalletTransferState::Verified,
+            (Some(_), _) => SuiDWalletTransferState::AwaitingCap,
+        };
+
+        Ok(SuiDWalletTransferStatus {
+            share,
+            state,
+            dwallet_owner,
+            encryption_key,
+        })
+    }
+
+    /// The tracker of the sign events of `filter`. A session selected by its ID is tracked from
+    /// its current state, so that its outcome is notified even if it precedes the subscription.
+    async fn sign_event_tracker(&self, filter: SignEventFilter) -> Result<SignEventTracker, Error> {
+        let mut tracker = SignEventTracker::new(filter);
+        let SignEventFilter::Session(session_id) = filter else {
+            return Ok(tracker);
+        };
+        let (session, object) = self.get_sign_session_object(session_id)?;
+        let pending = PendingSignSession {
+            dwallet_id: session.dwallet_id.bytes,
+            dwallet_cap_id: session.dwallet_cap_id.bytes,
+            sender: session.sender,
+        };
+        let epoch = self.creation_epoch(&object)?;
+        if let Some((output, object)) = self.find_sign_output(session_id).await? {
+            let timestamp_ms = self.checkpoint_timestamp_ms(&object.previous_transaction)?;
+            tracker.notify(SuiSignEvent::Completed {
+                session_id,
+                dwallet_id: pending.dwallet_id,
+                dwallet_cap_id: pending.dwallet_cap_id,
+                sender: pending.sender,
+                sign_output_id: *output.id.object_id(),
+                signatures: output.signatures,
+                tx_digest: object.previous_transaction,
+                timestamp_ms,
+            });
+        } else if epoch < self.state.load_epoch_store_one_call_per_task().epoch() {
+            tracker.notify(pending.failed(session_id, epoch));
+        } else {
+            tracker.pending.insert(session_id, pending);
+        }
+        Ok(tracker)
+    }
+
+    fn acquire_subscribe_permit(&self) -> anyhow::Result<OwnedSemaphorePermit> {
+        match self.subscription_semaphore.clone().try_acquire_owned() {
+            Ok(p) => Ok(p),
+            Err(_) => {
+                self.metrics.sign_event_subscriptions_rejected.inc();
+                bail!("Resources exhausted")
+            }
+        }
+    }
+
+    /// The sign output of the sign session `session_id`, even if it was since deleted, from the
+    /// `SignOutputCreated` event of the session, found by the index of the events of sessions.
+    async fn find_sign_output(
+        &self,
+        session_id: ObjectID,
+    ) -> Result<Option<(SignOutput, Object)>, Error> {
+        let events = self
+            .state
+            .query_events(
+                &self.transaction_kv_store,
+                EventFilter::DWalletSession(session_id),
+                None,
+                MAX_SIGN_SESSION_EVENTS,
+                true,
+            )
+            .await?;
+        for event in events {
+            let Some(Ok(DWalletEvent::SignOutputCreated(created))) =
+                DWalletEvent::try_from_event(&event.type_, &event.bcs)
+            else {
+                continue;
+            };
+            let Some(effects) = self
+                .state
+                .get_db()
+                .get_executed_effects(&event.id.tx_digest)?
+            else {
+                continue;
+            };
+            for ((object_id, version, _), _) in effects.created() {
+                if object_id != created.sign_output_id.bytes {
+                    continue;
+                }
+                if let PastObjectRead::VersionFound(_, object, _) =
+                    self.state.get_past_object_read(&object_id, version)?
+                {
+                    if let Some(output) = object.to_rust::<SignOutput>() {
+                        return Ok(Some((output, object)));
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn get_live_object(&self, object_id: ObjectID) -> Result<Object, Error> {
+        match self.state.get_object_read(&object_id)? {
+            ObjectRead::Exists(_, object, _) => Ok(object),
+            _ => Err(
+                SuiRpcInputError::UserInputError(UserInputError::ObjectNotFound {
+                    object_id,
+                    version: None,
+                })
+                .into(),
+            ),
+        }
+    }
+
+    /// The epoch in which the immutable `object` was created, by its last transaction.
+    fn creation_epoch(&self, object: &Object) -> Result<EpochId, Error> {
+        Ok(self
+            .state
+            .get_db()
+            .get_executed_effects(&object.previous_transaction)?
+            .ok_or_else(|| {
+                Error::UnexpectedError(format!(
+                    "Cannot find the transaction that created {}",
+                    object.id()
+                ))
+            })?
+            .executed_epoch())
+    }
+
+    /// The timestamp of the checkpoint of the transaction `digest`, if it is checkpointed.
+    fn checkpoint_timestamp_ms(&self, digest: &TransactionDigest) -> Result<Option<u64>, Error> {
+        let Some((_, sequence_number)) =
+            self.state.deprecated_get_transaction_checkpoint(digest)?
+        else {
+            return Ok(None);
+        };
+        Ok(self
+            .state
+            .get_checkpoint_by_sequence_number(sequence_number)?
+            .map(|checkpoint| checkpoint.timestamp_ms))
+    }
+
+    /// The signing policy holding the capability `dwallet_cap_id`, from the most recent
+    /// `SigningPolicySet` or `SigningPolicyRemoved` event of the capability.
+    async fn get_signing_policy(
+        &self,
+        dwallet_cap_id: ObjectID,
+    ) -> Result<Option<SigningPolicy>, Error> {
+        let filter = EventFilter::MoveEventModule {
+            package: SUI_SYSTEM_PACKAGE_ID,
+            module: DWALLET_POLICY_MODULE_NAME.to_owned(),
+        };
+        let mut cursor = None;
+        let mut scanned = 0;
+        while scanned < MAX_POLICY_EVENTS {
+            let events = self
+                .state
+                .query_events(
+                    &self.transaction_kv_store,
+                    filter.clone(),
+                    cursor,
+                    *QUERY_MAX_RESULT_LIMIT,
+                    true,
+                )
+                .await?;
+            for event in &events {
+                let name = event.type_.name.as_ident_str();
+                let policy_id = if name == SIGNING_POLICY_SET_EVENT_STRUCT_NAME {
+                    let set: SigningPolicySetEvent = bcs::from_bytes(&event.bcs)?;
+                    if set.dwallet_cap_id.bytes != dwallet_cap_id {
+                        continue;
+                    }
+                    set.policy_id.bytes
+                } else if name == SIGNING_POLICY_REMOVED_EVENT_STRUCT_NAME {
+                    let removed: SigningPolicyRemovedEvent = bcs::from_bytes(&event.bcs)?;
+                    if removed.dwallet_cap_id.bytes != dwallet_cap_id {
+                        continue;
+                    }
+                    return Ok(None);
+                } else {
+                    continue;
+                };
+                let ObjectRead::Exists(_, policy, _) = self.state.get_object_read(&policy_id)?
+                else {
+                    return Ok(None);
+                };
+                return Ok(policy
+                    .to_rust::<SigningPolicy>()
+                    .filter(|policy| *policy.dwallet_cap.id() == dwallet_cap_id));
+            }
+            if events.len() < *QUERY_MAX_RESULT_LIMIT {
+                break;
+            }
+            scanned += events.len();
+            cursor = events.last().map(|event| event.id);
+        }
+        Ok(None)
+    }
+
+    fn get_bitcoin_addresses(
+        &self,
+        dwallet_id: ObjectID,
+        network: Option<BitcoinNetwork>,
+    ) -> Result<Vec<BitcoinAddresses>, Error> {
+        let dwallet = self.get_dwallet(dwallet_id)?;
+        let public_key = dwallet_public_key_to_sec1(&dwallet.public_key)
+            .map_err(|e| Error::UnexpectedError(e.to_string()))?;
+        let networks = match network {
+            Some(network) => vec![network],
+            None => BitcoinNetwork::ALL.to_vec(),
+        };
+        networks
+            .into_iter()
+            .map(|network| {
+                BitcoinAddresses::new(&public_key, network)
+                    .map_err(|e| Error::UnexpectedError(e.to_string()))
+            })
+            .collect()
+    }
+
+    fn verify_ethereum_proof(
+        &self,
+        light_client_id: ObjectID,
+        block_number: u64,
+        proof: EthereumProof,
+    ) -> Result<VerifiedEthereumProof, Error> {
+        let object = self.get_live_object(light_client_id)?;
+        let light_client: EthereumLightClient = object_as(&object, EthereumLightClient::is_type)
+            .ok_or_else(|| {
+                SuiRpcInputError::GenericInvalid(format!(
+                    "Object {light_client_id} is not an Ethereum light client"
+                ))
+            })?;
+        let header = self.get_execution_header(&light_client, block_number)?;
+        let root = |root: &[u8]| -> Result<Root, Error> {
+            root.try_into().map_err(|_| {
+                Error::UnexpectedError(format!(
+                    "Invalid root in the header of block {block_number} of {light_client_id}"
+                ))
+            })
+        };
+        let invalid_proof = |e: EthProofError| SuiRpcInputError::GenericInvalid(e.to_string());
+
+        let value = match proof {
+            EthereumProof::Storage {
+                address,
+                account_proof,
+                slot,
+                storage_proof,
+            } => {
+                let account = eth_proofs::verify_account_proof(
+                    &root(&header.state_root)?,
+                    &address,
+                    &account_proof,
+                )
+                .map_err(invalid_proof)?;
+                let storage_root = account
+                    .as_ref()
+                    .map_or(eth_proofs::EMPTY_TRIE_ROOT, |account| account.storage_root);
+                let value = eth_proofs::verify_storage_proof(&storage_root, &slot, &storage_proof)
+                    .map_err(invalid_proof)?;
+                EthereumProvenValue::Storage {
+                    account: account.map(SuiEthereumAccount::from),
+                    value,
+                }
+            }
+            EthereumProof::Receipt {
+                transaction_index,
+                proof,
+            } => eth_proofs::verify_receipt_proof(
+                &root(&header.receipts_root)?,
+                transaction_index,
+                &proof,
+            )
+            .map_err(invalid_proof)?
+            .into(),
+        };
+
+        Ok(VerifiedEthereumProof {
+            light_client_id,
+            chain_id: light_client.chain_id,
+            block_number,
+            block_hash: header.block_hash,
+            value,
+        })
+    }
+
+    /// The execution header of block `block_number` retained by `light_client`, which it only
+    /// retains once the block is finalized.
+    fn get_execution_header(
+        &self,
+        light_client: &EthereumLightClient,
+        block_number: u64,
+    ) -> Result<ExecutionHeader, Error> {
+        let light_client_id = light_client.id.id.bytes;
+        let field_id = self
+            .state
+            .get_dynamic_field_object_id(
+                light_client.execution_headers.id,
+                TypeTag::U64,
+                &bcs::to_bytes(&block_number)?,
+            )?
+            .ok_or_else(|| {
+                SuiRpcInputError::GenericInvalid(format!(
+                    "Block {block_number} is not finalized by the light client {light_client_id}"
+                ))
+            })?;
+        let field: Field<u64, ExecutionHeader> =
+            self.get_live_object(field_id)?.to_rust().ok_or_else(|| {
+                Error::UnexpectedError(format!(
+                    "Failed to deserialize the header of block {block_number} of {light_client_id}"
+                ))
+            })?;
+        Ok(field.value)
+    }
+}
+
+#[async_trait]
+impl DWalletReadApiServer for DWalletReadApi {
+    #[instrument(skip(self))]
+    async fn get_bitcoin_addresses(
+        &self,
+        dwallet_id: ObjectID,
+        network: Option<BitcoinNetwork>,
+    ) -> RpcResult<Vec<BitcoinAddresses>> {
+        with_tracing!(self.observe("dwallet_getBitcoinAddresses", async move {
+            self.get_bitcoin_addresses(dwallet_id, network)
+        }))
+    }
+
+    #[instrument(skip(self))]
+    async fn get_dwallet(&self, dwallet_id: ObjectID) -> RpcResult<DWalletInfo> {
+        with_tracing!(self.observe("dwallet_getDWallet", self.get_dwallet_info(dwallet_id)))
+    }
+
+    #[instrument(skip(self))]
+    async fn get_dwallets_by_owner(
+        &self,
+        owner: SuiAddress,
+        query: Option<DWalletQuery>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<DWalletPage> {
+        with_tracing!(self.observe(
+            "dwallet_getDWalletsByOwner",
+            self.get_dwallets_by_owner(owner, query, cursor, limit),
+        ))
+    }
+
+    #[instrument(skip(self))]
+    async fn get_mpc_parameters(&self) -> RpcResult<SuiMpcParameters> {
+        with_tracing!(self.observe("dwallet_getMpcParameters", async move {
+            Ok(self.get_mpc_parameters())
+        }))
+    }
+
+    #[instrument(skip(self))]
+    async fn get_presign_pool_status(
+        &self,
+        dwallet_id: ObjectID,
+        owner: Option<SuiAddress>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<SuiPresignPoolStatus> {
+        with_tracing!(self.observe(
+            "dwallet_getPresignPoolStatus",
+            self.get_presign_pool_status(dwallet_id, owner, cursor, limit),
+        ))
+    }
+
+    #[instrument(skip(self))]
+    async fn get_sign_session(&self, session_id: ObjectID) -> RpcResult<SuiSignSession> {
+        with_tracing!(self.observe("dwallet_getSignSession", self.get_sign_session(session_id)))
+    }
+
+    #[instrument(skip(self))]
+    async fn get_encrypted_user_shares(
+        &self,
+        dwallet_id: ObjectID,
+        owner: Option<SuiAddress>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<EncryptedUserSharePage> {
+        with_tracing!(self.observe(
+            "dwallet_getEncryptedUserShares",
+            self.get_encrypted_user_shares(dwallet_id, owner, cursor, limit),
+        ))
+    }
+
+    #[instrument(skip(self))]
+    async fn get_transfer_status(&self, share_id: ObjectID) -> RpcResult<SuiDWalletTransferStatus> {
+        with_tracing!(self.observe(
+            "dwallet_getTransferStatus",
+            self.get_transfer_status(share_id)
+        ))
+    }
+
+    #[instrument(skip(self))]
+    fn subscribe_sign_events(
+        &self,
+        sink: SubscriptionSink,
+        filter: SignEventFilter,
+    ) -> SubscriptionResult {
+        let permit = self.acquire_subscribe_permit()?;
+        if let SignEventFilter::Session(session_id) = filter {
+            self.get_sign_session_object(session_id)
+                .map_err(|e| anyhow!(e.to_string()))?;
+        }
+        // Subscribe before reading the state of a session, for none of its events to be missed.
+        let events = self
+            .state
+            .get_subscription_handler()
+            .subscribe_events(EventFilter::Any(vec![
+                EventFilter::MoveEventType(dwallet_event_type(
+                    SIGN_SESSION_CREATED_EVENT_STRUCT_NAME,
+                )),
+                EventFilter::MoveEventType(dwallet_event_type(
+                    SIGN_OUTPUT_CREATED_EVENT_STRUCT_NAME,
+                )),
+                EventFilter::MoveEventType(system_epoch_info_event_type()),
+            ]));
+        // The state of a session is read by the subscription task, as finding its sign output
+        // reads the transaction index.
+        let api = self.clone();
+        let notifications = stream::once(async move {
+            let mut tracker = match api.sign_event_tracker(filter).await {
+                Ok(tracker) => tracker,
+                Err(e) => {
+                    warn!("Failed to read the sign sessions of {filter:?}: {e}");
+                    return stream::empty().boxed();
+                }
+            };
+            let subscription = SignEventSubscription::new(api.metrics.clone());
+            let initial = std::mem::take(&mut tracker.notifications);
+            initial
+                .iter()
+                .for_each(|notification| subscription.on_notification(notification, None));
+            stream::iter(initial)
+                .chain(events.flat_map(move |event| {
+                    let notifications = tracker.on_event(&event);
+                    for notification in &notifications {
+                        subscription.on_notification(notification, event.timestamp_ms);
+                    }
+                    stream::iter(notifications)
+                }))
+                .boxed()
+        })
+        .flatten()
+        .boxed();
+        spawn_subscription(sink, notifications, Some(permit));
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn verify_ethereum_proof(
+        &self,
+        light_client_id: ObjectID,
+        block_number: BigInt<u64>,
+        proof: EthereumProof,
+    ) -> RpcResult<VerifiedEthereumProof> {
+        with_tracing!(self.observe("dwallet_verifyEthereumProof", async move {
+            self.verify_ethereum_proof(light_client_id, *block_number, proof)
+        }))
+    }
+}
+
+/// The Move struct `object` as `T`, if it is of the type of `is_type`.
+fn object_as<T: DeserializeOwned>(object: &Object, is_type: fn(&StructTag) -> bool) -> Option<T> {
+    if !object
+        .struct_tag()
+        .is_some_and(|struct_tag| is_type(&struct_tag))
+    {
+        return None;
+    }
+    object.to_rust::<T>()
+}
+
+/// The `EncryptedUserShare` `object`, if it is one, with its recipient: the address owning it.
+fn encrypted_user_share(object: &Object) -> Option<SuiEncryptedUserShare> {
+    let share = object_as::<EncryptedUserShare>(object, EncryptedUserShare::is_type)?;
+    let Owner::AddressOwner(owner) = object.owner else {
+        return None;
+    };
+    Some(SuiEncryptedUserShare {
+        share_id: *share.id(),
+        dwallet_id: share.dwallet_id.bytes,
+        encryption_key_id: share.encryption_key_id.bytes,
+        encrypted_user_share: share.encrypted_user_share,
+        sender: share.sender,
+        owner,
+    })
+}
+
+/// The type of the event `name` of the `dwallet` module.
+fn dwallet_event_type(name: &IdentStr) -> StructTag {
+    StructTag {
+        address: SUI_SYSTEM_ADDRESS,
+        module: DWALLET_MODULE_NAME.to_owned(),
+        name: name.to_owned(),
+        type_params: vec![],
+    }
+}
+
+/// The type of the event emitted at the end of every epoch.
+fn system_epoch_info_event_type() -> StructTag {
+    StructTag {
+        address: SUI_SYSTEM_ADDRESS,
+        module: ident_str!("dwallet_system_state_inner").to_owned(),
+        name: ident_str!("SystemEpochInfoEvent").to_owned(),
+        type_params: vec![],
+    }
+}
+
+/// A sign session that was neither signed nor failed yet.
+struct PendingSignSession {
+    dwallet_id: ObjectID,
+    dwallet_cap_id: ObjectID,
+    sender: SuiAddress,
+}
+
+impl PendingSignSession {
+    fn failed(&self, session_id: ObjectID, epoch: EpochId) -> SuiSignEvent {
+        SuiSignEvent::Failed {
+            session_id,
+            dwallet_id: self.dwallet_id,
+            dwallet_cap_id: self.dwallet_cap_id,
+            sender: self.sender,
+            epoch,
+        }
+    }
+}
+
+/// The metrics of a sign event subscription, which is active until it is dropped along with the
+/// stream of its notifications.
+struct SignEventSubscription {
+    metrics: Arc<DWalletRpcMetrics>,
+}
+
+impl SignEventSubscription {
+    fn new(metrics: Arc<DWalletRpcMetrics>) -> Self {
+        metrics.sign_event_subscriptions.inc();
+        Self { metrics }
+    }
+
+    /// Record `notification`, sent for an event of `event_timestamp_ms`, or for the state of a
+    /// session at subscription if None.
+    fn on_notification(&self, notification: &SuiSignEvent, event_timestamp_ms: Option<u64>) {
+        let kind = match notification {
+            SuiSignEvent::Completed { .. } => "completed",
+            SuiSignEvent::Failed { .. } => "failed",
+        };
+        self.metrics
+            .sign_event_notifications
+            .with_label_values(&[kind])
+            .inc();
+        if let Some(event_timestamp_ms) = event_timestamp_ms {
+            let now_ms = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|now| now.as_millis() as u64)
+                .unwrap_or_default();
+            self.metrics
+                .sign_event_lag_ms
+                .report(now_ms.saturating_sub(event_timestamp_ms));
+        }
+    }
+}
+
+impl Drop for SignEventSubscription {
+    fn drop(&mut self) {
+        self.metrics.sign_event_subscriptions.dec();
+    }
+}
+
+/// Turns the stream of sign session, sign output and epoch events into the sign notifications of
+/// a filter: a session is pending from its creation, and completed by its sign output or failed
+/// by the end of the epoch, whichever comes first.
+struct SignEventTracker {
+    filter: SignEventFilter,
+    pending: HashMap<ObjectID, PendingSignSession>,
+    /// The sessions already notified of, from their state at subscription.
+    notified: HashSet<ObjectID>,
+    notifications: Vec<SuiSignEvent>,
+}
+
+impl SignEventTracker {
+    fn new(filter: SignEventFilter) -> Self {
+        Self {
+            filter,
+            pending: HashMap::new(),
+            notified: HashSet::new(),
+            notifications: vec![],
+        }
+    }
+
+    fn notify(&mut self, notification: SuiSignEvent) {
+        self.notified.insert(*notification.session_id());
+        self.notifications.push(notification);
+    }
+
+    /// The notifications of `event`.
+    fn on_event(&mut self, event: &SuiEvent) -> Vec<SuiSignEvent> {
+        if event.type_ == system_epoch_info_event_type() {
+            // Sessions do not outlive their epoch, so every pending session failed. The event
+            // carries the epoch that starts.
+            return match bcs::from_bytes::<SystemEpochInfoEvent>(&event.bcs) {
+                Ok(epoch_info) => {
+                    let ended_epoch = epoch_info.epoch.saturating_sub(1);
+                    self.pending
+                        .drain()
+                        .map(|(session_id, pending)| pending.failed(session_id, ended_epoch))
+                        .collect()
+                }
+                Err(e) => {
+                    warn!("Failed to deserialize the epoch info event: {e}");
+                    vec![]
+                }
+            };
+        }
+        match DWalletEvent::try_from_event(&event.type_, &event.bcs) {
+            Some(Ok(DWalletEvent::SignSessionCreated(created))) => {
+                let session_id = created.session_id.bytes;
+                if self
+                    .filter
+                    .matches(&session_id, &created.dwallet_id.bytes, &created.sender)
+                {
+                    self.pending.insert(
+                        session_id,
+                        PendingSignSession {
+                            dwallet_id: created.dwallet_id.bytes,
+                            dwallet_cap_id: created.dwallet_cap_id.bytes,
+                            sender: created.sender,
+                        },
+                    );
+                }
+                vec![]
+            }
+            Some(Ok(DWalletEvent::SignOutputCreated(output))) => {
+                let session_id = output.session_id.bytes;
+                self.pending.remove(&session_id);
+                if !self
+                    .filter
+                    .matches(&session_id, &output.dwallet_id.bytes, &output.sender)
+                    || self.notified.contains(&session_id)
+                {
+                    return vec![];
+                }
+                vec![SuiSignEvent::Completed {
+                    session_id,
+                    dwallet_id: output.dwallet_id.bytes,
+                    dwallet_cap_id: output.dwallet_cap_id.bytes,
+                    sender: output.sender,
+                    sign_output_id: output.sign_output_id.bytes,
+                    signatures: output.signatures,
+                    tx_digest: event.id.tx_digest,
+                    timestamp_ms: event.timestamp_ms,
+                }]
+            }
+            Some(Err(e)) => {
+                warn!("Failed to deserialize the sign event {}: {e}", event.type_);
+                vec![]
+            }
+            _ => vec![],
+        }
+    }
+}
+
+impl SuiRpcModule for DWalletReadApi {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        DWalletReadApiOpenRpc::module_doc()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use sui_types::event::EventID;
+    use sui_types::id::ID;
+    use sui_types::signature_mpc::{SignOutputCreatedEvent, SignSessionCreatedEvent};
+
+    fn sui_event(type_: StructTag, bcs: Vec<u8>) -> SuiEvent {
+        SuiEvent {
+            id: EventID {
+                tx_digest: TransactionDigest::random(),
+                event_seq: 0,
+            },
+            package_id: SUI_SYSTEM_PACKAGE_ID,
+            transaction_module: DWALLET_MODULE_NAME.to_owned(),
+            sender: SuiAddress::ZERO,
+            type_,
+            parsed_json: Value::Null,
+            bcs,
+            timestamp_ms: Some(1_000),
+        }
+    }
+
+    fn session_created(session_id: ObjectID, dwallet_id: ObjectID, sender: SuiAddress) -> SuiEvent {
+        let event = SignSessionCreatedEvent {
+            session_id: ID::new(session_id),
+            dwallet_id: ID::new(dwallet_id),
+            dwallet_cap_id: ID::new(ObjectID::random()),
+            messages: vec![b"message".to_vec()],
+            sender,
+        };
+        sui_event(
+            dwallet_event_type(SIGN_SESSION_CREATED_EVENT_STRUCT_NAME),
+            bcs::to_bytes(&event).unwrap(),
+        )
+    }
+
+    fn output_created(session_id: ObjectID, dwallet_id: ObjectID, sender: SuiAddress) -> SuiEvent {
+        let event = SignOutputCreatedEvent {
+            session_id: ID::new(session_id),
+            sign_output_id: ID::new(ObjectID::random()),
+            dwallet_id: ID::new(dwallet_id),
+            dwallet_cap_id: ID::new(ObjectID::random()),
+            signatures: vec![vec![1; 64]],
+            sender,
+        };
+        sui_event(
+            dwallet_event_type(SIGN_OUTPUT_CREATED_EVENT_STRUCT_NAME),
+            bcs::to_bytes(&event).unwrap(),
+        )
+    }
+
+    fn epoch_started(epoch: EpochId) -> SuiEvent {
+        let mut fields = [0u64; 12];
+        fields[0] = epoch;
+        sui_event(
+            system_epoch_info_event_type(),
+            bcs::to_bytes(&fields).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_sign_event_tracker() {
+        let dwallet_id = ObjectID::random();
+        let sender = SuiAddress::random_for_testing_only();
+        let mut tracker = SignEventTracker::new(SignEventFilter::DWallet(dwallet_id));
+        let (signed, unsigned, other) =
+            (ObjectID::random(), ObjectID::random(), ObjectID::random());
+
+        assert!(tracker
+            .on_event(&session_created(signed, dwallet_id, sender))
+            .is_empty());
+        assert!(tracker
+            .on_event(&session_created(unsigned, dwallet_id, sender))
+            .is_empty());
+        assert!(tracker
+            .on_event(&session_created(other, ObjectID::random(), sender))
+            .is_empty());
+
+        let notifications = tracker.on_event(&output_created(signed, dwallet_id, sender));
+        assert!(matches!(
+            &notifications[..],
+            [SuiSignEvent::Completed { session_id, signatures, timestamp_ms: Some(1_000), .. }]
+                if *session_id == signed && signatures == &vec![vec![1; 64]]
+        ));
+
+        // Only the pending session of the dWallet fails when its epoch ends.
+        let notifications = tracker.on_event(&epoch_started(5));
+        assert!(matches!(
+            &notifications[..],
+            [SuiSignEvent::Failed { session_id, epoch: 4, .. }] if *session_id == unsigned
+        ));
+        assert!(tracker.on_event(&epoch_started(6)).is_empty());
+    }
+
+    #[test]
+    fn test_sign_event_subscription_metrics() {
+        let metrics = Arc::new(DWalletRpcMetrics::new_for_tests());
+        let dwallet_id = ObjectID::random();
+        let sender = SuiAddress::random_for_testing_only();
+        let mut tracker = SignEventTracker::new(SignEventFilter::Owner(sender));
+        let subscription = SignEventSubscription::new(metrics.clone());
+        assert_eq!(metrics.sign_event_subscriptions.get(), 1);
+
+        let session_id = ObjectID::random();
+        tracker.on_event(&session_created(session_id, dwallet_id, sender));
+        let event = output_created(session_id, dwallet_id, sender);
+        for notification in &tracker.on_event(&event) {
+            subscription.on_notification(notification, event.timestamp_ms);
+        }
+        let notifications = |kind| {
+            metrics
+                .sign_event_notifications
+                .with_label_values(&[kind])
+                .get()
+        };
+        assert_eq!(notifications("completed"), 1);
+        assert_eq!(notifications("failed"), 0);
+
+        drop(subscription);
+        assert_eq!(metrics.sign_event_subscriptions.get(), 0);
+    }
+}
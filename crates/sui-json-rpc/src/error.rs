@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
 use fastcrypto::error::FastCryptoError;
-use hyper::header::InvalidHeaderValue;
+use hyper::header::{InvalidHeaderName, InvalidHeaderValue};
 use itertools::Itertools;
 use jsonrpsee::core::Error as RpcError;
 use jsonrpsee::types::error::{CallError, INTERNAL_ERROR_CODE};
@@ -37,6 +37,9 @@ pub enum Error {
     #[error(transparent)]
     InvalidHeaderValue(#[from] InvalidHeaderValue),
 
+    #[error(transparent)]
+    InvalidHeaderName(#[from] InvalidHeaderName),
+
     #[error(transparent)]
     UserInputError(#[from] UserInputError),
 
@@ -71,7 +71,7 @@ pub fn spawn_subscription<S, T>(
         };
     });
 }
-const DEFAULT_MAX_SUBSCRIPTIONS: usize = 100;
+pub(crate) const DEFAULT_MAX_SUBSCRIPTIONS: usize = 100;
 
 pub struct IndexerApi<R> {
     state: Arc<dyn StateRead>,
@@ -19,6 +19,7 @@ use tracing::info;
 
 pub use balance_changes::*;
 pub use object_changes::*;
+use sui_config::node::JsonRpcRateLimitConfig;
 use sui_json_rpc_api::{
     CLIENT_SDK_TYPE_HEADER, CLIENT_SDK_VERSION_HEADER, CLIENT_TARGET_API_VERSION_HEADER,
 };
@@ -26,12 +27,14 @@ use sui_open_rpc::{Module, Project};
 
 use crate::error::Error;
 use crate::metrics::MetricsLogger;
+use crate::rate_limiter::RpcRateLimiter;
 use crate::routing_layer::RpcRouter;
 
 pub mod authority_state;
 pub mod axum_router;
 mod balance_changes;
 pub mod coin_api;
+pub mod dwallet_api;
 pub mod error;
 pub mod governance_api;
 pub mod indexer_api;
@@ -40,6 +43,7 @@ mod metrics;
 pub mod move_utils;
 pub mod name_service;
 mod object_changes;
+mod rate_limiter;
 pub mod read_api;
 mod routing_layer;
 pub mod transaction_builder_api;
@@ -53,6 +57,7 @@ pub struct JsonRpcServerBuilder {
     module: RpcModule<()>,
     rpc_doc: Project,
     registry: Registry,
+    rate_limit_config: JsonRpcRateLimitConfig,
 }
 
 pub fn sui_rpc_doc(version: &str) -> Project {
@@ -79,9 +84,14 @@ impl JsonRpcServerBuilder {
             module: RpcModule::new(()),
             rpc_doc: sui_rpc_doc(version),
             registry: prometheus_registry.clone(),
+            rate_limit_config: JsonRpcRateLimitConfig::default(),
         }
     }
 
+    pub fn set_rate_limit_config(&mut self, config: JsonRpcRateLimitConfig) {
+        self.rate_limit_config = config;
+    }
+
     pub fn register_module<T: SuiRpcModule>(&mut self, module: T) -> Result<(), Error> {
         self.rpc_doc.add_module(T::rpc_doc_module());
         Ok(self.module.merge(module.rpc())?)
@@ -164,13 +174,18 @@ impl JsonRpcServerBuilder {
         let methods_names = module.method_names().collect::<Vec<_>>();
 
         let metrics_logger = MetricsLogger::new(&self.registry, &methods_names);
+        let rate_limiter = RpcRateLimiter::new(self.rate_limit_config.clone())?;
 
         let middleware = tower::ServiceBuilder::new()
             .layer(Self::trace_layer())
             .layer(Self::cors()?);
 
-        let service =
-            crate::axum_router::JsonRpcService::new(module.into(), rpc_router, metrics_logger);
+        let service = crate::axum_router::JsonRpcService::new(
+            module.into(),
+            rpc_router,
+            metrics_logger,
+            rate_limiter,
+        );
 
         let mut router = axum::Router::new();
 
@@ -224,7 +239,8 @@ impl JsonRpcServerBuilder {
     ) -> Result<ServerHandle, Error> {
         let app = self.to_router(server_type)?;
 
-        let server = axum::Server::bind(&listen_address).serve(app.into_make_service());
+        let server = axum::Server::bind(&listen_address)
+            .serve(app.into_make_service_with_connect_info::<SocketAddr>());
 
         let addr = server.local_addr();
         let handle = tokio::spawn(async move { server.await.unwrap() });
@@ -11,8 +11,8 @@ use prometheus::{
     register_histogram_vec_with_registry, register_int_counter_vec_with_registry,
     register_int_gauge_vec_with_registry, HistogramVec, IntCounterVec, IntGaugeVec,
 };
-use sui_json_rpc_api::TRANSIENT_ERROR_CODE;
 use sui_json_rpc_api::{CLIENT_SDK_TYPE_HEADER, CLIENT_TARGET_API_VERSION_HEADER};
+use sui_json_rpc_api::{RATE_LIMITED_ERROR_CODE, TRANSIENT_ERROR_CODE};
 use tokio::time::Instant;
 
 const SPAM_LABEL: &str = "SPAM";
@@ -33,6 +33,8 @@ pub struct Metrics {
     server_errors_by_route: IntCounterVec,
     client_errors_by_route: IntCounterVec,
     transient_errors_by_route: IntCounterVec,
+    /// Requests rejected for exceeding a rate limit, by route
+    rate_limited_by_route: IntCounterVec,
     /// Client info
     client: IntCounterVec,
     /// Connection count
@@ -103,6 +105,13 @@ impl MetricsLogger {
                 registry,
             )
             .unwrap(),
+            rate_limited_by_route: register_int_counter_vec_with_registry!(
+                "rate_limited_requests_by_route",
+                "Number of requests rejected for exceeding a rate limit by route",
+                &["route"],
+                registry,
+            )
+            .unwrap(),
             errors_by_route: register_int_counter_vec_with_registry!(
                 "errors_by_route",
                 "Number of client and server errors by route",
@@ -242,6 +251,11 @@ impl Logger for MetricsLogger {
                     .transient_errors_by_route
                     .with_label_values(&[method_name])
                     .inc();
+            } else if code == RATE_LIMITED_ERROR_CODE {
+                self.metrics
+                    .rate_limited_by_route
+                    .with_label_values(&[method_name])
+                    .inc();
             } else {
                 self.metrics
                     .client_errors_by_route
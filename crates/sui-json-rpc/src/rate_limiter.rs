@@ -0,0 +1,242 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use std::collections::HashMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::num::NonZeroU32;
+use std::str::FromStr;
+use std::time::Duration;
+
+use governor::clock::{Clock, DefaultClock};
+use governor::{DefaultDirectRateLimiter, DefaultKeyedRateLimiter, Quota, RateLimiter};
+use hyper::header::HeaderName;
+use hyper::HeaderMap;
+use jsonrpsee::types::{ErrorObject, ErrorObjectOwned};
+use sui_config::node::JsonRpcRateLimitConfig;
+use sui_json_rpc_api::RATE_LIMITED_ERROR_CODE;
+
+use crate::error::Error;
+
+/// The most clients tracked by a per-client rate limiter, beyond which the clients whose limit
+/// has been replenished are forgotten.
+const MAX_TRACKED_CLIENTS: usize = 100_000;
+
+/// Enforces the rate limits of a [JsonRpcRateLimitConfig] on the requests to the JSON-RPC server.
+pub struct RpcRateLimiter {
+    config: JsonRpcRateLimitConfig,
+    client_ip_header: Option<HeaderName>,
+    client_limiter: Option<DefaultKeyedRateLimiter<String>>,
+    method_limiters: HashMap<String, MethodRateLimiter>,
+}
+
+struct MethodRateLimiter {
+    limiter: Option<DefaultDirectRateLimiter>,
+    client_limiter: Option<DefaultKeyedRateLimiter<String>>,
+}
+
+impl RpcRateLimiter {
+    pub fn new(config: JsonRpcRateLimitConfig) -> Result<Self, Error> {
+        let client_ip_header = config
+            .client_ip_header
+            .as_deref()
+            .map(HeaderName::from_str)
+            .transpose()?;
+        let method_limiters = config
+            .method_rate_limits
+            .iter()
+            .map(|(method, limit)| {
+                let limiter = MethodRateLimiter {
+                    limiter: limit
+                        .rate_limit
+                        .map(|limit| RateLimiter::direct(quota(limit))),
+                    client_limiter: limit.client_rate_limit.map(keyed_rate_limiter),
+                };
+                (method.clone(), limiter)
+            })
+            .collect();
+        Ok(Self {
+            client_ip_header,
+            client_limiter: config.client_rate_limit.map(keyed_rate_limiter),
+            method_limiters,
+            config,
+        })
+    }
+
+    /// The client of a request: the last address of the client IP header if it is configured
+    /// and present, the one appended by the trusted proxy, or else the IP address of the
+    /// connection.
+    pub fn client(&self, headers: &HeaderMap, remote_addr: Option<SocketAddr>) -> Option<String> {
+        self.client_ip_header
+            .as_ref()
+            .and_then(|header| headers.get(header))
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.rsplit(',').next())
+            .map(|client| client.trim().to_owned())
+            .filter(|client| !client.is_empty())
+            .or_else(|| remote_addr.map(|addr| addr.ip().to_string()))
+    }
+
+    /// Check a request of `client` to `method` against the rate limits. The per-client limits
+    /// are not applied to a request of an unknown client.
+    ///
+    /// The limits of the client are checked first, for the requests they reject not to use up
+    /// the limit of the method, which all the clients share.
+    pub fn check(&self, method: &str, client: Option<&str>) -> Result<(), ErrorObjectOwned> {
+        let method_limiter = self.method_limiters.get(method);
+        if let Some(client) = client {
+            let client_limiters = method_limiter
+                .and_then(|limiter| limiter.client_limiter.as_ref())
+                .into_iter()
+                .chain(&self.client_limiter);
+            for limiter in client_limiters {
+                check_client(limiter, client).map_err(|wait| rate_limited(method, wait))?;
+            }
+        }
+        if let Some(limiter) = method_limiter.and_then(|limiter| limiter.limiter.as_ref()) {
+            limiter.check().map_err(|not_until| {
+                rate_limited(
+                    method,
+                    not_until.wait_time_from(DefaultClock::default().now()),
+                )
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for RpcRateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcRateLimiter")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+fn quota(limit: NonZeroU32) -> Quota {
+    Quota::per_second(limit)
+}
+
+fn keyed_rate_limiter(limit: NonZeroU32) -> DefaultKeyedRateLimiter<String> {
+    RateLimiter::keyed(quota(limit))
+}
+
+/// Check a request of `client` against `limiter`, returning how long to wait if it is over the
+/// limit.
+fn check_client(limiter: &DefaultKeyedRateLimiter<String>, client: &str) -> Result<(), Duration> {
+    if limiter.len() > MAX_TRACKED_CLIENTS {
+        limiter.retain_recent();
+    }
+    limiter
+        .check_key(&client.to_owned())
+        .map_err(|not_until| not_until.wait_time_from(DefaultClock::default().now()))
+}
+
+fn rate_limited(method: &str, wait: Duration) -> ErrorObjectOwned {
+    ErrorObject::owned(
+        RATE_LIMITED_ERROR_CODE,
+        format!(
+            "Too many requests to {method}, retry in {}ms",
+            wait.as_millis()
+        ),
+        None::<()>,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use sui_config::node::JsonRpcMethodRateLimit;
+
+    const METHOD: &str = "dwallet_getSignSession";
+
+    fn limiter(method_rate_limit: JsonRpcMethodRateLimit) -> RpcRateLimiter {
+        RpcRateLimiter::new(JsonRpcRateLimitConfig {
+            method_rate_limits: BTreeMap::from([(METHOD.to_owned(), method_rate_limit)]),
+            client_ip_header: Some("x-forwarded-for".to_owned()),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_method_client_rate_limit() {
+        let limiter = limiter(JsonRpcMethodRateLimit {
+            rate_limit: None,
+            client_rate_limit: NonZeroU32::new(1),
+        });
+
+        assert!(limiter.check(METHOD, Some("10.0.0.1")).is_ok());
+        let error = limiter.check(METHOD, Some("10.0.0.1")).unwrap_err();
+        assert_eq!(error.code(), RATE_LIMITED_ERROR_CODE);
+        // The limit is per client, per method, and does not apply to unknown clients.
+        assert!(limiter.check(METHOD, Some("10.0.0.2")).is_ok());
+        assert!(limiter
+            .check("dwallet_getDWallet", Some("10.0.0.1"))
+            .is_ok());
+        assert!(limiter.check(METHOD, None).is_ok());
+    }
+
+    #[test]
+    fn test_method_rate_limit() {
+        let limiter = limiter(JsonRpcMethodRateLimit {
+            rate_limit: NonZeroU32::new(1),
+            client_rate_limit: None,
+        });
+
+        assert!(limiter.check(METHOD, Some("10.0.0.1")).is_ok());
+        assert!(limiter.check(METHOD, Some("10.0.0.2")).is_err());
+        assert!(limiter.check(METHOD, None).is_err());
+    }
+
+    #[test]
+    fn test_client() {
+        let limiter = limiter(JsonRpcMethodRateLimit::default());
+        let remote_addr = "10.0.0.3:1234".parse().ok();
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            limiter.client(&headers, remote_addr).as_deref(),
+            Some("10.0.0.3")
+        );
+        // The client can prepend any address, the trusted proxy appends the one it sees.
+        headers.insert("x-forwarded-for", "10.0.0.1, 10.0.0.2".parse().unwrap());
+        assert_eq!(
+            limiter.client(&headers, remote_addr).as_deref(),
+            Some("10.0.0.2")
+        );
+
+        // Without a trusted proxy, the header is ignored.
+        let limiter = RpcRateLimiter::new(JsonRpcRateLimitConfig::default()).unwrap();
+        assert_eq!(
+            limiter.client(&headers, remote_addr).as_deref(),
+            Some("10.0.0.3")
+        );
+    }
+
+    #[test]
+    fn test_client_rate_limit_before_method_rate_limit() {
+        let limiter = RpcRateLimiter::new(JsonRpcRateLimitConfig {
+            client_rate_limit: NonZeroU32::new(1),
+            method_rate_limits: BTreeMap::from([(
+                METHOD.to_owned(),
+                JsonRpcMethodRateLimit {
+                    rate_limit: NonZeroU32::new(2),
+                    client_rate_limit: None,
+                },
+            )]),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(limiter.check(METHOD, Some("10.0.0.1")).is_ok());
+        // The requests of a client over its limit do not use up the limit of the method.
+        for _ in 0..10 {
+            assert!(limiter.check(METHOD, Some("10.0.0.1")).is_err());
+        }
+        assert!(limiter.check(METHOD, Some("10.0.0.2")).is_ok());
+        // Until the limit of the method is reached.
+        assert!(limiter.check(METHOD, Some("10.0.0.3")).is_err());
+    }
+}
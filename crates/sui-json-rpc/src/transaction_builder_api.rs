@@ -12,12 +12,16 @@ use move_core_types::language_storage::StructTag;
 use sui_core::authority::AuthorityState;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_api::{TransactionBuilderOpenRpc, TransactionBuilderServer};
-use sui_json_rpc_types::{RPCTransactionRequestParams, SuiObjectDataFilter};
+use sui_json_rpc_types::{
+    RPCTransactionRequestParams, SignBatch, SignBatchTransaction, SignRequestParams,
+    SuiObjectDataFilter,
+};
 use sui_json_rpc_types::{
     SuiObjectDataOptions, SuiObjectResponse, SuiTransactionBlockBuilderMode, SuiTypeTag,
     TransactionBlockBytes,
 };
 use sui_open_rpc::Module;
+use sui_protocol_config::ProtocolConfig;
 use sui_transaction_builder::{DataReader, TransactionBuilder};
 use sui_types::base_types::ObjectInfo;
 use sui_types::base_types::{ObjectID, SuiAddress};
@@ -77,6 +81,11 @@ impl DataReader for AuthorityStateDataReader {
         let epoch_store = self.0.load_epoch_store_one_call_per_task();
         Ok(epoch_store.reference_gas_price())
     }
+
+    async fn get_protocol_config(&self) -> Result<ProtocolConfig, anyhow::Error> {
+        let epoch_store = self.0.load_epoch_store_one_call_per_task();
+        Ok(epoch_store.protocol_config().clone())
+    }
 }
 
 #[async_trait]
@@ -313,6 +322,23 @@ impl TransactionBuilderServer for TransactionBuilderApi {
                 .await?,
         )?)
     }
+
+    async fn batch_sign(
+        &self,
+        signer: SuiAddress,
+        requests: Vec<SignRequestParams>,
+        gas_budget: BigInt<u64>,
+    ) -> RpcResult<SignBatch> {
+        let batches = self.0.batch_sign(signer, requests, *gas_budget).await?;
+        let mut transactions = Vec::with_capacity(batches.len());
+        for batch in batches {
+            transactions.push(SignBatchTransaction {
+                transaction: TransactionBlockBytes::from_data(batch.data)?,
+                requests: batch.requests.into_iter().map(|r| r as u64).collect(),
+            });
+        }
+        Ok(SignBatch { transactions })
+    }
 }
 
 impl SuiRpcModule for TransactionBuilderApi {
@@ -0,0 +1,396 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Encryption of keystore files with a passphrase.
+//!
+//! The key of the file is derived from the passphrase with argon2id, and the contents of the file
+//! are sealed with XChaCha20-Poly1305, under a fresh nonce on every write. The parameters of the
+//! key derivation are authenticated along with the contents, for a tampered file not to open.
+//! See [crate::keystore::FileBasedKeystore::encrypt].
+//!
+//! The user shares of dWallets are encrypted the same way, with a [ShareEncryptionKey] derived
+//! from the key of their account rather than from a passphrase.
+//!
+//! User shares sent to another account, when a dWallet is transferred, are encrypted to the
+//! published [ShareTransferKey] of the recipient: the [ShareEncryptionKey] is then agreed with
+//! ECDH on secp256k1 between a fresh ephemeral key and the transfer key (ECIES).
+//!
+//! User shares exported for backup are encrypted either to a transfer key, or with a
+//! [ShareEncryptionKey] derived from a passphrase like the key of a keystore file.
+
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::hash::HashFunction;
+use fastcrypto::traits::ToFromBytes;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use secp256k1::ecdh::SharedSecret;
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+use serde::{Deserialize, Serialize};
+use sui_types::crypto::{DefaultHash, SuiKeyPair};
+use zeroize::Zeroizing;
+
+/// The environment variable holding the passphrase of encrypted keystores, for non-interactive
+/// use. The passphrase is asked on the terminal when it is not set.
+pub const KEYSTORE_PASSPHRASE_ENV: &str = "SUI_KEYSTORE_PASSPHRASE";
+/// The environment variable holding the passphrase of exported dWallet user shares, for
+/// non-interactive use. The passphrase is asked on the terminal when it is not set.
+pub const SHARE_PASSPHRASE_ENV: &str = "SUI_DWALLET_SHARE_PASSPHRASE";
+
+const KEY_LENGTH: usize = 32;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 24;
+const SHARE_ENCRYPTION_KEY_DOMAIN: &[u8] = b"dwallet-user-share-encryption";
+const SHARE_TRANSFER_KEY_DOMAIN: &[u8] = b"dwallet-user-share-transfer";
+const SHARE_TRANSFER_ECDH_DOMAIN: &[u8] = b"dwallet-user-share-transfer-ecdh";
+/// The length of compressed secp256k1 public keys.
+const PUBLIC_KEY_LENGTH: usize = 33;
+
+/// The contents of an encrypted keystore file.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptedKeystore {
+    pub kdf: Kdf,
+    pub cipher: Cipher,
+    /// Base64 encoded.
+    pub nonce: String,
+    /// Base64 encoded, with the authentication tag.
+    pub ciphertext: String,
+}
+
+/// The derivation of the key of an [EncryptedKeystore] from its passphrase.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "algorithm", rename_all = "lowercase")]
+pub enum Kdf {
+    Argon2id {
+        memory_kib: u32,
+        iterations: u32,
+        parallelism: u32,
+        /// Base64 encoded.
+        salt: String,
+    },
+}
+
+impl Kdf {
+    /// The default argon2id parameters, with a fresh salt.
+    pub fn argon2id() -> Self {
+        let mut salt = [0; SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        Kdf::Argon2id {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+            salt: Base64::encode(salt),
+        }
+    }
+
+    /// The key derived from `passphrase`.
+    fn derive_key(&self, passphrase: &str) -> Result<Zeroizing<[u8; KEY_LENGTH]>, anyhow::Error> {
+        let Kdf::Argon2id {
+            memory_kib,
+            iterations,
+            parallelism,
+            salt,
+        } = self;
+        let salt = Base64::decode(salt).map_err(|e| anyhow!("Invalid key derivation salt: {e}"))?;
+        let params = Params::new(*memory_kib, *iterations, *parallelism, Some(KEY_LENGTH))
+            .map_err(|e| anyhow!("Invalid key derivation parameters: {e}"))?;
+        let mut key = Zeroizing::new([0; KEY_LENGTH]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut key[..])
+            .map_err(|e| anyhow!("Cannot derive the key from the passphrase: {e}"))?;
+        Ok(key)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Cipher {
+    XChaCha20Poly1305,
+}
+
+/// The key of an encrypted keystore, derived from its passphrase.
+pub struct KeystoreCipher {
+    kdf: Kdf,
+    key: Zeroizing<[u8; KEY_LENGTH]>,
+}
+
+impl KeystoreCipher {
+    /// A key derived from `passphrase` with a fresh salt, to encrypt a keystore with.
+    pub fn new(passphrase: &str) -> Result<Self, anyhow::Error> {
+        Self::derive(passphrase, Kdf::argon2id())
+    }
+
+    fn derive(passphrase: &str, kdf: Kdf) -> Result<Self, anyhow::Error> {
+        let key = kdf.derive_key(passphrase)?;
+        Ok(Self { kdf, key })
+    }
+
+    /// Decrypt `keystore` with `passphrase`, returning the key to encrypt it again with, and its
+    /// contents.
+    pub fn unlock(
+        keystore: &EncryptedKeystore,
+        passphrase: &str,
+    ) -> Result<(Self, Zeroizing<Vec<u8>>), anyhow::Error> {
+        let cipher = Self::derive(passphrase, keystore.kdf.clone())?;
+        let nonce =
+            Base64::decode(&keystore.nonce).map_err(|e| anyhow!("Invalid keystore nonce: {e}"))?;
+        if nonce.len() != NONCE_LENGTH {
+            bail!("Invalid keystore nonce length {}", nonce.len());
+        }
+        let ciphertext = Base64::decode(&keystore.ciphertext)
+            .map_err(|e| anyhow!("Invalid keystore ciphertext: {e}"))?;
+        let plaintext = XChaCha20Poly1305::new(Key::from_slice(&cipher.key[..]))
+            .decrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: &serde_json::to_vec(&keystore.kdf)?,
+                },
+            )
+            .map_err(|_| anyhow!("Wrong passphrase, or the keystore is corrupted"))?;
+        Ok((cipher, Zeroizing::new(plaintext)))
+    }
+
+    /// Encrypt `plaintext` under a fresh nonce.
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<EncryptedKeystore, anyhow::Error> {
+        let mut nonce = [0; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = XChaCha20Poly1305::new(Key::from_slice(&self.key[..]))
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: &serde_json::to_vec(&self.kdf)?,
+                },
+            )
+            .map_err(|_| anyhow!("Cannot encrypt the keystore"))?;
+        Ok(EncryptedKeystore {
+            kdf: self.kdf.clone(),
+            cipher: Cipher::XChaCha20Poly1305,
+            nonce: Base64::encode(nonce),
+            ciphertext: Base64::encode(ciphertext),
+        })
+    }
+}
+
+/// The key encrypting the user shares of the dWallets of an account.
+///
+/// It is derived from the private key of the account, itself derived from the mnemonic of the
+/// account, for the account and its dWallets to be restored from the mnemonic alone.
+pub struct ShareEncryptionKey(Zeroizing<[u8; KEY_LENGTH]>);
+
+impl ShareEncryptionKey {
+    pub fn derive(keypair: &SuiKeyPair) -> Self {
+        Self(derive_secret(SHARE_ENCRYPTION_KEY_DOMAIN, keypair))
+    }
+
+    /// The key derived from `passphrase` with `kdf`, e.g. [Kdf::argon2id], for user shares
+    /// exported to be restored without the key of their account.
+    pub fn from_passphrase(passphrase: &str, kdf: &Kdf) -> Result<Self, anyhow::Error> {
+        Ok(Self(kdf.derive_key(passphrase)?))
+    }
+
+    /// The key agreed between the ephemeral key `ephemeral` of a sender and the share transfer
+    /// key `transfer_key` of a recipient, whose ECDH shared secret is `shared_secret`.
+    fn agree(ephemeral: &PublicKey, transfer_key: &PublicKey, shared_secret: SharedSecret) -> Self {
+        let mut hasher = DefaultHash::default();
+        hasher.update(SHARE_TRANSFER_ECDH_DOMAIN);
+        hasher.update(ephemeral.serialize());
+        hasher.update(transfer_key.serialize());
+        hasher.update(shared_secret.secret_bytes());
+        Self(Zeroizing::new(hasher.finalize().digest))
+    }
+
+    /// Encrypt `share` under a fresh nonce, binding it to `context`, e.g. the id of its dWallet.
+    /// Returns `nonce || ciphertext`.
+    pub fn encrypt(&self, share: &[u8], context: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        let mut nonce = [0; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = XChaCha20Poly1305::new(Key::from_slice(&self.0[..]))
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: share,
+                    aad: context,
+                },
+            )
+            .map_err(|_| anyhow!("Cannot encrypt the user share"))?;
+        Ok([&nonce[..], &ciphertext].concat())
+    }
+
+    /// Decrypt `nonce || ciphertext`, encrypted with `context` by [Self::encrypt].
+    pub fn decrypt(
+        &self,
+        encrypted: &[u8],
+        context: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, anyhow::Error> {
+        if encrypted.len() < NONCE_LENGTH {
+            bail!("Invalid encrypted user share");
+        }
+        let (nonce, ciphertext) = encrypted.split_at(NONCE_LENGTH);
+        XChaCha20Poly1305::new(Key::from_slice(&self.0[..]))
+            .decrypt(
+                XNonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: context,
+                },
+            )
+            .map(Zeroizing::new)
+            .map_err(|_| anyhow!("Wrong key, or the encrypted user share is corrupted"))
+    }
+}
+
+/// The secp256k1 key of an account to which the user shares of dWallets transferred to it are
+/// encrypted, see [encrypt_share_to].
+///
+/// Like the [ShareEncryptionKey], it is derived from the private key of the account, for the
+/// shares sent to the account to be decrypted with its mnemonic alone. Its public key is
+/// published on chain for senders to find.
+pub struct ShareTransferKey(SecretKey);
+
+impl ShareTransferKey {
+    pub fn derive(keypair: &SuiKeyPair) -> Self {
+        let secret = derive_secret(SHARE_TRANSFER_KEY_DOMAIN, keypair);
+        Self(
+            SecretKey::from_slice(&secret[..])
+                .expect("a 32-byte hash is a secp256k1 secret key with overwhelming probability"),
+        )
+    }
+
+    /// The compressed SEC1 public key to publish.
+    pub fn public_key(&self) -> Vec<u8> {
+        self.0
+            .public_key(&Secp256k1::signing_only())
+            .serialize()
+            .to_vec()
+    }
+
+    /// Decrypt a share encrypted to this key with `context` by [encrypt_share_to].
+    pub fn decrypt(
+        &self,
+        encrypted: &[u8],
+        context: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, anyhow::Error> {
+        if encrypted.len() < PUBLIC_KEY_LENGTH {
+            bail!("Invalid encrypted user share");
+        }
+        let (ephemeral, encrypted) = encrypted.split_at(PUBLIC_KEY_LENGTH);
+        let ephemeral = PublicKey::from_slice(ephemeral)
+            .map_err(|_| anyhow!("Invalid encrypted user share"))?;
+        let transfer_key = self.0.public_key(&Secp256k1::signing_only());
+        let shared_secret = SharedSecret::new(&ephemeral, &self.0);
+        ShareEncryptionKey::agree(&ephemeral, &transfer_key, shared_secret)
+            .decrypt(encrypted, context)
+    }
+}
+
+/// Encrypt `share` to `transfer_key`, the published public key of the [ShareTransferKey] of
+/// another account, binding it to `context`, e.g. the id of its dWallet. Returns
+/// `ephemeral public key || nonce || ciphertext`.
+pub fn encrypt_share_to(
+    transfer_key: &[u8],
+    share: &[u8],
+    context: &[u8],
+) -> Result<Vec<u8>, anyhow::Error> {
+    let transfer_key = PublicKey::from_slice(transfer_key)
+        .map_err(|e| anyhow!("Invalid share transfer key: {e}"))?;
+    let ephemeral_secret = loop {
+        let mut bytes = Zeroizing::new([0; KEY_LENGTH]);
+        OsRng.fill_bytes(&mut bytes[..]);
+        if let Ok(secret) = SecretKey::from_slice(&bytes[..]) {
+            break secret;
+        }
+    };
+    let ephemeral = ephemeral_secret.public_key(&Secp256k1::signing_only());
+    let shared_secret = SharedSecret::new(&transfer_key, &ephemeral_secret);
+    let encrypted = ShareEncryptionKey::agree(&ephemeral, &transfer_key, shared_secret)
+        .encrypt(share, context)?;
+    Ok([&ephemeral.serialize()[..], &encrypted].concat())
+}
+
+/// A secret derived from the private key of `keypair`, separated by `domain`.
+fn derive_secret(domain: &[u8], keypair: &SuiKeyPair) -> Zeroizing<[u8; KEY_LENGTH]> {
+    let private_key = match keypair {
+        SuiKeyPair::Ed25519(kp) => kp.as_bytes(),
+        SuiKeyPair::Secp256k1(kp) => kp.as_bytes(),
+        SuiKeyPair::Secp256r1(kp) => kp.as_bytes(),
+    };
+    let mut hasher = DefaultHash::default();
+    hasher.update(domain);
+    hasher.update([keypair.public().flag()]);
+    hasher.update(private_key);
+    Zeroizing::new(hasher.finalize().digest)
+}
+
+/// The passphrase of the encrypted keystore at `path`, from [KEYSTORE_PASSPHRASE_ENV] or else
+/// asked on the terminal.
+pub fn read_passphrase(path: &Path) -> Result<Zeroizing<String>, anyhow::Error> {
+    prompt_passphrase(
+        KEYSTORE_PASSPHRASE_ENV,
+        format!("Passphrase of keystore {}: ", path.display()),
+    )
+}
+
+/// A new passphrase for the keystore at `path`, from [KEYSTORE_PASSPHRASE_ENV] or else asked
+/// twice on the terminal.
+pub fn read_new_passphrase(path: &Path) -> Result<Zeroizing<String>, anyhow::Error> {
+    prompt_new_passphrase(
+        KEYSTORE_PASSPHRASE_ENV,
+        format!("Passphrase of keystore {}: ", path.display()),
+    )
+}
+
+/// The passphrase of the exported user share at `path`, from [SHARE_PASSPHRASE_ENV] or else
+/// asked on the terminal.
+pub fn read_share_passphrase(path: &Path) -> Result<Zeroizing<String>, anyhow::Error> {
+    prompt_passphrase(
+        SHARE_PASSPHRASE_ENV,
+        format!("Passphrase of user share {}: ", path.display()),
+    )
+}
+
+/// A new passphrase for the user share exported to `path`, from [SHARE_PASSPHRASE_ENV] or else
+/// asked twice on the terminal.
+pub fn read_new_share_passphrase(path: &Path) -> Result<Zeroizing<String>, anyhow::Error> {
+    prompt_new_passphrase(
+        SHARE_PASSPHRASE_ENV,
+        format!("Passphrase of user share {}: ", path.display()),
+    )
+}
+
+fn prompt_passphrase(env: &str, prompt: String) -> Result<Zeroizing<String>, anyhow::Error> {
+    if let Ok(passphrase) = std::env::var(env) {
+        return Ok(Zeroizing::new(passphrase));
+    }
+    rpassword::prompt_password(prompt)
+        .map(Zeroizing::new)
+        .with_context(|| format!("Cannot read the passphrase, set it in {env} instead"))
+}
+
+fn prompt_new_passphrase(env: &str, prompt: String) -> Result<Zeroizing<String>, anyhow::Error> {
+    let passphrase = match std::env::var(env) {
+        Ok(passphrase) => Zeroizing::new(passphrase),
+        Err(_) => {
+            let passphrase = prompt_passphrase(env, prompt)?;
+            let confirmation = Zeroizing::new(
+                rpassword::prompt_password("Confirm the passphrase: ")
+                    .context("Cannot read the passphrase")?,
+            );
+            if passphrase != confirmation {
+                bail!("The passphrases do not match");
+            }
+            passphrase
+        }
+    };
+    if passphrase.is_empty() {
+        bail!("The passphrase must not be empty");
+    }
+    Ok(passphrase)
+}
@@ -0,0 +1,58 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Signers whose keys are held outside the process, such as remote signing services or hardware
+//! security modules.
+//!
+//! An [ExternalSigner] only signs the digests of intent messages, as the keys of a keystore do, so
+//! that integrations need not know the transactions they sign nor how Sui serializes them. See
+//! [sign_secure], and `WalletContext::with_external_signer` in the SDK.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use fastcrypto::hash::HashFunction;
+use serde::Serialize;
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{DefaultHash, PublicKey, Signature, SuiSignature};
+
+/// A signer holding keys outside the process.
+#[async_trait]
+pub trait ExternalSigner: Send + Sync {
+    /// The public keys of the accounts of the signer.
+    async fn public_keys(&self) -> anyhow::Result<Vec<PublicKey>>;
+
+    /// Sign `digest`, the Blake2b-256 hash of a BCS serialized intent message, with the key of
+    /// `address`, as `Signature::new_hashed` does with a key held in the process.
+    async fn sign_digest(
+        &self,
+        address: &SuiAddress,
+        digest: &[u8; 32],
+    ) -> anyhow::Result<Signature>;
+}
+
+/// Sign `msg` with `intent` by `address` through `signer`, checking that the signature is valid.
+pub async fn sign_secure<T: Serialize>(
+    signer: &dyn ExternalSigner,
+    address: &SuiAddress,
+    msg: &T,
+    intent: Intent,
+) -> anyhow::Result<Signature> {
+    let intent_message = IntentMessage::new(intent, msg);
+    let signature = signer
+        .sign_digest(address, &intent_message_digest(&intent_message)?)
+        .await?;
+    signature
+        .verify_secure(&intent_message, *address, signature.scheme())
+        .map_err(|e| anyhow!("Invalid signature of [{address}] from the external signer: {e}"))?;
+    Ok(signature)
+}
+
+/// The digest of `intent_message` signed by the keys of Sui.
+pub fn intent_message_digest<T: Serialize>(
+    intent_message: &IntentMessage<T>,
+) -> anyhow::Result<[u8; 32]> {
+    let mut hasher = DefaultHash::default();
+    hasher.update(bcs::to_bytes(intent_message)?);
+    Ok(hasher.finalize().digest)
+}
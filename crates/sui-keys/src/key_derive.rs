@@ -34,6 +34,26 @@ pub fn derive_key_pair_from_path(
     key_scheme: &SignatureScheme,
 ) -> Result<(SuiAddress, SuiKeyPair), SuiError> {
     let path = validate_path(key_scheme, derivation_path)?;
+    derive_key_pair(seed, path, key_scheme)
+}
+
+/// Like [derive_key_pair_from_path], at any derivation path valid for the key scheme rather than
+/// one following the conventions above, e.g. to import the key of an account of another wallet.
+/// See [validate_custom_path].
+pub fn derive_key_pair_from_custom_path(
+    seed: &[u8],
+    derivation_path: DerivationPath,
+    key_scheme: &SignatureScheme,
+) -> Result<(SuiAddress, SuiKeyPair), SuiError> {
+    let path = validate_custom_path(key_scheme, derivation_path)?;
+    derive_key_pair(seed, path, key_scheme)
+}
+
+fn derive_key_pair(
+    seed: &[u8],
+    path: DerivationPath,
+    key_scheme: &SignatureScheme,
+) -> Result<(SuiAddress, SuiKeyPair), SuiError> {
     match key_scheme {
         SignatureScheme::ED25519 => {
             let indexes = path.into_iter().map(|i| i.into()).collect::<Vec<_>>();
@@ -166,6 +186,61 @@ pub fn validate_path(
     }
 }
 
+/// Check that `path` can derive a key of `key_scheme`, whatever its purpose and levels: ed25519
+/// keys follow SLIP-0010, which only derives hardened levels.
+pub fn validate_custom_path(
+    key_scheme: &SignatureScheme,
+    path: DerivationPath,
+) -> Result<DerivationPath, SuiError> {
+    match key_scheme {
+        SignatureScheme::ED25519 => {
+            if path.iter().all(|level| level.is_hardened()) {
+                Ok(path)
+            } else {
+                Err(SuiError::SignatureKeyGenError(
+                    "Invalid path, ed25519 keys are only derived at hardened levels".to_string(),
+                ))
+            }
+        }
+        SignatureScheme::Secp256k1 | SignatureScheme::Secp256r1 => Ok(path),
+        SignatureScheme::BLS12381
+        | SignatureScheme::MultiSig
+        | SignatureScheme::ZkLoginAuthenticator => Err(SuiError::UnsupportedFeatureError {
+            error: format!("key derivation not supported {:?}", key_scheme),
+        }),
+    }
+}
+
+/// The derivation path of the account `account_index` of a mnemonic for `key_scheme`, the one of
+/// index 0 being the default path: m/44'/784'/0'/0'/{index}' for ed25519, m/54'/784'/0'/0/{index}
+/// for secp256k1 and m/74'/784'/0'/0/{index} for secp256r1.
+pub fn account_derivation_path(
+    key_scheme: &SignatureScheme,
+    account_index: u32,
+) -> Result<DerivationPath, SuiError> {
+    let path = match key_scheme {
+        SignatureScheme::ED25519 => format!(
+            "m/{DERVIATION_PATH_PURPOSE_ED25519}'/{DERIVATION_PATH_COIN_TYPE}'/0'/0'/{account_index}'"
+        ),
+        SignatureScheme::Secp256k1 => format!(
+            "m/{DERVIATION_PATH_PURPOSE_SECP256K1}'/{DERIVATION_PATH_COIN_TYPE}'/0'/0/{account_index}"
+        ),
+        SignatureScheme::Secp256r1 => format!(
+            "m/{DERVIATION_PATH_PURPOSE_SECP256R1}'/{DERIVATION_PATH_COIN_TYPE}'/0'/0/{account_index}"
+        ),
+        SignatureScheme::BLS12381
+        | SignatureScheme::MultiSig
+        | SignatureScheme::ZkLoginAuthenticator => {
+            return Err(SuiError::UnsupportedFeatureError {
+                error: format!("key derivation not supported {:?}", key_scheme),
+            })
+        }
+    };
+    path.parse().map_err(|_| {
+        SuiError::SignatureKeyGenError(format!("Invalid account index {account_index}"))
+    })
+}
+
 pub fn generate_new_key(
     key_scheme: SignatureScheme,
     derivation_path: Option<DerivationPath>,
@@ -0,0 +1,47 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Secrets held in the keyring of the platform: the macOS Keychain, the Secret Service on Linux or
+//! the Windows Credential Manager.
+//!
+//! Each secret is an entry of a service, such as [DEFAULT_KEYRING_SERVICE], named after what it
+//! secures, e.g. the address of a key. See [crate::keystore::KeyringKeystore].
+
+use anyhow::anyhow;
+use keyring::Entry;
+
+/// The keyring service of the secrets of the CLI.
+pub const DEFAULT_KEYRING_SERVICE: &str = "dwallet-network";
+
+/// Store `secret` as the entry `name` of `service`, replacing any previous one.
+pub fn set_secret(service: &str, name: &str, secret: &str) -> anyhow::Result<()> {
+    entry(service, name)?
+        .set_password(secret)
+        .map_err(|e| anyhow!("Cannot store [{name}] in the keyring service [{service}]: {e}"))
+}
+
+/// The entry `name` of `service`, `None` if there is none.
+pub fn get_secret(service: &str, name: &str) -> anyhow::Result<Option<String>> {
+    match entry(service, name)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(anyhow!(
+            "Cannot read [{name}] from the keyring service [{service}]: {e}"
+        )),
+    }
+}
+
+/// Remove the entry `name` of `service`, if any.
+pub fn delete_secret(service: &str, name: &str) -> anyhow::Result<()> {
+    match entry(service, name)?.delete_password() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(anyhow!(
+            "Cannot remove [{name}] from the keyring service [{service}]: {e}"
+        )),
+    }
+}
+
+fn entry(service: &str, name: &str) -> anyhow::Result<Entry> {
+    Entry::new(service, name)
+        .map_err(|e| anyhow!("Cannot access the keyring service [{service}]: {e}"))
+}
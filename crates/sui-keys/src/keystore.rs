@@ -1,7 +1,12 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
-use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
+use crate::encryption::{read_passphrase, EncryptedKeystore, KeystoreCipher};
+use crate::key_derive::{
+    derive_key_pair_from_custom_path, derive_key_pair_from_path, generate_new_key,
+};
+use crate::keyring;
+use crate::ledger::{self, HidLedgerTransport};
 use crate::random_names::{random_name, random_names};
 use anyhow::{anyhow, bail, ensure, Context};
 use bip32::DerivationPath;
@@ -17,17 +22,21 @@ use std::fs;
 use std::fs::File;
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::get_key_pair_from_rng;
 use sui_types::crypto::{
     enum_dispatch, EncodeDecodeBase64, PublicKey, Signature, SignatureScheme, SuiKeyPair,
+    SuiSignature,
 };
+use zeroize::Zeroizing;
 
 #[derive(Serialize, Deserialize)]
 #[enum_dispatch(AccountKeystore)]
 pub enum Keystore {
     File(FileBasedKeystore),
     InMem(InMemKeystore),
+    Keyring(KeyringKeystore),
 }
 #[enum_dispatch]
 pub trait AccountKeystore: Send + Sync {
@@ -117,6 +126,38 @@ pub trait AccountKeystore: Send + Sync {
         Ok((address, phrase, scheme))
     }
 
+    /// Generate a new key to replace the key of `address`, taking over its alias. The old key is
+    /// kept, renamed `<alias>-retired`, or `<alias>-retired-<n>` if taken, as it still owns the
+    /// objects of `address` until they are transferred.
+    fn rotate_key(
+        &mut self,
+        address: &SuiAddress,
+        key_scheme: SignatureScheme,
+        derivation_path: Option<DerivationPath>,
+        word_length: Option<String>,
+    ) -> Result<(SuiAddress, String, SignatureScheme), anyhow::Error> {
+        let alias = self.get_alias_by_address(address)?;
+        let retired_alias = std::iter::once(format!("{alias}-retired"))
+            .chain((2..).map(|n| format!("{alias}-retired-{n}")))
+            .find(|retired_alias| !self.alias_exists(retired_alias))
+            .expect("a free alias is found");
+        let (new_address, kp, scheme, phrase) =
+            generate_new_key(key_scheme, derivation_path, word_length)?;
+        self.update_alias(&alias, Some(&retired_alias))?;
+        self.add_key(Some(alias), kp)?;
+        Ok((new_address, phrase, scheme))
+    }
+
+    /// Add the account of `derivation_path` on a connected Ledger device, whose key stays on the
+    /// device: its transactions are signed by the device once approved by the user.
+    fn add_ledger_account(
+        &mut self,
+        _alias: Option<String>,
+        _derivation_path: DerivationPath,
+    ) -> Result<SuiAddress, anyhow::Error> {
+        bail!("This keystore does not support Ledger accounts")
+    }
+
     fn import_from_mnemonic(
         &mut self,
         phrase: &str,
@@ -134,6 +175,26 @@ pub trait AccountKeystore: Send + Sync {
             Err(e) => Err(anyhow!("error getting keypair {:?}", e)),
         }
     }
+
+    /// Like [Self::import_from_mnemonic], at any derivation path valid for `key_scheme`, see
+    /// [crate::key_derive::validate_custom_path].
+    fn import_from_mnemonic_with_custom_path(
+        &mut self,
+        phrase: &str,
+        key_scheme: SignatureScheme,
+        derivation_path: DerivationPath,
+    ) -> Result<SuiAddress, anyhow::Error> {
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English)
+            .map_err(|e| anyhow::anyhow!("Invalid mnemonic phrase: {:?}", e))?;
+        let seed = Seed::new(&mnemonic, "");
+        match derive_key_pair_from_custom_path(seed.as_bytes(), derivation_path, &key_scheme) {
+            Ok((address, kp)) => {
+                self.add_key(None, kp)?;
+                Ok(address)
+            }
+            Err(e) => Err(anyhow!("error getting keypair {:?}", e)),
+        }
+    }
 }
 
 impl Display for Keystore {
@@ -149,6 +210,12 @@ impl Display for Keystore {
                 writeln!(writer, "Keystore Type : InMem")?;
                 write!(f, "{}", writer)
             }
+            Keystore::Keyring(keyring) => {
+                writeln!(writer, "Keystore Type : Keyring")?;
+                writeln!(writer, "Keyring Service : {}", keyring.service)?;
+                write!(writer, "Keystore Path : {:?}", keyring.path)?;
+                write!(f, "{}", writer)
+            }
         }
     }
 }
@@ -159,10 +226,46 @@ pub struct Alias {
     pub public_key_base64: String,
 }
 
+/// An account whose key is held by the Sui app of a Ledger device, see [crate::ledger].
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LedgerAccount {
+    pub derivation_path: String,
+    pub public_key_base64: String,
+}
+
+impl LedgerAccount {
+    fn public_key(&self) -> Result<PublicKey, anyhow::Error> {
+        PublicKey::decode_base64(&self.public_key_base64)
+            .map_err(|e| anyhow!("Invalid public key of Ledger account: {e}"))
+    }
+
+    fn sign_secure<T: Serialize>(
+        &self,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, anyhow::Error> {
+        let derivation_path = DerivationPath::from_str(&self.derivation_path)
+            .map_err(|e| anyhow!("Invalid derivation path {}: {e}", self.derivation_path))?;
+        let transport = HidLedgerTransport::connect()?;
+        let signature = ledger::sign_secure(&transport, &derivation_path, msg, intent)?;
+        let public_key = self.public_key()?;
+        ensure!(
+            signature.public_key_bytes() == public_key.as_ref(),
+            "The connected Ledger device does not hold the key of [{}]",
+            SuiAddress::from(&public_key)
+        );
+        Ok(signature)
+    }
+}
+
 #[derive(Default)]
 pub struct FileBasedKeystore {
     keys: BTreeMap<SuiAddress, SuiKeyPair>,
     aliases: BTreeMap<SuiAddress, Alias>,
+    /// The accounts of Ledger devices, kept next to the keystore in a `.ledger` file.
+    ledger_accounts: BTreeMap<SuiAddress, LedgerAccount>,
+    /// The key of the keystore file if it is encrypted, see [Self::encrypt].
+    cipher: Option<KeystoreCipher>,
     path: Option<PathBuf>,
 }
 
@@ -194,6 +297,11 @@ impl<'de> Deserialize<'de> for FileBasedKeystore {
 
 impl AccountKeystore for FileBasedKeystore {
     fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        if self.ledger_accounts.contains_key(address) {
+            return Err(signature::Error::from_source(format!(
+                "The Ledger device of [{address}] only signs intent messages"
+            )));
+        }
         Ok(Signature::new_hashed(
             msg,
             self.keys.get(address).ok_or_else(|| {
@@ -210,6 +318,11 @@ impl AccountKeystore for FileBasedKeystore {
     where
         T: Serialize,
     {
+        if let Some(account) = self.ledger_accounts.get(address) {
+            return account
+                .sign_secure(msg, intent)
+                .map_err(|e| signature::Error::from_source(e.to_string()));
+        }
         Ok(Signature::new_secure(
             &IntentMessage::new(intent, msg),
             self.keys.get(address).ok_or_else(|| {
@@ -248,7 +361,15 @@ impl AccountKeystore for FileBasedKeystore {
     }
 
     fn keys(&self) -> Vec<PublicKey> {
-        self.keys.values().map(|key| key.public()).collect()
+        self.keys
+            .values()
+            .map(|key| key.public())
+            .chain(
+                self.ledger_accounts
+                    .values()
+                    .filter_map(|account| account.public_key().ok()),
+            )
+            .collect()
     }
 
     /// This function returns an error if the provided alias already exists. If the alias
@@ -290,10 +411,46 @@ impl AccountKeystore for FileBasedKeystore {
     fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
         match self.keys.get(address) {
             Some(key) => Ok(key),
+            None if self.ledger_accounts.contains_key(address) => Err(anyhow!(
+                "The key of address [{address}] is held by a Ledger device"
+            )),
             None => Err(anyhow!("Cannot find key for address: [{address}]")),
         }
     }
 
+    fn add_ledger_account(
+        &mut self,
+        alias: Option<String>,
+        derivation_path: DerivationPath,
+    ) -> Result<SuiAddress, anyhow::Error> {
+        let transport = HidLedgerTransport::connect()?;
+        let public_key =
+            PublicKey::Ed25519((&ledger::get_public_key(&transport, &derivation_path)?).into());
+        let address = SuiAddress::from(&public_key);
+        ensure!(
+            !self.keys.contains_key(&address) && !self.ledger_accounts.contains_key(&address),
+            "Address [{address}] is already in the keystore"
+        );
+        let alias = self.create_alias(alias)?;
+        let public_key_base64 = public_key.encode_base64();
+        self.aliases.insert(
+            address,
+            Alias {
+                alias,
+                public_key_base64: public_key_base64.clone(),
+            },
+        );
+        self.ledger_accounts.insert(
+            address,
+            LedgerAccount {
+                derivation_path: derivation_path.to_string(),
+                public_key_base64,
+            },
+        );
+        self.save()?;
+        Ok(address)
+    }
+
     /// Updates an old alias to the new alias and saves it to the alias file.
     /// If the new_alias is None, it will generate a new random alias.
     fn update_alias(
@@ -308,25 +465,39 @@ impl AccountKeystore for FileBasedKeystore {
 }
 
 impl FileBasedKeystore {
+    /// The keystore of the file at `path`, unlocked with a passphrase if it is encrypted, see
+    /// [read_passphrase].
     pub fn new(path: &PathBuf) -> Result<Self, anyhow::Error> {
-        let keys = if path.exists() {
-            let reader =
-                BufReader::new(File::open(path).with_context(|| {
+        let (keys, cipher) = if path.exists() {
+            let contents =
+                Zeroizing::new(fs::read(path).with_context(|| {
                     format!("Cannot open the keystore file: {}", path.display())
                 })?);
-            let kp_strings: Vec<String> = serde_json::from_reader(reader).with_context(|| {
-                format!("Cannot deserialize the keystore file: {}", path.display(),)
-            })?;
-            kp_strings
+            let (contents, cipher) = match serde_json::from_slice::<EncryptedKeystore>(&contents) {
+                Ok(encrypted) => {
+                    let (cipher, contents) =
+                        KeystoreCipher::unlock(&encrypted, &read_passphrase(path)?).with_context(
+                            || format!("Cannot decrypt the keystore file: {}", path.display()),
+                        )?;
+                    (contents, Some(cipher))
+                }
+                Err(_) => (contents, None),
+            };
+            let kp_strings: Zeroizing<Vec<String>> =
+                Zeroizing::new(serde_json::from_slice(&contents).with_context(|| {
+                    format!("Cannot deserialize the keystore file: {}", path.display(),)
+                })?);
+            let keys = kp_strings
                 .iter()
                 .map(|kpstr| {
                     let key = SuiKeyPair::decode_base64(kpstr);
                     key.map(|k| (SuiAddress::from(&k.public()), k))
                 })
                 .collect::<Result<BTreeMap<_, _>, _>>()
-                .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))?
+                .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))?;
+            (keys, cipher)
         } else {
-            BTreeMap::new()
+            (BTreeMap::new(), None)
         };
 
         // check aliases
@@ -392,9 +563,41 @@ impl FileBasedKeystore {
             aliases
         };
 
+        let mut ledger_path = path.clone();
+        ledger_path.set_extension("ledger");
+        let ledger_accounts = if ledger_path.exists() {
+            let reader = BufReader::new(File::open(&ledger_path).with_context(|| {
+                format!(
+                    "Cannot open Ledger accounts file in keystore: {}",
+                    ledger_path.display()
+                )
+            })?);
+            let accounts: Vec<LedgerAccount> =
+                serde_json::from_reader(reader).with_context(|| {
+                    format!(
+                        "Cannot deserialize Ledger accounts file in keystore: {}",
+                        ledger_path.display()
+                    )
+                })?;
+            accounts
+                .into_iter()
+                .map(|account| Ok((SuiAddress::from(&account.public_key()?), account)))
+                .collect::<Result<BTreeMap<_, _>, anyhow::Error>>()
+                .with_context(|| {
+                    format!(
+                        "Invalid Ledger accounts file in keystore: {}",
+                        ledger_path.display()
+                    )
+                })?
+        } else {
+            BTreeMap::new()
+        };
+
         Ok(Self {
             keys,
             aliases,
+            ledger_accounts,
+            cipher,
             path: Some(path.to_path_buf()),
         })
     }
@@ -423,15 +626,61 @@ impl FileBasedKeystore {
 
     pub fn save_keystore(&self) -> Result<(), anyhow::Error> {
         if let Some(path) = &self.path {
-            let store = serde_json::to_string_pretty(
-                &self
-                    .keys
+            let keys = Zeroizing::new(
+                self.keys
                     .values()
                     .map(EncodeDecodeBase64::encode_base64)
                     .collect::<Vec<_>>(),
-            )
-            .with_context(|| format!("Cannot serialize keystore to file: {}", path.display()))?;
-            fs::write(path, store)?;
+            );
+            let store =
+                Zeroizing::new(serde_json::to_string_pretty(&*keys).with_context(|| {
+                    format!("Cannot serialize keystore to file: {}", path.display())
+                })?);
+            match &self.cipher {
+                Some(cipher) => fs::write(
+                    path,
+                    serde_json::to_string_pretty(&cipher.encrypt(store.as_bytes())?)?,
+                )?,
+                None => fs::write(path, store.as_bytes())?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Encrypt the keystore file with `passphrase`, from now on. The file is then unlocked with
+    /// the passphrase when the keystore is loaded. The aliases and Ledger accounts files, which
+    /// hold no private key, are left in the clear.
+    pub fn encrypt(&mut self, passphrase: &str) -> Result<(), anyhow::Error> {
+        self.cipher = Some(KeystoreCipher::new(passphrase)?);
+        self.save_keystore()
+    }
+
+    /// Whether the keystore file is encrypted, see [Self::encrypt].
+    pub fn is_encrypted(&self) -> bool {
+        self.cipher.is_some()
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn save_ledger_accounts(&self) -> Result<(), anyhow::Error> {
+        if let Some(path) = &self.path {
+            if self.ledger_accounts.is_empty() {
+                return Ok(());
+            }
+            let accounts_store =
+                serde_json::to_string_pretty(&self.ledger_accounts.values().collect::<Vec<_>>())
+                    .with_context(|| {
+                        format!(
+                            "Cannot serialize Ledger accounts to file in keystore: {}",
+                            path.display()
+                        )
+                    })?;
+
+            let mut ledger_path = path.clone();
+            ledger_path.set_extension("ledger");
+            fs::write(ledger_path, accounts_store)?
         }
         Ok(())
     }
@@ -439,6 +688,7 @@ impl FileBasedKeystore {
     pub fn save(&self) -> Result<(), anyhow::Error> {
         self.save_aliases()?;
         self.save_keystore()?;
+        self.save_ledger_accounts()?;
         Ok(())
     }
 
@@ -599,6 +849,237 @@ impl InMemKeystore {
     }
 }
 
+/// A keystore holding its private keys in the keyring of the platform, see [crate::keyring], rather
+/// than in a plaintext file.
+///
+/// Each key is an entry of the keyring service `service` named after its address. The addresses
+/// and their aliases are listed, with the public keys only, in the file at `path`. In the client
+/// config:
+///
+/// ```yaml
+/// keystore:
+///   Keyring:
+///     service: dwallet-network
+///     path: /home/user/.sui/sui_config/sui.keyring
+/// ```
+pub struct KeyringKeystore {
+    service: String,
+    path: PathBuf,
+    keys: BTreeMap<SuiAddress, SuiKeyPair>,
+    aliases: BTreeMap<SuiAddress, Alias>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeyringKeystoreLocation {
+    service: String,
+    path: PathBuf,
+}
+
+impl Serialize for KeyringKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        KeyringKeystoreLocation {
+            service: self.service.clone(),
+            path: self.path.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyringKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        let location = KeyringKeystoreLocation::deserialize(deserializer)?;
+        KeyringKeystore::new(location.service, &location.path).map_err(D::Error::custom)
+    }
+}
+
+impl AccountKeystore for KeyringKeystore {
+    fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        Ok(Signature::new_hashed(
+            msg,
+            self.keys.get(address).ok_or_else(|| {
+                signature::Error::from_source(format!("Cannot find key for address: [{address}]"))
+            })?,
+        ))
+    }
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        Ok(Signature::new_secure(
+            &IntentMessage::new(intent, msg),
+            self.keys.get(address).ok_or_else(|| {
+                signature::Error::from_source(format!("Cannot find key for address: [{address}]"))
+            })?,
+        ))
+    }
+
+    fn add_key(&mut self, alias: Option<String>, keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        let address: SuiAddress = (&keypair.public()).into();
+        let alias = self.create_alias(alias)?;
+        keyring::set_secret(
+            &self.service,
+            &address.to_string(),
+            &keypair.encode_base64(),
+        )?;
+        self.aliases.insert(
+            address,
+            Alias {
+                alias,
+                public_key_base64: EncodeDecodeBase64::encode_base64(&keypair.public()),
+            },
+        );
+        self.keys.insert(address, keypair);
+        self.save()?;
+        Ok(())
+    }
+
+    fn aliases(&self) -> Vec<&Alias> {
+        self.aliases.values().collect()
+    }
+
+    fn addresses_with_alias(&self) -> Vec<(&SuiAddress, &Alias)> {
+        self.aliases.iter().collect::<Vec<_>>()
+    }
+
+    fn aliases_mut(&mut self) -> Vec<&mut Alias> {
+        self.aliases.values_mut().collect()
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.keys.values().map(|key| key.public()).collect()
+    }
+
+    fn create_alias(&self, alias: Option<String>) -> Result<String, anyhow::Error> {
+        match alias {
+            Some(a) if self.alias_exists(&a) => {
+                bail!("Alias {a} already exists. Please choose another alias.")
+            }
+            Some(a) => validate_alias(&a),
+            None => Ok(random_name(
+                &self
+                    .alias_names()
+                    .into_iter()
+                    .map(|x| x.to_string())
+                    .collect::<HashSet<_>>(),
+            )),
+        }
+    }
+
+    fn get_address_by_alias(&self, alias: String) -> Result<&SuiAddress, anyhow::Error> {
+        self.addresses_with_alias()
+            .iter()
+            .find(|x| x.1.alias == alias)
+            .ok_or_else(|| anyhow!("Cannot resolve alias {alias} to an address"))
+            .map(|x| x.0)
+    }
+
+    fn get_alias_by_address(&self, address: &SuiAddress) -> Result<String, anyhow::Error> {
+        match self.aliases.get(address) {
+            Some(alias) => Ok(alias.alias.clone()),
+            None => bail!("Cannot find alias for address {address}"),
+        }
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        match self.keys.get(address) {
+            Some(key) => Ok(key),
+            None => Err(anyhow!("Cannot find key for address: [{address}]")),
+        }
+    }
+
+    fn update_alias(
+        &mut self,
+        old_alias: &str,
+        new_alias: Option<&str>,
+    ) -> Result<String, anyhow::Error> {
+        let new_alias_name = self.update_alias_value(old_alias, new_alias)?;
+        self.save()?;
+        Ok(new_alias_name)
+    }
+}
+
+impl KeyringKeystore {
+    /// The keystore of the keys of the keyring service `service` listed in the file at `path`,
+    /// which is created on the first key added. Reads the keys from the keyring, which may ask
+    /// the user to unlock it.
+    pub fn new(service: String, path: &Path) -> Result<Self, anyhow::Error> {
+        let aliases = if path.exists() {
+            let reader = BufReader::new(File::open(path).with_context(|| {
+                format!("Cannot open the keyring index file: {}", path.display())
+            })?);
+            let aliases: Vec<Alias> = serde_json::from_reader(reader).with_context(|| {
+                format!(
+                    "Cannot deserialize the keyring index file: {}",
+                    path.display()
+                )
+            })?;
+            aliases
+                .into_iter()
+                .map(|alias| {
+                    let key = PublicKey::decode_base64(&alias.public_key_base64);
+                    key.map(|k| (SuiAddress::from(&k), alias))
+                })
+                .collect::<Result<BTreeMap<_, _>, _>>()
+                .map_err(|e| anyhow!("Invalid keyring index file: {}. {}", path.display(), e))?
+        } else {
+            BTreeMap::new()
+        };
+
+        let keys = aliases
+            .keys()
+            .map(|address| {
+                let secret = keyring::get_secret(&service, &address.to_string())?
+                    .ok_or_else(|| anyhow!("Cannot find the key of [{address}] in the keyring"))?;
+                let key = SuiKeyPair::decode_base64(&secret)
+                    .map_err(|e| anyhow!("Invalid key of [{address}] in the keyring: {e}"))?;
+                ensure!(
+                    SuiAddress::from(&key.public()) == *address,
+                    "The key of [{address}] in the keyring is of another address"
+                );
+                Ok((*address, key))
+            })
+            .collect::<Result<BTreeMap<_, _>, anyhow::Error>>()?;
+
+        Ok(Self {
+            service,
+            path: path.to_path_buf(),
+            keys,
+            aliases,
+        })
+    }
+
+    /// The keyring service holding the keys, where other secrets of the client, such as the user
+    /// shares of dWallets, are kept too.
+    pub fn service(&self) -> &str {
+        &self.service
+    }
+
+    /// Write the addresses and aliases of the keys, the keys themselves being in the keyring.
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        let index = serde_json::to_string_pretty(&self.aliases.values().collect::<Vec<_>>())
+            .with_context(|| {
+                format!(
+                    "Cannot serialize the keyring index to file: {}",
+                    self.path.display()
+                )
+            })?;
+        fs::write(&self.path, index)?;
+        Ok(())
+    }
+}
+
 fn validate_alias(alias: &str) -> Result<String, anyhow::Error> {
     let re = Regex::new(r"^[A-Za-z][A-Za-z0-9-_]*$")
         .map_err(|_| anyhow!("Cannot build the regex needed to validate the alias naming"))?;
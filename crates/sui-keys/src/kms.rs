@@ -0,0 +1,408 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! [ExternalSigner]s backed by the key management services of cloud providers, for the keys of
+//! the owners of dWallets to be held in their HSMs.
+//!
+//! [AwsKmsSigner] signs with the secp256k1 keys of AWS KMS, and [GcpKmsSigner] with the secp256k1
+//! and ed25519 keys of Google Cloud KMS, the schemes of Sui these services support. Both discover
+//! the signing keys of an account, or of a key ring, and derive their addresses from their public
+//! keys.
+
+use std::collections::BTreeMap;
+use std::process::Command;
+
+use anyhow::{anyhow, bail, Context};
+use async_trait::async_trait;
+use aws_sdk_kms::primitives::Blob;
+use aws_sdk_kms::types::{KeySpec, KeyState, KeyUsageType, MessageType, SigningAlgorithmSpec};
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::hash::{HashFunction, Sha256};
+use serde::Deserialize;
+use serde_json::json;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{PublicKey, Signature, SignatureScheme, ToFromBytes};
+
+use crate::external_signer::ExternalSigner;
+
+const GCP_KMS_URL: &str = "https://cloudkms.googleapis.com/v1";
+const GCP_SECP256K1_ALGORITHM: &str = "EC_SIGN_SECP256K1_SHA256";
+const GCP_ED25519_ALGORITHM: &str = "EC_SIGN_ED25519";
+
+// The DER encodings of the SubjectPublicKeyInfo of the keys of each scheme, up to the key.
+const ED25519_SPKI_PREFIX: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+const SECP256K1_SPKI_PREFIX: [u8; 23] = [
+    0x30, 0x56, 0x30, 0x10, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x05, 0x2b,
+    0x81, 0x04, 0x00, 0x0a, 0x03, 0x42, 0x00,
+];
+
+/// Signs with the secp256k1 keys of AWS KMS.
+pub struct AwsKmsSigner {
+    client: aws_sdk_kms::Client,
+    /// The id of the key of each address.
+    keys: BTreeMap<SuiAddress, (String, PublicKey)>,
+}
+
+impl AwsKmsSigner {
+    /// A signer with the keys `key_ids`, ids, ARNs or aliases of KMS keys.
+    pub async fn new(client: aws_sdk_kms::Client, key_ids: Vec<String>) -> anyhow::Result<Self> {
+        let mut keys = BTreeMap::new();
+        for key_id in key_ids {
+            let response = client
+                .get_public_key()
+                .key_id(&key_id)
+                .send()
+                .await
+                .with_context(|| format!("Cannot get the public key of AWS KMS key {key_id}"))?;
+            if response.key_spec() != Some(&KeySpec::EccSecgP256K1) {
+                bail!("AWS KMS key {key_id} is not a secp256k1 key");
+            }
+            let public_key = public_key_from_der(
+                response
+                    .public_key()
+                    .ok_or_else(|| anyhow!("No public key for AWS KMS key {key_id}"))?
+                    .as_ref(),
+            )?;
+            keys.insert(SuiAddress::from(&public_key), (key_id, public_key));
+        }
+        Ok(Self { client, keys })
+    }
+
+    /// A signer with all the enabled secp256k1 signing keys of the account of `client`.
+    pub async fn discover(client: aws_sdk_kms::Client) -> anyhow::Result<Self> {
+        let mut key_ids = vec![];
+        let mut marker = None;
+        loop {
+            let page = client
+                .list_keys()
+                .set_marker(marker)
+                .send()
+                .await
+                .context("Cannot list AWS KMS keys")?;
+            for key in page.keys().unwrap_or_default() {
+                let Some(key_id) = key.key_id() else {
+                    continue;
+                };
+                let description = client
+                    .describe_key()
+                    .key_id(key_id)
+                    .send()
+                    .await
+                    .with_context(|| format!("Cannot describe AWS KMS key {key_id}"))?;
+                if description.key_metadata().is_some_and(|metadata| {
+                    metadata.key_spec() == Some(&KeySpec::EccSecgP256K1)
+                        && metadata.key_usage() == Some(&KeyUsageType::SignVerify)
+                        && metadata.key_state() == Some(&KeyState::Enabled)
+                }) {
+                    key_ids.push(key_id.to_string());
+                }
+            }
+            if !page.truncated() {
+                break;
+            }
+            marker = page.next_marker().map(str::to_string);
+        }
+        Self::new(client, key_ids).await
+    }
+
+    /// The id of the key of each address of the signer.
+    pub fn key_ids(&self) -> impl Iterator<Item = (&SuiAddress, &str)> {
+        self.keys
+            .iter()
+            .map(|(address, (key_id, _))| (address, key_id.as_str()))
+    }
+}
+
+#[async_trait]
+impl ExternalSigner for AwsKmsSigner {
+    async fn public_keys(&self) -> anyhow::Result<Vec<PublicKey>> {
+        Ok(self.keys.values().map(|(_, key)| key.clone()).collect())
+    }
+
+    async fn sign_digest(
+        &self,
+        address: &SuiAddress,
+        digest: &[u8; 32],
+    ) -> anyhow::Result<Signature> {
+        let (key_id, public_key) = self
+            .keys
+            .get(address)
+            .ok_or_else(|| anyhow!("No AWS KMS key for address [{address}]"))?;
+        let response = self
+            .client
+            .sign()
+            .key_id(key_id)
+            .message(Blob::new(Sha256::digest(digest).digest.to_vec()))
+            .message_type(MessageType::Digest)
+            .signing_algorithm(SigningAlgorithmSpec::EcdsaSha256)
+            .send()
+            .await
+            .with_context(|| format!("Cannot sign with AWS KMS key {key_id}"))?;
+        let signature = response
+            .signature()
+            .ok_or_else(|| anyhow!("No signature from AWS KMS key {key_id}"))?;
+        signature_from_kms(public_key, signature.as_ref())
+    }
+}
+
+/// How [GcpKmsSigner] authenticates to Google Cloud.
+pub enum GcpCredentials {
+    /// An OAuth 2.0 access token, valid for an hour.
+    AccessToken(String),
+    /// The credentials of the gcloud CLI, through `gcloud auth print-access-token`.
+    Gcloud,
+}
+
+impl GcpCredentials {
+    fn access_token(&self) -> anyhow::Result<String> {
+        match self {
+            Self::AccessToken(token) => Ok(token.clone()),
+            Self::Gcloud => {
+                let output = Command::new("gcloud")
+                    .args(["auth", "print-access-token"])
+                    .output()
+                    .context("Cannot run gcloud")?;
+                if !output.status.success() {
+                    bail!(
+                        "Cannot get a Google Cloud access token from gcloud: {}",
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    );
+                }
+                Ok(String::from_utf8(output.stdout)?.trim().to_string())
+            }
+        }
+    }
+}
+
+/// Signs with the secp256k1 and ed25519 keys of Google Cloud KMS.
+pub struct GcpKmsSigner {
+    client: reqwest::Client,
+    credentials: GcpCredentials,
+    /// The name of the key version of each address.
+    keys: BTreeMap<SuiAddress, (String, PublicKey)>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GcpCryptoKeys {
+    #[serde(default)]
+    crypto_keys: Vec<GcpName>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GcpCryptoKeyVersions {
+    #[serde(default)]
+    crypto_key_versions: Vec<GcpCryptoKeyVersion>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GcpName {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct GcpCryptoKeyVersion {
+    name: String,
+    algorithm: String,
+}
+
+#[derive(Deserialize)]
+struct GcpPublicKey {
+    pem: String,
+}
+
+#[derive(Deserialize)]
+struct GcpSignature {
+    signature: String,
+}
+
+impl GcpKmsSigner {
+    /// A signer with the key versions `key_versions`, named
+    /// `projects/*/locations/*/keyRings/*/cryptoKeys/*/cryptoKeyVersions/*`.
+    pub async fn new(
+        credentials: GcpCredentials,
+        key_versions: Vec<String>,
+    ) -> anyhow::Result<Self> {
+        let mut signer = Self {
+            client: reqwest::Client::new(),
+            credentials,
+            keys: BTreeMap::new(),
+        };
+        for key_version in key_versions {
+            let response: GcpPublicKey = signer
+                .get(&format!("{GCP_KMS_URL}/{key_version}/publicKey"))
+                .await
+                .with_context(|| {
+                    format!("Cannot get the public key of Google Cloud KMS key {key_version}")
+                })?;
+            let public_key = public_key_from_der(&pem_to_der(&response.pem)?)?;
+            signer
+                .keys
+                .insert(SuiAddress::from(&public_key), (key_version, public_key));
+        }
+        Ok(signer)
+    }
+
+    /// A signer with all the enabled secp256k1 and ed25519 signing key versions of the key ring
+    /// `key_ring`, named `projects/*/locations/*/keyRings/*`.
+    pub async fn discover(credentials: GcpCredentials, key_ring: &str) -> anyhow::Result<Self> {
+        let signer = Self::new(credentials, vec![]).await?;
+        let mut key_versions = vec![];
+        let mut page_token = None;
+        loop {
+            let page: GcpCryptoKeys = signer
+                .get(&page_url(
+                    &format!(
+                        "{GCP_KMS_URL}/{key_ring}/cryptoKeys?filter=purpose%3DASYMMETRIC_SIGN"
+                    ),
+                    &page_token,
+                ))
+                .await
+                .with_context(|| format!("Cannot list the keys of key ring {key_ring}"))?;
+            for key in page.crypto_keys {
+                key_versions.extend(signer.enabled_key_versions(&key.name).await?);
+            }
+            page_token = page.next_page_token;
+            if page_token.is_none() {
+                break;
+            }
+        }
+        Self::new(signer.credentials, key_versions).await
+    }
+
+    /// The enabled key versions of the key `key` with a scheme of Sui.
+    async fn enabled_key_versions(&self, key: &str) -> anyhow::Result<Vec<String>> {
+        let mut key_versions = vec![];
+        let mut page_token = None;
+        loop {
+            let page: GcpCryptoKeyVersions = self
+                .get(&page_url(
+                    &format!("{GCP_KMS_URL}/{key}/cryptoKeyVersions?filter=state%3DENABLED"),
+                    &page_token,
+                ))
+                .await
+                .with_context(|| format!("Cannot list the versions of key {key}"))?;
+            key_versions.extend(
+                page.crypto_key_versions
+                    .into_iter()
+                    .filter(|version| {
+                        version.algorithm == GCP_SECP256K1_ALGORITHM
+                            || version.algorithm == GCP_ED25519_ALGORITHM
+                    })
+                    .map(|version| version.name),
+            );
+            page_token = page.next_page_token;
+            if page_token.is_none() {
+                return Ok(key_versions);
+            }
+        }
+    }
+
+    /// The name of the key version of each address of the signer.
+    pub fn key_versions(&self) -> impl Iterator<Item = (&SuiAddress, &str)> {
+        self.keys
+            .iter()
+            .map(|(address, (key_version, _))| (address, key_version.as_str()))
+    }
+
+    async fn get<T: for<'de> Deserialize<'de>>(&self, url: &str) -> anyhow::Result<T> {
+        Ok(self
+            .client
+            .get(url)
+            .bearer_auth(self.credentials.access_token()?)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+}
+
+#[async_trait]
+impl ExternalSigner for GcpKmsSigner {
+    async fn public_keys(&self) -> anyhow::Result<Vec<PublicKey>> {
+        Ok(self.keys.values().map(|(_, key)| key.clone()).collect())
+    }
+
+    async fn sign_digest(
+        &self,
+        address: &SuiAddress,
+        digest: &[u8; 32],
+    ) -> anyhow::Result<Signature> {
+        let (key_version, public_key) = self
+            .keys
+            .get(address)
+            .ok_or_else(|| anyhow!("No Google Cloud KMS key for address [{address}]"))?;
+        // Ed25519 signs the digest itself, and secp256k1 its SHA-256 hash.
+        let request = match public_key.scheme() {
+            SignatureScheme::ED25519 => json!({ "data": Base64::encode(digest) }),
+            _ => json!({ "digest": { "sha256": Base64::encode(Sha256::digest(digest).digest) } }),
+        };
+        let response: GcpSignature = self
+            .client
+            .post(format!("{GCP_KMS_URL}/{key_version}:asymmetricSign"))
+            .bearer_auth(self.credentials.access_token()?)
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .with_context(|| format!("Cannot sign with Google Cloud KMS key {key_version}"))?;
+        let signature = Base64::decode(&response.signature)
+            .map_err(|e| anyhow!("Invalid signature from Google Cloud KMS: {e}"))?;
+        signature_from_kms(public_key, &signature)
+    }
+}
+
+fn page_url(url: &str, page_token: &Option<String>) -> String {
+    match page_token {
+        Some(page_token) => format!("{url}&pageToken={page_token}"),
+        None => url.to_string(),
+    }
+}
+
+fn pem_to_der(pem: &str) -> anyhow::Result<Vec<u8>> {
+    let body: String = pem
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect();
+    Base64::decode(&body).map_err(|e| anyhow!("Invalid PEM public key: {e}"))
+}
+
+/// The public key of a DER encoded SubjectPublicKeyInfo, as KMS services return them.
+pub fn public_key_from_der(der: &[u8]) -> anyhow::Result<PublicKey> {
+    if let Some(key) = der.strip_prefix(&ED25519_SPKI_PREFIX[..]) {
+        return PublicKey::try_from_bytes(SignatureScheme::ED25519, key)
+            .map_err(|e| anyhow!("Invalid ed25519 public key: {e}"));
+    }
+    if let Some(key) = der.strip_prefix(&SECP256K1_SPKI_PREFIX[..]) {
+        let key = secp256k1::PublicKey::from_slice(key)
+            .map_err(|e| anyhow!("Invalid secp256k1 public key: {e}"))?;
+        return PublicKey::try_from_bytes(SignatureScheme::Secp256k1, &key.serialize())
+            .map_err(|e| anyhow!("Invalid secp256k1 public key: {e}"));
+    }
+    bail!("Unsupported public key, only ed25519 and secp256k1 keys are supported")
+}
+
+/// The Sui signature of `public_key` from the signature of a KMS service, raw for ed25519 and
+/// DER encoded for secp256k1, normalized to a low S as Sui requires.
+pub fn signature_from_kms(public_key: &PublicKey, signature: &[u8]) -> anyhow::Result<Signature> {
+    let signature = match public_key.scheme() {
+        SignatureScheme::ED25519 => signature.to_vec(),
+        SignatureScheme::Secp256k1 => {
+            let mut signature = secp256k1::ecdsa::Signature::from_der(signature)
+                .map_err(|e| anyhow!("Invalid secp256k1 signature: {e}"))?;
+            signature.normalize_s();
+            signature.serialize_compact().to_vec()
+        }
+        scheme => bail!("Unsupported signature scheme {scheme:?}"),
+    };
+    Signature::from_bytes(&[&[public_key.flag()][..], &signature, public_key.as_ref()].concat())
+        .map_err(|e| anyhow!("Invalid signature from the KMS: {e}"))
+}
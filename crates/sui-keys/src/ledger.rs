@@ -0,0 +1,208 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Signing with the Sui app of a Ledger hardware wallet, whose keys never leave the device.
+//!
+//! The app exchanges the payloads of a command with the host in linked blocks: the host starts
+//! the command with the hash of the first block of each payload, and the device requests the
+//! blocks by hash as it parses them, see [send_chunks]. Transactions are shown on the device, and
+//! only signed once the user approves them.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, ensure};
+use bip32::DerivationPath;
+use fastcrypto::ed25519::{Ed25519PublicKey, Ed25519Signature};
+use fastcrypto::hash::{HashFunction, Sha256};
+use fastcrypto::traits::ToFromBytes;
+use ledger_apdu::{APDUCommand, APDUErrorCode};
+use ledger_transport_hid::hidapi::HidApi;
+use ledger_transport_hid::TransportNativeHID;
+use serde::Serialize;
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_types::crypto::{Signature, SignatureScheme};
+
+pub const LEDGER_SUI_CLA: u8 = 0x00;
+const INS_GET_PUBLIC_KEY: u8 = 0x02;
+const INS_SIGN_TRANSACTION: u8 = 0x03;
+
+/// The largest payload chunk sent in one block, for blocks to fit in an APDU.
+const CHUNK_SIZE: usize = 180;
+const HASH_LENGTH: usize = 32;
+
+// The instructions of the host, as the first byte of the data of an APDU.
+const HOST_START: u8 = 0x00;
+const HOST_GET_CHUNK_RESPONSE_SUCCESS: u8 = 0x01;
+const HOST_GET_CHUNK_RESPONSE_FAILURE: u8 = 0x02;
+const HOST_PUT_CHUNK_RESPONSE: u8 = 0x03;
+const HOST_RESULT_ACCUMULATING_RESPONSE: u8 = 0x04;
+
+// The instructions of the device, as the first byte of the data of its answers.
+const DEVICE_RESULT_ACCUMULATING: u8 = 0x00;
+const DEVICE_RESULT_FINAL: u8 = 0x01;
+const DEVICE_GET_CHUNK: u8 = 0x02;
+const DEVICE_PUT_CHUNK: u8 = 0x03;
+
+/// A connection to the Sui app of a Ledger device.
+pub trait LedgerTransport: Send + Sync {
+    /// Send the APDU of instruction `ins` with `data` to the Sui app, and return the data of its
+    /// answer, without the status word.
+    fn exchange(&self, ins: u8, p1: u8, p2: u8, data: &[u8]) -> anyhow::Result<Vec<u8>>;
+}
+
+/// A Ledger device connected over USB.
+pub struct HidLedgerTransport(TransportNativeHID);
+
+impl HidLedgerTransport {
+    /// Connect to the first Ledger device found, which must be unlocked with the Sui app open.
+    pub fn connect() -> anyhow::Result<Self> {
+        let hid = HidApi::new().map_err(|e| anyhow!("Cannot access USB devices: {e}"))?;
+        let transport = TransportNativeHID::new(&hid)
+            .map_err(|e| anyhow!("Cannot connect to a Ledger device: {e}"))?;
+        Ok(Self(transport))
+    }
+}
+
+impl LedgerTransport for HidLedgerTransport {
+    fn exchange(&self, ins: u8, p1: u8, p2: u8, data: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let answer = self
+            .0
+            .exchange(&APDUCommand {
+                cla: LEDGER_SUI_CLA,
+                ins,
+                p1,
+                p2,
+                data,
+            })
+            .map_err(|e| anyhow!("Cannot exchange with the Ledger device: {e}"))?;
+        match answer.error_code() {
+            Ok(APDUErrorCode::NoError) => Ok(answer.data().to_vec()),
+            Ok(code) => bail!("The Ledger device refused the command: {code:?}"),
+            Err(code) => bail!("The Ledger device refused the command: 0x{code:04x}"),
+        }
+    }
+}
+
+/// The Ed25519 public key of the account of `derivation_path` on the device.
+pub fn get_public_key(
+    transport: &dyn LedgerTransport,
+    derivation_path: &DerivationPath,
+) -> anyhow::Result<Ed25519PublicKey> {
+    let response = send_chunks(
+        transport,
+        INS_GET_PUBLIC_KEY,
+        &[derivation_path_payload(derivation_path)],
+    )?;
+    // `length || public key || length || address`.
+    let public_key = response
+        .first()
+        .and_then(|length| response.get(1..1 + *length as usize))
+        .ok_or_else(|| anyhow!("Invalid public key from the Ledger device"))?;
+    Ed25519PublicKey::from_bytes(public_key)
+        .map_err(|e| anyhow!("Invalid public key from the Ledger device: {e}"))
+}
+
+/// Sign `msg` with `intent` with the account of `derivation_path`, once approved on the device.
+pub fn sign_secure<T: Serialize>(
+    transport: &dyn LedgerTransport,
+    derivation_path: &DerivationPath,
+    msg: &T,
+    intent: Intent,
+) -> anyhow::Result<Signature> {
+    let public_key = get_public_key(transport, derivation_path)?;
+    let intent_message = bcs::to_bytes(&IntentMessage::new(intent, msg))?;
+    let transaction_payload = [
+        &(intent_message.len() as u32).to_le_bytes()[..],
+        &intent_message,
+    ]
+    .concat();
+    let signature = send_chunks(
+        transport,
+        INS_SIGN_TRANSACTION,
+        &[
+            transaction_payload,
+            derivation_path_payload(derivation_path),
+        ],
+    )?;
+    let signature = Ed25519Signature::from_bytes(&signature)
+        .map_err(|e| anyhow!("Invalid signature from the Ledger device: {e}"))?;
+    Signature::from_bytes(
+        &[
+            &[SignatureScheme::ED25519.flag()][..],
+            signature.as_ref(),
+            public_key.as_ref(),
+        ]
+        .concat(),
+    )
+    .map_err(|e| anyhow!("Invalid signature from the Ledger device: {e}"))
+}
+
+/// Run the command `ins` with `payloads`, serving the blocks of the payloads the device requests,
+/// and return the result of the device.
+pub fn send_chunks(
+    transport: &dyn LedgerTransport,
+    ins: u8,
+    payloads: &[Vec<u8>],
+) -> anyhow::Result<Vec<u8>> {
+    let mut blocks = HashMap::new();
+    let mut first_hashes = Vec::with_capacity(payloads.len() * HASH_LENGTH);
+    for payload in payloads {
+        // Each block is `hash of the next block || chunk`, the last one pointing to zeros.
+        let mut next_hash = [0; HASH_LENGTH];
+        for chunk in payload.chunks(CHUNK_SIZE).rev() {
+            let block = [&next_hash[..], chunk].concat();
+            next_hash = Sha256::digest(&block).digest;
+            blocks.insert(next_hash, block);
+        }
+        if payload.is_empty() {
+            let block = next_hash.to_vec();
+            next_hash = Sha256::digest(&block).digest;
+            blocks.insert(next_hash, block);
+        }
+        first_hashes.extend_from_slice(&next_hash);
+    }
+
+    let mut result = vec![];
+    let mut request = [&[HOST_START][..], &first_hashes].concat();
+    loop {
+        let answer = transport.exchange(ins, 0, 0, &request)?;
+        let (instruction, payload) = answer
+            .split_first()
+            .ok_or_else(|| anyhow!("Empty answer from the Ledger device"))?;
+        request = match *instruction {
+            DEVICE_RESULT_ACCUMULATING => {
+                result.extend_from_slice(payload);
+                vec![HOST_RESULT_ACCUMULATING_RESPONSE]
+            }
+            DEVICE_RESULT_FINAL => {
+                result.extend_from_slice(payload);
+                return Ok(result);
+            }
+            DEVICE_GET_CHUNK => {
+                ensure!(
+                    payload.len() == HASH_LENGTH,
+                    "Invalid block request from the Ledger device"
+                );
+                match blocks.get(payload) {
+                    Some(block) => [&[HOST_GET_CHUNK_RESPONSE_SUCCESS][..], block].concat(),
+                    None => vec![HOST_GET_CHUNK_RESPONSE_FAILURE],
+                }
+            }
+            DEVICE_PUT_CHUNK => {
+                blocks.insert(Sha256::digest(payload).digest, payload.to_vec());
+                vec![HOST_PUT_CHUNK_RESPONSE]
+            }
+            other => bail!("Unknown instruction {other} from the Ledger device"),
+        };
+    }
+}
+
+/// `number of levels || level as u32 little endian...`
+fn derivation_path_payload(derivation_path: &DerivationPath) -> Vec<u8> {
+    let levels: Vec<u32> = derivation_path.iter().map(u32::from).collect();
+    let mut payload = vec![levels.len() as u8];
+    for level in levels {
+        payload.extend_from_slice(&level.to_le_bytes());
+    }
+    payload
+}
@@ -1,7 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
+pub mod encryption;
+pub mod external_signer;
 pub mod key_derive;
 pub mod keypair_file;
+pub mod keyring;
 pub mod keystore;
+pub mod kms;
+pub mod ledger;
 pub mod random_names;
+pub mod shamir;
@@ -0,0 +1,298 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Shamir secret sharing of keys and dWallet user shares, to back them up split between several
+//! custodians.
+//!
+//! A secret is split into `n` fragments, any `threshold` of which restore it, while fewer reveal
+//! nothing about it. Each byte of the secret is shared with its own random polynomial of degree
+//! `threshold - 1` over GF(256), whose constant term is the byte, evaluated at the index of each
+//! fragment. The secret is split followed by its checksum, so that only the restore reveals the
+//! checksum, which then detects a fragment that was tampered with.
+//!
+//! A fragment is encoded as `shamir:` followed by the Base64 encoding of
+//! `version || kind || threshold || index || split id || data || checksum`.
+//! The trailing checksum detects a corrupted fragment, and the random split id the fragments of
+//! other splits.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail};
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::hash::HashFunction;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::Serialize;
+use sui_types::crypto::DefaultHash;
+use zeroize::Zeroizing;
+
+/// The version of the encoding of fragments.
+pub const FRAGMENT_VERSION: u8 = 2;
+const FRAGMENT_PREFIX: &str = "shamir:";
+const SPLIT_ID_LENGTH: usize = 4;
+const CHECKSUM_LENGTH: usize = 4;
+/// The length of the fields of an encoded fragment before its data.
+const HEADER_LENGTH: usize = 4 + SPLIT_ID_LENGTH;
+
+/// What a split secret is, for it to be restored where it belongs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SecretKind {
+    /// A private key of the keystore, encoded as in the keystore.
+    Key,
+    /// A dWallet of the registry of the client config, with its user share.
+    DWalletShare,
+}
+
+impl SecretKind {
+    fn to_byte(self) -> u8 {
+        match self {
+            Self::Key => 0,
+            Self::DWalletShare => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self, anyhow::Error> {
+        match byte {
+            0 => Ok(Self::Key),
+            1 => Ok(Self::DWalletShare),
+            _ => Err(anyhow!("Unknown kind {byte} of Shamir fragment")),
+        }
+    }
+}
+
+/// One of the fragments of a split secret, see [split].
+#[derive(Clone, PartialEq, Eq)]
+pub struct Fragment {
+    pub kind: SecretKind,
+    pub threshold: u8,
+    /// The point the polynomials of the split are evaluated at, from 1.
+    pub index: u8,
+    /// Random, the same for all the fragments of a split.
+    pub split_id: [u8; SPLIT_ID_LENGTH],
+    /// The value of the polynomial of each byte of the secret and its checksum at `index`.
+    pub data: Zeroizing<Vec<u8>>,
+}
+
+impl Fragment {
+    /// Encode the fragment, see the [module](self) documentation.
+    pub fn encode(&self) -> Zeroizing<String> {
+        let mut bytes = Zeroizing::new(Vec::with_capacity(
+            HEADER_LENGTH + self.data.len() + CHECKSUM_LENGTH,
+        ));
+        bytes.extend([
+            FRAGMENT_VERSION,
+            self.kind.to_byte(),
+            self.threshold,
+            self.index,
+        ]);
+        bytes.extend(self.split_id);
+        bytes.extend(self.data.iter());
+        let checksum = checksum(&[&bytes[..]]);
+        bytes.extend(checksum);
+        Zeroizing::new(format!("{FRAGMENT_PREFIX}{}", Base64::encode(&*bytes)))
+    }
+
+    /// Whether the fragment belongs to the same split as `other`.
+    fn same_split(&self, other: &Fragment) -> bool {
+        self.kind == other.kind
+            && self.threshold == other.threshold
+            && self.split_id == other.split_id
+            && self.data.len() == other.data.len()
+    }
+}
+
+impl FromStr for Fragment {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let encoded = s
+            .trim()
+            .strip_prefix(FRAGMENT_PREFIX)
+            .ok_or_else(|| anyhow!("Not a Shamir fragment, which starts with {FRAGMENT_PREFIX}"))?;
+        let bytes = Zeroizing::new(
+            Base64::decode(encoded).map_err(|e| anyhow!("Invalid Shamir fragment: {e}"))?,
+        );
+        // The data holds at least a byte of the secret and its checksum.
+        if bytes.len() <= HEADER_LENGTH + 2 * CHECKSUM_LENGTH {
+            bail!("Invalid Shamir fragment: too short");
+        }
+        let (body, fragment_checksum) = bytes.split_at(bytes.len() - CHECKSUM_LENGTH);
+        if fragment_checksum != checksum(&[body]) {
+            bail!("Corrupted Shamir fragment: its checksum does not match");
+        }
+        let version = body[0];
+        if version != FRAGMENT_VERSION {
+            bail!("Unsupported version {version} of Shamir fragment, expected {FRAGMENT_VERSION}");
+        }
+        let fragment = Fragment {
+            kind: SecretKind::from_byte(body[1])?,
+            threshold: body[2],
+            index: body[3],
+            split_id: body[4..HEADER_LENGTH].try_into()?,
+            data: Zeroizing::new(body[HEADER_LENGTH..].to_vec()),
+        };
+        if fragment.index == 0 || fragment.threshold == 0 {
+            bail!("Invalid Shamir fragment: zero index or threshold");
+        }
+        Ok(fragment)
+    }
+}
+
+impl Display for Fragment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", *self.encode())
+    }
+}
+
+/// Split `secret` of `kind` into `shares` fragments, any `threshold` of which restore it with
+/// [restore].
+pub fn split(
+    kind: SecretKind,
+    secret: &[u8],
+    threshold: u8,
+    shares: u8,
+) -> Result<Vec<Fragment>, anyhow::Error> {
+    if secret.is_empty() {
+        bail!("Cannot split an empty secret");
+    }
+    if threshold < 2 || threshold > shares {
+        bail!(
+            "The threshold must be at least 2 and at most the {shares} fragments, not {threshold}"
+        );
+    }
+    let mut split_id = [0; SPLIT_ID_LENGTH];
+    OsRng.fill_bytes(&mut split_id);
+    let mut checked_secret = Zeroizing::new(Vec::with_capacity(secret.len() + CHECKSUM_LENGTH));
+    checked_secret.extend(secret);
+    checked_secret.extend(checksum(&[&split_id[..], secret]));
+
+    let mut fragments: Vec<_> = (1..=shares)
+        .map(|index| Fragment {
+            kind,
+            threshold,
+            index,
+            split_id,
+            data: Zeroizing::new(Vec::with_capacity(checked_secret.len())),
+        })
+        .collect();
+    // The coefficients of the polynomial of each byte, from the constant term.
+    let mut coefficients = Zeroizing::new(vec![0; threshold as usize]);
+    for byte in checked_secret.iter() {
+        coefficients[0] = *byte;
+        OsRng.fill_bytes(&mut coefficients[1..]);
+        for fragment in &mut fragments {
+            let value = coefficients.iter().rev().fold(0, |value, coefficient| {
+                gf_mul(value, fragment.index) ^ coefficient
+            });
+            fragment.data.push(value);
+        }
+    }
+    Ok(fragments)
+}
+
+/// Restore the secret split into `fragments`, with its kind, from at least the threshold of them.
+pub fn restore(fragments: &[Fragment]) -> Result<(SecretKind, Zeroizing<Vec<u8>>), anyhow::Error> {
+    let first = fragments
+        .first()
+        .ok_or_else(|| anyhow!("No Shamir fragments to restore"))?;
+    let mut by_index = BTreeMap::new();
+    for fragment in fragments {
+        if !fragment.same_split(first) {
+            bail!("The Shamir fragments are of different splits");
+        }
+        match by_index.insert(fragment.index, fragment) {
+            Some(other) if other != fragment => {
+                bail!(
+                    "Two different Shamir fragments have the index {}",
+                    fragment.index
+                )
+            }
+            _ => {}
+        }
+    }
+    let threshold = first.threshold as usize;
+    if by_index.len() < threshold {
+        bail!(
+            "{} distinct Shamir fragments of the {threshold} needed to restore the secret",
+            by_index.len()
+        );
+    }
+
+    let fragments: Vec<_> = by_index.into_values().take(threshold).collect();
+    // The Lagrange coefficients of the fragments at 0, where subtraction is addition (xor).
+    let weights: Vec<_> = fragments
+        .iter()
+        .map(|fragment| {
+            fragments
+                .iter()
+                .filter(|other| other.index != fragment.index)
+                .fold(1, |weight, other| {
+                    gf_mul(weight, gf_div(other.index, other.index ^ fragment.index))
+                })
+        })
+        .collect();
+    let mut secret = Zeroizing::new(
+        (0..first.data.len())
+            .map(|i| {
+                fragments
+                    .iter()
+                    .zip(&weights)
+                    .fold(0, |byte, (fragment, weight)| {
+                        byte ^ gf_mul(fragment.data[i], *weight)
+                    })
+            })
+            .collect::<Vec<_>>(),
+    );
+    let secret_length = secret
+        .len()
+        .checked_sub(CHECKSUM_LENGTH)
+        .ok_or_else(|| anyhow!("Invalid Shamir fragments: too short"))?;
+    if checksum(&[&first.split_id[..], &secret[..secret_length]]) != secret[secret_length..] {
+        bail!("The restored secret does not match its checksum, a Shamir fragment is wrong");
+    }
+    secret.truncate(secret_length);
+    Ok((first.kind, secret))
+}
+
+/// The first bytes of the hash of the concatenation of `parts`.
+fn checksum(parts: &[&[u8]]) -> [u8; CHECKSUM_LENGTH] {
+    let mut hasher = DefaultHash::default();
+    for part in parts {
+        hasher.update(part);
+    }
+    let digest = hasher.finalize().digest;
+    digest[..CHECKSUM_LENGTH]
+        .try_into()
+        .expect("digests are longer than checksums")
+}
+
+/// Multiplication in GF(256), modulo the AES polynomial x^8 + x^4 + x^3 + x + 1, without branching
+/// on the secret operands.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    product
+}
+
+/// Division in GF(256) by a non-zero `b`, whose inverse is `b^254`.
+fn gf_div(a: u8, b: u8) -> u8 {
+    let mut inverse = 1;
+    let mut power = b;
+    let mut exponent = 254u8;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            inverse = gf_mul(inverse, power);
+        }
+        power = gf_mul(power, power);
+        exponent >>= 1;
+    }
+    gf_mul(a, inverse)
+}
@@ -266,3 +266,407 @@ fn get_alias_by_address_test() {
     let address = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
     assert!(keystore.get_alias_by_address(&address.0).is_err())
 }
+
+/// A Ledger device with the Sui app, holding a single ed25519 key for any derivation path.
+struct MockLedger {
+    keypair: sui_types::crypto::SuiKeyPair,
+    /// The payloads received so far, and the hash of the next block of the last one.
+    state: std::sync::Mutex<(Vec<Vec<u8>>, Vec<[u8; 32]>)>,
+}
+
+impl sui_keys::ledger::LedgerTransport for MockLedger {
+    fn exchange(&self, ins: u8, _p1: u8, _p2: u8, data: &[u8]) -> anyhow::Result<Vec<u8>> {
+        use signature::Signer;
+        use sui_types::crypto::SuiSignature;
+
+        let mut state = self.state.lock().unwrap();
+        let (payloads, pending) = &mut *state;
+        match data[0] {
+            // Start: request the first block of each payload in turn.
+            0x00 => {
+                payloads.clear();
+                *pending = data[1..]
+                    .chunks(32)
+                    .map(|hash| hash.try_into().unwrap())
+                    .collect();
+                pending.reverse();
+                payloads.push(vec![]);
+            }
+            // A block: `hash of the next block || chunk`.
+            0x01 => {
+                let block = &data[1..];
+                let expected = pending.pop().unwrap();
+                assert_eq!(
+                    expected,
+                    fastcrypto::hash::Sha256::digest(block).digest,
+                    "block of the wrong hash"
+                );
+                payloads.last_mut().unwrap().extend_from_slice(&block[32..]);
+                let next_hash: [u8; 32] = block[..32].try_into().unwrap();
+                if next_hash != [0; 32] {
+                    pending.push(next_hash);
+                } else if !pending.is_empty() {
+                    payloads.push(vec![]);
+                }
+            }
+            other => panic!("unexpected host instruction {other}"),
+        }
+        if let Some(hash) = pending.last() {
+            return Ok([&[0x02][..], hash].concat());
+        }
+
+        let public_key = self.keypair.public();
+        let result = match ins {
+            0x02 => [
+                &[32][..],
+                public_key.as_ref(),
+                &[32][..],
+                SuiAddress::from(&public_key).as_ref(),
+            ]
+            .concat(),
+            0x03 => {
+                // `length || intent message` of the transaction, then the derivation path.
+                let intent_message = &payloads[0][4..];
+                assert_eq!(
+                    u32::from_le_bytes(payloads[0][..4].try_into().unwrap()) as usize,
+                    intent_message.len()
+                );
+                let mut hasher = DefaultHash::default();
+                hasher.update(intent_message);
+                let signature: sui_types::crypto::Signature =
+                    self.keypair.sign(&hasher.finalize().digest);
+                signature.signature_bytes().to_vec()
+            }
+            other => panic!("unexpected instruction {other}"),
+        };
+        Ok([&[0x01][..], &result].concat())
+    }
+}
+
+#[test]
+fn ledger_sign_secure_test() {
+    use bip32::DerivationPath;
+    use rand::{rngs::StdRng, SeedableRng};
+    use shared_crypto::intent::{Intent, IntentMessage};
+    use sui_keys::ledger;
+    use sui_types::crypto::{get_key_pair_from_rng, Signature, SuiKeyPair};
+
+    let (_, keypair) = get_key_pair_from_rng(&mut StdRng::from_seed([0; 32]));
+    let device = MockLedger {
+        keypair: SuiKeyPair::Ed25519(keypair.copy()),
+        state: Default::default(),
+    };
+    let keypair = SuiKeyPair::Ed25519(keypair);
+    let derivation_path = DerivationPath::from_str("m/44'/784'/0'/0'/0'").unwrap();
+
+    let public_key = ledger::get_public_key(&device, &derivation_path).unwrap();
+    assert_eq!(public_key.as_ref(), keypair.public().as_ref());
+
+    // Long enough to be sent in several blocks.
+    let msg = vec![7u8; 1000];
+    let signature =
+        ledger::sign_secure(&device, &derivation_path, &msg, Intent::sui_transaction()).unwrap();
+    let expected = Signature::new_secure(
+        &IntentMessage::new(Intent::sui_transaction(), &msg),
+        &keypair,
+    );
+    assert_eq!(expected, signature);
+    assert!(matches!(signature, Signature::Ed25519SuiSignature(_)));
+}
+
+#[test]
+fn keyring_keystore_config_test() {
+    use sui_keys::keystore::KeyringKeystore;
+
+    let temp_dir = TempDir::new().unwrap();
+    let index_path = temp_dir.path().join("sui.keyring");
+    let config = format!(
+        "Keyring:\n  service: dwallet-network\n  path: {}\n",
+        index_path.display()
+    );
+
+    // An empty keystore reads nothing from the keyring.
+    let keystore: Keystore = serde_yaml::from_str(&config).unwrap();
+    assert!(keystore.keys().is_empty());
+    assert!(keystore.to_string().contains("dwallet-network"));
+    let serialized: Keystore =
+        serde_yaml::from_str(&serde_yaml::to_string(&keystore).unwrap()).unwrap();
+    assert_eq!(keystore.to_string(), serialized.to_string());
+
+    // Keys listed in the index must be in the keyring.
+    let (_, keypair, _, _) = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
+    fs::write(
+        &index_path,
+        format!(
+            r#"[{{"alias": "missing", "public_key_base64": "{}"}}]"#,
+            keypair.public().encode_base64()
+        ),
+    )
+    .unwrap();
+    assert!(KeyringKeystore::new("dwallet-network-tests".to_string(), &index_path).is_err());
+}
+
+#[test]
+fn encrypted_keystore_test() {
+    use sui_keys::encryption::{EncryptedKeystore, KeystoreCipher, KEYSTORE_PASSPHRASE_ENV};
+
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+    let private_key = keystore.key_pairs()[0].encode_base64();
+    keystore.encrypt("correct horse battery staple").unwrap();
+    assert!(keystore.is_encrypted());
+
+    let contents = fs::read_to_string(&keystore_path).unwrap();
+    assert!(!contents.contains(&private_key));
+    let encrypted: EncryptedKeystore = serde_json::from_str(&contents).unwrap();
+    assert!(KeystoreCipher::unlock(&encrypted, "wrong passphrase").is_err());
+
+    // Unlocked with the passphrase of the environment, and encrypted again on save.
+    std::env::set_var(KEYSTORE_PASSPHRASE_ENV, "correct horse battery staple");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    assert!(keystore.is_encrypted());
+    assert_eq!(private_key, keystore.key_pairs()[0].encode_base64());
+    keystore
+        .generate_and_add_new_key(SignatureScheme::Secp256k1, None, None, None)
+        .unwrap();
+    let keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    std::env::remove_var(KEYSTORE_PASSPHRASE_ENV);
+    assert_eq!(2, keystore.addresses().len());
+    assert!(!fs::read_to_string(&keystore_path)
+        .unwrap()
+        .contains(&private_key));
+}
+
+#[test]
+fn share_encryption_key_test() {
+    use bip32::DerivationPath;
+    use sui_keys::encryption::ShareEncryptionKey;
+
+    let phrase = "result crisp session latin must fruit genuine question prevent start coconut brave speak student dismiss";
+    let mut keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(0));
+    let address = keystore
+        .import_from_mnemonic(phrase, SignatureScheme::ED25519, None)
+        .unwrap();
+    let key = ShareEncryptionKey::derive(keystore.get_key(&address).unwrap());
+    let encrypted = key.encrypt(b"user share", b"dwallet").unwrap();
+
+    // Restored from the mnemonic alone.
+    let mut restored = Keystore::from(InMemKeystore::new_insecure_for_tests(0));
+    restored
+        .import_from_mnemonic(phrase, SignatureScheme::ED25519, None)
+        .unwrap();
+    let restored_key = ShareEncryptionKey::derive(restored.get_key(&address).unwrap());
+    assert_eq!(
+        b"user share".to_vec(),
+        *restored_key.decrypt(&encrypted, b"dwallet").unwrap()
+    );
+    assert!(restored_key.decrypt(&encrypted, b"other dwallet").is_err());
+
+    // Other accounts of the mnemonic have other keys.
+    let other_address = restored
+        .import_from_mnemonic(
+            phrase,
+            SignatureScheme::ED25519,
+            Some(DerivationPath::from_str("m/44'/784'/0'/0'/1'").unwrap()),
+        )
+        .unwrap();
+    let other_key = ShareEncryptionKey::derive(restored.get_key(&other_address).unwrap());
+    assert!(other_key.decrypt(&encrypted, b"dwallet").is_err());
+}
+
+#[test]
+fn share_transfer_key_test() {
+    use sui_keys::encryption::{encrypt_share_to, ShareTransferKey};
+
+    let (_, sender, _, _) = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
+    let (_, recipient, _, _) = generate_new_key(SignatureScheme::Secp256k1, None, None).unwrap();
+    let recipient_key = ShareTransferKey::derive(&recipient);
+    let encrypted =
+        encrypt_share_to(&recipient_key.public_key(), b"user share", b"dwallet").unwrap();
+
+    assert_eq!(
+        b"user share".to_vec(),
+        *recipient_key.decrypt(&encrypted, b"dwallet").unwrap()
+    );
+    assert!(recipient_key.decrypt(&encrypted, b"other dwallet").is_err());
+    assert!(ShareTransferKey::derive(&sender)
+        .decrypt(&encrypted, b"dwallet")
+        .is_err());
+    assert!(encrypt_share_to(b"not a public key", b"user share", b"dwallet").is_err());
+}
+
+#[test]
+fn share_passphrase_key_test() {
+    use sui_keys::encryption::{Kdf, ShareEncryptionKey};
+
+    let kdf = Kdf::argon2id();
+    let key = ShareEncryptionKey::from_passphrase("correct horse", &kdf).unwrap();
+    let encrypted = key.encrypt(b"user share", b"dwallet").unwrap();
+
+    let restored = ShareEncryptionKey::from_passphrase("correct horse", &kdf).unwrap();
+    assert_eq!(
+        b"user share".to_vec(),
+        *restored.decrypt(&encrypted, b"dwallet").unwrap()
+    );
+    assert!(ShareEncryptionKey::from_passphrase("wrong horse", &kdf)
+        .unwrap()
+        .decrypt(&encrypted, b"dwallet")
+        .is_err());
+    // A fresh salt derives another key from the same passphrase.
+    assert!(
+        ShareEncryptionKey::from_passphrase("correct horse", &Kdf::argon2id())
+            .unwrap()
+            .decrypt(&encrypted, b"dwallet")
+            .is_err()
+    );
+}
+
+#[test]
+fn kms_signature_test() {
+    use fastcrypto::encoding::{Encoding, Hex};
+    use fastcrypto::hash::Sha256;
+    use fastcrypto::traits::ToFromBytes;
+    use shared_crypto::intent::{Intent, IntentMessage, PersonalMessage};
+    use sui_keys::external_signer::intent_message_digest;
+    use sui_keys::kms::{public_key_from_der, signature_from_kms};
+    use sui_types::crypto::{SuiKeyPair, SuiSignature};
+
+    let (address, keypair, _, _) =
+        generate_new_key(SignatureScheme::Secp256k1, None, None).unwrap();
+    let SuiKeyPair::Secp256k1(secp256k1_keypair) = &keypair else {
+        panic!("generated a secp256k1 key");
+    };
+    let secret_key = secp256k1::SecretKey::from_slice(secp256k1_keypair.as_bytes()).unwrap();
+    let secp = secp256k1::Secp256k1::new();
+
+    // The SubjectPublicKeyInfo of the key, as KMS services return it.
+    let spki = [
+        Hex::decode("3056301006072a8648ce3d020106052b8104000a034200").unwrap(),
+        secret_key
+            .public_key(&secp)
+            .serialize_uncompressed()
+            .to_vec(),
+    ]
+    .concat();
+    let public_key = public_key_from_der(&spki).unwrap();
+    assert_eq!(public_key, keypair.public());
+    assert_eq!(SuiAddress::from(&public_key), address);
+
+    // KMS services sign the SHA-256 hash of the digest, and return DER signatures.
+    let message = PersonalMessage {
+        message: b"dWallet".to_vec(),
+    };
+    let intent_message = IntentMessage::new(Intent::personal_message(), message);
+    let digest = intent_message_digest(&intent_message).unwrap();
+    let hash = secp256k1::Message::from_slice(&Sha256::digest(digest).digest).unwrap();
+    let der = secp.sign_ecdsa(&hash, &secret_key).serialize_der();
+    let signature = signature_from_kms(&public_key, &der).unwrap();
+    signature
+        .verify_secure(&intent_message, address, signature.scheme())
+        .unwrap();
+
+    assert!(public_key_from_der(&spki[1..]).is_err());
+    assert!(signature_from_kms(&public_key, &der[1..]).is_err());
+}
+
+#[test]
+fn rotate_key_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    let (old_address, _, _) = keystore
+        .generate_and_add_new_key(
+            SignatureScheme::ED25519,
+            Some("owner".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+
+    let (new_address, _, scheme) = keystore
+        .rotate_key(&old_address, SignatureScheme::Secp256k1, None, None)
+        .unwrap();
+    assert_eq!(scheme, SignatureScheme::Secp256k1);
+    assert_ne!(old_address, new_address);
+    assert_eq!(
+        *keystore.get_address_by_alias("owner".to_string()).unwrap(),
+        new_address
+    );
+    assert_eq!(
+        keystore.get_alias_by_address(&old_address).unwrap(),
+        "owner-retired"
+    );
+
+    // The rotation is persisted, and the old key kept.
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    assert!(keystore.get_key(&old_address).is_ok());
+    assert_eq!(
+        *keystore.get_address_by_alias("owner".to_string()).unwrap(),
+        new_address
+    );
+
+    // The retired alias of the first key is kept when rotating again.
+    let mut keystore = keystore;
+    keystore
+        .rotate_key(&new_address, SignatureScheme::ED25519, None, None)
+        .unwrap();
+    assert_eq!(
+        keystore.get_alias_by_address(&new_address).unwrap(),
+        "owner-retired-2"
+    );
+}
+
+#[test]
+fn shamir_test() {
+    use sui_keys::shamir::{restore, split, Fragment, SecretKind};
+
+    let secret = b"a dWallet user share".to_vec();
+    let fragments = split(SecretKind::DWalletShare, &secret, 3, 5).unwrap();
+    assert_eq!(fragments.len(), 5);
+
+    // Any 3 of the encoded fragments restore the secret.
+    let decoded: Vec<Fragment> = fragments
+        .iter()
+        .map(|fragment| fragment.encode().parse().unwrap())
+        .collect();
+    for subset in [[0, 1, 2], [0, 2, 4], [4, 3, 1]] {
+        let subset: Vec<_> = subset.iter().map(|i| decoded[*i].clone()).collect();
+        let (kind, restored) = restore(&subset).unwrap();
+        assert_eq!(kind, SecretKind::DWalletShare);
+        assert_eq!(*restored, secret);
+    }
+    assert_eq!(*restore(&decoded).unwrap().1, secret);
+
+    // Fewer fragments, even repeated, do not.
+    assert!(restore(&decoded[..2]).is_err());
+    assert!(restore(&[decoded[0].clone(), decoded[1].clone(), decoded[1].clone()]).is_err());
+
+    // Nor fragments of another split of the same secret.
+    let other = split(SecretKind::DWalletShare, &secret, 3, 5).unwrap();
+    assert!(restore(&[decoded[0].clone(), decoded[1].clone(), other[2].clone()]).is_err());
+
+    // The checksum of the secret is split along with it, not kept in the clear.
+    assert_eq!(decoded[0].data.len(), secret.len() + 4);
+
+    // A tampered fragment is caught by the checksum of the secret, restored with it.
+    for i in [0, secret.len()] {
+        let mut tampered = decoded[2].clone();
+        tampered.data[i] ^= 1;
+        assert!(restore(&[decoded[0].clone(), decoded[1].clone(), tampered]).is_err());
+    }
+
+    // A corrupted encoding is caught by the checksum of the fragment.
+    let encoded = fragments[0].encode();
+    let mut corrupted = encoded.to_string();
+    corrupted.replace_range(10..11, if &encoded[10..11] == "A" { "B" } else { "A" });
+    assert!(corrupted.parse::<Fragment>().is_err());
+    assert!(encoded[7..].parse::<Fragment>().is_err());
+
+    assert!(split(SecretKind::Key, &secret, 1, 5).is_err());
+    assert!(split(SecretKind::Key, &secret, 6, 5).is_err());
+    assert!(split(SecretKind::Key, b"", 2, 3).is_err());
+}
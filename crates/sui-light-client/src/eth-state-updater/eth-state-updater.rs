@@ -0,0 +1,51 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use clap::Parser;
+use mysten_metrics::start_prometheus_server;
+use std::path::PathBuf;
+use std::time::Duration;
+use sui_config::Config;
+use sui_light_client::eth_state_updater::{EthStateUpdater, EthStateUpdaterConfig};
+use sui_sdk::wallet_context::WalletContext;
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+#[clap(name = env!("CARGO_BIN_NAME"))]
+struct Args {
+    #[clap(long)]
+    pub updater_config_path: PathBuf,
+    #[clap(long)]
+    pub client_config_path: PathBuf,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    let config = EthStateUpdaterConfig::load(&args.updater_config_path)?;
+
+    let wallet_ctx = WalletContext::new(
+        &args.client_config_path,
+        Some(Duration::from_secs(10)),
+        None,
+    )
+    .await?;
+
+    // Init metrics server
+    let registry_service = start_prometheus_server(config.metrics_address);
+    let prometheus_registry = registry_service.default_registry();
+
+    // Init logging
+    let (_guard, _filter_handle) = telemetry_subscribers::TelemetryConfig::new()
+        .with_env()
+        .with_prom_registry(&prometheus_registry)
+        .init();
+
+    EthStateUpdater::new(config, wallet_ctx, &prometheus_registry)
+        .await?
+        .run()
+        .await;
+
+    Ok(())
+}
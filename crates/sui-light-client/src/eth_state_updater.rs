@@ -0,0 +1,365 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! A relayer keeping the on-chain light client of an Ethereum network up to date.
+//!
+//! The updater reads the light client store from the state object of the network, fetches the
+//! light client updates it is missing from the consensus RPC, verifies them locally against the
+//! store and submits the valid ones to the state object, batched into as few transactions as the
+//! transaction size limit allows. Anyone can run it: the on-chain light client verifies every
+//! update itself, so the updater only needs gas.
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use prometheus::Registry;
+use serde::{Deserialize, Serialize};
+use sui_config::Config;
+use sui_json_rpc_types::{
+    SuiObjectDataOptions, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions,
+};
+use sui_sdk::beacon_client::{BeaconClient, MAX_REQUEST_LIGHT_CLIENT_UPDATES};
+use sui_sdk::eth_light_client::{batch_updates, get_light_client, is_stale_update, submit_updates};
+use sui_sdk::wallet_context::WalletContext;
+use sui_sdk::SuiClient;
+use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use sui_types::eth_light_client::{
+    sync_committee_period, EthLightClientError, EthUpdate, LightClientStore,
+};
+use sui_types::gas_coin::GasCoin;
+use sui_types::object::Object;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use sui_types::transaction::TransactionData;
+use tracing::{error, info, warn};
+
+use crate::metrics::LightClientMetrics;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EthStateUpdaterConfig {
+    /// The Ethereum network of the active environment of the client config to update the light
+    /// client of, which must have a consensus RPC and a state object configured.
+    pub network: String,
+    /// The gas coin paying for the updates, owned by the active address of the client config.
+    pub gas_object_id: ObjectID,
+    #[serde(default = "default_poll_interval")]
+    pub poll_interval: Duration,
+    #[serde(default = "default_max_updates_per_transaction")]
+    pub max_updates_per_transaction: usize,
+    #[serde(default = "default_gas_budget_per_update")]
+    pub gas_budget_per_update: u64,
+    /// The gas coin balance, in MIST, under which the updater warns that it is running out of gas.
+    #[serde(default = "default_min_gas_balance")]
+    pub min_gas_balance: u64,
+    #[serde(default = "default_metrics_address")]
+    pub metrics_address: SocketAddr,
+}
+
+fn default_poll_interval() -> Duration {
+    // An epoch, the time between two finalized checkpoints.
+    Duration::from_secs(12 * 32)
+}
+
+fn default_max_updates_per_transaction() -> usize {
+    16
+}
+
+fn default_gas_budget_per_update() -> u64 {
+    50_000_000
+}
+
+fn default_min_gas_balance() -> u64 {
+    1_000_000_000
+}
+
+fn default_metrics_address() -> SocketAddr {
+    use std::net::{IpAddr, Ipv4Addr};
+    SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 9184)
+}
+
+impl Config for EthStateUpdaterConfig {}
+
+pub struct EthStateUpdater {
+    config: EthStateUpdaterConfig,
+    /// The metrics label of the network.
+    chain: String,
+    state_object_id: ObjectID,
+    beacon_client: BeaconClient,
+    wallet_ctx: WalletContext,
+    client: SuiClient,
+    signer_address: SuiAddress,
+    gas_obj_ref: ObjectRef,
+    metrics: LightClientMetrics,
+}
+
+impl EthStateUpdater {
+    pub async fn new(
+        config: EthStateUpdaterConfig,
+        mut wallet_ctx: WalletContext,
+        registry: &Registry,
+    ) -> anyhow::Result<Self> {
+        let env = wallet_ctx.config.get_active_env()?;
+        let network = env.eth_network(&config.network)?;
+        let state_object_id = network.state_object_id.ok_or_else(|| {
+            anyhow!(
+                "No state object configured for Ethereum network [{}]",
+                config.network
+            )
+        })?;
+        let beacon_client = env.create_beacon_client(&config.network)?;
+
+        let client = wallet_ctx.get_client().await?;
+        let signer_address = wallet_ctx.active_address()?;
+        let gas_obj_ref = wallet_ctx.get_object_ref(config.gas_object_id).await?;
+        if wallet_ctx.get_object_owner(&config.gas_object_id).await? != signer_address {
+            bail!(
+                "Gas object {} does not belong to {signer_address}",
+                config.gas_object_id
+            );
+        }
+
+        Ok(Self {
+            chain: format!("ethereum-{}", config.network),
+            config,
+            state_object_id,
+            beacon_client,
+            wallet_ctx,
+            client,
+            signer_address,
+            gas_obj_ref,
+            metrics: LightClientMetrics::new(registry),
+        })
+    }
+
+    pub async fn run(mut self) {
+        info!(
+            network = self.config.network,
+            state_object_id = ?self.state_object_id,
+            "Starting the Ethereum state updater"
+        );
+        let mut interval = tokio::time::interval(self.config.poll_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.run_once().await {
+                error!("Failed to update the Ethereum light client: {err:?}");
+            }
+        }
+    }
+
+    async fn run_once(&mut self) -> anyhow::Result<()> {
+        let (light_client, initial_shared_version) =
+            get_light_client(&self.client, self.state_object_id).await?;
+        let store = light_client.store()?;
+        let (updates, chain_head) = self.fetch_updates(&store).await?;
+        self.metrics
+            .observe_heads(&self.chain, light_client.finalized_slot, chain_head);
+
+        let (updates, store) = self.verify_updates(store, updates);
+        if updates.is_empty() {
+            return Ok(());
+        }
+        for batch in batch_updates(&updates, self.config.max_updates_per_transaction)? {
+            self.submit(initial_shared_version, batch).await?;
+        }
+        info!(
+            slot = store.finalized_header.beacon.slot,
+            block_number = store.finalized_header.execution.block_number,
+            "Ethereum light client advanced"
+        );
+        self.metrics
+            .observe_heads(&self.chain, store.finalized_header.beacon.slot, chain_head);
+        self.metrics
+            .sync_committee_period
+            .with_label_values(&[&self.chain])
+            .set(sync_committee_period(store.finalized_header.beacon.slot) as i64);
+        Ok(())
+    }
+
+    /// The updates that may advance `store`, oldest first, and the latest slot of the chain.
+    async fn fetch_updates(
+        &self,
+        store: &LightClientStore,
+    ) -> anyhow::Result<(Vec<EthUpdate>, u64)> {
+        let finality_update = self
+            .beacon_client
+            .finality_update()
+            .await
+            .inspect_err(|_| self.observe_rpc_error("finality_update"))?
+            .data;
+        let chain_head = finality_update.attested_header.beacon.slot;
+
+        // Sync committee period updates are needed to learn the next sync committee, and to
+        // catch up on the periods the light client missed.
+        let mut updates = vec![];
+        let store_period = sync_committee_period(store.finalized_header.beacon.slot);
+        let head_period = sync_committee_period(finality_update.signature_slot);
+        if store.next_sync_committee.is_none() || head_period > store_period {
+            let count = (head_period.saturating_sub(store_period) + 1)
+                .min(MAX_REQUEST_LIGHT_CLIENT_UPDATES);
+            let period_updates = self
+                .beacon_client
+                .updates(store_period, count)
+                .await
+                .inspect_err(|_| self.observe_rpc_error("updates"))?;
+            updates.extend(
+                period_updates
+                    .into_iter()
+                    .map(|update| EthUpdate::Update(update.data)),
+            );
+        }
+        updates.push(EthUpdate::FinalityUpdate(finality_update));
+        Ok((updates, chain_head))
+    }
+
+    /// The `updates` that are valid and advance `store` when applied in order, and the resulting
+    /// store.
+    fn verify_updates(
+        &self,
+        mut store: LightClientStore,
+        updates: Vec<EthUpdate>,
+    ) -> (Vec<EthUpdate>, LightClientStore) {
+        let mut verified = vec![];
+        for update in updates {
+            if is_stale_update(&store, &update) {
+                continue;
+            }
+            match store.apply_update(&update) {
+                Ok(()) => verified.push(update),
+                Err(err) => {
+                    warn!(
+                        slot = update.finalized_header().beacon.slot,
+                        "Dropping an invalid Ethereum light client update: {err}"
+                    );
+                    self.metrics
+                        .verification_failures
+                        .with_label_values(&[&self.chain, failure_reason(&err)])
+                        .inc();
+                }
+            }
+        }
+        (verified, store)
+    }
+
+    async fn submit(
+        &mut self,
+        initial_shared_version: SequenceNumber,
+        updates: &[EthUpdate],
+    ) -> anyhow::Result<()> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        submit_updates(
+            &mut builder,
+            self.state_object_id,
+            initial_shared_version,
+            updates,
+        )?;
+        let rgp = self
+            .client
+            .governance_api()
+            .get_reference_gas_price()
+            .await?;
+        let tx = TransactionData::new_programmable(
+            self.signer_address,
+            vec![self.gas_obj_ref],
+            builder.finish(),
+            self.config.gas_budget_per_update * updates.len() as u64,
+            rgp,
+        );
+        let signed_tx = self.wallet_ctx.sign_transaction(&tx);
+        let tx_digest = *signed_tx.digest();
+
+        let response = self
+            .client
+            .quorum_driver_api()
+            .execute_transaction_block(
+                signed_tx,
+                SuiTransactionBlockResponseOptions::new().with_effects(),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await;
+        let effects = match response {
+            Ok(response) => response
+                .effects
+                .ok_or_else(|| anyhow!("No effects for {tx_digest}"))?,
+            Err(err) => {
+                // The gas object may have been used by another transaction of the address.
+                self.gas_obj_ref = self
+                    .wallet_ctx
+                    .get_object_ref(self.config.gas_object_id)
+                    .await?;
+                bail!("Failed to submit {tx_digest}: {err}");
+            }
+        };
+        // It's critical to update the gas object reference for next transaction
+        self.gas_obj_ref = effects.gas_object().reference.to_object_ref();
+        self.check_gas_balance().await;
+
+        if effects.status().is_err() {
+            bail!(
+                "Light client update {tx_digest} failed: {:?}",
+                effects.status()
+            );
+        }
+        info!(
+            ?tx_digest,
+            net_gas_usage = effects.gas_cost_summary().net_gas_usage(),
+            "Submitted {} Ethereum light client updates",
+            updates.len()
+        );
+        Ok(())
+    }
+
+    async fn check_gas_balance(&self) {
+        let balance = match self.gas_balance().await {
+            Ok(balance) => balance,
+            Err(err) => {
+                warn!("Failed to read the gas balance: {err:?}");
+                return;
+            }
+        };
+        self.metrics
+            .gas_balance
+            .with_label_values(&[&self.chain])
+            .set(balance as i64);
+        if balance < self.config.min_gas_balance {
+            warn!(
+                gas_object_id = ?self.config.gas_object_id,
+                balance,
+                "The Ethereum state updater is running out of gas"
+            );
+        }
+    }
+
+    async fn gas_balance(&self) -> anyhow::Result<u64> {
+        let response = self
+            .client
+            .read_api()
+            .get_object_with_options(
+                self.config.gas_object_id,
+                SuiObjectDataOptions::bcs_lossless(),
+            )
+            .await?;
+        let object: Object = response.into_object()?.try_into()?;
+        Ok(GasCoin::try_from(&object)?.value())
+    }
+
+    fn observe_rpc_error(&self, endpoint: &str) {
+        self.metrics
+            .rpc_errors
+            .with_label_values(&[&self.chain, endpoint])
+            .inc();
+    }
+}
+
+fn failure_reason(err: &EthLightClientError) -> &'static str {
+    match err {
+        EthLightClientError::InvalidHeader(_) => "invalid_header",
+        EthLightClientError::InvalidUpdate(_) => "invalid_update",
+        EthLightClientError::UnknownFork(_) => "unknown_fork",
+        EthLightClientError::InvalidBootstrap(_) | EthLightClientError::InvalidCheckpoint(_) => {
+            "other"
+        }
+    }
+}
@@ -0,0 +1,5 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+pub mod eth_state_updater;
+pub mod metrics;
@@ -0,0 +1,97 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Metrics of the light client services keeping the Ethereum and Bitcoin light clients of the
+//! dWallet network up to date.
+//!
+//! All metrics are labeled by `chain`, e.g. `ethereum-mainnet` or `bitcoin-testnet`, so that a
+//! single dashboard and alert can cover every light client, and a stalled one is noticed before
+//! the conditional signatures depending on it stop firing.
+
+use prometheus::{
+    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, IntCounterVec,
+    IntGaugeVec, Registry,
+};
+
+#[derive(Clone)]
+pub struct LightClientMetrics {
+    /// The latest slot (Ethereum) or block height (Bitcoin) verified on-chain.
+    pub light_client_head: IntGaugeVec,
+    /// The latest slot or block height of the tracked chain, as reported by its RPC.
+    pub chain_head: IntGaugeVec,
+    pub head_lag: IntGaugeVec,
+    pub sync_committee_period: IntGaugeVec,
+    pub verification_failures: IntCounterVec,
+    pub rpc_errors: IntCounterVec,
+    /// The balance of the gas coin the service pays its transactions with, in MIST.
+    pub gas_balance: IntGaugeVec,
+}
+
+impl LightClientMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            light_client_head: register_int_gauge_vec_with_registry!(
+                "light_client_head",
+                "Latest slot or block height verified by the on-chain light client",
+                &["chain"],
+                registry,
+            )
+            .unwrap(),
+            chain_head: register_int_gauge_vec_with_registry!(
+                "light_client_chain_head",
+                "Latest slot or block height of the tracked chain",
+                &["chain"],
+                registry,
+            )
+            .unwrap(),
+            head_lag: register_int_gauge_vec_with_registry!(
+                "light_client_head_lag",
+                "Number of slots or blocks the on-chain light client is behind the tracked chain",
+                &["chain"],
+                registry,
+            )
+            .unwrap(),
+            sync_committee_period: register_int_gauge_vec_with_registry!(
+                "light_client_sync_committee_period",
+                "Sync committee period of the on-chain Ethereum light client",
+                &["chain"],
+                registry,
+            )
+            .unwrap(),
+            verification_failures: register_int_counter_vec_with_registry!(
+                "light_client_verification_failures",
+                "Total number of updates or headers that failed verification",
+                &["chain", "reason"],
+                registry,
+            )
+            .unwrap(),
+            rpc_errors: register_int_counter_vec_with_registry!(
+                "light_client_rpc_errors",
+                "Total number of failed requests to the RPC endpoints of the tracked chain",
+                &["chain", "endpoint"],
+                registry,
+            )
+            .unwrap(),
+            gas_balance: register_int_gauge_vec_with_registry!(
+                "light_client_gas_balance",
+                "Balance of the gas coin paying for the light client updates",
+                &["chain"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    /// Record the head of the on-chain light client and of the tracked chain.
+    pub fn observe_heads(&self, chain: &str, light_client_head: u64, chain_head: u64) {
+        self.light_client_head
+            .with_label_values(&[chain])
+            .set(light_client_head as i64);
+        self.chain_head
+            .with_label_values(&[chain])
+            .set(chain_head as i64);
+        self.head_lag
+            .with_label_values(&[chain])
+            .set(chain_head.saturating_sub(light_client_head) as i64);
+    }
+}
@@ -6,13 +6,18 @@ use axum::{
     extract::{Query, State},
     http::StatusCode,
     routing::{get, post},
-    Router,
+    Json, Router,
 };
 use humantime::parse_duration;
 use serde::Deserialize;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::str::FromStr;
 use std::sync::Arc;
+use sui_types::base_types::ObjectID;
 use sui_types::error::SuiError;
+use sui_types::messages_signature_mpc::{
+    SignatureMPCSessionID, SignatureMPCSessionInFlight, SignatureMPCStatusSummary,
+};
 use telemetry_subscribers::TracingHandle;
 use tracing::info;
 
@@ -39,6 +44,21 @@ use tracing::info;
 //
 //   $ curl 'http://127.0.0.1:1337/node-config'
 //
+// View the health of the signature MPC service: the sessions this node took part in during the
+// current epoch, its presign backlog and its recent failures:
+//
+//   $ curl 'http://127.0.0.1:1337/signature-mpc-status'
+//
+// List the signature MPC sessions this node participates in which are neither completed nor
+// failed, as JSON, oldest first:
+//
+//   $ curl 'http://127.0.0.1:1337/signature-mpc-sessions'
+//
+// Abort an in-flight signature MPC session on this node, by the ID of its session object. The
+// node ignores the messages of the session for the rest of the epoch, and records it as failed:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/abort-signature-mpc-session?session_id=0x1234'
+//
 // Set a time-limited tracing config. After the duration expires, tracing will be disabled
 // automatically.
 //
@@ -56,6 +76,9 @@ const CLEAR_BUFFER_STAKE_ROUTE: &str = "/clear-override-buffer-stake";
 const FORCE_CLOSE_EPOCH: &str = "/force-close-epoch";
 const CAPABILITIES: &str = "/capabilities";
 const NODE_CONFIG: &str = "/node-config";
+const SIGNATURE_MPC_STATUS: &str = "/signature-mpc-status";
+const SIGNATURE_MPC_SESSIONS: &str = "/signature-mpc-sessions";
+const ABORT_SIGNATURE_MPC_SESSION: &str = "/abort-signature-mpc-session";
 
 struct AppState {
     node: Arc<SuiNode>,
@@ -74,6 +97,8 @@ pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, tracing_handle: Tra
         .route(LOGGING_ROUTE, get(get_filter))
         .route(CAPABILITIES, get(capabilities))
         .route(NODE_CONFIG, get(node_config))
+        .route(SIGNATURE_MPC_STATUS, get(signature_mpc_status))
+        .route(SIGNATURE_MPC_SESSIONS, get(signature_mpc_sessions))
         .route(LOGGING_ROUTE, post(set_filter))
         .route(
             SET_BUFFER_STAKE_ROUTE,
@@ -84,6 +109,10 @@ pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, tracing_handle: Tra
             post(clear_override_protocol_upgrade_buffer_stake),
         )
         .route(FORCE_CLOSE_EPOCH, post(force_close_epoch))
+        .route(
+            ABORT_SIGNATURE_MPC_SESSION,
+            post(abort_signature_mpc_session),
+        )
         .route(TRACING_ROUTE, post(enable_tracing))
         .route(TRACING_RESET_ROUTE, post(reset_tracing))
         .with_state(Arc::new(app_state));
@@ -216,6 +245,57 @@ async fn node_config(State(state): State<Arc<AppState>>) -> (StatusCode, String)
     (StatusCode::OK, format!("{:#?}\n", node_config))
 }
 
+async fn signature_mpc_status(
+    State(state): State<Arc<AppState>>,
+) -> Json<SignatureMPCStatusSummary> {
+    Json(state.node.signature_mpc_status().summary())
+}
+
+async fn signature_mpc_sessions(
+    State(state): State<Arc<AppState>>,
+) -> Json<Vec<SignatureMPCSessionInFlight>> {
+    Json(state.node.signature_mpc_status().in_flight_sessions())
+}
+
+#[derive(Deserialize)]
+struct Session {
+    session_id: String,
+}
+
+async fn abort_signature_mpc_session(
+    State(state): State<Arc<AppState>>,
+    session: Query<Session>,
+) -> (StatusCode, String) {
+    let Query(Session { session_id }) = session;
+    let session_id = match ObjectID::from_str(&session_id) {
+        Ok(session_id) => session_id,
+        Err(err) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                format!("invalid session_id: {err}\n"),
+            )
+        }
+    };
+
+    match state
+        .node
+        .signature_mpc_status()
+        .abort_session(SignatureMPCSessionID(session_id.into_bytes()))
+    {
+        Some(protocol) => {
+            info!(%protocol, %session_id, "signature mpc session aborted by the operator");
+            (
+                StatusCode::OK,
+                format!("{protocol} session {session_id} aborted\n"),
+            )
+        }
+        None => (
+            StatusCode::NOT_FOUND,
+            format!("no in-flight signature mpc session {session_id}\n"),
+        ),
+    }
+}
+
 #[derive(Deserialize)]
 struct Epoch {
     epoch: u64,
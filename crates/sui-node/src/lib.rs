@@ -16,6 +16,7 @@ use narwhal_worker::LazyNarwhalClient;
 use prometheus::Registry;
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::str::FromStr;
 #[cfg(msim)]
@@ -24,7 +25,7 @@ use std::sync::Arc;
 use std::time::Duration;
 use sui_core::authority::CHAIN_IDENTIFIER;
 use sui_core::consensus_adapter::SubmitToConsensus;
-use sui_json_rpc_api::JsonRpcMetrics;
+use sui_json_rpc_api::{DWalletRpcMetrics, JsonRpcMetrics};
 use sui_types::base_types::ConciseableName;
 use sui_types::digests::ChainIdentifier;
 use sui_types::message_envelope::get_google_jwk_bytes;
@@ -85,6 +86,7 @@ use sui_core::{
     authority_client::NetworkAuthorityClient,
 };
 use sui_json_rpc::coin_api::CoinReadApi;
+use sui_json_rpc::dwallet_api::DWalletReadApi;
 use sui_json_rpc::governance_api::GovernanceReadApi;
 use sui_json_rpc::indexer_api::IndexerApi;
 use sui_json_rpc::move_utils::MoveUtils;
@@ -200,7 +202,7 @@ use simulator::*;
 pub use simulator::set_jwk_injector;
 use sui_core::consensus_handler::ConsensusHandlerInitializer;
 use sui_core::mysticeti_adapter::LazyMysticetiClient;
-use sui_core::signature_mpc::{SignatureMPCMetrics, SignatureMPCService, SubmitSignatureMPCToConsensus};
+use sui_core::signature_mpc::{SignatureMPCMetrics, SignatureMPCService, SignatureMPCStatus, SubmitSignatureMPCToConsensus};
 use sui_types::messages_signature_mpc::InitiateSignatureMPCProtocol;
 
 pub struct SuiNode {
@@ -212,6 +214,7 @@ pub struct SuiNode {
     transaction_orchestrator: Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>>,
     registry_service: RegistryService,
     metrics: Arc<SuiNodeMetrics>,
+    signature_mpc_status: Arc<SignatureMPCStatus>,
 
     _discovery: discovery::Handle,
     state_sync: state_sync::Handle,
@@ -682,6 +685,7 @@ impl SuiNode {
 
         let connection_monitor_status = Arc::new(connection_monitor_status);
         let sui_node_metrics = Arc::new(SuiNodeMetrics::new(&registry_service.default_registry()));
+        let signature_mpc_status = SignatureMPCStatus::new(config.signature_mpc_tiresias().is_some());
 
         let validator_components = if state.is_validator(&epoch_store) {
             let components = Self::construct_validator_components(
@@ -695,6 +699,7 @@ impl SuiNode {
                 connection_monitor_status.clone(),
                 &registry_service,
                 sui_node_metrics.clone(),
+                signature_mpc_status.clone(),
             )
             .await?;
             // This is only needed during cold start.
@@ -713,6 +718,7 @@ impl SuiNode {
             transaction_orchestrator,
             registry_service,
             metrics: sui_node_metrics,
+            signature_mpc_status,
 
             _discovery: discovery_handle,
             state_sync: state_sync_handle,
@@ -1016,6 +1022,7 @@ impl SuiNode {
         connection_monitor_status: Arc<ConnectionMonitorStatus>,
         registry_service: &RegistryService,
         sui_node_metrics: Arc<SuiNodeMetrics>,
+        signature_mpc_status: Arc<SignatureMPCStatus>,
     ) -> Result<ValidatorComponents> {
         let consensus_config = config
             .consensus_config()
@@ -1093,6 +1100,7 @@ impl SuiNode {
             validator_server_handle,
             checkpoint_metrics,
             signature_mpc_metrics,
+            signature_mpc_status,
             sui_node_metrics,
             sui_tx_validator_metrics,
         )
@@ -1112,6 +1120,7 @@ impl SuiNode {
         validator_server_handle: JoinHandle<Result<()>>,
         checkpoint_metrics: Arc<CheckpointMetrics>,
         signature_mpc_metrics: Arc<SignatureMPCMetrics>,
+        signature_mpc_status: Arc<SignatureMPCStatus>,
         sui_node_metrics: Arc<SuiNodeMetrics>,
         sui_tx_validator_metrics: Arc<SuiTxValidatorMetrics>,
     ) -> Result<ValidatorComponents> {
@@ -1132,6 +1141,7 @@ impl SuiNode {
             epoch_store.clone(),
             state.clone(),
             signature_mpc_metrics.clone(),
+            signature_mpc_status,
         );
 
         // create a new map that gets injected into both the consensus handler and the consensus adapter
@@ -1256,6 +1266,7 @@ impl SuiNode {
         epoch_store: Arc<AuthorityPerEpochStore>,
         state: Arc<AuthorityState>,
         signature_mpc_metrics: Arc<SignatureMPCMetrics>,
+        signature_mpc_status: Arc<SignatureMPCStatus>,
     ) -> (Arc<SignatureMPCService>, watch::Sender<()>) {
         let epoch_start_timestamp_ms = epoch_store.epoch_start_state().epoch_start_timestamp_ms();
         let epoch_duration_ms = epoch_store.epoch_start_state().epoch_duration_ms();
@@ -1292,6 +1303,7 @@ impl SuiNode {
             epoch_store,
             signature_mpc_submit,
             signature_mpc_metrics,
+            signature_mpc_status,
         )
     }
 
@@ -1355,6 +1367,10 @@ impl SuiNode {
         self.state.clone()
     }
 
+    pub fn signature_mpc_status(&self) -> Arc<SignatureMPCStatus> {
+        self.signature_mpc_status.clone()
+    }
+
     // Only used for testing because of how epoch store is loaded.
     pub fn reference_gas_price_for_testing(&self) -> Result<u64, anyhow::Error> {
         self.state.reference_gas_price_for_testing()
@@ -1555,6 +1571,7 @@ impl SuiNode {
                             validator_server_handle,
                             checkpoint_metrics,
                             signature_mpc_metrics,
+                            self.signature_mpc_status.clone(),
                             self.metrics.clone(),
                             sui_tx_validator_metrics,
                         )
@@ -1590,6 +1607,7 @@ impl SuiNode {
                             self.connection_monitor_status.clone(),
                             &self.registry_service,
                             self.metrics.clone(),
+                            self.signature_mpc_status.clone(),
                         )
                         .await?,
                     )
@@ -1785,6 +1803,9 @@ pub fn build_http_server(
 
     let json_rpc_router = {
         let mut server = JsonRpcServerBuilder::new(env!("CARGO_PKG_VERSION"), prometheus_registry);
+        if let Some(rate_limit_config) = &config.json_rpc_rate_limit_config {
+            server.set_rate_limit_config(rate_limit_config.clone());
+        }
 
         let kv_store = build_kv_store(&state, config, prometheus_registry)?;
 
@@ -1801,6 +1822,12 @@ pub fn build_http_server(
         ))?;
         server.register_module(TransactionBuilderApi::new(state.clone()))?;
         server.register_module(GovernanceReadApi::new(state.clone(), metrics.clone()))?;
+        server.register_module(DWalletReadApi::new(
+            state.clone(),
+            kv_store.clone(),
+            config.indexer_max_subscriptions,
+            Arc::new(DWalletRpcMetrics::new(prometheus_registry)),
+        ))?;
 
         if let Some(transaction_orchestrator) = transaction_orchestrator {
             server.register_module(TransactionExecutionApi::new(
@@ -1845,7 +1872,8 @@ pub fn build_http_server(
         router = router.nest("/rest", rest_router);
     }
 
-    let server = axum::Server::bind(&config.json_rpc_address).serve(router.into_make_service());
+    let server = axum::Server::bind(&config.json_rpc_address)
+        .serve(router.into_make_service_with_connect_info::<SocketAddr>());
 
     let addr = server.local_addr();
     let handle = tokio::spawn(async move { server.await.unwrap() });
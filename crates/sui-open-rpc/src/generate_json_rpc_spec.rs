@@ -10,6 +10,7 @@ use std::io::Write;
 //temporarily remove api ref content for indexer methods
 //use sui_json_rpc::api::ExtendedApiOpenRpc;
 use sui_json_rpc::coin_api::CoinReadApi;
+use sui_json_rpc::dwallet_api::DWalletReadApi;
 use sui_json_rpc::governance_api::GovernanceReadApi;
 use sui_json_rpc::read_api::ReadApi;
 use sui_json_rpc::sui_rpc_doc;
@@ -54,6 +55,7 @@ async fn main() {
     open_rpc.add_module(TransactionExecutionApi::rpc_doc_module());
     open_rpc.add_module(TransactionBuilderApi::rpc_doc_module());
     open_rpc.add_module(GovernanceReadApi::rpc_doc_module());
+    open_rpc.add_module(DWalletReadApi::rpc_doc_module());
     //temporarily remove api ref content for indexer methods
     //open_rpc.add_module(ExtendedApiOpenRpc::module_doc());
     open_rpc.add_module(MoveUtilsOpenRpc::module_doc());
@@ -872,6 +872,36 @@ pub struct ProtocolConfig {
     //sui_state_proof::sui_state_proof_verify_transaction_base
     sui_state_proof_verify_transaction_base: Option<u64>,
 
+    // tendermint_light_client::tendermint_verify_light_block
+    tendermint_verify_light_block_cost_base: Option<u64>,
+    tendermint_verify_light_block_cost_per_byte: Option<u64>,
+    tendermint_verify_light_block_cost_per_signature: Option<u64>,
+
+    // tendermint_light_client::tendermint_verify_membership
+    tendermint_verify_membership_cost_base: Option<u64>,
+    tendermint_verify_membership_cost_per_byte: Option<u64>,
+    tendermint_verify_membership_cost_per_step: Option<u64>,
+
+    // ethereum_light_client::ethereum_bootstrap
+    ethereum_bootstrap_cost_base: Option<u64>,
+    ethereum_bootstrap_cost_per_byte: Option<u64>,
+    ethereum_bootstrap_cost_per_pubkey: Option<u64>,
+
+    // ethereum_light_client::ethereum_apply_update
+    ethereum_apply_update_cost_base: Option<u64>,
+    ethereum_apply_update_cost_per_byte: Option<u64>,
+    ethereum_apply_update_cost_per_pubkey: Option<u64>,
+
+    // ethereum_light_client::ethereum_verify_receipt
+    ethereum_verify_receipt_cost_base: Option<u64>,
+    ethereum_verify_receipt_cost_per_node: Option<u64>,
+    ethereum_verify_receipt_cost_per_byte: Option<u64>,
+
+    // ethereum_light_client::ethereum_verify_log
+    ethereum_verify_log_cost_base: Option<u64>,
+    ethereum_verify_log_cost_per_node: Option<u64>,
+    ethereum_verify_log_cost_per_byte: Option<u64>,
+
 
 
     /// === Execution Version ===
@@ -1473,6 +1503,32 @@ impl ProtocolConfig {
             sui_state_proof_verify_link_cap_base: Some(52),
             //sui_state_proof::sui_state_proof_verify_transaction_base
             sui_state_proof_verify_transaction_base: Some(52),
+
+            // tendermint_light_client::tendermint_verify_light_block
+            tendermint_verify_light_block_cost_base: Some(52),
+            tendermint_verify_light_block_cost_per_byte: Some(2),
+            tendermint_verify_light_block_cost_per_signature: Some(52),
+            // tendermint_light_client::tendermint_verify_membership
+            tendermint_verify_membership_cost_base: Some(52),
+            tendermint_verify_membership_cost_per_byte: Some(2),
+            tendermint_verify_membership_cost_per_step: Some(10),
+
+            // ethereum_light_client::ethereum_bootstrap
+            ethereum_bootstrap_cost_base: Some(52),
+            ethereum_bootstrap_cost_per_byte: Some(2),
+            ethereum_bootstrap_cost_per_pubkey: Some(10),
+            // ethereum_light_client::ethereum_apply_update
+            ethereum_apply_update_cost_base: Some(52),
+            ethereum_apply_update_cost_per_byte: Some(2),
+            ethereum_apply_update_cost_per_pubkey: Some(10),
+            // ethereum_light_client::ethereum_verify_receipt
+            ethereum_verify_receipt_cost_base: Some(52),
+            ethereum_verify_receipt_cost_per_node: Some(10),
+            ethereum_verify_receipt_cost_per_byte: Some(2),
+            // ethereum_light_client::ethereum_verify_log
+            ethereum_verify_log_cost_base: Some(52),
+            ethereum_verify_log_cost_per_node: Some(10),
+            ethereum_verify_log_cost_per_byte: Some(2),
         
 
             max_size_written_objects: None,
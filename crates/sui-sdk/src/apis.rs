@@ -3,39 +3,56 @@
 
 use fastcrypto::encoding::Base64;
 use futures::stream;
-use futures::StreamExt;
+use futures::{StreamExt, TryStreamExt};
 use futures_core::Stream;
 use jsonrpsee::core::client::Subscription;
-use std::collections::BTreeMap;
-use std::future;
+use std::collections::{BTreeMap, VecDeque};
+use std::future::{self, Future};
 use std::sync::Arc;
 use std::time::Instant;
 
 use crate::error::{Error, SuiRpcResult};
 use crate::RpcClient;
+use move_core_types::identifier::IdentStr;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use sui_json_rpc_api::{
-    CoinReadApiClient, GovernanceReadApiClient, IndexerApiClient, MoveUtilsClient, ReadApiClient,
-    WriteApiClient,
+    CoinReadApiClient, DWalletReadApiClient, GovernanceReadApiClient, IndexerApiClient,
+    MoveUtilsClient, ReadApiClient, WriteApiClient, QUERY_MAX_RESULT_LIMIT,
 };
 use sui_json_rpc_types::{
-    Balance, Checkpoint, CheckpointId, Coin, CoinPage, DelegatedStake, DevInspectResults,
-    DryRunTransactionBlockResponse, DynamicFieldPage, EventFilter, EventPage, ObjectsPage,
-    ProtocolConfigResponse, SuiCoinMetadata, SuiCommittee, SuiEvent, SuiGetPastObjectRequest,
-    SuiMoveNormalizedModule, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
-    SuiPastObjectResponse, SuiTransactionBlockEffects, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery, TransactionBlocksPage,
-    TransactionFilter,
+    Balance, Checkpoint, CheckpointId, Coin, CoinPage, DWalletInfo, DWalletPage, DWalletQuery,
+    DelegatedStake, DevInspectResults, DryRunTransactionBlockResponse, DynamicFieldPage,
+    EncryptedUserSharePage, EthereumProof, EventFilter, EventPage, ObjectsPage, Page,
+    ProtocolConfigResponse, SignEventFilter, SuiCoinMetadata, SuiCommittee,
+    SuiDWalletTransferStatus, SuiEvent, SuiGetPastObjectRequest, SuiMoveNormalizedModule,
+    SuiMpcParameters, SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse,
+    SuiObjectResponseQuery, SuiPastObjectResponse, SuiPresignPoolStatus, SuiSignEvent,
+    SuiSignSession, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    SuiTransactionBlockResponseQuery, TransactionBlocksPage, TransactionFilter,
+    VerifiedEthereumProof,
 };
 use sui_json_rpc_types::{CheckpointPage, SuiLoadedChildObjectsResponse};
 use sui_types::balance::Supply;
-use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
+use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest};
+use sui_types::committee::EpochId;
 use sui_types::dynamic_field::DynamicFieldName;
 use sui_types::event::EventID;
+use sui_types::external_address::{BitcoinAddresses, BitcoinNetwork, ExternalAddresses};
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::Owner;
 use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use sui_types::signature_mpc::{
+    ApprovalsHolder, DWallet, DWalletBindingCreatedEvent, DWalletCap, DWalletEvent,
+    EncryptedUserShare, EncryptionKey, Presign, SignData, SignSession, SignSessionCreatedEvent,
+    SigningPolicy, DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME, DWALLET_BINDING_CREATED_EVENT_STRUCT_NAME,
+    DWALLET_BINDING_MODULE_NAME, DWALLET_MODULE_NAME, SIGN_OUTPUT_CREATED_EVENT_STRUCT_NAME,
+    SIGN_SESSION_CREATED_EVENT_STRUCT_NAME,
+};
 use sui_types::sui_serde::BigInt;
 use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
 use sui_types::transaction::{Transaction, TransactionData, TransactionKind};
+use sui_types::{SUI_SYSTEM_ADDRESS, SUI_SYSTEM_PACKAGE_ID};
 
 const WAIT_FOR_LOCAL_EXECUTION_RETRY_COUNT: u8 = 3;
 
@@ -210,11 +227,19 @@ impl ReadApi {
         version: SequenceNumber,
         options: SuiObjectDataOptions,
     ) -> SuiRpcResult<SuiPastObjectResponse> {
-        Ok(self
+        let key = format!("past_object:{object_id}:{version}:{options:?}");
+        if let Some(response) = self.api.cached(&key) {
+            return Ok(response);
+        }
+        let response = self
             .api
             .http
             .try_get_past_object(object_id, version, Some(options))
-            .await?)
+            .await?;
+        if let SuiPastObjectResponse::VersionFound(_) = response {
+            self.api.cache(key, &response);
+        }
+        Ok(response)
     }
 
     /// Return a list of [SuiPastObjectResponse] objects, or an error upon failure.
@@ -349,7 +374,16 @@ impl ReadApi {
         object_id: ObjectID,
         options: SuiObjectDataOptions,
     ) -> SuiRpcResult<SuiObjectResponse> {
-        Ok(self.api.http.get_object(object_id, Some(options)).await?)
+        let key = format!("object:{object_id}:{options:?}");
+        if let Some(response) = self.api.cached(&key) {
+            return Ok(response);
+        }
+        let response = self.api.http.get_object(object_id, Some(options)).await?;
+        // The owner of an object is only known if requested.
+        if let Some(Owner::Immutable) = response.data.as_ref().and_then(|data| data.owner) {
+            self.api.cache(key, &response);
+        }
+        Ok(response)
     }
 
     /// Return a list of [SuiObjectResponse] from the given vector of [ObjectID]s and [SuiObjectDataOptions], or an error upon failure.
@@ -408,6 +442,61 @@ impl ReadApi {
             .await?)
     }
 
+    /// Return the objects `object_ids`, in order, however many they are, or an error upon
+    /// failure.
+    ///
+    /// The ids are fetched in chunks of up to `QUERY_MAX_RESULT_LIMIT` per `multiGetObjects`
+    /// call, with up to `concurrency` calls in flight, bounded by the `max_concurrent_requests`
+    /// of the [crate::SuiClientBuilder], which is the default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use sui_sdk::rpc_types::SuiObjectDataOptions;
+    /// use sui_sdk::types::base_types::ObjectID;
+    /// use sui_sdk::SuiClientBuilder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), anyhow::Error> {
+    ///     let sui = SuiClientBuilder::default().build_localnet().await?;
+    ///     let object_ids: Vec<ObjectID> = vec![]; // e.g. the ids of 10k dWallets
+    ///     let objects = sui
+    ///         .read_api()
+    ///         .batch_get_objects_with_options(
+    ///             object_ids,
+    ///             SuiObjectDataOptions::bcs_lossless(),
+    ///             Some(8),
+    ///         )
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn batch_get_objects_with_options(
+        &self,
+        object_ids: Vec<ObjectID>,
+        options: SuiObjectDataOptions,
+        concurrency: Option<usize>,
+    ) -> SuiRpcResult<Vec<SuiObjectResponse>> {
+        batched(&self.api, object_ids, concurrency, |chunk| {
+            self.multi_get_object_with_options(chunk, options.clone())
+        })
+        .await
+    }
+
+    /// Return the transaction blocks `digests`, in order, however many they are, or an error upon
+    /// failure. Fetched in concurrent chunks, like [Self::batch_get_objects_with_options].
+    pub async fn batch_get_transactions_with_options(
+        &self,
+        digests: Vec<TransactionDigest>,
+        options: SuiTransactionBlockResponseOptions,
+        concurrency: Option<usize>,
+    ) -> SuiRpcResult<Vec<SuiTransactionBlockResponse>> {
+        batched(&self.api, digests, concurrency, |chunk| {
+            self.multi_get_transactions_with_options(chunk, options.clone())
+        })
+        .await
+    }
+
     /// Return the total number of transaction blocks known to server, or an error upon failure.
     ///
     /// # Examples
@@ -511,7 +600,13 @@ impl ReadApi {
     /// A Sui checkpoint is a sequence of transaction sets that a quorum of validators
     /// agree upon as having been executed within the Sui system.
     pub async fn get_checkpoint(&self, id: CheckpointId) -> SuiRpcResult<Checkpoint> {
-        Ok(self.api.http.get_checkpoint(id).await?)
+        let key = format!("checkpoint:{id:?}");
+        if let Some(checkpoint) = self.api.cached(&key) {
+            return Ok(checkpoint);
+        }
+        let checkpoint = self.api.http.get_checkpoint(id).await?;
+        self.api.cache(key, &checkpoint);
+        Ok(checkpoint)
     }
 
     /// Return a paginated list of checkpoints, or an error upon failure.
@@ -1202,3 +1297,884 @@ impl GovernanceApi {
         Ok(*self.api.http.get_reference_gas_price().await?)
     }
 }
+
+/// A dWallet owned by an address, through its `DWalletCap`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedDWallet {
+    pub dwallet_id: ObjectID,
+    pub dwallet_cap: ObjectRef,
+}
+
+/// The status of a sign session, see [DWalletApi::get_sign_session_statuses].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignSessionStatus {
+    /// The network has not signed the messages of the session yet.
+    Pending,
+    /// The network signed the messages of the session in the `SignOutput` `sign_output_id`.
+    Signed {
+        sign_output_id: ObjectID,
+        /// When the messages were signed, in milliseconds since the Unix epoch.
+        signed_at_ms: Option<u64>,
+    },
+}
+
+/// A sign session of a dWallet, with its status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignSessionInfo {
+    pub session: SignSessionCreatedEvent,
+    /// When the session was requested, in milliseconds since the Unix epoch.
+    pub requested_at_ms: Option<u64>,
+    pub status: SignSessionStatus,
+}
+
+/// dWallet API provides functionality related to dWallets and the external chains they control.
+#[derive(Debug, Clone)]
+pub struct DWalletApi {
+    api: Arc<RpcClient>,
+}
+
+impl DWalletApi {
+    pub(crate) fn new(api: Arc<RpcClient>) -> Self {
+        Self { api }
+    }
+
+    /// Return the [BitcoinAddresses] controlled by the dWallet with the given `dwallet_id`,
+    /// for the given `network` or for all networks if `None`, or an error upon failure.
+    pub async fn get_bitcoin_addresses(
+        &self,
+        dwallet_id: ObjectID,
+        network: Option<BitcoinNetwork>,
+    ) -> SuiRpcResult<Vec<BitcoinAddresses>> {
+        Ok(self
+            .api
+            .http
+            .get_bitcoin_addresses(dwallet_id, network)
+            .await?)
+    }
+
+    /// Return the [DWalletInfo] of the dWallet `dwallet_id`: its public data, as indexed by the
+    /// node, or an error if there is no such dWallet.
+    pub async fn get_dwallet_info(&self, dwallet_id: ObjectID) -> SuiRpcResult<DWalletInfo> {
+        Ok(self.api.http.get_dwallet(dwallet_id).await?)
+    }
+
+    /// Return a page of the dWallets of `owner` matching `query`, by the `DWalletCap`s it owns,
+    /// with the public data of the active ones.
+    pub async fn get_dwallets_by_owner(
+        &self,
+        owner: SuiAddress,
+        query: Option<DWalletQuery>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> SuiRpcResult<DWalletPage> {
+        Ok(self
+            .api
+            .http
+            .get_dwallets_by_owner(owner, query, cursor, limit)
+            .await?)
+    }
+
+    /// Return the parameters of the MPC protocols of the network in the current epoch: its
+    /// Tiresias public parameters, the curves and hash functions it supports, and the feature
+    /// flags and version of its protocol, against which requests can be validated before they
+    /// are sent.
+    pub async fn get_mpc_parameters(&self) -> SuiRpcResult<SuiMpcParameters> {
+        Ok(self.api.http.get_mpc_parameters().await?)
+    }
+
+    /// Return the presigns of the dWallet `dwallet_id`: how many are available to `owner`, by
+    /// default its owner, in a page of its presigns, and, with the first page, how many are
+    /// pending generation and consumed this epoch.
+    pub async fn get_presign_pool_status(
+        &self,
+        dwallet_id: ObjectID,
+        owner: Option<SuiAddress>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> SuiRpcResult<SuiPresignPoolStatus> {
+        Ok(self
+            .api
+            .http
+            .get_presign_pool_status(dwallet_id, owner, cursor, limit)
+            .await?)
+    }
+
+    /// Return the status of the sign session `session_id`, as indexed by the node, with its
+    /// signatures once signed.
+    pub async fn get_sign_session_status(
+        &self,
+        session_id: ObjectID,
+    ) -> SuiRpcResult<SuiSignSession> {
+        Ok(self.api.http.get_sign_session(session_id).await?)
+    }
+
+    /// Return the encrypted user shares of the dWallet `dwallet_id` owned by `owner`, or by the
+    /// owner of the dWallet if `None`, as indexed by the node, a page at a time.
+    pub async fn get_encrypted_user_shares(
+        &self,
+        dwallet_id: ObjectID,
+        owner: Option<SuiAddress>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> SuiRpcResult<EncryptedUserSharePage> {
+        Ok(self
+            .api
+            .http
+            .get_encrypted_user_shares(dwallet_id, owner, cursor, limit)
+            .await?)
+    }
+
+    /// Return the status of the transfer of a dWallet with the encrypted user share `share_id`,
+    /// for its recipient to check before decrypting the share.
+    pub async fn get_transfer_status(
+        &self,
+        share_id: ObjectID,
+    ) -> SuiRpcResult<SuiDWalletTransferStatus> {
+        Ok(self.api.http.get_transfer_status(share_id).await?)
+    }
+
+    /// Subscribe to the completion and failure of the sign sessions of `filter`, instead of
+    /// polling their status.
+    pub async fn subscribe_sign_events(
+        &self,
+        filter: SignEventFilter,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiSignEvent>>> {
+        match &self.api.ws {
+            Some(c) => {
+                let subscription: Subscription<SuiSignEvent> =
+                    c.subscribe_sign_events(filter).await?;
+                Ok(subscription.map(|item| Ok(item?)))
+            }
+            _ => Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            )),
+        }
+    }
+
+    /// Verify `proof` against the block `block_number` finalized by the Ethereum light client
+    /// `light_client_id`, as the light client verifies proofs on chain, and return the proven
+    /// value. Fails if the proof is invalid, or the light client has not finalized the block.
+    pub async fn verify_ethereum_proof(
+        &self,
+        light_client_id: ObjectID,
+        block_number: u64,
+        proof: EthereumProof,
+    ) -> SuiRpcResult<VerifiedEthereumProof> {
+        Ok(self
+            .api
+            .http
+            .verify_ethereum_proof(light_client_id, block_number.into(), proof)
+            .await?)
+    }
+
+    /// Return the dWallet `dwallet_id`, or an error if there is no such dWallet.
+    pub async fn get_dwallet(&self, dwallet_id: ObjectID) -> SuiRpcResult<DWallet> {
+        let response = self
+            .api
+            .http
+            .get_object(dwallet_id, Some(SuiObjectDataOptions::bcs_lossless()))
+            .await?;
+        let is_dwallet = response
+            .data
+            .as_ref()
+            .and_then(|data| data.type_.as_ref())
+            .and_then(|type_| type_.clone().try_into().ok())
+            .is_some_and(|struct_tag| DWallet::is_type(&struct_tag));
+        if !is_dwallet {
+            return Err(Error::DataError(format!(
+                "Object {dwallet_id} is not a dWallet"
+            )));
+        }
+        let bcs = response.move_object_bcs().ok_or_else(|| {
+            Error::DataError(format!("Fetched the dWallet {dwallet_id} without its data"))
+        })?;
+        Ok(DWallet::from_bcs_bytes(bcs)?)
+    }
+
+    /// Return the addresses of the dWallet `dwallet_id` on the external chains it controls:
+    /// Ethereum, Bitcoin on every network, and the Cosmos Hub. See [DWallet::external_addresses].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::str::FromStr;
+    /// use sui_sdk::types::base_types::ObjectID;
+    /// use sui_sdk::SuiClientBuilder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), anyhow::Error> {
+    ///     let sui = SuiClientBuilder::default().build_localnet().await?;
+    ///     let dwallet_id = ObjectID::from_str("0x0000....0000")?;
+    ///     let addresses = sui.dwallet_api().get_external_addresses(dwallet_id).await?;
+    ///     println!("Deposit ETH to {}", addresses.ethereum);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_external_addresses(
+        &self,
+        dwallet_id: ObjectID,
+    ) -> SuiRpcResult<ExternalAddresses> {
+        self.get_dwallet(dwallet_id)
+            .await?
+            .external_addresses()
+            .map_err(|e| Error::DataError(format!("dWallet {dwallet_id}: {e}")))
+    }
+
+    /// Return the ID of the dWallet controlled by the `DWalletCap` `dwallet_cap_id`, from the
+    /// `DWalletCreated` event of the transaction that created them, or an error upon failure.
+    pub async fn get_dwallet_id(&self, dwallet_cap_id: ObjectID) -> SuiRpcResult<ObjectID> {
+        self.creation_event(dwallet_cap_id, |event| match event {
+            DWalletEvent::DWalletCreated(created)
+                if created.dwallet_cap_id.bytes == dwallet_cap_id =>
+            {
+                Some(created.dwallet_id.bytes)
+            }
+            _ => None,
+        })
+        .await?
+        .ok_or_else(|| {
+            Error::DataError(format!(
+                "No dWallet was created with the DWalletCap {dwallet_cap_id}"
+            ))
+        })
+    }
+
+    /// Return the `SignSessionCreated` event of the sign session `session_id`, with its dWallet
+    /// and messages, or `None` if there is no such session.
+    pub async fn get_sign_session(
+        &self,
+        session_id: ObjectID,
+    ) -> SuiRpcResult<Option<SignSessionCreatedEvent>> {
+        self.creation_event(session_id, |event| match event {
+            DWalletEvent::SignSessionCreated(created) if created.session_id.bytes == session_id => {
+                Some(created)
+            }
+            _ => None,
+        })
+        .await
+    }
+
+    /// Return the hash function the messages of the sign session `session_id` are signed with, as
+    /// a [signature_mpc::twopc_mpc_protocols::Hash] byte, from its immutable `SignSession` object.
+    pub async fn get_sign_session_hash(&self, session_id: ObjectID) -> SuiRpcResult<u8> {
+        let response = self
+            .api
+            .http
+            .get_object(session_id, Some(SuiObjectDataOptions::bcs_lossless()))
+            .await?;
+        let session_type =
+            SignSession::<SignData>::type_(TypeTag::Struct(Box::new(SignData::type_())));
+        let is_session = response
+            .data
+            .as_ref()
+            .and_then(|data| data.type_.as_ref())
+            .and_then(|type_| StructTag::try_from(type_.clone()).ok())
+            .is_some_and(|struct_tag| struct_tag == session_type);
+        if !is_session {
+            return Err(Error::DataError(format!(
+                "Object {session_id} is not a sign session"
+            )));
+        }
+        let bcs = response.move_object_bcs().ok_or_else(|| {
+            Error::DataError(format!(
+                "Fetched the sign session {session_id} without its data"
+            ))
+        })?;
+        Ok(SignSession::<SignData>::from_bcs_bytes(bcs)?.sign_data.hash)
+    }
+
+    /// Return the digest of the transaction that created `object_id`, such as a dWallet or a sign
+    /// session, with the checkpoint that includes it once it is checkpointed, or `None` if there is
+    /// no such object.
+    pub async fn get_creation_transaction(
+        &self,
+        object_id: ObjectID,
+    ) -> SuiRpcResult<Option<(TransactionDigest, Option<CheckpointSequenceNumber>)>> {
+        let query = SuiTransactionBlockResponseQuery::new(
+            Some(TransactionFilter::ChangedObject(object_id)),
+            None,
+        );
+        let page = self
+            .api
+            .http
+            .query_transaction_blocks(query, None, Some(1), Some(false))
+            .await?;
+        Ok(page.data.first().map(|tx| (tx.digest, tx.checkpoint)))
+    }
+
+    /// The first dWallet event of the transaction that created `object_id` matched by `matches`.
+    async fn creation_event<T>(
+        &self,
+        object_id: ObjectID,
+        matches: impl Fn(DWalletEvent) -> Option<T>,
+    ) -> SuiRpcResult<Option<T>> {
+        let query = SuiTransactionBlockResponseQuery::new(
+            Some(TransactionFilter::ChangedObject(object_id)),
+            Some(SuiTransactionBlockResponseOptions::new().with_events()),
+        );
+        let page = self
+            .api
+            .http
+            .query_transaction_blocks(query, None, Some(1), Some(false))
+            .await?;
+        for event in page
+            .data
+            .iter()
+            .flat_map(|tx| tx.events.iter().flat_map(|events| &events.data))
+        {
+            if let Some(event) = DWalletEvent::try_from_event(&event.type_, &event.bcs) {
+                if let Some(matched) = matches(event?) {
+                    return Ok(Some(matched));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Return a stream of the dWallets owned by `owner`, through their `DWalletCap`s.
+    ///
+    /// The stream fetches the pages of the `DWalletCap`s of `owner` as it is consumed, and ends
+    /// after yielding an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use futures::TryStreamExt;
+    /// use std::str::FromStr;
+    /// use sui_sdk::types::base_types::SuiAddress;
+    /// use sui_sdk::SuiClientBuilder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), anyhow::Error> {
+    ///     let sui = SuiClientBuilder::default().build_localnet().await?;
+    ///     let owner = SuiAddress::from_str("0x0000....0000")?;
+    ///     let dwallets: Vec<_> = sui
+    ///         .dwallet_api()
+    ///         .get_owned_dwallets_stream(owner)
+    ///         .try_collect()
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn get_owned_dwallets_stream(
+        &self,
+        owner: SuiAddress,
+    ) -> impl Stream<Item = SuiRpcResult<OwnedDWallet>> + '_ {
+        let query = SuiObjectResponseQuery::new(
+            Some(SuiObjectDataFilter::StructType(DWalletCap::type_())),
+            Some(SuiObjectDataOptions::new()),
+        );
+        paginate(move |cursor| {
+            let query = query.clone();
+            async move {
+                Ok(self
+                    .api
+                    .http
+                    .get_owned_objects(owner, Some(query), cursor, None)
+                    .await?)
+            }
+        })
+        .and_then(move |object| async move {
+            let dwallet_cap = object.object_ref_if_exists().ok_or_else(|| {
+                Error::DataError(format!(
+                    "Fetched a DWalletCap of {owner} without its reference"
+                ))
+            })?;
+            Ok(OwnedDWallet {
+                dwallet_id: self.get_dwallet_id(dwallet_cap.0).await?,
+                dwallet_cap,
+            })
+        })
+    }
+
+    /// Return a stream of the presigns owned by `owner`, for all of their dWallets. A presign is
+    /// left unused when the sign request it was created for fails to complete.
+    ///
+    /// The stream fetches the pages of the presigns of `owner` as it is consumed, and ends after
+    /// yielding an error.
+    pub fn get_owned_presigns_stream(
+        &self,
+        owner: SuiAddress,
+    ) -> impl Stream<Item = SuiRpcResult<Presign>> + '_ {
+        let query = SuiObjectResponseQuery::new(
+            Some(SuiObjectDataFilter::StructType(Presign::type_())),
+            Some(SuiObjectDataOptions::bcs_lossless()),
+        );
+        paginate(move |cursor| {
+            let query = query.clone();
+            async move {
+                Ok(self
+                    .api
+                    .http
+                    .get_owned_objects(owner, Some(query), cursor, None)
+                    .await?)
+            }
+        })
+        .and_then(move |object| {
+            future::ready(match object.move_object_bcs() {
+                Some(bcs) => Presign::from_bcs_bytes(bcs).map_err(Error::from),
+                None => Err(Error::DataError(format!(
+                    "Fetched a Presign of {owner} without its data"
+                ))),
+            })
+        })
+    }
+
+    /// Return a stream of the `ApprovalsHolder`s owned by `owner`, the messages approved for their
+    /// dWallets to sign later.
+    pub fn get_owned_approvals_holders_stream(
+        &self,
+        owner: SuiAddress,
+    ) -> impl Stream<Item = SuiRpcResult<ApprovalsHolder>> + '_ {
+        let query = SuiObjectResponseQuery::new(
+            Some(SuiObjectDataFilter::StructType(ApprovalsHolder::type_())),
+            Some(SuiObjectDataOptions::bcs_lossless()),
+        );
+        paginate(move |cursor| {
+            let query = query.clone();
+            async move {
+                Ok(self
+                    .api
+                    .http
+                    .get_owned_objects(owner, Some(query), cursor, None)
+                    .await?)
+            }
+        })
+        .and_then(move |object| {
+            future::ready(match object.move_object_bcs() {
+                Some(bcs) => ApprovalsHolder::from_bcs_bytes(bcs).map_err(Error::from),
+                None => Err(Error::DataError(format!(
+                    "Fetched an ApprovalsHolder of {owner} without its data"
+                ))),
+            })
+        })
+    }
+
+    /// Return the `ApprovalsHolder` object `holder_id`.
+    pub async fn get_approvals_holder(&self, holder_id: ObjectID) -> SuiRpcResult<ApprovalsHolder> {
+        let response = self
+            .api
+            .http
+            .get_object(holder_id, Some(SuiObjectDataOptions::bcs_lossless()))
+            .await?;
+        let is_approvals_holder = response
+            .data
+            .as_ref()
+            .and_then(|data| data.type_.as_ref())
+            .and_then(|type_| type_.clone().try_into().ok())
+            .is_some_and(|struct_tag| ApprovalsHolder::is_type(&struct_tag));
+        if !is_approvals_holder {
+            return Err(Error::DataError(format!(
+                "Object {holder_id} is not an ApprovalsHolder"
+            )));
+        }
+        let bcs = response.move_object_bcs().ok_or_else(|| {
+            Error::DataError(format!(
+                "Fetched the ApprovalsHolder {holder_id} without its data"
+            ))
+        })?;
+        Ok(ApprovalsHolder::from_bcs_bytes(bcs)?)
+    }
+
+    /// Return the `SigningPolicy` object `policy_id`, with the version it was shared at.
+    pub async fn get_signing_policy(
+        &self,
+        policy_id: ObjectID,
+    ) -> SuiRpcResult<(SigningPolicy, SequenceNumber)> {
+        let response = self
+            .api
+            .http
+            .get_object(policy_id, Some(SuiObjectDataOptions::bcs_lossless()))
+            .await?;
+        let is_signing_policy = response
+            .data
+            .as_ref()
+            .and_then(|data| data.type_.as_ref())
+            .and_then(|type_| type_.clone().try_into().ok())
+            .is_some_and(|struct_tag| SigningPolicy::is_type(&struct_tag));
+        if !is_signing_policy {
+            return Err(Error::DataError(format!(
+                "Object {policy_id} is not a SigningPolicy"
+            )));
+        }
+        let Some(Owner::Shared {
+            initial_shared_version,
+        }) = response.owner()
+        else {
+            return Err(Error::DataError(format!(
+                "SigningPolicy {policy_id} is not shared"
+            )));
+        };
+        let bcs = response.move_object_bcs().ok_or_else(|| {
+            Error::DataError(format!(
+                "Fetched the SigningPolicy {policy_id} without its data"
+            ))
+        })?;
+        Ok((SigningPolicy::from_bcs_bytes(bcs)?, initial_shared_version))
+    }
+
+    /// Return the bindings of the dWallet `dwallet_id` to its addresses on external chains, oldest
+    /// first, from their `DWalletBindingCreated` events.
+    pub async fn get_dwallet_bindings(
+        &self,
+        dwallet_id: ObjectID,
+    ) -> SuiRpcResult<Vec<DWalletBindingCreatedEvent>> {
+        let query = EventFilter::MoveEventType(StructTag {
+            address: SUI_SYSTEM_ADDRESS,
+            module: DWALLET_BINDING_MODULE_NAME.to_owned(),
+            name: DWALLET_BINDING_CREATED_EVENT_STRUCT_NAME.to_owned(),
+            type_params: vec![],
+        });
+        self.events_stream(query, false)
+            .try_filter_map(move |event| {
+                future::ready(
+                    bcs::from_bytes::<DWalletBindingCreatedEvent>(&event.bcs)
+                        .map(|binding| Some(binding).filter(|b| b.dwallet_id.bytes == dwallet_id))
+                        .map_err(Error::from),
+                )
+            })
+            .try_collect()
+            .await
+    }
+
+    /// Return the encryption key published by `owner` for the user shares of the dWallets
+    /// transferred to them, or `None` if they have not published one.
+    pub async fn get_encryption_key(
+        &self,
+        owner: SuiAddress,
+    ) -> SuiRpcResult<Option<EncryptionKey>> {
+        let query = SuiObjectResponseQuery::new(
+            Some(SuiObjectDataFilter::StructType(EncryptionKey::type_())),
+            Some(SuiObjectDataOptions::bcs_lossless()),
+        );
+        let page = self
+            .api
+            .http
+            .get_owned_objects(owner, Some(query), None, Some(1))
+            .await?;
+        page.data
+            .first()
+            .map(|object| match object.move_object_bcs() {
+                Some(bcs) => EncryptionKey::from_bcs_bytes(bcs).map_err(Error::from),
+                None => Err(Error::DataError(format!(
+                    "Fetched an EncryptionKey of {owner} without its data"
+                ))),
+            })
+            .transpose()
+    }
+
+    /// Return the `EncryptedUserShare` object `share_id`, with its owner.
+    pub async fn get_encrypted_user_share(
+        &self,
+        share_id: ObjectID,
+    ) -> SuiRpcResult<(EncryptedUserShare, Owner)> {
+        let response = self
+            .api
+            .http
+            .get_object(share_id, Some(SuiObjectDataOptions::bcs_lossless()))
+            .await?;
+        let is_encrypted_user_share = response
+            .data
+            .as_ref()
+            .and_then(|data| data.type_.as_ref())
+            .and_then(|type_| type_.clone().try_into().ok())
+            .is_some_and(|struct_tag| EncryptedUserShare::is_type(&struct_tag));
+        if !is_encrypted_user_share {
+            return Err(Error::DataError(format!(
+                "Object {share_id} is not an EncryptedUserShare"
+            )));
+        }
+        let owner = response
+            .owner()
+            .ok_or_else(|| Error::DataError(format!("Fetched {share_id} without its owner")))?;
+        let bcs = response.move_object_bcs().ok_or_else(|| {
+            Error::DataError(format!(
+                "Fetched the EncryptedUserShare {share_id} without its data"
+            ))
+        })?;
+        Ok((EncryptedUserShare::from_bcs_bytes(bcs)?, owner))
+    }
+
+    /// Return the epoch in which the dWallet `dwallet_id` was created, from the first transaction
+    /// that changed it.
+    pub async fn get_dwallet_creation_epoch(&self, dwallet_id: ObjectID) -> SuiRpcResult<EpochId> {
+        let query = SuiTransactionBlockResponseQuery::new(
+            Some(TransactionFilter::ChangedObject(dwallet_id)),
+            Some(SuiTransactionBlockResponseOptions::new().with_effects()),
+        );
+        let page = self
+            .api
+            .http
+            .query_transaction_blocks(query, None, Some(1), Some(false))
+            .await?;
+        page.data
+            .first()
+            .and_then(|tx| tx.effects.as_ref())
+            .map(|effects| effects.executed_epoch())
+            .ok_or_else(|| {
+                Error::DataError(format!(
+                    "Cannot find the transaction that created dWallet {dwallet_id}"
+                ))
+            })
+    }
+
+    /// Return a stream of the sign sessions of the dWallet `dwallet_id`, from their
+    /// `SignSessionCreated` events.
+    ///
+    /// The ordering of the sessions can be set with the `descending_order` argument.
+    pub fn get_sign_sessions_stream(
+        &self,
+        dwallet_id: ObjectID,
+        descending_order: bool,
+    ) -> impl Stream<Item = SuiRpcResult<SignSessionCreatedEvent>> + '_ {
+        let events = self.events_stream(
+            dwallet_module_event_type(SIGN_SESSION_CREATED_EVENT_STRUCT_NAME),
+            descending_order,
+        );
+        dwallet_events(events, dwallet_id).try_filter_map(|event| {
+            future::ready(Ok(match event {
+                DWalletEvent::SignSessionCreated(event) => Some(event),
+                _ => None,
+            }))
+        })
+    }
+
+    /// Return the `limit` most recent sign sessions of the dWallet `dwallet_id`, most recent
+    /// first, with their status.
+    ///
+    /// The status of a session is read from the `SignOutputCreated` events of the dWallet. The
+    /// network records no failure of a session on chain, so a session it failed to sign stays
+    /// [SignSessionStatus::Pending].
+    pub async fn get_sign_session_statuses(
+        &self,
+        dwallet_id: ObjectID,
+        limit: usize,
+    ) -> SuiRpcResult<Vec<SignSessionInfo>> {
+        let sessions: Vec<_> = self
+            .events_stream(
+                dwallet_module_event_type(SIGN_SESSION_CREATED_EVENT_STRUCT_NAME),
+                true,
+            )
+            .try_filter_map(move |event| {
+                future::ready(
+                    match DWalletEvent::try_from_event(&event.type_, &event.bcs) {
+                        Some(Ok(DWalletEvent::SignSessionCreated(session)))
+                            if session.dwallet_id.bytes == dwallet_id =>
+                        {
+                            Ok(Some((session, event.timestamp_ms)))
+                        }
+                        Some(Err(e)) => Err(Error::from(e)),
+                        _ => Ok(None),
+                    },
+                )
+            })
+            .take(limit)
+            .try_collect()
+            .await?;
+
+        // Outputs are created after their sessions: the outputs are scanned from the most recent
+        // one back to the oldest session, or until every session is found signed.
+        let oldest_session_ms = sessions
+            .last()
+            .and_then(|(_, requested_at_ms)| *requested_at_ms);
+        let mut outputs = BTreeMap::new();
+        let mut output_events = Box::pin(self.events_stream(
+            dwallet_module_event_type(SIGN_OUTPUT_CREATED_EVENT_STRUCT_NAME),
+            true,
+        ));
+        while outputs.len() < sessions.len() {
+            let Some(event) = output_events.try_next().await? else {
+                break;
+            };
+            if event
+                .timestamp_ms
+                .zip(oldest_session_ms)
+                .is_some_and(|(signed_at_ms, oldest_session_ms)| signed_at_ms < oldest_session_ms)
+            {
+                break;
+            }
+            match DWalletEvent::try_from_event(&event.type_, &event.bcs) {
+                Some(Ok(DWalletEvent::SignOutputCreated(output)))
+                    if output.dwallet_id.bytes == dwallet_id =>
+                {
+                    outputs.insert(
+                        output.session_id.bytes,
+                        SignSessionStatus::Signed {
+                            sign_output_id: output.sign_output_id.bytes,
+                            signed_at_ms: event.timestamp_ms,
+                        },
+                    );
+                }
+                Some(Err(e)) => return Err(Error::from(e)),
+                _ => {}
+            }
+        }
+
+        Ok(sessions
+            .into_iter()
+            .map(|(session, requested_at_ms)| SignSessionInfo {
+                status: outputs
+                    .remove(&session.session_id.bytes)
+                    .unwrap_or(SignSessionStatus::Pending),
+                session,
+                requested_at_ms,
+            })
+            .collect())
+    }
+
+    /// Return a stream of the past lifecycle events of the dWallet `dwallet_id`, see
+    /// [crate::SuiClient::subscribe_dwallet_events] for the events to come.
+    ///
+    /// The ordering of the events can be set with the `descending_order` argument.
+    pub fn get_dwallet_events_stream(
+        &self,
+        dwallet_id: ObjectID,
+        descending_order: bool,
+    ) -> impl Stream<Item = SuiRpcResult<DWalletEvent>> + '_ {
+        // Fullnodes can only query the events of one module at a time, and not by their fields:
+        // the events of the two dWallet modules are merged by time, and filtered by dWallet.
+        let [ecdsa_k1_events, dwallet_module_events] =
+            [DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME, DWALLET_MODULE_NAME].map(|module| {
+                Box::pin(
+                    self.events_stream(
+                        EventFilter::MoveEventModule {
+                            package: SUI_SYSTEM_PACKAGE_ID,
+                            module: module.to_owned(),
+                        },
+                        descending_order,
+                    )
+                    .peekable(),
+                )
+            });
+        let events = stream::unfold(
+            (ecdsa_k1_events, dwallet_module_events),
+            move |(mut first, mut second)| async move {
+                let from_first = match (first.as_mut().peek().await, second.as_mut().peek().await) {
+                    (None, None) => return None,
+                    (Some(_), None) | (Some(Err(_)), _) => true,
+                    (None, Some(_)) | (_, Some(Err(_))) => false,
+                    (Some(Ok(first_event)), Some(Ok(second_event))) => {
+                        if descending_order {
+                            first_event.timestamp_ms >= second_event.timestamp_ms
+                        } else {
+                            first_event.timestamp_ms <= second_event.timestamp_ms
+                        }
+                    }
+                };
+                let event = if from_first {
+                    first.next().await
+                } else {
+                    second.next().await
+                }?;
+                Some((event, (first, second)))
+            },
+        );
+        dwallet_events(events, dwallet_id)
+    }
+
+    fn events_stream(
+        &self,
+        query: EventFilter,
+        descending_order: bool,
+    ) -> impl Stream<Item = SuiRpcResult<SuiEvent>> + '_ {
+        paginate(move |cursor| {
+            let query = query.clone();
+            async move {
+                Ok(self
+                    .api
+                    .http
+                    .query_events(query, cursor, None, Some(descending_order))
+                    .await?)
+            }
+        })
+    }
+}
+
+/// The events of `events` that are lifecycle events of the dWallet `dwallet_id`.
+/// The filter of the events of type `name` of the `dwallet` module.
+fn dwallet_module_event_type(name: &IdentStr) -> EventFilter {
+    EventFilter::MoveEventType(StructTag {
+        address: SUI_SYSTEM_ADDRESS,
+        module: DWALLET_MODULE_NAME.to_owned(),
+        name: name.to_owned(),
+        type_params: vec![],
+    })
+}
+
+fn dwallet_events(
+    events: impl Stream<Item = SuiRpcResult<SuiEvent>>,
+    dwallet_id: ObjectID,
+) -> impl Stream<Item = SuiRpcResult<DWalletEvent>> {
+    events.try_filter_map(move |event| {
+        future::ready(
+            match DWalletEvent::try_from_event(&event.type_, &event.bcs) {
+                Some(Ok(event)) if event.dwallet_id() == &dwallet_id => Ok(Some(event)),
+                Some(Err(e)) => Err(Error::from(e)),
+                _ => Ok(None),
+            },
+        )
+    })
+}
+
+/// The results of `fetch` for the chunks of `items` of up to `QUERY_MAX_RESULT_LIMIT` items, in
+/// order, with up to `concurrency` chunks fetched at once, and no more than the concurrent
+/// requests of the client.
+async fn batched<I: Clone, T, F, Fut>(
+    api: &RpcClient,
+    items: Vec<I>,
+    concurrency: Option<usize>,
+    fetch: F,
+) -> SuiRpcResult<Vec<T>>
+where
+    F: Fn(Vec<I>) -> Fut,
+    Fut: Future<Output = SuiRpcResult<Vec<T>>>,
+{
+    let concurrency = concurrency
+        .unwrap_or(api.max_concurrent_requests)
+        .clamp(1, api.max_concurrent_requests.max(1));
+    let chunks: Vec<_> = items
+        .chunks(*QUERY_MAX_RESULT_LIMIT)
+        .map(<[I]>::to_vec)
+        .collect();
+    let results: Vec<Vec<T>> = stream::iter(chunks)
+        .map(fetch)
+        .buffered(concurrency)
+        .try_collect()
+        .await?;
+    Ok(results.into_iter().flatten().collect())
+}
+
+/// A stream of the items of the pages returned by `fetch_page` for each cursor, from the first
+/// page to the last. The stream ends after yielding an error.
+fn paginate<T, C, F, Fut>(fetch_page: F) -> impl Stream<Item = SuiRpcResult<T>>
+where
+    F: Fn(Option<C>) -> Fut,
+    Fut: Future<Output = SuiRpcResult<Page<T, C>>>,
+{
+    stream::unfold(
+        (
+            VecDeque::new(),
+            /* cursor */ None,
+            /* has_next_page */ true,
+            fetch_page,
+        ),
+        |(mut data, mut cursor, mut has_next_page, fetch_page)| async move {
+            loop {
+                if let Some(item) = data.pop_front() {
+                    return Some((Ok(item), (data, cursor, has_next_page, fetch_page)));
+                }
+                if !has_next_page {
+                    return None;
+                }
+                match fetch_page(cursor.take()).await {
+                    Ok(page) => {
+                        data = page.data.into();
+                        cursor = page.next_cursor;
+                        has_next_page = page.has_next_page;
+                    }
+                    Err(e) => return Some((Err(e), (data, None, false, fetch_page))),
+                }
+            }
+        },
+    )
+}
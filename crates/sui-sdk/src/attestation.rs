@@ -0,0 +1,49 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Oracle-style attestations signed by dWallets.
+//!
+//! An attestation is an arbitrary payload, such as a price or the result of an off-chain
+//! computation, that a dWallet signs on behalf of its owner. Before hashing, the payload is wrapped
+//! in the network defined domain separator `dwallet-attestation:v1` and the schema tag of the
+//! attestation, see [attestation_message], so that an attestation signature can never be a valid
+//! signature of a transaction on any chain. The dWallet must approve the payloads with
+//! `dwallet::approve_attestations`, which wraps them the same way.
+
+pub use sui_types::signature_mpc::{attestation_message, ATTESTATION_DOMAIN};
+
+use crate::dwallet_signer::{sign_and_verify, verify_signature, DWalletSigner, Hash};
+use crate::error::{Error, SuiRpcResult};
+
+/// Sign the attestation `payload` of schema `schema` with the dWallet of `signer`, hashing its
+/// attestation message with `hash`. Returns the 64-byte low-S `r || s` signature.
+pub async fn sign_attestation(
+    schema: &[u8],
+    payload: &[u8],
+    hash: Hash,
+    signer: &impl DWalletSigner,
+) -> SuiRpcResult<Vec<u8>> {
+    if schema.is_empty() {
+        return Err(Error::DataError(
+            "the attestation schema must not be empty".to_string(),
+        ));
+    }
+    sign_and_verify(attestation_message(schema, payload), hash, signer).await
+}
+
+/// Verify that `signature` is the signature by the dWallet with the compressed SEC1 public key
+/// `public_key` of the attestation `payload` of schema `schema`, hashed with `hash`.
+pub fn verify_attestation(
+    public_key: &[u8],
+    schema: &[u8],
+    payload: &[u8],
+    hash: Hash,
+    signature: &[u8],
+) -> SuiRpcResult<()> {
+    verify_signature(
+        public_key,
+        &attestation_message(schema, payload),
+        hash,
+        signature,
+    )
+}
@@ -0,0 +1,245 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! An Ethereum consensus layer client for the Beacon API light client endpoints.
+//!
+//! Consensus RPC providers throttle aggressively, so requests are rate limited on the client
+//! side, retried with exponential backoff on throttling and server errors (honoring
+//! `Retry-After`), and responses are cached: bootstraps, which are addressed by block root, for
+//! as long as they stay in the cache, and the other responses until they may have changed.
+
+use std::num::{NonZeroU32, NonZeroUsize};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use backoff::future::retry;
+use backoff::ExponentialBackoff;
+use fastcrypto::encoding::{Encoding, Hex};
+use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
+use lru::LruCache;
+use reqwest::header::RETRY_AFTER;
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use sui_types::eth_light_client::{
+    Genesis, LightClientBootstrap, LightClientFinalityUpdate, LightClientOptimisticUpdate,
+    LightClientUpdate, Root, Versioned,
+};
+
+use crate::error::{Error, SuiRpcResult};
+use crate::sui_client_config::EthNetworkConfig;
+
+/// The maximum number of updates the Beacon API serves per request.
+pub const MAX_REQUEST_LIGHT_CLIENT_UPDATES: u64 = 128;
+
+const DEFAULT_REQUESTS_PER_SECOND: u32 = 5;
+const CACHE_SIZE: usize = 256;
+/// Finality and optimistic updates change at most once per slot.
+const SLOT: Duration = Duration::from_secs(12);
+/// The best update of the current sync committee period can still change, so updates are refreshed
+/// every epoch.
+const EPOCH: Duration = Duration::from_secs(12 * 32);
+
+pub struct BeaconClientBuilder {
+    requests_per_second: NonZeroU32,
+    max_elapsed_time: Duration,
+}
+
+impl Default for BeaconClientBuilder {
+    fn default() -> Self {
+        Self {
+            requests_per_second: NonZeroU32::new(DEFAULT_REQUESTS_PER_SECOND).unwrap(),
+            max_elapsed_time: Duration::from_secs(60),
+        }
+    }
+}
+
+impl BeaconClientBuilder {
+    /// The maximum number of requests per second sent to the provider.
+    pub fn requests_per_second(mut self, requests_per_second: NonZeroU32) -> Self {
+        self.requests_per_second = requests_per_second;
+        self
+    }
+
+    /// How long a request is retried before failing.
+    pub fn max_elapsed_time(mut self, max_elapsed_time: Duration) -> Self {
+        self.max_elapsed_time = max_elapsed_time;
+        self
+    }
+
+    pub fn build(self, url: impl AsRef<str>) -> BeaconClient {
+        BeaconClient {
+            url: url.as_ref().trim_end_matches('/').to_string(),
+            http: reqwest::Client::new(),
+            cache: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(CACHE_SIZE).unwrap(),
+            ))),
+            rate_limiter: Arc::new(RateLimiter::direct(Quota::per_second(
+                self.requests_per_second,
+            ))),
+            backoff: ExponentialBackoff {
+                max_elapsed_time: Some(self.max_elapsed_time),
+                ..ExponentialBackoff::default()
+            },
+        }
+    }
+}
+
+impl EthNetworkConfig {
+    /// The client of the consensus layer of the network, if it is configured.
+    pub fn build_beacon_client(&self) -> Option<BeaconClient> {
+        let mut builder = BeaconClientBuilder::default();
+        if let Some(requests_per_second) = self.consensus_rpc_requests_per_second {
+            builder = builder.requests_per_second(requests_per_second);
+        }
+        self.consensus_rpc.as_ref().map(|url| builder.build(url))
+    }
+}
+
+struct CachedResponse {
+    value: Value,
+    expires_at: Option<Instant>,
+}
+
+#[derive(Clone)]
+pub struct BeaconClient {
+    url: String,
+    http: reqwest::Client,
+    cache: Arc<Mutex<LruCache<String, CachedResponse>>>,
+    rate_limiter: Arc<DefaultDirectRateLimiter>,
+    backoff: ExponentialBackoff,
+}
+
+impl BeaconClient {
+    pub async fn genesis(&self) -> SuiRpcResult<Genesis> {
+        self.get_data("/eth/v1/beacon/genesis", None).await
+    }
+
+    /// The bootstrap of the light client at the finalized (epoch boundary) block `block_root`.
+    pub async fn bootstrap(
+        &self,
+        block_root: &Root,
+    ) -> SuiRpcResult<Versioned<LightClientBootstrap>> {
+        let path = format!(
+            "/eth/v1/beacon/light_client/bootstrap/0x{}",
+            Hex::encode(block_root)
+        );
+        self.get(&path, None).await
+    }
+
+    /// The best updates of `count` sync committee periods starting at `start_period`.
+    pub async fn updates(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> SuiRpcResult<Vec<Versioned<LightClientUpdate>>> {
+        let path = format!(
+            "/eth/v1/beacon/light_client/updates?start_period={start_period}&count={count}"
+        );
+        self.get(&path, Some(EPOCH)).await
+    }
+
+    pub async fn finality_update(&self) -> SuiRpcResult<Versioned<LightClientFinalityUpdate>> {
+        self.get("/eth/v1/beacon/light_client/finality_update", Some(SLOT))
+            .await
+    }
+
+    pub async fn optimistic_update(&self) -> SuiRpcResult<Versioned<LightClientOptimisticUpdate>> {
+        self.get("/eth/v1/beacon/light_client/optimistic_update", Some(SLOT))
+            .await
+    }
+
+    /// The root of the latest finalized block.
+    pub async fn finalized_block_root(&self) -> SuiRpcResult<Root> {
+        let header: Value = self
+            .get_data("/eth/v1/beacon/headers/finalized", Some(SLOT))
+            .await?;
+        header["root"]
+            .as_str()
+            .and_then(|root| root.strip_prefix("0x"))
+            .and_then(|root| Hex::decode(root).ok())
+            .and_then(|root| root.try_into().ok())
+            .ok_or_else(|| Error::EthRpcError(format!("invalid block root in {header}")))
+    }
+
+    /// Drop all cached responses.
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    async fn get_data<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        ttl: Option<Duration>,
+    ) -> SuiRpcResult<T> {
+        let mut response: Value = self.get(path, ttl).await?;
+        serde_json::from_value(response["data"].take())
+            .map_err(|e| Error::EthRpcError(format!("invalid response from {path}: {e}")))
+    }
+
+    /// GET `path`, from the cache if a response that has not expired is cached, and otherwise
+    /// from the provider, caching the response for `ttl`, or until evicted if `ttl` is `None`.
+    async fn get<T: DeserializeOwned>(&self, path: &str, ttl: Option<Duration>) -> SuiRpcResult<T> {
+        let cached =
+            self.cache
+                .lock()
+                .unwrap()
+                .get(path)
+                .and_then(|cached| match cached.expires_at {
+                    Some(expires_at) if expires_at <= Instant::now() => None,
+                    _ => Some(cached.value.clone()),
+                });
+        let value = match cached {
+            Some(value) => value,
+            None => {
+                let value = self.fetch(path).await?;
+                self.cache.lock().unwrap().put(
+                    path.to_string(),
+                    CachedResponse {
+                        value: value.clone(),
+                        expires_at: ttl.map(|ttl| Instant::now() + ttl),
+                    },
+                );
+                value
+            }
+        };
+        serde_json::from_value(value)
+            .map_err(|e| Error::EthRpcError(format!("invalid response from {path}: {e}")))
+    }
+
+    async fn fetch(&self, path: &str) -> SuiRpcResult<Value> {
+        let url = format!("{}{path}", self.url);
+        retry(self.backoff.clone(), || async {
+            self.rate_limiter.until_ready().await;
+            let response = self.http.get(&url).send().await.map_err(|e| {
+                backoff::Error::transient(Error::EthRpcError(format!("cannot reach {url}: {e}")))
+            })?;
+
+            let status = response.status();
+            if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+                let error = Error::EthRpcError(format!("{url} returned {status}"));
+                let retry_after = response
+                    .headers()
+                    .get(RETRY_AFTER)
+                    .and_then(|retry_after| retry_after.to_str().ok())
+                    .and_then(|retry_after| retry_after.parse().ok())
+                    .map(Duration::from_secs);
+                return Err(match retry_after {
+                    Some(retry_after) => backoff::Error::retry_after(error, retry_after),
+                    None => backoff::Error::transient(error),
+                });
+            }
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_default();
+                return Err(backoff::Error::permanent(Error::EthRpcError(format!(
+                    "{url} returned {status}: {body}"
+                ))));
+            }
+            response
+                .json::<Value>()
+                .await
+                .map_err(|e| backoff::Error::permanent(Error::EthRpcError(e.to_string())))
+        })
+        .await
+    }
+}
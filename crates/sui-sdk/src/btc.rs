@@ -0,0 +1,586 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Bitcoin custody with dWallets.
+//!
+//! This module finds the inputs of a PSBT that spend outputs controlled by a dWallet, computes
+//! their sighashes (legacy and segwit v0), obtains the signatures from the dWallet through a
+//! [DWalletSigner], and finalizes the PSBT into a broadcastable transaction.
+//!
+//! Taproot key-path spends require a Schnorr signature, which an ECDSA dWallet cannot produce, so a
+//! PSBT spending a taproot output of the dWallet is rejected.
+//!
+//! The amounts of the spent outputs are only trusted where the signature commits to them: segwit
+//! inputs may give their spent output in `witness_utxo`, but legacy inputs must give the whole
+//! previous transaction in `non_witness_utxo`, which is checked against the txid of the spent
+//! outpoint. Only `SIGHASH_ALL` is signed, unless the caller allows other sighash types.
+//!
+//! Bitcoin sighashes are the double SHA256 of a preimage, so the dWallet is asked to sign the
+//! single SHA256 of the preimage with [Hash::SHA256], which makes the network sign exactly the sighash.
+
+pub use bitcoin;
+
+use bitcoin::consensus::encode::serialize;
+use bitcoin::ecdsa;
+use bitcoin::hashes::{hash160, sha256, sha256d, Hash as _};
+use bitcoin::psbt::{Input, Psbt};
+use bitcoin::secp256k1::{self, Message, Secp256k1, XOnlyPublicKey};
+use bitcoin::sighash::EcdsaSighashType;
+use bitcoin::{Amount, PublicKey, Script, ScriptBuf, Sequence, Transaction, TxOut, Witness};
+use fastcrypto::encoding::{Base64, Encoding};
+use sui_types::external_address::BitcoinNetwork;
+
+use crate::dwallet_signer::{DWalletSigner, Hash};
+use crate::error::{Error, SuiRpcResult};
+
+const SIGHASH_ALL: u32 = 0x01;
+const SIGHASH_NONE: u32 = 0x02;
+const SIGHASH_SINGLE: u32 = 0x03;
+const SIGHASH_ANYONECANPAY: u32 = 0x80;
+
+/// The kind of output a dWallet-controlled input spends, which determines its sighash algorithm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputKind {
+    P2pkh,
+    P2shP2wpkh,
+    P2wpkh,
+}
+
+/// What needs to be signed for one input of a PSBT.
+#[derive(Clone, Debug)]
+pub struct SighashRequest {
+    pub input_index: usize,
+    pub kind: InputKind,
+    pub sighash_type: u32,
+    /// The serialized signing data the sighash commits to.
+    pub preimage: Vec<u8>,
+}
+
+impl SighashRequest {
+    pub fn sighash(&self) -> [u8; 32] {
+        sha256d::Hash::hash(&self.preimage).to_byte_array()
+    }
+
+    /// The message to submit to the dWallet, to be hashed by the network with [Hash::SHA256].
+    pub fn dwallet_message(&self) -> Vec<u8> {
+        sha256::Hash::hash(&self.preimage).to_byte_array().to_vec()
+    }
+}
+
+pub fn psbt_from_base64(psbt: &str) -> SuiRpcResult<Psbt> {
+    let bytes = Base64::decode(psbt).map_err(|e| Error::DataError(e.to_string()))?;
+    Psbt::deserialize(&bytes).map_err(|e| Error::DataError(format!("invalid PSBT: {e}")))
+}
+
+pub fn psbt_to_base64(psbt: &Psbt) -> String {
+    Base64::encode(psbt.serialize())
+}
+
+/// Create a PSBT for an unsigned transaction.
+/// The spent outputs must then be set on the inputs (`witness_utxo` or `non_witness_utxo`).
+pub fn create_psbt(unsigned_tx: Transaction) -> SuiRpcResult<Psbt> {
+    Psbt::from_unsigned_tx(unsigned_tx).map_err(|e| Error::DataError(format!("invalid PSBT: {e}")))
+}
+
+/// The kind of `script_pubkey` if it is controlled by `public_key`, or `None` otherwise.
+pub fn input_kind(script_pubkey: &Script, public_key: &PublicKey) -> Option<InputKind> {
+    let pubkey_hash = hash160::Hash::hash(&public_key.to_bytes()).to_byte_array();
+    let script_pubkey = script_pubkey.as_bytes();
+
+    if script_pubkey == p2wpkh_script(&pubkey_hash).as_bytes() {
+        Some(InputKind::P2wpkh)
+    } else if script_pubkey == p2pkh_script(&pubkey_hash).as_bytes() {
+        Some(InputKind::P2pkh)
+    } else if script_pubkey == p2sh_script(&p2wpkh_script(&pubkey_hash)).as_bytes() {
+        Some(InputKind::P2shP2wpkh)
+    } else {
+        None
+    }
+}
+
+/// Whether `script_pubkey` is the taproot output with `public_key` as its internal key and no
+/// script tree, which only a Schnorr signature spends.
+fn is_p2tr_key_path(script_pubkey: &Script, public_key: &PublicKey) -> bool {
+    script_pubkey.as_bytes()
+        == ScriptBuf::new_p2tr(
+            &Secp256k1::verification_only(),
+            XOnlyPublicKey::from(public_key.inner),
+            None,
+        )
+        .as_bytes()
+}
+
+/// Compute the sighash requests of all the inputs of `psbt` controlled by `public_key`.
+///
+/// A PSBT with inputs spending the taproot output of `public_key` is rejected, as an ECDSA dWallet
+/// cannot sign them. The inputs must use `SIGHASH_ALL`, unless `allow_any_sighash` is set, as the
+/// other sighash types let the rest of the transaction be changed after it is signed.
+pub fn sighash_requests(
+    psbt: &Psbt,
+    public_key: &PublicKey,
+    allow_any_sighash: bool,
+) -> SuiRpcResult<Vec<SighashRequest>> {
+    let tx = &psbt.unsigned_tx;
+    let taproot_inputs = (0..psbt.inputs.len())
+        .filter(|&input_index| {
+            spent_output(psbt, input_index)
+                .is_ok_and(|spent| is_p2tr_key_path(&spent.output.script_pubkey, public_key))
+        })
+        .map(|input_index| input_index.to_string())
+        .collect::<Vec<_>>();
+    if !taproot_inputs.is_empty() {
+        return Err(Error::DataError(format!(
+            "input(s) {} spend the taproot output of the dWallet, which requires a Schnorr signature that an ECDSA dWallet cannot produce",
+            taproot_inputs.join(", ")
+        )));
+    }
+
+    let mut requests = Vec::new();
+    for (input_index, input) in psbt.inputs.iter().enumerate() {
+        let SpentOutput {
+            output: spent_output,
+            verified,
+        } = spent_output(psbt, input_index)?;
+        let Some(kind) = input_kind(&spent_output.script_pubkey, public_key) else {
+            continue;
+        };
+        // The legacy sighash does not commit to the spent amount.
+        if kind == InputKind::P2pkh && !verified {
+            return Err(Error::DataError(format!(
+                "input {input_index} spends a legacy output, which requires the previous transaction in its non_witness_utxo"
+            )));
+        }
+        let pubkey_hash = hash160::Hash::hash(&public_key.to_bytes()).to_byte_array();
+        let sighash_type = input
+            .sighash_type
+            .map(|t| t.to_u32())
+            .unwrap_or(SIGHASH_ALL);
+        if EcdsaSighashType::from_standard(sighash_type).is_err() {
+            return Err(Error::DataError(format!(
+                "input {input_index} has the non-standard sighash type {sighash_type:#x}"
+            )));
+        }
+        if sighash_type != SIGHASH_ALL && !allow_any_sighash {
+            return Err(Error::DataError(format!(
+                "input {input_index} has the sighash type {sighash_type:#x}, only SIGHASH_ALL is signed unless other sighash types are allowed"
+            )));
+        }
+
+        let preimage = match kind {
+            InputKind::P2pkh => {
+                legacy_preimage(tx, input_index, &spent_output.script_pubkey, sighash_type)?
+            }
+            InputKind::P2wpkh | InputKind::P2shP2wpkh => segwit_v0_preimage(
+                tx,
+                input_index,
+                &p2pkh_script(&pubkey_hash),
+                spent_output.value,
+                sighash_type,
+            )?,
+        };
+        requests.push(SighashRequest {
+            input_index,
+            kind,
+            sighash_type,
+            preimage,
+        });
+    }
+    Ok(requests)
+}
+
+/// Sign every input of `psbt` controlled by the dWallet of `signer`, adding the signatures as
+/// partial signatures. Returns the indexes of the signed inputs.
+/// See [sighash_requests] for `allow_any_sighash`.
+pub async fn sign_psbt(
+    psbt: &mut Psbt,
+    signer: &impl DWalletSigner,
+    allow_any_sighash: bool,
+) -> SuiRpcResult<Vec<usize>> {
+    let public_key = PublicKey::from_slice(&signer.public_key())
+        .map_err(|e| Error::DataError(format!("invalid dWallet public key: {e}")))?;
+    let requests = sighash_requests(psbt, &public_key, allow_any_sighash)?;
+    if requests.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let messages = requests
+        .iter()
+        .map(SighashRequest::dwallet_message)
+        .collect();
+    let signatures = signer.sign(messages, Hash::SHA256).await?;
+    add_signatures(psbt, &public_key, &requests, signatures)?;
+    Ok(requests.iter().map(|r| r.input_index).collect())
+}
+
+/// Add `signatures`, the signatures of the [SighashRequest::dwallet_message]s of `requests` by the
+/// dWallet with key `public_key`, to the inputs of `psbt` as partial signatures. Each signature is
+/// checked against the sighash of its input.
+pub fn add_signatures(
+    psbt: &mut Psbt,
+    public_key: &PublicKey,
+    requests: &[SighashRequest],
+    signatures: Vec<Vec<u8>>,
+) -> SuiRpcResult<()> {
+    if signatures.len() != requests.len() {
+        return Err(Error::DataError(format!(
+            "expected {} signatures from the dWallet, got {}",
+            requests.len(),
+            signatures.len()
+        )));
+    }
+
+    let secp = Secp256k1::verification_only();
+    for (request, signature) in requests.iter().zip(signatures) {
+        let mut signature = secp256k1::ecdsa::Signature::from_compact(&signature)
+            .map_err(|e| Error::DataError(format!("invalid dWallet signature: {e}")))?;
+        // Bitcoin only relays low-S signatures.
+        signature.normalize_s();
+        let message = Message::from_slice(&request.sighash()).expect("sighash is 32 bytes");
+        secp.verify_ecdsa(&message, &signature, &public_key.inner)
+            .map_err(|e| Error::DataError(format!("dWallet signature does not verify: {e}")))?;
+
+        let hash_ty = EcdsaSighashType::from_standard(request.sighash_type)
+            .map_err(|e| Error::DataError(e.to_string()))?;
+        psbt.inputs[request.input_index].partial_sigs.insert(
+            *public_key,
+            ecdsa::Signature {
+                sig: signature,
+                hash_ty,
+            },
+        );
+    }
+    Ok(())
+}
+
+/// Finalize the inputs of `psbt` that were signed by the dWallet with key `public_key`.
+pub fn finalize_psbt(psbt: &mut Psbt, public_key: &PublicKey) -> SuiRpcResult<()> {
+    for input_index in 0..psbt.inputs.len() {
+        let spent = spent_output(psbt, input_index)?;
+        let Some(kind) = input_kind(&spent.output.script_pubkey, public_key) else {
+            continue;
+        };
+        let input = &psbt.inputs[input_index];
+        let Some(signature) = input.partial_sigs.get(public_key) else {
+            return Err(Error::DataError(format!(
+                "input {input_index} is controlled by the dWallet but is not signed"
+            )));
+        };
+        let signature = signature.to_vec();
+        let public_key_bytes = public_key.to_bytes();
+
+        let (final_script_sig, final_script_witness) = match kind {
+            InputKind::P2pkh => (
+                Some(ScriptBuf::from_bytes([push_data(&signature), push_data(&public_key_bytes)].concat())),
+                None,
+            ),
+            InputKind::P2wpkh => (None, Some(Witness::from_slice(&[signature, public_key_bytes]))),
+            InputKind::P2shP2wpkh => {
+                let pubkey_hash = hash160::Hash::hash(&public_key_bytes).to_byte_array();
+                let redeem_script = p2wpkh_script(&pubkey_hash);
+                (
+                    Some(ScriptBuf::from_bytes(push_data(redeem_script.as_bytes()))),
+                    Some(Witness::from_slice(&[signature, public_key_bytes])),
+                )
+            }
+        };
+
+        psbt.inputs[input_index] = Input {
+            non_witness_utxo: input.non_witness_utxo.clone(),
+            witness_utxo: input.witness_utxo.clone(),
+            final_script_sig,
+            final_script_witness,
+            ..Default::default()
+        };
+    }
+    Ok(())
+}
+
+/// Extract the network serialized transaction of a fully finalized `psbt`.
+pub fn extract_transaction(psbt: &Psbt) -> SuiRpcResult<Vec<u8>> {
+    let mut tx = psbt.unsigned_tx.clone();
+    for (input_index, (txin, input)) in tx.input.iter_mut().zip(psbt.inputs.iter()).enumerate() {
+        if input.final_script_sig.is_none() && input.final_script_witness.is_none() {
+            return Err(Error::DataError(format!(
+                "input {input_index} is not finalized"
+            )));
+        }
+        txin.script_sig = input.final_script_sig.clone().unwrap_or_default();
+        txin.witness = input.final_script_witness.clone().unwrap_or_default();
+    }
+    Ok(serialize(&tx))
+}
+
+/// An output of a PSBT, as shown to the signer before signing.
+#[derive(Clone, Debug)]
+pub struct PsbtOutputSummary {
+    /// The address of the output, or the hex of its script if it has no address form.
+    pub recipient: String,
+    pub amount: Amount,
+    /// Whether the output pays back to the dWallet.
+    pub change: bool,
+}
+
+/// What a PSBT does, in human terms.
+///
+/// The amounts of the spent outputs are as written in the PSBT: they are only verified for the
+/// inputs that are not in `unverified_inputs`.
+#[derive(Clone, Debug)]
+pub struct PsbtSummary {
+    pub outputs: Vec<PsbtOutputSummary>,
+    /// The total amount spent by the inputs controlled by the dWallet.
+    pub spent: Amount,
+    /// The fee, if the spent outputs of all the inputs are known.
+    pub fee: Option<Amount>,
+    /// The number of inputs that are not controlled by the dWallet.
+    pub foreign_inputs: usize,
+    /// The inputs whose spent amount is neither checked against their previous transaction nor
+    /// committed to by the signature of the dWallet, for which the PSBT may lie.
+    pub unverified_inputs: Vec<usize>,
+    /// Whether an input controlled by the dWallet is in `unverified_inputs`, so that `spent` may
+    /// be wrong.
+    pub spent_unverified: bool,
+}
+
+/// Summarize the outputs, amounts and fee of `psbt` for the dWallet `public_key`, with the
+/// addresses of `network`.
+pub fn summarize_psbt(
+    psbt: &Psbt,
+    public_key: &PublicKey,
+    network: BitcoinNetwork,
+) -> SuiRpcResult<PsbtSummary> {
+    let network = match network {
+        BitcoinNetwork::Mainnet => bitcoin::Network::Bitcoin,
+        BitcoinNetwork::Testnet => bitcoin::Network::Testnet,
+        BitcoinNetwork::Regtest => bitcoin::Network::Regtest,
+    };
+
+    let mut spent = Amount::ZERO;
+    let mut spent_unverified = false;
+    let mut total_in = Some(Amount::ZERO);
+    let mut foreign_inputs = 0;
+    let mut unverified_inputs = Vec::new();
+    for input_index in 0..psbt.inputs.len() {
+        match spent_output(psbt, input_index) {
+            Ok(SpentOutput { output, verified }) => {
+                let kind = input_kind(&output.script_pubkey, public_key);
+                // The segwit sighash commits to the amount of the input it signs.
+                let verified =
+                    verified || matches!(kind, Some(InputKind::P2wpkh | InputKind::P2shP2wpkh));
+                if !verified {
+                    unverified_inputs.push(input_index);
+                }
+                if kind.is_some() {
+                    spent += output.value;
+                    spent_unverified |= !verified;
+                } else {
+                    foreign_inputs += 1;
+                }
+                total_in = total_in.map(|total| total + output.value);
+            }
+            Err(_) => {
+                foreign_inputs += 1;
+                total_in = None;
+            }
+        }
+    }
+
+    let outputs: Vec<_> = psbt
+        .unsigned_tx
+        .output
+        .iter()
+        .map(|output| PsbtOutputSummary {
+            recipient: bitcoin::Address::from_script(&output.script_pubkey, network)
+                .map(|address| address.to_string())
+                .unwrap_or_else(|_| output.script_pubkey.to_hex_string()),
+            amount: output.value,
+            change: input_kind(&output.script_pubkey, public_key).is_some(),
+        })
+        .collect();
+    let total_out = outputs.iter().map(|output| output.amount).sum::<Amount>();
+    let fee = match total_in {
+        Some(total_in) => Some(total_in.checked_sub(total_out).ok_or_else(|| {
+            Error::DataError(format!(
+                "outputs of {total_out} exceed the spent outputs of {total_in}"
+            ))
+        })?),
+        None => None,
+    };
+
+    Ok(PsbtSummary {
+        outputs,
+        spent,
+        fee,
+        foreign_inputs,
+        unverified_inputs,
+        spent_unverified,
+    })
+}
+
+/// The output spent by an input of a PSBT.
+struct SpentOutput {
+    output: TxOut,
+    /// Whether the output is taken from the previous transaction in `non_witness_utxo`, whose
+    /// txid is checked, rather than from `witness_utxo`, which anyone may have written.
+    verified: bool,
+}
+
+fn spent_output(psbt: &Psbt, input_index: usize) -> SuiRpcResult<SpentOutput> {
+    let input = &psbt.inputs[input_index];
+    let prevout = psbt.unsigned_tx.input[input_index].previous_output;
+    if let Some(tx) = &input.non_witness_utxo {
+        if tx.txid() != prevout.txid {
+            return Err(Error::DataError(format!(
+                "the non_witness_utxo of input {input_index} is not the transaction {} it spends from",
+                prevout.txid
+            )));
+        }
+        let output = tx
+            .output
+            .get(prevout.vout as usize)
+            .cloned()
+            .ok_or_else(|| {
+                Error::DataError(format!(
+                    "the non_witness_utxo of input {input_index} has no output {}",
+                    prevout.vout
+                ))
+            })?;
+        if input
+            .witness_utxo
+            .as_ref()
+            .is_some_and(|witness_utxo| *witness_utxo != output)
+        {
+            return Err(Error::DataError(format!(
+                "the witness_utxo of input {input_index} is not the output of its non_witness_utxo"
+            )));
+        }
+        return Ok(SpentOutput {
+            output,
+            verified: true,
+        });
+    }
+    input
+        .witness_utxo
+        .clone()
+        .map(|output| SpentOutput {
+            output,
+            verified: false,
+        })
+        .ok_or_else(|| Error::DataError(format!("input {input_index} is missing its spent output")))
+}
+
+fn legacy_preimage(
+    tx: &Transaction,
+    input_index: usize,
+    script_code: &Script,
+    sighash_type: u32,
+) -> SuiRpcResult<Vec<u8>> {
+    let base_type = sighash_type & 0x1f;
+    let anyone_can_pay = sighash_type & SIGHASH_ANYONECANPAY != 0;
+    if base_type == SIGHASH_SINGLE && input_index >= tx.output.len() {
+        // Consensus signs the constant 1 in this case, which must never be signed blindly.
+        return Err(Error::DataError(format!(
+            "input {input_index} uses SIGHASH_SINGLE without a matching output"
+        )));
+    }
+
+    let mut tx = tx.clone();
+    for (i, input) in tx.input.iter_mut().enumerate() {
+        input.witness = Witness::default();
+        if i == input_index {
+            input.script_sig = script_code.to_owned();
+        } else {
+            input.script_sig = ScriptBuf::new();
+            if base_type == SIGHASH_NONE || base_type == SIGHASH_SINGLE {
+                input.sequence = Sequence::ZERO;
+            }
+        }
+    }
+    if anyone_can_pay {
+        tx.input = vec![tx.input[input_index].clone()];
+    }
+    match base_type {
+        SIGHASH_NONE => tx.output.clear(),
+        SIGHASH_SINGLE => {
+            tx.output.truncate(input_index + 1);
+            for output in tx.output.iter_mut().take(input_index) {
+                *output = TxOut {
+                    value: Amount::from_sat(u64::MAX),
+                    script_pubkey: ScriptBuf::new(),
+                };
+            }
+        }
+        _ => {}
+    }
+
+    let mut preimage = serialize(&tx);
+    preimage.extend_from_slice(&sighash_type.to_le_bytes());
+    Ok(preimage)
+}
+
+/// BIP-143 signing data.
+fn segwit_v0_preimage(
+    tx: &Transaction,
+    input_index: usize,
+    script_code: &Script,
+    value: Amount,
+    sighash_type: u32,
+) -> SuiRpcResult<Vec<u8>> {
+    let base_type = sighash_type & 0x1f;
+    let anyone_can_pay = sighash_type & SIGHASH_ANYONECANPAY != 0;
+    let zero = [0u8; 32];
+
+    let hash_prevouts = if anyone_can_pay {
+        zero
+    } else {
+        sha256d_concat(tx.input.iter().map(|i| serialize(&i.previous_output)))
+    };
+    let hash_sequence =
+        if anyone_can_pay || base_type == SIGHASH_SINGLE || base_type == SIGHASH_NONE {
+            zero
+        } else {
+            sha256d_concat(tx.input.iter().map(|i| serialize(&i.sequence)))
+        };
+    let hash_outputs = if base_type != SIGHASH_SINGLE && base_type != SIGHASH_NONE {
+        sha256d_concat(tx.output.iter().map(serialize))
+    } else if base_type == SIGHASH_SINGLE && input_index < tx.output.len() {
+        sha256d::Hash::hash(&serialize(&tx.output[input_index])).to_byte_array()
+    } else {
+        zero
+    };
+
+    let input = &tx.input[input_index];
+    let mut preimage = serialize(&tx.version);
+    preimage.extend_from_slice(&hash_prevouts);
+    preimage.extend_from_slice(&hash_sequence);
+    preimage.extend(serialize(&input.previous_output));
+    preimage.extend(serialize(&script_code.to_owned()));
+    preimage.extend_from_slice(&value.to_sat().to_le_bytes());
+    preimage.extend(serialize(&input.sequence));
+    preimage.extend_from_slice(&hash_outputs);
+    preimage.extend(serialize(&tx.lock_time));
+    preimage.extend_from_slice(&sighash_type.to_le_bytes());
+    Ok(preimage)
+}
+
+fn p2wpkh_script(pubkey_hash: &[u8; 20]) -> ScriptBuf {
+    ScriptBuf::from_bytes([&[0x00, 0x14][..], pubkey_hash].concat())
+}
+
+fn p2pkh_script(pubkey_hash: &[u8; 20]) -> ScriptBuf {
+    ScriptBuf::from_bytes([&[0x76, 0xa9, 0x14][..], pubkey_hash, &[0x88, 0xac]].concat())
+}
+
+fn p2sh_script(redeem_script: &Script) -> ScriptBuf {
+    let script_hash = hash160::Hash::hash(redeem_script.as_bytes()).to_byte_array();
+    ScriptBuf::from_bytes([&[0xa9, 0x14][..], &script_hash, &[0x87]].concat())
+}
+
+/// A minimal push of up to 75 bytes, which covers signatures, public keys and P2WPKH redeem scripts.
+fn push_data(data: &[u8]) -> Vec<u8> {
+    debug_assert!(data.len() <= 75);
+    [&[data.len() as u8][..], data].concat()
+}
+
+fn sha256d_concat(items: impl Iterator<Item = Vec<u8>>) -> [u8; 32] {
+    sha256d::Hash::hash(&items.flatten().collect::<Vec<_>>()).to_byte_array()
+}
@@ -0,0 +1,211 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! A minimal Bitcoin client for broadcasting the transactions signed with [crate::btc], and for
+//! querying confirmations and UTXOs, backed by either an Electrum server or a Bitcoin Core node.
+//!
+//! The backend is configured per environment, see [crate::sui_client_config::SuiEnv].
+
+use std::str::FromStr;
+
+use bitcoin::consensus::encode::serialize_hex;
+use bitcoin::hashes::{sha256, Hash};
+use bitcoin::{Amount, OutPoint, Script, Transaction, Txid};
+use fastcrypto::encoding::{Encoding, Hex};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+use crate::error::{Error, SuiRpcResult};
+
+/// The Bitcoin backend of an environment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "lowercase")]
+pub enum BitcoinClientConfig {
+    /// An Electrum server, reached over plain TCP at `host:port` (a `tcp://` prefix is accepted).
+    Electrum { url: String },
+    /// The JSON-RPC interface of a Bitcoin Core node.
+    Core {
+        url: String,
+        user: Option<String>,
+        password: Option<String>,
+    },
+}
+
+impl BitcoinClientConfig {
+    pub fn build(&self) -> BitcoinClient {
+        BitcoinClient {
+            config: self.clone(),
+            http: reqwest::Client::new(),
+        }
+    }
+}
+
+/// An unspent transaction output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Utxo {
+    pub outpoint: OutPoint,
+    pub value: Amount,
+    /// The height of the block including the output, `None` if it is still in the mempool.
+    pub height: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BitcoinClient {
+    config: BitcoinClientConfig,
+    http: reqwest::Client,
+}
+
+impl BitcoinClient {
+    /// Broadcast a finalized transaction, returning its txid.
+    pub async fn broadcast(&self, tx: &Transaction) -> SuiRpcResult<Txid> {
+        let method = match self.config {
+            BitcoinClientConfig::Electrum { .. } => "blockchain.transaction.broadcast",
+            BitcoinClientConfig::Core { .. } => "sendrawtransaction",
+        };
+        let txid = self.call(method, json!([serialize_hex(tx)])).await?;
+        parse_txid(&txid)
+    }
+
+    /// The number of confirmations of a transaction, 0 if it is in the mempool.
+    ///
+    /// Bitcoin Core only knows about transactions that are not in its wallet when it runs with
+    /// `-txindex`.
+    pub async fn confirmations(&self, txid: &Txid) -> SuiRpcResult<u32> {
+        let method = match self.config {
+            BitcoinClientConfig::Electrum { .. } => "blockchain.transaction.get",
+            BitcoinClientConfig::Core { .. } => "getrawtransaction",
+        };
+        let tx = self.call(method, json!([txid.to_string(), true])).await?;
+        Ok(tx["confirmations"].as_u64().unwrap_or(0) as u32)
+    }
+
+    /// The outputs locked by `script_pubkey` that are not spent.
+    ///
+    /// Bitcoin Core scans the UTXO set, so it does not report outputs that are still in the mempool.
+    pub async fn list_unspent(&self, script_pubkey: &Script) -> SuiRpcResult<Vec<Utxo>> {
+        match self.config {
+            BitcoinClientConfig::Electrum { .. } => {
+                // Electrum indexes scripts by their reversed SHA256.
+                let mut script_hash = sha256::Hash::hash(script_pubkey.as_bytes()).to_byte_array();
+                script_hash.reverse();
+                let utxos = self
+                    .call(
+                        "blockchain.scripthash.listunspent",
+                        json!([Hex::encode(script_hash)]),
+                    )
+                    .await?;
+                as_array(&utxos)?
+                    .iter()
+                    .map(|utxo| {
+                        Ok(Utxo {
+                            outpoint: OutPoint::new(
+                                parse_txid(&utxo["tx_hash"])?,
+                                as_u64(&utxo["tx_pos"])? as u32,
+                            ),
+                            value: Amount::from_sat(as_u64(&utxo["value"])?),
+                            height: match as_u64(&utxo["height"])? {
+                                0 => None,
+                                height => Some(height as u32),
+                            },
+                        })
+                    })
+                    .collect()
+            }
+            BitcoinClientConfig::Core { .. } => {
+                let descriptor = format!("raw({})", Hex::encode(script_pubkey.as_bytes()));
+                let scan = self
+                    .call("scantxoutset", json!(["start", [descriptor]]))
+                    .await?;
+                as_array(&scan["unspents"])?
+                    .iter()
+                    .map(|utxo| {
+                        let amount = utxo["amount"].as_f64().ok_or_else(|| {
+                            Error::BitcoinRpcError(format!("invalid amount in {utxo}"))
+                        })?;
+                        Ok(Utxo {
+                            outpoint: OutPoint::new(
+                                parse_txid(&utxo["txid"])?,
+                                as_u64(&utxo["vout"])? as u32,
+                            ),
+                            value: Amount::from_btc(amount)
+                                .map_err(|e| Error::BitcoinRpcError(e.to_string()))?,
+                            height: Some(as_u64(&utxo["height"])? as u32),
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    async fn call(&self, method: &str, params: Value) -> SuiRpcResult<Value> {
+        let response = match &self.config {
+            BitcoinClientConfig::Electrum { url } => {
+                let address = url.strip_prefix("tcp://").unwrap_or(url);
+                let request =
+                    json!({"jsonrpc": "2.0", "id": 0, "method": method, "params": params});
+                let mut stream = TcpStream::connect(address)
+                    .await
+                    .map_err(|e| Error::BitcoinRpcError(format!("cannot connect to {url}: {e}")))?;
+                stream
+                    .write_all(format!("{request}\n").as_bytes())
+                    .await
+                    .map_err(|e| Error::BitcoinRpcError(e.to_string()))?;
+                let mut line = String::new();
+                BufReader::new(stream)
+                    .read_line(&mut line)
+                    .await
+                    .map_err(|e| Error::BitcoinRpcError(e.to_string()))?;
+                serde_json::from_str::<Value>(&line)
+                    .map_err(|e| Error::BitcoinRpcError(e.to_string()))?
+            }
+            BitcoinClientConfig::Core {
+                url,
+                user,
+                password,
+            } => {
+                let request =
+                    json!({"jsonrpc": "1.0", "id": 0, "method": method, "params": params});
+                let mut builder = self.http.post(url).json(&request);
+                if let Some(user) = user {
+                    builder = builder.basic_auth(user, password.as_ref());
+                }
+                // Bitcoin Core reports errors with a non-success status, but still in a JSON-RPC body.
+                builder
+                    .send()
+                    .await
+                    .map_err(|e| Error::BitcoinRpcError(format!("cannot reach {url}: {e}")))?
+                    .json::<Value>()
+                    .await
+                    .map_err(|e| Error::BitcoinRpcError(e.to_string()))?
+            }
+        };
+        if !response["error"].is_null() {
+            return Err(Error::BitcoinRpcError(format!(
+                "{method} failed: {}",
+                response["error"]
+            )));
+        }
+        Ok(response["result"].clone())
+    }
+}
+
+fn parse_txid(value: &Value) -> SuiRpcResult<Txid> {
+    value
+        .as_str()
+        .and_then(|txid| Txid::from_str(txid).ok())
+        .ok_or_else(|| Error::BitcoinRpcError(format!("invalid txid {value}")))
+}
+
+fn as_u64(value: &Value) -> SuiRpcResult<u64> {
+    value
+        .as_u64()
+        .ok_or_else(|| Error::BitcoinRpcError(format!("expected an integer, got {value}")))
+}
+
+fn as_array(value: &Value) -> SuiRpcResult<&Vec<Value>> {
+    value
+        .as_array()
+        .ok_or_else(|| Error::BitcoinRpcError(format!("expected an array, got {value}")))
+}
@@ -0,0 +1,63 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! The cache of the responses of a fullnode that cannot change, see
+//! [crate::SuiClientBuilder::cache].
+//!
+//! Only immutable objects, such as packages and the DKG outputs and public parameters of
+//! dWallets, past versions of objects, and checkpoints are cached. Entries expire after a
+//! time-to-live, so that the cache does not serve the responses of a network that was reset.
+
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use lru::LruCache;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+struct CachedResponse {
+    value: Value,
+    expires_at: Instant,
+}
+
+pub(crate) struct ResponseCache {
+    entries: Mutex<LruCache<String, CachedResponse>>,
+    ttl: Duration,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(capacity: NonZeroUsize, ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+            ttl,
+        }
+    }
+
+    pub(crate) fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let mut entries = self.entries.lock().unwrap();
+        let cached = entries.get(key)?;
+        if cached.expires_at <= Instant::now() {
+            entries.pop(key);
+            return None;
+        }
+        serde_json::from_value(cached.value.clone()).ok()
+    }
+
+    pub(crate) fn put<T: Serialize>(&self, key: String, value: &T) {
+        if let Ok(value) = serde_json::to_value(value) {
+            self.entries.lock().unwrap().put(
+                key,
+                CachedResponse {
+                    value,
+                    expires_at: Instant::now() + self.ttl,
+                },
+            );
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Clients connected to several environments at once, e.g. the dWallet network and a Sui mainnet
+//! fullnode to read the state a dWallet application acts on.
+//!
+//! The clients of a [ClientManager] are connected concurrently, and can share a rate limit, for
+//! applications reading from several networks through one RPC provider to stay under its quota.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::try_join_all;
+use governor::{Quota, RateLimiter};
+
+use crate::error::{Error, SuiRpcResult};
+use crate::sui_client_config::SuiEnv;
+use crate::SuiClient;
+
+/// A builder of [ClientManager]s.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::num::NonZeroU32;
+/// use sui_sdk::client_manager::ClientManagerBuilder;
+/// use sui_sdk::sui_client_config::SuiEnv;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), anyhow::Error> {
+///     let dwallet = SuiEnv::builder("dwallet", "http://fullnode.alpha.testnet.dwallet.cloud:9000")
+///         .build()?;
+///     let sui = SuiEnv::builder("sui", "https://fullnode.mainnet.sui.io:443").build()?;
+///     let clients = ClientManagerBuilder::default()
+///         .requests_per_second(NonZeroU32::new(20).unwrap())
+///         .build([&dwallet, &sui])
+///         .await?;
+///
+///     let dwallet_checkpoint = clients
+///         .client("dwallet")?
+///         .read_api()
+///         .get_latest_checkpoint_sequence_number()
+///         .await?;
+///     let sui_checkpoint = clients
+///         .client("sui")?
+///         .read_api()
+///         .get_latest_checkpoint_sequence_number()
+///         .await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ClientManagerBuilder {
+    request_timeout: Option<Duration>,
+    max_concurrent_requests: Option<usize>,
+    requests_per_second: Option<NonZeroU32>,
+}
+
+impl ClientManagerBuilder {
+    /// Set the request timeout of every client.
+    pub fn request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = Some(request_timeout);
+        self
+    }
+
+    /// Set the max concurrent requests of each client.
+    pub fn max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = Some(max_concurrent_requests);
+        self
+    }
+
+    /// Send at most `requests_per_second` HTTP requests per second, retries included, across all
+    /// the clients.
+    pub fn requests_per_second(mut self, requests_per_second: NonZeroU32) -> Self {
+        self.requests_per_second = Some(requests_per_second);
+        self
+    }
+
+    /// Connect a client to each of `envs`, concurrently. Fails if two environments have the same
+    /// alias, or if any client fails to connect.
+    pub async fn build<'a>(
+        self,
+        envs: impl IntoIterator<Item = &'a SuiEnv>,
+    ) -> SuiRpcResult<ClientManager> {
+        let envs: Vec<_> = envs.into_iter().collect();
+        let mut aliases = BTreeSet::new();
+        for env in &envs {
+            if !aliases.insert(env.alias.as_str()) {
+                return Err(Error::InvalidConfig(format!(
+                    "Duplicate env alias [{}]",
+                    env.alias
+                )));
+            }
+        }
+
+        let rate_limiter = self.requests_per_second.map(|requests_per_second| {
+            Arc::new(RateLimiter::direct(Quota::per_second(requests_per_second)))
+        });
+        let clients = try_join_all(envs.iter().map(|env| {
+            let mut builder = env.client_builder();
+            if let Some(request_timeout) = self.request_timeout {
+                builder = builder.request_timeout(request_timeout);
+            }
+            if let Some(max_concurrent_requests) = self.max_concurrent_requests {
+                builder = builder.max_concurrent_requests(max_concurrent_requests);
+            }
+            if let Some(rate_limiter) = &rate_limiter {
+                builder = builder.rate_limiter(rate_limiter.clone());
+            }
+            builder.build(&env.rpc)
+        }))
+        .await?;
+
+        Ok(ClientManager {
+            clients: envs
+                .iter()
+                .map(|env| env.alias.clone())
+                .zip(clients)
+                .collect(),
+        })
+    }
+}
+
+/// Clients connected to several environments, by alias. Build with [ClientManagerBuilder].
+///
+/// Clones share the connections of the clients, and their rate limit.
+#[derive(Clone)]
+pub struct ClientManager {
+    clients: BTreeMap<String, SuiClient>,
+}
+
+impl ClientManager {
+    /// The client of the environment `alias`, if there is one.
+    pub fn get(&self, alias: &str) -> Option<&SuiClient> {
+        self.clients.get(alias)
+    }
+
+    /// The client of the environment `alias`, failing if there is none.
+    pub fn client(&self, alias: &str) -> SuiRpcResult<&SuiClient> {
+        self.get(alias)
+            .ok_or_else(|| Error::InvalidConfig(format!("No client for env [{alias}]")))
+    }
+
+    /// The aliases of the environments, in alphabetical order.
+    pub fn aliases(&self) -> impl Iterator<Item = &str> {
+        self.clients.keys().map(String::as_str)
+    }
+
+    /// The clients, with the aliases of their environments, in alphabetical order of the aliases.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &SuiClient)> {
+        self.clients
+            .iter()
+            .map(|(alias, client)| (alias.as_str(), client))
+    }
+}
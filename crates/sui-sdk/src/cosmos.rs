@@ -0,0 +1,233 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Cosmos SDK custody with dWallets.
+//!
+//! This module builds Cosmos SDK transactions for a single secp256k1 signer, signs their
+//! `SignDoc` (`SIGN_MODE_DIRECT`) with a [DWalletSigner], and assembles the `TxRaw` to broadcast.
+//!
+//! Cosmos signs the SHA256 of the `SignDoc`, so the dWallet is asked to sign the `SignDoc` itself
+//! with [Hash::SHA256].
+
+use bitcoin::hashes::{sha256, Hash as _};
+use bitcoin::secp256k1::{ecdsa, Message, PublicKey, Secp256k1};
+use prost::Message as _;
+use sui_types::external_address::cosmos_address;
+
+use crate::dwallet_signer::{DWalletSigner, Hash};
+use crate::error::{Error, SuiRpcResult};
+
+pub const SECP256K1_PUBLIC_KEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
+pub const MSG_SEND_TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgSend";
+/// `SignMode::SIGN_MODE_DIRECT`.
+const SIGN_MODE_DIRECT: i32 = 1;
+
+/// `google.protobuf.Any`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Any {
+    #[prost(string, tag = "1")]
+    pub type_url: String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub value: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Coin {
+    #[prost(string, tag = "1")]
+    pub denom: String,
+    #[prost(string, tag = "2")]
+    pub amount: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MsgSend {
+    #[prost(string, tag = "1")]
+    pub from_address: String,
+    #[prost(string, tag = "2")]
+    pub to_address: String,
+    #[prost(message, repeated, tag = "3")]
+    pub amount: Vec<Coin>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TxBody {
+    #[prost(message, repeated, tag = "1")]
+    pub messages: Vec<Any>,
+    #[prost(string, tag = "2")]
+    pub memo: String,
+    #[prost(uint64, tag = "3")]
+    pub timeout_height: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Fee {
+    #[prost(message, repeated, tag = "1")]
+    pub amount: Vec<Coin>,
+    #[prost(uint64, tag = "2")]
+    pub gas_limit: u64,
+    #[prost(string, tag = "3")]
+    pub payer: String,
+    #[prost(string, tag = "4")]
+    pub granter: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AuthInfo {
+    #[prost(message, repeated, tag = "1")]
+    pub signer_infos: Vec<SignerInfo>,
+    #[prost(message, optional, tag = "2")]
+    pub fee: Option<Fee>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SignerInfo {
+    #[prost(message, optional, tag = "1")]
+    pub public_key: Option<Any>,
+    #[prost(message, optional, tag = "2")]
+    pub mode_info: Option<ModeInfo>,
+    #[prost(uint64, tag = "3")]
+    pub sequence: u64,
+}
+
+/// `ModeInfo`, restricted to its `single` variant.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ModeInfo {
+    #[prost(message, optional, tag = "1")]
+    pub single: Option<ModeInfoSingle>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ModeInfoSingle {
+    #[prost(int32, tag = "1")]
+    pub mode: i32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SignDoc {
+    #[prost(bytes = "vec", tag = "1")]
+    pub body_bytes: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub auth_info_bytes: Vec<u8>,
+    #[prost(string, tag = "3")]
+    pub chain_id: String,
+    #[prost(uint64, tag = "4")]
+    pub account_number: u64,
+}
+
+/// The signed transaction, as broadcast to `cosmos.tx.v1beta1.Service/BroadcastTx`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TxRaw {
+    #[prost(bytes = "vec", tag = "1")]
+    pub body_bytes: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub auth_info_bytes: Vec<u8>,
+    #[prost(bytes = "vec", repeated, tag = "3")]
+    pub signatures: Vec<Vec<u8>>,
+}
+
+impl SignDoc {
+    pub fn new(body: &TxBody, auth_info: &AuthInfo, chain_id: &str, account_number: u64) -> Self {
+        Self {
+            body_bytes: body.encode_to_vec(),
+            auth_info_bytes: auth_info.encode_to_vec(),
+            chain_id: chain_id.to_string(),
+            account_number,
+        }
+    }
+}
+
+/// The account address of the dWallet key `public_key` on the chain with the bech32 `prefix`.
+pub fn address(public_key: &[u8], prefix: &str) -> SuiRpcResult<String> {
+    cosmos_address(public_key, prefix).map_err(|e| Error::DataError(e.to_string()))
+}
+
+pub fn coin(amount: u128, denom: &str) -> Coin {
+    Coin {
+        denom: denom.to_string(),
+        amount: amount.to_string(),
+    }
+}
+
+pub fn msg_send(from_address: &str, to_address: &str, amount: Vec<Coin>) -> Any {
+    Any {
+        type_url: MSG_SEND_TYPE_URL.to_string(),
+        value: MsgSend {
+            from_address: from_address.to_string(),
+            to_address: to_address.to_string(),
+            amount,
+        }
+        .encode_to_vec(),
+    }
+}
+
+/// The `AuthInfo` of a transaction signed by `public_key` alone, in `SIGN_MODE_DIRECT`.
+pub fn auth_info(public_key: &[u8], sequence: u64, fee: Fee) -> AuthInfo {
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct PubKey {
+        #[prost(bytes = "vec", tag = "1")]
+        key: Vec<u8>,
+    }
+
+    AuthInfo {
+        signer_infos: vec![SignerInfo {
+            public_key: Some(Any {
+                type_url: SECP256K1_PUBLIC_KEY_TYPE_URL.to_string(),
+                value: PubKey {
+                    key: public_key.to_vec(),
+                }
+                .encode_to_vec(),
+            }),
+            mode_info: Some(ModeInfo {
+                single: Some(ModeInfoSingle {
+                    mode: SIGN_MODE_DIRECT,
+                }),
+            }),
+            sequence,
+        }],
+        fee: Some(fee),
+    }
+}
+
+/// Sign `sign_doc` with the dWallet of `signer`, returning the 64-byte low-S signature that
+/// Cosmos SDK chains accept.
+pub async fn sign_doc(sign_doc: &SignDoc, signer: &impl DWalletSigner) -> SuiRpcResult<Vec<u8>> {
+    let public_key = PublicKey::from_slice(&signer.public_key())
+        .map_err(|e| Error::DataError(format!("invalid dWallet public key: {e}")))?;
+    let sign_bytes = sign_doc.encode_to_vec();
+    let signature = signer
+        .sign(vec![sign_bytes.clone()], Hash::SHA256)
+        .await?
+        .pop()
+        .ok_or_else(|| Error::DataError("no signature returned by the dWallet".to_string()))?;
+
+    let mut signature = ecdsa::Signature::from_compact(&signature)
+        .map_err(|e| Error::DataError(format!("invalid dWallet signature: {e}")))?;
+    // Cosmos SDK rejects high-S signatures.
+    signature.normalize_s();
+    let message = Message::from_slice(&sha256::Hash::hash(&sign_bytes).to_byte_array())
+        .expect("digest is 32 bytes");
+    Secp256k1::verification_only()
+        .verify_ecdsa(&message, &signature, &public_key)
+        .map_err(|e| Error::DataError(format!("dWallet signature does not verify: {e}")))?;
+    Ok(signature.serialize_compact().to_vec())
+}
+
+/// Build and sign a transaction of `body` paying `fee`, for the dWallet account with the given
+/// `account_number` and `sequence` on `chain_id`.
+pub async fn sign_tx(
+    body: &TxBody,
+    fee: Fee,
+    chain_id: &str,
+    account_number: u64,
+    sequence: u64,
+    signer: &impl DWalletSigner,
+) -> SuiRpcResult<TxRaw> {
+    let auth_info = auth_info(&signer.public_key(), sequence, fee);
+    let doc = SignDoc::new(body, &auth_info, chain_id, account_number);
+    let signature = sign_doc(&doc, signer).await?;
+    Ok(TxRaw {
+        body_bytes: doc.body_bytes,
+        auth_info_bytes: doc.auth_info_bytes,
+        signatures: vec![signature],
+    })
+}
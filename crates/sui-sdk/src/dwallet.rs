@@ -0,0 +1,1075 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Creating and signing with 2PC-MPC ECDSA secp256k1 dWallets.
+//!
+//! Every dWallet operation runs the user-side (centralized) party of the 2PC-MPC protocol locally,
+//! exchanging its messages with the network (decentralized) party through Move calls and the
+//! objects the validators send back. [DWalletClient] hides these rounds behind one call per
+//! operation, signing and executing the transactions with the keys of a [WalletContext], or, with
+//! [DWalletClient::with_offline_signing], with keys held on an air-gapped machine. With
+//! [DWalletClient::with_sponsor], the gas of the transactions is paid by a sponsor.
+//!
+//! Each round of a protocol waits for the network to answer, which can take a while; the
+//! progress of an operation is reported to the listener set with [DWalletClient::with_progress].
+
+use std::collections::BTreeMap;
+use std::future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail};
+use futures::TryStreamExt;
+use move_core_types::identifier::IdentStr;
+use move_core_types::language_storage::StructTag;
+use rand::rngs::OsRng;
+use serde::de::DeserializeOwned;
+use signature_mpc::twopc_mpc_protocols::{
+    initiate_centralized_party_dkg, initiate_centralized_party_presign,
+    initiate_centralized_party_sign, message_digest, Hash, PresignDecentralizedPartyOutput,
+    ProtocolContext, SecretKeyShareEncryptionAndProof,
+};
+use sui_json_rpc_types::{
+    ObjectChange, SuiExecutionStatus, SuiObjectDataFilter, SuiObjectDataOptions,
+    SuiObjectResponseQuery, SuiProtocolConfigValue, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponse,
+};
+use sui_keys::encryption::encrypt_share_to;
+use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use sui_types::crypto::default_hash;
+use sui_types::digests::TransactionDigest;
+use sui_types::external_address::{ExternalAddresses, ExternalChain};
+use sui_types::object::Owner;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::signature_mpc::{
+    DKGSessionOutput, DWallet, EncryptionKey, Presign, PresignSessionOutput, SignOutput,
+    SigningPolicy, SigningPolicyRules, APPROVALS_HOLDER_STRUCT_NAME, DKG_SESSION_STRUCT_NAME,
+    DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME, DWALLET_BINDING_MODULE_NAME, DWALLET_BINDING_STRUCT_NAME,
+    DWALLET_MODULE_NAME, DWALLET_POLICY_MODULE_NAME, DWALLET_STRUCT_NAME,
+    ENCRYPTED_USER_SHARE_STRUCT_NAME, ENCRYPTION_KEY_STRUCT_NAME, PRESIGN_SESSION_STRUCT_NAME,
+    SIGNING_POLICY_STRUCT_NAME, SIGN_SESSION_STRUCT_NAME,
+};
+use sui_types::transaction::{ObjectArg, Transaction, TransactionData, TransactionKind};
+use sui_types::SUI_SYSTEM_PACKAGE_ID;
+use tokio::time::{interval, sleep};
+use tracing::info;
+use zeroize::Zeroizing;
+
+use crate::apis::OwnedDWallet;
+use crate::dwallet_transaction_builder::DWalletTransactionBuilder;
+use crate::error::Error;
+use crate::offline::{read_signed_transaction, write_unsigned_transaction};
+use crate::sponsor::{sponsor_transaction, sponsored_transaction_data, GasSponsor};
+use crate::sui_client_config::{DWalletCurve, DWalletSecretShare};
+use crate::wallet_context::WalletContext;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The progress of a dWallet operation, see [DWalletClient::with_progress].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DWalletProgress {
+    /// A transaction of the operation was executed.
+    Executed { digest: TransactionDigest },
+    /// Waiting for the network to answer the round `round` of the protocol, e.g. `presign`, for
+    /// `elapsed` so far. The operation fails once `elapsed` reaches `timeout`.
+    ///
+    /// The network publishes the output of a round once the validators have computed it, not the
+    /// shares of the individual validators, so there is no finer progress within a round.
+    Waiting {
+        round: &'static str,
+        elapsed: Duration,
+        timeout: Duration,
+    },
+}
+
+pub type ProgressListener = Arc<dyn Fn(&DWalletProgress) + Send + Sync>;
+
+/// A sign request checked by [DWalletClient::dry_run_sign] without being made.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SignDryRun {
+    /// Why the request would fail, empty if it would not.
+    pub errors: Vec<String>,
+    /// What may not be intended, although the request would not fail.
+    pub warnings: Vec<String>,
+    /// The size of the messages as an argument of the request transactions, in bytes.
+    pub messages_size: usize,
+    /// The largest argument of a transaction accepted by the network, in bytes.
+    pub max_messages_size: Option<u64>,
+    /// The estimated gas budget of the transaction requesting the presign, the first of the
+    /// request. The transaction requesting the signatures depends on the answer of the network to
+    /// the presign, so its gas cannot be estimated beforehand.
+    pub presign_gas_budget: Option<u64>,
+    /// The presigns of the dWallet owned by the sender, left by sign requests that did not
+    /// complete. A sign request runs a presign of its own rather than using them.
+    pub unused_presigns: usize,
+}
+
+pub struct DWalletClient<'a> {
+    context: &'a WalletContext,
+    sender: SuiAddress,
+    gas: Option<ObjectID>,
+    gas_budget: Option<u64>,
+    timeout: Duration,
+    offline_dir: Option<PathBuf>,
+    sponsor: Option<Arc<dyn GasSponsor>>,
+    progress: Option<ProgressListener>,
+}
+
+impl<'a> DWalletClient<'a> {
+    /// A client sending the transactions of dWallet operations from `sender`, whose key must be in
+    /// the keystore of `context`. The gas budget of each transaction is estimated with
+    /// [crate::SuiClient::estimate_gas], as the cost of sign requests grows with their number of
+    /// messages.
+    pub fn new(context: &'a WalletContext, sender: SuiAddress) -> Self {
+        Self {
+            context,
+            sender,
+            gas: None,
+            gas_budget: None,
+            timeout: DEFAULT_TIMEOUT,
+            offline_dir: None,
+            sponsor: None,
+            progress: None,
+        }
+    }
+
+    /// Pay for the transactions with `gas` instead of a gas object selected for each of them. With
+    /// [Self::with_sponsor], `gas` must be owned by the sponsor.
+    pub fn with_gas(mut self, gas: ObjectID) -> Self {
+        self.gas = Some(gas);
+        self
+    }
+
+    /// Use a gas budget of `gas_budget` for every transaction, instead of estimating it.
+    pub fn with_gas_budget(mut self, gas_budget: u64) -> Self {
+        self.gas_budget = Some(gas_budget);
+        self
+    }
+
+    /// How long to wait for the network to answer each round of a protocol. A round timing out
+    /// fails with [Error::DWalletRoundTimeout], or [Error::SignSessionTimeout] for the signatures.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Report the progress of operations to `progress`: each executed transaction, and, while
+    /// waiting for the network, the round waited for every 500ms.
+    pub fn with_progress(mut self, progress: ProgressListener) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Sign the transactions on an air-gapped machine, rather than with the keystore of the
+    /// context. Each transaction is written, unsigned, to `<digest>.unsigned` in `dir`, and
+    /// submitted once its signed transaction, e.g. by [crate::offline::sign_transaction_file],
+    /// appears in `<digest>.signed`. The client waits for the signed transactions without a
+    /// timeout.
+    pub fn with_offline_signing(mut self, dir: PathBuf) -> Self {
+        self.offline_dir = Some(dir);
+        self
+    }
+
+    /// Have `sponsor` pay for the gas of the transactions, with gas coins it owns, so that the
+    /// sender needs no SUI. Each transaction is signed by the sender, then by the sponsor.
+    pub fn with_sponsor(mut self, sponsor: Arc<dyn GasSponsor>) -> Self {
+        self.sponsor = Some(sponsor);
+        self
+    }
+
+    pub async fn get_dwallet(&self, dwallet_id: ObjectID) -> anyhow::Result<DWallet> {
+        let response = self
+            .context
+            .get_client()
+            .await?
+            .read_api()
+            .get_object_with_options(dwallet_id, SuiObjectDataOptions::bcs_lossless())
+            .await?;
+        let data = response
+            .data
+            .ok_or_else(|| anyhow!("could not find dWallet {dwallet_id}"))?;
+        Ok(data
+            .bcs
+            .ok_or_else(|| anyhow!("fetched dWallet {dwallet_id} without its data"))?
+            .try_as_move()
+            .ok_or_else(|| anyhow!("dWallet {dwallet_id} is not a Move object"))?
+            .deserialize()?)
+    }
+
+    /// Create a dWallet with the sender as the owner of its capability, running the DKG protocol
+    /// with the network. The returned secret share, named `alias`, is needed to sign with the
+    /// dWallet and must be kept, e.g. in the client config. It lists the addresses of the dWallet
+    /// on the external chains it controls.
+    pub async fn create_dwallet(&self, alias: String) -> anyhow::Result<DWalletSecretShare> {
+        let (commitment, decommitment_round_party) = initiate_centralized_party_dkg()
+            .map_err(|e| anyhow!("cannot start the DKG: {e:?}"))?
+            .sample_commit_and_prove_secret_key_share(&mut OsRng)
+            .map_err(|e| anyhow!("cannot commit to the secret key share: {e:?}"))?;
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.create_dkg_session(&commitment)?;
+        let response = self.execute(builder).await?;
+        let (session_id, _) = created_object(
+            &response,
+            DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+            DKG_SESSION_STRUCT_NAME,
+        )?;
+
+        let (output, output_ref) = self
+            .wait_for_owned_object(
+                "DKG",
+                DKGSessionOutput::type_(),
+                |output: &DKGSessionOutput| output.session_id.bytes == session_id,
+            )
+            .await?;
+        let secret_key_share_encryption_and_proof =
+            bcs::from_bytes::<SecretKeyShareEncryptionAndProof<ProtocolContext>>(
+                &output.secret_key_share_encryption_and_proof,
+            )?;
+        let (decommitment_and_proof, dkg_output) = decommitment_round_party
+            .decommit_proof_public_key_share(secret_key_share_encryption_and_proof, &mut OsRng)
+            .map_err(|e| anyhow!("cannot decommit the public key share: {e:?}"))?;
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.create_dwallet(output_ref, &decommitment_and_proof)?;
+        let response = self.execute(builder).await?;
+        let (dwallet_id, _) = created_object(
+            &response,
+            DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+            DWALLET_STRUCT_NAME,
+        )?;
+        let external_addresses = self.get_dwallet(dwallet_id).await?.external_addresses()?;
+
+        Ok(DWalletSecretShare {
+            alias,
+            dkg_output: Some(dkg_output),
+            dwallet_id,
+            dwallet_cap_id: output.dwallet_cap_id.bytes,
+            curve: DWalletCurve::Secp256k1,
+            external_addresses: external_addresses_by_chain(&external_addresses),
+            encrypted_share_path: None,
+            keyring_service: None,
+            signing_policy_id: None,
+            session_retries: BTreeMap::new(),
+        })
+    }
+
+    /// Request the signature of `messages`, each hashed with `hash`, by `dwallet`, whose
+    /// capability the sender must own. Runs the presign protocol with the network, and returns the
+    /// sign session to pass to [Self::wait_for_signatures].
+    pub async fn request_sign(
+        &self,
+        dwallet: &DWalletSecretShare,
+        messages: Vec<Vec<u8>>,
+        hash: Hash,
+    ) -> anyhow::Result<ObjectID> {
+        self.sign_with_approvals(dwallet, messages, hash, None)
+            .await
+    }
+
+    /// Request again the signature of the messages of the sign session `session_id` of `dwallet`,
+    /// which the network did not sign, e.g. as it timed out or identified malicious parties. The
+    /// messages are signed with the hash function of the session and a fresh presign, and the new
+    /// sign session is returned to pass to [Self::wait_for_signatures].
+    pub async fn retry_sign(
+        &self,
+        dwallet: &DWalletSecretShare,
+        session_id: ObjectID,
+    ) -> anyhow::Result<ObjectID> {
+        let client = self.context.get_client().await?;
+        let dwallet_api = client.dwallet_api();
+        let session = dwallet_api
+            .get_sign_session(session_id)
+            .await?
+            .ok_or_else(|| anyhow!("there is no sign session {session_id}"))?;
+        if session.dwallet_id.bytes != dwallet.dwallet_id {
+            bail!(
+                "sign session {session_id} is of dWallet {}, not {}",
+                session.dwallet_id.bytes,
+                dwallet.dwallet_id
+            );
+        }
+        if let Some(output) = client.get_sign_output(session_id).await? {
+            bail!(
+                "the messages of sign session {session_id} were signed in {}",
+                output.id.object_id()
+            );
+        }
+        let hash = Hash::from(dwallet_api.get_sign_session_hash(session_id).await?);
+        self.request_sign(dwallet, session.messages, hash).await
+    }
+
+    /// Check the sign request of `messages` by `dwallet`, as made by [Self::request_sign], without
+    /// making it: no presign is consumed and no session is started.
+    ///
+    /// The user share of the dWallet must be held, the sender must own its capability, and the
+    /// messages must fit in a transaction argument. The transaction requesting the presign is
+    /// dev-inspected, which also estimates its gas.
+    pub async fn dry_run_sign(
+        &self,
+        dwallet: &DWalletSecretShare,
+        messages: &[Vec<u8>],
+        hash: Hash,
+    ) -> anyhow::Result<SignDryRun> {
+        let client = self.context.get_client().await?;
+        let mut dry_run = SignDryRun {
+            messages_size: bcs::serialized_size(messages)?,
+            ..Default::default()
+        };
+        if messages.is_empty() {
+            dry_run.errors.push("no messages to sign".to_string());
+        }
+        for (i, message) in messages.iter().enumerate() {
+            // Messages are hashed before they are signed, digests included.
+            if message.len() == 32 {
+                dry_run.warnings.push(format!(
+                    "message {i} is 32 bytes long, like a digest, and is hashed again before it is signed"
+                ));
+            }
+        }
+
+        dry_run.max_messages_size = client
+            .read_api()
+            .get_protocol_config(None)
+            .await?
+            .attributes
+            .get("max_pure_argument_size")
+            .cloned()
+            .flatten()
+            .and_then(|size| match size {
+                SuiProtocolConfigValue::U32(size) => Some(size.into()),
+                SuiProtocolConfigValue::U64(size) => Some(size),
+                _ => None,
+            });
+        if let Some(max_messages_size) = dry_run.max_messages_size {
+            if dry_run.messages_size as u64 > max_messages_size {
+                dry_run.errors.push(format!(
+                    "the messages take {} bytes, more than the {max_messages_size} bytes of a transaction argument",
+                    dry_run.messages_size
+                ));
+            }
+        }
+
+        if let Some(policy_id) = dwallet.signing_policy_id {
+            match client.dwallet_api().get_signing_policy(policy_id).await {
+                Ok((policy, _)) => dry_run.errors.extend(signing_policy_violations(
+                    &policy,
+                    self.sender,
+                    messages.len(),
+                )),
+                Err(e) => dry_run
+                    .errors
+                    .push(format!("cannot read the signing policy {policy_id}: {e}")),
+            }
+        } else {
+            let dwallet_cap = client
+                .read_api()
+                .get_object_with_options(
+                    dwallet.dwallet_cap_id,
+                    SuiObjectDataOptions::new().with_owner(),
+                )
+                .await?;
+            match dwallet_cap.owner() {
+                Some(Owner::AddressOwner(owner)) if owner == self.sender => {}
+                Some(owner) => dry_run.errors.push(format!(
+                    "the capability {} of the dWallet is owned by {owner}, not the sender {}",
+                    dwallet.dwallet_cap_id, self.sender
+                )),
+                None => dry_run.errors.push(format!(
+                    "no capability {} of the dWallet",
+                    dwallet.dwallet_cap_id
+                )),
+            }
+        }
+
+        dry_run.unused_presigns = client
+            .dwallet_api()
+            .get_owned_presigns_stream(self.sender)
+            .try_fold(0, |count, presign| {
+                future::ready(Ok(
+                    count + usize::from(presign.dwallet_id.bytes == dwallet.dwallet_id)
+                ))
+            })
+            .await?;
+
+        let dkg_output = match dwallet.dkg_output() {
+            Ok(dkg_output) => dkg_output,
+            Err(e) => {
+                dry_run.errors.push(e.to_string());
+                return Ok(dry_run);
+            }
+        };
+        if messages.is_empty() {
+            return Ok(dry_run);
+        }
+        let (nonce_shares_commitments_and_batched_proof, _) =
+            initiate_centralized_party_presign(dkg_output)
+                .map_err(|e| anyhow!("cannot start the presign: {e:?}"))?
+                .sample_commit_and_prove_signature_nonce_share(messages.len(), &mut OsRng)
+                .map_err(|e| anyhow!("cannot commit to the nonce shares: {e:?}"))?;
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.create_presign_session(
+            self.context.get_object_ref(dwallet.dwallet_id).await?,
+            messages,
+            &nonce_shares_commitments_and_batched_proof,
+            hash,
+        )?;
+        let gas_price = self.context.config.get_active_env()?.default_gas_price;
+        match client
+            .estimate_gas(
+                self.sender,
+                TransactionKind::programmable(builder.finish()),
+                gas_price,
+            )
+            .await
+        {
+            Ok(gas_budget) => dry_run.presign_gas_budget = Some(gas_budget),
+            Err(e) => dry_run
+                .errors
+                .push(format!("the presign request fails: {e}")),
+        }
+        Ok(dry_run)
+    }
+
+    /// Approve the signature of `messages` by `dwallet`, whose capability the sender must own,
+    /// ahead of time. The approvals are kept in an `ApprovalsHolder` owned by the sender, which
+    /// [Self::request_future_sign] later uses to sign the messages without the capability, e.g.
+    /// once the capability has been transferred to a multisig or a contract. Returns the ID of
+    /// the holder.
+    pub async fn approve_future_sign(
+        &self,
+        dwallet: &DWalletSecretShare,
+        messages: &[Vec<u8>],
+    ) -> anyhow::Result<ObjectID> {
+        if messages.is_empty() {
+            bail!("no messages to approve");
+        }
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let message_approvals = builder.approve_messages(
+            self.context.get_object_ref(dwallet.dwallet_cap_id).await?,
+            messages,
+        )?;
+        builder.create_approvals_holder(message_approvals);
+        let response = self.execute(builder).await?;
+        let (holder_id, _) =
+            created_object(&response, DWALLET_MODULE_NAME, APPROVALS_HOLDER_STRUCT_NAME)?;
+        Ok(holder_id)
+    }
+
+    /// Request the signature by `dwallet` of the messages approved in the `ApprovalsHolder`
+    /// `holder_id`, each hashed with `hash`, consuming the holder. Returns the sign session to
+    /// pass to [Self::wait_for_signatures].
+    pub async fn request_future_sign(
+        &self,
+        dwallet: &DWalletSecretShare,
+        holder_id: ObjectID,
+        hash: Hash,
+    ) -> anyhow::Result<ObjectID> {
+        let holder = self
+            .context
+            .get_client()
+            .await?
+            .dwallet_api()
+            .get_approvals_holder(holder_id)
+            .await?;
+        if let Some(approval) = holder
+            .message_approvals
+            .iter()
+            .find(|approval| approval.dwallet_cap_id.bytes != dwallet.dwallet_cap_id)
+        {
+            bail!(
+                "the approvals of {holder_id} are for the dWallet capability {}, not {}",
+                approval.dwallet_cap_id.bytes,
+                dwallet.dwallet_cap_id
+            );
+        }
+        self.sign_with_approvals(dwallet, holder.messages(), hash, Some(holder_id))
+            .await
+    }
+
+    /// Cancel the future-sign approvals kept in the `ApprovalsHolder` `holder_id`, destroying it.
+    pub async fn cancel_future_sign(
+        &self,
+        holder_id: ObjectID,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.cancel_approvals_holder(self.context.get_object_ref(holder_id).await?)?;
+        self.execute(builder).await
+    }
+
+    /// Put the message approvals of `dwallet` under a signing policy with `rules`, or replace the
+    /// rules of its policy if it has one. The capability of the dWallet, which the sender must own,
+    /// is then held by a shared `SigningPolicy` owned by the sender, and the messages of the
+    /// dWallet are only approved within its rules. Returns the ID of the policy, to record as the
+    /// `signing_policy_id` of `dwallet`.
+    pub async fn set_signing_policy(
+        &self,
+        dwallet: &DWalletSecretShare,
+        rules: &SigningPolicyRules,
+    ) -> anyhow::Result<(ObjectID, SuiTransactionBlockResponse)> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        if let Some(policy_id) = dwallet.signing_policy_id {
+            let (_, initial_shared_version) = self.get_signing_policy(policy_id).await?;
+            builder.update_signing_policy(policy_id, initial_shared_version, rules)?;
+            return Ok((policy_id, self.execute(builder).await?));
+        }
+        builder.create_signing_policy(
+            self.context.get_object_ref(dwallet.dwallet_cap_id).await?,
+            rules,
+        )?;
+        let response = self.execute(builder).await?;
+        let (policy_id, _) = created_object(
+            &response,
+            DWALLET_POLICY_MODULE_NAME,
+            SIGNING_POLICY_STRUCT_NAME,
+        )?;
+        Ok((policy_id, response))
+    }
+
+    /// Remove the signing policy of `dwallet`, which the sender must own, sending the capability
+    /// of the dWallet it holds back to the sender.
+    pub async fn remove_signing_policy(
+        &self,
+        dwallet: &DWalletSecretShare,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let policy_id = dwallet
+            .signing_policy_id
+            .ok_or_else(|| anyhow!("dWallet [{}] has no signing policy", dwallet.alias))?;
+        let (_, initial_shared_version) = self.get_signing_policy(policy_id).await?;
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let dwallet_cap = builder.remove_signing_policy(policy_id, initial_shared_version)?;
+        builder.transfer_arg(self.sender, dwallet_cap);
+        self.execute(builder).await
+    }
+
+    /// The `SigningPolicy` `policy_id`, with the version it was shared at.
+    pub async fn get_signing_policy(
+        &self,
+        policy_id: ObjectID,
+    ) -> anyhow::Result<(SigningPolicy, SequenceNumber)> {
+        Ok(self
+            .context
+            .get_client()
+            .await?
+            .dwallet_api()
+            .get_signing_policy(policy_id)
+            .await?)
+    }
+
+    /// Sign `messages` with `dwallet`, approving them with its capability, or its signing policy
+    /// if it has one, or, with `approvals_holder`, with the approvals kept in that
+    /// `ApprovalsHolder`.
+    async fn sign_with_approvals(
+        &self,
+        dwallet: &DWalletSecretShare,
+        messages: Vec<Vec<u8>>,
+        hash: Hash,
+        approvals_holder: Option<ObjectID>,
+    ) -> anyhow::Result<ObjectID> {
+        if messages.is_empty() {
+            bail!("no messages to sign");
+        }
+        let dkg_output = dwallet.dkg_output()?;
+        let dwallet_ref = self.context.get_object_ref(dwallet.dwallet_id).await?;
+
+        let (nonce_shares_commitments_and_batched_proof, proof_verification_round_party) =
+            initiate_centralized_party_presign(dkg_output.clone())
+                .map_err(|e| anyhow!("cannot start the presign: {e:?}"))?
+                .sample_commit_and_prove_signature_nonce_share(messages.len(), &mut OsRng)
+                .map_err(|e| anyhow!("cannot commit to the nonce shares: {e:?}"))?;
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.create_presign_session(
+            dwallet_ref,
+            &messages,
+            &nonce_shares_commitments_and_batched_proof,
+            hash.clone(),
+        )?;
+        let response = self.execute(builder).await?;
+        let (presign_session_id, presign_session_ref) = created_object(
+            &response,
+            DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+            PRESIGN_SESSION_STRUCT_NAME,
+        )?;
+
+        let (presign_output, presign_output_ref) = self
+            .wait_for_owned_object(
+                "presign",
+                PresignSessionOutput::type_(),
+                |output: &PresignSessionOutput| output.session_id.bytes == presign_session_id,
+            )
+            .await?;
+        let presign_output = bcs::from_bytes::<PresignDecentralizedPartyOutput<ProtocolContext>>(
+            &presign_output.output,
+        )?;
+        let presigns = proof_verification_round_party
+            .verify_presign_output(presign_output, &mut OsRng)
+            .map_err(|e| anyhow!("invalid presign output: {e:?}"))?;
+
+        let sign_round_parties = initiate_centralized_party_sign(dkg_output.clone(), presigns)
+            .map_err(|e| anyhow!("cannot start the sign: {e:?}"))?;
+        let public_nonce_encrypted_partial_signature_and_proofs = messages
+            .iter()
+            .zip(sign_round_parties)
+            .map(|(message, party)| {
+                party
+                    .evaluate_encrypted_partial_signature_prehash(
+                        message_digest(message, &hash),
+                        &mut OsRng,
+                    )
+                    .map(|(partial_signature_and_proof, _)| partial_signature_and_proof)
+                    .map_err(|e| anyhow!("cannot evaluate the partial signature: {e:?}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let (_, presign_ref) = self
+            .wait_for_owned_object("presign", Presign::type_(), |presign: &Presign| {
+                presign.session_id.bytes == presign_session_id
+            })
+            .await?;
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let message_approvals = match approvals_holder {
+            Some(holder_id) => {
+                builder.remove_approvals_holder(self.context.get_object_ref(holder_id).await?)?
+            }
+            None => match dwallet.signing_policy_id {
+                Some(policy_id) => {
+                    let (policy, initial_shared_version) =
+                        self.get_signing_policy(policy_id).await?;
+                    if let Some(violation) =
+                        signing_policy_violations(&policy, self.sender, messages.len()).first()
+                    {
+                        bail!("the signing policy {policy_id} forbids signing: {violation}");
+                    }
+                    builder.approve_messages_with_policy(
+                        policy_id,
+                        initial_shared_version,
+                        &messages,
+                    )?
+                }
+                None => builder.approve_messages(
+                    self.context.get_object_ref(dwallet.dwallet_cap_id).await?,
+                    &messages,
+                )?,
+            },
+        };
+        let sign_messages = builder.create_sign_messages(
+            dwallet_ref,
+            presign_session_ref,
+            presign_output_ref,
+            presign_ref,
+            &public_nonce_encrypted_partial_signature_and_proofs,
+        )?;
+        builder.sign_messages(sign_messages, message_approvals);
+        let response = self.execute(builder).await?;
+        let (sign_session_id, _) =
+            created_object(&response, DWALLET_MODULE_NAME, SIGN_SESSION_STRUCT_NAME)?;
+        Ok(sign_session_id)
+    }
+
+    /// Transfer the ownership of `dwallet`, whose capability the sender must own, to
+    /// `recipient`. The recipient needs the secret share of the dWallet to sign with it.
+    pub async fn transfer_dwallet(
+        &self,
+        dwallet: &DWalletSecretShare,
+        recipient: SuiAddress,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.transfer_dwallet_cap(
+            self.context.get_object_ref(dwallet.dwallet_cap_id).await?,
+            recipient,
+        )?;
+        self.execute(builder).await
+    }
+
+    /// Bind the dWallet `dwallet_id`, whose capability the sender must own, to its address
+    /// `external_address` on `chain`, with an immutable `DWalletBinding` for contracts to discover
+    /// which dWallet controls the address.
+    ///
+    /// The address is checked against the addresses derived from the public key of the dWallet
+    /// before it is bound, as only Ethereum addresses are checked on chain. Returns the ID of the
+    /// binding, with the name of the address, e.g. `bitcoin-mainnet`, and its checksummed
+    /// encoding as bound.
+    pub async fn bind_dwallet(
+        &self,
+        dwallet_id: ObjectID,
+        chain: &ExternalChain,
+        external_address: &str,
+    ) -> anyhow::Result<(ObjectID, String, String, SuiTransactionBlockResponse)> {
+        let dwallet = self.get_dwallet(dwallet_id).await?;
+        let public_key = dwallet
+            .public_key_sec1()
+            .map_err(|e| anyhow!("invalid public key of dWallet {dwallet_id}: {e}"))?;
+        let (chain, external_address) = chain
+            .find_address(&public_key, external_address)?
+            .ok_or_else(|| {
+                anyhow!("{external_address} is not an address of dWallet {dwallet_id} on {chain}")
+            })?;
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.create_dwallet_binding(
+            self.context.get_object_ref(dwallet_id).await?,
+            self.context
+                .get_object_ref(dwallet.dwallet_cap_id.bytes)
+                .await?,
+            &chain,
+            &external_address,
+        )?;
+        let response = self.execute(builder).await?;
+        let (binding_id, _) = created_object(
+            &response,
+            DWALLET_BINDING_MODULE_NAME,
+            DWALLET_BINDING_STRUCT_NAME,
+        )?;
+        Ok((binding_id, chain, external_address, response))
+    }
+
+    /// Publish `encryption_key`, the public key of the [sui_keys::encryption::ShareTransferKey] of
+    /// the sender, for the dWallets transferred to the sender to come with their user share.
+    /// Returns the ID of the published `EncryptionKey`.
+    pub async fn publish_encryption_key(&self, encryption_key: &[u8]) -> anyhow::Result<ObjectID> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.publish_encryption_key(encryption_key)?;
+        let response = self.execute(builder).await?;
+        let (encryption_key_id, _) = created_object(
+            &response,
+            DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+            ENCRYPTION_KEY_STRUCT_NAME,
+        )?;
+        Ok(encryption_key_id)
+    }
+
+    /// Transfer the ownership of `dwallet`, whose capability the sender must own, to `recipient`
+    /// along with its user share, encrypted to `encryption_key`, the key published by the
+    /// recipient, in one transaction.
+    ///
+    /// The encrypted share is read back once the transaction is executed, and the transfer fails
+    /// if it is not owned by the recipient, or is not the share that was sent. Returns the ID of
+    /// the `EncryptedUserShare`, with the response of the transaction.
+    pub async fn transfer_dwallet_with_share(
+        &self,
+        dwallet: &DWalletSecretShare,
+        recipient: SuiAddress,
+        encryption_key: &EncryptionKey,
+    ) -> anyhow::Result<(ObjectID, SuiTransactionBlockResponse)> {
+        let share = Zeroizing::new(serde_json::to_vec(&dwallet.dkg_output()?)?);
+        let encrypted_user_share = encrypt_share_to(
+            &encryption_key.encryption_key,
+            &share,
+            dwallet.dwallet_id.as_ref(),
+        )?;
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let dwallet_cap = builder.obj(ObjectArg::ImmOrOwnedObject(
+            self.context.get_object_ref(dwallet.dwallet_cap_id).await?,
+        ))?;
+        builder.send_encrypted_user_share(
+            self.context.get_object_ref(dwallet.dwallet_id).await?,
+            dwallet_cap,
+            *encryption_key.id(),
+            &encrypted_user_share,
+            recipient,
+        )?;
+        builder.transfer_arg(recipient, dwallet_cap);
+        let response = self.execute(builder).await?;
+        let (share_id, _) = created_object(
+            &response,
+            DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+            ENCRYPTED_USER_SHARE_STRUCT_NAME,
+        )?;
+
+        let (sent, owner) = self
+            .context
+            .get_client()
+            .await?
+            .dwallet_api()
+            .get_encrypted_user_share(share_id)
+            .await?;
+        if owner != Owner::AddressOwner(recipient)
+            || sent.encryption_key_id.bytes != *encryption_key.id()
+            || sent.encrypted_user_share != encrypted_user_share
+        {
+            bail!("the encrypted user share {share_id} is not the one sent to {recipient}");
+        }
+        Ok((share_id, response))
+    }
+
+    /// Transfer the ownership of all the dWallets of the sender to `recipient` in one
+    /// transaction, e.g. after rotating the key of the sender. Returns the transferred dWallets,
+    /// with the response of the transaction if there were any.
+    pub async fn transfer_all_dwallets(
+        &self,
+        recipient: SuiAddress,
+    ) -> anyhow::Result<(Vec<OwnedDWallet>, Option<SuiTransactionBlockResponse>)> {
+        let dwallets: Vec<_> = self
+            .context
+            .get_client()
+            .await?
+            .dwallet_api()
+            .get_owned_dwallets_stream(self.sender)
+            .try_collect()
+            .await?;
+        if dwallets.is_empty() {
+            return Ok((dwallets, None));
+        }
+        let mut builder = ProgrammableTransactionBuilder::new();
+        for dwallet in &dwallets {
+            builder.transfer_dwallet_cap(dwallet.dwallet_cap, recipient)?;
+        }
+        let response = self.execute(builder).await?;
+        Ok((dwallets, Some(response)))
+    }
+
+    /// Wait for the network to sign the messages of the sign session `session_id`, see
+    /// [crate::SuiClient::wait_for_sign_completion].
+    pub async fn wait_for_signatures(&self, session_id: ObjectID) -> anyhow::Result<SignOutput> {
+        let client = self.context.get_client().await?;
+        let signatures = client.wait_for_sign_completion(session_id, self.timeout);
+        tokio::pin!(signatures);
+        let start = Instant::now();
+        let mut ticks = interval(POLL_INTERVAL);
+        loop {
+            tokio::select! {
+                output = &mut signatures => return Ok(output?),
+                _ = ticks.tick() => self.report_waiting("sign", start),
+            }
+        }
+    }
+
+    async fn execute(
+        &self,
+        builder: ProgrammableTransactionBuilder,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let client = self.context.get_client().await?;
+        let gas_budget = match self.gas_budget {
+            Some(gas_budget) => gas_budget,
+            None => {
+                let tx = TransactionKind::programmable(builder.clone().finish());
+                let gas_price = self.context.config.get_active_env()?.default_gas_price;
+                client.estimate_gas(self.sender, tx, gas_price).await?
+            }
+        };
+        let tx_data: TransactionData = match &self.sponsor {
+            Some(sponsor) => {
+                sponsored_transaction_data(
+                    &client,
+                    TransactionKind::programmable(builder.finish()),
+                    self.sender,
+                    sponsor.address(),
+                    self.gas,
+                    gas_budget,
+                    client.read_api().get_reference_gas_price().await?,
+                )
+                .await?
+            }
+            None => {
+                client
+                    .transaction_builder()
+                    .finish_programmable_transaction(self.sender, builder, self.gas, gas_budget)
+                    .await?
+            }
+        };
+        let tx_data = self.context.with_default_gas_price(tx_data)?;
+        let transaction = match &self.offline_dir {
+            Some(dir) => self.sign_offline(dir, tx_data).await?,
+            None => self.context.try_sign_transaction(&tx_data).await?,
+        };
+        let transaction = match &self.sponsor {
+            Some(sponsor) => sponsor_transaction(sponsor.as_ref(), transaction).await?,
+            None => transaction,
+        };
+        let response = self
+            .context
+            .execute_transaction_may_fail(transaction)
+            .await?;
+        let effects = response
+            .effects
+            .as_ref()
+            .ok_or_else(|| anyhow!("no effects in the response of {}", response.digest))?;
+        if let SuiExecutionStatus::Failure { error } = effects.status() {
+            bail!("transaction {} failed: {error}", response.digest);
+        }
+        self.report(DWalletProgress::Executed {
+            digest: response.digest,
+        });
+        Ok(response)
+    }
+
+    fn report(&self, progress: DWalletProgress) {
+        if let Some(listener) = &self.progress {
+            listener(&progress);
+        }
+    }
+
+    fn report_waiting(&self, round: &'static str, start: Instant) {
+        self.report(DWalletProgress::Waiting {
+            round,
+            elapsed: start.elapsed(),
+            timeout: self.timeout,
+        });
+    }
+
+    /// Write `tx_data` to `dir`, and wait for its signed transaction to be written next to it.
+    async fn sign_offline(
+        &self,
+        dir: &Path,
+        tx_data: TransactionData,
+    ) -> anyhow::Result<Transaction> {
+        let digest = TransactionDigest::new(default_hash(&tx_data));
+        let unsigned_path = dir.join(format!("{digest}.unsigned"));
+        let signed_path = dir.join(format!("{digest}.signed"));
+        write_unsigned_transaction(&unsigned_path, &tx_data)?;
+        info!(
+            "Waiting for the transaction {} to be signed into {}",
+            unsigned_path.display(),
+            signed_path.display()
+        );
+        while !signed_path.exists() {
+            sleep(POLL_INTERVAL).await;
+        }
+        let transaction = read_signed_transaction(&signed_path)?;
+        if transaction.transaction_data() != &tx_data {
+            bail!(
+                "{} does not hold the signed transaction of {}",
+                signed_path.display(),
+                unsigned_path.display()
+            );
+        }
+        Ok(transaction)
+    }
+
+    /// Wait for an object of type `type_` matching `matches`, the answer of the network to the
+    /// round `round` of a protocol, to be sent to the sender.
+    async fn wait_for_owned_object<T: DeserializeOwned>(
+        &self,
+        round: &'static str,
+        type_: StructTag,
+        matches: impl Fn(&T) -> bool,
+    ) -> anyhow::Result<(T, ObjectRef)> {
+        let start = Instant::now();
+        let client = self.context.get_client().await?;
+        loop {
+            let mut cursor = None;
+            loop {
+                let response = client
+                    .read_api()
+                    .get_owned_objects(
+                        self.sender,
+                        Some(SuiObjectResponseQuery::new(
+                            Some(SuiObjectDataFilter::StructType(type_.clone())),
+                            Some(SuiObjectDataOptions::bcs_lossless()),
+                        )),
+                        cursor,
+                        None,
+                    )
+                    .await?;
+                for object in &response.data {
+                    let value: T =
+                        bcs::from_bytes(object.move_object_bcs().ok_or_else(|| {
+                            anyhow!("fetched a {type_} object without its data")
+                        })?)?;
+                    if matches(&value) {
+                        return Ok((
+                            value,
+                            object.object_ref_if_exists().ok_or_else(|| {
+                                anyhow!("fetched a {type_} object without its reference")
+                            })?,
+                        ));
+                    }
+                }
+                if !response.has_next_page {
+                    break;
+                }
+                cursor = response.next_cursor;
+            }
+
+            if start.elapsed() >= self.timeout {
+                return Err(Error::DWalletRoundTimeout {
+                    round: round.to_string(),
+                    timeout: self.timeout,
+                }
+                .into());
+            }
+            self.report_waiting(round, start);
+            sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// The ID and reference of the object of type `module::name` created by the transaction of
+/// `response`.
+fn created_object(
+    response: &SuiTransactionBlockResponse,
+    module: &IdentStr,
+    name: &IdentStr,
+) -> anyhow::Result<(ObjectID, ObjectRef)> {
+    response
+        .object_changes
+        .iter()
+        .flatten()
+        .find_map(|change| match change {
+            ObjectChange::Created { object_type, .. }
+                if object_type.address == SUI_SYSTEM_PACKAGE_ID.into()
+                    && object_type.module.as_ident_str() == module
+                    && object_type.name.as_ident_str() == name =>
+            {
+                Some((change.object_id(), change.object_ref()))
+            }
+            _ => None,
+        })
+        .ok_or_else(|| {
+            anyhow!(
+                "transaction {} created no {module}::{name}",
+                response.digest
+            )
+        })
+}
+
+/// Why `sender` cannot have `messages` messages approved under `policy`, empty if it can, as far
+/// as can be told off-chain: the messages already approved in the current window of the rate limit
+/// are only counted on-chain.
+fn signing_policy_violations(
+    policy: &SigningPolicy,
+    sender: SuiAddress,
+    messages: usize,
+) -> Vec<String> {
+    let mut violations = vec![];
+    if !policy.is_allowed(&sender) {
+        violations.push(format!(
+            "the sender {sender} is neither the owner of the policy nor in its allowlist"
+        ));
+    }
+    if policy.max_messages != 0 && messages as u64 > policy.max_messages {
+        violations.push(format!(
+            "{messages} messages exceed the limit of {} messages per {} ms",
+            policy.max_messages, policy.window_ms
+        ));
+    }
+    if policy.co_approval_threshold != 0 {
+        violations.push(format!(
+            "the messages must be co-approved by {} of {} co-approvers",
+            policy.co_approval_threshold,
+            policy.co_approvers.len()
+        ));
+    }
+    violations
+}
+
+/// The external addresses of a dWallet as listed in the client config: `ethereum`, `cosmos`, and
+/// `bitcoin-<network>` for each Bitcoin network.
+pub fn external_addresses_by_chain(addresses: &ExternalAddresses) -> BTreeMap<String, String> {
+    let mut by_chain = BTreeMap::from([
+        ("ethereum".to_string(), addresses.ethereum.clone()),
+        ("cosmos".to_string(), addresses.cosmos.clone()),
+    ]);
+    for bitcoin in &addresses.bitcoin {
+        by_chain.insert(
+            format!("bitcoin-{}", bitcoin.network),
+            bitcoin.p2wpkh.clone(),
+        );
+    }
+    by_chain
+}
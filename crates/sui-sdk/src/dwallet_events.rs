@@ -0,0 +1,365 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Subscriptions to the lifecycle events of dWallets.
+//!
+//! Rather than polling for the objects the network creates, such as the output of a sign session,
+//! an application can subscribe to the events of a dWallet over the WebSocket endpoint of a
+//! fullnode with [SuiClient::subscribe_dwallet_events], or wait for the signatures of a sign
+//! session with [SuiClient::wait_for_sign_completion].
+//!
+//! The events of dWallets queried or subscribed to as [SuiEvent]s convert to the typed events
+//! re-exported here with `TryFrom`, rather than by reading the fields of their JSON by name:
+//!
+//! ```rust,no_run
+//! use sui_sdk::dwallet_events::{DWalletEvent, SignOutputCreatedEvent};
+//! use sui_sdk::rpc_types::SuiEvent;
+//!
+//! fn signatures(event: SuiEvent) -> anyhow::Result<Vec<Vec<u8>>> {
+//!     Ok(SignOutputCreatedEvent::try_from(event)?.signatures)
+//! }
+//!
+//! fn dwallet_of(event: SuiEvent) -> anyhow::Result<String> {
+//!     Ok(DWalletEvent::try_from(event)?.dwallet_id().to_string())
+//! }
+//! ```
+
+use std::future;
+use std::time::Duration;
+
+use backoff::future::retry;
+use backoff::ExponentialBackoff;
+use futures::{stream, StreamExt};
+use futures_core::Stream;
+use jsonrpsee::core::client::Subscription;
+use jsonrpsee::ws_client::WsClient;
+use move_core_types::language_storage::StructTag;
+use serde_json::json;
+use sui_json_rpc_api::IndexerApiClient;
+use sui_json_rpc_types::{
+    EventFilter, SuiEvent, SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponseQuery,
+};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::id::UID;
+use sui_types::signature_mpc::{
+    SignOutput, DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME, DWALLET_CREATED_EVENT_STRUCT_NAME,
+    DWALLET_MODULE_NAME, PRESIGN_CREATED_EVENT_STRUCT_NAME,
+    PRESIGN_SESSION_CREATED_EVENT_STRUCT_NAME, SIGN_OUTPUT_CREATED_EVENT_STRUCT_NAME,
+    SIGN_SESSION_CREATED_EVENT_STRUCT_NAME,
+};
+use sui_types::SUI_SYSTEM_ADDRESS;
+use tokio::time::interval;
+use tracing::warn;
+
+pub use sui_types::signature_mpc::{
+    DWalletCreatedEvent, DWalletEvent, PresignCreatedEvent, PresignSessionCreatedEvent,
+    SignOutputCreatedEvent, SignSessionCreatedEvent,
+};
+
+use crate::error::{Error, SuiRpcResult};
+use crate::{SuiClient, WsConfig};
+
+const SIGN_OUTPUT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The event filter matching the events of the dWallet `dwallet_id`.
+///
+/// The filter is built of the generic filters rather than [EventFilter::DWallet], which nodes
+/// older than this SDK fail to deserialize.
+pub fn dwallet_event_filter(dwallet_id: ObjectID) -> EventFilter {
+    let event_types = [
+        (
+            DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+            DWALLET_CREATED_EVENT_STRUCT_NAME,
+        ),
+        (
+            DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+            PRESIGN_SESSION_CREATED_EVENT_STRUCT_NAME,
+        ),
+        (
+            DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+            PRESIGN_CREATED_EVENT_STRUCT_NAME,
+        ),
+        (DWALLET_MODULE_NAME, SIGN_SESSION_CREATED_EVENT_STRUCT_NAME),
+        (DWALLET_MODULE_NAME, SIGN_OUTPUT_CREATED_EVENT_STRUCT_NAME),
+    ]
+    .into_iter()
+    .map(|(module, name)| {
+        EventFilter::MoveEventType(StructTag {
+            address: SUI_SYSTEM_ADDRESS,
+            module: module.to_owned(),
+            name: name.to_owned(),
+            type_params: vec![],
+        })
+    })
+    .collect();
+    EventFilter::And(
+        Box::new(EventFilter::Any(event_types)),
+        Box::new(EventFilter::MoveEventField {
+            path: "/dwallet_id".to_string(),
+            value: json!(dwallet_id.to_string()),
+        }),
+    )
+}
+
+struct DWalletEventSubscription {
+    config: WsConfig,
+    filter: EventFilter,
+    /// `None` while reconnecting.
+    connection: Option<(WsClient, Subscription<SuiEvent>)>,
+}
+
+impl SuiClient {
+    /// Subscribe to the lifecycle events of the dWallet `dwallet_id`: its creation, and the
+    /// presign and sign sessions of its owner, including the signatures of sign sessions.
+    ///
+    /// The subscription has its own WebSocket connection, which is reconnected, and the
+    /// subscription renewed, with exponential backoff when it drops. Events emitted while
+    /// reconnecting are missed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use std::str::FromStr;
+    /// use sui_sdk::dwallet_events::DWalletEvent;
+    /// use sui_sdk::types::base_types::ObjectID;
+    /// use sui_sdk::SuiClientBuilder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), anyhow::Error> {
+    ///     let sui = SuiClientBuilder::default()
+    ///         .ws_url("ws://127.0.0.1:9000")
+    ///         .build("http://127.0.0.1:9000")
+    ///         .await?;
+    ///     let dwallet_id = ObjectID::from_str("0x0000....0000")?;
+    ///     let mut events = sui.subscribe_dwallet_events(dwallet_id).await?;
+    ///     while let Some(event) = events.next().await {
+    ///         if let DWalletEvent::SignOutputCreated(output) = event? {
+    ///             println!("{:?}", output.signatures);
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn subscribe_dwallet_events(
+        &self,
+        dwallet_id: ObjectID,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<DWalletEvent>>> {
+        let Some(config) = self.api.ws_config.clone() else {
+            return Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            ));
+        };
+        let filter = dwallet_event_filter(dwallet_id);
+        // The first connection is not retried, for misconfigurations to surface to the caller.
+        let connection = subscribe(&config, &filter).await?;
+        let subscription = DWalletEventSubscription {
+            config,
+            filter,
+            connection: Some(connection),
+        };
+
+        Ok(stream::unfold(subscription, next_event))
+    }
+
+    /// Wait up to `timeout` for the network to sign the messages of the sign session
+    /// `session_id`, returning its output with one signature per message.
+    ///
+    /// The output is awaited through a subscription to the events of the dWallet of the session
+    /// if the client has a WebSocket endpoint, and by polling the objects of the sender of the
+    /// session in any case, in case events are missed. Fails with [Error::SignSessionTimeout]
+    /// after `timeout`, and with [Error::SignSessionFailed] if there is no such session or its
+    /// output does not sign all its messages.
+    ///
+    /// Dropping the returned future cancels the wait without side effects, so that it can be
+    /// raced against other futures, e.g. in `tokio::select!`.
+    pub async fn wait_for_sign_completion(
+        &self,
+        session_id: ObjectID,
+        timeout: Duration,
+    ) -> SuiRpcResult<SignOutput> {
+        tokio::time::timeout(timeout, self.wait_for_sign_output(session_id))
+            .await
+            .unwrap_or_else(|_| {
+                Err(Error::SignSessionTimeout {
+                    session_id,
+                    timeout,
+                })
+            })
+    }
+
+    /// The output of the sign session `session_id`, with one signature per message, or `None` if
+    /// the network has not signed its messages yet. Fails if there is no such session.
+    pub async fn get_sign_output(&self, session_id: ObjectID) -> SuiRpcResult<Option<SignOutput>> {
+        let session = self
+            .dwallet_api()
+            .get_sign_session(session_id)
+            .await?
+            .ok_or_else(|| Error::SignSessionFailed {
+                session_id,
+                reason: "no such sign session".to_string(),
+            })?;
+        self.find_sign_output(session.sender, session_id).await
+    }
+
+    async fn wait_for_sign_output(&self, session_id: ObjectID) -> SuiRpcResult<SignOutput> {
+        let session = self
+            .dwallet_api()
+            .get_sign_session(session_id)
+            .await?
+            .ok_or_else(|| Error::SignSessionFailed {
+                session_id,
+                reason: "no such sign session".to_string(),
+            })?;
+
+        // Subscribe before the first poll, for an output created in between not to be missed.
+        let events = match self.api.ws_config {
+            Some(_) => self
+                .subscribe_dwallet_events(session.dwallet_id.bytes)
+                .await
+                .map_err(|e| {
+                    warn!(
+                        "Cannot subscribe to the events of sign session {session_id}, polling: {e}"
+                    )
+                })
+                .ok(),
+            None => None,
+        };
+        let mut outputs = match events {
+            Some(events) => events
+                .filter_map(move |event| {
+                    future::ready(match event {
+                        Ok(DWalletEvent::SignOutputCreated(output))
+                            if output.session_id.bytes == session_id =>
+                        {
+                            Some(sign_output(output))
+                        }
+                        _ => None,
+                    })
+                })
+                .boxed(),
+            None => stream::pending().boxed(),
+        };
+        let mut poll = interval(SIGN_OUTPUT_POLL_INTERVAL);
+
+        let output = loop {
+            tokio::select! {
+                Some(output) = outputs.next() => break output,
+                _ = poll.tick() => {
+                    if let Some(output) = self.find_sign_output(session.sender, session_id).await? {
+                        break output;
+                    }
+                }
+            }
+        };
+        if output.signatures.len() != session.messages.len() {
+            return Err(Error::SignSessionFailed {
+                session_id,
+                reason: format!(
+                    "{} signatures for {} messages",
+                    output.signatures.len(),
+                    session.messages.len()
+                ),
+            });
+        }
+        Ok(output)
+    }
+
+    /// The output of the sign session `session_id`, if it was sent to `sender`.
+    async fn find_sign_output(
+        &self,
+        sender: SuiAddress,
+        session_id: ObjectID,
+    ) -> SuiRpcResult<Option<SignOutput>> {
+        let query = SuiObjectResponseQuery::new(
+            Some(SuiObjectDataFilter::StructType(SignOutput::type_())),
+            Some(SuiObjectDataOptions::bcs_lossless()),
+        );
+        let mut cursor = None;
+        loop {
+            let page = self
+                .read_api()
+                .get_owned_objects(sender, Some(query.clone()), cursor, None)
+                .await?;
+            for object in &page.data {
+                let output =
+                    SignOutput::from_bcs_bytes(object.move_object_bcs().ok_or_else(|| {
+                        Error::DataError("Fetched a SignOutput object without its data".to_string())
+                    })?)?;
+                if output.session_id.bytes == session_id {
+                    return Ok(Some(output));
+                }
+            }
+            if !page.has_next_page {
+                return Ok(None);
+            }
+            cursor = page.next_cursor;
+        }
+    }
+}
+
+fn sign_output(event: SignOutputCreatedEvent) -> SignOutput {
+    SignOutput {
+        id: UID::new(event.sign_output_id.bytes),
+        session_id: event.session_id,
+        dwallet_id: event.dwallet_id,
+        dwallet_cap_id: event.dwallet_cap_id,
+        signatures: event.signatures,
+        sender: event.sender,
+    }
+}
+
+async fn next_event(
+    mut subscription: DWalletEventSubscription,
+) -> Option<(SuiRpcResult<DWalletEvent>, DWalletEventSubscription)> {
+    loop {
+        if subscription.connection.is_none() {
+            subscription.connection =
+                Some(resubscribe(&subscription.config, &subscription.filter).await);
+        }
+        let (_, events) = subscription.connection.as_mut().expect("connected above");
+        match events.next().await {
+            Some(Ok(event)) => {
+                if let Some(event) = DWalletEvent::try_from_event(&event.type_, &event.bcs) {
+                    return Some((event.map_err(Error::from), subscription));
+                }
+            }
+            Some(Err(e)) => {
+                warn!("dWallet event subscription failed, reconnecting: {e}");
+                subscription.connection = None;
+            }
+            None => {
+                warn!("dWallet event subscription closed, reconnecting");
+                subscription.connection = None;
+            }
+        }
+    }
+}
+
+async fn subscribe(
+    config: &WsConfig,
+    filter: &EventFilter,
+) -> SuiRpcResult<(WsClient, Subscription<SuiEvent>)> {
+    let client = config.connect().await?;
+    let subscription = client.subscribe_event(filter.clone()).await?;
+    Ok((client, subscription))
+}
+
+/// Reconnect and subscribe again, until it succeeds.
+async fn resubscribe(
+    config: &WsConfig,
+    filter: &EventFilter,
+) -> (WsClient, Subscription<SuiEvent>) {
+    let backoff = ExponentialBackoff {
+        max_elapsed_time: None,
+        ..ExponentialBackoff::default()
+    };
+    retry(backoff, || async {
+        subscribe(config, filter).await.map_err(|e| {
+            warn!("Cannot resubscribe to dWallet events: {e}");
+            backoff::Error::transient(e)
+        })
+    })
+    .await
+    .expect("retried until it succeeds")
+}
@@ -0,0 +1,43 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Messages signed by dWallets on behalf of external consumers, wrapped in the canonical intent
+//! envelope of [sui_types::dwallet_intent].
+//!
+//! The signer wraps the application message in a [DWalletIntent] for the chain of the consumer and
+//! signs its encoding; the consumer decodes the intent, checks its signature, verifies it against
+//! the application message with [DWalletIntent::verify], and records its nonce.
+
+pub use sui_types::dwallet_intent::{DWalletIntent, DWalletIntentError, INTENT_DOMAIN};
+
+use crate::dwallet_signer::{sign_and_verify, verify_signature, DWalletSigner, Hash};
+use crate::error::{Error, SuiRpcResult};
+
+/// Sign the encoding of `intent` with the dWallet of `signer`, hashing it with `hash`.
+/// Returns the 64-byte low-S `r || s` signature.
+pub async fn sign_intent(
+    intent: &DWalletIntent,
+    hash: Hash,
+    signer: &impl DWalletSigner,
+) -> SuiRpcResult<Vec<u8>> {
+    sign_and_verify(intent.encode(), hash, signer).await
+}
+
+/// Decode the signed intent `encoded`, and verify its signature by the dWallet with the compressed
+/// SEC1 public key `public_key` and that it is an unexpired intent for `payload` on `chain`.
+pub fn verify_signed_intent(
+    public_key: &[u8],
+    encoded: &[u8],
+    hash: Hash,
+    signature: &[u8],
+    chain: &str,
+    payload: &[u8],
+    now_ms: u64,
+) -> SuiRpcResult<DWalletIntent> {
+    let intent = DWalletIntent::decode(encoded).map_err(|e| Error::DataError(e.to_string()))?;
+    verify_signature(public_key, encoded, hash, signature)?;
+    intent
+        .verify(chain, payload, now_ms)
+        .map_err(|e| Error::DataError(e.to_string()))?;
+    Ok(intent)
+}
@@ -0,0 +1,105 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use async_trait::async_trait;
+use bitcoin::hashes::{sha256, Hash as _};
+use bitcoin::secp256k1::{ecdsa, Message, PublicKey, Secp256k1};
+use fastcrypto::hash::{HashFunction, Keccak256};
+pub use signature_mpc::twopc_mpc_protocols::Hash;
+use sui_types::signature_mpc::DWallet;
+
+use crate::error::{Error, SuiRpcResult};
+
+/// A source of ECDSA secp256k1 signatures produced by a dWallet.
+///
+/// The chain specific helpers of the SDK (e.g. [crate::btc]) compute what needs to be signed,
+/// and rely on an implementation of this trait to run the sign protocol with the network.
+#[async_trait]
+pub trait DWalletSigner: Send + Sync {
+    /// The compressed SEC1 encoding of the dWallet's public key.
+    fn public_key(&self) -> Vec<u8>;
+
+    /// Sign `messages`, each of which is hashed by the network with `hash` before signing.
+    /// Returns one 64-byte `r || s` signature per message, in order.
+    async fn sign(&self, messages: Vec<Vec<u8>>, hash: Hash) -> SuiRpcResult<Vec<Vec<u8>>>;
+}
+
+/// A source of Ed25519 signatures produced by a dWallet, used by [crate::sol] and
+/// [crate::foreign_sui].
+///
+/// The network only runs the ECDSA secp256k1 protocol for now, so no Ed25519 dWallet is available
+/// to implement this trait yet.
+#[async_trait]
+pub trait Ed25519DWalletSigner: Send + Sync {
+    /// The 32-byte Ed25519 public key of the dWallet.
+    fn public_key(&self) -> [u8; 32];
+
+    /// Sign `messages` as they are, returning one 64-byte signature per message, in order.
+    async fn sign(&self, messages: Vec<Vec<u8>>) -> SuiRpcResult<Vec<Vec<u8>>>;
+}
+
+/// Sign `message` with the dWallet of `signer`, hashing it with `hash`, and check the signature.
+/// Returns the 64-byte low-S `r || s` signature.
+pub async fn sign_and_verify(
+    message: Vec<u8>,
+    hash: Hash,
+    signer: &impl DWalletSigner,
+) -> SuiRpcResult<Vec<u8>> {
+    let signature = signer
+        .sign(vec![message.clone()], hash.clone())
+        .await?
+        .pop()
+        .ok_or_else(|| Error::DataError("no signature returned by the dWallet".to_string()))?;
+
+    let mut signature = ecdsa::Signature::from_compact(&signature)
+        .map_err(|e| Error::DataError(format!("invalid dWallet signature: {e}")))?;
+    signature.normalize_s();
+    let signature = signature.serialize_compact().to_vec();
+    verify_signature(&signer.public_key(), &message, hash, &signature)?;
+    Ok(signature)
+}
+
+/// Verify that `signature` is the signature of `message`, hashed with `hash`, by the dWallet with
+/// the compressed SEC1 public key `public_key`.
+pub fn verify_signature(
+    public_key: &[u8],
+    message: &[u8],
+    hash: Hash,
+    signature: &[u8],
+) -> SuiRpcResult<()> {
+    let public_key = PublicKey::from_slice(public_key)
+        .map_err(|e| Error::DataError(format!("invalid dWallet public key: {e}")))?;
+    let mut signature = ecdsa::Signature::from_compact(signature)
+        .map_err(|e| Error::DataError(format!("invalid dWallet signature: {e}")))?;
+    signature.normalize_s();
+
+    let digest = match hash {
+        Hash::KECCAK256 => Keccak256::digest(message).digest,
+        Hash::SHA256 => sha256::Hash::hash(message).to_byte_array(),
+    };
+    let message = Message::from_slice(&digest).expect("digest is 32 bytes");
+    Secp256k1::verification_only()
+        .verify_ecdsa(&message, &signature, &public_key)
+        .map_err(|e| Error::DataError(format!("dWallet signature does not verify: {e}")))
+}
+
+/// Verify that `signature` is the signature of `message`, hashed with `hash`, by `dwallet`, e.g.
+/// one of the signatures of a `SignOutput` returned by a fullnode, before acting on it.
+///
+/// The signature is checked against the public key of `dwallet` as it was fetched: an
+/// application that does not trust its fullnode should also check that it is the public key it
+/// expects, e.g. that it derives the addresses of the dWallet it knows.
+pub fn verify_signature_for_dwallet(
+    dwallet: &DWallet,
+    message: &[u8],
+    hash: Hash,
+    signature: &[u8],
+) -> SuiRpcResult<()> {
+    let public_key = dwallet.public_key_sec1().map_err(|e| {
+        Error::DataError(format!(
+            "invalid public key of dWallet {}: {e}",
+            dwallet.id.object_id()
+        ))
+    })?;
+    verify_signature(&public_key, message, hash, signature)
+}
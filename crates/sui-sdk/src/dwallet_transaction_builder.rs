@@ -0,0 +1,508 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! The Move calls of dWallet operations, as commands of programmable transactions.
+//!
+//! [DWalletTransactionBuilder] extends [ProgrammableTransactionBuilder] with one method per entry
+//! point of the dWallet modules of the system package, taking care of their package, module and
+//! function names, type arguments, and the encoding of their arguments. The messages of the
+//! 2PC-MPC protocol are passed as they are produced by the centralized party, and BCS encoded into
+//! the `vector<u8>` arguments the Move functions expect.
+
+use move_core_types::identifier::IdentStr;
+use move_core_types::language_storage::TypeTag;
+use serde::Serialize;
+use signature_mpc::twopc_mpc_protocols::Hash;
+use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::signature_mpc::{
+    SignData, SigningPolicyRules, APPROVE_ATTESTATIONS_FUNC_NAME, APPROVE_MESSAGES_FUNC_NAME,
+    CANCEL_APPROVALS_HOLDER_FUNC_NAME, CREATE_APPROVALS_HOLDER_FUNC_NAME, CREATE_BINDING_FUNC_NAME,
+    CREATE_DKG_SESSION_FUNC_NAME, CREATE_DWALLET_FUNC_NAME, CREATE_POLICY_FUNC_NAME,
+    CREATE_PRESIGN_SESSION_FUNC_NAME, CREATE_SIGN_MESSAGES_FUNC_NAME,
+    DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME, DWALLET_BINDING_MODULE_NAME, DWALLET_MODULE_NAME,
+    DWALLET_POLICY_MODULE_NAME, PUBLISH_ENCRYPTION_KEY_FUNC_NAME,
+    REMOVE_APPROVALS_HOLDER_FUNC_NAME, REMOVE_POLICY_FUNC_NAME,
+    SEND_ENCRYPTED_USER_SHARE_FUNC_NAME, SIGN_MESSAGES_FUNC_NAME, UPDATE_POLICY_FUNC_NAME,
+};
+use sui_types::transaction::{Argument, ObjectArg};
+use sui_types::{SUI_CLOCK_OBJECT_ID, SUI_CLOCK_OBJECT_SHARED_VERSION, SUI_SYSTEM_PACKAGE_ID};
+
+pub trait DWalletTransactionBuilder {
+    /// `dwallet_2pc_mpc_ecdsa_k1::create_dkg_session`: start the DKG of a new dWallet with the
+    /// commitment of the user to its secret key share. The network answers with a
+    /// `DKGSessionOutput` sent to the sender.
+    fn create_dkg_session(&mut self, commitment: &impl Serialize) -> anyhow::Result<Argument>;
+
+    /// `dwallet_2pc_mpc_ecdsa_k1::create_dwallet`: create the dWallet of the DKG output
+    /// `dkg_output` with the decommitment of the user, transferring its `DWalletCap` to the
+    /// sender.
+    fn create_dwallet(
+        &mut self,
+        dkg_output: ObjectRef,
+        decommitment_and_proof: &impl Serialize,
+    ) -> anyhow::Result<Argument>;
+
+    /// `dwallet_2pc_mpc_ecdsa_k1::create_presign_session`: start the presign of `messages`, each
+    /// hashed with `hash`, with the commitments of the user to its nonce shares.
+    fn create_presign_session(
+        &mut self,
+        dwallet: ObjectRef,
+        messages: &[Vec<u8>],
+        nonce_shares_commitments_and_batched_proof: &impl Serialize,
+        hash: Hash,
+    ) -> anyhow::Result<Argument>;
+
+    /// `dwallet::approve_messages`: the approvals of the owner of `dwallet_cap` to sign
+    /// `messages`.
+    fn approve_messages(
+        &mut self,
+        dwallet_cap: ObjectRef,
+        messages: &[Vec<u8>],
+    ) -> anyhow::Result<Argument>;
+
+    /// `dwallet::approve_attestations`: the approvals of the owner of `dwallet_cap` to sign the
+    /// attestations of `payloads` of schema `schema`, see [crate::attestation].
+    fn approve_attestations(
+        &mut self,
+        dwallet_cap: ObjectRef,
+        schema: &[u8],
+        payloads: &[Vec<u8>],
+    ) -> anyhow::Result<Argument>;
+
+    /// `dwallet::create_approvals_holder`: keep `message_approvals`, the result of
+    /// [Self::approve_messages], in an `ApprovalsHolder` sent to the sender, to sign their messages
+    /// later with [Self::remove_approvals_holder].
+    fn create_approvals_holder(&mut self, message_approvals: Argument);
+
+    /// `dwallet::remove_approvals_holder`: the message approvals kept in `holder`.
+    fn remove_approvals_holder(&mut self, holder: ObjectRef) -> anyhow::Result<Argument>;
+
+    /// `dwallet::cancel_approvals_holder`: destroy `holder` with its message approvals.
+    fn cancel_approvals_holder(&mut self, holder: ObjectRef) -> anyhow::Result<()>;
+
+    /// `dwallet_policy::create_policy`: put the message approvals of `dwallet_cap` under a new
+    /// shared `SigningPolicy` with `rules`, owned by the sender.
+    fn create_signing_policy(
+        &mut self,
+        dwallet_cap: ObjectRef,
+        rules: &SigningPolicyRules,
+    ) -> anyhow::Result<()>;
+
+    /// `dwallet_policy::update_policy`: replace the rules of the `SigningPolicy` `policy`, shared
+    /// at `initial_shared_version`, with `rules`.
+    fn update_signing_policy(
+        &mut self,
+        policy: ObjectID,
+        initial_shared_version: SequenceNumber,
+        rules: &SigningPolicyRules,
+    ) -> anyhow::Result<()>;
+
+    /// `dwallet_policy::remove_policy`: remove the `SigningPolicy` `policy`, shared at
+    /// `initial_shared_version`, returning the `DWalletCap` it holds.
+    fn remove_signing_policy(
+        &mut self,
+        policy: ObjectID,
+        initial_shared_version: SequenceNumber,
+    ) -> anyhow::Result<Argument>;
+
+    /// `dwallet_policy::approve_messages`: the approvals to sign `messages` under the
+    /// `SigningPolicy` `policy`, shared at `initial_shared_version`, in place of
+    /// [Self::approve_messages] once the policy holds the capability.
+    fn approve_messages_with_policy(
+        &mut self,
+        policy: ObjectID,
+        initial_shared_version: SequenceNumber,
+        messages: &[Vec<u8>],
+    ) -> anyhow::Result<Argument>;
+
+    /// `dwallet_2pc_mpc_ecdsa_k1::create_sign_messages`: the messages of a completed presign
+    /// session, with the encrypted partial signatures of the user, ready to be signed with
+    /// [Self::sign_messages].
+    fn create_sign_messages(
+        &mut self,
+        dwallet: ObjectRef,
+        presign_session: ObjectRef,
+        presign_session_output: ObjectRef,
+        presign: ObjectRef,
+        public_nonce_encrypted_partial_signature_and_proofs: &impl Serialize,
+    ) -> anyhow::Result<Argument>;
+
+    /// `dwallet::sign_messages`: start the sign session of `sign_messages`, the result of
+    /// [Self::create_sign_messages], approved by `message_approvals`.
+    fn sign_messages(&mut self, sign_messages: Argument, message_approvals: Argument);
+
+    /// Transfer the ownership of a dWallet, held by its `DWalletCap`, to `recipient`.
+    fn transfer_dwallet_cap(
+        &mut self,
+        dwallet_cap: ObjectRef,
+        recipient: SuiAddress,
+    ) -> anyhow::Result<()>;
+
+    /// `dwallet_2pc_mpc_ecdsa_k1::publish_encryption_key`: publish `encryption_key`, for the user
+    /// shares of the dWallets transferred to the sender to be encrypted to.
+    fn publish_encryption_key(&mut self, encryption_key: &[u8]) -> anyhow::Result<()>;
+
+    /// `dwallet_2pc_mpc_ecdsa_k1::send_encrypted_user_share`: send the user share of `dwallet`,
+    /// encrypted to the `EncryptionKey` `encryption_key_id` of `recipient`, to `recipient`.
+    fn send_encrypted_user_share(
+        &mut self,
+        dwallet: ObjectRef,
+        dwallet_cap: Argument,
+        encryption_key_id: ObjectID,
+        encrypted_user_share: &[u8],
+        recipient: SuiAddress,
+    ) -> anyhow::Result<()>;
+
+    /// `dwallet_binding::create_binding`: bind `dwallet`, of `dwallet_cap`, to its address
+    /// `external_address` on `chain`, with a new immutable `DWalletBinding`.
+    fn create_dwallet_binding(
+        &mut self,
+        dwallet: ObjectRef,
+        dwallet_cap: ObjectRef,
+        chain: &str,
+        external_address: &str,
+    ) -> anyhow::Result<()>;
+}
+
+impl DWalletTransactionBuilder for ProgrammableTransactionBuilder {
+    fn create_dkg_session(&mut self, commitment: &impl Serialize) -> anyhow::Result<Argument> {
+        let commitment = self.pure(bcs::to_bytes(commitment)?)?;
+        Ok(dwallet_call(
+            self,
+            DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+            CREATE_DKG_SESSION_FUNC_NAME,
+            vec![],
+            vec![commitment],
+        ))
+    }
+
+    fn create_dwallet(
+        &mut self,
+        dkg_output: ObjectRef,
+        decommitment_and_proof: &impl Serialize,
+    ) -> anyhow::Result<Argument> {
+        let dkg_output = self.obj(ObjectArg::ImmOrOwnedObject(dkg_output))?;
+        let decommitment_and_proof = self.pure(bcs::to_bytes(decommitment_and_proof)?)?;
+        Ok(dwallet_call(
+            self,
+            DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+            CREATE_DWALLET_FUNC_NAME,
+            vec![],
+            vec![dkg_output, decommitment_and_proof],
+        ))
+    }
+
+    fn create_presign_session(
+        &mut self,
+        dwallet: ObjectRef,
+        messages: &[Vec<u8>],
+        nonce_shares_commitments_and_batched_proof: &impl Serialize,
+        hash: Hash,
+    ) -> anyhow::Result<Argument> {
+        let dwallet = self.obj(ObjectArg::ImmOrOwnedObject(dwallet))?;
+        let messages = self.pure(messages)?;
+        let commitments = self.pure(bcs::to_bytes(nonce_shares_commitments_and_batched_proof)?)?;
+        let hash = self.pure(u8::from(hash))?;
+        Ok(dwallet_call(
+            self,
+            DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+            CREATE_PRESIGN_SESSION_FUNC_NAME,
+            vec![],
+            vec![dwallet, messages, commitments, hash],
+        ))
+    }
+
+    fn approve_messages(
+        &mut self,
+        dwallet_cap: ObjectRef,
+        messages: &[Vec<u8>],
+    ) -> anyhow::Result<Argument> {
+        let dwallet_cap = self.obj(ObjectArg::ImmOrOwnedObject(dwallet_cap))?;
+        let messages = self.pure(messages)?;
+        Ok(dwallet_call(
+            self,
+            DWALLET_MODULE_NAME,
+            APPROVE_MESSAGES_FUNC_NAME,
+            vec![],
+            vec![dwallet_cap, messages],
+        ))
+    }
+
+    fn approve_attestations(
+        &mut self,
+        dwallet_cap: ObjectRef,
+        schema: &[u8],
+        payloads: &[Vec<u8>],
+    ) -> anyhow::Result<Argument> {
+        let dwallet_cap = self.obj(ObjectArg::ImmOrOwnedObject(dwallet_cap))?;
+        let schema = self.pure(schema)?;
+        let payloads = self.pure(payloads)?;
+        Ok(dwallet_call(
+            self,
+            DWALLET_MODULE_NAME,
+            APPROVE_ATTESTATIONS_FUNC_NAME,
+            vec![],
+            vec![dwallet_cap, schema, payloads],
+        ))
+    }
+
+    fn create_approvals_holder(&mut self, message_approvals: Argument) {
+        dwallet_call(
+            self,
+            DWALLET_MODULE_NAME,
+            CREATE_APPROVALS_HOLDER_FUNC_NAME,
+            vec![],
+            vec![message_approvals],
+        );
+    }
+
+    fn remove_approvals_holder(&mut self, holder: ObjectRef) -> anyhow::Result<Argument> {
+        let holder = self.obj(ObjectArg::ImmOrOwnedObject(holder))?;
+        Ok(dwallet_call(
+            self,
+            DWALLET_MODULE_NAME,
+            REMOVE_APPROVALS_HOLDER_FUNC_NAME,
+            vec![],
+            vec![holder],
+        ))
+    }
+
+    fn cancel_approvals_holder(&mut self, holder: ObjectRef) -> anyhow::Result<()> {
+        let holder = self.obj(ObjectArg::ImmOrOwnedObject(holder))?;
+        dwallet_call(
+            self,
+            DWALLET_MODULE_NAME,
+            CANCEL_APPROVALS_HOLDER_FUNC_NAME,
+            vec![],
+            vec![holder],
+        );
+        Ok(())
+    }
+
+    fn create_signing_policy(
+        &mut self,
+        dwallet_cap: ObjectRef,
+        rules: &SigningPolicyRules,
+    ) -> anyhow::Result<()> {
+        let dwallet_cap = self.obj(ObjectArg::ImmOrOwnedObject(dwallet_cap))?;
+        let mut arguments = vec![dwallet_cap];
+        arguments.extend(policy_rules_arguments(self, rules)?);
+        dwallet_call(
+            self,
+            DWALLET_POLICY_MODULE_NAME,
+            CREATE_POLICY_FUNC_NAME,
+            vec![],
+            arguments,
+        );
+        Ok(())
+    }
+
+    fn update_signing_policy(
+        &mut self,
+        policy: ObjectID,
+        initial_shared_version: SequenceNumber,
+        rules: &SigningPolicyRules,
+    ) -> anyhow::Result<()> {
+        let policy = self.obj(ObjectArg::SharedObject {
+            id: policy,
+            initial_shared_version,
+            mutable: true,
+        })?;
+        let mut arguments = vec![policy];
+        arguments.extend(policy_rules_arguments(self, rules)?);
+        dwallet_call(
+            self,
+            DWALLET_POLICY_MODULE_NAME,
+            UPDATE_POLICY_FUNC_NAME,
+            vec![],
+            arguments,
+        );
+        Ok(())
+    }
+
+    fn remove_signing_policy(
+        &mut self,
+        policy: ObjectID,
+        initial_shared_version: SequenceNumber,
+    ) -> anyhow::Result<Argument> {
+        let policy = self.obj(ObjectArg::SharedObject {
+            id: policy,
+            initial_shared_version,
+            mutable: true,
+        })?;
+        Ok(dwallet_call(
+            self,
+            DWALLET_POLICY_MODULE_NAME,
+            REMOVE_POLICY_FUNC_NAME,
+            vec![],
+            vec![policy],
+        ))
+    }
+
+    fn approve_messages_with_policy(
+        &mut self,
+        policy: ObjectID,
+        initial_shared_version: SequenceNumber,
+        messages: &[Vec<u8>],
+    ) -> anyhow::Result<Argument> {
+        let policy = self.obj(ObjectArg::SharedObject {
+            id: policy,
+            initial_shared_version,
+            mutable: true,
+        })?;
+        let messages = self.pure(messages)?;
+        let clock = self.obj(ObjectArg::SharedObject {
+            id: SUI_CLOCK_OBJECT_ID,
+            initial_shared_version: SUI_CLOCK_OBJECT_SHARED_VERSION,
+            mutable: false,
+        })?;
+        Ok(dwallet_call(
+            self,
+            DWALLET_POLICY_MODULE_NAME,
+            APPROVE_MESSAGES_FUNC_NAME,
+            vec![],
+            vec![policy, messages, clock],
+        ))
+    }
+
+    fn create_sign_messages(
+        &mut self,
+        dwallet: ObjectRef,
+        presign_session: ObjectRef,
+        presign_session_output: ObjectRef,
+        presign: ObjectRef,
+        public_nonce_encrypted_partial_signature_and_proofs: &impl Serialize,
+    ) -> anyhow::Result<Argument> {
+        let dwallet = self.obj(ObjectArg::ImmOrOwnedObject(dwallet))?;
+        let presign_session = self.obj(ObjectArg::ImmOrOwnedObject(presign_session))?;
+        let presign_session_output =
+            self.obj(ObjectArg::ImmOrOwnedObject(presign_session_output))?;
+        let presign = self.obj(ObjectArg::ImmOrOwnedObject(presign))?;
+        let partial_signatures = self.pure(bcs::to_bytes(
+            public_nonce_encrypted_partial_signature_and_proofs,
+        )?)?;
+        Ok(dwallet_call(
+            self,
+            DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+            CREATE_SIGN_MESSAGES_FUNC_NAME,
+            vec![],
+            vec![
+                dwallet,
+                presign_session,
+                presign_session_output,
+                presign,
+                partial_signatures,
+            ],
+        ))
+    }
+
+    fn sign_messages(&mut self, sign_messages: Argument, message_approvals: Argument) {
+        dwallet_call(
+            self,
+            DWALLET_MODULE_NAME,
+            SIGN_MESSAGES_FUNC_NAME,
+            vec![TypeTag::Struct(Box::new(SignData::type_()))],
+            vec![sign_messages, message_approvals],
+        );
+    }
+
+    fn transfer_dwallet_cap(
+        &mut self,
+        dwallet_cap: ObjectRef,
+        recipient: SuiAddress,
+    ) -> anyhow::Result<()> {
+        self.transfer_object(recipient, dwallet_cap)
+    }
+
+    fn publish_encryption_key(&mut self, encryption_key: &[u8]) -> anyhow::Result<()> {
+        let encryption_key = self.pure(encryption_key)?;
+        dwallet_call(
+            self,
+            DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+            PUBLISH_ENCRYPTION_KEY_FUNC_NAME,
+            vec![],
+            vec![encryption_key],
+        );
+        Ok(())
+    }
+
+    fn send_encrypted_user_share(
+        &mut self,
+        dwallet: ObjectRef,
+        dwallet_cap: Argument,
+        encryption_key_id: ObjectID,
+        encrypted_user_share: &[u8],
+        recipient: SuiAddress,
+    ) -> anyhow::Result<()> {
+        let dwallet = self.obj(ObjectArg::ImmOrOwnedObject(dwallet))?;
+        let encryption_key_id = self.pure(encryption_key_id)?;
+        let encrypted_user_share = self.pure(encrypted_user_share)?;
+        let recipient = self.pure(recipient)?;
+        dwallet_call(
+            self,
+            DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+            SEND_ENCRYPTED_USER_SHARE_FUNC_NAME,
+            vec![],
+            vec![
+                dwallet,
+                dwallet_cap,
+                encryption_key_id,
+                encrypted_user_share,
+                recipient,
+            ],
+        );
+        Ok(())
+    }
+
+    fn create_dwallet_binding(
+        &mut self,
+        dwallet: ObjectRef,
+        dwallet_cap: ObjectRef,
+        chain: &str,
+        external_address: &str,
+    ) -> anyhow::Result<()> {
+        let dwallet = self.obj(ObjectArg::ImmOrOwnedObject(dwallet))?;
+        let dwallet_cap = self.obj(ObjectArg::ImmOrOwnedObject(dwallet_cap))?;
+        let chain = self.pure(chain.as_bytes())?;
+        let external_address = self.pure(external_address.as_bytes())?;
+        dwallet_call(
+            self,
+            DWALLET_BINDING_MODULE_NAME,
+            CREATE_BINDING_FUNC_NAME,
+            vec![],
+            vec![dwallet, dwallet_cap, chain, external_address],
+        );
+        Ok(())
+    }
+}
+
+/// The arguments of `rules`, in the order of `create_policy` and `update_policy`.
+fn policy_rules_arguments(
+    builder: &mut ProgrammableTransactionBuilder,
+    rules: &SigningPolicyRules,
+) -> anyhow::Result<Vec<Argument>> {
+    Ok(vec![
+        builder.pure(&rules.allowlist)?,
+        builder.pure(rules.max_messages)?,
+        builder.pure(rules.window_ms)?,
+        builder.pure(&rules.co_approvers)?,
+        builder.pure(rules.co_approval_threshold)?,
+    ])
+}
+
+fn dwallet_call(
+    builder: &mut ProgrammableTransactionBuilder,
+    module: &IdentStr,
+    function: &IdentStr,
+    type_arguments: Vec<TypeTag>,
+    arguments: Vec<Argument>,
+) -> Argument {
+    builder.programmable_move_call(
+        SUI_SYSTEM_PACKAGE_ID,
+        module.to_owned(),
+        function.to_owned(),
+        type_arguments,
+        arguments,
+    )
+}
@@ -2,8 +2,11 @@
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
 pub use crate::json_rpc_error::Error as JsonRpcError;
-use sui_types::base_types::{SuiAddress, TransactionDigest};
+use std::time::Duration;
+use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use sui_types::error::UserInputError;
+use sui_types::eth_light_client::EthLightClientError;
+use sui_types::eth_proofs::EthProofError;
 use thiserror::Error;
 
 pub type SuiRpcResult<T = ()> = Result<T, Error>;
@@ -24,11 +27,52 @@ pub enum Error {
     FailToConfirmTransactionStatus(TransactionDigest, u64),
     #[error("Data error: {0}")]
     DataError(String),
+    #[error("Invalid client configuration: {0}")]
+    InvalidConfig(String),
     #[error("Client/Server api version mismatch, client api version : {client_version}, server api version : {server_version}")]
     ServerVersionMismatch {
         client_version: String,
         server_version: String,
     },
+    #[error("Bitcoin RPC error: {0}")]
+    BitcoinRpcError(String),
+    #[error("Ethereum RPC error: {0}")]
+    EthRpcError(String),
+    #[error("Faucet error: {0}")]
+    FaucetError(String),
+    #[error(
+        "Faucet {url} is rate limiting the requests{}",
+        .retry_after_secs.map(|secs| format!(", retry in {secs}s")).unwrap_or_default()
+    )]
+    FaucetRateLimited {
+        url: String,
+        /// The wait the faucet asked for, from its `Retry-After` header.
+        retry_after_secs: Option<u64>,
+    },
+    #[error(transparent)]
+    EthLightClientError(#[from] EthLightClientError),
+    #[error(transparent)]
+    EthProofError(#[from] EthProofError),
+    #[error("Ethereum block {number} with hash {hash} was reorged out, the canonical block is {canonical_hash}")]
+    EthReorg {
+        number: u64,
+        hash: String,
+        canonical_hash: String,
+    },
+    #[error("Sign session {session_id} was not completed within {timeout:?}")]
+    SignSessionTimeout {
+        session_id: ObjectID,
+        timeout: Duration,
+    },
+    #[error(
+        "The network did not answer the {round} round of the dWallet protocol within {timeout:?}"
+    )]
+    DWalletRoundTimeout { round: String, timeout: Duration },
+    #[error("Sign session {session_id} failed: {reason}")]
+    SignSessionFailed {
+        session_id: ObjectID,
+        reason: String,
+    },
     #[error("Insufficient fund for address [{address}], requested amount: {amount}")]
     InsufficientFund { address: SuiAddress, amount: u128 },
 }
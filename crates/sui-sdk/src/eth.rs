@@ -0,0 +1,225 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Ethereum custody with dWallets.
+//!
+//! This module encodes EIP-1559 transactions, has the dWallet of a [DWalletSigner] sign their
+//! signing payload, and assembles the raw transaction to broadcast with `eth_sendRawTransaction`.
+//!
+//! The sighash of an Ethereum transaction is the Keccak-256 hash of its signing payload, so the
+//! dWallet is asked to sign the payload with [Hash::KECCAK256].
+//!
+//! Signing and broadcasting can be kept apart: [assemble_from_sign_session] assembles the raw
+//! transaction from the signature published by the network for a past sign session, so that the
+//! machine broadcasting transactions needs no access to the user share of the dWallet.
+
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{HashFunction, Keccak256};
+use secp256k1::ecdsa::{RecoverableSignature, RecoveryId, Signature};
+use secp256k1::{Message, PublicKey, Secp256k1};
+use sui_types::base_types::ObjectID;
+
+use crate::dwallet_signer::{sign_and_verify, verify_signature, DWalletSigner, Hash};
+use crate::error::{Error, SuiRpcResult};
+use crate::SuiClient;
+
+pub type Address = [u8; 20];
+
+/// The type of EIP-1559 transactions, prefixing their signing payload and encoding (EIP-2718).
+const EIP1559_TRANSACTION_TYPE: u8 = 0x02;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccessListItem {
+    pub address: Address,
+    pub storage_keys: Vec<[u8; 32]>,
+}
+
+/// An EIP-1559 transaction, with amounts in wei.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Eip1559Transaction {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: u128,
+    pub max_fee_per_gas: u128,
+    pub gas_limit: u64,
+    /// `None` to create a contract.
+    pub to: Option<Address>,
+    pub value: u128,
+    pub data: Vec<u8>,
+    pub access_list: Vec<AccessListItem>,
+}
+
+impl Eip1559Transaction {
+    /// The payload signed by the dWallet, `0x02 || rlp([chain_id, ..., access_list])`.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        [&[EIP1559_TRANSACTION_TYPE][..], &rlp_list(&self.fields())].concat()
+    }
+
+    /// The raw transaction signed with `signature`, the 64-byte `r || s` signature of the signing
+    /// payload by the dWallet with the compressed SEC1 public key `public_key`. Fails if the
+    /// signature does not verify.
+    pub fn encode_signed(&self, public_key: &[u8], signature: &[u8]) -> SuiRpcResult<Vec<u8>> {
+        let payload = self.signing_payload();
+        verify_signature(public_key, &payload, Hash::KECCAK256, signature)?;
+
+        // Ethereum only accepts low-S signatures.
+        let mut signature = Signature::from_compact(signature)
+            .map_err(|e| Error::DataError(format!("invalid dWallet signature: {e}")))?;
+        signature.normalize_s();
+        let signature = signature.serialize_compact();
+        let y_parity = y_parity(&Keccak256::digest(&payload).digest, &signature, public_key)?;
+
+        let mut fields = self.fields();
+        fields.push(rlp_uint(y_parity.into()));
+        fields.push(rlp_bytes(strip_leading_zeros(&signature[..32])));
+        fields.push(rlp_bytes(strip_leading_zeros(&signature[32..])));
+        Ok([&[EIP1559_TRANSACTION_TYPE][..], &rlp_list(&fields)].concat())
+    }
+
+    fn fields(&self) -> Vec<Vec<u8>> {
+        let access_list: Vec<_> = self
+            .access_list
+            .iter()
+            .map(|item| {
+                let storage_keys: Vec<_> =
+                    item.storage_keys.iter().map(|key| rlp_bytes(key)).collect();
+                rlp_list(&[rlp_bytes(&item.address), rlp_list(&storage_keys)])
+            })
+            .collect();
+        vec![
+            rlp_uint(self.chain_id.into()),
+            rlp_uint(self.nonce.into()),
+            rlp_uint(self.max_priority_fee_per_gas),
+            rlp_uint(self.max_fee_per_gas),
+            rlp_uint(self.gas_limit.into()),
+            rlp_bytes(self.to.as_ref().map_or(&[][..], |to| &to[..])),
+            rlp_uint(self.value),
+            rlp_bytes(&self.data),
+            rlp_list(&access_list),
+        ]
+    }
+}
+
+/// The hash of the raw transaction `raw_transaction`, which identifies it on chain.
+pub fn transaction_hash(raw_transaction: &[u8]) -> [u8; 32] {
+    Keccak256::digest(raw_transaction).digest
+}
+
+pub fn address_from_hex(address: &str) -> SuiRpcResult<Address> {
+    Hex::decode(address.strip_prefix("0x").unwrap_or(address))
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| Error::DataError(format!("invalid Ethereum address [{address}]")))
+}
+
+/// Sign `transaction` with the dWallet of `signer`, returning the raw transaction.
+pub async fn sign_transaction(
+    transaction: &Eip1559Transaction,
+    signer: &impl DWalletSigner,
+) -> SuiRpcResult<Vec<u8>> {
+    let signature = sign_and_verify(transaction.signing_payload(), Hash::KECCAK256, signer).await?;
+    transaction.encode_signed(&signer.public_key(), &signature)
+}
+
+/// Assemble the raw transaction of `transaction` from its signature by the sign session
+/// `session_id`, which must have signed its signing payload, hashed with [Hash::KECCAK256].
+///
+/// The signature is checked against the public key of the dWallet of the session, so a fullnode
+/// cannot have a transaction with a bogus signature broadcast. Fails if the network has not
+/// signed the messages of the session yet.
+pub async fn assemble_from_sign_session(
+    sui: &SuiClient,
+    session_id: ObjectID,
+    transaction: &Eip1559Transaction,
+) -> SuiRpcResult<Vec<u8>> {
+    let failed = |reason: &str| Error::SignSessionFailed {
+        session_id,
+        reason: reason.to_string(),
+    };
+    let session = sui
+        .dwallet_api()
+        .get_sign_session(session_id)
+        .await?
+        .ok_or_else(|| failed("no such sign session"))?;
+    let payload = transaction.signing_payload();
+    let index = session
+        .messages
+        .iter()
+        .position(|message| *message == payload)
+        .ok_or_else(|| failed("the session did not sign the transaction"))?;
+    let output = sui
+        .get_sign_output(session_id)
+        .await?
+        .ok_or_else(|| failed("the network has not signed the messages of the session yet"))?;
+    let signature = output
+        .signatures
+        .get(index)
+        .ok_or_else(|| failed("no signature of the transaction in the output of the session"))?;
+
+    let dwallet = sui
+        .dwallet_api()
+        .get_dwallet(session.dwallet_id.bytes)
+        .await?;
+    let public_key = dwallet.public_key_sec1().map_err(|e| {
+        Error::DataError(format!(
+            "invalid public key of dWallet {}: {e}",
+            session.dwallet_id.bytes
+        ))
+    })?;
+    transaction.encode_signed(&public_key, signature)
+}
+
+/// The parity of the y coordinate of the nonce point of the signature `signature` of `digest` by
+/// `public_key`, which recovers `public_key` from the signature.
+fn y_parity(digest: &[u8; 32], signature: &[u8; 64], public_key: &[u8]) -> SuiRpcResult<u8> {
+    let secp = Secp256k1::verification_only();
+    let message = Message::from_slice(digest).expect("digest is 32 bytes");
+    let public_key = PublicKey::from_slice(public_key)
+        .map_err(|e| Error::DataError(format!("invalid dWallet public key: {e}")))?;
+    (0..2)
+        .find(|&parity| {
+            RecoveryId::from_i32(parity)
+                .and_then(|id| RecoverableSignature::from_compact(signature, id))
+                .and_then(|signature| secp.recover_ecdsa(&message, &signature))
+                .is_ok_and(|recovered| recovered == public_key)
+        })
+        .map(|parity| parity as u8)
+        .ok_or_else(|| {
+            Error::DataError("the dWallet public key cannot be recovered from the signature".into())
+        })
+}
+
+fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+    match bytes {
+        [byte] if *byte < 0x80 => vec![*byte],
+        _ => [rlp_header(0x80, bytes.len()), bytes.to_vec()].concat(),
+    }
+}
+
+/// Integers are encoded as their big-endian bytes, without leading zeros.
+fn rlp_uint(value: u128) -> Vec<u8> {
+    rlp_bytes(strip_leading_zeros(&value.to_be_bytes()))
+}
+
+fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    [rlp_header(0xc0, payload.len()), payload].concat()
+}
+
+fn rlp_header(offset: u8, length: usize) -> Vec<u8> {
+    if length < 56 {
+        vec![offset + length as u8]
+    } else {
+        let length = length.to_be_bytes();
+        let length = strip_leading_zeros(&length);
+        [&[offset + 55 + length.len() as u8][..], length].concat()
+    }
+}
+
+fn strip_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|byte| *byte != 0)
+        .unwrap_or(bytes.len());
+    &bytes[start..]
+}
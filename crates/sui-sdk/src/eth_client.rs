@@ -0,0 +1,330 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! A minimal Ethereum execution layer client, for reading the blocks and logs that dWallet
+//! actions are triggered by.
+//!
+//! Data is only returned once it is final according to the [EthFinality] rule of the network,
+//! and is checked against the canonical chain, so that no signature is issued against an event
+//! that was reorged out.
+
+use fastcrypto::encoding::{Encoding, Hex};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::{Error, SuiRpcResult};
+use crate::sui_client_config::EthNetworkConfig;
+
+/// When execution layer data is considered final.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EthFinality {
+    /// The block is finalized by the beacon chain, about two epochs behind the head.
+    Finalized,
+    /// The block is at least this many blocks deep, counting the head as 0.
+    /// Suitable for chains without a beacon chain, such as L2s.
+    Depth(u64),
+}
+
+impl Default for EthFinality {
+    fn default() -> Self {
+        Self::Finalized
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub number: u64,
+    pub hash: [u8; 32],
+    pub parent_hash: [u8; 32],
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Log {
+    pub address: [u8; 20],
+    pub topics: Vec<[u8; 32]>,
+    pub data: Vec<u8>,
+    pub block_number: u64,
+    pub block_hash: [u8; 32],
+    pub transaction_hash: [u8; 32],
+    pub log_index: u64,
+}
+
+/// The proofs of an account and some of its storage slots, as served by `eth_getProof`, to be
+/// verified against the state root of the block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountProof {
+    pub address: [u8; 20],
+    /// The RLP encoded nodes of the state trie on the path to the account.
+    pub account_proof: Vec<Vec<u8>>,
+    pub storage_proofs: Vec<StorageProof>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageProof {
+    pub slot: [u8; 32],
+    /// The RLP encoded nodes of the storage trie of the account on the path to the slot.
+    pub proof: Vec<Vec<u8>>,
+}
+
+/// The logs to fetch with [EthClient::final_logs]. Topics are matched by position, `None`
+/// matching any topic.
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    pub address: Option<[u8; 20]>,
+    pub topics: Vec<Option<[u8; 32]>>,
+    pub from_block: u64,
+    /// Capped at the final block.
+    pub to_block: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EthClient {
+    url: String,
+    finality: EthFinality,
+    http: reqwest::Client,
+}
+
+impl EthNetworkConfig {
+    pub fn build_client(&self) -> EthClient {
+        EthClient {
+            url: self.execution_rpc.clone(),
+            finality: self.evm_chain().finality,
+            http: reqwest::Client::new(),
+        }
+    }
+}
+
+impl EthClient {
+    pub fn finality(&self) -> EthFinality {
+        self.finality
+    }
+
+    pub async fn chain_id(&self) -> SuiRpcResult<u64> {
+        parse_quantity(&self.call("eth_chainId", json!([])).await?)
+    }
+
+    pub async fn block_number(&self) -> SuiRpcResult<u64> {
+        parse_quantity(&self.call("eth_blockNumber", json!([])).await?)
+    }
+
+    /// The canonical block at `number`, `None` if the chain is not that long yet.
+    pub async fn block(&self, number: u64) -> SuiRpcResult<Option<BlockHeader>> {
+        self.block_by_tag(json!(format!("{number:#x}"))).await
+    }
+
+    /// The latest block that is final.
+    pub async fn final_block(&self) -> SuiRpcResult<BlockHeader> {
+        let block = match self.finality {
+            EthFinality::Finalized => self.block_by_tag(json!("finalized")).await?,
+            EthFinality::Depth(depth) => {
+                let head = self.block_number().await?;
+                let number = head.checked_sub(depth).ok_or_else(|| {
+                    Error::EthRpcError(format!("the chain is not {depth} blocks long yet"))
+                })?;
+                self.block(number).await?
+            }
+        };
+        block.ok_or_else(|| Error::EthRpcError("no final block".to_string()))
+    }
+
+    /// Check that the block `number` with hash `hash` is final and still canonical.
+    pub async fn ensure_final(&self, number: u64, hash: &[u8; 32]) -> SuiRpcResult<()> {
+        let final_block = self.final_block().await?;
+        if number > final_block.number {
+            return Err(Error::EthRpcError(format!(
+                "block {number} is not final, the latest final block is {}",
+                final_block.number
+            )));
+        }
+        self.ensure_canonical(number, hash).await
+    }
+
+    /// The logs matching `filter` in final blocks.
+    ///
+    /// Fails with [Error::EthReorg] if a block of the returned logs is no longer canonical by the
+    /// time the logs are checked, in which case the logs must be fetched again.
+    pub async fn final_logs(&self, filter: &LogFilter) -> SuiRpcResult<Vec<Log>> {
+        let final_block = self.final_block().await?;
+        let to_block = filter
+            .to_block
+            .map_or(final_block.number, |to| to.min(final_block.number));
+        if filter.from_block > to_block {
+            return Ok(vec![]);
+        }
+
+        let topics: Vec<Value> = filter
+            .topics
+            .iter()
+            .map(|topic| topic.map_or(Value::Null, |t| json!(to_hex(&t))))
+            .collect();
+        let mut params = json!({
+            "fromBlock": format!("{:#x}", filter.from_block),
+            "toBlock": format!("{to_block:#x}"),
+            "topics": topics,
+        });
+        if let Some(address) = &filter.address {
+            params["address"] = json!(to_hex(address));
+        }
+        let logs = self.call("eth_getLogs", json!([params])).await?;
+        let logs = logs
+            .as_array()
+            .ok_or_else(|| Error::EthRpcError(format!("expected an array, got {logs}")))?
+            .iter()
+            // Logs of a block that was reorged out are marked as removed by the node.
+            .filter(|log| !log["removed"].as_bool().unwrap_or(false))
+            .map(parse_log)
+            .collect::<SuiRpcResult<Vec<_>>>()?;
+
+        let mut checked = None;
+        for log in &logs {
+            if checked != Some(log.block_number) {
+                self.ensure_canonical(log.block_number, &log.block_hash)
+                    .await?;
+                checked = Some(log.block_number);
+            }
+        }
+        Ok(logs)
+    }
+
+    /// The proofs of the account `address` and its storage `slots` at block `number`.
+    pub async fn proof(
+        &self,
+        address: &[u8; 20],
+        slots: &[[u8; 32]],
+        number: u64,
+    ) -> SuiRpcResult<AccountProof> {
+        let keys: Vec<_> = slots.iter().map(|slot| to_hex(slot)).collect();
+        let proof = self
+            .call(
+                "eth_getProof",
+                json!([to_hex(address), keys, format!("{number:#x}")]),
+            )
+            .await?;
+        // Storage proofs are served in the order of the slots, with the slots formatted in
+        // different ways by different nodes.
+        let storage_proofs = proof["storageProof"]
+            .as_array()
+            .filter(|storage_proofs| storage_proofs.len() == slots.len())
+            .ok_or_else(|| Error::EthRpcError(format!("invalid storage proofs in {proof}")))?
+            .iter()
+            .zip(slots)
+            .map(|(storage_proof, slot)| {
+                Ok(StorageProof {
+                    slot: *slot,
+                    proof: parse_proof(&storage_proof["proof"])?,
+                })
+            })
+            .collect::<SuiRpcResult<_>>()?;
+        Ok(AccountProof {
+            address: *address,
+            account_proof: parse_proof(&proof["accountProof"])?,
+            storage_proofs,
+        })
+    }
+
+    async fn ensure_canonical(&self, number: u64, hash: &[u8; 32]) -> SuiRpcResult<()> {
+        let canonical = self
+            .block(number)
+            .await?
+            .ok_or_else(|| Error::EthRpcError(format!("block {number} not found")))?;
+        if &canonical.hash != hash {
+            return Err(Error::EthReorg {
+                number,
+                hash: to_hex(hash),
+                canonical_hash: to_hex(&canonical.hash),
+            });
+        }
+        Ok(())
+    }
+
+    async fn block_by_tag(&self, tag: Value) -> SuiRpcResult<Option<BlockHeader>> {
+        let block = self
+            .call("eth_getBlockByNumber", json!([tag, false]))
+            .await?;
+        if block.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(BlockHeader {
+            number: parse_quantity(&block["number"])?,
+            hash: parse_bytes(&block["hash"])?,
+            parent_hash: parse_bytes(&block["parentHash"])?,
+            timestamp: parse_quantity(&block["timestamp"])?,
+        }))
+    }
+
+    async fn call(&self, method: &str, params: Value) -> SuiRpcResult<Value> {
+        let request = json!({"jsonrpc": "2.0", "id": 0, "method": method, "params": params});
+        let response = self
+            .http
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| Error::EthRpcError(format!("cannot reach {}: {e}", self.url)))?
+            .json::<Value>()
+            .await
+            .map_err(|e| Error::EthRpcError(e.to_string()))?;
+        if !response["error"].is_null() {
+            return Err(Error::EthRpcError(format!(
+                "{method} failed: {}",
+                response["error"]
+            )));
+        }
+        Ok(response["result"].clone())
+    }
+}
+
+fn parse_log(log: &Value) -> SuiRpcResult<Log> {
+    let topics = log["topics"]
+        .as_array()
+        .ok_or_else(|| Error::EthRpcError(format!("invalid topics in {log}")))?
+        .iter()
+        .map(parse_bytes)
+        .collect::<SuiRpcResult<_>>()?;
+    Ok(Log {
+        address: parse_bytes(&log["address"])?,
+        topics,
+        data: parse_hex(&log["data"])?,
+        block_number: parse_quantity(&log["blockNumber"])?,
+        block_hash: parse_bytes(&log["blockHash"])?,
+        transaction_hash: parse_bytes(&log["transactionHash"])?,
+        log_index: parse_quantity(&log["logIndex"])?,
+    })
+}
+
+fn parse_proof(value: &Value) -> SuiRpcResult<Vec<Vec<u8>>> {
+    value
+        .as_array()
+        .ok_or_else(|| Error::EthRpcError(format!("expected a proof, got {value}")))?
+        .iter()
+        .map(parse_hex)
+        .collect()
+}
+
+fn parse_quantity(value: &Value) -> SuiRpcResult<u64> {
+    value
+        .as_str()
+        .and_then(|quantity| quantity.strip_prefix("0x"))
+        .and_then(|quantity| u64::from_str_radix(quantity, 16).ok())
+        .ok_or_else(|| Error::EthRpcError(format!("expected a quantity, got {value}")))
+}
+
+fn parse_hex(value: &Value) -> SuiRpcResult<Vec<u8>> {
+    value
+        .as_str()
+        .and_then(|data| data.strip_prefix("0x"))
+        .and_then(|data| Hex::decode(data).ok())
+        .ok_or_else(|| Error::EthRpcError(format!("expected hex data, got {value}")))
+}
+
+fn parse_bytes<const N: usize>(value: &Value) -> SuiRpcResult<[u8; N]> {
+    parse_hex(value)?
+        .try_into()
+        .map_err(|_| Error::EthRpcError(format!("expected {N} bytes, got {value}")))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", Hex::encode(bytes))
+}
@@ -0,0 +1,324 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Initialization of Ethereum light clients from a weak subjectivity checkpoint.
+//!
+//! A light client trusts the sync committee of the checkpoint it is bootstrapped from, so a
+//! malicious bootstrap configuration would let its author forge any Ethereum state. Before
+//! bootstrapping, the configured genesis validators root and checkpoint are verified against
+//! known-good values, and initialization is refused on any mismatch, or when no checkpoint is
+//! known. The known-good checkpoints are the compiled-in ones: the `known_checkpoints` of the
+//! network's configuration come from the same configuration they would vet, so they only count
+//! when the caller explicitly trusts them.
+//!
+//! Once created, the on-chain light client is kept up to date by submitting the light client
+//! updates returned by [fetch_updates], which relayers and operators verify locally first.
+
+use std::collections::BTreeMap;
+
+use fastcrypto::encoding::{Encoding, Hex};
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde_with::serde_as;
+use sui_json_rpc_types::SuiObjectDataOptions;
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::eth_light_client::{
+    self, sync_committee_period, Checkpoint, EthUpdate, EthereumLightClient, Fork,
+    LightClientBootstrap, LightClientStore, Root, Versioned,
+    ETHEREUM_LIGHT_CLIENT_CREATE_FUNCTION_NAME, ETHEREUM_LIGHT_CLIENT_MODULE_NAME,
+    ETHEREUM_LIGHT_CLIENT_SUBMIT_UPDATE_FUNCTION_NAME,
+};
+use sui_types::object::Owner;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::ObjectArg;
+use sui_types::SUI_SYSTEM_PACKAGE_ID;
+
+use crate::beacon_client::{BeaconClient, MAX_REQUEST_LIGHT_CLIENT_UPDATES};
+use crate::error::{Error, SuiRpcResult};
+use crate::sui_client_config::EthNetworkConfig;
+use crate::SuiClient;
+
+/// The size of the updates submitted in a single transaction, leaving room under the maximum
+/// transaction size for the rest of the transaction.
+const MAX_BATCH_SIZE_BYTES: usize = 96 * 1024;
+
+/// The genesis validators root and vetted checkpoints of a public Ethereum network.
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+pub struct KnownNetwork {
+    pub name: String,
+    pub chain_id: u64,
+    #[serde_as(as = "Hex")]
+    pub genesis_validators_root: Root,
+    /// The forks supported by the light client, ordered by epoch.
+    pub forks: Vec<Fork>,
+    /// Vetted finalized checkpoints, which bootstrap configurations are checked against.
+    #[serde(default)]
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+static KNOWN_NETWORKS: Lazy<Vec<KnownNetwork>> = Lazy::new(|| {
+    serde_json::from_str(include_str!("eth_checkpoints.json"))
+        .expect("the compiled-in Ethereum checkpoints are valid")
+});
+
+/// The compiled-in values of the network with `chain_id`, if it is a known network.
+pub fn known_network(chain_id: u64) -> Option<&'static KnownNetwork> {
+    KNOWN_NETWORKS
+        .iter()
+        .find(|network| network.chain_id == chain_id)
+}
+
+/// The known-good checkpoints of `network`: the compiled-in ones, extended with the ones of its
+/// configuration if `trust_configured_checkpoints` is set. The compiled-in checkpoints take
+/// precedence over configured ones of the same epoch.
+pub fn known_checkpoints(
+    network: &EthNetworkConfig,
+    trust_configured_checkpoints: bool,
+) -> Vec<Checkpoint> {
+    let configured = if trust_configured_checkpoints {
+        network.known_checkpoints.as_slice()
+    } else {
+        &[]
+    };
+    let checkpoints: BTreeMap<u64, Checkpoint> = configured
+        .iter()
+        .chain(
+            known_network(network.chain_id)
+                .into_iter()
+                .flat_map(|known| known.checkpoints.iter()),
+        )
+        .map(|checkpoint| (checkpoint.epoch, *checkpoint))
+        .collect();
+    checkpoints.into_values().collect()
+}
+
+/// The fork schedule of `network`: the configured one, or else the compiled-in one.
+pub fn known_forks(network: &EthNetworkConfig) -> SuiRpcResult<Vec<Fork>> {
+    if !network.forks.is_empty() {
+        return Ok(network.forks.clone());
+    }
+    known_network(network.chain_id)
+        .map(|known| known.forks.clone())
+        .ok_or_else(|| {
+            Error::DataError(format!(
+                "no fork schedule configured for chain {}",
+                network.chain_id
+            ))
+        })
+}
+
+/// Verify the genesis validators root and bootstrap checkpoint configured for `network`,
+/// returning the checkpoint. The configured known checkpoints are only used if
+/// `trust_configured_checkpoints` is set, see [known_checkpoints].
+pub fn verify_bootstrap_config(
+    network: &EthNetworkConfig,
+    trust_configured_checkpoints: bool,
+) -> SuiRpcResult<Checkpoint> {
+    let genesis_validators_root = network
+        .genesis_validators_root
+        .ok_or_else(|| Error::DataError("no genesis validators root configured".to_string()))?;
+    // Without known checkpoints, nothing vouches for the configured one.
+    let known_checkpoints = known_checkpoints(network, trust_configured_checkpoints);
+    if known_checkpoints.is_empty() {
+        return Err(Error::DataError(format!(
+            "no known checkpoints for chain {}: none are compiled in, and the configured ones are only used when explicitly trusted",
+            network.chain_id
+        )));
+    }
+    let checkpoint = network
+        .checkpoint
+        .ok_or_else(|| Error::DataError("no bootstrap checkpoint configured".to_string()))?;
+
+    if let Some(known) = known_network(network.chain_id) {
+        if known.genesis_validators_root != genesis_validators_root {
+            return Err(Error::DataError(format!(
+                "the genesis validators root of {} is 0x{}, not 0x{}",
+                known.name,
+                Hex::encode(known.genesis_validators_root),
+                Hex::encode(genesis_validators_root),
+            )));
+        }
+    }
+    eth_light_client::verify_checkpoint(&checkpoint, &known_checkpoints)?;
+    Ok(checkpoint)
+}
+
+/// Verify the bootstrap configuration of `network`, then fetch the bootstrap of its checkpoint
+/// from `beacon_client` and verify it. See [verify_bootstrap_config] for
+/// `trust_configured_checkpoints`.
+pub async fn fetch_bootstrap(
+    network: &EthNetworkConfig,
+    beacon_client: &BeaconClient,
+    trust_configured_checkpoints: bool,
+) -> SuiRpcResult<Versioned<LightClientBootstrap>> {
+    let checkpoint = verify_bootstrap_config(network, trust_configured_checkpoints)?;
+
+    let genesis = beacon_client.genesis().await?;
+    if Some(genesis.genesis_validators_root) != network.genesis_validators_root {
+        return Err(Error::DataError(format!(
+            "the consensus RPC serves the network with genesis validators root 0x{}",
+            Hex::encode(genesis.genesis_validators_root)
+        )));
+    }
+
+    let bootstrap = beacon_client.bootstrap(&checkpoint.block_root).await?;
+    eth_light_client::verify_bootstrap(bootstrap.version, &bootstrap.data, &checkpoint.block_root)?;
+    Ok(bootstrap)
+}
+
+/// The light client updates that may advance `store`, oldest first, and the latest slot of the
+/// chain.
+///
+/// Sync committee period updates are needed to learn the next sync committee, and to catch up on
+/// the periods `store` missed, so they are fetched before the latest finality update when `store`
+/// lacks the next sync committee or is periods behind.
+pub async fn fetch_updates(
+    beacon_client: &BeaconClient,
+    store: &LightClientStore,
+) -> SuiRpcResult<(Vec<EthUpdate>, u64)> {
+    let finality_update = beacon_client.finality_update().await?.data;
+    let chain_head = finality_update.attested_header.beacon.slot;
+
+    let mut updates = vec![];
+    let store_period = sync_committee_period(store.finalized_header.beacon.slot);
+    let head_period = sync_committee_period(finality_update.signature_slot);
+    if store.next_sync_committee.is_none() || head_period > store_period {
+        let count =
+            (head_period.saturating_sub(store_period) + 1).min(MAX_REQUEST_LIGHT_CLIENT_UPDATES);
+        updates.extend(
+            beacon_client
+                .updates(store_period, count)
+                .await?
+                .into_iter()
+                .map(|update| EthUpdate::Update(update.data)),
+        );
+    }
+    updates.push(EthUpdate::FinalityUpdate(finality_update));
+    Ok((updates, chain_head))
+}
+
+/// Whether `update` cannot teach `store` anything. The best update of the current period is
+/// served until the period ends, and only teaches the next sync committee once.
+pub fn is_stale_update(store: &LightClientStore, update: &EthUpdate) -> bool {
+    store.next_sync_committee.is_some()
+        && update.finalized_header().beacon.slot <= store.finalized_header.beacon.slot
+}
+
+/// Split `updates` into batches of at most `max_updates_per_transaction` updates that fit in a
+/// transaction, keeping their order.
+pub fn batch_updates(
+    updates: &[EthUpdate],
+    max_updates_per_transaction: usize,
+) -> SuiRpcResult<Vec<&[EthUpdate]>> {
+    let mut batches = vec![];
+    let mut start = 0;
+    let mut size = 0;
+    for (i, update) in updates.iter().enumerate() {
+        let update_size = bcs::serialized_size(update)?;
+        if i > start
+            && (size + update_size > MAX_BATCH_SIZE_BYTES
+                || i - start == max_updates_per_transaction)
+        {
+            batches.push(&updates[start..i]);
+            start = i;
+            size = 0;
+        }
+        size += update_size;
+    }
+    batches.push(&updates[start..]);
+    Ok(batches)
+}
+
+/// The on-chain light client of the state object `state_object_id`, and the initial shared
+/// version of the object.
+pub async fn get_light_client(
+    sui: &SuiClient,
+    state_object_id: ObjectID,
+) -> SuiRpcResult<(EthereumLightClient, SequenceNumber)> {
+    let response = sui
+        .read_api()
+        .get_object_with_options(state_object_id, SuiObjectDataOptions::bcs_lossless())
+        .await?;
+    let is_light_client = response
+        .data
+        .as_ref()
+        .and_then(|data| data.type_.as_ref())
+        .and_then(|type_| type_.clone().try_into().ok())
+        .is_some_and(|struct_tag| EthereumLightClient::is_type(&struct_tag));
+    if !is_light_client {
+        return Err(Error::DataError(format!(
+            "Object {state_object_id} is not an EthereumLightClient"
+        )));
+    }
+    let Some(Owner::Shared {
+        initial_shared_version,
+    }) = response.owner()
+    else {
+        return Err(Error::DataError(format!(
+            "State object {state_object_id} is not shared"
+        )));
+    };
+    let bcs = response.move_object_bcs().ok_or_else(|| {
+        Error::DataError(format!(
+            "Fetched the EthereumLightClient {state_object_id} without its data"
+        ))
+    })?;
+    Ok((bcs::from_bytes(bcs)?, initial_shared_version))
+}
+
+/// Add a call to `builder` creating and sharing the light client of `network`, from `bootstrap`,
+/// the bootstrap of its configured checkpoint verified by [fetch_bootstrap].
+pub fn create_light_client(
+    builder: &mut ProgrammableTransactionBuilder,
+    network: &EthNetworkConfig,
+    bootstrap: &LightClientBootstrap,
+) -> anyhow::Result<()> {
+    let checkpoint = verify_bootstrap_config(network)?;
+    let arguments = vec![
+        builder.pure(network.chain_id)?,
+        builder.pure(
+            network
+                .genesis_validators_root
+                .expect("verified by verify_bootstrap_config")
+                .to_vec(),
+        )?,
+        builder.pure(bcs::to_bytes(&known_forks(network)?)?)?,
+        builder.pure(checkpoint.block_root.to_vec())?,
+        builder.pure(bcs::to_bytes(bootstrap)?)?,
+    ];
+    builder.programmable_move_call(
+        SUI_SYSTEM_PACKAGE_ID,
+        ETHEREUM_LIGHT_CLIENT_MODULE_NAME.to_owned(),
+        ETHEREUM_LIGHT_CLIENT_CREATE_FUNCTION_NAME.to_owned(),
+        vec![],
+        arguments,
+    );
+    Ok(())
+}
+
+/// Add calls to `builder` submitting `updates`, in order, to the light client of the state object
+/// `state_object_id`, shared at `initial_shared_version`.
+pub fn submit_updates(
+    builder: &mut ProgrammableTransactionBuilder,
+    state_object_id: ObjectID,
+    initial_shared_version: SequenceNumber,
+    updates: &[EthUpdate],
+) -> anyhow::Result<()> {
+    let light_client = builder.obj(ObjectArg::SharedObject {
+        id: state_object_id,
+        initial_shared_version,
+        mutable: true,
+    })?;
+    for update in updates {
+        let update = builder.pure(bcs::to_bytes(update)?)?;
+        builder.programmable_move_call(
+            SUI_SYSTEM_PACKAGE_ID,
+            ETHEREUM_LIGHT_CLIENT_MODULE_NAME.to_owned(),
+            ETHEREUM_LIGHT_CLIENT_SUBMIT_UPDATE_FUNCTION_NAME.to_owned(),
+            vec![],
+            vec![light_client, update],
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,151 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Client-side verification of Ethereum state.
+//!
+//! Before submitting a transaction that depends on the state of an Ethereum network, an
+//! application can check that state itself instead of trusting the view of a fullnode or of an
+//! Ethereum RPC provider. [EthVerifier] runs the light client of the dWallet network locally, with
+//! the same verification code, to follow the finalized headers of the network from its
+//! configured checkpoint, and verifies accounts, storage slots and receipts against them.
+
+use sui_types::eth_light_client::{ExecutionPayloadHeader, LightClientStore};
+use sui_types::eth_proofs::{self, Account, Receipt};
+
+use crate::beacon_client::BeaconClient;
+use crate::error::{Error, SuiRpcResult};
+use crate::eth_client::EthClient;
+use crate::eth_light_client::{
+    fetch_bootstrap, fetch_updates, is_stale_update, known_forks, verify_bootstrap_config,
+};
+use crate::sui_client_config::EthNetworkConfig;
+
+/// An account and storage slots verified against the state root of a finalized block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedAccount {
+    pub block_number: u64,
+    pub address: [u8; 20],
+    /// `None` if the account does not exist.
+    pub account: Option<Account>,
+    /// The values of the requested storage slots, big-endian, in order.
+    pub storage: Vec<[u8; 32]>,
+}
+
+pub struct EthVerifier {
+    store: LightClientStore,
+    beacon_client: BeaconClient,
+    eth_client: EthClient,
+}
+
+impl EthVerifier {
+    /// Bootstrap a light client for `network` from its configured checkpoint, which is verified
+    /// against the known-good checkpoints first, see [verify_bootstrap_config] for
+    /// `trust_configured_checkpoints`.
+    pub async fn new(
+        network: &EthNetworkConfig,
+        trust_configured_checkpoints: bool,
+    ) -> SuiRpcResult<Self> {
+        let beacon_client = network.build_beacon_client().ok_or_else(|| {
+            Error::DataError("no consensus RPC configured for the network".to_string())
+        })?;
+        let checkpoint = verify_bootstrap_config(network, trust_configured_checkpoints)?;
+        let bootstrap =
+            fetch_bootstrap(network, &beacon_client, trust_configured_checkpoints).await?;
+        let store = LightClientStore::new(
+            network
+                .genesis_validators_root
+                .expect("verified by verify_bootstrap_config"),
+            known_forks(network)?,
+            &checkpoint.block_root,
+            bootstrap.data,
+        )?;
+        Ok(Self::from_store(
+            store,
+            beacon_client,
+            network.build_client(),
+        ))
+    }
+
+    /// Resume from a light client store the application trusts, such as one it persisted.
+    pub fn from_store(
+        store: LightClientStore,
+        beacon_client: BeaconClient,
+        eth_client: EthClient,
+    ) -> Self {
+        Self {
+            store,
+            beacon_client,
+            eth_client,
+        }
+    }
+
+    pub fn store(&self) -> &LightClientStore {
+        &self.store
+    }
+
+    /// The execution header of the latest finalized block the light client verified.
+    pub fn finalized_execution_header(&self) -> &ExecutionPayloadHeader {
+        &self.store.finalized_header.execution
+    }
+
+    /// Advance the light client to the latest finalized header, verifying every update.
+    pub async fn sync(&mut self) -> SuiRpcResult<()> {
+        let (updates, _) = fetch_updates(&self.beacon_client, &self.store).await?;
+        for update in updates {
+            if !is_stale_update(&self.store, &update) {
+                self.store.apply_update(&update)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Verify the account `address` and its storage `slots` at the latest finalized block.
+    pub async fn verify_account(
+        &self,
+        address: &[u8; 20],
+        slots: &[[u8; 32]],
+    ) -> SuiRpcResult<VerifiedAccount> {
+        let header = self.finalized_execution_header();
+        let proof = self
+            .eth_client
+            .proof(address, slots, header.block_number)
+            .await?;
+
+        let account =
+            eth_proofs::verify_account_proof(&header.state_root, address, &proof.account_proof)?;
+        let storage_root = account
+            .as_ref()
+            .map_or(eth_proofs::EMPTY_TRIE_ROOT, |account| account.storage_root);
+        let storage = proof
+            .storage_proofs
+            .iter()
+            .map(|storage_proof| {
+                eth_proofs::verify_storage_proof(
+                    &storage_root,
+                    &storage_proof.slot,
+                    &storage_proof.proof,
+                )
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(VerifiedAccount {
+            block_number: header.block_number,
+            address: *address,
+            account,
+            storage,
+        })
+    }
+
+    /// Verify the receipt of the transaction at `transaction_index` of the latest finalized block
+    /// against `proof`, the nodes of its receipts trie on the path to the receipt.
+    pub fn verify_receipt(
+        &self,
+        transaction_index: u64,
+        proof: &[Vec<u8>],
+    ) -> SuiRpcResult<Receipt> {
+        Ok(eth_proofs::verify_receipt_proof(
+            &self.finalized_execution_header().receipts_root,
+            transaction_index,
+            proof,
+        )?)
+    }
+}
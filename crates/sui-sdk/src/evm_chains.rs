@@ -0,0 +1,97 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! A registry of EVM chain parameters.
+//!
+//! The parameters of the chains the dWallet network knows are compiled in, and the configuration
+//! of an Ethereum network can set or override any of them, so supporting a new EVM chain, such as
+//! an L2, only takes an `eth_networks` entry in the client config.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::eth_client::EthFinality;
+use crate::sui_client_config::EthNetworkConfig;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NativeCurrency {
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+impl Default for NativeCurrency {
+    fn default() -> Self {
+        Self {
+            symbol: "ETH".to_string(),
+            decimals: 18,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvmChain {
+    pub name: String,
+    pub chain_id: u64,
+    pub finality: EthFinality,
+    pub native_currency: NativeCurrency,
+    pub explorer_url: Option<String>,
+}
+
+impl EvmChain {
+    /// The explorer page of the transaction `hash`, a `0x` prefixed hex string.
+    pub fn transaction_url(&self, hash: &str) -> Option<String> {
+        self.explorer_url
+            .as_ref()
+            .map(|url| format!("{}/tx/{hash}", url.trim_end_matches('/')))
+    }
+
+    /// The explorer page of the account `address`, a `0x` prefixed hex string.
+    pub fn address_url(&self, address: &str) -> Option<String> {
+        self.explorer_url
+            .as_ref()
+            .map(|url| format!("{}/address/{address}", url.trim_end_matches('/')))
+    }
+}
+
+static EVM_CHAINS: Lazy<Vec<EvmChain>> = Lazy::new(|| {
+    serde_json::from_str(include_str!("evm_chains.json"))
+        .expect("the compiled-in EVM chains are valid")
+});
+
+/// The compiled-in EVM chains.
+pub fn evm_chains() -> &'static [EvmChain] {
+    &EVM_CHAINS
+}
+
+/// The compiled-in parameters of the chain `chain_id`, if it is a known chain.
+pub fn evm_chain(chain_id: u64) -> Option<&'static EvmChain> {
+    EVM_CHAINS.iter().find(|chain| chain.chain_id == chain_id)
+}
+
+impl EthNetworkConfig {
+    /// The parameters of the chain of the network: the configured ones, falling back to the
+    /// compiled-in ones of the chain, and to the defaults of Ethereum for an unknown chain.
+    pub fn evm_chain(&self) -> EvmChain {
+        let known = evm_chain(self.chain_id);
+        EvmChain {
+            name: known.map_or_else(
+                || format!("EVM chain {}", self.chain_id),
+                |chain| chain.name.clone(),
+            ),
+            chain_id: self.chain_id,
+            finality: self
+                .finality
+                .or(known.map(|chain| chain.finality))
+                .unwrap_or_default(),
+            native_currency: self
+                .native_currency
+                .clone()
+                .or_else(|| known.map(|chain| chain.native_currency.clone()))
+                .unwrap_or_default(),
+            explorer_url: self
+                .explorer_url
+                .clone()
+                .or_else(|| known.and_then(|chain| chain.explorer_url.clone())),
+        }
+    }
+}
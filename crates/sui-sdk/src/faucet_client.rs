@@ -0,0 +1,246 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! A client of the HTTP API of the faucet of a network, to fund addresses on devnet, testnet or a
+//! local network from tests and onboarding flows.
+//!
+//! Requests are queued by the faucet, which sends the coins in batches: [FaucetClient::request_gas]
+//! submits a request, retrying it with exponential backoff while the faucet is unreachable or
+//! overloaded, and polls its status until the coins are sent.
+//!
+//! Faucets limit the requests of an address or IP over hours, so a rate limited request is not
+//! retried but fails with [Error::FaucetRateLimited].
+
+use std::time::{Duration, Instant};
+
+use backoff::future::retry;
+use backoff::ExponentialBackoff;
+use reqwest::header::RETRY_AFTER;
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use tokio::time::sleep;
+
+use crate::error::{Error, SuiRpcResult};
+use crate::SUI_LOCAL_NETWORK_FAUCET_URL;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120);
+const DEFAULT_RETRY_MAX_ELAPSED_TIME: Duration = Duration::from_secs(60);
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A coin sent by the faucet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FaucetCoin {
+    pub amount: u64,
+    pub id: ObjectID,
+    pub transfer_tx_digest: TransactionDigest,
+}
+
+/// The status of a request to the faucet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FaucetRequestStatus {
+    /// The request is queued, or its coins are being sent.
+    InProgress,
+    /// The coins of the request were sent.
+    Succeeded(Vec<FaucetCoin>),
+    /// The faucet gave up on the request.
+    Discarded,
+}
+
+#[derive(Deserialize)]
+struct BatchFaucetResponse {
+    task: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BatchStatusFaucetResponse {
+    status: Option<BatchSendStatus>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BatchSendStatus {
+    status: String,
+    transferred_gas_objects: Option<FaucetReceipt>,
+}
+
+#[derive(Deserialize)]
+struct FaucetReceipt {
+    sent: Vec<FaucetCoin>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FaucetClient {
+    http: reqwest::Client,
+    url: String,
+    timeout: Duration,
+    retry_max_elapsed_time: Duration,
+}
+
+impl FaucetClient {
+    /// A client of the faucet at `url`, e.g. `http://127.0.0.1:5003`.
+    pub fn new(url: impl AsRef<str>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: url.as_ref().trim_end_matches('/').to_string(),
+            timeout: DEFAULT_TIMEOUT,
+            retry_max_elapsed_time: DEFAULT_RETRY_MAX_ELAPSED_TIME,
+        }
+    }
+
+    /// A client of the faucet of the local network started by `sui start`.
+    pub fn localnet() -> Self {
+        Self::new(SUI_LOCAL_NETWORK_FAUCET_URL)
+    }
+
+    /// Send the requests with `http`, e.g. to go through a proxy.
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// How long [Self::request_gas] waits for the coins of a request to be sent.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// How long to retry requests while the faucet is unreachable or overloaded.
+    pub fn with_retry_max_elapsed_time(mut self, max_elapsed_time: Duration) -> Self {
+        self.retry_max_elapsed_time = max_elapsed_time;
+        self
+    }
+
+    /// Request coins for `recipient`, and wait for the faucet to send them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use sui_sdk::faucet_client::FaucetClient;
+    /// use sui_sdk::types::base_types::SuiAddress;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), anyhow::Error> {
+    ///     let coins = FaucetClient::localnet()
+    ///         .request_gas(SuiAddress::random_for_testing_only())
+    ///         .await?;
+    ///     println!("{coins:?}");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn request_gas(&self, recipient: SuiAddress) -> SuiRpcResult<Vec<FaucetCoin>> {
+        let task = self.submit_request(recipient).await?;
+        self.wait_for_request(&task).await
+    }
+
+    /// Request coins for `recipient`, returning the id of the request to pass to
+    /// [Self::request_status].
+    pub async fn submit_request(&self, recipient: SuiAddress) -> SuiRpcResult<String> {
+        let body = json!({ "FixedAmountRequest": { "recipient": recipient } });
+        let response: BatchFaucetResponse = self
+            .send(|| self.http.post(format!("{}/v1/gas", self.url)).json(&body))
+            .await?;
+        match (response.task, response.error) {
+            (_, Some(error)) => Err(Error::FaucetError(error)),
+            (Some(task), None) => Ok(task),
+            (None, None) => Err(Error::FaucetError(
+                "no request id in the response of the faucet".to_string(),
+            )),
+        }
+    }
+
+    /// The status of the request `task`.
+    pub async fn request_status(&self, task: &str) -> SuiRpcResult<FaucetRequestStatus> {
+        let response: BatchStatusFaucetResponse = self
+            .send(|| self.http.get(format!("{}/v1/status/{task}", self.url)))
+            .await?;
+        if let Some(error) = response.error {
+            return Err(Error::FaucetError(error));
+        }
+        let status = response.status.ok_or_else(|| {
+            Error::FaucetError(format!("no status of request {task} from the faucet"))
+        })?;
+        match status.status.as_str() {
+            "INPROGRESS" => Ok(FaucetRequestStatus::InProgress),
+            "SUCCEEDED" => Ok(FaucetRequestStatus::Succeeded(
+                status
+                    .transferred_gas_objects
+                    .map(|receipt| receipt.sent)
+                    .unwrap_or_default(),
+            )),
+            "DISCARDED" => Ok(FaucetRequestStatus::Discarded),
+            other => Err(Error::FaucetError(format!(
+                "unknown status {other} of request {task}"
+            ))),
+        }
+    }
+
+    /// Poll the status of the request `task` until its coins are sent.
+    pub async fn wait_for_request(&self, task: &str) -> SuiRpcResult<Vec<FaucetCoin>> {
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            match self.request_status(task).await? {
+                FaucetRequestStatus::Succeeded(coins) => return Ok(coins),
+                FaucetRequestStatus::Discarded => {
+                    return Err(Error::FaucetError(format!(
+                        "request {task} was discarded by the faucet"
+                    )))
+                }
+                FaucetRequestStatus::InProgress => {}
+            }
+            if Instant::now() >= deadline {
+                return Err(Error::FaucetError(format!(
+                    "timed out waiting for request {task} to be served"
+                )));
+            }
+            sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: impl Fn() -> reqwest::RequestBuilder,
+    ) -> SuiRpcResult<T> {
+        let backoff = ExponentialBackoff {
+            max_elapsed_time: Some(self.retry_max_elapsed_time),
+            ..ExponentialBackoff::default()
+        };
+        retry(backoff, || async {
+            let response = request().send().await.map_err(|e| {
+                backoff::Error::transient(Error::FaucetError(format!(
+                    "cannot reach {}: {e}",
+                    self.url
+                )))
+            })?;
+            let status = response.status();
+            if status == StatusCode::TOO_MANY_REQUESTS {
+                let retry_after_secs = response
+                    .headers()
+                    .get(RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok()?.trim().parse().ok());
+                return Err(backoff::Error::permanent(Error::FaucetRateLimited {
+                    url: self.url.clone(),
+                    retry_after_secs,
+                }));
+            }
+            if status == StatusCode::SERVICE_UNAVAILABLE {
+                return Err(backoff::Error::transient(Error::FaucetError(format!(
+                    "{} returned {status}",
+                    self.url
+                ))));
+            }
+            // The faucet reports its errors in the body of the response.
+            response.json().await.map_err(|e| {
+                backoff::Error::permanent(Error::FaucetError(format!(
+                    "invalid response from {} ({status}): {e}",
+                    self.url
+                )))
+            })
+        })
+        .await
+    }
+}
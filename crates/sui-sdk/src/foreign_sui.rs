@@ -0,0 +1,78 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Custody on other Sui networks, such as Sui mainnet, with Ed25519 dWallets.
+//!
+//! A Sui signature commits to the Blake2b256 digest of the BCS encoded intent message of the
+//! transaction, so this module wraps the transaction data in its intent message, has the dWallet
+//! of an [Ed25519DWalletSigner] sign the digest, and assembles the signed transaction to submit
+//! with `sui_executeTransactionBlock` on the other network.
+//!
+//! The transaction data must be built against the other network, with its objects and reference
+//! gas price, and only use the transaction kinds the two networks share.
+
+use fastcrypto::ed25519::{Ed25519PublicKey, Ed25519Signature};
+use fastcrypto::hash::HashFunction;
+use fastcrypto::traits::{ToFromBytes, VerifyingKey};
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{DefaultHash, Signature, SignatureScheme};
+use sui_types::transaction::{Transaction, TransactionData, TransactionDataAPI};
+
+use crate::dwallet_signer::Ed25519DWalletSigner;
+use crate::error::{Error, SuiRpcResult};
+
+/// The Sui address of an Ed25519 public key, the same on every Sui network.
+pub fn address(public_key: &[u8; 32]) -> SuiRpcResult<SuiAddress> {
+    let public_key = Ed25519PublicKey::from_bytes(public_key)
+        .map_err(|e| Error::DataError(format!("invalid Ed25519 public key: {e}")))?;
+    Ok(SuiAddress::from(&public_key))
+}
+
+/// The digest signed by the sender of `tx_data`: the Blake2b256 hash of its intent message.
+pub fn signing_digest(tx_data: &TransactionData) -> [u8; 32] {
+    let intent_message = IntentMessage::new(Intent::sui_transaction(), tx_data);
+    let mut hasher = DefaultHash::default();
+    hasher.update(bcs::to_bytes(&intent_message).expect("transaction data is serializable"));
+    hasher.finalize().digest
+}
+
+/// Sign `tx_data`, of which the dWallet of `signer` must be the sender and the gas owner.
+pub async fn sign_transaction(
+    tx_data: TransactionData,
+    signer: &impl Ed25519DWalletSigner,
+) -> SuiRpcResult<Transaction> {
+    let public_key = signer.public_key();
+    let address = address(&public_key)?;
+    if tx_data.sender() != address || tx_data.gas_owner() != address {
+        return Err(Error::DataError(format!(
+            "the dWallet {address} must be the sender and the gas owner of the transaction"
+        )));
+    }
+
+    let digest = signing_digest(&tx_data);
+    let signature = signer
+        .sign(vec![digest.to_vec()])
+        .await?
+        .pop()
+        .ok_or_else(|| Error::DataError("no signature returned by the dWallet".to_string()))?;
+
+    let verifying_key = Ed25519PublicKey::from_bytes(&public_key)
+        .map_err(|e| Error::DataError(format!("invalid dWallet public key: {e}")))?;
+    let sig = Ed25519Signature::from_bytes(&signature)
+        .map_err(|e| Error::DataError(format!("invalid dWallet signature: {e}")))?;
+    verifying_key
+        .verify(&digest, &sig)
+        .map_err(|e| Error::DataError(format!("dWallet signature does not verify: {e}")))?;
+
+    let signature = Signature::from_bytes(
+        &[
+            &[SignatureScheme::ED25519.flag()][..],
+            sig.as_ref(),
+            verifying_key.as_ref(),
+        ]
+        .concat(),
+    )
+    .map_err(|e| Error::DataError(format!("invalid dWallet signature: {e}")))?;
+    Ok(Transaction::from_data(tx_data, vec![signature]))
+}
@@ -0,0 +1,197 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! The HTTP transport of the JSON-RPC client of [crate::SuiClient].
+//!
+//! Requests are posted with [reqwest], so that the connection to the fullnode can be configured
+//! like the other HTTP clients of the SDK, e.g. to go through an HTTP or SOCKS5 proxy. Requests
+//! that fail to reach a fullnode, or that it fails to serve, are retried with exponential backoff,
+//! failing over to the next endpoint. Every JSON-RPC method of the fullnode is safe to retry:
+//! reads are idempotent, and executing a transaction again only returns its effects.
+
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use backoff::future::retry;
+use backoff::ExponentialBackoff;
+use governor::DefaultDirectRateLimiter;
+use jsonrpsee::core::client::{ReceivedMessage, TransportReceiverT, TransportSenderT};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+
+/// The JSON-RPC error code of requests that failed before reaching a fullnode.
+const TRANSPORT_ERROR_CODE: i64 = -32000;
+
+/// The fullnode endpoints of a client, in order of preference.
+pub(crate) struct HttpEndpoints {
+    client: reqwest::Client,
+    urls: Vec<String>,
+    /// The endpoint requests are sent to, until it fails.
+    active: AtomicUsize,
+    /// Limits the requests sent, retries included, possibly shared with other clients.
+    rate_limiter: Option<Arc<DefaultDirectRateLimiter>>,
+}
+
+impl HttpEndpoints {
+    pub(crate) fn new(
+        client: reqwest::Client,
+        urls: Vec<String>,
+        rate_limiter: Option<Arc<DefaultDirectRateLimiter>>,
+    ) -> Self {
+        assert!(!urls.is_empty(), "at least one endpoint is required");
+        Self {
+            client,
+            urls,
+            active: AtomicUsize::new(0),
+            rate_limiter,
+        }
+    }
+
+    async fn post(&self, body: &str, max_elapsed_time: Duration) -> Result<String, String> {
+        let backoff = ExponentialBackoff {
+            max_elapsed_time: Some(max_elapsed_time),
+            ..ExponentialBackoff::default()
+        };
+        retry(backoff, || async {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.until_ready().await;
+            }
+            let active = self.active.load(Ordering::Relaxed);
+            let result = self.post_to(&self.urls[active], body).await;
+            if let Err(backoff::Error::Transient { .. }) = result {
+                // Unless a concurrent request already failed over.
+                let _ = self.active.compare_exchange(
+                    active,
+                    (active + 1) % self.urls.len(),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                );
+            }
+            result
+        })
+        .await
+    }
+
+    async fn post_to(&self, url: &str, body: &str) -> Result<String, backoff::Error<String>> {
+        let response = self
+            .client
+            .post(url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .map_err(|e| backoff::Error::transient(format!("cannot reach {url}: {e}")))?;
+
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+            return Err(backoff::Error::transient(format!(
+                "{url} returned {status}"
+            )));
+        }
+        let text = response
+            .text()
+            .await
+            .map_err(|e| backoff::Error::transient(format!("cannot read from {url}: {e}")))?;
+        // Notifications have no response.
+        if (text.trim().is_empty() && status.is_success())
+            || serde_json::from_str::<Value>(&text).is_ok()
+        {
+            Ok(text)
+        } else {
+            Err(backoff::Error::permanent(format!(
+                "{url} returned {status}"
+            )))
+        }
+    }
+}
+
+pub(crate) fn http_transport(
+    endpoints: Arc<HttpEndpoints>,
+    max_elapsed_time: Duration,
+) -> (HttpSender, HttpReceiver) {
+    let (responses_tx, responses_rx) = mpsc::unbounded_channel();
+    (
+        HttpSender {
+            endpoints,
+            max_elapsed_time,
+            responses: responses_tx,
+        },
+        HttpReceiver {
+            responses: responses_rx,
+        },
+    )
+}
+
+pub(crate) struct HttpSender {
+    endpoints: Arc<HttpEndpoints>,
+    max_elapsed_time: Duration,
+    responses: mpsc::UnboundedSender<String>,
+}
+
+#[async_trait]
+impl TransportSenderT for HttpSender {
+    type Error = io::Error;
+
+    async fn send(&mut self, body: String) -> Result<(), Self::Error> {
+        // The requests are posted concurrently, the client matches the responses to them by id.
+        let endpoints = self.endpoints.clone();
+        let max_elapsed_time = self.max_elapsed_time;
+        let responses = self.responses.clone();
+        tokio::spawn(async move {
+            let response = match endpoints.post(&body, max_elapsed_time).await {
+                Ok(text) if text.trim().is_empty() => return,
+                Ok(text) => Some(text),
+                Err(e) => error_response(&body, &e),
+            };
+            if let Some(response) = response {
+                let _ = responses.send(response);
+            }
+        });
+        Ok(())
+    }
+}
+
+pub(crate) struct HttpReceiver {
+    responses: mpsc::UnboundedReceiver<String>,
+}
+
+#[async_trait]
+impl TransportReceiverT for HttpReceiver {
+    type Error = io::Error;
+
+    async fn receive(&mut self) -> Result<ReceivedMessage, Self::Error> {
+        self.responses
+            .recv()
+            .await
+            .map(ReceivedMessage::Text)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::BrokenPipe, "HTTP transport closed"))
+    }
+}
+
+/// The JSON-RPC error response to the request, or batch of requests, `request` that failed with
+/// `message`. Failing the requests one by one, rather than the transport, keeps the client alive.
+fn error_response(request: &str, message: &str) -> Option<String> {
+    let error = |id: &Value| {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": TRANSPORT_ERROR_CODE, "message": message },
+        })
+    };
+    let response = match serde_json::from_str::<Value>(request).ok()? {
+        Value::Array(requests) => Value::Array(
+            requests
+                .iter()
+                .filter_map(|request| request.get("id"))
+                .map(error)
+                .collect(),
+        ),
+        request => error(request.get("id")?),
+    };
+    Some(response.to_string())
+}
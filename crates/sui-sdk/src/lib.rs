@@ -11,6 +11,7 @@
 //! The API is split in several parts corresponding to different functionalities
 //! as following:
 //! * [CoinReadApi] - provides read-only functions to work with the coins
+//! * [DWalletApi] - provides functionality related to dWallets
 //! * [EventApi] - provides event related functions functions to
 //! * [GovernanceApi] - provides functionality related to staking
 //! * [QuorumDriverApi] - provides functionality to execute a transaction
@@ -72,15 +73,20 @@
 
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::num::{NonZeroU32, NonZeroUsize};
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
-use jsonrpsee::core::client::ClientT;
-use jsonrpsee::http_client::{HeaderMap, HeaderValue, HttpClient, HttpClientBuilder};
+use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
+use jsonrpsee::core::client::{Client, ClientBuilder, ClientT};
 use jsonrpsee::rpc_params;
 use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use serde_json::Value;
+use tracing::warn;
 
 use move_core_types::language_storage::StructTag;
 pub use sui_json as json;
@@ -90,27 +96,62 @@ use sui_json_rpc_api::{
 pub use sui_json_rpc_types as rpc_types;
 use sui_json_rpc_types::{
     ObjectsPage, SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse,
-    SuiObjectResponseQuery,
+    SuiObjectResponseQuery, SuiTransactionBlockEffectsAPI,
 };
+use sui_protocol_config::{Chain, ProtocolConfig};
 use sui_transaction_builder::{DataReader, TransactionBuilder};
 pub use sui_types as types;
 use sui_types::base_types::{ObjectID, ObjectInfo, SuiAddress};
+use sui_types::digests::{get_mainnet_chain_identifier, get_testnet_chain_identifier};
+use sui_types::gas::GasCostSummary;
+use sui_types::transaction::TransactionKind;
 
-use crate::apis::{CoinReadApi, EventApi, GovernanceApi, QuorumDriverApi, ReadApi};
+use crate::apis::{CoinReadApi, DWalletApi, EventApi, GovernanceApi, QuorumDriverApi, ReadApi};
+use crate::cache::ResponseCache;
 use crate::error::{Error, SuiRpcResult};
+use crate::http_transport::{http_transport, HttpEndpoints};
 
 pub mod apis;
+pub mod attestation;
+pub mod beacon_client;
+pub mod btc;
+pub mod btc_client;
+mod cache;
+pub mod client_manager;
+pub mod cosmos;
+pub mod dwallet;
+pub mod dwallet_events;
+pub mod dwallet_intent;
+pub mod dwallet_signer;
+pub mod dwallet_transaction_builder;
 pub mod error;
+pub mod eth;
+pub mod eth_client;
+pub mod eth_light_client;
+pub mod eth_verifier;
+pub mod evm_chains;
+pub mod faucet_client;
+pub mod foreign_sui;
+mod http_transport;
 pub mod json_rpc_error;
+pub mod offline;
+pub mod sign_proof;
+pub mod sol;
+pub mod sponsor;
 pub mod sui_client_config;
 pub mod wallet_context;
 
 pub const SUI_COIN_TYPE: &str = "0x2::dwlt::DWLT";
 pub const SUI_LOCAL_NETWORK_URL: &str = "http://127.0.0.1:9000";
 pub const SUI_LOCAL_NETWORK_GAS_URL: &str = "http://127.0.0.1:5003/gas";
+pub const SUI_LOCAL_NETWORK_FAUCET_URL: &str = "http://127.0.0.1:5003";
 pub const SUI_DEVNET_URL: &str = "http://fullnode.devnet.dwallet.cloud:9000";
 pub const SUI_TESTNET_URL: &str = "http://fullnode.alpha.testnet.dwallet.cloud:9000";
 
+/// The gas units added to the computation cost of a transaction by [SuiClient::estimate_gas], for
+/// the cost to change between the estimation and the execution.
+pub const GAS_SAFE_OVERHEAD: u64 = 1_000;
+
 /// A Sui client builder for connecting to the Sui network
 ///
 /// By default the `maximum concurrent requests` is set to 256 and
@@ -139,6 +180,16 @@ pub struct SuiClientBuilder {
     max_concurrent_requests: usize,
     ws_url: Option<String>,
     ws_ping_interval: Option<Duration>,
+    proxy: Option<String>,
+    headers: Vec<(String, String)>,
+    bearer_token: Option<String>,
+    fallback_urls: Vec<String>,
+    retry_max_elapsed_time: Duration,
+    cache: Option<(NonZeroUsize, Duration)>,
+    root_certificates: Vec<Vec<u8>>,
+    client_identity: Option<Vec<u8>>,
+    insecure_skip_verify: bool,
+    rate_limiter: Option<Arc<DefaultDirectRateLimiter>>,
 }
 
 impl Default for SuiClientBuilder {
@@ -148,6 +199,16 @@ impl Default for SuiClientBuilder {
             max_concurrent_requests: 256,
             ws_url: None,
             ws_ping_interval: None,
+            proxy: None,
+            headers: vec![],
+            bearer_token: None,
+            fallback_urls: vec![],
+            retry_max_elapsed_time: Duration::from_secs(10),
+            cache: None,
+            root_certificates: vec![],
+            client_identity: None,
+            insecure_skip_verify: false,
+            rate_limiter: None,
         }
     }
 }
@@ -177,6 +238,107 @@ impl SuiClientBuilder {
         self
     }
 
+    /// Send the HTTP requests to the Sui network through the HTTP or SOCKS5 proxy at `url`, e.g.
+    /// `http://proxy.example.com:3128`, or `socks5h://127.0.0.1:9050` for Tor.
+    ///
+    /// WebSocket connections cannot go through a proxy, so it can't be combined with a WebSocket URL.
+    pub fn proxy(mut self, url: impl AsRef<str>) -> Self {
+        self.proxy = Some(url.as_ref().to_string());
+        self
+    }
+
+    /// Add the header `name: value` to every request, e.g. the API key header of an RPC provider.
+    pub fn header(mut self, name: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        self.headers
+            .push((name.as_ref().to_string(), value.as_ref().to_string()));
+        self
+    }
+
+    /// Authenticate every request with the header `Authorization: Bearer <token>`.
+    pub fn bearer_token(mut self, token: impl AsRef<str>) -> Self {
+        self.bearer_token = Some(token.as_ref().to_string());
+        self
+    }
+
+    /// Fail over to these endpoints, in order, when the endpoint the client is built with fails.
+    pub fn fallback_urls(mut self, urls: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        self.fallback_urls = urls
+            .into_iter()
+            .map(|url| url.as_ref().to_string())
+            .collect();
+        self
+    }
+
+    /// How long a request that fails to reach the Sui network is retried, with exponential backoff
+    /// and across the endpoints, before failing. Zero disables retries. Defaults to 10 seconds,
+    /// and can be overridden per call with [SuiClient::with_retry_max_elapsed_time].
+    pub fn retry_max_elapsed_time(mut self, max_elapsed_time: Duration) -> Self {
+        self.retry_max_elapsed_time = max_elapsed_time;
+        self
+    }
+
+    /// Cache up to `capacity` responses that cannot change, for `ttl` each: immutable objects,
+    /// such as packages and the DKG outputs of dWallets, past versions of objects, and
+    /// checkpoints. The cache is shared by the clones of the client.
+    pub fn cache(mut self, capacity: NonZeroUsize, ttl: Duration) -> Self {
+        self.cache = Some((capacity, ttl));
+        self
+    }
+
+    /// Trust the PEM encoded CA certificates of `pem` for the HTTPS connections to the Sui network,
+    /// besides the built-in root certificates, e.g. the CA of a private RPC endpoint.
+    ///
+    /// WebSocket connections only trust the built-in root certificates, so it can't be combined
+    /// with a WebSocket URL.
+    pub fn root_certificate(mut self, pem: impl AsRef<[u8]>) -> Self {
+        self.root_certificates.push(pem.as_ref().to_vec());
+        self
+    }
+
+    /// Authenticate the HTTPS connections to the Sui network with the PEM encoded client
+    /// certificate chain `certificate_chain` and its private key `private_key`, for endpoints
+    /// requiring mutual TLS.
+    ///
+    /// WebSocket connections cannot present a client certificate, so it can't be combined with a
+    /// WebSocket URL.
+    pub fn client_certificate(
+        mut self,
+        certificate_chain: impl AsRef<[u8]>,
+        private_key: impl AsRef<[u8]>,
+    ) -> Self {
+        self.client_identity =
+            Some([private_key.as_ref(), b"\n", certificate_chain.as_ref()].concat());
+        self
+    }
+
+    /// Skip the verification of the certificates of the HTTPS endpoints, e.g. self-signed
+    /// certificates of a development network. For development only: the connections are then
+    /// open to man-in-the-middle attacks.
+    ///
+    /// WebSocket connections always verify the certificates, so it can't be combined with a
+    /// WebSocket URL.
+    pub fn insecure_skip_verify(mut self, skip: bool) -> Self {
+        self.insecure_skip_verify = skip;
+        self
+    }
+
+    /// Send at most `requests_per_second` HTTP requests per second to the Sui network, retries
+    /// included, e.g. to stay under the quota of an RPC provider.
+    pub fn requests_per_second(mut self, requests_per_second: NonZeroU32) -> Self {
+        self.rate_limiter = Some(Arc::new(RateLimiter::direct(Quota::per_second(
+            requests_per_second,
+        ))));
+        self
+    }
+
+    /// Rate limit the HTTP requests to the Sui network with `rate_limiter`, which may be shared
+    /// with other clients for their requests to count against the same quota. See
+    /// [client_manager::ClientManager].
+    pub fn rate_limiter(mut self, rate_limiter: Arc<DefaultDirectRateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
     /// Returns a [SuiClient] object connected to the Sui network running at the URI provided.
     ///
     /// # Examples
@@ -207,50 +369,113 @@ impl SuiClientBuilder {
             HeaderValue::from_static(client_version),
         );
         headers.insert(CLIENT_SDK_TYPE_HEADER, HeaderValue::from_static("rust"));
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| Error::InvalidConfig(format!("Invalid header name [{name}]: {e}")))?;
+            let value = HeaderValue::from_str(value).map_err(|e| {
+                Error::InvalidConfig(format!("Invalid value of header [{name}]: {e}"))
+            })?;
+            headers.insert(name, value);
+        }
+        if let Some(token) = &self.bearer_token {
+            let mut value = HeaderValue::from_str(&format!("Bearer {token}"))
+                .map_err(|e| Error::InvalidConfig(format!("Invalid bearer token: {e}")))?;
+            value.set_sensitive(true);
+            headers.insert(AUTHORIZATION, value);
+        }
 
-        let ws = if let Some(url) = self.ws_url {
-            let mut builder = WsClientBuilder::default()
-                .max_request_body_size(2 << 30)
-                .max_concurrent_requests(self.max_concurrent_requests)
-                .set_headers(headers.clone())
-                .request_timeout(self.request_timeout);
-
-            if let Some(duration) = self.ws_ping_interval {
-                builder = builder.ping_interval(duration)
-            }
+        if self.proxy.is_some() && self.ws_url.is_some() {
+            return Err(Error::InvalidConfig(
+                "WebSocket connections cannot go through a proxy".to_string(),
+            ));
+        }
+        if !self.root_certificates.is_empty() && self.ws_url.is_some() {
+            return Err(Error::InvalidConfig(
+                "WebSocket connections cannot trust custom root certificates".to_string(),
+            ));
+        }
+        if self.client_identity.is_some() && self.ws_url.is_some() {
+            return Err(Error::InvalidConfig(
+                "WebSocket connections cannot present a client certificate".to_string(),
+            ));
+        }
+        if self.insecure_skip_verify && self.ws_url.is_some() {
+            return Err(Error::InvalidConfig(
+                "WebSocket connections cannot skip the verification of certificates".to_string(),
+            ));
+        }
 
-            Some(builder.build(url).await?)
-        } else {
-            None
+        let ws_config = self.ws_url.map(|url| WsConfig {
+            url,
+            headers: headers.clone(),
+            max_concurrent_requests: self.max_concurrent_requests,
+            request_timeout: self.request_timeout,
+            ping_interval: self.ws_ping_interval,
+        });
+        let ws = match &ws_config {
+            Some(ws_config) => Some(ws_config.connect().await?),
+            None => None,
         };
 
-        let http = HttpClientBuilder::default()
-            .max_request_body_size(2 << 30)
-            .max_concurrent_requests(self.max_concurrent_requests)
-            .set_headers(headers.clone())
-            .request_timeout(self.request_timeout)
-            .build(http)?;
+        let mut http_client = reqwest::Client::builder()
+            .default_headers(headers)
+            .timeout(self.request_timeout);
+        if let Some(proxy) = &self.proxy {
+            let proxy = reqwest::Proxy::all(proxy)
+                .map_err(|e| Error::InvalidConfig(format!("Invalid proxy URL [{proxy}]: {e}")))?;
+            http_client = http_client.proxy(proxy);
+        }
+        for pem in &self.root_certificates {
+            let certificates = rustls_pemfile::certs(&mut pem.as_slice())
+                .map_err(|e| Error::InvalidConfig(format!("Invalid root certificate: {e}")))?;
+            if certificates.is_empty() {
+                return Err(Error::InvalidConfig(
+                    "No PEM encoded root certificate found".to_string(),
+                ));
+            }
+            for der in certificates {
+                let certificate = reqwest::Certificate::from_der(&der)
+                    .map_err(|e| Error::InvalidConfig(format!("Invalid root certificate: {e}")))?;
+                http_client = http_client.add_root_certificate(certificate);
+            }
+        }
+        if let Some(pem) = &self.client_identity {
+            let identity = reqwest::Identity::from_pem(pem)
+                .map_err(|e| Error::InvalidConfig(format!("Invalid client certificate: {e}")))?;
+            http_client = http_client.identity(identity);
+        }
+        if self.insecure_skip_verify {
+            warn!("Skipping the verification of the certificates of the Sui network endpoints");
+            http_client = http_client.danger_accept_invalid_certs(true);
+        }
+        let http_client = http_client
+            .build()
+            .map_err(|e| Error::InvalidConfig(e.to_string()))?;
+        let urls = std::iter::once(http.as_ref().to_string())
+            .chain(self.fallback_urls)
+            .collect();
+        let endpoints = Arc::new(HttpEndpoints::new(http_client, urls, self.rate_limiter));
+        let http = RpcClient::http_client(
+            endpoints.clone(),
+            self.max_concurrent_requests,
+            self.request_timeout,
+            self.retry_max_elapsed_time,
+        );
 
         let info = Self::get_server_info(&http, &ws).await?;
 
-        let rpc = RpcClient { http, ws, info };
-        let api = Arc::new(rpc);
-        let read_api = Arc::new(ReadApi::new(api.clone()));
-        let quorum_driver_api = QuorumDriverApi::new(api.clone());
-        let event_api = EventApi::new(api.clone());
-        let transaction_builder = TransactionBuilder::new(read_api.clone());
-        let coin_read_api = CoinReadApi::new(api.clone());
-        let governance_api = GovernanceApi::new(api.clone());
-
-        Ok(SuiClient {
-            api,
-            transaction_builder,
-            read_api,
-            coin_read_api,
-            event_api,
-            quorum_driver_api,
-            governance_api,
-        })
+        Ok(SuiClient::new(RpcClient {
+            http,
+            ws: ws.map(Arc::new),
+            ws_config,
+            info: Arc::new(info),
+            endpoints,
+            max_concurrent_requests: self.max_concurrent_requests,
+            request_timeout: self.request_timeout,
+            cache: self
+                .cache
+                .map(|(capacity, ttl)| Arc::new(ResponseCache::new(capacity, ttl))),
+        }))
     }
 
     /// Returns a [SuiClient] object that is ready to interact with the local
@@ -327,10 +552,7 @@ impl SuiClientBuilder {
     /// Return the server information as a `ServerInfo` structure.
     ///
     /// Fails with an error if it cannot call the RPC discover.
-    async fn get_server_info(
-        http: &HttpClient,
-        ws: &Option<WsClient>,
-    ) -> Result<ServerInfo, Error> {
+    async fn get_server_info(http: &Client, ws: &Option<WsClient>) -> Result<ServerInfo, Error> {
         let rpc_spec: Value = http.request("rpc.discover", rpc_params![]).await?;
         let version = rpc_spec
             .pointer("/info/version")
@@ -414,12 +636,71 @@ pub struct SuiClient {
     event_api: EventApi,
     quorum_driver_api: QuorumDriverApi,
     governance_api: GovernanceApi,
+    dwallet_api: DWalletApi,
 }
 
 pub(crate) struct RpcClient {
-    http: HttpClient,
-    ws: Option<WsClient>,
-    info: ServerInfo,
+    http: Client,
+    ws: Option<Arc<WsClient>>,
+    ws_config: Option<WsConfig>,
+    info: Arc<ServerInfo>,
+    endpoints: Arc<HttpEndpoints>,
+    max_concurrent_requests: usize,
+    request_timeout: Duration,
+    cache: Option<Arc<ResponseCache>>,
+}
+
+impl RpcClient {
+    fn http_client(
+        endpoints: Arc<HttpEndpoints>,
+        max_concurrent_requests: usize,
+        request_timeout: Duration,
+        retry_max_elapsed_time: Duration,
+    ) -> Client {
+        let (sender, receiver) = http_transport(endpoints, retry_max_elapsed_time);
+        ClientBuilder::default()
+            .max_concurrent_requests(max_concurrent_requests)
+            // Each attempt times out after `request_timeout`.
+            .request_timeout(request_timeout + retry_max_elapsed_time)
+            .build_with_tokio(sender, receiver)
+    }
+
+    /// The cached response of `key`, if the client has a cache.
+    fn cached<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.cache.as_ref()?.get(key)
+    }
+
+    fn cache<T: Serialize>(&self, key: String, value: &T) {
+        if let Some(cache) = &self.cache {
+            cache.put(key, value);
+        }
+    }
+}
+
+/// How to connect a WebSocket client, to reconnect subscriptions when their connection drops.
+#[derive(Clone)]
+struct WsConfig {
+    url: String,
+    headers: HeaderMap,
+    max_concurrent_requests: usize,
+    request_timeout: Duration,
+    ping_interval: Option<Duration>,
+}
+
+impl WsConfig {
+    async fn connect(&self) -> SuiRpcResult<WsClient> {
+        let mut builder = WsClientBuilder::default()
+            .max_request_body_size(2 << 30)
+            .max_concurrent_requests(self.max_concurrent_requests)
+            .set_headers(self.headers.clone())
+            .request_timeout(self.request_timeout);
+
+        if let Some(duration) = self.ping_interval {
+            builder = builder.ping_interval(duration)
+        }
+
+        Ok(builder.build(&self.url).await?)
+    }
 }
 
 impl Debug for RpcClient {
@@ -440,6 +721,67 @@ struct ServerInfo {
 }
 
 impl SuiClient {
+    fn new(rpc: RpcClient) -> Self {
+        let api = Arc::new(rpc);
+        let read_api = Arc::new(ReadApi::new(api.clone()));
+        let quorum_driver_api = QuorumDriverApi::new(api.clone());
+        let event_api = EventApi::new(api.clone());
+        let transaction_builder = TransactionBuilder::new(read_api.clone());
+        let coin_read_api = CoinReadApi::new(api.clone());
+        let governance_api = GovernanceApi::new(api.clone());
+        let dwallet_api = DWalletApi::new(api.clone());
+
+        SuiClient {
+            api,
+            transaction_builder,
+            read_api,
+            coin_read_api,
+            event_api,
+            quorum_driver_api,
+            governance_api,
+            dwallet_api,
+        }
+    }
+
+    /// Returns a client sharing the connections of this one, whose requests are retried for
+    /// `max_elapsed_time`, e.g. zero for calls that should fail fast.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use sui_sdk::SuiClientBuilder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), anyhow::Error> {
+    ///     let sui = SuiClientBuilder::default().build_localnet().await?;
+    ///     let gas_price = sui
+    ///         .with_retry_max_elapsed_time(Duration::ZERO)
+    ///         .read_api()
+    ///         .get_reference_gas_price()
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_retry_max_elapsed_time(&self, max_elapsed_time: Duration) -> SuiClient {
+        let http = RpcClient::http_client(
+            self.api.endpoints.clone(),
+            self.api.max_concurrent_requests,
+            self.api.request_timeout,
+            max_elapsed_time,
+        );
+        SuiClient::new(RpcClient {
+            http,
+            ws: self.api.ws.clone(),
+            ws_config: self.api.ws_config.clone(),
+            info: self.api.info.clone(),
+            endpoints: self.api.endpoints.clone(),
+            max_concurrent_requests: self.api.max_concurrent_requests,
+            request_timeout: self.api.request_timeout,
+            cache: self.api.cache.clone(),
+        })
+    }
+
     /// Returns a list of RPC methods supported by the node the client is connected to.
     pub fn available_rpc_methods(&self) -> &Vec<String> {
         &self.api.info.rpc_methods
@@ -471,11 +813,75 @@ impl SuiClient {
         Ok(())
     }
 
+    /// Estimate the gas budget of the transaction `tx` of `sender`, paying `gas_price`, or else
+    /// the reference gas price, by dev-inspecting it. See [recommended_gas_budget] for the margin
+    /// added to its cost.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use sui_sdk::types::base_types::SuiAddress;
+    /// use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+    /// use sui_sdk::types::transaction::TransactionKind;
+    /// use sui_sdk::SuiClientBuilder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), anyhow::Error> {
+    ///     let sui = SuiClientBuilder::default().build_localnet().await?;
+    ///     let mut builder = ProgrammableTransactionBuilder::new();
+    ///     builder.transfer_sui(SuiAddress::ZERO, Some(1));
+    ///     let gas_budget = sui
+    ///         .estimate_gas(
+    ///             SuiAddress::ZERO,
+    ///             TransactionKind::programmable(builder.finish()),
+    ///             None,
+    ///         )
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn estimate_gas(
+        &self,
+        sender: SuiAddress,
+        tx: TransactionKind,
+        gas_price: Option<u64>,
+    ) -> SuiRpcResult<u64> {
+        let gas_price = match gas_price {
+            Some(gas_price) => gas_price,
+            None => self.read_api.get_reference_gas_price().await?,
+        };
+        let results = self
+            .read_api
+            .dev_inspect_transaction_block(sender, tx, Some(gas_price.into()), None)
+            .await?;
+        if let Some(error) = results.error {
+            return Err(Error::DataError(format!(
+                "cannot estimate the gas of a failing transaction: {error}"
+            )));
+        }
+        Ok(recommended_gas_budget(
+            results.effects.gas_cost_summary(),
+            gas_price,
+        ))
+    }
+
+    /// Drop the responses cached by the client, see [SuiClientBuilder::cache].
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.api.cache {
+            cache.clear();
+        }
+    }
+
     /// Returns a reference to the coin read API.
     pub fn coin_read_api(&self) -> &CoinReadApi {
         &self.coin_read_api
     }
 
+    /// Returns a reference to the dWallet API.
+    pub fn dwallet_api(&self) -> &DWalletApi {
+        &self.dwallet_api
+    }
+
     /// Returns a reference to the event API.
     pub fn event_api(&self) -> &EventApi {
         &self.event_api
@@ -502,16 +908,25 @@ impl SuiClient {
     }
 
     /// Returns a reference to the underlying http client.
-    pub fn http(&self) -> &HttpClient {
+    pub fn http(&self) -> &Client {
         &self.api.http
     }
 
     /// Returns a reference to the underlying WebSocket client, if any.
     pub fn ws(&self) -> Option<&WsClient> {
-        self.api.ws.as_ref()
+        self.api.ws.as_deref()
     }
 }
 
+/// The gas budget of a transaction costing `gas_cost` at `gas_price`: its computation cost, with
+/// [GAS_SAFE_OVERHEAD] more gas units, and its net storage cost if positive.
+pub fn recommended_gas_budget(gas_cost: &GasCostSummary, gas_price: u64) -> u64 {
+    let computation_cost = gas_cost.computation_cost + GAS_SAFE_OVERHEAD * gas_price;
+    (computation_cost + gas_cost.storage_cost)
+        .saturating_sub(gas_cost.storage_rebate)
+        .max(computation_cost)
+}
+
 #[async_trait]
 impl DataReader for ReadApi {
     async fn get_owned_objects(
@@ -564,4 +979,22 @@ impl DataReader for ReadApi {
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
         Ok(self.get_reference_gas_price().await?)
     }
+
+    async fn get_protocol_config(&self) -> Result<ProtocolConfig, anyhow::Error> {
+        let version = self.get_protocol_config(None).await?.protocol_version;
+        let chain_identifier = self.get_chain_identifier().await?;
+        let chain = if chain_identifier == get_mainnet_chain_identifier().to_string() {
+            Chain::Mainnet
+        } else if chain_identifier == get_testnet_chain_identifier().to_string() {
+            Chain::Testnet
+        } else {
+            Chain::Unknown
+        };
+        ProtocolConfig::get_for_version_if_supported(version, chain).ok_or_else(|| {
+            anyhow::anyhow!(
+                "The protocol version {} of the network is not supported by this SDK",
+                version.as_u64()
+            )
+        })
+    }
 }
@@ -0,0 +1,81 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Signing transactions on an air-gapped machine.
+//!
+//! The online machine builds a transaction and writes it, unsigned, to a file with
+//! [write_unsigned_transaction]. The file is carried to an air-gapped machine holding the keys,
+//! which signs it with [sign_transaction_file], and the signed file is carried back and submitted
+//! with [crate::wallet_context::WalletContext::execute_signed_transaction_file].
+//!
+//! The files hold the Base64 encoded BCS bytes of the [TransactionData] of the transaction, or of
+//! its [SenderSignedData] once signed, as printed by the `--serialize-unsigned-transaction` and
+//! `--serialize-signed-transaction` options of the CLI.
+//!
+//! dWallet operations take several transactions, each depending on the response of the network to
+//! the previous one, see [crate::dwallet::DWalletClient::with_offline_signing].
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, Context};
+use fastcrypto::encoding::{Base64, Encoding};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use shared_crypto::intent::Intent;
+use sui_keys::keystore::AccountKeystore;
+use sui_types::transaction::{SenderSignedData, Transaction, TransactionData, TransactionDataAPI};
+
+/// Write the unsigned transaction `data` to `path`.
+pub fn write_unsigned_transaction(path: &Path, data: &TransactionData) -> anyhow::Result<()> {
+    write_bcs(path, data)
+}
+
+pub fn read_unsigned_transaction(path: &Path) -> anyhow::Result<TransactionData> {
+    read_bcs(path)
+}
+
+/// Write the signed transaction `transaction` to `path`.
+pub fn write_signed_transaction(path: &Path, transaction: &Transaction) -> anyhow::Result<()> {
+    write_bcs(path, transaction.data())
+}
+
+pub fn read_signed_transaction(path: &Path) -> anyhow::Result<Transaction> {
+    Ok(Transaction::new(read_bcs::<SenderSignedData>(path)?))
+}
+
+/// Sign `data` with the key of its sender in `keystore`.
+pub fn sign_transaction(
+    keystore: &impl AccountKeystore,
+    data: TransactionData,
+) -> anyhow::Result<Transaction> {
+    let signature = keystore
+        .sign_secure(&data.sender(), &data, Intent::sui_transaction())
+        .map_err(|e| anyhow!("Cannot sign the transaction of [{}]: {e}", data.sender()))?;
+    Ok(Transaction::from_data(data, vec![signature]))
+}
+
+/// Sign the unsigned transaction of `unsigned_path` with the key of its sender in `keystore`, and
+/// write it to `signed_path`.
+pub fn sign_transaction_file(
+    keystore: &impl AccountKeystore,
+    unsigned_path: &Path,
+    signed_path: &Path,
+) -> anyhow::Result<Transaction> {
+    let transaction = sign_transaction(keystore, read_unsigned_transaction(unsigned_path)?)?;
+    write_signed_transaction(signed_path, &transaction)?;
+    Ok(transaction)
+}
+
+fn write_bcs(path: &Path, value: &impl Serialize) -> anyhow::Result<()> {
+    fs::write(path, Base64::encode(bcs::to_bytes(value)?))
+        .with_context(|| format!("Cannot write the transaction to {}", path.display()))
+}
+
+fn read_bcs<T: DeserializeOwned>(path: &Path) -> anyhow::Result<T> {
+    let encoded = fs::read_to_string(path)
+        .with_context(|| format!("Cannot read the transaction from {}", path.display()))?;
+    let bytes = Base64::decode(encoded.trim())
+        .map_err(|e| anyhow!("Invalid transaction in {}: {e}", path.display()))?;
+    bcs::from_bytes(&bytes).with_context(|| format!("Invalid transaction in {}", path.display()))
+}
@@ -0,0 +1,304 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Proofs that the network signed the messages of a sign session, verifiable offline.
+//!
+//! A [SignProof] carries the transactions that created a sign session, its sign output and the
+//! dWallet, each with the checkpoint that includes it and the committee that certified the
+//! checkpoint. [SignProof::verify] checks the certificates of the checkpoints, the inclusion of the
+//! transactions and their events, and the signatures of the messages against the public key of the
+//! dWallet, without access to the network. This supports audits and the resolution of disputes by
+//! third parties.
+//!
+//! A proof is only as trusted as its committees: a verifier must check that they are the
+//! committees of the network for their epochs, e.g. against the checkpoints of a light client.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::{AuthorityName, ObjectID};
+use sui_types::committee::{Committee, EpochId, StakeUnit};
+use sui_types::digests::{CheckpointDigest, TransactionDigest};
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::full_checkpoint_content::{CheckpointData, CheckpointTransaction};
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber,
+};
+use sui_types::object::Object;
+use sui_types::signature_mpc::{DWallet, DWalletEvent};
+
+use crate::dwallet_signer::{verify_signature, Hash};
+use crate::error::{Error, SuiRpcResult};
+
+/// A transaction, with the checkpoint that includes it and the committee that certified the
+/// checkpoint.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransactionProof {
+    /// The voting rights of the committee of the epoch of the checkpoint.
+    pub committee: BTreeMap<AuthorityName, StakeUnit>,
+    pub checkpoint_summary: CertifiedCheckpointSummary,
+    pub checkpoint_contents: CheckpointContents,
+    pub transaction: CheckpointTransaction,
+}
+
+impl TransactionProof {
+    /// The proof of the transaction `digest` of the full checkpoint `checkpoint`, certified by the
+    /// committee with the voting rights `committee`.
+    pub fn new(
+        committee: BTreeMap<AuthorityName, StakeUnit>,
+        checkpoint: CheckpointData,
+        digest: TransactionDigest,
+    ) -> SuiRpcResult<Self> {
+        let sequence_number = checkpoint.checkpoint_summary.sequence_number;
+        let transaction = checkpoint
+            .transactions
+            .into_iter()
+            .find(|transaction| *transaction.transaction.digest() == digest)
+            .ok_or_else(|| {
+                Error::DataError(format!(
+                    "transaction {digest} is not in checkpoint {sequence_number}"
+                ))
+            })?;
+        Ok(Self {
+            committee,
+            checkpoint_summary: checkpoint.checkpoint_summary,
+            checkpoint_contents: checkpoint.checkpoint_contents,
+            transaction,
+        })
+    }
+
+    pub fn digest(&self) -> &TransactionDigest {
+        self.transaction.transaction.digest()
+    }
+
+    /// Verify that the checkpoint is certified by the committee, and that it includes the
+    /// transaction with its effects and events.
+    pub fn verify(&self) -> SuiRpcResult<()> {
+        let summary = &self.checkpoint_summary;
+        let committee = Committee::new(summary.epoch, self.committee.clone());
+        summary
+            .verify_with_contents(&committee, Some(&self.checkpoint_contents))
+            .map_err(|e| {
+                Error::DataError(format!(
+                    "invalid certificate of checkpoint {}: {e}",
+                    summary.sequence_number
+                ))
+            })?;
+
+        let transaction = &self.transaction;
+        let execution_digests = transaction.effects.execution_digests();
+        if execution_digests.transaction != *self.digest()
+            || !self
+                .checkpoint_contents
+                .iter()
+                .any(|digests| *digests == execution_digests)
+        {
+            return Err(Error::DataError(format!(
+                "transaction {} and its effects are not in checkpoint {}",
+                self.digest(),
+                summary.sequence_number
+            )));
+        }
+        let events_digest = transaction.events.as_ref().map(|events| events.digest());
+        if events_digest.as_ref() != transaction.effects.events_digest() {
+            return Err(Error::DataError(format!(
+                "the events of transaction {} do not match its effects",
+                self.digest()
+            )));
+        }
+        Ok(())
+    }
+
+    /// The dWallet events emitted by the transaction.
+    fn dwallet_events(&self) -> SuiRpcResult<Vec<DWalletEvent>> {
+        self.transaction
+            .events
+            .iter()
+            .flat_map(|events| &events.data)
+            .filter_map(|event| DWalletEvent::try_from_event(&event.type_, &event.contents))
+            .map(|event| Ok(event?))
+            .collect()
+    }
+
+    /// The object `object_id` as written by the transaction.
+    fn output_object(&self, object_id: ObjectID) -> SuiRpcResult<&Object> {
+        let object = self
+            .transaction
+            .output_objects
+            .iter()
+            .find(|object| object.id() == object_id)
+            .ok_or_else(|| {
+                Error::DataError(format!(
+                    "object {object_id} is not written by transaction {}",
+                    self.digest()
+                ))
+            })?;
+        let object_ref = object.compute_object_reference();
+        if !self
+            .transaction
+            .effects
+            .all_changed_objects()
+            .iter()
+            .any(|(changed, _, _)| *changed == object_ref)
+        {
+            return Err(Error::DataError(format!(
+                "object {object_id} does not match the effects of transaction {}",
+                self.digest()
+            )));
+        }
+        Ok(object)
+    }
+
+    fn checkpoint(&self) -> ProvenCheckpoint {
+        ProvenCheckpoint {
+            epoch: self.checkpoint_summary.epoch,
+            sequence_number: self.checkpoint_summary.sequence_number,
+            digest: *self.checkpoint_summary.digest(),
+        }
+    }
+}
+
+/// A proof that the network signed the messages of the sign session `session_id` with a dWallet.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignProof {
+    pub session_id: ObjectID,
+    /// The [Hash] the messages were signed with.
+    pub hash: u8,
+    /// The transaction that created the sign session, with its messages.
+    pub session: TransactionProof,
+    /// The transaction that created the sign output, with its signatures.
+    pub sign_output: TransactionProof,
+    /// The transaction that created the dWallet, with its public key.
+    pub dwallet: TransactionProof,
+}
+
+/// A checkpoint a [SignProof] relies on, to check against the checkpoints of the network.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProvenCheckpoint {
+    pub epoch: EpochId,
+    pub sequence_number: CheckpointSequenceNumber,
+    pub digest: CheckpointDigest,
+}
+
+/// What a verified [SignProof] proves.
+#[derive(Clone, Debug)]
+pub struct VerifiedSignProof {
+    pub session_id: ObjectID,
+    pub dwallet_id: ObjectID,
+    /// The compressed SEC1 public key of the dWallet.
+    pub public_key: Vec<u8>,
+    pub hash: Hash,
+    pub messages: Vec<Vec<u8>>,
+    /// One 64-byte `r || s` signature per message, in order.
+    pub signatures: Vec<Vec<u8>>,
+    /// The checkpoints of the session, of the sign output and of the dWallet.
+    pub checkpoints: Vec<ProvenCheckpoint>,
+}
+
+impl SignProof {
+    pub fn from_bytes(bytes: &[u8]) -> SuiRpcResult<Self> {
+        Ok(bcs::from_bytes(bytes)?)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("sign proofs are serializable")
+    }
+
+    /// Verify the proof offline, see the [module](self) documentation.
+    pub fn verify(&self) -> SuiRpcResult<VerifiedSignProof> {
+        let session_id = self.session_id;
+        for proof in [&self.session, &self.sign_output, &self.dwallet] {
+            proof.verify()?;
+        }
+
+        let session = self
+            .session
+            .dwallet_events()?
+            .into_iter()
+            .find_map(|event| match event {
+                DWalletEvent::SignSessionCreated(session)
+                    if session.session_id.bytes == session_id =>
+                {
+                    Some(session)
+                }
+                _ => None,
+            })
+            .ok_or_else(|| {
+                Error::DataError(format!(
+                    "transaction {} did not create sign session {session_id}",
+                    self.session.digest()
+                ))
+            })?;
+        let sign_output = self
+            .sign_output
+            .dwallet_events()?
+            .into_iter()
+            .find_map(|event| match event {
+                DWalletEvent::SignOutputCreated(output)
+                    if output.session_id.bytes == session_id =>
+                {
+                    Some(output)
+                }
+                _ => None,
+            })
+            .ok_or_else(|| {
+                Error::DataError(format!(
+                    "transaction {} did not sign the messages of session {session_id}",
+                    self.sign_output.digest()
+                ))
+            })?;
+        let dwallet_id = session.dwallet_id.bytes;
+        if sign_output.dwallet_id.bytes != dwallet_id {
+            return Err(Error::DataError(format!(
+                "the sign output of session {session_id} is of dWallet {}, not {dwallet_id}",
+                sign_output.dwallet_id.bytes
+            )));
+        }
+
+        let object = self.dwallet.output_object(dwallet_id)?;
+        let dwallet = match object.data.try_as_move() {
+            Some(object) if DWallet::is_type(&object.type_().clone().into()) => {
+                DWallet::from_bcs_bytes(object.contents())?
+            }
+            _ => {
+                return Err(Error::DataError(format!(
+                    "object {dwallet_id} is not a dWallet"
+                )))
+            }
+        };
+        let public_key = dwallet.public_key_sec1().map_err(|e| {
+            Error::DataError(format!("invalid public key of dWallet {dwallet_id}: {e}"))
+        })?;
+
+        if sign_output.signatures.len() != session.messages.len() {
+            return Err(Error::DataError(format!(
+                "session {session_id} has {} messages but {} signatures",
+                session.messages.len(),
+                sign_output.signatures.len()
+            )));
+        }
+        for (i, (message, signature)) in session
+            .messages
+            .iter()
+            .zip(&sign_output.signatures)
+            .enumerate()
+        {
+            verify_signature(&public_key, message, Hash::from(self.hash), signature)
+                .map_err(|e| Error::DataError(format!("invalid signature of message {i}: {e}")))?;
+        }
+
+        Ok(VerifiedSignProof {
+            session_id,
+            dwallet_id,
+            public_key,
+            hash: Hash::from(self.hash),
+            messages: session.messages,
+            signatures: sign_output.signatures,
+            checkpoints: vec![
+                self.session.checkpoint(),
+                self.sign_output.checkpoint(),
+                self.dwallet.checkpoint(),
+            ],
+        })
+    }
+}
@@ -0,0 +1,259 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Solana custody with Ed25519 dWallets.
+//!
+//! This module compiles instructions into a legacy Solana message, has the dWallet of an
+//! [Ed25519DWalletSigner] sign the serialized message, which is the signing payload on Solana,
+//! and assembles the transaction to submit with `sendTransaction`.
+
+use fastcrypto::ed25519::{Ed25519PublicKey, Ed25519Signature};
+use fastcrypto::encoding::{Base58, Encoding};
+use fastcrypto::traits::{ToFromBytes, VerifyingKey};
+
+use crate::dwallet_signer::Ed25519DWalletSigner;
+use crate::error::{Error, SuiRpcResult};
+
+pub type Pubkey = [u8; 32];
+
+pub const SYSTEM_PROGRAM_ID: Pubkey = [0; 32];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MessageHeader {
+    pub num_required_signatures: u8,
+    pub num_readonly_signed_accounts: u8,
+    pub num_readonly_unsigned_accounts: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompiledInstruction {
+    pub program_id_index: u8,
+    pub accounts: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+/// A legacy (non-versioned) Solana message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+    pub header: MessageHeader,
+    pub account_keys: Vec<Pubkey>,
+    pub recent_blockhash: [u8; 32],
+    pub instructions: Vec<CompiledInstruction>,
+}
+
+impl Message {
+    /// Compile `instructions` paid by `payer`. Accounts are ordered as Solana requires: writable
+    /// signers (starting with the payer), read-only signers, writable and then read-only non-signers.
+    pub fn new(
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        recent_blockhash: [u8; 32],
+    ) -> SuiRpcResult<Self> {
+        let mut accounts = vec![AccountMeta {
+            pubkey: *payer,
+            is_signer: true,
+            is_writable: true,
+        }];
+        let mut add_account = |meta: &AccountMeta| {
+            match accounts.iter_mut().find(|a| a.pubkey == meta.pubkey) {
+                Some(account) => {
+                    account.is_signer |= meta.is_signer;
+                    account.is_writable |= meta.is_writable;
+                }
+                None => accounts.push(meta.clone()),
+            };
+        };
+        for instruction in instructions {
+            instruction.accounts.iter().for_each(&mut add_account);
+            add_account(&AccountMeta {
+                pubkey: instruction.program_id,
+                is_signer: false,
+                is_writable: false,
+            });
+        }
+        // Stable, so the payer stays first.
+        accounts.sort_by_key(|a| (!a.is_signer, !a.is_writable));
+        if accounts.len() > u8::MAX as usize {
+            return Err(Error::DataError("too many accounts".to_string()));
+        }
+
+        let count = |f: fn(&AccountMeta) -> bool| accounts.iter().filter(|a| f(a)).count() as u8;
+        let header = MessageHeader {
+            num_required_signatures: count(|a| a.is_signer),
+            num_readonly_signed_accounts: count(|a| a.is_signer && !a.is_writable),
+            num_readonly_unsigned_accounts: count(|a| !a.is_signer && !a.is_writable),
+        };
+        let account_keys: Vec<Pubkey> = accounts.iter().map(|a| a.pubkey).collect();
+        let index_of = |pubkey: &Pubkey| {
+            account_keys
+                .iter()
+                .position(|key| key == pubkey)
+                .expect("all accounts were added") as u8
+        };
+        let instructions = instructions
+            .iter()
+            .map(|instruction| CompiledInstruction {
+                program_id_index: index_of(&instruction.program_id),
+                accounts: instruction
+                    .accounts
+                    .iter()
+                    .map(|a| index_of(&a.pubkey))
+                    .collect(),
+                data: instruction.data.clone(),
+            })
+            .collect();
+        Ok(Self {
+            header,
+            account_keys,
+            recent_blockhash,
+            instructions,
+        })
+    }
+
+    /// The wire encoding of the message, which is what its signers sign.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut bytes = vec![
+            self.header.num_required_signatures,
+            self.header.num_readonly_signed_accounts,
+            self.header.num_readonly_unsigned_accounts,
+        ];
+        encode_length(&mut bytes, self.account_keys.len());
+        self.account_keys
+            .iter()
+            .for_each(|key| bytes.extend_from_slice(key));
+        bytes.extend_from_slice(&self.recent_blockhash);
+        encode_length(&mut bytes, self.instructions.len());
+        for instruction in &self.instructions {
+            bytes.push(instruction.program_id_index);
+            encode_length(&mut bytes, instruction.accounts.len());
+            bytes.extend_from_slice(&instruction.accounts);
+            encode_length(&mut bytes, instruction.data.len());
+            bytes.extend_from_slice(&instruction.data);
+        }
+        bytes
+    }
+
+    pub fn signers(&self) -> &[Pubkey] {
+        &self.account_keys[..self.header.num_required_signatures as usize]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transaction {
+    /// One signature per required signer, in the order of [Message::signers].
+    pub signatures: Vec<[u8; 64]>,
+    pub message: Message,
+}
+
+impl Transaction {
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        encode_length(&mut bytes, self.signatures.len());
+        self.signatures
+            .iter()
+            .for_each(|signature| bytes.extend_from_slice(signature));
+        bytes.extend(self.message.serialize());
+        bytes
+    }
+
+    /// The serialized transaction in base58, as accepted by the `sendTransaction` RPC method.
+    pub fn to_base58(&self) -> String {
+        Base58::encode(self.serialize())
+    }
+}
+
+/// The Solana address of an Ed25519 public key, its base58 encoding.
+pub fn address(public_key: &Pubkey) -> String {
+    Base58::encode(public_key)
+}
+
+pub fn pubkey_from_address(address: &str) -> SuiRpcResult<Pubkey> {
+    Base58::decode(address)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| Error::DataError(format!("invalid Solana address [{address}]")))
+}
+
+/// A System Program instruction transferring `lamports` from `from` to `to`.
+pub fn system_transfer(from: &Pubkey, to: &Pubkey, lamports: u64) -> Instruction {
+    // `SystemInstruction::Transfer` is variant 2, with a little-endian u32 tag.
+    let mut data = 2u32.to_le_bytes().to_vec();
+    data.extend(lamports.to_le_bytes());
+    Instruction {
+        program_id: SYSTEM_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta {
+                pubkey: *from,
+                is_signer: true,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: *to,
+                is_signer: false,
+                is_writable: true,
+            },
+        ],
+        data,
+    }
+}
+
+/// Sign `message`, of which the dWallet of `signer` must be the only signer.
+pub async fn sign_transaction(
+    message: Message,
+    signer: &impl Ed25519DWalletSigner,
+) -> SuiRpcResult<Transaction> {
+    let public_key = signer.public_key();
+    if message.signers() != [public_key] {
+        return Err(Error::DataError(format!(
+            "the dWallet {} must be the only signer of the message",
+            address(&public_key)
+        )));
+    }
+    let payload = message.serialize();
+    let signature = signer
+        .sign(vec![payload.clone()])
+        .await?
+        .pop()
+        .ok_or_else(|| Error::DataError("no signature returned by the dWallet".to_string()))?;
+
+    let verifying_key = Ed25519PublicKey::from_bytes(&public_key)
+        .map_err(|e| Error::DataError(format!("invalid dWallet public key: {e}")))?;
+    let sig = Ed25519Signature::from_bytes(&signature)
+        .map_err(|e| Error::DataError(format!("invalid dWallet signature: {e}")))?;
+    verifying_key
+        .verify(&payload, &sig)
+        .map_err(|e| Error::DataError(format!("dWallet signature does not verify: {e}")))?;
+
+    Ok(Transaction {
+        signatures: vec![signature.try_into().expect("signature is 64 bytes")],
+        message,
+    })
+}
+
+/// Solana's compact-u16 length prefix.
+fn encode_length(bytes: &mut Vec<u8>, mut length: usize) {
+    loop {
+        let mut byte = (length & 0x7f) as u8;
+        length >>= 7;
+        if length == 0 {
+            bytes.push(byte);
+            return;
+        }
+        byte |= 0x80;
+        bytes.push(byte);
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Transactions whose gas is paid by a sponsor rather than by their sender.
+//!
+//! The backend of an application can pay for the dWallet operations of its users, who then need
+//! no SUI: the transaction of the user is paid with gas coins of the sponsor, selected by
+//! [sponsored_transaction_data], and submitted with the signatures of both, the one of the sponsor
+//! being added by [sponsor_transaction]. See [crate::dwallet::DWalletClient::with_sponsor].
+
+use anyhow::{anyhow, bail};
+use async_trait::async_trait;
+use shared_crypto::intent::Intent;
+use sui_json_rpc_types::{Coin, SuiObjectDataOptions};
+use sui_keys::keystore::AccountKeystore;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::crypto::Signature;
+use sui_types::transaction::{Transaction, TransactionData, TransactionDataAPI, TransactionKind};
+
+use crate::SuiClient;
+
+/// The payer of the gas of sponsored transactions.
+///
+/// The sponsor may hold its key locally, as [KeystoreGasSponsor], or sign remotely, e.g. behind
+/// the API of the backend of an application.
+#[async_trait]
+pub trait GasSponsor: Send + Sync {
+    /// The address owning the gas coins of the sponsored transactions.
+    fn address(&self) -> SuiAddress;
+
+    /// Sign `tx_data`, whose gas is paid by [Self::address], as its sponsor.
+    async fn sign(&self, tx_data: &TransactionData) -> anyhow::Result<Signature>;
+}
+
+/// A sponsor whose key is in a local keystore.
+pub struct KeystoreGasSponsor<K> {
+    keystore: K,
+    address: SuiAddress,
+}
+
+impl<K: AccountKeystore> KeystoreGasSponsor<K> {
+    /// A sponsor paying with the gas coins of `address`, whose key must be in `keystore`.
+    pub fn new(keystore: K, address: SuiAddress) -> Self {
+        Self { keystore, address }
+    }
+}
+
+#[async_trait]
+impl<K: AccountKeystore + Send + Sync> GasSponsor for KeystoreGasSponsor<K> {
+    fn address(&self) -> SuiAddress {
+        self.address
+    }
+
+    async fn sign(&self, tx_data: &TransactionData) -> anyhow::Result<Signature> {
+        if tx_data.gas_owner() != self.address {
+            bail!(
+                "The gas of the transaction is paid by [{}], not by the sponsor [{}]",
+                tx_data.gas_owner(),
+                self.address
+            );
+        }
+        self.keystore
+            .sign_secure(&self.address, tx_data, Intent::sui_transaction())
+            .map_err(|e| {
+                anyhow!(
+                    "Cannot sign the transaction as the sponsor [{}]: {e}",
+                    self.address
+                )
+            })
+    }
+}
+
+/// The data of the transaction `kind` of `sender`, paid by `sponsor` with its gas coin `gas`, or
+/// with gas coins of `sponsor` covering `gas_budget` if `None`.
+pub async fn sponsored_transaction_data(
+    client: &SuiClient,
+    kind: TransactionKind,
+    sender: SuiAddress,
+    sponsor: SuiAddress,
+    gas: Option<ObjectID>,
+    gas_budget: u64,
+    gas_price: u64,
+) -> anyhow::Result<TransactionData> {
+    let gas_payment = match gas {
+        Some(gas) => vec![client
+            .read_api()
+            .get_object_with_options(gas, SuiObjectDataOptions::new())
+            .await?
+            .object_ref_if_exists()
+            .ok_or_else(|| anyhow!("Cannot find the gas object [{gas}] of the sponsor"))?],
+        None => client
+            .coin_read_api()
+            .select_coins(sponsor, None, gas_budget.into(), vec![])
+            .await?
+            .iter()
+            .map(Coin::object_ref)
+            .collect(),
+    };
+    Ok(TransactionData::new_with_gas_coins_allow_sponsor(
+        kind,
+        sender,
+        gas_payment,
+        gas_budget,
+        gas_price,
+        sponsor,
+    ))
+}
+
+/// Add the signature of `sponsor` to `transaction`, signed by its sender.
+pub async fn sponsor_transaction(
+    sponsor: &dyn GasSponsor,
+    transaction: Transaction,
+) -> anyhow::Result<Transaction> {
+    let tx_data = transaction.transaction_data().clone();
+    let signature = sponsor.sign(&tx_data).await?;
+    let mut signatures = transaction.tx_signatures().to_vec();
+    signatures.push(signature.into());
+    Ok(Transaction::from_generic_sig_data(tx_data, signatures))
+}
@@ -1,38 +1,83 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter, Write};
+use std::fs;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
-use anyhow::anyhow;
+use anyhow::{anyhow, Context};
+use fastcrypto::encoding::{Base64, Hex};
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+use serde_yaml::{Mapping, Value};
+use tracing::warn;
+use zeroize::Zeroizing;
 
-use crate::{SuiClient, SuiClientBuilder, SUI_DEVNET_URL, SUI_LOCAL_NETWORK_URL, SUI_TESTNET_URL};
+use crate::beacon_client::BeaconClient;
+use crate::btc_client::{BitcoinClient, BitcoinClientConfig};
+use crate::eth_client::{EthClient, EthFinality};
+use crate::eth_light_client::{
+    known_checkpoints, known_forks, known_network, verify_bootstrap_config,
+};
+use crate::evm_chains::NativeCurrency;
+use crate::{
+    SuiClient, SuiClientBuilder, SUI_DEVNET_URL, SUI_LOCAL_NETWORK_FAUCET_URL,
+    SUI_LOCAL_NETWORK_URL, SUI_TESTNET_URL,
+};
+use signature_mpc::twopc_mpc_protocols::{DKGCentralizedPartyOutput, DKGDecentralizedPartyOutput};
 use sui_config::Config;
+use sui_keys::encryption::{encrypt_share_to, Kdf, ShareEncryptionKey, ShareTransferKey};
+use sui_keys::keyring;
 use sui_keys::keystore::{AccountKeystore, Keystore};
 use sui_types::base_types::*;
-use signature_mpc::twopc_mpc_protocols::{DKGCentralizedPartyOutput, DKGDecentralizedPartyOutput};
+use sui_types::eth_light_client::{Checkpoint, Fork, Root};
+
+/// The version of the format of the client config files written by this release. Files of older
+/// versions are migrated when loaded, see [SuiClientConfig::migrate].
+pub const SUI_CLIENT_CONFIG_VERSION: u64 = 1;
 
 #[serde_as]
 #[derive(Serialize, Deserialize)]
 pub struct SuiClientConfig {
+    /// The version of the format of the config file, see [SUI_CLIENT_CONFIG_VERSION].
+    #[serde(default)]
+    pub version: u64,
     pub keystore: Keystore,
     pub envs: Vec<SuiEnv>,
     pub dwallets: Vec<DWalletSecretShare>,
     pub active_env: Option<String>,
+    /// The active address of the environments without one of their own.
     pub active_address: Option<SuiAddress>,
     pub active_dwallet: Option<String>,
+    /// Names of addresses, such as the treasury of an organization, usable wherever an address
+    /// is accepted, like the aliases of the keys of the keystore.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub address_aliases: BTreeMap<String, SuiAddress>,
+    /// Named bundles of the active settings, switched between with `sui client --profile`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, ClientProfile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_profile: Option<String>,
 }
 
 impl SuiClientConfig {
     pub fn new(keystore: Keystore) -> Self {
         SuiClientConfig {
+            version: SUI_CLIENT_CONFIG_VERSION,
             keystore,
             envs: vec![],
             dwallets: vec![],
             active_env: None,
             active_address: None,
             active_dwallet: None,
+            address_aliases: BTreeMap::new(),
+            profiles: BTreeMap::new(),
+            active_profile: None,
         }
     }
 
@@ -53,6 +98,39 @@ impl SuiClientConfig {
         })
     }
 
+    pub fn get_active_env_mut(&mut self) -> Result<&mut SuiEnv, anyhow::Error> {
+        let env = match &self.active_env {
+            Some(alias) => self.envs.iter_mut().find(|env| &env.alias == alias),
+            None => self.envs.first_mut(),
+        };
+        env.ok_or_else(|| {
+            anyhow!(
+                "Environment configuration not found for env [{}]",
+                self.active_env.as_deref().unwrap_or("None")
+            )
+        })
+    }
+
+    /// The active address of the active environment, or else the global active address.
+    pub fn get_active_address(&self) -> Option<SuiAddress> {
+        self.get_env(&self.active_env)
+            .and_then(|env| env.active_address)
+            .or(self.active_address)
+    }
+
+    /// Set the active address of the active environment, or the global active address if no
+    /// environment is configured.
+    pub fn set_active_address(&mut self, address: SuiAddress) {
+        let env = match &self.active_env {
+            Some(alias) => self.envs.iter_mut().find(|env| &env.alias == alias),
+            None => self.envs.first_mut(),
+        };
+        match env {
+            Some(env) => env.active_address = Some(address),
+            None => self.active_address = Some(address),
+        }
+    }
+
     pub fn add_env(&mut self, env: SuiEnv) {
         if !self
             .envs
@@ -63,11 +141,114 @@ impl SuiClientConfig {
         }
     }
 
+    /// Write the environment `alias` to `path` as a standalone file, in TOML if `path` has the
+    /// `toml` extension and in YAML otherwise, for others to add with [Self::import_env]. The file
+    /// holds no keystore material, nor the bearer token of the environment.
+    pub fn export_env(&self, alias: &str, path: &Path) -> Result<(), anyhow::Error> {
+        let env = self
+            .envs
+            .iter()
+            .find(|env| env.alias == alias)
+            .ok_or_else(|| anyhow!("Environment configuration not found for env [{alias}]"))?;
+        let file = SuiEnvFile {
+            version: SUI_CLIENT_CONFIG_VERSION,
+            env: SuiEnv {
+                bearer_token: None,
+                ..env.clone()
+            },
+        };
+        let contents = if is_toml(path) {
+            toml::to_string(&file)?
+        } else {
+            serde_yaml::to_string(&file)?
+        };
+        fs::write(path, contents)
+            .with_context(|| format!("Unable to export env to {}", path.display()))
+    }
+
+    /// Add the environment of the file at `path`, written by [Self::export_env], returning it.
+    pub fn import_env(&mut self, path: &Path) -> Result<&SuiEnv, anyhow::Error> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Unable to import env from {}", path.display()))?;
+        let file: SuiEnvFile = if is_toml(path) {
+            toml::from_str(&contents)?
+        } else {
+            serde_yaml::from_str(&contents)?
+        };
+        if file.version > SUI_CLIENT_CONFIG_VERSION {
+            return Err(anyhow!(
+                "The env file version {} is newer than the latest version supported by this release, {SUI_CLIENT_CONFIG_VERSION}",
+                file.version
+            ));
+        }
+        file.env.validate()?;
+        if self.envs.iter().any(|env| env.alias == file.env.alias) {
+            return Err(anyhow!(
+                "Environment config with name [{}] already exists.",
+                file.env.alias
+            ));
+        }
+        self.envs.push(file.env);
+        Ok(self.envs.last().expect("added above"))
+    }
+
+    /// Name `address` `alias`, for the alias to be usable wherever an address is accepted.
+    pub fn add_address_alias(
+        &mut self,
+        alias: String,
+        address: SuiAddress,
+    ) -> Result<(), anyhow::Error> {
+        if alias.trim().is_empty() || alias.starts_with("0x") {
+            return Err(anyhow!(
+                "Invalid address alias [{alias}], it must not be empty nor start with 0x"
+            ));
+        }
+        if self.address_aliases.contains_key(&alias) || self.keystore.alias_exists(&alias) {
+            return Err(anyhow!("Address alias [{alias}] already exists."));
+        }
+        self.address_aliases.insert(alias, address);
+        Ok(())
+    }
+
+    pub fn remove_address_alias(&mut self, alias: &str) -> Result<SuiAddress, anyhow::Error> {
+        self.address_aliases
+            .remove(alias)
+            .ok_or_else(|| anyhow!("Address alias [{alias}] not found"))
+    }
+
+    /// Resolve `identity`, an address, an address alias, or the alias of a key of the keystore,
+    /// to an address.
+    pub fn resolve_address(&self, identity: &str) -> Result<SuiAddress, anyhow::Error> {
+        if identity.starts_with("0x") {
+            return SuiAddress::from_str(identity);
+        }
+        if let Some(address) = self.address_aliases.get(identity) {
+            return Ok(*address);
+        }
+        Ok(*self.keystore.get_address_by_alias(identity.to_string())?)
+    }
+
+    /// Point the active addresses and the address aliases naming `old` to `new`, e.g. after
+    /// rotating the key of `old`, see [sui_keys::keystore::AccountKeystore::rotate_key].
+    pub fn replace_address(&mut self, old: SuiAddress, new: SuiAddress) {
+        let active_addresses = self
+            .envs
+            .iter_mut()
+            .filter_map(|env| env.active_address.as_mut())
+            .chain(self.active_address.as_mut());
+        for address in active_addresses.chain(self.address_aliases.values_mut()) {
+            if *address == old {
+                *address = new;
+            }
+        }
+    }
+
     pub fn get_dwallet(&self, alias: &Option<String>) -> Option<&DWalletSecretShare> {
+        let mut dwallets = self.profile_dwallets();
         if let Some(alias) = alias {
-            self.dwallets.iter().find(|dwallet| &dwallet.alias == alias)
+            dwallets.find(|dwallet| &dwallet.alias == alias)
         } else {
-            self.dwallets.first()
+            dwallets.next()
         }
     }
 
@@ -80,6 +261,30 @@ impl SuiClientConfig {
         })
     }
 
+    /// The dWallet of the registry with the alias or id `dwallet`.
+    pub fn resolve_dwallet(&self, dwallet: &str) -> Result<&DWalletSecretShare, anyhow::Error> {
+        let dwallet_id = ObjectID::from_str(dwallet).ok();
+        let entry = self
+            .dwallets
+            .iter()
+            .find(|entry| entry.alias == dwallet || Some(entry.dwallet_id) == dwallet_id)
+            .ok_or_else(|| anyhow!("dWallet [{dwallet}] not found in the dWallet registry"))?;
+        match self.get_active_profile() {
+            Some((name, profile)) if !profile.includes_dwallet(&entry.alias) => Err(anyhow!(
+                "dWallet [{dwallet}] is not in the dWallets of profile [{name}]"
+            )),
+            _ => Ok(entry),
+        }
+    }
+
+    /// The id of the dWallet with the alias or id `dwallet`, which needs not be in the registry.
+    pub fn resolve_dwallet_id(&self, dwallet: &str) -> Result<ObjectID, anyhow::Error> {
+        match self.resolve_dwallet(dwallet) {
+            Ok(entry) => Ok(entry.dwallet_id),
+            Err(e) => ObjectID::from_str(dwallet).map_err(|_| e),
+        }
+    }
+
     pub fn add_dwallet(&mut self, dwallet: DWalletSecretShare) {
         if !self
             .dwallets
@@ -89,16 +294,170 @@ impl SuiClientConfig {
             if self.active_dwallet.is_none() {
                 self.active_dwallet = Some(dwallet.alias.clone());
             }
+            // The dWallets created under a profile belong to it.
+            if let Some(profile) = self
+                .active_profile
+                .as_ref()
+                .and_then(|name| self.profiles.get_mut(name))
+            {
+                if !profile.dwallets.is_empty() {
+                    profile.dwallets.push(dwallet.alias.clone());
+                }
+            }
             self.dwallets.push(dwallet)
         }
     }
+
+    /// The dWallets of the registry in the active profile, all of them without one.
+    pub fn profile_dwallets(&self) -> impl Iterator<Item = &DWalletSecretShare> {
+        let profile = self.get_active_profile().map(|(_, profile)| profile);
+        self.dwallets.iter().filter(move |dwallet| {
+            profile.map_or(true, |profile| profile.includes_dwallet(&dwallet.alias))
+        })
+    }
+
+    /// The active profile and its name, if any.
+    pub fn get_active_profile(&self) -> Option<(&str, &ClientProfile)> {
+        let name = self.active_profile.as_ref()?;
+        Some((name.as_str(), self.profiles.get(name)?))
+    }
+
+    /// Check that the environment, dWallets and gas settings of `profile` are configured.
+    pub fn validate_profile(&self, profile: &ClientProfile) -> Result<(), anyhow::Error> {
+        if let Some(env) = &profile.env {
+            if self.get_env(&Some(env.clone())).is_none() {
+                return Err(anyhow!("Environment config not found for [{env}]"));
+            }
+        }
+        for alias in profile.dwallets.iter().chain(&profile.active_dwallet) {
+            if !self.dwallets.iter().any(|dwallet| &dwallet.alias == alias) {
+                return Err(anyhow!(
+                    "dWallet [{alias}] not found in the dWallet registry"
+                ));
+            }
+        }
+        if let Some(active_dwallet) = &profile.active_dwallet {
+            if !profile.includes_dwallet(active_dwallet) {
+                return Err(anyhow!(
+                    "The active dWallet [{active_dwallet}] must be one of the dWallets of the profile"
+                ));
+            }
+        }
+        if profile.gas_budget == Some(0) || profile.gas_price == Some(0) {
+            return Err(anyhow!("The gas budget and gas price must not be 0"));
+        }
+        Ok(())
+    }
+
+    /// Switch to the profile `name`, making its environment, address and dWallet the active
+    /// ones, and its gas settings the defaults of the environment.
+    pub fn switch_profile(&mut self, name: &str) -> Result<(), anyhow::Error> {
+        let profile = self.profiles.get(name).cloned().ok_or_else(|| {
+            anyhow!("Profile [{name}] not found, add it using the `sui client profile set` command")
+        })?;
+        self.validate_profile(&profile)?;
+        if let Some(env) = &profile.env {
+            self.active_env = Some(env.clone());
+        }
+        if let Some(address) = profile.active_address {
+            self.set_active_address(address);
+        }
+        if let Ok(env) = self.get_active_env_mut() {
+            if profile.gas_budget.is_some() {
+                env.default_gas_budget = profile.gas_budget;
+            }
+            if profile.gas_price.is_some() {
+                env.default_gas_price = profile.gas_price;
+            }
+        }
+        self.active_profile = Some(name.to_string());
+        self.active_dwallet = match (&profile.active_dwallet, &self.active_dwallet) {
+            (Some(alias), _) => Some(alias.clone()),
+            (None, Some(alias)) if profile.includes_dwallet(alias) => Some(alias.clone()),
+            _ => self
+                .profile_dwallets()
+                .next()
+                .map(|dwallet| dwallet.alias.clone()),
+        };
+        Ok(())
+    }
+}
+
+/// A named bundle of the active settings of the client, such as those of a custody tenant: the
+/// environment, the address, the dWallets and the gas settings, see
+/// [SuiClientConfig::switch_profile].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_address: Option<SuiAddress>,
+    /// The aliases of the dWallets of the registry usable in the profile, all of them if empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dwallets: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_dwallet: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_budget: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<u64>,
+}
+
+impl ClientProfile {
+    /// Whether the dWallet `alias` is usable in the profile.
+    pub fn includes_dwallet(&self, alias: &str) -> bool {
+        self.dwallets.is_empty() || self.dwallets.iter().any(|dwallet| dwallet == alias)
+    }
+}
+
+/// An environment shared as a standalone file, see [SuiClientConfig::export_env].
+#[derive(Serialize, Deserialize)]
+struct SuiEnvFile {
+    /// The version of the client config format of the environment.
+    version: u64,
+    env: SuiEnv,
+}
+
+fn is_toml(path: &Path) -> bool {
+    path.extension()
+        .map_or(false, |extension| extension == "toml")
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SuiEnv {
     pub alias: String,
     pub rpc: String,
+    /// RPC endpoints the client fails over to, in order, when `rpc` fails.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_rpcs: Vec<String>,
     pub ws: Option<String>,
+    /// The faucet of the network, to fund addresses with `sui client faucet`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub faucet: Option<String>,
+    /// The HTTP or SOCKS5 proxy the RPC requests go through, see [SuiClientBuilder::proxy].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// Headers added to every RPC request, e.g. the API key header of the RPC provider.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// The bearer token authenticating the RPC requests to the RPC provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bearer_token: Option<String>,
+    /// The Bitcoin backend used by this environment's dWallet Bitcoin flows.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bitcoin: Option<BitcoinClientConfig>,
+    /// The Ethereum and EVM networks used by this environment's dWallet flows, by name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub eth_networks: BTreeMap<String, EthNetworkConfig>,
+    /// The gas budget of transactions that are not given one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_gas_budget: Option<u64>,
+    /// The gas price of transactions, instead of the reference gas price of the network.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_gas_price: Option<u64>,
+    /// The active address while this environment is active, overriding the global one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_address: Option<SuiAddress>,
 }
 
 impl SuiEnv {
@@ -107,41 +466,248 @@ impl SuiEnv {
         request_timeout: Option<std::time::Duration>,
         max_concurrent_requests: Option<u64>,
     ) -> Result<SuiClient, anyhow::Error> {
-        let mut builder = SuiClientBuilder::default();
+        let mut builder = self.client_builder();
         if let Some(request_timeout) = request_timeout {
             builder = builder.request_timeout(request_timeout);
         }
+        if let Some(max_concurrent_requests) = max_concurrent_requests {
+            builder = builder.max_concurrent_requests(max_concurrent_requests as usize);
+        }
+        Ok(builder.build(&self.rpc).await?)
+    }
+
+    /// A builder of clients of this environment, configured with its endpoints, proxy and
+    /// credentials, to be built with [SuiEnv::rpc].
+    pub fn client_builder(&self) -> SuiClientBuilder {
+        let mut builder = SuiClientBuilder::default();
         if let Some(ws_url) = &self.ws {
             builder = builder.ws_url(ws_url);
         }
+        if !self.fallback_rpcs.is_empty() {
+            builder = builder.fallback_urls(&self.fallback_rpcs);
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(proxy);
+        }
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(token) = &self.bearer_token {
+            builder = builder.bearer_token(token);
+        }
+        builder
+    }
 
-        if let Some(max_concurrent_requests) = max_concurrent_requests {
-            builder = builder.max_concurrent_requests(max_concurrent_requests as usize);
+    /// The faucet of the environment: its configured faucet, or else the faucet of `sui start` for
+    /// the local network.
+    pub fn faucet_url(&self) -> Result<&str, anyhow::Error> {
+        match &self.faucet {
+            Some(faucet) => Ok(faucet),
+            None if self.rpc.trim_end_matches('/') == SUI_LOCAL_NETWORK_URL => {
+                Ok(SUI_LOCAL_NETWORK_FAUCET_URL)
+            }
+            None => Err(anyhow!(
+                "No faucet configured for env [{}], add an env with `sui client new-env --faucet`",
+                self.alias
+            )),
         }
-        Ok(builder.build(&self.rpc).await?)
+    }
+
+    pub fn create_bitcoin_client(&self) -> Result<BitcoinClient, anyhow::Error> {
+        self.bitcoin
+            .as_ref()
+            .map(BitcoinClientConfig::build)
+            .ok_or_else(|| anyhow!("No Bitcoin backend configured for env [{}]", self.alias))
+    }
+
+    pub fn eth_network(&self, name: &str) -> Result<&EthNetworkConfig, anyhow::Error> {
+        self.eth_networks.get(name).ok_or_else(|| {
+            anyhow!(
+                "Ethereum network [{name}] not configured for env [{}]",
+                self.alias
+            )
+        })
+    }
+
+    pub fn create_eth_client(&self, network: &str) -> Result<EthClient, anyhow::Error> {
+        Ok(self.eth_network(network)?.build_client())
+    }
+
+    pub fn create_beacon_client(&self, network: &str) -> Result<BeaconClient, anyhow::Error> {
+        self.eth_network(network)?
+            .build_beacon_client()
+            .ok_or_else(|| anyhow!("No consensus RPC configured for Ethereum network [{network}]"))
     }
 
     pub fn devnet() -> Self {
-        Self {
-            alias: "devnet".to_string(),
-            rpc: SUI_DEVNET_URL.into(),
-            ws: None,
-        }
+        Self::builder("devnet", SUI_DEVNET_URL)
+            .build()
+            .expect("the devnet environment is valid")
     }
+
     pub fn testnet() -> Self {
-        Self {
-            alias: "testnet".to_string(),
-            rpc: SUI_TESTNET_URL.into(),
-            ws: None,
-        }
+        Self::builder("testnet", SUI_TESTNET_URL)
+            .build()
+            .expect("the testnet environment is valid")
     }
 
     pub fn localnet() -> Self {
-        Self {
-            alias: "local".to_string(),
-            rpc: SUI_LOCAL_NETWORK_URL.into(),
-            ws: None,
+        Self::builder("local", SUI_LOCAL_NETWORK_URL)
+            .build()
+            .expect("the localnet environment is valid")
+    }
+
+    /// A builder of the environment `alias` connecting to the fullnode at `rpc`, validating the
+    /// settings when built.
+    pub fn builder(alias: impl AsRef<str>, rpc: impl AsRef<str>) -> SuiEnvBuilder {
+        SuiEnvBuilder {
+            env: Self {
+                alias: alias.as_ref().to_string(),
+                rpc: rpc.as_ref().to_string(),
+                fallback_rpcs: vec![],
+                ws: None,
+                faucet: None,
+                proxy: None,
+                headers: BTreeMap::new(),
+                bearer_token: None,
+                bitcoin: None,
+                eth_networks: BTreeMap::new(),
+                default_gas_budget: None,
+                default_gas_price: None,
+                active_address: None,
+            },
+        }
+    }
+
+    /// Check that the settings are well-formed, as [SuiEnvBuilder::build] does.
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        if self.alias.trim().is_empty() {
+            return Err(anyhow!("The environment alias must not be empty"));
+        }
+        for rpc in std::iter::once(&self.rpc).chain(&self.fallback_rpcs) {
+            validate_url(rpc, &["http", "https"])?;
+        }
+        if let Some(ws) = &self.ws {
+            validate_url(ws, &["ws", "wss"])?;
+        }
+        if let Some(faucet) = &self.faucet {
+            validate_url(faucet, &["http", "https"])?;
         }
+        if let Some(proxy) = &self.proxy {
+            if self.ws.is_some() {
+                return Err(anyhow!(
+                    "WebSocket connections cannot go through a proxy, remove the WebSocket URL of env [{}]",
+                    self.alias
+                ));
+            }
+            validate_url(proxy, &["http", "https", "socks5", "socks5h"])?;
+        }
+        for (name, value) in &self.headers {
+            HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| anyhow!("Invalid header name [{name}]: {e}"))?;
+            HeaderValue::from_str(value)
+                .map_err(|e| anyhow!("Invalid value of header [{name}]: {e}"))?;
+        }
+        if let Some(token) = &self.bearer_token {
+            HeaderValue::from_str(&format!("Bearer {token}"))
+                .map_err(|e| anyhow!("Invalid bearer token: {e}"))?;
+        }
+        if self.eth_networks.keys().any(|name| name.trim().is_empty()) {
+            return Err(anyhow!("Ethereum network names must not be empty"));
+        }
+        if self.default_gas_budget == Some(0) || self.default_gas_price == Some(0) {
+            return Err(anyhow!(
+                "The default gas budget and gas price must not be 0"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Builds a [SuiEnv], see [SuiEnv::builder].
+///
+/// # Examples
+///
+/// ```rust
+/// use sui_sdk::sui_client_config::{EthNetworkConfig, SuiEnv};
+///
+/// let sepolia = EthNetworkConfig::builder(11155111, "https://rpc.sepolia.org").build()?;
+/// let env = SuiEnv::builder("local", "http://127.0.0.1:9000")
+///     .ws("ws://127.0.0.1:9000")
+///     .eth_network("sepolia", sepolia)
+///     .build()?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SuiEnvBuilder {
+    env: SuiEnv,
+}
+
+impl SuiEnvBuilder {
+    /// Add an RPC endpoint the client fails over to, after the previously added ones.
+    pub fn fallback_rpc(mut self, url: impl AsRef<str>) -> Self {
+        self.env.fallback_rpcs.push(url.as_ref().to_string());
+        self
+    }
+
+    pub fn ws(mut self, url: impl AsRef<str>) -> Self {
+        self.env.ws = Some(url.as_ref().to_string());
+        self
+    }
+
+    pub fn faucet(mut self, url: impl AsRef<str>) -> Self {
+        self.env.faucet = Some(url.as_ref().to_string());
+        self
+    }
+
+    pub fn proxy(mut self, url: impl AsRef<str>) -> Self {
+        self.env.proxy = Some(url.as_ref().to_string());
+        self
+    }
+
+    pub fn header(mut self, name: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        self.env
+            .headers
+            .insert(name.as_ref().to_string(), value.as_ref().to_string());
+        self
+    }
+
+    pub fn bearer_token(mut self, token: impl AsRef<str>) -> Self {
+        self.env.bearer_token = Some(token.as_ref().to_string());
+        self
+    }
+
+    pub fn bitcoin(mut self, bitcoin: BitcoinClientConfig) -> Self {
+        self.env.bitcoin = Some(bitcoin);
+        self
+    }
+
+    /// Add the Ethereum network `name`, replacing any network of the same name.
+    pub fn eth_network(mut self, name: impl AsRef<str>, network: EthNetworkConfig) -> Self {
+        self.env
+            .eth_networks
+            .insert(name.as_ref().to_string(), network);
+        self
+    }
+
+    pub fn default_gas_budget(mut self, gas_budget: u64) -> Self {
+        self.env.default_gas_budget = Some(gas_budget);
+        self
+    }
+
+    pub fn default_gas_price(mut self, gas_price: u64) -> Self {
+        self.env.default_gas_price = Some(gas_price);
+        self
+    }
+
+    pub fn active_address(mut self, address: SuiAddress) -> Self {
+        self.env.active_address = Some(address);
+        self
+    }
+
+    pub fn build(self) -> Result<SuiEnv, anyhow::Error> {
+        self.env.validate()?;
+        Ok(self.env)
     }
 }
 
@@ -150,21 +716,559 @@ impl Display for SuiEnv {
         let mut writer = String::new();
         writeln!(writer, "Active environment : {}", self.alias)?;
         write!(writer, "RPC URL: {}", self.rpc)?;
+        for rpc in &self.fallback_rpcs {
+            writeln!(writer)?;
+            write!(writer, "Fallback RPC URL: {rpc}")?;
+        }
         if let Some(ws) = &self.ws {
             writeln!(writer)?;
             write!(writer, "Websocket URL: {ws}")?;
         }
+        if let Some(faucet) = &self.faucet {
+            writeln!(writer)?;
+            write!(writer, "Faucet URL: {faucet}")?;
+        }
+        if let Some(proxy) = &self.proxy {
+            writeln!(writer)?;
+            write!(writer, "Proxy: {proxy}")?;
+        }
+        if let Some(address) = self.active_address {
+            writeln!(writer)?;
+            write!(writer, "Active address: {address}")?;
+        }
+        if let Some(gas_budget) = self.default_gas_budget {
+            writeln!(writer)?;
+            write!(writer, "Default gas budget: {gas_budget}")?;
+        }
+        if let Some(gas_price) = self.default_gas_price {
+            writeln!(writer)?;
+            write!(writer, "Default gas price: {gas_price}")?;
+        }
+        for (name, network) in &self.eth_networks {
+            writeln!(writer)?;
+            write!(
+                writer,
+                "Ethereum network [{name}]: {} (chain id {}), RPC URL: {}",
+                network.evm_chain().name,
+                network.chain_id,
+                network.execution_rpc
+            )?;
+        }
         write!(f, "{}", writer)
     }
 }
 
+/// An Ethereum network, or an EVM L2, and the on-chain object tracking its state.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EthNetworkConfig {
+    pub chain_id: u64,
+    /// Execution layer JSON-RPC endpoint.
+    pub execution_rpc: String,
+    /// Consensus layer (Beacon API) endpoint, if the network's state is verified by a light client.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consensus_rpc: Option<String>,
+    /// The request rate limit of the consensus layer endpoint's provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consensus_rpc_requests_per_second: Option<NonZeroU32>,
+    /// The object holding the verified state of the network on the dWallet network.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_object_id: Option<ObjectID>,
+    /// When execution layer data, such as the events triggering dWallet actions, is final.
+    /// Defaults to the finality rule of the chain in the EVM chain registry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finality: Option<EthFinality>,
+    /// Defaults to the native currency of the chain in the EVM chain registry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub native_currency: Option<NativeCurrency>,
+    /// Defaults to the explorer of the chain in the EVM chain registry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explorer_url: Option<String>,
+    /// The genesis validators root of the network's beacon chain, identifying the network to its
+    /// light client.
+    #[serde_as(as = "Option<Hex>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genesis_validators_root: Option<Root>,
+    /// The weak subjectivity checkpoint the network's light client is bootstrapped from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint: Option<Checkpoint>,
+    /// Known-good checkpoints, added to the compiled-in ones when explicitly trusted, see
+    /// [known_checkpoints].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub known_checkpoints: Vec<Checkpoint>,
+    /// The fork schedule of the network's beacon chain, ordered by epoch. Defaults to the
+    /// compiled-in schedule of known networks.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forks: Vec<Fork>,
+}
+
+impl EthNetworkConfig {
+    /// A builder of the network with `chain_id` and the execution layer JSON-RPC endpoint
+    /// `execution_rpc`, validating the settings when built.
+    pub fn builder(chain_id: u64, execution_rpc: impl AsRef<str>) -> EthNetworkConfigBuilder {
+        EthNetworkConfigBuilder {
+            network: Self {
+                chain_id,
+                execution_rpc: execution_rpc.as_ref().to_string(),
+                consensus_rpc: None,
+                consensus_rpc_requests_per_second: None,
+                state_object_id: None,
+                finality: None,
+                native_currency: None,
+                explorer_url: None,
+                genesis_validators_root: None,
+                checkpoint: None,
+                known_checkpoints: vec![],
+                forks: vec![],
+            },
+            trust_configured_checkpoints: false,
+        }
+    }
+
+    /// A builder of the network with the settings of this one, to change some of them and
+    /// validate the result.
+    pub fn into_builder(self) -> EthNetworkConfigBuilder {
+        EthNetworkConfigBuilder {
+            network: self,
+            trust_configured_checkpoints: false,
+        }
+    }
+}
+
+/// Builds an [EthNetworkConfig], see [EthNetworkConfig::builder].
+#[derive(Debug, Clone)]
+pub struct EthNetworkConfigBuilder {
+    network: EthNetworkConfig,
+    trust_configured_checkpoints: bool,
+}
+
+impl EthNetworkConfigBuilder {
+    pub fn chain_id(mut self, chain_id: u64) -> Self {
+        self.network.chain_id = chain_id;
+        self
+    }
+
+    pub fn execution_rpc(mut self, url: impl AsRef<str>) -> Self {
+        self.network.execution_rpc = url.as_ref().to_string();
+        self
+    }
+
+    /// The Beacon API endpoint the light client of the network follows. For known networks, the
+    /// genesis validators root defaults to the compiled-in one, and the bootstrap checkpoint to
+    /// the latest of their known checkpoints, if there is any.
+    pub fn consensus_rpc(mut self, url: impl AsRef<str>) -> Self {
+        self.network.consensus_rpc = Some(url.as_ref().to_string());
+        self
+    }
+
+    pub fn consensus_rpc_requests_per_second(mut self, requests_per_second: NonZeroU32) -> Self {
+        self.network.consensus_rpc_requests_per_second = Some(requests_per_second);
+        self
+    }
+
+    pub fn state_object_id(mut self, state_object_id: ObjectID) -> Self {
+        self.network.state_object_id = Some(state_object_id);
+        self
+    }
+
+    pub fn finality(mut self, finality: EthFinality) -> Self {
+        self.network.finality = Some(finality);
+        self
+    }
+
+    pub fn native_currency(mut self, native_currency: NativeCurrency) -> Self {
+        self.network.native_currency = Some(native_currency);
+        self
+    }
+
+    pub fn explorer_url(mut self, url: impl AsRef<str>) -> Self {
+        self.network.explorer_url = Some(url.as_ref().to_string());
+        self
+    }
+
+    pub fn genesis_validators_root(mut self, genesis_validators_root: Root) -> Self {
+        self.network.genesis_validators_root = Some(genesis_validators_root);
+        self
+    }
+
+    pub fn checkpoint(mut self, checkpoint: Checkpoint) -> Self {
+        self.network.checkpoint = Some(checkpoint);
+        self
+    }
+
+    pub fn known_checkpoint(mut self, checkpoint: Checkpoint) -> Self {
+        self.network.known_checkpoints.push(checkpoint);
+        self
+    }
+
+    /// Verify the bootstrap checkpoint against the configured known checkpoints too, and not
+    /// only against the compiled-in ones. Only set this if the known checkpoints of the network
+    /// come from a source you trust.
+    pub fn trust_configured_checkpoints(mut self) -> Self {
+        self.trust_configured_checkpoints = true;
+        self
+    }
+
+    /// The fork schedule of the network's beacon chain, ordered by epoch.
+    pub fn forks(mut self, forks: Vec<Fork>) -> Self {
+        self.network.forks = forks;
+        self
+    }
+
+    pub fn build(self) -> Result<EthNetworkConfig, anyhow::Error> {
+        let mut network = self.network;
+        if network.chain_id == 0 {
+            return Err(anyhow!("The chain id must not be 0"));
+        }
+        validate_url(&network.execution_rpc, &["http", "https"])?;
+        if let Some(explorer_url) = &network.explorer_url {
+            validate_url(explorer_url, &["http", "https"])?;
+        }
+        if !network
+            .forks
+            .windows(2)
+            .all(|forks| forks[0].epoch <= forks[1].epoch)
+        {
+            return Err(anyhow!("The forks must be ordered by epoch"));
+        }
+
+        let Some(consensus_rpc) = &network.consensus_rpc else {
+            if network.genesis_validators_root.is_some() || network.checkpoint.is_some() {
+                return Err(anyhow!(
+                    "A light client bootstrap configuration requires a consensus RPC"
+                ));
+            }
+            return Ok(network);
+        };
+        validate_url(consensus_rpc, &["http", "https"])?;
+        if let Some(known) = known_network(network.chain_id) {
+            network
+                .genesis_validators_root
+                .get_or_insert(known.genesis_validators_root);
+            if network.checkpoint.is_none() {
+                network.checkpoint = known_checkpoints(&network, self.trust_configured_checkpoints)
+                    .last()
+                    .copied();
+            }
+        }
+        verify_bootstrap_config(&network, self.trust_configured_checkpoints)?;
+        known_forks(&network)?;
+        Ok(network)
+    }
+}
+
+/// Check that `url` is a URL with one of the `schemes`.
+fn validate_url(url: &str, schemes: &[&str]) -> Result<(), anyhow::Error> {
+    let parsed = Url::parse(url).map_err(|e| anyhow!("Invalid URL [{url}]: {e}"))?;
+    if !schemes.contains(&parsed.scheme()) {
+        return Err(anyhow!(
+            "Invalid URL [{url}]: the scheme must be one of {}",
+            schemes.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// The elliptic curve of the keys of a dWallet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DWalletCurve {
+    #[default]
+    Secp256k1,
+}
+
+impl Display for DWalletCurve {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Secp256k1 => write!(f, "secp256k1"),
+        }
+    }
+}
+
+/// A dWallet of the registry of the client config, see [SuiClientConfig::resolve_dwallet].
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DWalletSecretShare {
     pub alias: String,
     //pub public_key: String,
-    pub dkg_output: DKGCentralizedPartyOutput,
+    /// The user share of the dWallet, `None` if it is held encrypted, see `encrypted_share_path`,
+    /// or in the keyring, see `keyring_service`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dkg_output: Option<DKGCentralizedPartyOutput>,
     pub dwallet_id: ObjectID,
     pub dwallet_cap_id: ObjectID,
+    #[serde(default)]
+    pub curve: DWalletCurve,
+    /// The addresses of the dWallet on external chains, by chain, e.g. `ethereum`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub external_addresses: BTreeMap<String, String>,
+    /// The local file holding the encrypted user share of the dWallet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encrypted_share_path: Option<PathBuf>,
+    /// The keyring service holding the user share of the dWallet, see [Self::move_share_to_keyring].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyring_service: Option<String>,
+    /// The shared `SigningPolicy` holding the capability of the dWallet, through which its
+    /// messages are approved, see `sui client dwallet policy`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_policy_id: Option<ObjectID>,
+    /// The sign sessions of the dWallet the network did not sign, by the session that requested
+    /// their signatures again, see `sui client dwallet retry`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub session_retries: BTreeMap<ObjectID, ObjectID>,
+}
+
+impl DWalletSecretShare {
+    /// The user share of the dWallet, needed to sign with it. Read from the keyring if it is held
+    /// there, which may ask the user to unlock it.
+    pub fn dkg_output(&self) -> Result<DKGCentralizedPartyOutput, anyhow::Error> {
+        if let Some(dkg_output) = &self.dkg_output {
+            return Ok(dkg_output.clone());
+        }
+        if let Some(service) = &self.keyring_service {
+            let share = keyring::get_secret(service, &self.keyring_entry())?.ok_or_else(|| {
+                anyhow!(
+                    "The user share of dWallet [{}] is missing from the keyring",
+                    self.alias
+                )
+            })?;
+            return serde_json::from_str(&share).with_context(|| {
+                format!(
+                    "Invalid user share of dWallet [{}] in the keyring",
+                    self.alias
+                )
+            });
+        }
+        Err(match &self.encrypted_share_path {
+            Some(path) => anyhow!(
+                "The user share of dWallet [{}] is encrypted in {}, decrypt it first",
+                self.alias,
+                path.display()
+            ),
+            None => anyhow!("No user share held for dWallet [{}]", self.alias),
+        })
+    }
+
+    /// Move the user share of the dWallet to the keyring service `service`, for the config file
+    /// to hold no secret of the dWallet. See [sui_keys::keystore::KeyringKeystore].
+    pub fn move_share_to_keyring(&mut self, service: &str) -> Result<(), anyhow::Error> {
+        let Some(dkg_output) = &self.dkg_output else {
+            return Err(anyhow!("No user share held for dWallet [{}]", self.alias));
+        };
+        keyring::set_secret(
+            service,
+            &self.keyring_entry(),
+            &serde_json::to_string(dkg_output)?,
+        )?;
+        self.dkg_output = None;
+        self.keyring_service = Some(service.to_string());
+        Ok(())
+    }
+
+    /// Encrypt the user share of the dWallet with `key`, the share encryption key of the account
+    /// owning the dWallet, to the file at `path`, for the config file to hold no secret of the
+    /// dWallet. See [Self::decrypt_share].
+    pub fn encrypt_share(
+        &mut self,
+        key: &ShareEncryptionKey,
+        path: PathBuf,
+    ) -> Result<(), anyhow::Error> {
+        let Some(dkg_output) = &self.dkg_output else {
+            return Err(anyhow!("No user share held for dWallet [{}]", self.alias));
+        };
+        let share = Zeroizing::new(serde_json::to_vec(dkg_output)?);
+        fs::write(&path, key.encrypt(&share, self.dwallet_id.as_ref())?)
+            .with_context(|| format!("Cannot write the user share to {}", path.display()))?;
+        self.dkg_output = None;
+        self.encrypted_share_path = Some(path);
+        Ok(())
+    }
+
+    /// The user share of the dWallet, decrypted with `key` from the file it was encrypted to by
+    /// [Self::encrypt_share].
+    pub fn decrypt_share(
+        &self,
+        key: &ShareEncryptionKey,
+    ) -> Result<DKGCentralizedPartyOutput, anyhow::Error> {
+        let Some(path) = &self.encrypted_share_path else {
+            return self.dkg_output();
+        };
+        let encrypted = fs::read(path)
+            .with_context(|| format!("Cannot read the user share from {}", path.display()))?;
+        let share = key
+            .decrypt(&encrypted, self.dwallet_id.as_ref())
+            .with_context(|| {
+                format!("Cannot decrypt the user share of dWallet [{}]", self.alias)
+            })?;
+        serde_json::from_slice(&share)
+            .with_context(|| format!("Invalid user share of dWallet [{}]", self.alias))
+    }
+
+    /// Encrypt the user share of the dWallet again, from `old_key` to `new_key`, when the dWallet
+    /// is transferred to a new account. Shares not held encrypted are left as they are.
+    pub fn reencrypt_share(
+        &mut self,
+        old_key: &ShareEncryptionKey,
+        new_key: &ShareEncryptionKey,
+    ) -> Result<(), anyhow::Error> {
+        let Some(path) = self.encrypted_share_path.clone() else {
+            return Ok(());
+        };
+        let dkg_output = self.decrypt_share(old_key)?;
+        let share = Zeroizing::new(serde_json::to_vec(&dkg_output)?);
+        fs::write(&path, new_key.encrypt(&share, self.dwallet_id.as_ref())?)
+            .with_context(|| format!("Cannot write the user share to {}", path.display()))
+    }
+
+    /// Export the dWallet with `dkg_output`, its user share, encrypted with the key derived from
+    /// `passphrase`, for it to be imported into another client config.
+    pub fn export_share_with_passphrase(
+        &self,
+        dkg_output: &DKGCentralizedPartyOutput,
+        passphrase: &str,
+    ) -> Result<ExportedDWalletShare, anyhow::Error> {
+        let kdf = Kdf::argon2id();
+        let share = Zeroizing::new(serde_json::to_vec(dkg_output)?);
+        let encrypted_share = ShareEncryptionKey::from_passphrase(passphrase, &kdf)?
+            .encrypt(&share, self.dwallet_id.as_ref())?;
+        Ok(self.exported(ShareExportEncryption::Passphrase { kdf }, encrypted_share))
+    }
+
+    /// Export the dWallet with `dkg_output`, its user share, encrypted to `transfer_key`, the
+    /// public key of the [ShareTransferKey] of the account to import it.
+    pub fn export_share_to(
+        &self,
+        dkg_output: &DKGCentralizedPartyOutput,
+        transfer_key: &[u8],
+    ) -> Result<ExportedDWalletShare, anyhow::Error> {
+        let share = Zeroizing::new(serde_json::to_vec(dkg_output)?);
+        let encrypted_share = encrypt_share_to(transfer_key, &share, self.dwallet_id.as_ref())?;
+        Ok(self.exported(
+            ShareExportEncryption::TransferKey {
+                transfer_key: transfer_key.to_vec(),
+            },
+            encrypted_share,
+        ))
+    }
+
+    fn exported(
+        &self,
+        encryption: ShareExportEncryption,
+        encrypted_share: Vec<u8>,
+    ) -> ExportedDWalletShare {
+        ExportedDWalletShare {
+            alias: self.alias.clone(),
+            dwallet_id: self.dwallet_id,
+            dwallet_cap_id: self.dwallet_cap_id,
+            curve: self.curve,
+            external_addresses: self.external_addresses.clone(),
+            encryption,
+            encrypted_share,
+        }
+    }
+
+    /// The name of the keyring entry of the user share, distinct from the ones of keys.
+    fn keyring_entry(&self) -> String {
+        format!("dwallet:{}", self.dwallet_id)
+    }
+}
+
+/// A dWallet exported from the registry of a client config with its user share, encrypted, see
+/// [DWalletSecretShare::export_share_with_passphrase] and [DWalletSecretShare::export_share_to].
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedDWalletShare {
+    pub alias: String,
+    pub dwallet_id: ObjectID,
+    pub dwallet_cap_id: ObjectID,
+    #[serde(default)]
+    pub curve: DWalletCurve,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub external_addresses: BTreeMap<String, String>,
+    pub encryption: ShareExportEncryption,
+    /// The user share, encrypted with the id of the dWallet as context.
+    #[serde_as(as = "Base64")]
+    pub encrypted_share: Vec<u8>,
+}
+
+/// How the user share of an [ExportedDWalletShare] is encrypted.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "scheme", rename_all = "kebab-case")]
+pub enum ShareExportEncryption {
+    /// With the [ShareEncryptionKey] derived from a passphrase with `kdf`.
+    Passphrase { kdf: Kdf },
+    /// To the public key of a [ShareTransferKey], see [encrypt_share_to].
+    TransferKey {
+        #[serde_as(as = "Hex")]
+        transfer_key: Vec<u8>,
+    },
+}
+
+impl ExportedDWalletShare {
+    /// The user share, decrypted with the key derived from `passphrase`.
+    pub fn decrypt_with_passphrase(
+        &self,
+        passphrase: &str,
+    ) -> Result<DKGCentralizedPartyOutput, anyhow::Error> {
+        let ShareExportEncryption::Passphrase { kdf } = &self.encryption else {
+            return Err(anyhow!(
+                "The user share of dWallet [{}] is not encrypted with a passphrase",
+                self.alias
+            ));
+        };
+        let share = ShareEncryptionKey::from_passphrase(passphrase, kdf)?
+            .decrypt(&self.encrypted_share, self.dwallet_id.as_ref())
+            .with_context(|| {
+                format!("Cannot decrypt the user share of dWallet [{}]", self.alias)
+            })?;
+        self.parse_share(&share)
+    }
+
+    /// The user share, decrypted with `key`, whose public key it must be encrypted to.
+    pub fn decrypt_with_transfer_key(
+        &self,
+        key: &ShareTransferKey,
+    ) -> Result<DKGCentralizedPartyOutput, anyhow::Error> {
+        let ShareExportEncryption::TransferKey { transfer_key } = &self.encryption else {
+            return Err(anyhow!(
+                "The user share of dWallet [{}] is not encrypted to a public key",
+                self.alias
+            ));
+        };
+        if *transfer_key != key.public_key() {
+            return Err(anyhow!(
+                "The user share of dWallet [{}] is encrypted to another public key",
+                self.alias
+            ));
+        }
+        let share = key
+            .decrypt(&self.encrypted_share, self.dwallet_id.as_ref())
+            .with_context(|| {
+                format!("Cannot decrypt the user share of dWallet [{}]", self.alias)
+            })?;
+        self.parse_share(&share)
+    }
+
+    /// The registry entry of the dWallet, holding `dkg_output`, its decrypted user share.
+    pub fn into_dwallet(self, dkg_output: DKGCentralizedPartyOutput) -> DWalletSecretShare {
+        DWalletSecretShare {
+            alias: self.alias,
+            dkg_output: Some(dkg_output),
+            dwallet_id: self.dwallet_id,
+            dwallet_cap_id: self.dwallet_cap_id,
+            curve: self.curve,
+            external_addresses: self.external_addresses,
+            encrypted_share_path: None,
+            keyring_service: None,
+            signing_policy_id: None,
+            session_retries: BTreeMap::new(),
+        }
+    }
+
+    fn parse_share(&self, share: &[u8]) -> Result<DKGCentralizedPartyOutput, anyhow::Error> {
+        serde_json::from_slice(share)
+            .with_context(|| format!("Invalid user share of dWallet [{}]", self.alias))
+    }
 }
 
 impl Display for DWalletSecretShare {
@@ -173,11 +1277,103 @@ impl Display for DWalletSecretShare {
         writeln!(writer, "Active dWallet : {}", self.alias)?;
         write!(writer, "dwallet_id: {}", self.dwallet_id)?;
         write!(writer, "dwallet_cap_id: {}", self.dwallet_cap_id)?;
+        for (chain, address) in &self.external_addresses {
+            writeln!(writer)?;
+            write!(writer, "{chain} address: {address}")?;
+        }
         write!(f, "{}", writer)
     }
 }
 
-impl Config for SuiClientConfig {}
+impl Config for SuiClientConfig {
+    /// Load the config file at `path`, migrating it from an older version if needed. The migrated
+    /// config is written on the next save.
+    fn load<P: AsRef<Path>>(path: P) -> Result<Self, anyhow::Error> {
+        let path = path.as_ref();
+        let reader = fs::File::open(path)
+            .with_context(|| format!("Unable to load config from {}", path.display()))?;
+        Self::migrate(serde_yaml::from_reader(reader)?)
+    }
+}
+
+/// The migrations of the client config format, the one at index `i` upgrading a config of
+/// version `i` to version `i + 1`.
+const MIGRATIONS: [fn(&mut Mapping); SUI_CLIENT_CONFIG_VERSION as usize] = [migrate_v0];
+
+impl SuiClientConfig {
+    /// Deserialize `config`, a client config of any version up to [SUI_CLIENT_CONFIG_VERSION],
+    /// upgrading it to the current version first.
+    pub fn migrate(config: Value) -> Result<Self, anyhow::Error> {
+        let Value::Mapping(mut config) = config else {
+            return Err(anyhow!("The client config must be a mapping"));
+        };
+        let version = match config.get(&"version".into()) {
+            Some(version) => version
+                .as_u64()
+                .ok_or_else(|| anyhow!("Invalid client config version [{version:?}]"))?,
+            None => 0,
+        };
+        if version > SUI_CLIENT_CONFIG_VERSION {
+            return Err(anyhow!(
+                "The client config version {version} is newer than the latest version supported by this release, {SUI_CLIENT_CONFIG_VERSION}"
+            ));
+        }
+        for migration in &MIGRATIONS[version as usize..] {
+            migration(&mut config);
+        }
+        config.insert("version".into(), SUI_CLIENT_CONFIG_VERSION.into());
+        Ok(serde_yaml::from_value(Value::Mapping(config))?)
+    }
+}
+
+/// Version 0, the unversioned format, had no dWallets, and configured at most one Ethereum
+/// network per environment, as `eth_client_settings`.
+fn migrate_v0(config: &mut Mapping) {
+    let dwallets = Value::from("dwallets");
+    if !config.contains_key(&dwallets) {
+        config.insert(dwallets, Value::Sequence(vec![]));
+    }
+    let Some(Value::Sequence(envs)) = config.get_mut(&"envs".into()) else {
+        return;
+    };
+    for env in envs {
+        let Value::Mapping(env) = env else {
+            continue;
+        };
+        let Some(Value::Mapping(settings)) = env.remove(&"eth_client_settings".into()) else {
+            continue;
+        };
+        let mut network = Mapping::new();
+        for (from, to) in [
+            ("eth_chain_id", "chain_id"),
+            ("eth_execution_rpc", "execution_rpc"),
+            ("eth_consensus_rpc", "consensus_rpc"),
+            ("state_object_id", "state_object_id"),
+        ] {
+            match settings.get(&from.into()) {
+                Some(Value::Null) | None => {}
+                Some(value) => {
+                    network.insert(to.into(), value.clone());
+                }
+            }
+        }
+        if settings.contains_key(&"checkpoint".into()) {
+            // A bare block root cannot be verified against the known-good checkpoints.
+            warn!(
+                "Dropping the Ethereum light client checkpoint of env [{}], configure it again with its epoch",
+                env.get(&"alias".into()).and_then(Value::as_str).unwrap_or_default()
+            );
+        }
+        let name = network
+            .get(&"chain_id".into())
+            .and_then(Value::as_u64)
+            .and_then(known_network)
+            .map_or("ethereum", |known| known.name.as_str());
+        let mut networks = Mapping::new();
+        networks.insert(name.into(), Value::Mapping(network));
+        env.insert("eth_networks".into(), Value::Mapping(networks));
+    }
+}
 
 impl Display for SuiClientConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -189,7 +1385,7 @@ impl Display for SuiClientConfig {
             self.keystore.addresses().len()
         )?;
         write!(writer, "Active address: ")?;
-        match self.active_address {
+        match self.get_active_address() {
             Some(r) => writeln!(writer, "{}", r)?,
             None => writeln!(writer, "None")?,
         };
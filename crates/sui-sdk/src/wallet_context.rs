@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
+use crate::offline;
 use crate::sui_client_config::SuiClientConfig;
 use crate::SuiClient;
 use anyhow::anyhow;
@@ -14,6 +15,7 @@ use sui_json_rpc_types::{
     SuiObjectData, SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse,
     SuiObjectResponseQuery, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
+use sui_keys::external_signer::{self, ExternalSigner};
 use sui_keys::keystore::AccountKeystore;
 use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
 use sui_types::gas_coin::GasCoin;
@@ -26,6 +28,7 @@ pub struct WalletContext {
     request_timeout: Option<std::time::Duration>,
     client: Arc<RwLock<Option<SuiClient>>>,
     max_concurrent_requests: Option<u64>,
+    external_signer: Option<Arc<dyn ExternalSigner>>,
 }
 
 impl WalletContext {
@@ -47,10 +50,18 @@ impl WalletContext {
             request_timeout,
             client: Default::default(),
             max_concurrent_requests,
+            external_signer: None,
         };
         Ok(context)
     }
 
+    /// Sign the transactions of senders not in the keystore with `signer`, see
+    /// [Self::try_sign_transaction].
+    pub fn with_external_signer(mut self, signer: Arc<dyn ExternalSigner>) -> Self {
+        self.external_signer = Some(signer);
+        self
+    }
+
     pub fn get_addresses(&self) -> Vec<SuiAddress> {
         self.config.keystore.addresses()
     }
@@ -96,15 +107,14 @@ impl WalletContext {
             ));
         }
 
+        if let Some(address) = self.config.get_active_address() {
+            return Ok(address);
+        }
         // Ok to unwrap because we checked that config addresses not empty
         // Set it if not exists
-        self.config.active_address = Some(
-            self.config
-                .active_address
-                .unwrap_or(*self.config.keystore.addresses().get(0).unwrap()),
-        );
-
-        Ok(self.config.active_address.unwrap())
+        let address = *self.config.keystore.addresses().get(0).unwrap();
+        self.config.active_address = Some(address);
+        Ok(address)
     }
 
     /// Get the latest object reference given a object id
@@ -291,6 +301,33 @@ impl WalletContext {
         Ok(gas_price)
     }
 
+    /// The gas budget of a transaction: `gas_budget` if given, or else the default gas budget of
+    /// the active environment.
+    pub fn gas_budget(&self, gas_budget: Option<u64>) -> Result<u64, anyhow::Error> {
+        if let Some(gas_budget) = gas_budget {
+            return Ok(gas_budget);
+        }
+        let env = self.config.get_active_env()?;
+        env.default_gas_budget.ok_or_else(|| {
+            anyhow!(
+                "No gas budget given, and no default gas budget configured for env [{}]",
+                env.alias
+            )
+        })
+    }
+
+    /// Set the gas price of `data` to the default gas price of the active environment, if it has
+    /// one.
+    pub fn with_default_gas_price(
+        &self,
+        mut data: TransactionData,
+    ) -> Result<TransactionData, anyhow::Error> {
+        if let Some(gas_price) = self.config.get_active_env()?.default_gas_price {
+            data.gas_data_mut().price = gas_price;
+        }
+        Ok(data)
+    }
+
     /// Sign a transaction with a key currently managed by the WalletContext
     pub fn sign_transaction(&self, data: &TransactionData) -> Transaction {
         let sig = self
@@ -302,6 +339,52 @@ impl WalletContext {
         Transaction::from_data(data.clone(), vec![sig])
     }
 
+    /// Sign a transaction with a key of the keystore or, if the sender is not in the keystore,
+    /// with the external signer of the WalletContext.
+    pub async fn try_sign_transaction(
+        &self,
+        data: &TransactionData,
+    ) -> Result<Transaction, anyhow::Error> {
+        let sender = data.sender();
+        let sig = match &self.external_signer {
+            Some(signer) if !self.config.keystore.addresses().contains(&sender) => {
+                external_signer::sign_secure(
+                    signer.as_ref(),
+                    &sender,
+                    data,
+                    Intent::sui_transaction(),
+                )
+                .await?
+            }
+            _ => self
+                .config
+                .keystore
+                .sign_secure(&sender, data, Intent::sui_transaction())?,
+        };
+        Ok(Transaction::from_data(data.clone(), vec![sig]))
+    }
+
+    /// Sign the unsigned transaction of `unsigned_path` with a key currently managed by the
+    /// WalletContext, and write it to `signed_path`. Needs no connection to the network, see
+    /// [crate::offline].
+    pub fn sign_transaction_file(
+        &self,
+        unsigned_path: &Path,
+        signed_path: &Path,
+    ) -> anyhow::Result<Transaction> {
+        offline::sign_transaction_file(&self.config.keystore, unsigned_path, signed_path)
+    }
+
+    /// Execute the signed transaction of `path`, written by [Self::sign_transaction_file], and
+    /// wait for it to be locally executed on the fullnode.
+    pub async fn execute_signed_transaction_file(
+        &self,
+        path: &Path,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        self.execute_transaction_may_fail(offline::read_signed_transaction(path)?)
+            .await
+    }
+
     /// Execute a transaction and wait for it to be locally executed on the fullnode.
     /// Also expects the effects status to be ExecutionStatus::Success.
     pub async fn execute_transaction_must_succeed(
@@ -3,6 +3,7 @@
 use tempfile::TempDir;
 
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_sdk::dwallet_signer::DWalletSigner;
 use sui_types::crypto::Ed25519SuiSignature;
 use sui_types::crypto::{SignatureScheme, SuiSignatureInner};
 #[test]
@@ -32,3 +33,2034 @@ fn keystore_display_test() -> Result<(), anyhow::Error> {
     assert!(!keystore.to_string().contains("keys:"));
     Ok(())
 }
+
+/// Signs with a local secp256k1 key, the way the network signs for a dWallet.
+struct LocalSigner(bitcoin::secp256k1::SecretKey);
+
+#[async_trait::async_trait]
+impl DWalletSigner for LocalSigner {
+    fn public_key(&self) -> Vec<u8> {
+        self.0
+            .public_key(&bitcoin::secp256k1::Secp256k1::signing_only())
+            .serialize()
+            .to_vec()
+    }
+
+    async fn sign(
+        &self,
+        messages: Vec<Vec<u8>>,
+        hash: sui_sdk::dwallet_signer::Hash,
+    ) -> sui_sdk::error::SuiRpcResult<Vec<Vec<u8>>> {
+        use bitcoin::hashes::{sha256, Hash};
+        use fastcrypto::hash::{HashFunction, Keccak256};
+        let secp = bitcoin::secp256k1::Secp256k1::signing_only();
+        Ok(messages
+            .iter()
+            .map(|message| {
+                let digest = match hash {
+                    sui_sdk::dwallet_signer::Hash::KECCAK256 => Keccak256::digest(message).digest,
+                    sui_sdk::dwallet_signer::Hash::SHA256 => {
+                        sha256::Hash::hash(message).to_byte_array()
+                    }
+                };
+                let message = bitcoin::secp256k1::Message::from_slice(&digest).unwrap();
+                secp.sign_ecdsa(&message, &self.0)
+                    .serialize_compact()
+                    .to_vec()
+            })
+            .collect())
+    }
+}
+
+#[tokio::test]
+async fn cosmos_sign_tx_test() {
+    use sui_sdk::cosmos;
+
+    let signer = LocalSigner(bitcoin::secp256k1::SecretKey::from_slice(&[1; 32]).unwrap());
+    let from = cosmos::address(&signer.public_key(), "cosmos").unwrap();
+    let body = cosmos::TxBody {
+        messages: vec![cosmos::msg_send(
+            &from,
+            "cosmos1w508d6qejxtdg4y5r3zarvary0c5xw7k6ah60c",
+            vec![cosmos::coin(1000, "uatom")],
+        )],
+        memo: String::new(),
+        timeout_height: 0,
+    };
+    let fee = cosmos::Fee {
+        amount: vec![cosmos::coin(500, "uatom")],
+        gas_limit: 200_000,
+        payer: String::new(),
+        granter: String::new(),
+    };
+    let tx = cosmos::sign_tx(&body, fee, "cosmoshub-4", 7, 3, &signer)
+        .await
+        .unwrap();
+
+    assert_eq!(tx.signatures.len(), 1);
+    // The signature is low-S.
+    let signature = bitcoin::secp256k1::ecdsa::Signature::from_compact(&tx.signatures[0]).unwrap();
+    let mut normalized = signature;
+    normalized.normalize_s();
+    assert_eq!(signature, normalized);
+}
+
+struct LocalEd25519Signer(fastcrypto::ed25519::Ed25519KeyPair);
+
+#[async_trait::async_trait]
+impl sui_sdk::dwallet_signer::Ed25519DWalletSigner for LocalEd25519Signer {
+    fn public_key(&self) -> [u8; 32] {
+        use fastcrypto::traits::{KeyPair, ToFromBytes};
+        self.0.public().as_bytes().try_into().unwrap()
+    }
+
+    async fn sign(&self, messages: Vec<Vec<u8>>) -> sui_sdk::error::SuiRpcResult<Vec<Vec<u8>>> {
+        use fastcrypto::traits::{Signer, ToFromBytes};
+        Ok(messages
+            .iter()
+            .map(|message| self.0.sign(message).as_bytes().to_vec())
+            .collect())
+    }
+}
+
+#[tokio::test]
+async fn sol_sign_transaction_test() {
+    use fastcrypto::traits::KeyPair;
+    use rand::SeedableRng;
+    use sui_sdk::dwallet_signer::Ed25519DWalletSigner;
+    use sui_sdk::sol;
+
+    assert_eq!(
+        sol::address(&sol::SYSTEM_PROGRAM_ID),
+        "11111111111111111111111111111111"
+    );
+
+    let signer = LocalEd25519Signer(fastcrypto::ed25519::Ed25519KeyPair::generate(
+        &mut rand::rngs::StdRng::from_seed([0; 32]),
+    ));
+    let from = signer.public_key();
+    let to = [7; 32];
+    assert_eq!(
+        sol::pubkey_from_address(&sol::address(&from)).unwrap(),
+        from
+    );
+
+    let message =
+        sol::Message::new(&[sol::system_transfer(&from, &to, 1_000)], &from, [9; 32]).unwrap();
+    assert_eq!(message.account_keys, vec![from, to, sol::SYSTEM_PROGRAM_ID]);
+    assert_eq!(message.header.num_required_signatures, 1);
+    assert_eq!(message.header.num_readonly_unsigned_accounts, 1);
+
+    let tx = sol::sign_transaction(message.clone(), &signer)
+        .await
+        .unwrap();
+    let bytes = tx.serialize();
+    assert_eq!(bytes[0], 1);
+    assert_eq!(&bytes[65..], message.serialize().as_slice());
+
+    // The dWallet must be the only signer.
+    let other_payer =
+        sol::Message::new(&[sol::system_transfer(&to, &from, 1)], &to, [9; 32]).unwrap();
+    assert!(sol::sign_transaction(other_payer, &signer).await.is_err());
+}
+
+#[tokio::test]
+async fn foreign_sui_sign_transaction_test() {
+    use fastcrypto::traits::KeyPair;
+    use rand::SeedableRng;
+    use shared_crypto::intent::{Intent, IntentMessage};
+    use sui_sdk::dwallet_signer::Ed25519DWalletSigner;
+    use sui_sdk::foreign_sui;
+    use sui_types::base_types::{random_object_ref, SuiAddress};
+    use sui_types::crypto::Signature;
+    use sui_types::signature::GenericSignature;
+    use sui_types::transaction::TransactionData;
+
+    let keypair =
+        fastcrypto::ed25519::Ed25519KeyPair::generate(&mut rand::rngs::StdRng::from_seed([0; 32]));
+    let sender = SuiAddress::from(keypair.public());
+    let signer = LocalEd25519Signer(keypair.copy());
+    assert_eq!(foreign_sui::address(&signer.public_key()).unwrap(), sender);
+
+    let tx_data = TransactionData::new_transfer_sui(
+        SuiAddress::random_for_testing_only(),
+        sender,
+        Some(1_000),
+        random_object_ref(),
+        10_000_000,
+        1_000,
+    );
+    let tx = foreign_sui::sign_transaction(tx_data.clone(), &signer)
+        .await
+        .unwrap();
+    // Ed25519 signatures are deterministic, so the dWallet signs as the key pair would.
+    let expected = Signature::new_secure(
+        &IntentMessage::new(Intent::sui_transaction(), tx_data.clone()),
+        &keypair,
+    );
+    assert_eq!(
+        tx.data().tx_signatures(),
+        &[GenericSignature::from(expected)]
+    );
+
+    // The dWallet must be the sender.
+    let other_sender = TransactionData::new_transfer_sui(
+        sender,
+        SuiAddress::random_for_testing_only(),
+        Some(1_000),
+        random_object_ref(),
+        10_000_000,
+        1_000,
+    );
+    assert!(foreign_sui::sign_transaction(other_sender, &signer)
+        .await
+        .is_err());
+}
+
+#[test]
+fn eth_bootstrap_config_test() {
+    use sui_sdk::eth_light_client::verify_bootstrap_config;
+    use sui_sdk::sui_client_config::EthNetworkConfig;
+    use sui_types::eth_light_client::Checkpoint;
+
+    let checkpoint = Checkpoint {
+        epoch: 300_000,
+        block_root: [1; 32],
+    };
+    let mut network: EthNetworkConfig = serde_json::from_value(serde_json::json!({
+        "chain_id": 1,
+        "execution_rpc": "http://127.0.0.1:8545",
+        "genesis_validators_root": "0x4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95",
+    }))
+    .unwrap();
+
+    // No checkpoint configured.
+    assert!(verify_bootstrap_config(&network, true).is_err());
+
+    // A checkpoint that is not known.
+    network.checkpoint = Some(checkpoint);
+    assert!(verify_bootstrap_config(&network, true).is_err());
+
+    // A configured known checkpoint only vouches for the checkpoint when trusted.
+    network.known_checkpoints = vec![checkpoint];
+    assert!(verify_bootstrap_config(&network, false).is_err());
+    assert_eq!(verify_bootstrap_config(&network, true).unwrap(), checkpoint);
+
+    // Another genesis validators root than the one of mainnet.
+    network.genesis_validators_root = Some([2; 32]);
+    assert!(verify_bootstrap_config(&network, true).is_err());
+}
+
+#[test]
+fn eth_known_forks_test() {
+    use sui_sdk::eth_light_client::known_forks;
+    use sui_sdk::sui_client_config::EthNetworkConfig;
+    use sui_types::eth_light_client::ForkName;
+
+    let mut network: EthNetworkConfig = serde_json::from_value(serde_json::json!({
+        "chain_id": 1,
+        "execution_rpc": "http://127.0.0.1:8545",
+    }))
+    .unwrap();
+    let forks = known_forks(&network).unwrap();
+    assert_eq!(forks.last().unwrap().name, ForkName::Electra);
+    assert_eq!(forks.last().unwrap().epoch, 364032);
+
+    // The configured fork schedule overrides the compiled-in one.
+    network.forks = forks[..1].to_vec();
+    assert_eq!(known_forks(&network).unwrap(), forks[..1]);
+
+    network.chain_id = 999_999;
+    network.forks = vec![];
+    assert!(known_forks(&network).is_err());
+}
+
+#[test]
+fn evm_chain_registry_test() {
+    use sui_sdk::eth_client::EthFinality;
+    use sui_sdk::evm_chains::{evm_chain, NativeCurrency};
+    use sui_sdk::sui_client_config::EthNetworkConfig;
+
+    // The chain ids of the compiled-in chains are unique.
+    let chains = sui_sdk::evm_chains::evm_chains();
+    let chain_ids: std::collections::BTreeSet<_> =
+        chains.iter().map(|chain| chain.chain_id).collect();
+    assert_eq!(chain_ids.len(), chains.len());
+
+    let base: EthNetworkConfig = serde_json::from_value(serde_json::json!({
+        "chain_id": 8453,
+        "execution_rpc": "http://127.0.0.1:8545",
+    }))
+    .unwrap();
+    assert_eq!(&base.evm_chain(), evm_chain(8453).unwrap());
+    assert_eq!(
+        base.evm_chain().transaction_url("0x01").unwrap(),
+        "https://basescan.org/tx/0x01"
+    );
+
+    // A chain that is not compiled in is a config entry.
+    let l2: EthNetworkConfig = serde_json::from_value(serde_json::json!({
+        "chain_id": 999_999,
+        "execution_rpc": "http://127.0.0.1:8545",
+        "finality": { "depth": 20 },
+        "native_currency": { "symbol": "L2", "decimals": 9 },
+        "explorer_url": "https://explorer.example/",
+    }))
+    .unwrap();
+    let chain = l2.evm_chain();
+    assert_eq!(chain.finality, EthFinality::Depth(20));
+    assert_eq!(
+        chain.native_currency,
+        NativeCurrency {
+            symbol: "L2".to_string(),
+            decimals: 9
+        }
+    );
+    assert_eq!(
+        chain.address_url("0x02").unwrap(),
+        "https://explorer.example/address/0x02"
+    );
+    assert_eq!(l2.build_client().finality(), EthFinality::Depth(20));
+}
+
+#[tokio::test]
+async fn attestation_test() {
+    use sui_sdk::attestation::{attestation_message, sign_attestation, verify_attestation};
+    use sui_sdk::dwallet_signer::Hash;
+
+    assert_eq!(
+        attestation_message(b"price-feed:v1", &[1, 2]),
+        [&b"dwallet-attestation:v1\x0dprice-feed:v1"[..], &[1, 2]].concat()
+    );
+
+    let signer = LocalSigner(bitcoin::secp256k1::SecretKey::from_slice(&[1; 32]).unwrap());
+    let public_key = signer.public_key();
+    let signature = sign_attestation(b"price-feed:v1", b"BTC/USD 65000", Hash::SHA256, &signer)
+        .await
+        .unwrap();
+    verify_attestation(
+        &public_key,
+        b"price-feed:v1",
+        b"BTC/USD 65000",
+        Hash::SHA256,
+        &signature,
+    )
+    .unwrap();
+
+    // The signature does not verify for another schema, nor for the bare payload.
+    assert!(verify_attestation(
+        &public_key,
+        b"price-feed:v2",
+        b"BTC/USD 65000",
+        Hash::SHA256,
+        &signature
+    )
+    .is_err());
+    let bare = signer
+        .sign(vec![b"BTC/USD 65000".to_vec()], Hash::SHA256)
+        .await
+        .unwrap();
+    assert_ne!(bare[0], signature);
+
+    assert!(
+        sign_attestation(b"", b"BTC/USD 65000", Hash::SHA256, &signer)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn dwallet_intent_test() {
+    use sui_sdk::dwallet_intent::{sign_intent, verify_signed_intent, DWalletIntent};
+    use sui_sdk::dwallet_signer::Hash;
+
+    let signer = LocalSigner(bitcoin::secp256k1::SecretKey::from_slice(&[1; 32]).unwrap());
+    let payload = b"transfer 100 to 0xabc";
+    let intent = DWalletIntent::new("eip155:1", 7, 1_000, payload).unwrap();
+    let signature = sign_intent(&intent, Hash::SHA256, &signer).await.unwrap();
+
+    let verify = |chain: &str, now_ms: u64| {
+        verify_signed_intent(
+            &signer.public_key(),
+            &intent.encode(),
+            Hash::SHA256,
+            &signature,
+            chain,
+            payload,
+            now_ms,
+        )
+    };
+    assert_eq!(verify("eip155:1", 1_000).unwrap(), intent);
+    // The signature cannot be reused on another chain, nor after the expiry.
+    assert!(verify("eip155:10", 1_000).is_err());
+    assert!(verify("eip155:1", 1_001).is_err());
+}
+
+#[tokio::test]
+async fn client_builder_proxy_test() {
+    use sui_sdk::error::Error;
+    use sui_sdk::SuiClientBuilder;
+
+    let result = SuiClientBuilder::default()
+        .proxy("socks5h://127.0.0.1:9050")
+        .ws_url("ws://127.0.0.1:9000")
+        .build("http://127.0.0.1:9000")
+        .await;
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+
+    let result = SuiClientBuilder::default()
+        .proxy("not a proxy url")
+        .build("http://127.0.0.1:9000")
+        .await;
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+}
+
+#[tokio::test]
+async fn client_builder_headers_test() {
+    use sui_sdk::error::Error;
+    use sui_sdk::SuiClientBuilder;
+
+    let result = SuiClientBuilder::default()
+        .header("x api key", "secret")
+        .build("http://127.0.0.1:9000")
+        .await;
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+
+    let result = SuiClientBuilder::default()
+        .bearer_token("secret\n")
+        .build("http://127.0.0.1:9000")
+        .await;
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+}
+
+#[tokio::test]
+async fn client_builder_tls_test() {
+    use sui_sdk::error::Error;
+    use sui_sdk::SuiClientBuilder;
+
+    let result = SuiClientBuilder::default()
+        .root_certificate("not a certificate")
+        .build("https://127.0.0.1:9000")
+        .await;
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+
+    let result = SuiClientBuilder::default()
+        .client_certificate("not a certificate", "not a key")
+        .build("https://127.0.0.1:9000")
+        .await;
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+
+    // The WebSocket connection would not use the TLS settings of the HTTPS ones.
+    let result = SuiClientBuilder::default()
+        .root_certificate("not a certificate")
+        .ws_url("wss://127.0.0.1:9000")
+        .build("https://127.0.0.1:9000")
+        .await;
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+
+    let result = SuiClientBuilder::default()
+        .client_certificate("not a certificate", "not a key")
+        .ws_url("wss://127.0.0.1:9000")
+        .build("https://127.0.0.1:9000")
+        .await;
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+
+    let result = SuiClientBuilder::default()
+        .insecure_skip_verify(true)
+        .ws_url("wss://127.0.0.1:9000")
+        .build("https://127.0.0.1:9000")
+        .await;
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+}
+
+/// Serves HTTP on a local port, answering each request with the JSON body returned by `respond`
+/// for its request line and body. Returns the URL of the server.
+async fn serve_http(respond: impl Fn(&str, &str) -> String + Send + Sync + 'static) -> String {
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let respond = Arc::new(respond);
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let respond = respond.clone();
+            tokio::spawn(async move {
+                let mut request = vec![];
+                let mut buf = [0; 4096];
+                let (request_line, body) = loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        return;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                    let request = String::from_utf8_lossy(&request);
+                    let Some((head, body)) = request.split_once("\r\n\r\n") else {
+                        continue;
+                    };
+                    let content_length = head
+                        .lines()
+                        .find_map(|line| {
+                            line.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|l| l.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if body.len() >= content_length {
+                        let request_line = head.lines().next().unwrap_or_default().to_string();
+                        break (request_line, body.to_string());
+                    }
+                };
+                let response = respond(&request_line, &body);
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{response}",
+                    response.len()
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            });
+        }
+    });
+    url
+}
+
+/// Serves `rpc.discover` over HTTP on a local port, returning its URL.
+async fn serve_rpc_discover() -> String {
+    serve_http(|_, body| {
+        let id = serde_json::from_str::<serde_json::Value>(body).unwrap()["id"].clone();
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "result": { "info": { "version": "0.0.0" }, "methods": [] },
+        })
+        .to_string()
+    })
+    .await
+}
+
+#[tokio::test]
+async fn client_failover_test() {
+    use std::time::Duration;
+    use sui_sdk::SuiClientBuilder;
+
+    let url = serve_rpc_discover().await;
+    // Nothing listens on port 1.
+    let sui = SuiClientBuilder::default()
+        .fallback_urls([&url])
+        .build("http://127.0.0.1:1")
+        .await
+        .unwrap();
+    assert_eq!(sui.api_version(), "0.0.0");
+
+    // Without retries, requests fail on the unreachable endpoint.
+    assert!(SuiClientBuilder::default()
+        .retry_max_elapsed_time(Duration::ZERO)
+        .fallback_urls([&url])
+        .build("http://127.0.0.1:1")
+        .await
+        .is_err());
+}
+
+#[test]
+fn sui_env_builder_test() {
+    use sui_sdk::sui_client_config::{EthNetworkConfig, SuiEnv};
+    use sui_types::eth_light_client::Checkpoint;
+
+    let checkpoint = Checkpoint {
+        epoch: 300_000,
+        block_root: [1; 32],
+    };
+    // The genesis validators root of mainnet is compiled in.
+    let mainnet = EthNetworkConfig::builder(1, "http://127.0.0.1:8545")
+        .consensus_rpc("http://127.0.0.1:5052")
+        .checkpoint(checkpoint)
+        .known_checkpoint(checkpoint)
+        .trust_configured_checkpoints()
+        .build()
+        .unwrap();
+    // The configured known checkpoints are not trusted by default.
+    assert!(mainnet.clone().into_builder().build().is_err());
+    assert_eq!(mainnet.checkpoint, Some(checkpoint));
+    assert!(mainnet.genesis_validators_root.is_some());
+
+    let env = SuiEnv::builder("local", "http://127.0.0.1:9000")
+        .fallback_rpc("http://127.0.0.1:9001")
+        .ws("ws://127.0.0.1:9000")
+        .header("x-api-key", "secret")
+        .eth_network("mainnet", mainnet.clone())
+        .build()
+        .unwrap();
+    assert_eq!(env.fallback_rpcs, vec!["http://127.0.0.1:9001".to_string()]);
+    assert_eq!(env.eth_network("mainnet").unwrap(), &mainnet);
+
+    // The local network has the faucet of `sui start`, other networks a configured one.
+    assert_eq!(env.faucet_url().unwrap(), "http://127.0.0.1:5003");
+    let devnet = SuiEnv::devnet();
+    assert!(devnet.faucet_url().is_err());
+    let devnet = SuiEnv::builder("devnet", &devnet.rpc)
+        .faucet("https://faucet.example.com")
+        .build()
+        .unwrap();
+    assert_eq!(devnet.faucet_url().unwrap(), "https://faucet.example.com");
+
+    // Invalid environments.
+    assert!(SuiEnv::builder("", "http://127.0.0.1:9000")
+        .build()
+        .is_err());
+    assert!(SuiEnv::builder("local", "127.0.0.1:9000").build().is_err());
+    assert!(SuiEnv::builder("local", "http://127.0.0.1:9000")
+        .ws("http://127.0.0.1:9000")
+        .build()
+        .is_err());
+    assert!(SuiEnv::builder("local", "http://127.0.0.1:9000")
+        .ws("ws://127.0.0.1:9000")
+        .proxy("socks5h://127.0.0.1:9050")
+        .build()
+        .is_err());
+    assert!(SuiEnv::builder("local", "http://127.0.0.1:9000")
+        .header("x api key", "secret")
+        .build()
+        .is_err());
+    assert!(SuiEnv::builder("local", "http://127.0.0.1:9000")
+        .faucet("127.0.0.1:5003")
+        .build()
+        .is_err());
+
+    // Invalid Ethereum networks.
+    assert!(EthNetworkConfig::builder(0, "http://127.0.0.1:8545")
+        .build()
+        .is_err());
+    assert!(EthNetworkConfig::builder(1, "http://127.0.0.1:8545")
+        .checkpoint(checkpoint)
+        .build()
+        .is_err());
+    // A checkpoint that is not known.
+    assert!(EthNetworkConfig::builder(1, "http://127.0.0.1:8545")
+        .consensus_rpc("http://127.0.0.1:5052")
+        .checkpoint(checkpoint)
+        .known_checkpoint(Checkpoint {
+            epoch: 300_001,
+            block_root: [2; 32],
+        })
+        .trust_configured_checkpoints()
+        .build()
+        .is_err());
+    // No checkpoints are compiled in, nor configured.
+    let error = EthNetworkConfig::builder(1, "http://127.0.0.1:8545")
+        .consensus_rpc("http://127.0.0.1:5052")
+        .build()
+        .unwrap_err();
+    assert!(error.to_string().contains("no known checkpoints"));
+    // No genesis validators root nor fork schedule is compiled in for the chain.
+    assert!(EthNetworkConfig::builder(999_999, "http://127.0.0.1:8545")
+        .consensus_rpc("http://127.0.0.1:5052")
+        .build()
+        .is_err());
+}
+
+#[test]
+fn client_config_migration_test() {
+    use sui_config::{Config, PersistedConfig};
+    use sui_sdk::sui_client_config::{SuiClientConfig, SUI_CLIENT_CONFIG_VERSION};
+
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("client.yaml");
+    let keystore_path = temp_dir.path().join("sui.keystore");
+
+    // An unversioned config, without dWallets, and with the legacy Ethereum settings.
+    std::fs::write(
+        &config_path,
+        format!(
+            "---
+keystore:
+  File: {}
+envs:
+  - alias: testnet
+    rpc: \"http://127.0.0.1:9000\"
+    ws: ~
+    eth_client_settings:
+      eth_execution_rpc: \"http://127.0.0.1:8545\"
+      eth_consensus_rpc: \"http://127.0.0.1:5052\"
+      eth_chain_id: 11155111
+      state_object_id: ~
+active_env: testnet
+active_address: ~
+",
+            keystore_path.display()
+        ),
+    )
+    .unwrap();
+
+    let config: SuiClientConfig = PersistedConfig::read(&config_path).unwrap();
+    assert_eq!(config.version, SUI_CLIENT_CONFIG_VERSION);
+    assert!(config.dwallets.is_empty());
+    let env = config.get_active_env().unwrap();
+    let sepolia = env.eth_network("sepolia").unwrap();
+    assert_eq!(sepolia.chain_id, 11155111);
+    assert_eq!(sepolia.execution_rpc, "http://127.0.0.1:8545");
+    assert_eq!(
+        sepolia.consensus_rpc.as_deref(),
+        Some("http://127.0.0.1:5052")
+    );
+    assert_eq!(sepolia.state_object_id, None);
+
+    // The migrated config round-trips.
+    config.save(&config_path).unwrap();
+    let saved: SuiClientConfig = PersistedConfig::read(&config_path).unwrap();
+    assert_eq!(saved.envs[0].eth_networks, config.envs[0].eth_networks);
+
+    // A config written by a newer release is refused.
+    std::fs::write(
+        &config_path,
+        std::fs::read_to_string(&config_path).unwrap().replace(
+            &format!("version: {SUI_CLIENT_CONFIG_VERSION}"),
+            &format!("version: {}", SUI_CLIENT_CONFIG_VERSION + 1),
+        ),
+    )
+    .unwrap();
+    assert!(PersistedConfig::<SuiClientConfig>::read(&config_path).is_err());
+}
+
+#[test]
+fn export_import_env_test() {
+    use sui_sdk::sui_client_config::{EthNetworkConfig, SuiClientConfig, SuiEnv};
+
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    let mut config = SuiClientConfig::new(keystore);
+    let env = SuiEnv::builder("shared", "http://127.0.0.1:9000")
+        .bearer_token("secret")
+        .eth_network(
+            "base",
+            EthNetworkConfig::builder(8453, "http://127.0.0.1:8545")
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+    config.add_env(env.clone());
+
+    for file_name in ["shared.toml", "shared.yaml"] {
+        let path = temp_dir.path().join(file_name);
+        config.export_env("shared", &path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("secret"));
+        assert!(!contents.contains("sui.keystore"));
+
+        let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+        let mut other = SuiClientConfig::new(keystore);
+        let imported = other.import_env(&path).unwrap();
+        assert_eq!(imported.rpc, env.rpc);
+        assert_eq!(imported.bearer_token, None);
+        assert_eq!(imported.eth_networks, env.eth_networks);
+
+        // The alias is taken now.
+        assert!(other.import_env(&path).is_err());
+    }
+
+    assert!(config
+        .export_env("unknown", &temp_dir.path().join("unknown.yaml"))
+        .is_err());
+}
+
+#[tokio::test]
+async fn default_gas_budget_and_price_test() {
+    use sui_config::Config;
+    use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv};
+    use sui_sdk::wallet_context::WalletContext;
+    use sui_types::base_types::{random_object_ref, SuiAddress};
+    use sui_types::transaction::{TransactionData, TransactionDataAPI};
+
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("client.yaml");
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    let mut config = SuiClientConfig::new(keystore);
+    config.add_env(
+        SuiEnv::builder("custom", "http://127.0.0.1:9000")
+            .default_gas_budget(50_000_000)
+            .default_gas_price(2_000)
+            .build()
+            .unwrap(),
+    );
+    config.add_env(SuiEnv::localnet());
+    config.active_env = Some("custom".to_string());
+    config.save(&config_path).unwrap();
+
+    let mut context = WalletContext::new(&config_path, None, None).await.unwrap();
+    assert_eq!(context.gas_budget(None).unwrap(), 50_000_000);
+    assert_eq!(context.gas_budget(Some(10_000_000)).unwrap(), 10_000_000);
+
+    let data = TransactionData::new_transfer_sui(
+        SuiAddress::random_for_testing_only(),
+        SuiAddress::random_for_testing_only(),
+        None,
+        random_object_ref(),
+        50_000_000,
+        1_000,
+    );
+    let data = context.with_default_gas_price(data).unwrap();
+    assert_eq!(data.gas_data().price, 2_000);
+
+    // The other env has no defaults.
+    context.config.active_env = Some(SuiEnv::localnet().alias);
+    assert!(context.gas_budget(None).is_err());
+    let data = context.with_default_gas_price(data).unwrap();
+    assert_eq!(data.gas_data().price, 2_000);
+}
+
+#[test]
+fn per_env_active_address_test() {
+    use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv};
+    use sui_types::base_types::SuiAddress;
+
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    let mut config = SuiClientConfig::new(keystore);
+
+    // Without environments, the global active address is set.
+    let global = SuiAddress::random_for_testing_only();
+    config.set_active_address(global);
+    assert_eq!(config.active_address, Some(global));
+    assert_eq!(config.get_active_address(), Some(global));
+
+    config.add_env(SuiEnv::devnet());
+    config.add_env(SuiEnv::testnet());
+    config.active_env = Some("testnet".to_string());
+    let testnet = SuiAddress::random_for_testing_only();
+    config.set_active_address(testnet);
+    assert_eq!(config.get_active_address(), Some(testnet));
+    assert_eq!(config.active_address, Some(global));
+
+    // Environments without an active address of their own fall back to the global one.
+    config.active_env = Some("devnet".to_string());
+    assert_eq!(config.get_active_address(), Some(global));
+}
+
+#[test]
+fn address_aliases_test() {
+    use sui_sdk::sui_client_config::SuiClientConfig;
+    use sui_types::base_types::SuiAddress;
+
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    let (key_address, _, _) = keystore
+        .generate_and_add_new_key(
+            SignatureScheme::ED25519,
+            Some("signer".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+    let mut config = SuiClientConfig::new(keystore);
+
+    let treasury = SuiAddress::random_for_testing_only();
+    config
+        .add_address_alias("treasury".to_string(), treasury)
+        .unwrap();
+    assert_eq!(config.resolve_address("treasury").unwrap(), treasury);
+    assert_eq!(config.resolve_address("signer").unwrap(), key_address);
+    assert_eq!(
+        config.resolve_address(&treasury.to_string()).unwrap(),
+        treasury
+    );
+    assert!(config.resolve_address("unknown").is_err());
+
+    // Aliases are unique across the config and the keystore, and cannot pass for addresses.
+    assert!(config
+        .add_address_alias("treasury".to_string(), treasury)
+        .is_err());
+    assert!(config
+        .add_address_alias("signer".to_string(), treasury)
+        .is_err());
+    assert!(config
+        .add_address_alias("0xtreasury".to_string(), treasury)
+        .is_err());
+
+    assert_eq!(config.remove_address_alias("treasury").unwrap(), treasury);
+    assert!(config.resolve_address("treasury").is_err());
+}
+
+#[test]
+fn dwallet_registry_test() {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+    use sui_sdk::sui_client_config::{DWalletCurve, DWalletSecretShare, SuiClientConfig};
+    use sui_types::base_types::ObjectID;
+
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    let mut config = SuiClientConfig::new(keystore);
+
+    let dwallet_id = ObjectID::random();
+    config.add_dwallet(DWalletSecretShare {
+        alias: "my-btc-vault".to_string(),
+        dkg_output: None,
+        dwallet_id,
+        dwallet_cap_id: ObjectID::random(),
+        curve: DWalletCurve::Secp256k1,
+        external_addresses: BTreeMap::from([(
+            "bitcoin".to_string(),
+            "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string(),
+        )]),
+        encrypted_share_path: Some(PathBuf::from("my-btc-vault.share")),
+        keyring_service: None,
+        signing_policy_id: None,
+        session_retries: BTreeMap::new(),
+    });
+
+    let dwallet = config.resolve_dwallet("my-btc-vault").unwrap();
+    assert_eq!(dwallet.dwallet_id, dwallet_id);
+    assert_eq!(
+        config
+            .resolve_dwallet(&dwallet_id.to_string())
+            .unwrap()
+            .alias,
+        "my-btc-vault"
+    );
+    assert!(config.resolve_dwallet("unknown").is_err());
+
+    // Ids of dWallets outside the registry are accepted too.
+    let other_id = ObjectID::random();
+    assert_eq!(
+        config.resolve_dwallet_id(&other_id.to_string()).unwrap(),
+        other_id
+    );
+    assert!(config.resolve_dwallet_id("unknown").is_err());
+
+    // The user share must be decrypted before signing.
+    let error = dwallet.dkg_output().unwrap_err().to_string();
+    assert!(error.contains("my-btc-vault.share"), "{error}");
+}
+
+#[test]
+fn client_profiles_test() {
+    use std::collections::BTreeMap;
+    use sui_sdk::sui_client_config::{
+        ClientProfile, DWalletCurve, DWalletSecretShare, SuiClientConfig, SuiEnv,
+    };
+    use sui_types::base_types::{ObjectID, SuiAddress};
+
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    let mut config = SuiClientConfig::new(keystore);
+    config.add_env(SuiEnv::devnet());
+    config.add_env(SuiEnv::testnet());
+    for alias in ["tenant-a-vault", "tenant-b-vault"] {
+        config.add_dwallet(DWalletSecretShare {
+            alias: alias.to_string(),
+            dkg_output: None,
+            dwallet_id: ObjectID::random(),
+            dwallet_cap_id: ObjectID::random(),
+            curve: DWalletCurve::Secp256k1,
+            external_addresses: BTreeMap::new(),
+            encrypted_share_path: None,
+            keyring_service: None,
+            signing_policy_id: None,
+            session_retries: BTreeMap::new(),
+        });
+    }
+
+    let address = SuiAddress::random_for_testing_only();
+    let profile = ClientProfile {
+        env: Some("testnet".to_string()),
+        active_address: Some(address),
+        dwallets: vec!["tenant-b-vault".to_string()],
+        active_dwallet: None,
+        gas_budget: Some(50_000_000),
+        gas_price: None,
+    };
+    config.validate_profile(&profile).unwrap();
+    config.profiles.insert("tenant-b".to_string(), profile);
+    assert!(config.switch_profile("unknown").is_err());
+
+    config.switch_profile("tenant-b").unwrap();
+    assert_eq!(config.active_env.as_deref(), Some("testnet"));
+    assert_eq!(config.get_active_address(), Some(address));
+    assert_eq!(config.active_dwallet.as_deref(), Some("tenant-b-vault"));
+    assert_eq!(
+        config.get_active_env().unwrap().default_gas_budget,
+        Some(50_000_000)
+    );
+
+    // The dWallets of other profiles are out of reach.
+    assert!(config.resolve_dwallet("tenant-a-vault").is_err());
+    assert_eq!(
+        config
+            .profile_dwallets()
+            .map(|dwallet| dwallet.alias.as_str())
+            .collect::<Vec<_>>(),
+        ["tenant-b-vault"]
+    );
+
+    // Profiles must refer to configured environments and dWallets.
+    for profile in [
+        ClientProfile {
+            env: Some("unknown".to_string()),
+            ..Default::default()
+        },
+        ClientProfile {
+            dwallets: vec!["unknown".to_string()],
+            ..Default::default()
+        },
+        ClientProfile {
+            dwallets: vec!["tenant-b-vault".to_string()],
+            active_dwallet: Some("tenant-a-vault".to_string()),
+            ..Default::default()
+        },
+    ] {
+        assert!(config.validate_profile(&profile).is_err());
+    }
+}
+
+#[test]
+fn offline_signing_test() {
+    use sui_sdk::offline::{
+        read_signed_transaction, read_unsigned_transaction, sign_transaction_file,
+        write_unsigned_transaction,
+    };
+    use sui_types::base_types::{random_object_ref, SuiAddress};
+    use sui_types::transaction::TransactionData;
+
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    let (sender, _, _) = keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+
+    let data = TransactionData::new_transfer_sui(
+        SuiAddress::random_for_testing_only(),
+        sender,
+        Some(1),
+        random_object_ref(),
+        10_000_000,
+        1_000,
+    );
+    let unsigned_path = temp_dir.path().join("transfer.unsigned");
+    let signed_path = temp_dir.path().join("transfer.signed");
+    write_unsigned_transaction(&unsigned_path, &data).unwrap();
+    assert_eq!(read_unsigned_transaction(&unsigned_path).unwrap(), data);
+
+    let transaction = sign_transaction_file(&keystore, &unsigned_path, &signed_path).unwrap();
+    let signed = read_signed_transaction(&signed_path).unwrap();
+    assert_eq!(signed.transaction_data(), &data);
+    assert_eq!(signed.tx_signatures(), transaction.tx_signatures());
+    assert_eq!(signed.tx_signatures().len(), 1);
+
+    // Only transactions of senders in the keystore can be signed.
+    let other = TransactionData::new_transfer_sui(
+        sender,
+        SuiAddress::random_for_testing_only(),
+        Some(1),
+        random_object_ref(),
+        10_000_000,
+        1_000,
+    );
+    write_unsigned_transaction(&unsigned_path, &other).unwrap();
+    assert!(sign_transaction_file(&keystore, &unsigned_path, &signed_path).is_err());
+}
+
+#[test]
+fn dwallet_transaction_builder_test() {
+    use sui_sdk::dwallet_transaction_builder::DWalletTransactionBuilder;
+    use sui_types::base_types::{random_object_ref, SuiAddress};
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+    use sui_types::transaction::{Argument, Command};
+    use sui_types::SUI_SYSTEM_PACKAGE_ID;
+
+    let messages = vec![b"message".to_vec()];
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let message_approvals = builder
+        .approve_messages(random_object_ref(), &messages)
+        .unwrap();
+    let sign_messages = builder
+        .create_sign_messages(
+            random_object_ref(),
+            random_object_ref(),
+            random_object_ref(),
+            random_object_ref(),
+            &vec![0u8; 4],
+        )
+        .unwrap();
+    builder.sign_messages(sign_messages, message_approvals);
+    builder
+        .transfer_dwallet_cap(random_object_ref(), SuiAddress::random_for_testing_only())
+        .unwrap();
+    let transaction = builder.finish();
+
+    let calls = transaction
+        .commands
+        .iter()
+        .filter_map(|command| match command {
+            Command::MoveCall(call) => {
+                assert_eq!(call.package, SUI_SYSTEM_PACKAGE_ID);
+                Some(format!("{}::{}", call.module, call.function))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        calls,
+        [
+            "dwallet::approve_messages",
+            "dwallet_2pc_mpc_ecdsa_k1::create_sign_messages",
+            "dwallet::sign_messages",
+        ]
+    );
+    let Command::MoveCall(sign) = &transaction.commands[2] else {
+        panic!("expected a Move call");
+    };
+    assert_eq!(sign.arguments, [Argument::Result(1), Argument::Result(0)]);
+    assert!(matches!(
+        transaction.commands[3],
+        Command::TransferObjects(..)
+    ));
+}
+
+#[test]
+fn recommended_gas_budget_test() {
+    use sui_sdk::{recommended_gas_budget, GAS_SAFE_OVERHEAD};
+    use sui_types::gas::GasCostSummary;
+
+    let gas_price = 1_000;
+    let overhead = GAS_SAFE_OVERHEAD * gas_price;
+
+    // The net storage cost is added to the computation cost.
+    let gas_cost = GasCostSummary::new(2_000_000, 5_000_000, 1_000_000, 0);
+    assert_eq!(
+        recommended_gas_budget(&gas_cost, gas_price),
+        2_000_000 + overhead + 4_000_000
+    );
+
+    // But a storage rebate does not lower the budget below the computation cost.
+    let gas_cost = GasCostSummary::new(2_000_000, 1_000_000, 5_000_000, 0);
+    assert_eq!(
+        recommended_gas_budget(&gas_cost, gas_price),
+        2_000_000 + overhead
+    );
+}
+
+#[tokio::test]
+async fn faucet_client_test() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use sui_sdk::faucet_client::{FaucetClient, FaucetRequestStatus};
+    use sui_types::base_types::{ObjectID, SuiAddress};
+    use sui_types::digests::TransactionDigest;
+
+    let recipient = SuiAddress::random_for_testing_only();
+    let coin_id = ObjectID::random();
+    let digest = TransactionDigest::random();
+    let polls = AtomicUsize::new(0);
+    let url = serve_http(move |request_line, body| {
+        if request_line.starts_with("POST /v1/gas") {
+            let request = serde_json::from_str::<serde_json::Value>(body).unwrap();
+            assert_eq!(
+                request["FixedAmountRequest"]["recipient"],
+                recipient.to_string()
+            );
+            serde_json::json!({ "task": "task-1", "error": null }).to_string()
+        } else if request_line.starts_with("GET /v1/status/task-1") {
+            // The request is served on the second poll.
+            let status = if polls.fetch_add(1, Ordering::SeqCst) == 0 {
+                serde_json::json!({ "status": "INPROGRESS", "transferred_gas_objects": null })
+            } else {
+                serde_json::json!({
+                    "status": "SUCCEEDED",
+                    "transferred_gas_objects": { "sent": [{
+                        "amount": 1000,
+                        "id": coin_id,
+                        "transferTxDigest": digest,
+                    }] },
+                })
+            };
+            serde_json::json!({ "status": status, "error": null }).to_string()
+        } else {
+            serde_json::json!({ "status": null, "error": "unknown request" }).to_string()
+        }
+    })
+    .await;
+
+    let faucet = FaucetClient::new(&url).with_timeout(Duration::from_secs(10));
+    let coins = faucet.request_gas(recipient).await.unwrap();
+    assert_eq!(coins.len(), 1);
+    assert_eq!(coins[0].amount, 1000);
+    assert_eq!(coins[0].id, coin_id);
+    assert_eq!(coins[0].transfer_tx_digest, digest);
+    assert_eq!(
+        faucet.request_status("task-1").await.unwrap(),
+        FaucetRequestStatus::Succeeded(coins)
+    );
+
+    // Errors reported by the faucet are surfaced.
+    assert!(faucet.request_status("task-2").await.is_err());
+}
+
+#[tokio::test]
+async fn client_cache_test() {
+    use std::num::NonZeroUsize;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use sui_sdk::rpc_types::{SuiObjectData, SuiObjectDataOptions, SuiObjectResponse};
+    use sui_sdk::SuiClientBuilder;
+    use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
+    use sui_types::digests::ObjectDigest;
+    use sui_types::object::Owner;
+
+    let immutable_id = ObjectID::random();
+    let owned_id = ObjectID::random();
+    let object_requests = Arc::new(AtomicUsize::new(0));
+    let requests = object_requests.clone();
+    let url = serve_http(move |_, body| {
+        let request = serde_json::from_str::<serde_json::Value>(body).unwrap();
+        let result = if request["method"] == "sui_getObject" {
+            requests.fetch_add(1, Ordering::SeqCst);
+            let object_id =
+                serde_json::from_value::<ObjectID>(request["params"][0].clone()).unwrap();
+            let owner = if object_id == immutable_id {
+                Owner::Immutable
+            } else {
+                Owner::AddressOwner(SuiAddress::random_for_testing_only())
+            };
+            serde_json::to_value(SuiObjectResponse::new_with_data(SuiObjectData {
+                object_id,
+                version: SequenceNumber::from(1),
+                digest: ObjectDigest::random(),
+                type_: None,
+                owner: Some(owner),
+                previous_transaction: None,
+                storage_rebate: None,
+                display: None,
+                content: None,
+                bcs: None,
+            }))
+            .unwrap()
+        } else {
+            serde_json::json!({ "info": { "version": "0.0.0" }, "methods": [] })
+        };
+        serde_json::json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string()
+    })
+    .await;
+
+    let sui = SuiClientBuilder::default()
+        .cache(NonZeroUsize::new(16).unwrap(), Duration::from_secs(60))
+        .build(&url)
+        .await
+        .unwrap();
+    let options = SuiObjectDataOptions::new().with_owner();
+
+    // Immutable objects are fetched once.
+    for _ in 0..2 {
+        let response = sui
+            .read_api()
+            .get_object_with_options(immutable_id, options.clone())
+            .await
+            .unwrap();
+        assert_eq!(response.object_id().unwrap(), immutable_id);
+    }
+    assert_eq!(object_requests.load(Ordering::SeqCst), 1);
+
+    // Owned objects may change, and are fetched every time.
+    for _ in 0..2 {
+        sui.read_api()
+            .get_object_with_options(owned_id, options.clone())
+            .await
+            .unwrap();
+    }
+    assert_eq!(object_requests.load(Ordering::SeqCst), 3);
+
+    sui.clear_cache();
+    sui.read_api()
+        .get_object_with_options(immutable_id, options)
+        .await
+        .unwrap();
+    assert_eq!(object_requests.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn dwallet_sign_sessions_stream_test() {
+    use futures::TryStreamExt;
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::StructTag;
+    use sui_sdk::rpc_types::{EventPage, SuiEvent};
+    use sui_sdk::SuiClientBuilder;
+    use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+    use sui_types::event::EventID;
+    use sui_types::id::ID;
+    use sui_types::signature_mpc::{
+        SignSessionCreatedEvent, DWALLET_MODULE_NAME, SIGN_SESSION_CREATED_EVENT_STRUCT_NAME,
+    };
+    use sui_types::SUI_SYSTEM_ADDRESS;
+
+    let dwallet_id = ObjectID::random();
+    let sign_session = |dwallet_id: ObjectID| SignSessionCreatedEvent {
+        session_id: ID::new(ObjectID::random()),
+        dwallet_id: ID::new(dwallet_id),
+        dwallet_cap_id: ID::new(ObjectID::random()),
+        messages: vec![b"message".to_vec()],
+        sender: SuiAddress::random_for_testing_only(),
+    };
+    let event = |event_seq: u64, sign_session: &SignSessionCreatedEvent| SuiEvent {
+        id: EventID {
+            tx_digest: TransactionDigest::random(),
+            event_seq,
+        },
+        package_id: ObjectID::from(SUI_SYSTEM_ADDRESS),
+        transaction_module: Identifier::new("dwallet").unwrap(),
+        sender: sign_session.sender,
+        type_: StructTag {
+            address: SUI_SYSTEM_ADDRESS,
+            module: DWALLET_MODULE_NAME.to_owned(),
+            name: SIGN_SESSION_CREATED_EVENT_STRUCT_NAME.to_owned(),
+            type_params: vec![],
+        },
+        parsed_json: serde_json::Value::Null,
+        bcs: bcs::to_bytes(sign_session).unwrap(),
+        timestamp_ms: None,
+    };
+
+    // Two pages, with a sign session of another dWallet in between.
+    let sessions = [
+        sign_session(dwallet_id),
+        sign_session(ObjectID::random()),
+        sign_session(dwallet_id),
+    ];
+    let first_page = EventPage {
+        data: vec![event(0, &sessions[0]), event(1, &sessions[1])],
+        next_cursor: Some(event(1, &sessions[1]).id),
+        has_next_page: true,
+    };
+    let last_page = EventPage {
+        data: vec![event(2, &sessions[2])],
+        next_cursor: None,
+        has_next_page: false,
+    };
+    let (first_page, last_page) = (
+        serde_json::to_value(first_page).unwrap(),
+        serde_json::to_value(last_page).unwrap(),
+    );
+    let url = serve_http(move |_, body| {
+        let request = serde_json::from_str::<serde_json::Value>(body).unwrap();
+        let result = if request["method"] == "suix_queryEvents" {
+            if request["params"][1].is_null() {
+                first_page.clone()
+            } else {
+                last_page.clone()
+            }
+        } else {
+            serde_json::json!({ "info": { "version": "0.0.0" }, "methods": [] })
+        };
+        serde_json::json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string()
+    })
+    .await;
+
+    let sui = SuiClientBuilder::default().build(&url).await.unwrap();
+    let sign_sessions: Vec<_> = sui
+        .dwallet_api()
+        .get_sign_sessions_stream(dwallet_id, false)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(
+        sign_sessions,
+        vec![sessions[0].clone(), sessions[2].clone()]
+    );
+}
+
+#[tokio::test]
+async fn sponsored_transaction_test() {
+    use sui_sdk::offline::sign_transaction;
+    use sui_sdk::sponsor::{sponsor_transaction, KeystoreGasSponsor};
+    use sui_types::base_types::{random_object_ref, SuiAddress};
+    use sui_types::signature::VerifyParams;
+    use sui_types::transaction::TransactionData;
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut sender_keystore =
+        Keystore::from(FileBasedKeystore::new(&temp_dir.path().join("sender.keystore")).unwrap());
+    let (sender, _, _) = sender_keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+    let mut sponsor_keystore =
+        Keystore::from(FileBasedKeystore::new(&temp_dir.path().join("sponsor.keystore")).unwrap());
+    let (sponsor_address, _, _) = sponsor_keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+    let sponsor = KeystoreGasSponsor::new(sponsor_keystore, sponsor_address);
+
+    let data = TransactionData::new_transfer_sui_allow_sponsor(
+        SuiAddress::random_for_testing_only(),
+        sender,
+        Some(1),
+        random_object_ref(),
+        10_000_000,
+        1_000,
+        sponsor_address,
+    );
+    let transaction = sign_transaction(&sender_keystore, data.clone()).unwrap();
+    // The signature of the sender alone does not authorize the sponsored transaction.
+    assert!(transaction
+        .verify_signature(&VerifyParams::default())
+        .is_err());
+
+    let transaction = sponsor_transaction(&sponsor, transaction).await.unwrap();
+    assert_eq!(transaction.transaction_data(), &data);
+    assert_eq!(transaction.tx_signatures().len(), 2);
+    transaction
+        .verify_signature(&VerifyParams::default())
+        .unwrap();
+
+    // The sponsor only signs transactions it pays for.
+    let unsponsored = TransactionData::new_transfer_sui(
+        SuiAddress::random_for_testing_only(),
+        sender,
+        Some(1),
+        random_object_ref(),
+        10_000_000,
+        1_000,
+    );
+    let transaction = sign_transaction(&sender_keystore, unsponsored).unwrap();
+    assert!(sponsor_transaction(&sponsor, transaction).await.is_err());
+}
+
+#[tokio::test]
+async fn wait_for_sign_completion_test() {
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::StructTag;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use sui_sdk::error::Error;
+    use sui_sdk::rpc_types::{
+        ObjectsPage, SuiEvent, SuiObjectData, SuiObjectResponse, SuiRawData, SuiRawMoveObject,
+        SuiTransactionBlockEvents, SuiTransactionBlockResponse, TransactionBlocksPage,
+    };
+    use sui_sdk::SuiClientBuilder;
+    use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
+    use sui_types::digests::ObjectDigest;
+    use sui_types::event::EventID;
+    use sui_types::id::{ID, UID};
+    use sui_types::object::Owner;
+    use sui_types::signature_mpc::{
+        SignOutput, SignSessionCreatedEvent, DWALLET_MODULE_NAME,
+        SIGN_SESSION_CREATED_EVENT_STRUCT_NAME,
+    };
+    use sui_types::SUI_SYSTEM_ADDRESS;
+
+    let sender = SuiAddress::random_for_testing_only();
+    let sign_session = |session_id: ObjectID| SignSessionCreatedEvent {
+        session_id: ID::new(session_id),
+        dwallet_id: ID::new(ObjectID::random()),
+        dwallet_cap_id: ID::new(ObjectID::random()),
+        messages: vec![b"message".to_vec()],
+        sender,
+    };
+    let (session_id, pending_session_id) = (ObjectID::random(), ObjectID::random());
+    let sessions = [sign_session(session_id), sign_session(pending_session_id)];
+    let output = SignOutput {
+        id: UID::new(ObjectID::random()),
+        session_id: sessions[0].session_id.clone(),
+        dwallet_id: sessions[0].dwallet_id.clone(),
+        dwallet_cap_id: sessions[0].dwallet_cap_id.clone(),
+        signatures: vec![vec![1; 64]],
+        sender,
+    };
+
+    let output_object = SuiObjectResponse::new_with_data(SuiObjectData {
+        object_id: *output.id(),
+        version: SequenceNumber::from(1),
+        digest: ObjectDigest::random(),
+        type_: None,
+        owner: Some(Owner::AddressOwner(sender)),
+        previous_transaction: None,
+        storage_rebate: None,
+        display: None,
+        content: None,
+        bcs: Some(SuiRawData::MoveObject(SuiRawMoveObject {
+            type_: SignOutput::type_(),
+            has_public_transfer: false,
+            version: SequenceNumber::from(1),
+            bcs_bytes: output.to_bcs_bytes(),
+        })),
+    });
+    // The output is only sent on the second poll.
+    let polls = Arc::new(AtomicUsize::new(0));
+    let url = serve_http({
+        let polls = polls.clone();
+        move |_, body| {
+            let request = serde_json::from_str::<serde_json::Value>(body).unwrap();
+            let result = match request["method"].as_str().unwrap() {
+                "suix_queryTransactionBlocks" => {
+                    let object_id = serde_json::from_value::<ObjectID>(
+                        request["params"][0]["filter"]["ChangedObject"].clone(),
+                    )
+                    .unwrap();
+                    let data = sessions
+                        .iter()
+                        .filter(|session| session.session_id.bytes == object_id)
+                        .map(|session| {
+                            let digest = TransactionDigest::random();
+                            let mut response = SuiTransactionBlockResponse::new(digest);
+                            response.events = Some(SuiTransactionBlockEvents {
+                                data: vec![SuiEvent {
+                                    id: EventID {
+                                        tx_digest: digest,
+                                        event_seq: 0,
+                                    },
+                                    package_id: ObjectID::from(SUI_SYSTEM_ADDRESS),
+                                    transaction_module: Identifier::new("dwallet").unwrap(),
+                                    sender,
+                                    type_: StructTag {
+                                        address: SUI_SYSTEM_ADDRESS,
+                                        module: DWALLET_MODULE_NAME.to_owned(),
+                                        name: SIGN_SESSION_CREATED_EVENT_STRUCT_NAME.to_owned(),
+                                        type_params: vec![],
+                                    },
+                                    parsed_json: serde_json::Value::Null,
+                                    bcs: bcs::to_bytes(session).unwrap(),
+                                    timestamp_ms: None,
+                                }],
+                            });
+                            response
+                        })
+                        .collect();
+                    serde_json::to_value(TransactionBlocksPage {
+                        data,
+                        next_cursor: None,
+                        has_next_page: false,
+                    })
+                    .unwrap()
+                }
+                "suix_getOwnedObjects" => {
+                    let data = if polls.fetch_add(1, Ordering::SeqCst) == 0 {
+                        vec![]
+                    } else {
+                        vec![output_object.clone()]
+                    };
+                    serde_json::to_value(ObjectsPage {
+                        data,
+                        next_cursor: None,
+                        has_next_page: false,
+                    })
+                    .unwrap()
+                }
+                _ => serde_json::json!({ "info": { "version": "0.0.0" }, "methods": [] }),
+            };
+            serde_json::json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })
+                .to_string()
+        }
+    })
+    .await;
+    let sui = SuiClientBuilder::default().build(&url).await.unwrap();
+
+    let completed = sui
+        .wait_for_sign_completion(session_id, Duration::from_secs(30))
+        .await
+        .unwrap();
+    assert_eq!(completed, output);
+    assert_eq!(polls.load(Ordering::SeqCst), 2);
+
+    assert!(matches!(
+        sui.wait_for_sign_completion(pending_session_id, Duration::from_millis(100))
+            .await,
+        Err(Error::SignSessionTimeout { session_id, .. }) if session_id == pending_session_id
+    ));
+    assert!(matches!(
+        sui.wait_for_sign_completion(ObjectID::random(), Duration::from_secs(30))
+            .await,
+        Err(Error::SignSessionFailed { .. })
+    ));
+}
+
+#[tokio::test]
+async fn external_signer_test() {
+    use std::sync::Arc;
+    use sui_keys::external_signer::ExternalSigner;
+    use sui_keys::keystore::InMemKeystore;
+    use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv};
+    use sui_sdk::wallet_context::WalletContext;
+    use sui_types::base_types::{random_object_ref, SuiAddress};
+    use sui_types::crypto::{PublicKey, Signature};
+    use sui_types::signature::VerifyParams;
+    use sui_types::transaction::TransactionData;
+
+    /// A remote signing service, holding its keys in memory.
+    struct RemoteSigner(InMemKeystore);
+
+    #[async_trait::async_trait]
+    impl ExternalSigner for RemoteSigner {
+        async fn public_keys(&self) -> anyhow::Result<Vec<PublicKey>> {
+            Ok(self.0.keys())
+        }
+
+        async fn sign_digest(
+            &self,
+            address: &SuiAddress,
+            digest: &[u8; 32],
+        ) -> anyhow::Result<Signature> {
+            Ok(self.0.sign_hashed(address, digest)?)
+        }
+    }
+
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("client.yaml");
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    let mut config = SuiClientConfig::new(keystore);
+    config.add_env(SuiEnv::localnet());
+    config.active_env = Some(SuiEnv::localnet().alias);
+    config.save(&config_path).unwrap();
+
+    let signer = RemoteSigner(InMemKeystore::new_insecure_for_tests(1));
+    let sender = signer.0.addresses()[0];
+    let data = TransactionData::new_transfer_sui(
+        SuiAddress::random_for_testing_only(),
+        sender,
+        None,
+        random_object_ref(),
+        50_000_000,
+        1_000,
+    );
+
+    // The sender is not in the keystore.
+    let context = WalletContext::new(&config_path, None, None).await.unwrap();
+    assert!(context.try_sign_transaction(&data).await.is_err());
+
+    let context = context.with_external_signer(Arc::new(signer));
+    let transaction = context.try_sign_transaction(&data).await.unwrap();
+    transaction
+        .verify_signature(&VerifyParams::default())
+        .unwrap();
+
+    // Neither the keystore nor the external signer has the key of the sender.
+    let other = TransactionData::new_transfer_sui(
+        SuiAddress::random_for_testing_only(),
+        SuiAddress::random_for_testing_only(),
+        None,
+        random_object_ref(),
+        50_000_000,
+        1_000,
+    );
+    assert!(context.try_sign_transaction(&other).await.is_err());
+}
+
+#[test]
+fn replace_address_test() {
+    use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv};
+    use sui_types::base_types::SuiAddress;
+
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    let mut config = SuiClientConfig::new(keystore);
+    let old = SuiAddress::random_for_testing_only();
+    let other = SuiAddress::random_for_testing_only();
+    config.active_address = Some(old);
+    config.add_env(
+        SuiEnv::builder("custom", "http://127.0.0.1:9000")
+            .active_address(old)
+            .build()
+            .unwrap(),
+    );
+    config.add_env(
+        SuiEnv::builder("other", "http://127.0.0.1:9001")
+            .active_address(other)
+            .build()
+            .unwrap(),
+    );
+    config
+        .add_address_alias("treasury".to_string(), old)
+        .unwrap();
+    config
+        .add_address_alias("friend".to_string(), other)
+        .unwrap();
+
+    let new = SuiAddress::random_for_testing_only();
+    config.replace_address(old, new);
+    assert_eq!(config.active_address, Some(new));
+    assert_eq!(config.envs[0].active_address, Some(new));
+    assert_eq!(config.envs[1].active_address, Some(other));
+    assert_eq!(config.resolve_address("treasury").unwrap(), new);
+    assert_eq!(config.resolve_address("friend").unwrap(), other);
+}
+
+#[tokio::test]
+async fn batch_get_objects_test() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use sui_sdk::rpc_types::{SuiObjectData, SuiObjectDataOptions, SuiObjectResponse};
+    use sui_sdk::SuiClientBuilder;
+    use sui_types::base_types::{ObjectID, SequenceNumber};
+    use sui_types::digests::ObjectDigest;
+
+    let multi_get_requests = Arc::new(AtomicUsize::new(0));
+    let largest_request = Arc::new(AtomicUsize::new(0));
+    let (requests, largest) = (multi_get_requests.clone(), largest_request.clone());
+    let url = serve_http(move |_, body| {
+        let request = serde_json::from_str::<serde_json::Value>(body).unwrap();
+        let result = if request["method"] == "sui_multiGetObjects" {
+            requests.fetch_add(1, Ordering::SeqCst);
+            let object_ids =
+                serde_json::from_value::<Vec<ObjectID>>(request["params"][0].clone()).unwrap();
+            largest.fetch_max(object_ids.len(), Ordering::SeqCst);
+            let objects: Vec<_> = object_ids
+                .into_iter()
+                .map(|object_id| {
+                    SuiObjectResponse::new_with_data(SuiObjectData {
+                        object_id,
+                        version: SequenceNumber::from(1),
+                        digest: ObjectDigest::random(),
+                        type_: None,
+                        owner: None,
+                        previous_transaction: None,
+                        storage_rebate: None,
+                        display: None,
+                        content: None,
+                        bcs: None,
+                    })
+                })
+                .collect();
+            serde_json::to_value(objects).unwrap()
+        } else {
+            serde_json::json!({ "info": { "version": "0.0.0" }, "methods": [] })
+        };
+        serde_json::json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string()
+    })
+    .await;
+
+    let sui = SuiClientBuilder::default()
+        .max_concurrent_requests(2)
+        .build(&url)
+        .await
+        .unwrap();
+    let object_ids: Vec<_> = (0..120).map(|_| ObjectID::random()).collect();
+    let objects = sui
+        .read_api()
+        .batch_get_objects_with_options(object_ids.clone(), SuiObjectDataOptions::new(), Some(8))
+        .await
+        .unwrap();
+
+    // The objects are fetched in chunks of the largest page, and returned in order.
+    assert_eq!(multi_get_requests.load(Ordering::SeqCst), 3);
+    assert_eq!(largest_request.load(Ordering::SeqCst), 50);
+    let fetched: Vec<_> = objects
+        .iter()
+        .map(|object| object.object_id().unwrap())
+        .collect();
+    assert_eq!(fetched, object_ids);
+
+    let objects = sui
+        .read_api()
+        .batch_get_objects_with_options(vec![], SuiObjectDataOptions::new(), None)
+        .await
+        .unwrap();
+    assert!(objects.is_empty());
+    assert_eq!(multi_get_requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn client_manager_test() {
+    use std::num::NonZeroU32;
+    use std::time::{Duration, Instant};
+    use sui_sdk::client_manager::ClientManagerBuilder;
+    use sui_sdk::sui_client_config::SuiEnv;
+
+    let dwallet = SuiEnv::builder("dwallet", serve_rpc_discover().await)
+        .build()
+        .unwrap();
+    let sui = SuiEnv::builder("sui", serve_rpc_discover().await)
+        .build()
+        .unwrap();
+
+    // The clients share the rate limit: the second rpc.discover waits for the next second.
+    let start = Instant::now();
+    let clients = ClientManagerBuilder::default()
+        .requests_per_second(NonZeroU32::new(1).unwrap())
+        .build([&sui, &dwallet])
+        .await
+        .unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(900));
+    assert_eq!(clients.aliases().collect::<Vec<_>>(), ["dwallet", "sui"]);
+    assert_eq!(clients.client("sui").unwrap().api_version(), "0.0.0");
+    assert!(clients.get("dwallet").is_some());
+    assert!(clients.client("mainnet").is_err());
+
+    // Aliases must be unique.
+    assert!(ClientManagerBuilder::default()
+        .build([&sui, &sui])
+        .await
+        .is_err());
+}
+
+#[test]
+fn verify_signature_for_dwallet_test() {
+    use sui_sdk::dwallet_signer::{verify_signature_for_dwallet, Hash};
+    use sui_types::base_types::ObjectID;
+    use sui_types::id::{ID, UID};
+    use sui_types::signature_mpc::DWallet;
+
+    // A dWallet whose public key, as returned by the fullnode, is not a valid point.
+    let dwallet = DWallet {
+        id: UID::new(ObjectID::random()),
+        session_id: ID::new(ObjectID::random()),
+        dwallet_cap_id: ID::new(ObjectID::random()),
+        output: vec![],
+        public_key: vec![1, 2, 3],
+        encrypted_secret_key_share: vec![],
+    };
+    let error = verify_signature_for_dwallet(&dwallet, b"message", Hash::SHA256, &[1; 64])
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains(&dwallet.id.object_id().to_string()));
+}
+
+#[tokio::test]
+async fn eth_sign_transaction_test() {
+    use sui_sdk::dwallet_signer::Hash;
+    use sui_sdk::eth::{self, AccessListItem, Eip1559Transaction};
+
+    let signer = LocalSigner(bitcoin::secp256k1::SecretKey::from_slice(&[1; 32]).unwrap());
+    let transaction = Eip1559Transaction {
+        chain_id: 1,
+        nonce: 7,
+        max_priority_fee_per_gas: 1_000_000_000,
+        max_fee_per_gas: 30_000_000_000,
+        gas_limit: 21_000,
+        to: Some(eth::address_from_hex("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf").unwrap()),
+        value: 10u128.pow(18),
+        data: vec![],
+        access_list: vec![],
+    };
+    let payload = transaction.signing_payload();
+    assert_eq!(payload[0], 0x02);
+    // A list of 9 fields, short enough for a single byte header.
+    assert_eq!(payload[1] as usize, 0xc0 + payload.len() - 2);
+
+    let raw = eth::sign_transaction(&transaction, &signer).await.unwrap();
+    assert_eq!(raw[0], 0x02);
+    assert_eq!(eth::transaction_hash(&raw).len(), 32);
+
+    // The raw transaction can be assembled again from the published signature alone.
+    let signature = signer
+        .sign(vec![payload.clone()], Hash::KECCAK256)
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+    assert_eq!(
+        transaction
+            .encode_signed(&signer.public_key(), &signature)
+            .unwrap(),
+        raw
+    );
+
+    // A signature of another transaction, or by another dWallet, is rejected.
+    let other = Eip1559Transaction {
+        nonce: 8,
+        ..transaction.clone()
+    };
+    assert!(other
+        .encode_signed(&signer.public_key(), &signature)
+        .is_err());
+    let other_signer = LocalSigner(bitcoin::secp256k1::SecretKey::from_slice(&[2; 32]).unwrap());
+    assert!(transaction
+        .encode_signed(&other_signer.public_key(), &signature)
+        .is_err());
+
+    // Long payloads get a multi-byte length header.
+    let long = Eip1559Transaction {
+        data: vec![0xab; 100],
+        access_list: vec![AccessListItem {
+            address: [1; 20],
+            storage_keys: vec![[2; 32]],
+        }],
+        ..transaction
+    };
+    let payload = long.signing_payload();
+    assert_eq!(payload[1], 0xf7 + 1);
+    assert_eq!(payload[2] as usize, payload.len() - 3);
+}
+
+#[test]
+fn summarize_psbt_test() {
+    use bitcoin::{absolute, transaction, Amount, OutPoint, ScriptBuf, Sequence, TxIn, TxOut};
+    use sui_sdk::btc;
+    use sui_types::external_address::BitcoinNetwork;
+
+    let secp = bitcoin::secp256k1::Secp256k1::signing_only();
+    let public_key = |secret: u8| {
+        let secret_key = bitcoin::secp256k1::SecretKey::from_slice(&[secret; 32]).unwrap();
+        bitcoin::PublicKey::new(secret_key.public_key(&secp))
+    };
+    let p2wpkh = |public_key: &bitcoin::PublicKey| {
+        ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash().unwrap())
+    };
+    let dwallet = public_key(1);
+    let recipient = public_key(2);
+
+    let input = TxIn {
+        previous_output: OutPoint::null(),
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::MAX,
+        witness: Default::default(),
+    };
+    let mut psbt = btc::create_psbt(bitcoin::Transaction {
+        version: transaction::Version::TWO,
+        lock_time: absolute::LockTime::ZERO,
+        input: vec![input],
+        output: vec![
+            TxOut {
+                value: Amount::from_sat(60_000),
+                script_pubkey: p2wpkh(&recipient),
+            },
+            TxOut {
+                value: Amount::from_sat(39_000),
+                script_pubkey: p2wpkh(&dwallet),
+            },
+        ],
+    })
+    .unwrap();
+
+    // The fee is unknown until the spent output is set.
+    let summary = btc::summarize_psbt(&psbt, &dwallet, BitcoinNetwork::Testnet).unwrap();
+    assert_eq!(summary.fee, None);
+    assert_eq!(summary.foreign_inputs, 1);
+
+    psbt.inputs[0].witness_utxo = Some(TxOut {
+        value: Amount::from_sat(100_000),
+        script_pubkey: p2wpkh(&dwallet),
+    });
+    let summary = btc::summarize_psbt(&psbt, &dwallet, BitcoinNetwork::Testnet).unwrap();
+    assert_eq!(summary.spent, Amount::from_sat(100_000));
+    assert_eq!(summary.fee, Some(Amount::from_sat(1_000)));
+    assert_eq!(summary.foreign_inputs, 0);
+    // The segwit signature of the dWallet commits to the amount of its input.
+    assert!(summary.unverified_inputs.is_empty());
+    assert!(!summary.spent_unverified);
+    assert!(summary.outputs[0].recipient.starts_with("tb1q"));
+    assert!(!summary.outputs[0].change);
+    assert!(summary.outputs[1].change);
+
+    // The amount of a foreign input without its previous transaction is unverified.
+    let mut foreign_psbt = psbt.clone();
+    foreign_psbt.inputs[0].witness_utxo = Some(TxOut {
+        value: Amount::from_sat(100_000),
+        script_pubkey: p2wpkh(&recipient),
+    });
+    let summary = btc::summarize_psbt(&foreign_psbt, &dwallet, BitcoinNetwork::Testnet).unwrap();
+    assert_eq!(summary.unverified_inputs, vec![0]);
+    assert!(!summary.spent_unverified);
+
+    // Outputs larger than the spent outputs are rejected.
+    psbt.unsigned_tx.output[0].value = Amount::from_sat(70_000);
+    assert!(btc::summarize_psbt(&psbt, &dwallet, BitcoinNetwork::Testnet).is_err());
+}
+
+#[test]
+fn sighash_requests_taproot_test() {
+    use bitcoin::{absolute, transaction, Amount, OutPoint, ScriptBuf, Sequence, TxIn, TxOut};
+    use sui_sdk::btc;
+
+    let secp = bitcoin::secp256k1::Secp256k1::new();
+    let secret_key = bitcoin::secp256k1::SecretKey::from_slice(&[1; 32]).unwrap();
+    let dwallet = bitcoin::PublicKey::new(secret_key.public_key(&secp));
+    let p2wpkh = ScriptBuf::new_p2wpkh(&dwallet.wpubkey_hash().unwrap());
+    let p2tr = ScriptBuf::new_p2tr(&secp, dwallet.inner.x_only_public_key().0, None);
+
+    let input = TxIn {
+        previous_output: OutPoint::null(),
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::MAX,
+        witness: Default::default(),
+    };
+    let mut psbt = btc::create_psbt(bitcoin::Transaction {
+        version: transaction::Version::TWO,
+        lock_time: absolute::LockTime::ZERO,
+        input: vec![input.clone(), input.clone(), input],
+        output: vec![TxOut {
+            value: Amount::from_sat(1_000),
+            script_pubkey: p2wpkh.clone(),
+        }],
+    })
+    .unwrap();
+    for (input, script_pubkey) in psbt.inputs.iter_mut().zip([&p2tr, &p2wpkh, &p2tr]) {
+        input.witness_utxo = Some(TxOut {
+            value: Amount::from_sat(1_000),
+            script_pubkey: script_pubkey.clone(),
+        });
+    }
+
+    // The PSBT is rejected as a whole, naming all the taproot inputs.
+    let error = btc::sighash_requests(&psbt, &dwallet, false).unwrap_err().to_string();
+    assert!(error.contains("input(s) 0, 2 spend the taproot output"), "{error}");
+
+    psbt.unsigned_tx.input.truncate(2);
+    psbt.inputs.truncate(2);
+    psbt.inputs[0].witness_utxo.as_mut().unwrap().script_pubkey = p2wpkh;
+    let requests = btc::sighash_requests(&psbt, &dwallet, false).unwrap();
+    assert_eq!(requests.len(), 2);
+}
+
+#[test]
+fn sighash_requests_spent_outputs_test() {
+    use bitcoin::psbt::PsbtSighashType;
+    use bitcoin::sighash::EcdsaSighashType;
+    use bitcoin::{absolute, transaction, Amount, OutPoint, ScriptBuf, Sequence, TxIn, TxOut};
+    use sui_sdk::btc;
+
+    let secp = bitcoin::secp256k1::Secp256k1::signing_only();
+    let secret_key = bitcoin::secp256k1::SecretKey::from_slice(&[1; 32]).unwrap();
+    let dwallet = bitcoin::PublicKey::new(secret_key.public_key(&secp));
+    let p2pkh = ScriptBuf::new_p2pkh(&dwallet.pubkey_hash());
+
+    let previous_tx = bitcoin::Transaction {
+        version: transaction::Version::TWO,
+        lock_time: absolute::LockTime::ZERO,
+        input: vec![],
+        output: vec![TxOut {
+            value: Amount::from_sat(100_000),
+            script_pubkey: p2pkh.clone(),
+        }],
+    };
+    let mut psbt = btc::create_psbt(bitcoin::Transaction {
+        version: transaction::Version::TWO,
+        lock_time: absolute::LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint::new(previous_tx.txid(), 0),
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::MAX,
+            witness: Default::default(),
+        }],
+        output: vec![TxOut {
+            value: Amount::from_sat(99_000),
+            script_pubkey: p2pkh.clone(),
+        }],
+    })
+    .unwrap();
+
+    // A legacy input is not signed with a `witness_utxo`, whose amount its sighash does not cover.
+    psbt.inputs[0].witness_utxo = Some(previous_tx.output[0].clone());
+    assert!(btc::sighash_requests(&psbt, &dwallet, false).is_err());
+
+    // Nor with a `non_witness_utxo` that is not the transaction it spends from.
+    psbt.inputs[0].witness_utxo = None;
+    let mut forged_tx = previous_tx.clone();
+    forged_tx.output[0].value = Amount::from_sat(1_000_000);
+    psbt.inputs[0].non_witness_utxo = Some(forged_tx);
+    assert!(btc::sighash_requests(&psbt, &dwallet, false).is_err());
+
+    psbt.inputs[0].non_witness_utxo = Some(previous_tx);
+    assert_eq!(btc::sighash_requests(&psbt, &dwallet, false).unwrap().len(), 1);
+
+    // Sighash types other than SIGHASH_ALL are only signed when allowed.
+    psbt.inputs[0].sighash_type = Some(PsbtSighashType::from(EcdsaSighashType::None));
+    assert!(btc::sighash_requests(&psbt, &dwallet, false).is_err());
+    assert_eq!(btc::sighash_requests(&psbt, &dwallet, true).unwrap().len(), 1);
+}
@@ -33,6 +33,7 @@ use sui_types::error::{SuiError, SuiResult, UserInputError};
 use sui_types::inner_temporary_store::TxCoins;
 use sui_types::object::{Object, Owner};
 use sui_types::parse_sui_struct_tag;
+use sui_types::signature_mpc::DWalletEvent;
 use tokio::task::spawn_blocking;
 use tracing::{debug, trace};
 use typed_store::rocks::{
@@ -213,6 +214,9 @@ pub struct IndexStoreTables {
     event_by_sender: DBMap<(SuiAddress, EventId), EventIndex>,
     #[default_options_override_fn = "index_table_default_config"]
     event_by_time: DBMap<(u64, EventId), EventIndex>,
+    /// The events of the presign and sign sessions of dWallets, by session.
+    #[default_options_override_fn = "index_table_default_config"]
+    event_by_dwallet_session: DBMap<(ObjectID, EventId), EventIndex>,
 }
 
 impl IndexStoreTables {
@@ -580,6 +584,17 @@ impl IndexStore {
             }),
         )?;
 
+        batch.insert_batch(
+            &self.tables.event_by_dwallet_session,
+            events.data.iter().enumerate().filter_map(|(i, e)| {
+                let event = DWalletEvent::try_from_event(&e.type_, &e.contents)?.ok()?;
+                Some((
+                    (*event.session_id()?, (sequence, i)),
+                    (event_digest, *digest, timestamp_ms),
+                ))
+            }),
+        )?;
+
         batch.insert_batch(
             &self.tables.event_by_time,
             events.data.iter().enumerate().map(|(i, _)| {
@@ -1094,6 +1109,24 @@ impl IndexStore {
         )
     }
 
+    pub fn events_by_dwallet_session(
+        &self,
+        session_id: &ObjectID,
+        tx_seq: TxSequenceNumber,
+        event_seq: usize,
+        limit: usize,
+        descending: bool,
+    ) -> SuiResult<Vec<(TransactionEventsDigest, TransactionDigest, usize, u64)>> {
+        Self::get_event_from_index(
+            &self.tables.event_by_dwallet_session,
+            session_id,
+            tx_seq,
+            event_seq,
+            limit,
+            descending,
+        )
+    }
+
     pub fn event_iterator(
         &self,
         start_time: u64,
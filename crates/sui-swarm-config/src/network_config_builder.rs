@@ -16,7 +16,7 @@ use sui_protocol_config::SupportedProtocolVersions;
 use sui_types::base_types::{AuthorityName, SuiAddress};
 use sui_types::committee::{Committee, ProtocolVersion};
 use sui_types::crypto::{get_key_pair_from_rng, AccountKeyPair, KeypairTraits, PublicKey};
-use signature_mpc::twopc_mpc_protocols::{config_signature_mpc_secret_for_network_for_testing, DecryptionPublicParameters, LargeBiPrimeSizedNumber, PaillierModulusSizedNumber, PartyID, SecretKeyShareSizedNumber, tiresias_deal_trusted_shares};
+use signature_mpc::twopc_mpc_protocols::{config_signature_mpc_secret_for_network_for_testing_with_threshold, default_decryption_threshold, DecryptionPublicParameters, LargeBiPrimeSizedNumber, PaillierModulusSizedNumber, PartyID, SecretKeyShareSizedNumber, tiresias_deal_trusted_shares};
 use sui_types::object::Object;
 
 pub enum CommitteeConfig {
@@ -61,6 +61,7 @@ pub struct ConfigBuilder<R = OsRng> {
     num_unpruned_validators: Option<usize>,
     overload_threshold_config: Option<OverloadThresholdConfig>,
     data_ingestion_dir: Option<PathBuf>,
+    signature_mpc_threshold: Option<usize>,
 }
 
 impl ConfigBuilder {
@@ -77,6 +78,7 @@ impl ConfigBuilder {
             num_unpruned_validators: None,
             overload_threshold_config: None,
             data_ingestion_dir: None,
+            signature_mpc_threshold: None,
         }
     }
 
@@ -199,6 +201,13 @@ impl<R> ConfigBuilder<R> {
         self
     }
 
+    /// The number of validators needed to decrypt in the signature MPC protocols, more than two
+    /// thirds of the committee by default. Only applies to committees of a given size.
+    pub fn with_signature_mpc_threshold(mut self, threshold: usize) -> Self {
+        self.signature_mpc_threshold = Some(threshold);
+        self
+    }
+
     pub fn rng<N: rand::RngCore + rand::CryptoRng>(self, rng: N) -> ConfigBuilder<N> {
         ConfigBuilder {
             rng: Some(rng),
@@ -212,6 +221,7 @@ impl<R> ConfigBuilder<R> {
             jwk_fetch_interval: self.jwk_fetch_interval,
             overload_threshold_config: self.overload_threshold_config,
             data_ingestion_dir: self.data_ingestion_dir,
+            signature_mpc_threshold: self.signature_mpc_threshold,
         }
     }
 
@@ -239,7 +249,9 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
                 // this same committee.
                 let (_, mut keys) = Committee::new_simple_test_committee_of_size(size.into());
 
-                let (decryption_key_share_public_parameters, decryption_key_shares) = config_signature_mpc_secret_for_network_for_testing(size.get() as PartyID);
+                let number_of_parties = size.get() as PartyID;
+                let threshold = self.signature_mpc_threshold.map_or_else(|| default_decryption_threshold(number_of_parties), |threshold| threshold as PartyID);
+                let (decryption_key_share_public_parameters, decryption_key_shares) = config_signature_mpc_secret_for_network_for_testing_with_threshold(number_of_parties, threshold);
 
                 keys.sort_by_key(|k| AuthorityName::from(k.public()));
                 keys.into_iter()
@@ -46,6 +46,7 @@ pub struct SwarmBuilder<R = OsRng> {
     num_unpruned_validators: Option<usize>,
     overload_threshold_config: Option<OverloadThresholdConfig>,
     data_ingestion_dir: Option<PathBuf>,
+    signature_mpc_threshold: Option<usize>,
 }
 
 impl SwarmBuilder {
@@ -68,6 +69,7 @@ impl SwarmBuilder {
             num_unpruned_validators: None,
             overload_threshold_config: None,
             data_ingestion_dir: None,
+            signature_mpc_threshold: None,
         }
     }
 }
@@ -92,6 +94,7 @@ impl<R> SwarmBuilder<R> {
             num_unpruned_validators: self.num_unpruned_validators,
             overload_threshold_config: self.overload_threshold_config,
             data_ingestion_dir: self.data_ingestion_dir,
+            signature_mpc_threshold: self.signature_mpc_threshold,
         }
     }
 
@@ -222,6 +225,14 @@ impl<R> SwarmBuilder<R> {
         self
     }
 
+    /// The number of validators needed to decrypt in the signature MPC protocols, see
+    /// [ConfigBuilder::with_signature_mpc_threshold].
+    pub fn with_signature_mpc_threshold(mut self, threshold: usize) -> Self {
+        assert!(self.network_config.is_none());
+        self.signature_mpc_threshold = Some(threshold);
+        self
+    }
+
     pub fn with_data_ingestion_dir(mut self, path: PathBuf) -> Self {
         self.data_ingestion_dir = Some(path);
         self
@@ -270,6 +281,10 @@ impl<R: rand::RngCore + rand::CryptoRng> SwarmBuilder<R> {
                 config_builder = config_builder.with_data_ingestion_dir(path);
             }
 
+            if let Some(threshold) = self.signature_mpc_threshold {
+                config_builder = config_builder.with_signature_mpc_threshold(threshold);
+            }
+
             config_builder
                 .committee(self.committee)
                 .rng(self.rng)
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use axum::{
     extract::Path,
     response::IntoResponse,
@@ -80,6 +80,17 @@ struct Args {
     #[clap(long, default_value = "60000")]
     epoch_duration_ms: u64,
 
+    /// The number of validators, each a party of the signature MPC protocols (DKG, presign and
+    /// sign)
+    #[clap(long, default_value = "4")]
+    mpc_parties: usize,
+
+    /// The number of MPC parties needed to decrypt, from 1 to `--mpc-parties`
+    /// Defaults to more than two thirds of the parties. Setting it to `--mpc-parties` makes every
+    /// validator needed, to reproduce the sessions stalling on a single validator
+    #[clap(long)]
+    mpc_threshold: Option<usize>,
+
     /// if we should run indexer
     #[clap(long)]
     pub with_indexer: bool,
@@ -112,6 +123,8 @@ async fn main() -> Result<()> {
         pg_user,
         pg_password,
         epoch_duration_ms,
+        mpc_parties,
+        mpc_threshold,
         faucet_port,
         with_indexer,
         use_indexer_experimental_methods,
@@ -125,6 +138,20 @@ async fn main() -> Result<()> {
         Some(epoch_duration_ms)
     };
 
+    // Nor the MPC committee shape, which is that of the genesis config.
+    if mpc_parties == 0 {
+        bail!("--mpc-parties must be at least 1");
+    }
+    if let Some(mpc_threshold) = mpc_threshold {
+        if config_dir.is_some() {
+            bail!("--mpc-threshold cannot be set with --config-dir, the genesis config sets it");
+        }
+        if !(1..=mpc_parties).contains(&mpc_threshold) {
+            bail!("--mpc-threshold must be between 1 and --mpc-parties ({mpc_parties})");
+        }
+    }
+    let mpc_parties = config_dir.is_none().then_some(mpc_parties);
+
     if graphql_port.is_none() {
         println!("Graphql port not provided. Graphql service will not run.")
     }
@@ -148,6 +175,8 @@ async fn main() -> Result<()> {
         config_dir,
         graphql_address: graphql_port.map(|p| format!("{}:{}", graphql_host, p)),
         use_indexer_v2,
+        mpc_parties,
+        mpc_threshold,
     };
 
     println!("Starting Sui validator with config: {:#?}", cluster_config);
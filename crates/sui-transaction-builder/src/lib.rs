@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::result::Result;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -17,8 +17,8 @@ use move_core_types::language_storage::{StructTag, TypeTag};
 
 use sui_json::{is_receiving_argument, resolve_move_function_args, ResolvedCallArg, SuiJsonValue};
 use sui_json_rpc_types::{
-    RPCTransactionRequestParams, SuiData, SuiObjectDataOptions, SuiObjectResponse, SuiRawData,
-    SuiTypeTag,
+    RPCTransactionRequestParams, SignRequestParams, SuiData, SuiObjectDataOptions,
+    SuiObjectResponse, SuiRawData, SuiTypeTag,
 };
 use sui_protocol_config::ProtocolConfig;
 use sui_types::base_types::{ObjectID, ObjectInfo, ObjectRef, ObjectType, SuiAddress};
@@ -28,9 +28,15 @@ use sui_types::governance::{ADD_STAKE_MUL_COIN_FUN_NAME, WITHDRAW_STAKE_FUN_NAME
 use sui_types::move_package::MovePackage;
 use sui_types::object::{Object, Owner};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::signature_mpc::{
+    DWalletCap, Presign, PresignSession, PresignSessionOutput, SignData,
+    APPROVE_MESSAGES_FUNC_NAME, CREATE_SIGN_MESSAGES_FUNC_NAME,
+    DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME, DWALLET_MODULE_NAME, SIGN_MESSAGES_FUNC_NAME,
+};
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::transaction::{
-    Argument, CallArg, Command, InputObjectKind, ObjectArg, TransactionData, TransactionKind,
+    Argument, CallArg, Command, InputObjectKind, ObjectArg, ProgrammableTransaction,
+    TransactionData, TransactionKind,
 };
 use sui_types::{coin, fp_ensure, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID};
 
@@ -49,6 +55,9 @@ pub trait DataReader {
     ) -> Result<SuiObjectResponse, anyhow::Error>;
 
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error>;
+
+    /// The protocol config of the current epoch.
+    async fn get_protocol_config(&self) -> Result<ProtocolConfig, anyhow::Error>;
 }
 
 #[derive(Clone)]
@@ -674,6 +683,140 @@ impl TransactionBuilder {
         ))
     }
 
+    /// Build the transactions signing the messages of the presigns of `requests`, packed into as
+    /// few transactions as the transaction limits allow, each paying for its gas with a coin of
+    /// its own to be executed concurrently.
+    ///
+    /// The requests of the same `DWalletCap` are packed into the same transaction, as every
+    /// transaction using the capability changes its version.
+    pub async fn batch_sign(
+        &self,
+        signer: SuiAddress,
+        requests: Vec<SignRequestParams>,
+        gas_budget: u64,
+    ) -> anyhow::Result<Vec<SignBatchTransactionData>> {
+        fp_ensure!(
+            !requests.is_empty(),
+            UserInputError::InvalidBatchTransaction {
+                error: "Batch sign cannot be empty".to_owned(),
+            }
+            .into()
+        );
+        let mut presigns = HashSet::new();
+        let mut resolved = Vec::with_capacity(requests.len());
+        for (index, request) in requests.into_iter().enumerate() {
+            ensure!(
+                presigns.insert(request.presign_id),
+                "The presign [{}] is signed by more than one request.",
+                request.presign_id
+            );
+            let request = self
+                .resolve_sign_request(signer, request)
+                .await
+                .map_err(|e| anyhow!("Invalid sign request {index}: {e}"))?;
+            resolved.push(request);
+        }
+
+        let mut groups: BTreeMap<ObjectID, SignRequestGroup> = BTreeMap::new();
+        for (index, request) in resolved.iter().enumerate() {
+            let group = groups.entry(request.dwallet_cap.0).or_default();
+            group.requests.push(index);
+        }
+        let limits = SignBatchLimits::new(&self.0.get_protocol_config().await?);
+        for (dwallet_cap_id, group) in groups.iter_mut() {
+            let requests: Vec<_> = group.requests.iter().map(|i| &resolved[*i]).collect();
+            group.size = bcs::serialized_size(&sign_transaction(&requests)?)? as u64;
+            group.objects = 1 + SIGN_REQUEST_INPUT_OBJECTS * requests.len() as u64;
+            ensure!(
+                limits.fits(group.requests.len(), group.size, group.objects),
+                "The sign requests of the dWallet capability [{dwallet_cap_id}] do not fit in one \
+                 transaction."
+            );
+        }
+
+        let batches = pack_sign_request_groups(groups.into_values().collect(), &limits);
+
+        let gas_price = self.0.get_reference_gas_price().await?;
+        let mut excluded: Vec<_> = resolved
+            .iter()
+            .flat_map(|request| request.owned_objects())
+            .collect();
+        let mut transactions = Vec::with_capacity(batches.len());
+        for batch in batches {
+            let requests: Vec<_> = batch.requests.iter().map(|i| &resolved[*i]).collect();
+            let pt = sign_transaction(&requests)?;
+            let gas = self
+                .select_gas(signer, None, gas_budget, excluded.clone(), gas_price)
+                .await?;
+            excluded.push(gas.0);
+            let data = TransactionData::new(
+                TransactionKind::programmable(pt),
+                signer,
+                gas,
+                gas_budget,
+                gas_price,
+            );
+            transactions.push(SignBatchTransactionData {
+                data,
+                requests: batch.requests,
+            });
+        }
+        Ok(transactions)
+    }
+
+    /// Check that the presign of `request`, its presign session output and the capability of its
+    /// dWallet are owned by `signer`, and read the references of the objects signing it.
+    async fn resolve_sign_request(
+        &self,
+        signer: SuiAddress,
+        request: SignRequestParams,
+    ) -> anyhow::Result<ResolvedSignRequest> {
+        ensure!(
+            !request
+                .public_nonce_encrypted_partial_signature_and_proofs
+                .is_empty(),
+            "No partial signatures."
+        );
+        let (bcs, presign_ref) = self
+            .get_owned_move_object(signer, request.presign_id, Presign::type_())
+            .await?;
+        let presign: Presign = bcs::from_bytes(&bcs)?;
+        let (bcs, presign_session_output_ref) = self
+            .get_owned_move_object(
+                signer,
+                request.presign_session_output_id,
+                PresignSessionOutput::type_(),
+            )
+            .await?;
+        let output: PresignSessionOutput = bcs::from_bytes(&bcs)?;
+        ensure!(
+            output.session_id == presign.session_id,
+            "The presign session output [{}] is not of the presign session [{}] of the presign [{}].",
+            request.presign_session_output_id,
+            presign.session_id.bytes,
+            request.presign_id
+        );
+        let (_, dwallet_cap) = self
+            .get_owned_move_object(signer, presign.dwallet_cap_id.bytes, DWalletCap::type_())
+            .await?;
+        let (bcs, presign_session, _) = self
+            .get_move_object(presign.session_id.bytes, PresignSession::type_())
+            .await?;
+        let session: PresignSession = bcs::from_bytes(&bcs)?;
+        let dwallet = self.get_object_ref(presign.dwallet_id.bytes).await?;
+
+        Ok(ResolvedSignRequest {
+            dwallet_cap,
+            dwallet,
+            presign_session,
+            presign_session_output: presign_session_output_ref,
+            presign: presign_ref,
+            messages: session.messages,
+            public_nonce_encrypted_partial_signature_and_proofs: request
+                .public_nonce_encrypted_partial_signature_and_proofs,
+        })
+    }
+
     pub async fn finish_programmable_transaction(
         &self,
         signer: SuiAddress,
@@ -807,6 +950,53 @@ impl TransactionBuilder {
             .map(|(oref, _)| oref)
     }
 
+    /// The BCS bytes of the Move object `object_id` of type `type_`, with its reference and owner.
+    async fn get_move_object(
+        &self,
+        object_id: ObjectID,
+        type_: StructTag,
+    ) -> anyhow::Result<(Vec<u8>, ObjectRef, Owner)> {
+        let object = self
+            .0
+            .get_object_with_options(object_id, SuiObjectDataOptions::bcs_lossless())
+            .await?
+            .into_object()?;
+        let object_type: StructTag = object.object_type()?.try_into()?;
+        ensure!(
+            object_type == type_,
+            "Object [{object_id}] is not a {}, but a {object_type}.",
+            type_.name
+        );
+        let owner = object
+            .owner
+            .ok_or_else(|| anyhow!("owner field is unexpectedly empty"))?;
+        let bcs = object
+            .bcs
+            .as_ref()
+            .ok_or_else(|| anyhow!("bcs field is unexpectedly empty"))?
+            .try_as_move()
+            .ok_or_else(|| anyhow!("Cannot parse move object [{object_id}]"))?
+            .bcs_bytes
+            .clone();
+        Ok((bcs, object.object_ref(), owner))
+    }
+
+    /// The BCS bytes of the Move object `object_id` of type `type_`, with its reference, checked
+    /// to be owned by `signer`.
+    async fn get_owned_move_object(
+        &self,
+        signer: SuiAddress,
+        object_id: ObjectID,
+        type_: StructTag,
+    ) -> anyhow::Result<(Vec<u8>, ObjectRef)> {
+        let (bcs, object_ref, owner) = self.get_move_object(object_id, type_).await?;
+        ensure!(
+            owner == Owner::AddressOwner(signer),
+            "Object [{object_id}] is owned by {owner}, not by the signer [{signer}]."
+        );
+        Ok((bcs, object_ref))
+    }
+
     async fn get_object_ref_and_type(
         &self,
         object_id: ObjectID,
@@ -820,3 +1010,212 @@ impl TransactionBuilder {
         Ok((object.object_ref(), object.object_type()?))
     }
 }
+
+/// The objects created by the signature of a request: its `SignData`, its `SignMessages`, and its
+/// `SignSession`.
+const SIGN_REQUEST_CREATED_OBJECTS: u64 = 3;
+/// The input objects of a request besides the capability of its dWallet: its dWallet, presign
+/// session, presign session output and presign.
+const SIGN_REQUEST_INPUT_OBJECTS: u64 = 4;
+/// The room left in a transaction of `batch_sign` for its gas data and signatures.
+const SIGN_TRANSACTION_SIZE_MARGIN: u64 = 4 * 1024;
+
+/// An unsigned transaction of [TransactionBuilder::batch_sign].
+pub struct SignBatchTransactionData {
+    pub data: TransactionData,
+    /// The indices of the requests signed by the transaction, in the order of its sign sessions:
+    /// once executed, the transaction emits a `SignSessionCreated` event per request, in order.
+    pub requests: Vec<usize>,
+}
+
+struct ResolvedSignRequest {
+    dwallet_cap: ObjectRef,
+    dwallet: ObjectRef,
+    presign_session: ObjectRef,
+    presign_session_output: ObjectRef,
+    presign: ObjectRef,
+    messages: Vec<Vec<u8>>,
+    public_nonce_encrypted_partial_signature_and_proofs: Vec<u8>,
+}
+
+impl ResolvedSignRequest {
+    /// The owned objects of the request, which no transaction can pay its gas with.
+    fn owned_objects(&self) -> [ObjectID; 3] {
+        [
+            self.dwallet_cap.0,
+            self.presign_session_output.0,
+            self.presign.0,
+        ]
+    }
+}
+
+/// Requests packed into the same transaction, with the size of their commands and their input
+/// objects.
+#[derive(Debug, Default, PartialEq)]
+struct SignRequestGroup {
+    requests: Vec<usize>,
+    size: u64,
+    objects: u64,
+}
+
+impl SignRequestGroup {
+    fn extend(&mut self, other: SignRequestGroup) {
+        self.requests.extend(other.requests);
+        self.size += other.size;
+        self.objects += other.objects;
+    }
+}
+
+/// Pack `groups`, each fitting in a transaction, into as few transactions as `limits` allow.
+///
+/// First fit decreasing: the largest groups are packed first, each into the first batch with room
+/// for it.
+fn pack_sign_request_groups(
+    mut groups: Vec<SignRequestGroup>,
+    limits: &SignBatchLimits,
+) -> Vec<SignRequestGroup> {
+    groups.sort_by(|a, b| b.size.cmp(&a.size));
+    let mut batches: Vec<SignRequestGroup> = vec![];
+    for group in groups {
+        match batches.iter_mut().find(|batch| {
+            limits.fits(
+                batch.requests.len() + group.requests.len(),
+                batch.size + group.size,
+                batch.objects + group.objects,
+            )
+        }) {
+            Some(batch) => batch.extend(group),
+            None => batches.push(group),
+        }
+    }
+    batches
+}
+
+/// The limits of a transaction of `batch_sign`. Every request emits a `SignSessionCreated` event
+/// with three commands.
+struct SignBatchLimits {
+    requests: usize,
+    size: u64,
+    objects: u64,
+}
+
+impl SignBatchLimits {
+    fn new(config: &ProtocolConfig) -> Self {
+        let requests = (config.max_programmable_tx_commands() as u64 / 3)
+            .min(config.max_num_event_emit())
+            .min(config.max_num_new_move_object_ids() / SIGN_REQUEST_CREATED_OBJECTS);
+        Self {
+            requests: requests as usize,
+            size: config.max_tx_size_bytes() - SIGN_TRANSACTION_SIZE_MARGIN,
+            objects: config.max_input_objects(),
+        }
+    }
+
+    fn fits(&self, requests: usize, size: u64, objects: u64) -> bool {
+        requests <= self.requests && size <= self.size && objects <= self.objects
+    }
+}
+
+/// The transaction signing the messages of `requests`, as `dwallet::sign_messages` approved by
+/// the capabilities of their dWallets.
+fn sign_transaction(requests: &[&ResolvedSignRequest]) -> anyhow::Result<ProgrammableTransaction> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    for request in requests {
+        let dwallet_cap = builder.obj(ObjectArg::ImmOrOwnedObject(request.dwallet_cap))?;
+        let messages = builder.pure(&request.messages)?;
+        let message_approvals = builder.programmable_move_call(
+            SUI_SYSTEM_PACKAGE_ID,
+            DWALLET_MODULE_NAME.to_owned(),
+            APPROVE_MESSAGES_FUNC_NAME.to_owned(),
+            vec![],
+            vec![dwallet_cap, messages],
+        );
+        let arguments = vec![
+            builder.obj(ObjectArg::ImmOrOwnedObject(request.dwallet))?,
+            builder.obj(ObjectArg::ImmOrOwnedObject(request.presign_session))?,
+            builder.obj(ObjectArg::ImmOrOwnedObject(request.presign_session_output))?,
+            builder.obj(ObjectArg::ImmOrOwnedObject(request.presign))?,
+            builder.pure(&request.public_nonce_encrypted_partial_signature_and_proofs)?,
+        ];
+        let sign_messages = builder.programmable_move_call(
+            SUI_SYSTEM_PACKAGE_ID,
+            DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME.to_owned(),
+            CREATE_SIGN_MESSAGES_FUNC_NAME.to_owned(),
+            vec![],
+            arguments,
+        );
+        builder.programmable_move_call(
+            SUI_SYSTEM_PACKAGE_ID,
+            DWALLET_MODULE_NAME.to_owned(),
+            SIGN_MESSAGES_FUNC_NAME.to_owned(),
+            vec![TypeTag::Struct(Box::new(SignData::type_()))],
+            vec![sign_messages, message_approvals],
+        );
+    }
+    Ok(builder.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(requests: &[usize], size: u64) -> SignRequestGroup {
+        SignRequestGroup {
+            requests: requests.to_vec(),
+            size,
+            objects: 1 + SIGN_REQUEST_INPUT_OBJECTS * requests.len() as u64,
+        }
+    }
+
+    #[test]
+    fn test_sign_batch_limits() {
+        let config = ProtocolConfig::get_for_max_version_UNSAFE();
+        let limits = SignBatchLimits::new(&config);
+
+        assert!(limits.requests > 0);
+        assert!(3 * limits.requests as u64 <= config.max_programmable_tx_commands() as u64);
+        assert!(limits.requests as u64 <= config.max_num_event_emit());
+        assert!(
+            SIGN_REQUEST_CREATED_OBJECTS * limits.requests as u64
+                <= config.max_num_new_move_object_ids()
+        );
+        assert!(limits.size < config.max_tx_size_bytes());
+    }
+
+    #[test]
+    fn test_pack_sign_request_groups() {
+        let limits = SignBatchLimits {
+            requests: 3,
+            size: 100,
+            objects: 1_000,
+        };
+        let groups = vec![
+            group(&[0], 30),
+            group(&[1, 2], 60),
+            group(&[3], 50),
+            group(&[4], 10),
+        ];
+
+        // The group of 2 requests is packed first, then those of request 3, which does not fit
+        // with it, of request 0, which does, and of request 4, over the request limit of the first.
+        let mut first = group(&[1, 2], 60);
+        first.extend(group(&[0], 30));
+        let mut second = group(&[3], 50);
+        second.extend(group(&[4], 10));
+        assert_eq!(
+            pack_sign_request_groups(groups, &limits),
+            vec![first, second]
+        );
+
+        // The input objects are limited too.
+        let limits = SignBatchLimits {
+            requests: 3,
+            size: 100,
+            objects: 9,
+        };
+        assert_eq!(
+            pack_sign_request_groups(vec![group(&[0], 10), group(&[1], 10)], &limits),
+            vec![group(&[0], 10), group(&[1], 10)]
+        );
+    }
+}
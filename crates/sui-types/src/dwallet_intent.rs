@@ -0,0 +1,132 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! The canonical envelope of messages signed by dWallets on behalf of external consumers.
+//!
+//! A consumer, such as a contract on another chain, that accepts dWallet signatures over
+//! application messages should only accept signatures over an encoded [DWalletIntent]: it binds
+//! the message to the chain it is meant for, a nonce and an expiry, so that the signature can be
+//! neither replayed on another chain or protocol, nor after it expires. The encoding starts with
+//! the domain separator [INTENT_DOMAIN], so it can never be a valid transaction of any chain.
+
+use fastcrypto::hash::{HashFunction, Sha256};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub const INTENT_DOMAIN: &[u8] = b"dwallet-intent:v1";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DWalletIntentError {
+    #[error("Invalid intent encoding: {0}")]
+    InvalidEncoding(String),
+    #[error("Invalid chain namespace {0:?}, expected a CAIP-2 chain id such as \"eip155:1\"")]
+    InvalidChain(String),
+    #[error("Intent is for chain {actual}, expected {expected}")]
+    ChainMismatch { expected: String, actual: String },
+    #[error("Intent payload hash does not match the payload")]
+    PayloadHashMismatch,
+    #[error("Intent expired at {expiry_ms}, now is {now_ms}")]
+    Expired { expiry_ms: u64, now_ms: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DWalletIntent {
+    /// The CAIP-2 id of the chain the message is meant for, e.g. `eip155:1` or
+    /// `cosmos:cosmoshub-4`.
+    pub chain: String,
+    /// Chosen by the consumer, which must reject the nonces it already accepted.
+    pub nonce: u64,
+    /// The Unix time in milliseconds after which the intent must be rejected.
+    pub expiry_ms: u64,
+    /// The SHA256 hash of the application message.
+    pub payload_hash: [u8; 32],
+}
+
+impl DWalletIntent {
+    pub fn new(
+        chain: &str,
+        nonce: u64,
+        expiry_ms: u64,
+        payload: &[u8],
+    ) -> Result<Self, DWalletIntentError> {
+        validate_chain(chain)?;
+        Ok(Self {
+            chain: chain.to_string(),
+            nonce,
+            expiry_ms,
+            payload_hash: payload_hash(payload),
+        })
+    }
+
+    /// The message signed by the dWallet: `INTENT_DOMAIN || bcs(intent)`.
+    pub fn encode(&self) -> Vec<u8> {
+        [
+            INTENT_DOMAIN,
+            &bcs::to_bytes(self).expect("intent is serializable"),
+        ]
+        .concat()
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, DWalletIntentError> {
+        let intent = bytes
+            .strip_prefix(INTENT_DOMAIN)
+            .ok_or_else(|| DWalletIntentError::InvalidEncoding("missing domain".to_string()))?;
+        let intent: Self = bcs::from_bytes(intent)
+            .map_err(|e| DWalletIntentError::InvalidEncoding(e.to_string()))?;
+        validate_chain(&intent.chain)?;
+        Ok(intent)
+    }
+
+    /// Verify that the intent is for `payload` on chain `chain`, and not expired at `now_ms`.
+    /// The nonce is left to the consumer to check.
+    pub fn verify(
+        &self,
+        chain: &str,
+        payload: &[u8],
+        now_ms: u64,
+    ) -> Result<(), DWalletIntentError> {
+        if self.chain != chain {
+            return Err(DWalletIntentError::ChainMismatch {
+                expected: chain.to_string(),
+                actual: self.chain.clone(),
+            });
+        }
+        if self.payload_hash != payload_hash(payload) {
+            return Err(DWalletIntentError::PayloadHashMismatch);
+        }
+        if now_ms > self.expiry_ms {
+            return Err(DWalletIntentError::Expired {
+                expiry_ms: self.expiry_ms,
+                now_ms,
+            });
+        }
+        Ok(())
+    }
+}
+
+pub fn payload_hash(payload: &[u8]) -> [u8; 32] {
+    Sha256::digest(payload).digest
+}
+
+/// Check that `chain` is a CAIP-2 chain id: a namespace of 3 to 8 lowercase alphanumerics or `-`,
+/// and a reference of 1 to 32 alphanumerics, `-` or `_`, separated by `:`.
+fn validate_chain(chain: &str) -> Result<(), DWalletIntentError> {
+    let valid = chain.split_once(':').is_some_and(|(namespace, reference)| {
+        (3..=8).contains(&namespace.len())
+            && namespace
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+            && (1..=32).contains(&reference.len())
+            && reference
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+    });
+    if !valid {
+        return Err(DWalletIntentError::InvalidChain(chain.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "unit_tests/dwallet_intent_tests.rs"]
+mod dwallet_intent_tests;
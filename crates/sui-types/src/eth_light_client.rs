@@ -0,0 +1,748 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Ethereum consensus layer light client data, as served by the Beacon API light client
+//! endpoints (Deneb and later forks).
+//!
+//! The human readable (JSON) encoding follows the Beacon API: integers as decimal strings and
+//! bytes as `0x` prefixed hex. The BCS encoding is the one submitted to the dWallet network.
+//!
+//! Verification follows the Altair light client specification, with the SSZ hash tree roots
+//! of the containers involved reproduced here.
+
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{HashFunction, Sha256};
+use move_core_types::ident_str;
+use move_core_types::identifier::IdentStr;
+use move_core_types::language_storage::StructTag;
+use move_core_types::u256::U256;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, Bytes};
+use thiserror::Error;
+
+use crate::collection_types::Table;
+use crate::id::UID;
+use crate::sui_serde::{BigInt, Readable};
+use crate::SUI_SYSTEM_ADDRESS;
+
+pub const ETHEREUM_LIGHT_CLIENT_MODULE_NAME: &IdentStr = ident_str!("ethereum_light_client");
+pub const ETHEREUM_LIGHT_CLIENT_STRUCT_NAME: &IdentStr = ident_str!("EthereumLightClient");
+pub const ETHEREUM_LIGHT_CLIENT_CREATE_FUNCTION_NAME: &IdentStr = ident_str!("create_light_client");
+pub const ETHEREUM_LIGHT_CLIENT_SUBMIT_UPDATE_FUNCTION_NAME: &IdentStr =
+    ident_str!("submit_update");
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum EthLightClientError {
+    #[error("Invalid header: {0}")]
+    InvalidHeader(String),
+    #[error("Invalid bootstrap: {0}")]
+    InvalidBootstrap(String),
+    #[error("Invalid checkpoint: {0}")]
+    InvalidCheckpoint(String),
+    #[error("Invalid update: {0}")]
+    InvalidUpdate(String),
+    #[error("No fork scheduled at epoch {0}")]
+    UnknownFork(u64),
+}
+
+pub type Root = [u8; 32];
+pub type BlsPublicKey = [u8; 48];
+pub type BlsSignature = [u8; 96];
+
+pub const SYNC_COMMITTEE_SIZE: usize = 512;
+pub const SLOTS_PER_EPOCH: u64 = 32;
+pub const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256;
+
+/// The sync committee period of `slot`.
+pub fn sync_committee_period(slot: u64) -> u64 {
+    slot / SLOTS_PER_EPOCH / EPOCHS_PER_SYNC_COMMITTEE_PERIOD
+}
+
+/// The generalized index of the execution payload in a beacon block body.
+const EXECUTION_PAYLOAD_GINDEX: u64 = 25;
+const DOMAIN_SYNC_COMMITTEE: [u8; 4] = [7, 0, 0, 0];
+/// The ciphersuite of Ethereum BLS signatures, with proof of possession.
+const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForkName {
+    Deneb,
+    Electra,
+}
+
+impl ForkName {
+    /// The generalized index of the current sync committee in a beacon state, which gained a
+    /// level in Electra.
+    pub fn current_sync_committee_gindex(&self) -> u64 {
+        match self {
+            ForkName::Deneb => 54,
+            ForkName::Electra => 86,
+        }
+    }
+
+    pub fn next_sync_committee_gindex(&self) -> u64 {
+        self.current_sync_committee_gindex() + 1
+    }
+
+    pub fn finalized_root_gindex(&self) -> u64 {
+        match self {
+            ForkName::Deneb => 105,
+            ForkName::Electra => 169,
+        }
+    }
+}
+
+/// A fork of the beacon chain and the epoch from which it is active.
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fork {
+    pub name: ForkName,
+    pub epoch: u64,
+    #[serde_as(as = "Readable<Hex, _>")]
+    pub version: [u8; 4],
+}
+
+/// A finalized checkpoint, identified by its epoch and the root of its beacon block.
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub epoch: u64,
+    #[serde_as(as = "Readable<Hex, _>")]
+    pub block_root: Root,
+}
+
+/// A Beacon API response tagged with the fork of its data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Versioned<T> {
+    pub version: ForkName,
+    pub data: T,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BeaconBlockHeader {
+    #[serde_as(as = "Readable<BigInt<u64>, _>")]
+    pub slot: u64,
+    #[serde_as(as = "Readable<BigInt<u64>, _>")]
+    pub proposer_index: u64,
+    #[serde_as(as = "Readable<Hex, _>")]
+    pub parent_root: Root,
+    #[serde_as(as = "Readable<Hex, _>")]
+    pub state_root: Root,
+    #[serde_as(as = "Readable<Hex, _>")]
+    pub body_root: Root,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionPayloadHeader {
+    #[serde_as(as = "Readable<Hex, _>")]
+    pub parent_hash: [u8; 32],
+    #[serde_as(as = "Readable<Hex, _>")]
+    pub fee_recipient: [u8; 20],
+    #[serde_as(as = "Readable<Hex, _>")]
+    pub state_root: [u8; 32],
+    #[serde_as(as = "Readable<Hex, _>")]
+    pub receipts_root: [u8; 32],
+    #[serde_as(as = "Readable<Hex, Bytes>")]
+    pub logs_bloom: [u8; 256],
+    #[serde_as(as = "Readable<Hex, _>")]
+    pub prev_randao: [u8; 32],
+    #[serde_as(as = "Readable<BigInt<u64>, _>")]
+    pub block_number: u64,
+    #[serde_as(as = "Readable<BigInt<u64>, _>")]
+    pub gas_limit: u64,
+    #[serde_as(as = "Readable<BigInt<u64>, _>")]
+    pub gas_used: u64,
+    #[serde_as(as = "Readable<BigInt<u64>, _>")]
+    pub timestamp: u64,
+    #[serde_as(as = "Readable<Hex, _>")]
+    pub extra_data: Vec<u8>,
+    #[serde_as(as = "Readable<BigInt<U256>, _>")]
+    pub base_fee_per_gas: U256,
+    #[serde_as(as = "Readable<Hex, _>")]
+    pub block_hash: [u8; 32],
+    #[serde_as(as = "Readable<Hex, _>")]
+    pub transactions_root: Root,
+    #[serde_as(as = "Readable<Hex, _>")]
+    pub withdrawals_root: Root,
+    #[serde_as(as = "Readable<BigInt<u64>, _>")]
+    pub blob_gas_used: u64,
+    #[serde_as(as = "Readable<BigInt<u64>, _>")]
+    pub excess_blob_gas: u64,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientHeader {
+    pub beacon: BeaconBlockHeader,
+    pub execution: ExecutionPayloadHeader,
+    /// Proof of the execution payload header in the beacon block body.
+    #[serde_as(as = "Readable<Vec<Hex>, _>")]
+    pub execution_branch: Vec<Root>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncCommittee {
+    #[serde_as(as = "Readable<Vec<Hex>, Vec<Bytes>>")]
+    pub pubkeys: Vec<BlsPublicKey>,
+    #[serde_as(as = "Readable<Hex, Bytes>")]
+    pub aggregate_pubkey: BlsPublicKey,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncAggregate {
+    /// One bit per sync committee member, little-endian within each byte.
+    #[serde_as(as = "Readable<Hex, Bytes>")]
+    pub sync_committee_bits: [u8; SYNC_COMMITTEE_SIZE / 8],
+    #[serde_as(as = "Readable<Hex, Bytes>")]
+    pub sync_committee_signature: BlsSignature,
+}
+
+impl SyncAggregate {
+    pub fn participants(&self) -> usize {
+        self.sync_committee_bits
+            .iter()
+            .map(|byte| byte.count_ones() as usize)
+            .sum()
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientBootstrap {
+    pub header: LightClientHeader,
+    pub current_sync_committee: SyncCommittee,
+    #[serde_as(as = "Readable<Vec<Hex>, _>")]
+    pub current_sync_committee_branch: Vec<Root>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientUpdate {
+    pub attested_header: LightClientHeader,
+    pub next_sync_committee: SyncCommittee,
+    #[serde_as(as = "Readable<Vec<Hex>, _>")]
+    pub next_sync_committee_branch: Vec<Root>,
+    pub finalized_header: LightClientHeader,
+    #[serde_as(as = "Readable<Vec<Hex>, _>")]
+    pub finality_branch: Vec<Root>,
+    pub sync_aggregate: SyncAggregate,
+    #[serde_as(as = "Readable<BigInt<u64>, _>")]
+    pub signature_slot: u64,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientFinalityUpdate {
+    pub attested_header: LightClientHeader,
+    pub finalized_header: LightClientHeader,
+    #[serde_as(as = "Readable<Vec<Hex>, _>")]
+    pub finality_branch: Vec<Root>,
+    pub sync_aggregate: SyncAggregate,
+    #[serde_as(as = "Readable<BigInt<u64>, _>")]
+    pub signature_slot: u64,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientOptimisticUpdate {
+    pub attested_header: LightClientHeader,
+    pub sync_aggregate: SyncAggregate,
+    #[serde_as(as = "Readable<BigInt<u64>, _>")]
+    pub signature_slot: u64,
+}
+
+/// An update of the light client, as submitted to the dWallet network: either a sync committee
+/// period update or a finality update.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EthUpdate {
+    Update(LightClientUpdate),
+    FinalityUpdate(LightClientFinalityUpdate),
+}
+
+impl EthUpdate {
+    pub fn finalized_header(&self) -> &LightClientHeader {
+        match self {
+            EthUpdate::Update(update) => &update.finalized_header,
+            EthUpdate::FinalityUpdate(update) => &update.finalized_header,
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Genesis {
+    #[serde_as(as = "Readable<BigInt<u64>, _>")]
+    pub genesis_time: u64,
+    #[serde_as(as = "Readable<Hex, _>")]
+    pub genesis_validators_root: Root,
+    #[serde_as(as = "Readable<Hex, _>")]
+    pub genesis_fork_version: [u8; 4],
+}
+
+impl BeaconBlockHeader {
+    pub fn hash_tree_root(&self) -> Root {
+        merkleize(vec![
+            uint64_chunk(self.slot),
+            uint64_chunk(self.proposer_index),
+            self.parent_root,
+            self.state_root,
+            self.body_root,
+        ])
+    }
+}
+
+impl ExecutionPayloadHeader {
+    pub fn hash_tree_root(&self) -> Root {
+        let mut fee_recipient = [0; 32];
+        fee_recipient[..20].copy_from_slice(&self.fee_recipient);
+        let logs_bloom = merkleize(
+            self.logs_bloom
+                .chunks(32)
+                .map(|chunk| chunk.try_into().unwrap())
+                .collect(),
+        );
+        // `extra_data` is a `ByteList[32]`, a single chunk mixed in with its length.
+        let mut extra_data = [0; 32];
+        let length = self.extra_data.len().min(32);
+        extra_data[..length].copy_from_slice(&self.extra_data[..length]);
+        let extra_data = hash_pair(&extra_data, &uint64_chunk(self.extra_data.len() as u64));
+
+        merkleize(vec![
+            self.parent_hash,
+            fee_recipient,
+            self.state_root,
+            self.receipts_root,
+            logs_bloom,
+            self.prev_randao,
+            uint64_chunk(self.block_number),
+            uint64_chunk(self.gas_limit),
+            uint64_chunk(self.gas_used),
+            uint64_chunk(self.timestamp),
+            extra_data,
+            self.base_fee_per_gas.to_le_bytes(),
+            self.block_hash,
+            self.transactions_root,
+            self.withdrawals_root,
+            uint64_chunk(self.blob_gas_used),
+            uint64_chunk(self.excess_blob_gas),
+        ])
+    }
+}
+
+impl LightClientHeader {
+    /// Check that the execution payload header is the one of the beacon block.
+    pub fn verify(&self) -> Result<(), EthLightClientError> {
+        if self.execution.extra_data.len() > 32 {
+            return Err(EthLightClientError::InvalidHeader(
+                "extra data longer than 32 bytes".to_string(),
+            ));
+        }
+        if !is_valid_merkle_branch(
+            &self.execution.hash_tree_root(),
+            &self.execution_branch,
+            EXECUTION_PAYLOAD_GINDEX,
+            &self.beacon.body_root,
+        ) {
+            return Err(EthLightClientError::InvalidHeader(
+                "invalid execution branch".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl SyncCommittee {
+    pub fn hash_tree_root(&self) -> Root {
+        let pubkeys = merkleize(self.pubkeys.iter().map(pubkey_root).collect());
+        hash_pair(&pubkeys, &pubkey_root(&self.aggregate_pubkey))
+    }
+}
+
+/// Verify `bootstrap`, fetched for the block `trusted_block_root` of a trusted checkpoint.
+pub fn verify_bootstrap(
+    fork: ForkName,
+    bootstrap: &LightClientBootstrap,
+    trusted_block_root: &Root,
+) -> Result<(), EthLightClientError> {
+    bootstrap.header.verify()?;
+    if &bootstrap.header.beacon.hash_tree_root() != trusted_block_root {
+        return Err(EthLightClientError::InvalidBootstrap(
+            "the header is not the trusted block".to_string(),
+        ));
+    }
+    if bootstrap.current_sync_committee.pubkeys.len() != SYNC_COMMITTEE_SIZE {
+        return Err(EthLightClientError::InvalidBootstrap(format!(
+            "expected {SYNC_COMMITTEE_SIZE} sync committee members, got {}",
+            bootstrap.current_sync_committee.pubkeys.len()
+        )));
+    }
+    if !is_valid_merkle_branch(
+        &bootstrap.current_sync_committee.hash_tree_root(),
+        &bootstrap.current_sync_committee_branch,
+        fork.current_sync_committee_gindex(),
+        &bootstrap.header.beacon.state_root,
+    ) {
+        return Err(EthLightClientError::InvalidBootstrap(
+            "invalid current sync committee branch".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The state of an Ethereum light client: the latest finalized header and the sync committees
+/// it trusts.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientStore {
+    #[serde_as(as = "Readable<Hex, _>")]
+    pub genesis_validators_root: Root,
+    /// The fork schedule of the network, ordered by epoch.
+    pub forks: Vec<Fork>,
+    pub finalized_header: LightClientHeader,
+    pub current_sync_committee: SyncCommittee,
+    pub next_sync_committee: Option<SyncCommittee>,
+}
+
+impl LightClientStore {
+    /// Bootstrap a light client from the bootstrap of the trusted block `trusted_block_root`.
+    pub fn new(
+        genesis_validators_root: Root,
+        forks: Vec<Fork>,
+        trusted_block_root: &Root,
+        bootstrap: LightClientBootstrap,
+    ) -> Result<Self, EthLightClientError> {
+        if forks
+            .windows(2)
+            .any(|forks| forks[0].epoch >= forks[1].epoch)
+        {
+            return Err(EthLightClientError::InvalidBootstrap(
+                "forks are not ordered by epoch".to_string(),
+            ));
+        }
+        let store = Self {
+            genesis_validators_root,
+            forks,
+            finalized_header: bootstrap.header.clone(),
+            current_sync_committee: bootstrap.current_sync_committee.clone(),
+            next_sync_committee: None,
+        };
+        let fork = store.fork_at(bootstrap.header.beacon.slot)?;
+        verify_bootstrap(fork.name, &bootstrap, trusted_block_root)?;
+        Ok(store)
+    }
+
+    /// The fork active at `slot`.
+    pub fn fork_at(&self, slot: u64) -> Result<&Fork, EthLightClientError> {
+        let epoch = slot / SLOTS_PER_EPOCH;
+        self.forks
+            .iter()
+            .rev()
+            .find(|fork| fork.epoch <= epoch)
+            .ok_or(EthLightClientError::UnknownFork(epoch))
+    }
+
+    /// Verify `update` and apply it, following the light client specification, except that
+    /// updates are only applied once finalized by a supermajority of the sync committee.
+    pub fn apply_update(&mut self, update: &EthUpdate) -> Result<(), EthLightClientError> {
+        match update {
+            EthUpdate::Update(update) => self.apply(
+                &update.attested_header,
+                Some((
+                    &update.next_sync_committee,
+                    &update.next_sync_committee_branch,
+                )),
+                &update.finalized_header,
+                &update.finality_branch,
+                &update.sync_aggregate,
+                update.signature_slot,
+            ),
+            EthUpdate::FinalityUpdate(update) => self.apply(
+                &update.attested_header,
+                None,
+                &update.finalized_header,
+                &update.finality_branch,
+                &update.sync_aggregate,
+                update.signature_slot,
+            ),
+        }
+    }
+
+    fn apply(
+        &mut self,
+        attested_header: &LightClientHeader,
+        next_sync_committee: Option<(&SyncCommittee, &Vec<Root>)>,
+        finalized_header: &LightClientHeader,
+        finality_branch: &[Root],
+        sync_aggregate: &SyncAggregate,
+        signature_slot: u64,
+    ) -> Result<(), EthLightClientError> {
+        let invalid = |reason: &str| Err(EthLightClientError::InvalidUpdate(reason.to_string()));
+
+        if sync_aggregate.participants() * 3 < SYNC_COMMITTEE_SIZE * 2 {
+            return invalid("not signed by a supermajority of the sync committee");
+        }
+        attested_header.verify()?;
+        finalized_header.verify()?;
+        let attested_slot = attested_header.beacon.slot;
+        let finalized_slot = finalized_header.beacon.slot;
+        if signature_slot <= attested_slot || attested_slot < finalized_slot {
+            return invalid("slots out of order");
+        }
+
+        let store_period = sync_committee_period(self.finalized_header.beacon.slot);
+        let signature_period = sync_committee_period(signature_slot);
+        let max_signature_period = match self.next_sync_committee {
+            Some(_) => store_period + 1,
+            None => store_period,
+        };
+        if signature_period < store_period || signature_period > max_signature_period {
+            return invalid("signature period is not known");
+        }
+
+        // Only a next sync committee proven by a finalized state is taken.
+        let attested_period = sync_committee_period(attested_slot);
+        let finalized_period = sync_committee_period(finalized_slot);
+        let next_sync_committee =
+            next_sync_committee.filter(|_| attested_period == finalized_period);
+        let learns_next_sync_committee = self.next_sync_committee.is_none()
+            && next_sync_committee.is_some()
+            && attested_period == store_period;
+        if finalized_slot <= self.finalized_header.beacon.slot && !learns_next_sync_committee {
+            return invalid("not newer than the finalized header");
+        }
+
+        let fork = self.fork_at(attested_slot)?;
+        if !is_valid_merkle_branch(
+            &finalized_header.beacon.hash_tree_root(),
+            finality_branch,
+            fork.name.finalized_root_gindex(),
+            &attested_header.beacon.state_root,
+        ) {
+            return invalid("invalid finality branch");
+        }
+        if let Some((committee, branch)) = next_sync_committee {
+            if attested_period == store_period
+                && self
+                    .next_sync_committee
+                    .as_ref()
+                    .is_some_and(|next| next != committee)
+            {
+                return invalid("conflicting next sync committee");
+            }
+            if committee.pubkeys.len() != SYNC_COMMITTEE_SIZE
+                || !is_valid_merkle_branch(
+                    &committee.hash_tree_root(),
+                    branch,
+                    fork.name.next_sync_committee_gindex(),
+                    &attested_header.beacon.state_root,
+                )
+            {
+                return invalid("invalid next sync committee branch");
+            }
+        }
+
+        let committee = if signature_period == store_period {
+            &self.current_sync_committee
+        } else {
+            self.next_sync_committee.as_ref().expect("checked above")
+        };
+        let fork_version = self.fork_at(signature_slot.saturating_sub(1))?.version;
+        let signing_root = hash_pair(
+            &attested_header.beacon.hash_tree_root(),
+            &compute_domain(fork_version, &self.genesis_validators_root),
+        );
+        if !verify_sync_aggregate(committee, sync_aggregate, &signing_root) {
+            return invalid("invalid sync committee signature");
+        }
+
+        let next_sync_committee = next_sync_committee.map(|(committee, _)| committee.clone());
+        if self.next_sync_committee.is_none() {
+            if finalized_period != store_period {
+                return invalid("the next sync committee must be known to change period");
+            }
+            self.next_sync_committee = next_sync_committee;
+        } else if finalized_period == store_period + 1 {
+            self.current_sync_committee = self.next_sync_committee.take().expect("checked above");
+            self.next_sync_committee = next_sync_committee;
+        }
+        if finalized_slot > self.finalized_header.beacon.slot {
+            self.finalized_header = finalized_header.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Rust version of the Move `dwallet_system::ethereum_light_client::ExecutionHeader` type, what
+/// the light client retains of a finalized execution header.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionHeader {
+    pub block_hash: Vec<u8>,
+    pub state_root: Vec<u8>,
+    pub receipts_root: Vec<u8>,
+    pub timestamp: u64,
+}
+
+/// Rust version of the Move `dwallet_system::ethereum_light_client::EthereumLightClient` type.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EthereumLightClient {
+    pub id: UID,
+    pub chain_id: u64,
+    /// The BCS encoded [LightClientStore].
+    pub store: Vec<u8>,
+    pub finalized_slot: u64,
+    pub latest_block_number: u64,
+    pub execution_headers: Table,
+}
+
+impl EthereumLightClient {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_SYSTEM_ADDRESS,
+            module: ETHEREUM_LIGHT_CLIENT_MODULE_NAME.to_owned(),
+            name: ETHEREUM_LIGHT_CLIENT_STRUCT_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+
+    pub fn is_type(other: &StructTag) -> bool {
+        other.address == SUI_SYSTEM_ADDRESS
+            && other.module.as_ident_str() == ETHEREUM_LIGHT_CLIENT_MODULE_NAME
+            && other.name.as_ident_str() == ETHEREUM_LIGHT_CLIENT_STRUCT_NAME
+    }
+
+    pub fn store(&self) -> Result<LightClientStore, bcs::Error> {
+        bcs::from_bytes(&self.store)
+    }
+}
+
+/// Check that `checkpoint` is one of `known_checkpoints`. The light client can only be safely
+/// bootstrapped from a checkpoint within the weak subjectivity period that is known to be
+/// canonical, and a checkpoint of the same epoch with another root is a sign of an attack.
+pub fn verify_checkpoint(
+    checkpoint: &Checkpoint,
+    known_checkpoints: &[Checkpoint],
+) -> Result<(), EthLightClientError> {
+    match known_checkpoints
+        .iter()
+        .find(|known| known.epoch == checkpoint.epoch)
+    {
+        Some(known) if known.block_root == checkpoint.block_root => Ok(()),
+        Some(known) => Err(EthLightClientError::InvalidCheckpoint(format!(
+            "the block root of epoch {} is 0x{}, not 0x{}",
+            checkpoint.epoch,
+            Hex::encode(known.block_root),
+            Hex::encode(checkpoint.block_root),
+        ))),
+        None => Err(EthLightClientError::InvalidCheckpoint(format!(
+            "epoch {} is not a known checkpoint",
+            checkpoint.epoch
+        ))),
+    }
+}
+
+/// Whether `branch` proves `leaf` at the generalized index `gindex` of the tree with `root`.
+pub fn is_valid_merkle_branch(leaf: &Root, branch: &[Root], gindex: u64, root: &Root) -> bool {
+    let depth = gindex.ilog2() as usize;
+    if branch.len() != depth {
+        return false;
+    }
+    let node = branch
+        .iter()
+        .enumerate()
+        .fold(*leaf, |node, (level, sibling)| {
+            if (gindex >> level) & 1 == 1 {
+                hash_pair(sibling, &node)
+            } else {
+                hash_pair(&node, sibling)
+            }
+        });
+    &node == root
+}
+
+/// The sync committee signing domain of the fork with `fork_version`.
+fn compute_domain(fork_version: [u8; 4], genesis_validators_root: &Root) -> Root {
+    let mut version = [0; 32];
+    version[..4].copy_from_slice(&fork_version);
+    let fork_data_root = hash_pair(&version, genesis_validators_root);
+    let mut domain = [0; 32];
+    domain[..4].copy_from_slice(&DOMAIN_SYNC_COMMITTEE);
+    domain[4..].copy_from_slice(&fork_data_root[..28]);
+    domain
+}
+
+/// Verify the aggregate signature of the participants of `committee` over `signing_root`.
+/// Public keys are not group checked, the beacon chain only admits valid keys in sync committees.
+fn verify_sync_aggregate(
+    committee: &SyncCommittee,
+    sync_aggregate: &SyncAggregate,
+    signing_root: &Root,
+) -> bool {
+    let Ok(pubkeys) = committee
+        .pubkeys
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| sync_aggregate.sync_committee_bits[i / 8] & (1 << (i % 8)) != 0)
+        .map(|(_, pubkey)| blst::min_pk::PublicKey::from_bytes(pubkey))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return false;
+    };
+    let pubkeys: Vec<_> = pubkeys.iter().collect();
+    let Ok(aggregate) = blst::min_pk::AggregatePublicKey::aggregate(&pubkeys, false) else {
+        return false;
+    };
+    let Ok(signature) =
+        blst::min_pk::Signature::from_bytes(&sync_aggregate.sync_committee_signature)
+    else {
+        return false;
+    };
+    signature.verify(
+        true,
+        signing_root,
+        BLS_DST,
+        &[],
+        &aggregate.to_public_key(),
+        false,
+    ) == blst::BLST_ERROR::BLST_SUCCESS
+}
+
+/// The SSZ merkleization of `chunks`, padded with zero chunks to a power of two.
+fn merkleize(mut chunks: Vec<Root>) -> Root {
+    chunks.resize(chunks.len().next_power_of_two(), [0; 32]);
+    while chunks.len() > 1 {
+        chunks = chunks
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], &pair[1]))
+            .collect();
+    }
+    chunks[0]
+}
+
+fn hash_pair(left: &Root, right: &Root) -> Root {
+    let mut hasher = Sha256::default();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().digest
+}
+
+fn uint64_chunk(value: u64) -> Root {
+    let mut chunk = [0; 32];
+    chunk[..8].copy_from_slice(&value.to_le_bytes());
+    chunk
+}
+
+/// The hash tree root of a `Bytes48`, two chunks.
+fn pubkey_root(pubkey: &BlsPublicKey) -> Root {
+    let mut second = [0; 32];
+    second[..16].copy_from_slice(&pubkey[32..]);
+    hash_pair(pubkey[..32].try_into().unwrap(), &second)
+}
+
+#[cfg(test)]
+#[path = "unit_tests/eth_light_client_tests.rs"]
+mod eth_light_client_tests;
@@ -0,0 +1,426 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Inclusion proofs of Ethereum execution layer data against the roots of a verified execution
+//! header.
+//!
+//! The receipts of a block are stored in a Merkle Patricia Trie keyed by the RLP encoded index of
+//! their transaction, of which the receipts root is the root. A receipt is proven by the RLP
+//! encoded trie nodes on the path from the root to it, which anyone holding the receipts of the
+//! block can build. Post-Byzantium receipts only, typed (EIP-2718) or legacy.
+//!
+//! Accounts and their storage are proven the same way, against the state root of the block and
+//! the storage root of the account, by the proofs served by `eth_getProof`.
+
+use fastcrypto::hash::{HashFunction, Keccak256};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::eth_light_client::Root;
+
+/// The deepest nesting of RLP lists decoded. Trie nodes nest at most 2 lists, embedded nodes
+/// included, and receipts 4, down to the topics of their logs.
+const MAX_RLP_DEPTH: usize = 8;
+
+/// The root of an empty trie, such as the storage trie of an account without storage.
+pub const EMPTY_TRIE_ROOT: Root = [
+    0x56, 0xe8, 0x1f, 0x17, 0x1b, 0xcc, 0x55, 0xa6, 0xff, 0x83, 0x45, 0xe6, 0x92, 0xc0, 0xf8, 0x6e,
+    0x5b, 0x48, 0xe0, 0x1b, 0x99, 0x6c, 0xad, 0xc0, 0x01, 0x62, 0x2f, 0xb5, 0xe3, 0x63, 0xb4, 0x21,
+];
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum EthProofError {
+    #[error("Invalid RLP: {0}")]
+    InvalidRlp(String),
+    #[error("Invalid proof: {0}")]
+    InvalidProof(String),
+    #[error("Invalid receipt: {0}")]
+    InvalidReceipt(String),
+    #[error("Log {0} not found in the receipt")]
+    LogNotFound(u64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Log {
+    pub address: [u8; 20],
+    pub topics: Vec<[u8; 32]>,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Receipt {
+    /// The EIP-2718 transaction type, 0 for legacy transactions.
+    pub transaction_type: u8,
+    /// Whether the transaction succeeded.
+    pub status: bool,
+    pub cumulative_gas_used: u64,
+    pub logs: Vec<Log>,
+}
+
+impl Receipt {
+    /// Decode a receipt as encoded in the receipts trie.
+    pub fn decode(bytes: &[u8]) -> Result<Self, EthProofError> {
+        let invalid = |reason: &str| EthProofError::InvalidReceipt(reason.to_string());
+
+        // Legacy receipts are RLP lists, and typed receipts are prefixed by their type.
+        let (transaction_type, payload) = match bytes.split_first() {
+            Some((&transaction_type, payload)) if transaction_type < 0x80 => {
+                (transaction_type, payload)
+            }
+            _ => (0, bytes),
+        };
+        let rlp = Rlp::decode(payload)?;
+        let [status, cumulative_gas_used, _logs_bloom, logs] = rlp.as_list()? else {
+            return Err(invalid("expected 4 fields"));
+        };
+        let status = match status.as_bytes()? {
+            [] => false,
+            [1] => true,
+            _ => return Err(invalid("pre-Byzantium receipts are not supported")),
+        };
+        let logs = logs
+            .as_list()?
+            .iter()
+            .map(|log| {
+                let [address, topics, data] = log.as_list()? else {
+                    return Err(invalid("expected 3 log fields"));
+                };
+                Ok(Log {
+                    address: address.as_array()?,
+                    topics: topics
+                        .as_list()?
+                        .iter()
+                        .map(Rlp::as_array)
+                        .collect::<Result<_, _>>()?,
+                    data: data.as_bytes()?.to_vec(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            transaction_type,
+            status,
+            cumulative_gas_used: cumulative_gas_used.as_u64()?,
+            logs,
+        })
+    }
+
+    pub fn log(&self, log_index: u64) -> Result<&Log, EthProofError> {
+        usize::try_from(log_index)
+            .ok()
+            .and_then(|index| self.logs.get(index))
+            .ok_or(EthProofError::LogNotFound(log_index))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Account {
+    pub nonce: u64,
+    /// The balance in wei, big-endian.
+    pub balance: [u8; 32],
+    pub storage_root: Root,
+    pub code_hash: Root,
+}
+
+impl Account {
+    /// Decode an account as encoded in the state trie.
+    pub fn decode(bytes: &[u8]) -> Result<Self, EthProofError> {
+        let rlp = Rlp::decode(bytes)?;
+        let [nonce, balance, storage_root, code_hash] = rlp.as_list()? else {
+            return Err(invalid_rlp("expected 4 account fields"));
+        };
+        Ok(Self {
+            nonce: nonce.as_u64()?,
+            balance: balance.as_u256()?,
+            storage_root: storage_root.as_array()?,
+            code_hash: code_hash.as_array()?,
+        })
+    }
+}
+
+/// Verify that `proof` proves the receipt of the transaction at `transaction_index` of the block
+/// with receipts root `receipts_root`, returning the receipt.
+pub fn verify_receipt_proof(
+    receipts_root: &Root,
+    transaction_index: u64,
+    proof: &[Vec<u8>],
+) -> Result<Receipt, EthProofError> {
+    let key = rlp_encode_u64(transaction_index);
+    Receipt::decode(&verify_mpt_proof(receipts_root, &key, proof)?)
+}
+
+/// Verify that `proof` proves the account `address`, or its absence, in the state trie of the
+/// block with state root `state_root`.
+pub fn verify_account_proof(
+    state_root: &Root,
+    address: &[u8; 20],
+    proof: &[Vec<u8>],
+) -> Result<Option<Account>, EthProofError> {
+    verify_mpt_lookup(state_root, &keccak256(address), proof)?
+        .map(|account| Account::decode(&account))
+        .transpose()
+}
+
+/// Verify that `proof` proves the value of the storage slot `slot` in the storage trie with root
+/// `storage_root`, returning it big-endian. Slots absent from the trie have value zero.
+pub fn verify_storage_proof(
+    storage_root: &Root,
+    slot: &[u8; 32],
+    proof: &[Vec<u8>],
+) -> Result<[u8; 32], EthProofError> {
+    match verify_mpt_lookup(storage_root, &keccak256(slot), proof)? {
+        Some(value) => Rlp::decode(&value)?.as_u256(),
+        None => Ok([0; 32]),
+    }
+}
+
+/// Verify that `proof`, the nodes on the path from the root to `key`, proves that `key` is in the
+/// Merkle Patricia Trie with root `root`, returning its value.
+pub fn verify_mpt_proof(
+    root: &Root,
+    key: &[u8],
+    proof: &[Vec<u8>],
+) -> Result<Vec<u8>, EthProofError> {
+    verify_mpt_lookup(root, key, proof)?
+        .ok_or_else(|| EthProofError::InvalidProof("key not in the trie".to_string()))
+}
+
+/// Verify that `proof` proves the value of `key` in the Merkle Patricia Trie with root `root`, or
+/// that `key` is not in the trie.
+pub fn verify_mpt_lookup(
+    root: &Root,
+    key: &[u8],
+    proof: &[Vec<u8>],
+) -> Result<Option<Vec<u8>>, EthProofError> {
+    let invalid = |reason: &str| Err(EthProofError::InvalidProof(reason.to_string()));
+
+    if root == &EMPTY_TRIE_ROOT && proof.is_empty() {
+        return Ok(None);
+    }
+
+    let nibbles: Vec<u8> = key.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect();
+    let mut position = 0;
+    let mut proof = proof.iter();
+    let mut next_hash = *root;
+    // Nodes shorter than 32 bytes are embedded in their parent instead of being referenced by hash.
+    let mut embedded = None;
+    loop {
+        let node = match embedded.take() {
+            Some(node) => node,
+            None => {
+                let Some(node) = proof.next() else {
+                    return invalid("missing node");
+                };
+                if keccak256(node) != next_hash {
+                    return invalid("node hash mismatch");
+                }
+                Rlp::decode(node)?
+            }
+        };
+
+        let child = match node.as_list()? {
+            [children @ .., value] if children.len() == 16 => {
+                let Some(&nibble) = nibbles.get(position) else {
+                    let value = value.as_bytes()?;
+                    return Ok((!value.is_empty()).then(|| value.to_vec()));
+                };
+                position += 1;
+                children[nibble as usize].clone()
+            }
+            [path, child] => {
+                let (is_leaf, path) = decode_compact_path(path.as_bytes()?)?;
+                if !nibbles[position..].starts_with(&path) {
+                    return Ok(None);
+                }
+                position += path.len();
+                if is_leaf {
+                    if position != nibbles.len() {
+                        return Ok(None);
+                    }
+                    return Ok(Some(child.as_bytes()?.to_vec()));
+                }
+                child.clone()
+            }
+            _ => return invalid("expected a branch, extension or leaf node"),
+        };
+
+        match child {
+            Rlp::Bytes([]) => return Ok(None),
+            Rlp::Bytes(hash) => {
+                let Ok(hash) = hash.try_into() else {
+                    return invalid("expected a node hash");
+                };
+                next_hash = hash;
+            }
+            list @ Rlp::List(_) => embedded = Some(list),
+        }
+    }
+}
+
+/// The nibbles of a hex-prefix encoded path, and whether it is the path of a leaf.
+fn decode_compact_path(path: &[u8]) -> Result<(bool, Vec<u8>), EthProofError> {
+    let Some((&first, rest)) = path.split_first() else {
+        return Err(EthProofError::InvalidProof("empty path".to_string()));
+    };
+    let flag = first >> 4;
+    if flag > 3 || (flag & 1 == 0 && first & 0x0f != 0) {
+        return Err(EthProofError::InvalidProof(
+            "invalid path prefix".to_string(),
+        ));
+    }
+    let mut nibbles = Vec::with_capacity(2 * rest.len() + 1);
+    if flag & 1 == 1 {
+        nibbles.push(first & 0x0f);
+    }
+    nibbles.extend(rest.iter().flat_map(|b| [b >> 4, b & 0x0f]));
+    Ok((flag >= 2, nibbles))
+}
+
+fn keccak256(bytes: &[u8]) -> Root {
+    Keccak256::digest(bytes).digest
+}
+
+fn rlp_encode_u64(value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let bytes = &bytes[value.leading_zeros() as usize / 8..];
+    match bytes {
+        [byte] if *byte < 0x80 => vec![*byte],
+        _ => [&[0x80 + bytes.len() as u8][..], bytes].concat(),
+    }
+}
+
+/// A decoded RLP item, borrowing the encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Rlp<'a> {
+    Bytes(&'a [u8]),
+    List(Vec<Rlp<'a>>),
+}
+
+impl<'a> Rlp<'a> {
+    fn decode(bytes: &'a [u8]) -> Result<Self, EthProofError> {
+        let (item, rest) = Self::decode_item(bytes, 0)?;
+        if !rest.is_empty() {
+            return Err(invalid_rlp("trailing bytes"));
+        }
+        Ok(item)
+    }
+
+    /// Decode the item at the start of `bytes`, nested in `depth` lists, returning it and the
+    /// bytes following it.
+    fn decode_item(bytes: &'a [u8], depth: usize) -> Result<(Self, &'a [u8]), EthProofError> {
+        let Some((&prefix, rest)) = bytes.split_first() else {
+            return Err(invalid_rlp("unexpected end of input"));
+        };
+        match prefix {
+            0x00..=0x7f => Ok((Rlp::Bytes(&bytes[..1]), rest)),
+            0x80..=0xb7 => {
+                let (payload, rest) = split(rest, (prefix - 0x80) as usize)?;
+                if let [byte] = payload {
+                    if *byte < 0x80 {
+                        return Err(invalid_rlp("non-canonical single byte"));
+                    }
+                }
+                Ok((Rlp::Bytes(payload), rest))
+            }
+            0xb8..=0xbf => {
+                let (length, rest) = decode_long_length(rest, (prefix - 0xb7) as usize)?;
+                let (payload, rest) = split(rest, length)?;
+                Ok((Rlp::Bytes(payload), rest))
+            }
+            0xc0..=0xf7 => {
+                let (payload, rest) = split(rest, (prefix - 0xc0) as usize)?;
+                Ok((Rlp::List(Self::decode_list(payload, depth + 1)?), rest))
+            }
+            0xf8..=0xff => {
+                let (length, rest) = decode_long_length(rest, (prefix - 0xf7) as usize)?;
+                let (payload, rest) = split(rest, length)?;
+                Ok((Rlp::List(Self::decode_list(payload, depth + 1)?), rest))
+            }
+        }
+    }
+
+    /// Decode the items of a list nested in `depth` lists, bounded for adversarial inputs not to
+    /// exhaust the stack.
+    fn decode_list(mut payload: &'a [u8], depth: usize) -> Result<Vec<Self>, EthProofError> {
+        if depth > MAX_RLP_DEPTH {
+            return Err(invalid_rlp("too deeply nested"));
+        }
+        let mut items = vec![];
+        while !payload.is_empty() {
+            let (item, rest) = Self::decode_item(payload, depth)?;
+            items.push(item);
+            payload = rest;
+        }
+        Ok(items)
+    }
+
+    fn as_bytes(&self) -> Result<&'a [u8], EthProofError> {
+        match self {
+            Rlp::Bytes(bytes) => Ok(bytes),
+            Rlp::List(_) => Err(invalid_rlp("expected bytes, got a list")),
+        }
+    }
+
+    fn as_list(&self) -> Result<&[Rlp<'a>], EthProofError> {
+        match self {
+            Rlp::List(items) => Ok(items),
+            Rlp::Bytes(_) => Err(invalid_rlp("expected a list, got bytes")),
+        }
+    }
+
+    fn as_array<const N: usize>(&self) -> Result<[u8; N], EthProofError> {
+        self.as_bytes()?
+            .try_into()
+            .map_err(|_| invalid_rlp(&format!("expected {N} bytes")))
+    }
+
+    /// A big-endian integer of up to 32 bytes, left-padded.
+    fn as_u256(&self) -> Result<[u8; 32], EthProofError> {
+        let bytes = self.as_bytes()?;
+        if bytes.len() > 32 || bytes.first() == Some(&0) {
+            return Err(invalid_rlp("invalid integer"));
+        }
+        let mut value = [0; 32];
+        value[32 - bytes.len()..].copy_from_slice(bytes);
+        Ok(value)
+    }
+
+    fn as_u64(&self) -> Result<u64, EthProofError> {
+        let bytes = self.as_bytes()?;
+        if bytes.len() > 8 || bytes.first() == Some(&0) {
+            return Err(invalid_rlp("invalid integer"));
+        }
+        Ok(bytes.iter().fold(0, |value, &b| value << 8 | b as u64))
+    }
+}
+
+fn invalid_rlp(reason: &str) -> EthProofError {
+    EthProofError::InvalidRlp(reason.to_string())
+}
+
+fn split(bytes: &[u8], length: usize) -> Result<(&[u8], &[u8]), EthProofError> {
+    if bytes.len() < length {
+        return Err(invalid_rlp("unexpected end of input"));
+    }
+    Ok(bytes.split_at(length))
+}
+
+/// Decode the `length_of_length` bytes long length of a long string or list.
+fn decode_long_length(
+    bytes: &[u8],
+    length_of_length: usize,
+) -> Result<(usize, &[u8]), EthProofError> {
+    let (length, rest) = split(bytes, length_of_length)?;
+    if length.first() == Some(&0) || length.len() > 8 {
+        return Err(invalid_rlp("invalid length"));
+    }
+    let length = length.iter().fold(0u64, |value, &b| value << 8 | b as u64);
+    if length < 56 {
+        return Err(invalid_rlp("non-canonical length"));
+    }
+    let length = usize::try_from(length).map_err(|_| invalid_rlp("invalid length"))?;
+    Ok((length, rest))
+}
+
+#[cfg(test)]
+#[path = "unit_tests/eth_proofs_tests.rs"]
+mod eth_proofs_tests;
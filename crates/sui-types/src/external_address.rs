@@ -0,0 +1,320 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Addresses on external chains derived from dWallet public keys.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use bech32::{u5, ToBase32, Variant};
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{HashFunction, Keccak256, Sha256};
+use ripemd::{Digest, Ripemd160};
+use schemars::JsonSchema;
+use secp256k1::{PublicKey, Scalar, Secp256k1};
+use serde::{Deserialize, Serialize};
+use signature_mpc::twopc_mpc_protocols::{public_key_to_sec1_compressed, PublicKeyValue};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ExternalAddressError {
+    #[error("Invalid public key: {0}")]
+    InvalidPublicKey(String),
+    #[error("Address encoding error: {0}")]
+    Encoding(String),
+}
+
+/// Decode the `public_key` field of a `DWallet` object into its compressed SEC1 encoding.
+pub fn dwallet_public_key_to_sec1(public_key: &[u8]) -> Result<Vec<u8>, ExternalAddressError> {
+    let public_key: PublicKeyValue = bcs::from_bytes(public_key)
+        .map_err(|e| ExternalAddressError::InvalidPublicKey(e.to_string()))?;
+    Ok(public_key_to_sec1_compressed(public_key))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BitcoinNetwork {
+    Mainnet,
+    Testnet,
+    Regtest,
+}
+
+impl BitcoinNetwork {
+    pub const ALL: [BitcoinNetwork; 3] = [Self::Mainnet, Self::Testnet, Self::Regtest];
+
+    /// The human readable part of segwit addresses on this network.
+    pub fn hrp(&self) -> &'static str {
+        match self {
+            Self::Mainnet => "bc",
+            Self::Testnet => "tb",
+            Self::Regtest => "bcrt",
+        }
+    }
+}
+
+impl Display for BitcoinNetwork {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mainnet => write!(f, "mainnet"),
+            Self::Testnet => write!(f, "testnet"),
+            Self::Regtest => write!(f, "regtest"),
+        }
+    }
+}
+
+impl FromStr for BitcoinNetwork {
+    type Err = ExternalAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mainnet" | "bitcoin" => Ok(Self::Mainnet),
+            "testnet" | "signet" => Ok(Self::Testnet),
+            "regtest" => Ok(Self::Regtest),
+            _ => Err(ExternalAddressError::Encoding(format!(
+                "unknown bitcoin network [{s}]"
+            ))),
+        }
+    }
+}
+
+/// The Bitcoin addresses that receive funds to a dWallet on a given network.
+///
+/// Only the P2WPKH address is given: ECDSA dWallets cannot produce the Schnorr signatures that
+/// spend a taproot output, see [bitcoin_p2tr_address].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BitcoinAddresses {
+    pub network: BitcoinNetwork,
+    /// Native segwit (BIP-84) address.
+    pub p2wpkh: String,
+}
+
+impl BitcoinAddresses {
+    pub fn new(public_key: &[u8], network: BitcoinNetwork) -> Result<Self, ExternalAddressError> {
+        Ok(Self {
+            network,
+            p2wpkh: bitcoin_p2wpkh_address(public_key, network)?,
+        })
+    }
+}
+
+/// The addresses that receive funds to a dWallet on the external chains it controls, as shown
+/// to its owner for deposits.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalAddresses {
+    /// The EIP-55 checksummed address, on Ethereum and every EVM chain.
+    pub ethereum: String,
+    /// The addresses on each Bitcoin network, see [BitcoinNetwork::ALL].
+    pub bitcoin: Vec<BitcoinAddresses>,
+    /// The account address on the Cosmos Hub, see [cosmos_address] for other Cosmos SDK chains.
+    pub cosmos: String,
+}
+
+impl ExternalAddresses {
+    pub fn new(public_key: &[u8]) -> Result<Self, ExternalAddressError> {
+        Ok(Self {
+            ethereum: ethereum_address(public_key)?,
+            bitcoin: BitcoinNetwork::ALL
+                .into_iter()
+                .map(|network| BitcoinAddresses::new(public_key, network))
+                .collect::<Result<_, _>>()?,
+            cosmos: cosmos_address(public_key, COSMOS_HUB_PREFIX)?,
+        })
+    }
+}
+
+/// The bech32 prefix of account addresses on the Cosmos Hub.
+pub const COSMOS_HUB_PREFIX: &str = "cosmos";
+
+/// An external chain to derive the addresses of a dWallet on, parsed from `eth`, `btc` or
+/// `btc-<network>`, and `cosmos` or `cosmos:<prefix>` for another Cosmos SDK chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExternalChain {
+    Ethereum,
+    Bitcoin(BitcoinNetwork),
+    Cosmos { prefix: String },
+}
+
+impl ExternalChain {
+    /// The chains of [ExternalAddresses].
+    pub fn all() -> Vec<Self> {
+        let mut chains = vec![Self::Ethereum];
+        chains.extend(BitcoinNetwork::ALL.map(Self::Bitcoin));
+        chains.push(Self::Cosmos {
+            prefix: COSMOS_HUB_PREFIX.to_string(),
+        });
+        chains
+    }
+
+    /// The addresses of the compressed SEC1 `public_key` on this chain, by the names of the
+    /// external addresses of dWallets in the client config: `ethereum`, `cosmos` (or
+    /// `cosmos:<prefix>`), and `bitcoin-<network>`.
+    pub fn addresses(
+        &self,
+        public_key: &[u8],
+    ) -> Result<Vec<(String, String)>, ExternalAddressError> {
+        Ok(match self {
+            Self::Ethereum => vec![("ethereum".to_string(), ethereum_address(public_key)?)],
+            Self::Bitcoin(network) => {
+                let addresses = BitcoinAddresses::new(public_key, *network)?;
+                vec![(format!("bitcoin-{network}"), addresses.p2wpkh)]
+            }
+            Self::Cosmos { prefix } => {
+                vec![(self.to_string(), cosmos_address(public_key, prefix)?)]
+            }
+        })
+    }
+
+    /// The name and the address of the compressed SEC1 `public_key` on this chain that `address`
+    /// is, as in [Self::addresses], or `None` if `address` is not derived from `public_key`.
+    /// Addresses are compared regardless of their case, which is only a checksum.
+    pub fn find_address(
+        &self,
+        public_key: &[u8],
+        address: &str,
+    ) -> Result<Option<(String, String)>, ExternalAddressError> {
+        Ok(self
+            .addresses(public_key)?
+            .into_iter()
+            .find(|(_, derived)| derived.eq_ignore_ascii_case(address)))
+    }
+}
+
+impl Display for ExternalChain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ethereum => write!(f, "ethereum"),
+            Self::Bitcoin(network) => write!(f, "bitcoin-{network}"),
+            Self::Cosmos { prefix } if prefix == COSMOS_HUB_PREFIX => write!(f, "cosmos"),
+            Self::Cosmos { prefix } => write!(f, "cosmos:{prefix}"),
+        }
+    }
+}
+
+impl FromStr for ExternalChain {
+    type Err = ExternalAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "eth" | "ethereum" => return Ok(Self::Ethereum),
+            "btc" | "bitcoin" => return Ok(Self::Bitcoin(BitcoinNetwork::Mainnet)),
+            "cosmos" => {
+                return Ok(Self::Cosmos {
+                    prefix: COSMOS_HUB_PREFIX.to_string(),
+                })
+            }
+            _ => {}
+        }
+        if let Some(network) = s
+            .strip_prefix("btc-")
+            .or_else(|| s.strip_prefix("bitcoin-"))
+        {
+            return Ok(Self::Bitcoin(network.parse()?));
+        }
+        match s.strip_prefix("cosmos:") {
+            Some(prefix) if !prefix.is_empty() => Ok(Self::Cosmos {
+                prefix: prefix.to_string(),
+            }),
+            _ => Err(ExternalAddressError::Encoding(format!(
+                "unknown chain [{s}], expected eth, btc, btc-<network> or cosmos:<prefix>"
+            ))),
+        }
+    }
+}
+
+/// The EIP-55 checksummed Ethereum address of a compressed SEC1 `public_key`: the last 20 bytes of
+/// the Keccak-256 hash of its uncompressed encoding.
+pub fn ethereum_address(public_key: &[u8]) -> Result<String, ExternalAddressError> {
+    let public_key = parse_public_key(public_key)?.serialize_uncompressed();
+    let address = Hex::encode(&Keccak256::digest(&public_key[1..]).digest[12..]);
+    let checksum = Keccak256::digest(address.as_bytes()).digest;
+    let address: String = address
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (checksum[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0xf;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+    Ok(format!("0x{address}"))
+}
+
+/// The P2WPKH address of a compressed SEC1 `public_key`.
+pub fn bitcoin_p2wpkh_address(
+    public_key: &[u8],
+    network: BitcoinNetwork,
+) -> Result<String, ExternalAddressError> {
+    let public_key = parse_public_key(public_key)?;
+    segwit_address(network.hrp(), 0, &hash160(&public_key.serialize()))
+}
+
+/// The P2TR address of a compressed SEC1 `public_key` used as the internal key, without a script tree.
+///
+/// Outputs to this address are spent with a Schnorr signature, so it is not given as an address of
+/// ECDSA dWallets.
+pub fn bitcoin_p2tr_address(
+    public_key: &[u8],
+    network: BitcoinNetwork,
+) -> Result<String, ExternalAddressError> {
+    let (internal_key, _) = parse_public_key(public_key)?.x_only_public_key();
+    let tweak = tagged_hash(b"TapTweak", &internal_key.serialize());
+    let tweak = Scalar::from_be_bytes(tweak)
+        .map_err(|e| ExternalAddressError::InvalidPublicKey(e.to_string()))?;
+    let (output_key, _) = internal_key
+        .add_tweak(&Secp256k1::verification_only(), &tweak)
+        .map_err(|e| ExternalAddressError::InvalidPublicKey(e.to_string()))?;
+    segwit_address(network.hrp(), 1, &output_key.serialize())
+}
+
+/// The bech32 account address of a compressed SEC1 `public_key` on the Cosmos SDK chain with the
+/// given bech32 `prefix` (e.g. `cosmos`, `osmo`).
+pub fn cosmos_address(public_key: &[u8], prefix: &str) -> Result<String, ExternalAddressError> {
+    let public_key = parse_public_key(public_key)?;
+    bech32::encode(
+        prefix,
+        hash160(&public_key.serialize()).to_base32(),
+        Variant::Bech32,
+    )
+    .map_err(|e| ExternalAddressError::Encoding(e.to_string()))
+}
+
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(Sha256::digest(data).digest).into()
+}
+
+/// BIP-340 tagged hash.
+pub fn tagged_hash(tag: &[u8], data: &[u8]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag).digest;
+    let mut hasher = Sha256::default();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+    hasher.update(data);
+    hasher.finalize().digest
+}
+
+fn parse_public_key(public_key: &[u8]) -> Result<PublicKey, ExternalAddressError> {
+    PublicKey::from_slice(public_key)
+        .map_err(|e| ExternalAddressError::InvalidPublicKey(e.to_string()))
+}
+
+/// Encode a segwit address: bech32 for witness version 0, bech32m for later versions (BIP-350).
+fn segwit_address(hrp: &str, version: u8, program: &[u8]) -> Result<String, ExternalAddressError> {
+    let variant = if version == 0 {
+        Variant::Bech32
+    } else {
+        Variant::Bech32m
+    };
+    let mut data =
+        vec![u5::try_from_u8(version).map_err(|e| ExternalAddressError::Encoding(e.to_string()))?];
+    data.extend(program.to_base32());
+    bech32::encode(hrp, data, variant).map_err(|e| ExternalAddressError::Encoding(e.to_string()))
+}
+
+#[cfg(test)]
+#[path = "unit_tests/external_address_tests.rs"]
+mod external_address_tests;
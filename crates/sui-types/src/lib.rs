@@ -36,14 +36,18 @@ pub mod committee;
 pub mod crypto;
 pub mod digests;
 pub mod display;
+pub mod dwallet_intent;
 pub mod dynamic_field;
 pub mod effects;
 pub mod epoch_data;
+pub mod eth_light_client;
+pub mod eth_proofs;
 pub mod event;
 pub mod executable_transaction;
 pub mod execution;
 pub mod execution_mode;
 pub mod execution_status;
+pub mod external_address;
 pub mod full_checkpoint_content;
 pub mod gas;
 pub mod gas_coin;
@@ -72,6 +76,7 @@ pub mod signature;
 pub mod storage;
 pub mod sui_serde;
 pub mod sui_system_state;
+pub mod tendermint;
 pub mod transaction;
 pub mod transfer;
 pub mod type_resolver;
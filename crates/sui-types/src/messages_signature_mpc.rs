@@ -17,7 +17,7 @@ use signature_mpc::twopc_mpc_protocols::{Commitment, DecentralizedPartyPresign,
 
 pub use crate::digests::CheckpointContentsDigest;
 pub use crate::digests::CheckpointDigest;
-use crate::base_types::ObjectRef;
+use crate::base_types::{ObjectID, ObjectRef};
 
 pub type InitSignatureMPCProtocolSequenceNumber = u64;
 pub type SignatureMPCRound = u64;
@@ -377,6 +377,71 @@ pub enum InitiateSignatureMPCProtocol {
     },
 }
 
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum SignatureMPCProtocol {
+    DKG,
+    Presign,
+    Sign,
+}
+
+impl Display for SignatureMPCProtocol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignatureMPCProtocol::DKG => write!(f, "dkg"),
+            SignatureMPCProtocol::Presign => write!(f, "presign"),
+            SignatureMPCProtocol::Sign => write!(f, "sign"),
+        }
+    }
+}
+
+/// The sessions of a protocol this node took part in during the epoch.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SignatureMPCSessionsStatus {
+    /// The sessions this node initiated its party of.
+    pub participated: u64,
+    /// The sessions this node submitted the output of.
+    pub completed: u64,
+    pub failed: u64,
+    /// The sessions this node participates in which are neither completed nor failed.
+    pub pending: u64,
+}
+
+/// A session that failed on this node, with the error it failed with.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SignatureMPCFailure {
+    pub timestamp_ms: u64,
+    pub epoch: EpochId,
+    pub protocol: SignatureMPCProtocol,
+    pub session_id: SignatureMPCSessionID,
+    pub error: String,
+}
+
+/// A session this node participates in, which is neither completed nor failed, as listed by its
+/// admin interface.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SignatureMPCSessionInFlight {
+    /// The ID of the session object, which is the `SignatureMPCSessionID` of the session.
+    pub session_id: ObjectID,
+    pub protocol: SignatureMPCProtocol,
+    /// When this node initiated its party of the session, in milliseconds since the Unix epoch.
+    pub started_at_ms: u64,
+}
+
+/// The health of the signature MPC service of a validator, as served by its admin interface.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SignatureMPCStatusSummary {
+    /// Whether the node is configured with its share of the decryption key of the network.
+    pub key_share_present: bool,
+    /// The epoch the service runs in, `None` while it is not running.
+    pub epoch: Option<EpochId>,
+    pub party_id: Option<PartyID>,
+    pub dkg: SignatureMPCSessionsStatus,
+    pub presign: SignatureMPCSessionsStatus,
+    pub sign: SignatureMPCSessionsStatus,
+    /// The most recent failures, oldest first, across epochs.
+    pub recent_failures: Vec<SignatureMPCFailure>,
+}
+
 pub fn config_signature_mpc_secret_for_network_for_testing(number_of_parties: PartyID) -> (DecryptionPublicParameters, HashMap<PartyID, SecretKeyShareSizedNumber>) {
     let t = (((number_of_parties * 2) / 3) + 1) as PartyID;
 
@@ -18,14 +18,14 @@ use crate::{
     SUI_FRAMEWORK_PACKAGE_ID,
 };
 
-#[derive(PartialEq, Eq, Hash)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 enum BuilderArg {
     Object(ObjectID),
     Pure(Vec<u8>),
     ForcedNonUniquePure(usize),
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct ProgrammableTransactionBuilder {
     inputs: IndexMap<BuilderArg, CallArg>,
     commands: Vec<Command>,
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
 use crate::{base_types::{ObjectID, SuiAddress}, id::{ID, UID}, SUI_SYSTEM_ADDRESS};
+use crate::external_address::{dwallet_public_key_to_sec1, ExternalAddressError, ExternalAddresses};
 use move_core_types::{
     ident_str,
     identifier::IdentStr,
@@ -12,9 +13,32 @@ use serde::{Deserialize, Serialize};
 use serde::de::DeserializeOwned;
 
 pub const DWALLET_MODULE_NAME: &IdentStr = ident_str!("dwallet");
+pub const DWALLET_CAP_STRUCT_NAME: &IdentStr = ident_str!("DWalletCap");
 pub const MESSAGE_APPROVAL_STRUCT_NAME: &IdentStr = ident_str!("MessageApproval");
 pub const APPROVE_MESSAGES_FUNC_NAME: &IdentStr = ident_str!("approve_messages");
 pub const SIGN_MESSAGES_FUNC_NAME: &IdentStr = ident_str!("sign_messages");
+pub const APPROVE_ATTESTATIONS_FUNC_NAME: &IdentStr = ident_str!("approve_attestations");
+pub const APPROVALS_HOLDER_STRUCT_NAME: &IdentStr = ident_str!("ApprovalsHolder");
+pub const CREATE_APPROVALS_HOLDER_FUNC_NAME: &IdentStr = ident_str!("create_approvals_holder");
+pub const REMOVE_APPROVALS_HOLDER_FUNC_NAME: &IdentStr = ident_str!("remove_approvals_holder");
+pub const CANCEL_APPROVALS_HOLDER_FUNC_NAME: &IdentStr = ident_str!("cancel_approvals_holder");
+
+pub const DWALLET_POLICY_MODULE_NAME: &IdentStr = ident_str!("dwallet_policy");
+pub const SIGNING_POLICY_STRUCT_NAME: &IdentStr = ident_str!("SigningPolicy");
+pub const CREATE_POLICY_FUNC_NAME: &IdentStr = ident_str!("create_policy");
+pub const UPDATE_POLICY_FUNC_NAME: &IdentStr = ident_str!("update_policy");
+pub const REMOVE_POLICY_FUNC_NAME: &IdentStr = ident_str!("remove_policy");
+pub const SIGNING_POLICY_SET_EVENT_STRUCT_NAME: &IdentStr = ident_str!("SigningPolicySet");
+pub const SIGNING_POLICY_REMOVED_EVENT_STRUCT_NAME: &IdentStr = ident_str!("SigningPolicyRemoved");
+
+pub const DWALLET_BINDING_MODULE_NAME: &IdentStr = ident_str!("dwallet_binding");
+pub const DWALLET_BINDING_STRUCT_NAME: &IdentStr = ident_str!("DWalletBinding");
+pub const CREATE_BINDING_FUNC_NAME: &IdentStr = ident_str!("create_binding");
+pub const DWALLET_BINDING_CREATED_EVENT_STRUCT_NAME: &IdentStr =
+    ident_str!("DWalletBindingCreated");
+
+/// The domain separator of dWallet attestations, see `dwallet_system::dwallet::attestation_message`.
+pub const ATTESTATION_DOMAIN: &[u8] = b"dwallet-attestation:v1";
 
 
 pub const DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME: &IdentStr = ident_str!("dwallet_2pc_mpc_ecdsa_k1");
@@ -36,8 +60,251 @@ pub const CREATE_PRESIGN_OUTPUT_FUNC_NAME: &IdentStr = ident_str!("create_presig
 pub const CREATE_PRESIGN_FUNC_NAME: &IdentStr = ident_str!("create_presign");
 pub const CREATE_SIGN_MESSAGES_FUNC_NAME: &IdentStr = ident_str!("create_sign_messages");
 pub const CREATE_SIGN_OUTPUT_FUNC_NAME: &IdentStr = ident_str!("create_sign_output");
+pub const ENCRYPTION_KEY_STRUCT_NAME: &IdentStr = ident_str!("EncryptionKey");
+pub const ENCRYPTED_USER_SHARE_STRUCT_NAME: &IdentStr = ident_str!("EncryptedUserShare");
+pub const PUBLISH_ENCRYPTION_KEY_FUNC_NAME: &IdentStr = ident_str!("publish_encryption_key");
+pub const SEND_ENCRYPTED_USER_SHARE_FUNC_NAME: &IdentStr = ident_str!("send_encrypted_user_share");
+
+pub const DWALLET_CREATED_EVENT_STRUCT_NAME: &IdentStr = ident_str!("DWalletCreated");
+pub const PRESIGN_SESSION_CREATED_EVENT_STRUCT_NAME: &IdentStr =
+    ident_str!("PresignSessionCreated");
+pub const PRESIGN_CREATED_EVENT_STRUCT_NAME: &IdentStr = ident_str!("PresignCreated");
+pub const SIGN_SESSION_CREATED_EVENT_STRUCT_NAME: &IdentStr = ident_str!("SignSessionCreated");
+pub const SIGN_OUTPUT_CREATED_EVENT_STRUCT_NAME: &IdentStr = ident_str!("SignOutputCreated");
+
+
+/// The message signed by a dWallet for the attestation `payload` of schema `schema`:
+/// `ATTESTATION_DOMAIN || bcs(schema) || payload`, as built by `approve_attestations`.
+/// No chain transaction starts with the domain separator, so the signature of an attestation can
+/// never be replayed as a transaction signature.
+pub fn attestation_message(schema: &[u8], payload: &[u8]) -> Vec<u8> {
+    [
+        ATTESTATION_DOMAIN,
+        &bcs::to_bytes(schema).expect("bytes are serializable"),
+        payload,
+    ]
+    .concat()
+}
+
+// Rust version of the Move sui_system::dwallet::DWalletCap type
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+pub struct DWalletCap {
+    pub id: UID,
+}
+
+impl DWalletCap {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_SYSTEM_ADDRESS,
+            name: DWALLET_CAP_STRUCT_NAME.to_owned(),
+            module: DWALLET_MODULE_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+
+    pub fn is_type(other: &StructTag) -> bool {
+        other.address == SUI_SYSTEM_ADDRESS
+            && other.module.as_ident_str() == DWALLET_MODULE_NAME
+            && other.name.as_ident_str() == DWALLET_CAP_STRUCT_NAME
+    }
+
+    pub fn id(&self) -> &ObjectID {
+        self.id.object_id()
+    }
+}
+
+// Rust version of the Move sui_system::dwallet::MessageApproval type
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+pub struct MessageApproval {
+    pub dwallet_cap_id: ID,
+    pub message: Vec<u8>,
+}
+
+// Rust version of the Move sui_system::dwallet::ApprovalsHolder type
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+pub struct ApprovalsHolder {
+    pub id: UID,
+    pub message_approvals: Vec<MessageApproval>,
+}
+
+impl ApprovalsHolder {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_SYSTEM_ADDRESS,
+            name: APPROVALS_HOLDER_STRUCT_NAME.to_owned(),
+            module: DWALLET_MODULE_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+
+    pub fn is_type(other: &StructTag) -> bool {
+        other.address == SUI_SYSTEM_ADDRESS
+            && other.module.as_ident_str() == DWALLET_MODULE_NAME
+            && other.name.as_ident_str() == APPROVALS_HOLDER_STRUCT_NAME
+    }
+
+    /// Create from BCS bytes
+    pub fn from_bcs_bytes(content: &[u8]) -> Result<Self, bcs::Error> {
+        bcs::from_bytes(content)
+    }
+
+    pub fn id(&self) -> &ObjectID {
+        self.id.object_id()
+    }
+
+    /// The approved messages, in the order they were passed to `approve_messages`, which is the
+    /// order `sign_messages` expects them in. `approve_messages` stores their approvals in
+    /// reverse order.
+    pub fn messages(&self) -> Vec<Vec<u8>> {
+        self.message_approvals
+            .iter()
+            .rev()
+            .map(|approval| approval.message.clone())
+            .collect()
+    }
+}
+
+// Rust version of the Move sui_system::dwallet_policy::SigningPolicy type
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+pub struct SigningPolicy {
+    pub id: UID,
+    pub owner: SuiAddress,
+    pub dwallet_cap: DWalletCap,
+    pub allowlist: Vec<SuiAddress>,
+    pub max_messages: u64,
+    pub window_ms: u64,
+    pub window_start_ms: u64,
+    pub window_messages: u64,
+    pub co_approvers: Vec<SuiAddress>,
+    pub co_approval_threshold: u64,
+    pub requests: Vec<ApprovalRequest>,
+    pub next_request_id: u64,
+}
+
+// Rust version of the Move sui_system::dwallet_policy::ApprovalRequest type
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+pub struct ApprovalRequest {
+    pub id: u64,
+    pub requester: SuiAddress,
+    pub messages: Vec<Vec<u8>>,
+    pub co_approvals: Vec<SuiAddress>,
+}
+
+impl SigningPolicy {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_SYSTEM_ADDRESS,
+            name: SIGNING_POLICY_STRUCT_NAME.to_owned(),
+            module: DWALLET_POLICY_MODULE_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+
+    pub fn is_type(other: &StructTag) -> bool {
+        other.address == SUI_SYSTEM_ADDRESS
+            && other.module.as_ident_str() == DWALLET_POLICY_MODULE_NAME
+            && other.name.as_ident_str() == SIGNING_POLICY_STRUCT_NAME
+    }
+
+    /// Create from BCS bytes
+    pub fn from_bcs_bytes(content: &[u8]) -> Result<Self, bcs::Error> {
+        bcs::from_bytes(content)
+    }
+
+    pub fn id(&self) -> &ObjectID {
+        self.id.object_id()
+    }
+
+    /// Whether `address` may have messages approved under the policy.
+    pub fn is_allowed(&self, address: &SuiAddress) -> bool {
+        *address == self.owner || self.allowlist.contains(address)
+    }
+
+    pub fn rules(&self) -> SigningPolicyRules {
+        SigningPolicyRules {
+            allowlist: self.allowlist.clone(),
+            max_messages: self.max_messages,
+            window_ms: self.window_ms,
+            co_approvers: self.co_approvers.clone(),
+            co_approval_threshold: self.co_approval_threshold,
+        }
+    }
+}
+
+/// The rules of a `SigningPolicy`, as passed to `create_policy` and `update_policy`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema, Eq, PartialEq)]
+pub struct SigningPolicyRules {
+    /// The addresses that may have messages approved, besides the owner of the policy.
+    pub allowlist: Vec<SuiAddress>,
+    /// The messages approved per window of `window_ms` milliseconds at most, `0` for no limit.
+    pub max_messages: u64,
+    pub window_ms: u64,
+    /// The addresses that co-approve the requests, `co_approval_threshold` of which must
+    /// co-approve a request before its messages are approved, `0` for no co-approval.
+    pub co_approvers: Vec<SuiAddress>,
+    pub co_approval_threshold: u64,
+}
 
+// Rust version of the Move sui_system::dwallet_policy::SigningPolicySet event
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+pub struct SigningPolicySetEvent {
+    pub policy_id: ID,
+    pub dwallet_cap_id: ID,
+    pub owner: SuiAddress,
+}
 
+// Rust version of the Move sui_system::dwallet_policy::SigningPolicyRemoved event
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+pub struct SigningPolicyRemovedEvent {
+    pub policy_id: ID,
+    pub dwallet_cap_id: ID,
+}
+
+// Rust version of the Move sui_system::dwallet_binding::DWalletBinding type
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+pub struct DWalletBinding {
+    pub id: UID,
+    pub dwallet_id: ID,
+    pub dwallet_cap_id: ID,
+    pub chain: Vec<u8>,
+    pub external_address: Vec<u8>,
+}
+
+impl DWalletBinding {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_SYSTEM_ADDRESS,
+            name: DWALLET_BINDING_STRUCT_NAME.to_owned(),
+            module: DWALLET_BINDING_MODULE_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+
+    pub fn is_type(other: &StructTag) -> bool {
+        other.address == SUI_SYSTEM_ADDRESS
+            && other.module.as_ident_str() == DWALLET_BINDING_MODULE_NAME
+            && other.name.as_ident_str() == DWALLET_BINDING_STRUCT_NAME
+    }
+
+    /// Create from BCS bytes
+    pub fn from_bcs_bytes(content: &[u8]) -> Result<Self, bcs::Error> {
+        bcs::from_bytes(content)
+    }
+
+    pub fn id(&self) -> &ObjectID {
+        self.id.object_id()
+    }
+}
+
+// Rust version of the Move sui_system::dwallet_binding::DWalletBindingCreated event
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+pub struct DWalletBindingCreatedEvent {
+    pub binding_id: ID,
+    pub dwallet_id: ID,
+    pub chain: Vec<u8>,
+    pub external_address: Vec<u8>,
+    pub sender: SuiAddress,
+}
 
 // Rust version of the Move sui_system::dwallet::SignSession type
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
@@ -159,6 +426,16 @@ impl DWallet {
         self.id.object_id()
     }
 
+    /// The compressed SEC1 encoding of the public key of the dWallet.
+    pub fn public_key_sec1(&self) -> Result<Vec<u8>, ExternalAddressError> {
+        dwallet_public_key_to_sec1(&self.public_key)
+    }
+
+    /// The addresses of the dWallet on the external chains it controls.
+    pub fn external_addresses(&self) -> Result<ExternalAddresses, ExternalAddressError> {
+        ExternalAddresses::new(&self.public_key_sec1()?)
+    }
+
     pub fn to_bcs_bytes(&self) -> Vec<u8> {
         bcs::to_bytes(&self).unwrap()
     }
@@ -366,6 +643,75 @@ impl Presign {
     }
 }
 
+// Rust version of the Move sui_system::dwallet_2pc_mpc_ecdsa_k1::EncryptionKey type
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+pub struct EncryptionKey {
+    pub id: UID,
+    pub encryption_key: Vec<u8>,
+}
+
+impl EncryptionKey {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_SYSTEM_ADDRESS,
+            name: ENCRYPTION_KEY_STRUCT_NAME.to_owned(),
+            module: DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+
+    pub fn is_type(other: &StructTag) -> bool {
+        other.address == SUI_SYSTEM_ADDRESS
+            && other.module.as_ident_str() == DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME
+            && other.name.as_ident_str() == ENCRYPTION_KEY_STRUCT_NAME
+    }
+
+    /// Create from BCS bytes
+    pub fn from_bcs_bytes(content: &[u8]) -> Result<Self, bcs::Error> {
+        bcs::from_bytes(content)
+    }
+
+    pub fn id(&self) -> &ObjectID {
+        self.id.object_id()
+    }
+}
+
+// Rust version of the Move sui_system::dwallet_2pc_mpc_ecdsa_k1::EncryptedUserShare type
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+pub struct EncryptedUserShare {
+    pub id: UID,
+    pub dwallet_id: ID,
+    pub encryption_key_id: ID,
+    pub encrypted_user_share: Vec<u8>,
+    pub sender: SuiAddress,
+}
+
+impl EncryptedUserShare {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_SYSTEM_ADDRESS,
+            name: ENCRYPTED_USER_SHARE_STRUCT_NAME.to_owned(),
+            module: DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+
+    pub fn is_type(other: &StructTag) -> bool {
+        other.address == SUI_SYSTEM_ADDRESS
+            && other.module.as_ident_str() == DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME
+            && other.name.as_ident_str() == ENCRYPTED_USER_SHARE_STRUCT_NAME
+    }
+
+    /// Create from BCS bytes
+    pub fn from_bcs_bytes(content: &[u8]) -> Result<Self, bcs::Error> {
+        bcs::from_bytes(content)
+    }
+
+    pub fn id(&self) -> &ObjectID {
+        self.id.object_id()
+    }
+}
+
 // Rust version of the Move sui_system::dwallet_2pc_mpc_ecdsa_k1::SignData type
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
 pub struct SignData {
@@ -405,4 +751,183 @@ impl SignData {
     pub fn to_bcs_bytes(&self) -> Vec<u8> {
         bcs::to_bytes(&self).unwrap()
     }
-}
\ No newline at end of file
+}
+
+// Rust version of the Move sui_system::dwallet_2pc_mpc_ecdsa_k1::DWalletCreated event
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+pub struct DWalletCreatedEvent {
+    pub dwallet_id: ID,
+    pub dwallet_cap_id: ID,
+    pub sender: SuiAddress,
+}
+
+// Rust version of the Move sui_system::dwallet_2pc_mpc_ecdsa_k1::PresignSessionCreated event
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+pub struct PresignSessionCreatedEvent {
+    pub session_id: ID,
+    pub dwallet_id: ID,
+    pub dwallet_cap_id: ID,
+    pub sender: SuiAddress,
+}
+
+// Rust version of the Move sui_system::dwallet_2pc_mpc_ecdsa_k1::PresignCreated event
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+pub struct PresignCreatedEvent {
+    pub session_id: ID,
+    pub presign_id: ID,
+    pub dwallet_id: ID,
+    pub dwallet_cap_id: ID,
+    pub sender: SuiAddress,
+}
+
+// Rust version of the Move sui_system::dwallet::SignSessionCreated event
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+pub struct SignSessionCreatedEvent {
+    pub session_id: ID,
+    pub dwallet_id: ID,
+    pub dwallet_cap_id: ID,
+    pub messages: Vec<Vec<u8>>,
+    pub sender: SuiAddress,
+}
+
+// Rust version of the Move sui_system::dwallet::SignOutputCreated event
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+pub struct SignOutputCreatedEvent {
+    pub session_id: ID,
+    pub sign_output_id: ID,
+    pub dwallet_id: ID,
+    pub dwallet_cap_id: ID,
+    pub signatures: Vec<Vec<u8>>,
+    pub sender: SuiAddress,
+}
+
+/// An event of the lifecycle of a dWallet: its creation, and the presign and sign sessions of its
+/// owner.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+pub enum DWalletEvent {
+    DWalletCreated(DWalletCreatedEvent),
+    PresignSessionCreated(PresignSessionCreatedEvent),
+    PresignCreated(PresignCreatedEvent),
+    SignSessionCreated(SignSessionCreatedEvent),
+    SignOutputCreated(SignOutputCreatedEvent),
+}
+
+impl DWalletEvent {
+    /// Parse the BCS `contents` of an event of type `type_`, if it is a dWallet event.
+    pub fn try_from_event(type_: &StructTag, contents: &[u8]) -> Option<Result<Self, bcs::Error>> {
+        if type_.address != SUI_SYSTEM_ADDRESS || !type_.type_params.is_empty() {
+            return None;
+        }
+        let module = type_.module.as_ident_str();
+        let name = type_.name.as_ident_str();
+        let event = if module == DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME {
+            if name == DWALLET_CREATED_EVENT_STRUCT_NAME {
+                bcs::from_bytes(contents).map(Self::DWalletCreated)
+            } else if name == PRESIGN_SESSION_CREATED_EVENT_STRUCT_NAME {
+                bcs::from_bytes(contents).map(Self::PresignSessionCreated)
+            } else if name == PRESIGN_CREATED_EVENT_STRUCT_NAME {
+                bcs::from_bytes(contents).map(Self::PresignCreated)
+            } else {
+                return None;
+            }
+        } else if module == DWALLET_MODULE_NAME {
+            if name == SIGN_SESSION_CREATED_EVENT_STRUCT_NAME {
+                bcs::from_bytes(contents).map(Self::SignSessionCreated)
+            } else if name == SIGN_OUTPUT_CREATED_EVENT_STRUCT_NAME {
+                bcs::from_bytes(contents).map(Self::SignOutputCreated)
+            } else {
+                return None;
+            }
+        } else {
+            return None;
+        };
+        Some(event)
+    }
+
+    pub fn dwallet_id(&self) -> &ObjectID {
+        match self {
+            Self::DWalletCreated(event) => &event.dwallet_id.bytes,
+            Self::PresignSessionCreated(event) => &event.dwallet_id.bytes,
+            Self::PresignCreated(event) => &event.dwallet_id.bytes,
+            Self::SignSessionCreated(event) => &event.dwallet_id.bytes,
+            Self::SignOutputCreated(event) => &event.dwallet_id.bytes,
+        }
+    }
+
+    /// The address whose session, or dWallet, the event is of.
+    pub fn sender(&self) -> &SuiAddress {
+        match self {
+            Self::DWalletCreated(event) => &event.sender,
+            Self::PresignSessionCreated(event) => &event.sender,
+            Self::PresignCreated(event) => &event.sender,
+            Self::SignSessionCreated(event) => &event.sender,
+            Self::SignOutputCreated(event) => &event.sender,
+        }
+    }
+
+    /// The presign or sign session the event is of, if any.
+    pub fn session_id(&self) -> Option<&ObjectID> {
+        match self {
+            Self::DWalletCreated(_) => None,
+            Self::PresignSessionCreated(event) => Some(&event.session_id.bytes),
+            Self::PresignCreated(event) => Some(&event.session_id.bytes),
+            Self::SignSessionCreated(event) => Some(&event.session_id.bytes),
+            Self::SignOutputCreated(event) => Some(&event.session_id.bytes),
+        }
+    }
+
+    /// The kind of the event, by its Move event type.
+    pub fn kind(&self) -> DWalletEventKind {
+        match self {
+            Self::DWalletCreated(_) => DWalletEventKind::DWalletCreated,
+            Self::PresignSessionCreated(_) => DWalletEventKind::PresignSessionCreated,
+            Self::PresignCreated(_) => DWalletEventKind::PresignCreated,
+            Self::SignSessionCreated(_) => DWalletEventKind::SignSessionCreated,
+            Self::SignOutputCreated(_) => DWalletEventKind::SignOutputCreated,
+        }
+    }
+}
+
+/// The kind of a [DWalletEvent], by the Move event struct it is parsed from.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, JsonSchema, Eq, PartialEq, Hash)]
+pub enum DWalletEventKind {
+    DWalletCreated,
+    PresignSessionCreated,
+    PresignCreated,
+    SignSessionCreated,
+    SignOutputCreated,
+}
+
+impl DWalletEventKind {
+    /// The type of the Move events of this kind.
+    pub fn event_type(&self) -> StructTag {
+        let (module, name) = match self {
+            Self::DWalletCreated => (
+                DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+                DWALLET_CREATED_EVENT_STRUCT_NAME,
+            ),
+            Self::PresignSessionCreated => (
+                DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+                PRESIGN_SESSION_CREATED_EVENT_STRUCT_NAME,
+            ),
+            Self::PresignCreated => (
+                DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+                PRESIGN_CREATED_EVENT_STRUCT_NAME,
+            ),
+            Self::SignSessionCreated => {
+                (DWALLET_MODULE_NAME, SIGN_SESSION_CREATED_EVENT_STRUCT_NAME)
+            }
+            Self::SignOutputCreated => (DWALLET_MODULE_NAME, SIGN_OUTPUT_CREATED_EVENT_STRUCT_NAME),
+        };
+        StructTag {
+            address: SUI_SYSTEM_ADDRESS,
+            module: module.to_owned(),
+            name: name.to_owned(),
+            type_params: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "unit_tests/signature_mpc_tests.rs"]
+mod signature_mpc_tests;
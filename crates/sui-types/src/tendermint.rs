@@ -0,0 +1,592 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Verification of Tendermint light blocks and of ICS-23 (IAVL) membership proofs against the
+//! app hash of a verified header, used by the `tendermint_light_client` Move natives.
+//!
+//! Light blocks are submitted BCS encoded; the Tendermint protobuf encodings that headers,
+//! validator sets and votes are hashed and signed over are reproduced here.
+
+use fastcrypto::ed25519::{Ed25519PublicKey, Ed25519Signature};
+use fastcrypto::hash::{HashFunction, Sha256};
+use fastcrypto::traits::{ToFromBytes, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TendermintError {
+    #[error("Invalid header: {0}")]
+    InvalidHeader(String),
+    #[error("Invalid commit: {0}")]
+    InvalidCommit(String),
+    #[error("Invalid validator set: {0}")]
+    InvalidValidatorSet(String),
+    #[error("Insufficient voting power: {signed} of {total}")]
+    InsufficientVotingPower { signed: u64, total: u64 },
+    #[error("Invalid proof: {0}")]
+    InvalidProof(String),
+}
+
+/// `SignedMsgType::Precommit`.
+const PRECOMMIT_TYPE: u64 = 2;
+/// `MaxTotalVotingPower` of Tendermint, the most voting power of a validator set.
+pub const MAX_TOTAL_VOTING_POWER: u64 = i64::MAX as u64 / 8;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timestamp {
+    pub seconds: i64,
+    pub nanos: i32,
+}
+
+impl Timestamp {
+    /// The milliseconds since the Unix epoch, for a timestamp after it.
+    pub fn unix_ms(&self) -> Result<u64, TendermintError> {
+        let invalid = || {
+            TendermintError::InvalidHeader(format!(
+                "invalid timestamp of {}s and {}ns",
+                self.seconds, self.nanos
+            ))
+        };
+        let seconds = u64::try_from(self.seconds).map_err(|_| invalid())?;
+        let nanos = u64::try_from(self.nanos)
+            .ok()
+            .filter(|nanos| *nanos < 1_000_000_000)
+            .ok_or_else(invalid)?;
+        seconds
+            .checked_mul(1000)
+            .and_then(|ms| ms.checked_add(nanos / 1_000_000))
+            .ok_or_else(invalid)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartSetHeader {
+    pub total: u32,
+    pub hash: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockId {
+    pub hash: Vec<u8>,
+    pub part_set_header: PartSetHeader,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Header {
+    pub version_block: u64,
+    pub version_app: u64,
+    pub chain_id: String,
+    pub height: u64,
+    pub time: Timestamp,
+    pub last_block_id: BlockId,
+    pub last_commit_hash: Vec<u8>,
+    pub data_hash: Vec<u8>,
+    pub validators_hash: Vec<u8>,
+    pub next_validators_hash: Vec<u8>,
+    pub consensus_hash: Vec<u8>,
+    pub app_hash: Vec<u8>,
+    pub last_results_hash: Vec<u8>,
+    pub evidence_hash: Vec<u8>,
+    pub proposer_address: Vec<u8>,
+}
+
+impl Header {
+    /// The block hash, the merkle root of the protobuf encoded header fields.
+    pub fn hash(&self) -> Vec<u8> {
+        let mut version = vec![];
+        encode_varint_field(&mut version, 1, self.version_block);
+        encode_varint_field(&mut version, 2, self.version_app);
+        merkle_root(&[
+            version,
+            wrap_bytes(self.chain_id.as_bytes()),
+            wrap_varint(self.height),
+            encode_timestamp(&self.time),
+            encode_block_id(&self.last_block_id),
+            wrap_bytes(&self.last_commit_hash),
+            wrap_bytes(&self.data_hash),
+            wrap_bytes(&self.validators_hash),
+            wrap_bytes(&self.next_validators_hash),
+            wrap_bytes(&self.consensus_hash),
+            wrap_bytes(&self.app_hash),
+            wrap_bytes(&self.last_results_hash),
+            wrap_bytes(&self.evidence_hash),
+            wrap_bytes(&self.proposer_address),
+        ])
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlockIdFlag {
+    Absent,
+    Commit,
+    Nil,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitSig {
+    pub block_id_flag: BlockIdFlag,
+    pub validator_address: Vec<u8>,
+    pub timestamp: Timestamp,
+    pub signature: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Commit {
+    pub height: u64,
+    pub round: u32,
+    pub block_id: BlockId,
+    pub signatures: Vec<CommitSig>,
+}
+
+impl Commit {
+    /// The bytes signed by the validator of `signature`, a length delimited `CanonicalVote`.
+    pub fn vote_sign_bytes(&self, chain_id: &str, signature: &CommitSig) -> Vec<u8> {
+        let mut vote = vec![];
+        encode_varint_field(&mut vote, 1, PRECOMMIT_TYPE);
+        encode_sfixed64_field(&mut vote, 2, self.height);
+        encode_sfixed64_field(&mut vote, 3, self.round as u64);
+        if signature.block_id_flag == BlockIdFlag::Commit {
+            encode_message_field(&mut vote, 4, &encode_block_id(&self.block_id));
+        }
+        encode_message_field(&mut vote, 5, &encode_timestamp(&signature.timestamp));
+        encode_bytes_field(&mut vote, 6, chain_id.as_bytes());
+        let mut sign_bytes = vec![];
+        encode_varint(&mut sign_bytes, vote.len() as u64);
+        sign_bytes.extend(vote);
+        sign_bytes
+    }
+}
+
+/// An Ed25519 validator.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Validator {
+    pub pub_key: Vec<u8>,
+    pub voting_power: u64,
+}
+
+impl Validator {
+    pub fn address(&self) -> Vec<u8> {
+        Sha256::digest(&self.pub_key).digest[..20].to_vec()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorSet {
+    pub validators: Vec<Validator>,
+}
+
+impl ValidatorSet {
+    pub fn hash(&self) -> Vec<u8> {
+        let leaves: Vec<Vec<u8>> = self
+            .validators
+            .iter()
+            .map(|validator| {
+                let mut pub_key = vec![];
+                encode_bytes_field(&mut pub_key, 1, &validator.pub_key);
+                let mut simple_validator = vec![];
+                encode_bytes_field(&mut simple_validator, 1, &pub_key);
+                encode_varint_field(&mut simple_validator, 2, validator.voting_power);
+                simple_validator
+            })
+            .collect();
+        merkle_root(&leaves)
+    }
+
+    /// The voting power of the validators, at most [MAX_TOTAL_VOTING_POWER].
+    pub fn total_voting_power(&self) -> Result<u64, TendermintError> {
+        self.validators
+            .iter()
+            .try_fold(0u64, |total, v| total.checked_add(v.voting_power))
+            .filter(|total| *total <= MAX_TOTAL_VOTING_POWER)
+            .ok_or_else(|| {
+                TendermintError::InvalidValidatorSet(format!(
+                    "total voting power exceeds {MAX_TOTAL_VOTING_POWER}"
+                ))
+            })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightBlock {
+    pub header: Header,
+    pub commit: Commit,
+    pub validators: ValidatorSet,
+}
+
+/// Verify `light_block` of `chain_id`, given the validator set trusted to produce the block after
+/// the trusted height, i.e. the next validator set of the trusted header.
+///
+/// An adjacent block must be produced by the trusted validators. For a non-adjacent block, more
+/// than 1/3 of the trusted voting power must have signed it. In both cases, more than 2/3 of the
+/// block's own validator set must have signed it.
+pub fn verify_light_block(
+    chain_id: &str,
+    trusted_height: u64,
+    trusted_validators: &ValidatorSet,
+    light_block: &LightBlock,
+) -> Result<(), TendermintError> {
+    let LightBlock {
+        header,
+        commit,
+        validators,
+    } = light_block;
+    if header.chain_id != chain_id {
+        return Err(TendermintError::InvalidHeader(format!(
+            "chain id {} does not match {chain_id}",
+            header.chain_id
+        )));
+    }
+    header.time.unix_ms()?;
+    if header.height <= trusted_height {
+        return Err(TendermintError::InvalidHeader(format!(
+            "height {} is not above the trusted height {trusted_height}",
+            header.height
+        )));
+    }
+    if header.validators_hash != validators.hash() {
+        return Err(TendermintError::InvalidHeader(
+            "validators do not match the header".to_string(),
+        ));
+    }
+    if commit.height != header.height || commit.block_id.hash != header.hash() {
+        return Err(TendermintError::InvalidCommit(
+            "commit is not for the header".to_string(),
+        ));
+    }
+
+    if header.height == trusted_height + 1 {
+        if header.validators_hash != trusted_validators.hash() {
+            return Err(TendermintError::InvalidHeader(
+                "validators of an adjacent header must be the trusted validators".to_string(),
+            ));
+        }
+    } else {
+        let signed = signed_voting_power(chain_id, commit, trusted_validators, false)?;
+        let total = trusted_validators.total_voting_power()?;
+        if signed as u128 * 3 <= total as u128 {
+            return Err(TendermintError::InsufficientVotingPower { signed, total });
+        }
+    }
+
+    let signed = signed_voting_power(chain_id, commit, validators, true)?;
+    let total = validators.total_voting_power()?;
+    if signed as u128 * 3 <= total as u128 * 2 {
+        return Err(TendermintError::InsufficientVotingPower { signed, total });
+    }
+    Ok(())
+}
+
+/// The voting power of `validators` that signed `commit`. If `by_index`, the signatures are in the
+/// order of the validator set, as they are for the validators of the committed block.
+fn signed_voting_power(
+    chain_id: &str,
+    commit: &Commit,
+    validators: &ValidatorSet,
+    by_index: bool,
+) -> Result<u64, TendermintError> {
+    if by_index && commit.signatures.len() != validators.validators.len() {
+        return Err(TendermintError::InvalidCommit(
+            "number of signatures does not match the validator set".to_string(),
+        ));
+    }
+    let mut seen = HashSet::new();
+    let mut signed = 0u64;
+    for (index, signature) in commit.signatures.iter().enumerate() {
+        if signature.block_id_flag != BlockIdFlag::Commit {
+            continue;
+        }
+        let validator = if by_index {
+            &validators.validators[index]
+        } else {
+            match validators
+                .validators
+                .iter()
+                .find(|validator| validator.address() == signature.validator_address)
+            {
+                Some(validator) => validator,
+                None => continue,
+            }
+        };
+        if !seen.insert(validator.pub_key.clone()) {
+            return Err(TendermintError::InvalidCommit(
+                "duplicate signature".to_string(),
+            ));
+        }
+        let pub_key = Ed25519PublicKey::from_bytes(&validator.pub_key)
+            .map_err(|e| TendermintError::InvalidCommit(e.to_string()))?;
+        let sig = Ed25519Signature::from_bytes(&signature.signature)
+            .map_err(|e| TendermintError::InvalidCommit(e.to_string()))?;
+        pub_key
+            .verify(&commit.vote_sign_bytes(chain_id, signature), &sig)
+            .map_err(|_| TendermintError::InvalidCommit("invalid signature".to_string()))?;
+        signed = signed.saturating_add(validator.voting_power);
+    }
+    Ok(signed)
+}
+
+/// An ICS-23 existence proof, restricted to the hash operations shared by the IAVL and Tendermint
+/// proof specs: leaves are the SHA256 of the prefix, the length prefixed key and the length
+/// prefixed SHA256 of the value, inner nodes the SHA256 of the prefix, the child and the suffix.
+/// The proof carries no hash operations, so that no others can be proven.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExistenceProof {
+    pub key: Vec<u8>,
+    pub value: Vec<u8>,
+    pub leaf_prefix: Vec<u8>,
+    pub path: Vec<InnerOp>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InnerOp {
+    pub prefix: Vec<u8>,
+    pub suffix: Vec<u8>,
+}
+
+/// The ICS-23 `ProofSpec` of a tree, beyond the hash operations, which are those of every spec
+/// supported. Trees are binary, and leaf prefixes start with 0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProofSpec {
+    /// Whether node prefixes start with the height, size and version of the node, as IAVL ones.
+    pub iavl: bool,
+    /// The size of the sibling of a child in an inner node, with its length prefix if any.
+    pub child_size: usize,
+    pub min_prefix_length: usize,
+    pub max_prefix_length: usize,
+    pub min_depth: usize,
+    pub max_depth: usize,
+}
+
+/// The spec of the IAVL trees of Cosmos SDK module stores. Node heights are an `int8`.
+pub const IAVL_SPEC: ProofSpec = ProofSpec {
+    iavl: true,
+    child_size: 33,
+    min_prefix_length: 4,
+    max_prefix_length: 12,
+    min_depth: 0,
+    max_depth: i8::MAX as usize,
+};
+
+/// The spec of the simple Merkle trees of Tendermint, such as the Cosmos SDK multistore.
+pub const TENDERMINT_SPEC: ProofSpec = ProofSpec {
+    iavl: false,
+    child_size: 32,
+    min_prefix_length: 1,
+    max_prefix_length: 1,
+    min_depth: 0,
+    max_depth: 64,
+};
+
+impl ExistenceProof {
+    /// The root of the tree of spec `spec` proven to hold the value of the proof under its key.
+    pub fn calculate_root(&self, spec: &ProofSpec) -> Result<Vec<u8>, TendermintError> {
+        self.check_against_spec(spec)?;
+        let mut leaf = self.leaf_prefix.clone();
+        encode_varint(&mut leaf, self.key.len() as u64);
+        leaf.extend(&self.key);
+        encode_varint(&mut leaf, 32);
+        leaf.extend(Sha256::digest(&self.value).digest);
+        let mut hash = Sha256::digest(&leaf).digest.to_vec();
+        for inner in &self.path {
+            let mut node = inner.prefix.clone();
+            node.extend(&hash);
+            node.extend(&inner.suffix);
+            hash = Sha256::digest(&node).digest.to_vec();
+        }
+        Ok(hash)
+    }
+
+    fn check_against_spec(&self, spec: &ProofSpec) -> Result<(), TendermintError> {
+        let invalid = |reason: &str| Err(TendermintError::InvalidProof(reason.to_string()));
+
+        // Leaf and inner nodes are domain separated by the first byte of their prefix.
+        if self.leaf_prefix.first() != Some(&0) {
+            return invalid("leaf prefix must start with 0");
+        }
+        if spec.iavl {
+            let Some((height, size, _, rest)) = read_iavl_header(&self.leaf_prefix) else {
+                return invalid("invalid IAVL leaf prefix");
+            };
+            if height != 0 || size != 1 || !rest.is_empty() {
+                return invalid("invalid IAVL leaf prefix");
+            }
+        }
+        if self.path.len() < spec.min_depth || self.path.len() > spec.max_depth {
+            return invalid("proof depth out of the spec bounds");
+        }
+
+        let max_prefix_length = spec.max_prefix_length + spec.child_size;
+        for (layer, inner) in self.path.iter().enumerate() {
+            if inner.prefix.first().map_or(true, |b| *b == 0) {
+                return invalid("inner prefix must start with a non-zero byte");
+            }
+            if inner.prefix.len() < spec.min_prefix_length || inner.prefix.len() > max_prefix_length
+            {
+                return invalid("inner prefix length out of the spec bounds");
+            }
+            if inner.suffix.len() % spec.child_size != 0 || inner.suffix.len() > spec.child_size {
+                return invalid("inner suffix length out of the spec bounds");
+            }
+            if spec.iavl {
+                // The height of a node is above those of its children.
+                let Some((height, size, _, _)) = read_iavl_header(&inner.prefix) else {
+                    return invalid("invalid IAVL inner prefix");
+                };
+                if height <= layer as i64 || size < 2 {
+                    return invalid("invalid IAVL inner prefix");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The height, size and version an IAVL node prefix starts with, and the rest of the prefix.
+fn read_iavl_header(prefix: &[u8]) -> Option<(i64, i64, i64, &[u8])> {
+    let (height, rest) = read_signed_varint(prefix)?;
+    let (size, rest) = read_signed_varint(rest)?;
+    let (version, rest) = read_signed_varint(rest)?;
+    (version >= 0).then_some((height, size, version, rest))
+}
+
+/// A zigzag encoded varint, as Go's `binary.Varint`, and the bytes after it.
+fn read_signed_varint(bytes: &[u8]) -> Option<(i64, &[u8])> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().enumerate().take(10) {
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            let value = ((value >> 1) as i64) ^ -((value & 1) as i64);
+            return Some((value, &bytes[i + 1..]));
+        }
+    }
+    None
+}
+
+/// Verify that `value` is stored under `path` in the state committed to by `app_hash`.
+///
+/// `proofs` go from the innermost store to the root: for a Cosmos SDK chain, the IAVL proof of the
+/// key in its module store, then the proof of the module store root in the multistore, with
+/// `path` being `[store_name, key]`. The first proof is checked against the IAVL spec, the others
+/// against the Tendermint spec.
+pub fn verify_membership(
+    app_hash: &[u8],
+    proofs: &[ExistenceProof],
+    path: &[Vec<u8>],
+    value: &[u8],
+) -> Result<(), TendermintError> {
+    if proofs.is_empty() || proofs.len() != path.len() {
+        return Err(TendermintError::InvalidProof(
+            "expected one proof per path element".to_string(),
+        ));
+    }
+    let mut value = value.to_vec();
+    for (i, (proof, key)) in proofs.iter().zip(path.iter().rev()).enumerate() {
+        if &proof.key != key || proof.value != value {
+            return Err(TendermintError::InvalidProof(
+                "proof is not for the given path and value".to_string(),
+            ));
+        }
+        let spec = if i == 0 { &IAVL_SPEC } else { &TENDERMINT_SPEC };
+        value = proof.calculate_root(spec)?;
+    }
+    if value != app_hash {
+        return Err(TendermintError::InvalidProof(
+            "proof does not match the app hash".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// RFC-6962 merkle root, as computed by Tendermint's `merkle.HashFromByteSlices`.
+pub fn merkle_root(items: &[Vec<u8>]) -> Vec<u8> {
+    match items.len() {
+        0 => Sha256::digest([]).digest.to_vec(),
+        1 => {
+            let mut leaf = vec![0u8];
+            leaf.extend(&items[0]);
+            Sha256::digest(&leaf).digest.to_vec()
+        }
+        n => {
+            let split = n.next_power_of_two() / 2;
+            let mut inner = vec![1u8];
+            inner.extend(merkle_root(&items[..split]));
+            inner.extend(merkle_root(&items[split..]));
+            Sha256::digest(&inner).digest.to_vec()
+        }
+    }
+}
+
+fn encode_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// Proto3 omits scalar fields holding their default value.
+fn encode_varint_field(buf: &mut Vec<u8>, field: u64, value: u64) {
+    if value != 0 {
+        encode_varint(buf, field << 3);
+        encode_varint(buf, value);
+    }
+}
+
+fn encode_sfixed64_field(buf: &mut Vec<u8>, field: u64, value: u64) {
+    if value != 0 {
+        encode_varint(buf, (field << 3) | 1);
+        buf.extend(value.to_le_bytes());
+    }
+}
+
+fn encode_bytes_field(buf: &mut Vec<u8>, field: u64, value: &[u8]) {
+    if !value.is_empty() {
+        encode_message_field(buf, field, value);
+    }
+}
+
+/// Non-nullable message fields are encoded even when empty.
+fn encode_message_field(buf: &mut Vec<u8>, field: u64, value: &[u8]) {
+    encode_varint(buf, (field << 3) | 2);
+    encode_varint(buf, value.len() as u64);
+    buf.extend(value);
+}
+
+fn encode_timestamp(timestamp: &Timestamp) -> Vec<u8> {
+    let mut buf = vec![];
+    encode_varint_field(&mut buf, 1, timestamp.seconds as u64);
+    encode_varint_field(&mut buf, 2, timestamp.nanos as u64);
+    buf
+}
+
+fn encode_block_id(block_id: &BlockId) -> Vec<u8> {
+    let mut part_set_header = vec![];
+    encode_varint_field(
+        &mut part_set_header,
+        1,
+        block_id.part_set_header.total as u64,
+    );
+    encode_bytes_field(&mut part_set_header, 2, &block_id.part_set_header.hash);
+    let mut buf = vec![];
+    encode_bytes_field(&mut buf, 1, &block_id.hash);
+    encode_message_field(&mut buf, 2, &part_set_header);
+    buf
+}
+
+/// A `gogoproto` `BytesValue` / `StringValue`, as header fields are hashed.
+fn wrap_bytes(value: &[u8]) -> Vec<u8> {
+    let mut buf = vec![];
+    encode_bytes_field(&mut buf, 1, value);
+    buf
+}
+
+/// A `gogoproto` `Int64Value`.
+fn wrap_varint(value: u64) -> Vec<u8> {
+    let mut buf = vec![];
+    encode_varint_field(&mut buf, 1, value);
+    buf
+}
+
+#[cfg(test)]
+#[path = "unit_tests/tendermint_tests.rs"]
+mod tendermint_tests;
@@ -0,0 +1,58 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use super::*;
+
+const PAYLOAD: &[u8] = b"transfer 100 to 0xabc";
+
+#[test]
+fn test_encode_decode() {
+    let intent = DWalletIntent::new("eip155:1", 7, 1_700_000_000_000, PAYLOAD).unwrap();
+    let encoded = intent.encode();
+    assert!(encoded.starts_with(b"dwallet-intent:v1"));
+    assert_eq!(DWalletIntent::decode(&encoded).unwrap(), intent);
+
+    assert!(matches!(
+        DWalletIntent::decode(&encoded[INTENT_DOMAIN.len()..]),
+        Err(DWalletIntentError::InvalidEncoding(_))
+    ));
+    assert!(matches!(
+        DWalletIntent::decode(&[&encoded[..], &[0]].concat()),
+        Err(DWalletIntentError::InvalidEncoding(_))
+    ));
+}
+
+#[test]
+fn test_verify() {
+    let intent = DWalletIntent::new("cosmos:cosmoshub-4", 7, 1_000, PAYLOAD).unwrap();
+    intent.verify("cosmos:cosmoshub-4", PAYLOAD, 1_000).unwrap();
+
+    assert_eq!(
+        intent.verify("eip155:1", PAYLOAD, 1_000),
+        Err(DWalletIntentError::ChainMismatch {
+            expected: "eip155:1".to_string(),
+            actual: "cosmos:cosmoshub-4".to_string(),
+        })
+    );
+    assert_eq!(
+        intent.verify("cosmos:cosmoshub-4", b"transfer 100 to 0xdef", 1_000),
+        Err(DWalletIntentError::PayloadHashMismatch)
+    );
+    assert_eq!(
+        intent.verify("cosmos:cosmoshub-4", PAYLOAD, 1_001),
+        Err(DWalletIntentError::Expired {
+            expiry_ms: 1_000,
+            now_ms: 1_001
+        })
+    );
+}
+
+#[test]
+fn test_invalid_chain() {
+    for chain in ["1", "eip155", "EIP155:1", "ab:1", "eip155:", "eip155:1:2"] {
+        assert_eq!(
+            DWalletIntent::new(chain, 0, 0, PAYLOAD),
+            Err(DWalletIntentError::InvalidChain(chain.to_string()))
+        );
+    }
+}
@@ -0,0 +1,385 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use super::*;
+
+/// The root of the tree with `leaf` at `gindex` and the siblings `branch`.
+fn root_from_branch(leaf: &Root, branch: &[Root], gindex: u64) -> Root {
+    branch
+        .iter()
+        .enumerate()
+        .fold(*leaf, |node, (level, sibling)| {
+            if (gindex >> level) & 1 == 1 {
+                hash_pair(sibling, &node)
+            } else {
+                hash_pair(&node, sibling)
+            }
+        })
+}
+
+fn branch(depth: u8) -> Vec<Root> {
+    (0..depth).map(|i| [i + 1; 32]).collect()
+}
+
+fn light_client_header(slot: u64) -> LightClientHeader {
+    let execution = ExecutionPayloadHeader {
+        parent_hash: [1; 32],
+        fee_recipient: [2; 20],
+        state_root: [3; 32],
+        receipts_root: [4; 32],
+        logs_bloom: [0; 256],
+        prev_randao: [5; 32],
+        block_number: 100,
+        gas_limit: 30_000_000,
+        gas_used: 15_000_000,
+        timestamp: 1_700_000_000,
+        extra_data: b"dwallet".to_vec(),
+        base_fee_per_gas: U256::from(7u64),
+        block_hash: [6; 32],
+        transactions_root: [7; 32],
+        withdrawals_root: [8; 32],
+        blob_gas_used: 0,
+        excess_blob_gas: 0,
+    };
+    let execution_branch = branch(4);
+    LightClientHeader {
+        beacon: BeaconBlockHeader {
+            slot,
+            proposer_index: 42,
+            parent_root: [9; 32],
+            state_root: [0; 32],
+            body_root: root_from_branch(
+                &execution.hash_tree_root(),
+                &execution_branch,
+                EXECUTION_PAYLOAD_GINDEX,
+            ),
+        },
+        execution,
+        execution_branch,
+    }
+}
+
+fn sync_committee() -> SyncCommittee {
+    SyncCommittee {
+        pubkeys: (0..SYNC_COMMITTEE_SIZE)
+            .map(|i| [(i % 256) as u8; 48])
+            .collect(),
+        aggregate_pubkey: [0xaa; 48],
+    }
+}
+
+fn bootstrap(fork: ForkName) -> LightClientBootstrap {
+    let mut header = light_client_header(8192);
+    let current_sync_committee = sync_committee();
+    let current_sync_committee_branch = branch(fork.current_sync_committee_gindex().ilog2() as u8);
+    header.beacon.state_root = root_from_branch(
+        &current_sync_committee.hash_tree_root(),
+        &current_sync_committee_branch,
+        fork.current_sync_committee_gindex(),
+    );
+    LightClientBootstrap {
+        header,
+        current_sync_committee,
+        current_sync_committee_branch,
+    }
+}
+
+#[test]
+fn test_beacon_block_header_hash_tree_root() {
+    let header = BeaconBlockHeader {
+        slot: 0,
+        proposer_index: 0,
+        parent_root: [0; 32],
+        state_root: [0; 32],
+        body_root: [0; 32],
+    };
+    // The root of a depth 3 tree of zero chunks.
+    assert_eq!(
+        Hex::encode(header.hash_tree_root()),
+        "c78009fdf07fc56a11f122370658a353aaa542ed63e44c4bc15ff4cd105ab33c"
+    );
+}
+
+#[test]
+fn test_merkle_branch() {
+    let leaf = [0xff; 32];
+    let branch = branch(5);
+    let root = root_from_branch(&leaf, &branch, 54);
+    assert!(is_valid_merkle_branch(&leaf, &branch, 54, &root));
+    assert!(!is_valid_merkle_branch(&leaf, &branch, 55, &root));
+    assert!(!is_valid_merkle_branch(&[0; 32], &branch, 54, &root));
+    assert!(!is_valid_merkle_branch(&leaf, &branch[..4], 54, &root));
+}
+
+#[test]
+fn test_header_execution_branch() {
+    let header = light_client_header(8192);
+    header.verify().unwrap();
+
+    let mut tampered = header;
+    tampered.execution.receipts_root = [0; 32];
+    assert!(matches!(
+        tampered.verify(),
+        Err(EthLightClientError::InvalidHeader(_))
+    ));
+}
+
+#[test]
+fn test_verify_bootstrap() {
+    for fork in [ForkName::Deneb, ForkName::Electra] {
+        let bootstrap = bootstrap(fork);
+        let block_root = bootstrap.header.beacon.hash_tree_root();
+        verify_bootstrap(fork, &bootstrap, &block_root).unwrap();
+    }
+}
+
+#[test]
+fn test_verify_bootstrap_of_another_block() {
+    let bootstrap = bootstrap(ForkName::Deneb);
+    assert!(matches!(
+        verify_bootstrap(ForkName::Deneb, &bootstrap, &[0; 32]),
+        Err(EthLightClientError::InvalidBootstrap(_))
+    ));
+}
+
+#[test]
+fn test_verify_bootstrap_with_another_sync_committee() {
+    let mut bootstrap = bootstrap(ForkName::Deneb);
+    bootstrap.current_sync_committee.aggregate_pubkey = [0xbb; 48];
+    let block_root = bootstrap.header.beacon.hash_tree_root();
+    assert!(matches!(
+        verify_bootstrap(ForkName::Deneb, &bootstrap, &block_root),
+        Err(EthLightClientError::InvalidBootstrap(_))
+    ));
+}
+
+#[test]
+fn test_verify_bootstrap_of_another_fork() {
+    let bootstrap = bootstrap(ForkName::Deneb);
+    let block_root = bootstrap.header.beacon.hash_tree_root();
+    assert!(verify_bootstrap(ForkName::Electra, &bootstrap, &block_root).is_err());
+}
+
+#[test]
+fn test_verify_checkpoint() {
+    let known = [
+        Checkpoint {
+            epoch: 100,
+            block_root: [1; 32],
+        },
+        Checkpoint {
+            epoch: 200,
+            block_root: [2; 32],
+        },
+    ];
+    verify_checkpoint(&known[1], &known).unwrap();
+    assert!(verify_checkpoint(
+        &Checkpoint {
+            epoch: 200,
+            block_root: [1; 32],
+        },
+        &known
+    )
+    .is_err());
+    assert!(verify_checkpoint(
+        &Checkpoint {
+            epoch: 300,
+            block_root: [3; 32],
+        },
+        &known
+    )
+    .is_err());
+}
+
+/// A sparse Merkle tree of the given depth, with `leaves` at their generalized indices and zero
+/// chunks elsewhere.
+struct SparseTree {
+    depth: u32,
+    leaves: Vec<(u64, Root)>,
+}
+
+impl SparseTree {
+    fn node(&self, gindex: u64) -> Root {
+        if let Some((_, leaf)) = self.leaves.iter().find(|(g, _)| *g == gindex) {
+            return *leaf;
+        }
+        if gindex >= 1 << self.depth {
+            return [0; 32];
+        }
+        hash_pair(&self.node(2 * gindex), &self.node(2 * gindex + 1))
+    }
+
+    fn branch(&self, mut gindex: u64) -> Vec<Root> {
+        let mut branch = vec![];
+        while gindex > 1 {
+            branch.push(self.node(gindex ^ 1));
+            gindex >>= 1;
+        }
+        branch
+    }
+}
+
+const GENESIS_VALIDATORS_ROOT: Root = [0x4b; 32];
+const PERIOD: u64 = EPOCHS_PER_SYNC_COMMITTEE_PERIOD * SLOTS_PER_EPOCH;
+const BOOTSTRAP_SLOT: u64 = 10 * PERIOD + 64;
+
+fn bls_keys(seed: u8) -> Vec<blst::min_pk::SecretKey> {
+    (0..SYNC_COMMITTEE_SIZE)
+        .map(|i| {
+            let mut ikm = [seed; 32];
+            ikm[..2].copy_from_slice(&(i as u16).to_le_bytes());
+            blst::min_pk::SecretKey::key_gen(&ikm, &[]).unwrap()
+        })
+        .collect()
+}
+
+fn bls_sync_committee(keys: &[blst::min_pk::SecretKey]) -> SyncCommittee {
+    SyncCommittee {
+        pubkeys: keys.iter().map(|key| key.sk_to_pk().to_bytes()).collect(),
+        aggregate_pubkey: [0xaa; 48],
+    }
+}
+
+fn forks() -> Vec<Fork> {
+    vec![Fork {
+        name: ForkName::Deneb,
+        epoch: 0,
+        version: [4, 0, 0, 0],
+    }]
+}
+
+fn store(keys: &[blst::min_pk::SecretKey]) -> LightClientStore {
+    let mut bootstrap = bootstrap(ForkName::Deneb);
+    bootstrap.header = light_client_header(BOOTSTRAP_SLOT);
+    bootstrap.current_sync_committee = bls_sync_committee(keys);
+    let tree = SparseTree {
+        depth: 5,
+        leaves: vec![(54, bootstrap.current_sync_committee.hash_tree_root())],
+    };
+    bootstrap.current_sync_committee_branch = tree.branch(54);
+    bootstrap.header.beacon.state_root = tree.node(1);
+    let block_root = bootstrap.header.beacon.hash_tree_root();
+    LightClientStore::new(GENESIS_VALIDATORS_ROOT, forks(), &block_root, bootstrap).unwrap()
+}
+
+/// An update finalizing the block at `finalized_slot`, with the next sync committee if given,
+/// signed at `signature_slot` by the first `signers` of `keys`.
+fn update(
+    finalized_slot: u64,
+    next_sync_committee: Option<SyncCommittee>,
+    signature_slot: u64,
+    keys: &[blst::min_pk::SecretKey],
+    signers: usize,
+) -> EthUpdate {
+    let finalized_header = light_client_header(finalized_slot);
+    let mut leaves = vec![(105, finalized_header.beacon.hash_tree_root())];
+    if let Some(committee) = &next_sync_committee {
+        leaves.push((55, committee.hash_tree_root()));
+    }
+    let tree = SparseTree { depth: 6, leaves };
+    let mut attested_header = light_client_header(signature_slot - 1);
+    attested_header.beacon.state_root = tree.node(1);
+
+    let signing_root = hash_pair(
+        &attested_header.beacon.hash_tree_root(),
+        &compute_domain(forks()[0].version, &GENESIS_VALIDATORS_ROOT),
+    );
+    let signatures: Vec<_> = keys[..signers]
+        .iter()
+        .map(|key| key.sign(&signing_root, BLS_DST, &[]))
+        .collect();
+    let signatures: Vec<_> = signatures.iter().collect();
+    let mut sync_committee_bits = [0; SYNC_COMMITTEE_SIZE / 8];
+    (0..signers).for_each(|i| sync_committee_bits[i / 8] |= 1 << (i % 8));
+    let sync_aggregate = SyncAggregate {
+        sync_committee_bits,
+        sync_committee_signature: blst::min_pk::AggregateSignature::aggregate(&signatures, false)
+            .unwrap()
+            .to_signature()
+            .to_bytes(),
+    };
+
+    match next_sync_committee {
+        Some(next_sync_committee) => EthUpdate::Update(LightClientUpdate {
+            attested_header,
+            next_sync_committee,
+            next_sync_committee_branch: tree.branch(55),
+            finalized_header,
+            finality_branch: tree.branch(105),
+            sync_aggregate,
+            signature_slot,
+        }),
+        None => EthUpdate::FinalityUpdate(LightClientFinalityUpdate {
+            attested_header,
+            finalized_header,
+            finality_branch: tree.branch(105),
+            sync_aggregate,
+            signature_slot,
+        }),
+    }
+}
+
+#[test]
+fn test_apply_finality_update() {
+    let keys = bls_keys(1);
+    let mut store = store(&keys);
+    let update = update(BOOTSTRAP_SLOT + 64, None, BOOTSTRAP_SLOT + 128, &keys, 400);
+    store.apply_update(&update).unwrap();
+    assert_eq!(&store.finalized_header, update.finalized_header());
+
+    // The same update again.
+    assert!(store.apply_update(&update).is_err());
+}
+
+#[test]
+fn test_apply_update_without_supermajority() {
+    let keys = bls_keys(1);
+    let mut store = store(&keys);
+    let update = update(BOOTSTRAP_SLOT + 64, None, BOOTSTRAP_SLOT + 128, &keys, 341);
+    assert!(matches!(
+        store.apply_update(&update),
+        Err(EthLightClientError::InvalidUpdate(_))
+    ));
+}
+
+#[test]
+fn test_apply_update_with_invalid_signature() {
+    let keys = bls_keys(1);
+    let mut store = store(&keys);
+    let update = update(
+        BOOTSTRAP_SLOT + 64,
+        None,
+        BOOTSTRAP_SLOT + 128,
+        &bls_keys(2),
+        512,
+    );
+    assert!(store.apply_update(&update).is_err());
+}
+
+#[test]
+fn test_sync_committee_rotation() {
+    let keys = bls_keys(1);
+    let next_keys = bls_keys(2);
+    let mut store = store(&keys);
+
+    // A finality update of the next period cannot be verified yet.
+    let next_period_update = update(11 * PERIOD + 32, None, 11 * PERIOD + 96, &next_keys, 512);
+    assert!(store.apply_update(&next_period_update).is_err());
+
+    let committee_update = update(
+        BOOTSTRAP_SLOT + 64,
+        Some(bls_sync_committee(&next_keys)),
+        BOOTSTRAP_SLOT + 128,
+        &keys,
+        512,
+    );
+    store.apply_update(&committee_update).unwrap();
+    assert_eq!(
+        store.next_sync_committee,
+        Some(bls_sync_committee(&next_keys))
+    );
+
+    store.apply_update(&next_period_update).unwrap();
+    assert_eq!(store.current_sync_committee, bls_sync_committee(&next_keys));
+    assert_eq!(store.next_sync_committee, None);
+}
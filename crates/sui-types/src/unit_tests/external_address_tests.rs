@@ -0,0 +1,193 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use super::*;
+use fastcrypto::encoding::{Encoding, Hex};
+
+// The secp256k1 generator, used by the BIP-173 test vectors.
+const GENERATOR: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+#[test]
+fn test_p2wpkh_address() {
+    let public_key = Hex::decode(GENERATOR).unwrap();
+    assert_eq!(
+        bitcoin_p2wpkh_address(&public_key, BitcoinNetwork::Mainnet).unwrap(),
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+    );
+    assert_eq!(
+        bitcoin_p2wpkh_address(&public_key, BitcoinNetwork::Testnet).unwrap(),
+        "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"
+    );
+    assert_eq!(
+        bitcoin_p2wpkh_address(&public_key, BitcoinNetwork::Regtest).unwrap(),
+        "bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080"
+    );
+}
+
+#[test]
+fn test_p2tr_address() {
+    // BIP-86 test vector for m/86'/0'/0'/0/0, the parity of the internal key is irrelevant.
+    let public_key =
+        Hex::decode("02cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115").unwrap();
+    assert_eq!(
+        bitcoin_p2tr_address(&public_key, BitcoinNetwork::Mainnet).unwrap(),
+        "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+    );
+}
+
+#[test]
+fn test_cosmos_address() {
+    let public_key = Hex::decode(GENERATOR).unwrap();
+    assert_eq!(
+        cosmos_address(&public_key, "cosmos").unwrap(),
+        "cosmos1w508d6qejxtdg4y5r3zarvary0c5xw7k6ah60c"
+    );
+    assert_eq!(
+        cosmos_address(&public_key, "osmo").unwrap(),
+        "osmo1w508d6qejxtdg4y5r3zarvary0c5xw7kjxy2e2"
+    );
+}
+
+#[test]
+fn test_ethereum_address() {
+    // The address of the private key 1.
+    let public_key = Hex::decode(GENERATOR).unwrap();
+    assert_eq!(
+        ethereum_address(&public_key).unwrap(),
+        "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+    );
+    assert!(ethereum_address(&public_key[1..]).is_err());
+}
+
+#[test]
+fn test_external_addresses() {
+    let public_key = Hex::decode(GENERATOR).unwrap();
+    let addresses = ExternalAddresses::new(&public_key).unwrap();
+    assert_eq!(addresses.ethereum, ethereum_address(&public_key).unwrap());
+    assert_eq!(
+        addresses.bitcoin,
+        BitcoinNetwork::ALL
+            .map(|network| BitcoinAddresses::new(&public_key, network).unwrap())
+            .to_vec()
+    );
+    assert_eq!(
+        addresses.cosmos,
+        "cosmos1w508d6qejxtdg4y5r3zarvary0c5xw7k6ah60c"
+    );
+}
+
+#[test]
+fn test_invalid_public_key() {
+    assert!(matches!(
+        bitcoin_p2wpkh_address(&[5u8; 33], BitcoinNetwork::Mainnet),
+        Err(ExternalAddressError::InvalidPublicKey(_))
+    ));
+}
+
+#[test]
+fn test_external_chain() {
+    assert_eq!("eth".parse(), Ok(ExternalChain::Ethereum));
+    assert_eq!(
+        "btc".parse(),
+        Ok(ExternalChain::Bitcoin(BitcoinNetwork::Mainnet))
+    );
+    assert_eq!(
+        "btc-testnet".parse(),
+        Ok(ExternalChain::Bitcoin(BitcoinNetwork::Testnet))
+    );
+    assert_eq!(
+        "cosmos:osmo".parse(),
+        Ok(ExternalChain::Cosmos {
+            prefix: "osmo".to_string()
+        })
+    );
+    assert!("btc-litecoin".parse::<ExternalChain>().is_err());
+    assert!("cosmos:".parse::<ExternalChain>().is_err());
+    assert!("solana".parse::<ExternalChain>().is_err());
+
+    let public_key = Hex::decode(GENERATOR).unwrap();
+    assert_eq!(
+        ExternalChain::Bitcoin(BitcoinNetwork::Testnet)
+            .addresses(&public_key)
+            .unwrap(),
+        vec![(
+            "bitcoin-testnet".to_string(),
+            "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx".to_string()
+        )]
+    );
+    assert_eq!(
+        ExternalChain::Cosmos {
+            prefix: "osmo".to_string()
+        }
+        .addresses(&public_key)
+        .unwrap(),
+        vec![(
+            "cosmos:osmo".to_string(),
+            "osmo1w508d6qejxtdg4y5r3zarvary0c5xw7kjxy2e2".to_string()
+        )]
+    );
+
+    // All the chains give the external addresses of the client config.
+    let addresses: Vec<_> = ExternalChain::all()
+        .iter()
+        .flat_map(|chain| chain.addresses(&public_key).unwrap())
+        .map(|(_, address)| address)
+        .collect();
+    assert_eq!(addresses.len(), 5);
+    assert!(addresses.contains(&ExternalAddresses::new(&public_key).unwrap().cosmos));
+}
+
+#[test]
+fn test_find_address() {
+    let public_key = Hex::decode(GENERATOR).unwrap();
+    assert_eq!(
+        ExternalChain::Ethereum
+            .find_address(&public_key, "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf")
+            .unwrap(),
+        Some((
+            "ethereum".to_string(),
+            "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf".to_string()
+        ))
+    );
+    assert_eq!(
+        ExternalChain::Bitcoin(BitcoinNetwork::Mainnet)
+            .find_address(&public_key, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4")
+            .unwrap(),
+        Some((
+            "bitcoin-mainnet".to_string(),
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".to_string()
+        ))
+    );
+    // The taproot address, which ECDSA dWallets cannot spend from, is not found.
+    let taproot = bitcoin_p2tr_address(&public_key, BitcoinNetwork::Mainnet).unwrap();
+    assert_eq!(
+        ExternalChain::Bitcoin(BitcoinNetwork::Mainnet)
+            .find_address(&public_key, &taproot)
+            .unwrap(),
+        None
+    );
+    // The address on another network, or of another key, is not found.
+    assert_eq!(
+        ExternalChain::Bitcoin(BitcoinNetwork::Mainnet)
+            .find_address(&public_key, "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx")
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        ExternalChain::Ethereum
+            .find_address(&public_key, "0x0000000000000000000000000000000000000001")
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_dwallet_public_key_to_sec1() {
+    // The BCS encoding of the public key, which `dwallet_binding` peels on chain.
+    let public_key = Hex::decode(&format!("21{GENERATOR}")).unwrap();
+    assert_eq!(
+        dwallet_public_key_to_sec1(&public_key).unwrap(),
+        Hex::decode(GENERATOR).unwrap()
+    );
+    assert!(dwallet_public_key_to_sec1(&public_key[1..]).is_err());
+}
@@ -0,0 +1,70 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use super::*;
+
+fn event_type(module: &IdentStr, name: &IdentStr) -> StructTag {
+    StructTag {
+        address: SUI_SYSTEM_ADDRESS,
+        module: module.to_owned(),
+        name: name.to_owned(),
+        type_params: vec![],
+    }
+}
+
+#[test]
+fn test_dwallet_event_from_event() {
+    let dwallet_id = ObjectID::random();
+    let output = SignOutputCreatedEvent {
+        session_id: ID::new(ObjectID::random()),
+        sign_output_id: ID::new(ObjectID::random()),
+        dwallet_id: ID::new(dwallet_id),
+        dwallet_cap_id: ID::new(ObjectID::random()),
+        signatures: vec![vec![1; 64]],
+        sender: SuiAddress::random_for_testing_only(),
+    };
+    let event = DWalletEvent::try_from_event(
+        &event_type(DWALLET_MODULE_NAME, SIGN_OUTPUT_CREATED_EVENT_STRUCT_NAME),
+        &bcs::to_bytes(&output).unwrap(),
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(event, DWalletEvent::SignOutputCreated(output));
+    assert_eq!(event.dwallet_id(), &dwallet_id);
+
+    // Events of other modules are not dWallet events, even with the same name.
+    assert!(DWalletEvent::try_from_event(
+        &event_type(
+            DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME,
+            SIGN_OUTPUT_CREATED_EVENT_STRUCT_NAME
+        ),
+        &[],
+    )
+    .is_none());
+    assert!(DWalletEvent::try_from_event(
+        &event_type(DWALLET_MODULE_NAME, SIGN_SESSION_CREATED_EVENT_STRUCT_NAME),
+        &[0],
+    )
+    .unwrap()
+    .is_err());
+}
+
+#[test]
+fn test_approvals_holder_messages() {
+    let dwallet_cap_id = ID::new(ObjectID::random());
+    // `approve_messages` pops the messages from the back of the vector it is given.
+    let holder = ApprovalsHolder {
+        id: UID::new(ObjectID::random()),
+        message_approvals: ["third", "second", "first"]
+            .map(|message| MessageApproval {
+                dwallet_cap_id: dwallet_cap_id.clone(),
+                message: message.as_bytes().to_vec(),
+            })
+            .to_vec(),
+    };
+    let holder = ApprovalsHolder::from_bcs_bytes(&bcs::to_bytes(&holder).unwrap()).unwrap();
+    assert_eq!(
+        holder.messages(),
+        vec![b"first".to_vec(), b"second".to_vec(), b"third".to_vec()]
+    );
+}
@@ -0,0 +1,304 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use super::*;
+use crate::crypto::get_key_pair_from_rng;
+use fastcrypto::ed25519::Ed25519KeyPair;
+use fastcrypto::traits::{KeyPair, Signer};
+use rand::{rngs::StdRng, SeedableRng};
+
+const CHAIN_ID: &str = "cosmoshub-4";
+
+fn keys(n: usize) -> Vec<Ed25519KeyPair> {
+    let mut rng = StdRng::from_seed([0; 32]);
+    (0..n)
+        .map(|_| get_key_pair_from_rng::<Ed25519KeyPair, _>(&mut rng).1)
+        .collect()
+}
+
+fn validator_set(keys: &[Ed25519KeyPair]) -> ValidatorSet {
+    ValidatorSet {
+        validators: keys
+            .iter()
+            .map(|key| Validator {
+                pub_key: key.public().as_bytes().to_vec(),
+                voting_power: 10,
+            })
+            .collect(),
+    }
+}
+
+/// A light block at `height` signed by the first `signers` of `keys`.
+fn light_block(height: u64, keys: &[Ed25519KeyPair], signers: usize) -> LightBlock {
+    let validators = validator_set(keys);
+    let header = Header {
+        version_block: 11,
+        version_app: 0,
+        chain_id: CHAIN_ID.to_string(),
+        height,
+        time: Timestamp {
+            seconds: 1_700_000_000 + height as i64,
+            nanos: 0,
+        },
+        last_block_id: BlockId::default(),
+        last_commit_hash: vec![1; 32],
+        data_hash: vec![2; 32],
+        validators_hash: validators.hash(),
+        next_validators_hash: validators.hash(),
+        consensus_hash: vec![3; 32],
+        app_hash: vec![4; 32],
+        last_results_hash: vec![],
+        evidence_hash: vec![],
+        proposer_address: validators.validators[0].address(),
+    };
+    let mut commit = Commit {
+        height,
+        round: 0,
+        block_id: BlockId {
+            hash: header.hash(),
+            part_set_header: PartSetHeader {
+                total: 1,
+                hash: vec![5; 32],
+            },
+        },
+        signatures: vec![],
+    };
+    commit.signatures = keys
+        .iter()
+        .enumerate()
+        .map(|(index, key)| {
+            let mut signature = CommitSig {
+                block_id_flag: BlockIdFlag::Absent,
+                validator_address: validators.validators[index].address(),
+                timestamp: header.time.clone(),
+                signature: vec![],
+            };
+            if index < signers {
+                signature.block_id_flag = BlockIdFlag::Commit;
+                signature.signature = key
+                    .sign(&commit.vote_sign_bytes(CHAIN_ID, &signature))
+                    .as_bytes()
+                    .to_vec();
+            }
+            signature
+        })
+        .collect();
+    LightBlock {
+        header,
+        commit,
+        validators,
+    }
+}
+
+#[test]
+fn test_verify_light_block() {
+    let keys = keys(4);
+    let trusted = validator_set(&keys);
+
+    // Adjacent and non-adjacent blocks signed by 3 of 4 validators.
+    verify_light_block(CHAIN_ID, 9, &trusted, &light_block(10, &keys, 3)).unwrap();
+    verify_light_block(CHAIN_ID, 1, &trusted, &light_block(10, &keys, 3)).unwrap();
+
+    // 2 of 4 is not more than 2/3 of the voting power.
+    assert_eq!(
+        verify_light_block(CHAIN_ID, 9, &trusted, &light_block(10, &keys, 2)),
+        Err(TendermintError::InsufficientVotingPower {
+            signed: 20,
+            total: 40
+        })
+    );
+
+    assert!(matches!(
+        verify_light_block("osmosis-1", 9, &trusted, &light_block(10, &keys, 3)),
+        Err(TendermintError::InvalidHeader(_))
+    ));
+    assert!(matches!(
+        verify_light_block(CHAIN_ID, 10, &trusted, &light_block(10, &keys, 3)),
+        Err(TendermintError::InvalidHeader(_))
+    ));
+}
+
+#[test]
+fn test_verify_light_block_tampered() {
+    let keys = keys(4);
+    let trusted = validator_set(&keys);
+
+    let mut block = light_block(10, &keys, 4);
+    block.header.app_hash = vec![0; 32];
+    assert!(matches!(
+        verify_light_block(CHAIN_ID, 9, &trusted, &block),
+        Err(TendermintError::InvalidCommit(_))
+    ));
+
+    let mut block = light_block(10, &keys, 4);
+    block.commit.signatures[0].timestamp.nanos = 1;
+    assert!(matches!(
+        verify_light_block(CHAIN_ID, 9, &trusted, &block),
+        Err(TendermintError::InvalidCommit(_))
+    ));
+
+    // A non-adjacent block of unknown validators.
+    let other_keys: Vec<_> = {
+        let mut rng = StdRng::from_seed([1; 32]);
+        (0..4)
+            .map(|_| get_key_pair_from_rng::<Ed25519KeyPair, _>(&mut rng).1)
+            .collect()
+    };
+    assert_eq!(
+        verify_light_block(CHAIN_ID, 1, &trusted, &light_block(10, &other_keys, 4)),
+        Err(TendermintError::InsufficientVotingPower {
+            signed: 0,
+            total: 40
+        })
+    );
+}
+
+#[test]
+fn test_voting_power_and_timestamp_bounds() {
+    let keys = keys(4);
+    let mut trusted = validator_set(&keys);
+    trusted.validators[0].voting_power = MAX_TOTAL_VOTING_POWER - 30;
+    assert_eq!(trusted.total_voting_power(), Ok(MAX_TOTAL_VOTING_POWER));
+
+    // A total voting power above `MaxTotalVotingPower`, or overflowing, is rejected.
+    trusted.validators[0].voting_power = MAX_TOTAL_VOTING_POWER - 29;
+    assert!(matches!(
+        trusted.total_voting_power(),
+        Err(TendermintError::InvalidValidatorSet(_))
+    ));
+    trusted.validators[0].voting_power = u64::MAX;
+    assert!(matches!(
+        trusted.total_voting_power(),
+        Err(TendermintError::InvalidValidatorSet(_))
+    ));
+    assert!(matches!(
+        verify_light_block(CHAIN_ID, 1, &trusted, &light_block(10, &keys, 4)),
+        Err(TendermintError::InvalidValidatorSet(_))
+    ));
+
+    let time = |seconds, nanos| Timestamp { seconds, nanos }.unix_ms();
+    assert_eq!(time(1_700_000_000, 999_999_999), Ok(1_700_000_000_999));
+    for (seconds, nanos) in [(-1, 0), (0, -1), (0, 1_000_000_000), (i64::MAX, 0)] {
+        assert!(matches!(
+            time(seconds, nanos),
+            Err(TendermintError::InvalidHeader(_))
+        ));
+    }
+    let mut block = light_block(10, &keys, 4);
+    block.header.time.seconds = -1;
+    assert!(matches!(
+        verify_light_block(CHAIN_ID, 9, &validator_set(&keys), &block),
+        Err(TendermintError::InvalidHeader(_))
+    ));
+}
+
+#[test]
+fn test_verify_membership() {
+    let iavl_proof = ExistenceProof {
+        key: b"balances/alice".to_vec(),
+        value: b"100uatom".to_vec(),
+        leaf_prefix: vec![0, 2, 2],
+        path: vec![
+            InnerOp {
+                prefix: vec![2, 4, 2, 0x20],
+                suffix: [vec![0x20], vec![7; 32]].concat(),
+            },
+            InnerOp {
+                prefix: [vec![4, 8, 2, 0x20], vec![8; 32], vec![0x20]].concat(),
+                suffix: vec![],
+            },
+        ],
+    };
+    let store_root = iavl_proof.calculate_root(&IAVL_SPEC).unwrap();
+    let multistore_proof = ExistenceProof {
+        key: b"bank".to_vec(),
+        value: store_root,
+        leaf_prefix: vec![0],
+        path: vec![InnerOp {
+            prefix: [vec![1], vec![9; 32]].concat(),
+            suffix: vec![],
+        }],
+    };
+    let app_hash = multistore_proof.calculate_root(&TENDERMINT_SPEC).unwrap();
+    let proofs = vec![iavl_proof, multistore_proof];
+    let path = vec![b"bank".to_vec(), b"balances/alice".to_vec()];
+
+    verify_membership(&app_hash, &proofs, &path, b"100uatom").unwrap();
+    assert!(verify_membership(&app_hash, &proofs, &path, b"1000uatom").is_err());
+    assert!(verify_membership(&[0; 32], &proofs, &path, b"100uatom").is_err());
+
+    // A leaf can't be passed off as an inner node.
+    let mut proofs = proofs;
+    proofs[0].path[0].prefix[0] = 0;
+    assert!(matches!(
+        verify_membership(&app_hash, &proofs, &path, b"100uatom"),
+        Err(TendermintError::InvalidProof(_))
+    ));
+}
+
+/// The IAVL proof of a key at the left of a tree of 4 keys, all at version 1.
+fn iavl_proof() -> ExistenceProof {
+    ExistenceProof {
+        key: b"balances/alice".to_vec(),
+        value: b"100uatom".to_vec(),
+        leaf_prefix: vec![0, 2, 2],
+        path: vec![
+            InnerOp {
+                prefix: vec![2, 4, 2, 0x20],
+                suffix: [vec![0x20], vec![7; 32]].concat(),
+            },
+            InnerOp {
+                prefix: vec![4, 8, 2, 0x20],
+                suffix: [vec![0x20], vec![8; 32]].concat(),
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_proof_spec() {
+    let invalid = |proof: &ExistenceProof, spec: &ProofSpec| {
+        matches!(
+            proof.calculate_root(spec),
+            Err(TendermintError::InvalidProof(_))
+        )
+    };
+    let proof = iavl_proof();
+    proof.calculate_root(&IAVL_SPEC).unwrap();
+
+    // The leaf of an IAVL tree has height 0 and size 1, and nothing else in its prefix.
+    let mut tampered = proof.clone();
+    tampered.leaf_prefix = vec![0];
+    assert!(invalid(&tampered, &IAVL_SPEC));
+    tampered.leaf_prefix = vec![0, 2, 2, 0x20];
+    assert!(invalid(&tampered, &IAVL_SPEC));
+
+    // Every inner node is higher than the one below it.
+    let mut tampered = proof.clone();
+    tampered.path[1].prefix = vec![2, 8, 2, 0x20];
+    assert!(invalid(&tampered, &IAVL_SPEC));
+
+    // Prefixes and suffixes hold at most one sibling.
+    let mut tampered = proof.clone();
+    tampered.path[1].prefix = [vec![4, 8, 2, 0x20], vec![8; 64], vec![0x20]].concat();
+    assert!(invalid(&tampered, &IAVL_SPEC));
+    let mut tampered = proof.clone();
+    tampered.path[0].suffix.pop();
+    assert!(invalid(&tampered, &IAVL_SPEC));
+
+    // A simple Merkle tree has 2 children of 32 bytes per inner node, and is at most 64 deep.
+    let inner = InnerOp {
+        prefix: vec![1],
+        suffix: vec![9; 32],
+    };
+    let mut multistore_proof = ExistenceProof {
+        key: b"bank".to_vec(),
+        value: vec![1; 32],
+        leaf_prefix: vec![0],
+        path: vec![inner.clone(); 64],
+    };
+    multistore_proof.calculate_root(&TENDERMINT_SPEC).unwrap();
+    assert!(invalid(&proof, &TENDERMINT_SPEC));
+    multistore_proof.path.push(inner);
+    assert!(invalid(&multistore_proof, &TENDERMINT_SPEC));
+}
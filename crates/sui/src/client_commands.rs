@@ -2,13 +2,15 @@
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
 use std::{
+    collections::BTreeMap,
     fmt::{Debug, Display, Formatter, Write},
     path::PathBuf,
     sync::Arc,
 };
+use std::num::NonZeroU32;
 use std::time::Duration;
 
-use anyhow::{anyhow, bail, ensure};
+use anyhow::{anyhow, bail, ensure, Context};
 use bip32::DerivationPath;
 use bip32::secp256k1::elliptic_curve::rand_core::OsRng;
 use clap::*;
@@ -18,6 +20,7 @@ use fastcrypto::{
     traits::ToFromBytes,
 };
 
+use indicatif::{ProgressBar, ProgressFinish};
 use json_to_table::json_to_table;
 use move_core_types::language_storage::{StructTag, TypeTag};
 use move_package::BuildConfig as MoveBuildConfig;
@@ -33,13 +36,17 @@ use sui_execution::verifier::VerifierOverrides;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{DynamicFieldPage, MoveCallParams, ObjectChange, RPCTransactionRequestParams, SuiData, SuiObjectData, SuiObjectDataFilter, SuiObjectResponse, SuiObjectResponseQuery, SuiParsedData, SuiRawData, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions};
 use sui_json_rpc_types::{SuiExecutionStatus, SuiObjectDataOptions};
+use sui_keys::encryption::ShareEncryptionKey;
 use sui_keys::keystore::AccountKeystore;
 use sui_move_build::{
     build_from_resolution_graph, check_invalid_dependencies, check_unpublished_dependencies,
     gather_published_ids, BuildConfig, CompiledPackage, PackageDependencies, PublishedAtError,
 };
 use sui_replay::ReplayToolCommand;
-use sui_sdk::sui_client_config::{DWalletSecretShare, SuiClientConfig, SuiEnv};
+use sui_sdk::dwallet::DWalletClient;
+use sui_sdk::eth_client::EthFinality;
+use sui_sdk::faucet_client::{FaucetClient, FaucetCoin};
+use sui_sdk::sui_client_config::{DWalletCurve, DWalletSecretShare, SuiClientConfig, SuiEnv};
 use sui_sdk::wallet_context::WalletContext;
 use sui_sdk::SuiClient;
 use sui_types::{base_types::{ObjectID, SequenceNumber, SuiAddress}, crypto::SignatureScheme, digests::TransactionDigest, dynamic_field::DynamicFieldInfo, error::SuiError, gas_coin::GasCoin, metrics::BytecodeVerifierMetrics, move_package::UpgradeCap, object::Owner, parse_sui_type_tag, signature::GenericSignature, SUI_SYSTEM_PACKAGE_ID, transaction::{SenderSignedData, Transaction, TransactionData, TransactionDataAPI}};
@@ -54,10 +61,16 @@ use tabled::{
 use tokio::time::sleep;
 use tracing::info;
 use signature_mpc::twopc_mpc_protocols::{initiate_centralized_party_dkg, SecretKeyShareEncryptionAndProof, EncryptedDecentralizedPartySecretKeyShareValue, initiate_centralized_party_presign, DKGCentralizedPartyOutput, PresignDecentralizedPartyOutput, initiate_centralized_party_sign, message_digest};
+use sui_types::committee::EpochId;
+use sui_types::digests::CheckpointDigest;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::signature_mpc::{APPROVE_MESSAGES_FUNC_NAME, CREATE_DKG_SESSION_FUNC_NAME, CREATE_DWALLET_FUNC_NAME, CREATE_PRESIGN_SESSION_FUNC_NAME, DKG_SESSION_OUTPUT_STRUCT_NAME, DKG_SESSION_STRUCT_NAME, DKGSessionOutput, DWallet, DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME, DWALLET_MODULE_NAME, DWALLET_STRUCT_NAME, PRESIGN_SESSION_STRUCT_NAME, PresignSessionOutput, Presign};
 use sui_types::transaction::{Argument, CallArg, ObjectArg, TransactionKind};
-use crate::dwallet_commands::SuiDWalletCommands;
+use crate::dwallet_commands::{DWalletWaitOptions, SuiDWalletCommands};
+use crate::env_commands::SuiEnvCommands;
+use crate::eth_light_client_commands::SuiEthLightClientCommands;
+use crate::profile_commands::SuiProfileCommands;
 
 use crate::key_identity::{get_identity_address, KeyIdentity};
 use crate::sui_commands::SuiCommand;
@@ -69,16 +82,17 @@ macro_rules! serialize_or_execute {
             !$serialize_unsigned || !$serialize_signed,
             "Cannot specify both --serialize-unsigned-transaction and --serialize-signed-transaction"
         );
+        let tx_data = $context.with_default_gas_price($tx_data)?;
         if $serialize_unsigned {
-            SuiClientCommandResult::SerializedUnsignedTransaction($tx_data)
+            SuiClientCommandResult::SerializedUnsignedTransaction(tx_data)
         } else {
             let signature = $context.config.keystore.sign_secure(
-                &$tx_data.sender(),
-                &$tx_data,
+                &tx_data.sender(),
+                &tx_data,
                 Intent::sui_transaction(),
             )?;
             let sender_signed_data = SenderSignedData::new_from_sender_signature(
-                $tx_data,
+                tx_data,
                 Intent::sui_transaction(),
                 signature,
             );
@@ -114,6 +128,23 @@ pub enum SuiClientCommands {
     /// Obtain the Addresses managed by the client.
     #[clap(name = "addresses")]
     Addresses,
+    /// Name an address, for the name to be usable wherever an address is accepted.
+    #[clap(name = "add-address-alias")]
+    AddAddressAlias {
+        #[clap(long)]
+        alias: String,
+        #[clap(long)]
+        address: SuiAddress,
+    },
+    /// Obtain the named addresses of the client config.
+    #[clap(name = "address-aliases")]
+    AddressAliases,
+    /// Remove the name of an address.
+    #[clap(name = "remove-address-alias")]
+    RemoveAddressAlias {
+        #[clap(long)]
+        alias: String,
+    },
 
     /// Call Move function
     #[clap(name = "call")]
@@ -144,9 +175,10 @@ pub enum SuiClientCommands {
         /// If not provided, a gas object with at least gas_budget value will be selected
         #[clap(long)]
         gas: Option<ObjectID>,
-        /// Gas budget for this call
+        /// Gas budget for this call.
+        /// Defaults to the default gas budget of the active environment.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
 
         /// Instead of executing the transaction, serialize the bcs bytes of the unsigned transaction data
         /// (TransactionData) using base64 encoding, and print out the string.
@@ -191,6 +223,18 @@ pub enum SuiClientCommands {
         signatures: Vec<String>,
     },
 
+    /// Request coins from the faucet of the active environment for an address, and wait for the
+    /// faucet to send them. Devnet and testnet faucets limit the requests of an address or IP.
+    #[clap(name = "faucet")]
+    Faucet {
+        /// The address or alias to fund. Defaults to the active address.
+        #[clap(long)]
+        address: Option<KeyIdentity>,
+        /// The faucet to request the coins from, instead of the faucet of the active environment.
+        #[clap(long, value_hint = ValueHint::Url)]
+        url: Option<String>,
+    },
+
     /// Obtain all gas objects owned by the address.
     /// An address' alias can be used instead of the address.
     #[clap(name = "gas")]
@@ -213,9 +257,10 @@ pub enum SuiClientCommands {
         /// If not provided, a gas object with at least gas_budget value will be selected.
         #[clap(long)]
         gas: Option<ObjectID>,
-        /// Gas budget for this call
+        /// Gas budget for this call.
+        /// Defaults to the default gas budget of the active environment.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
 
         /// Instead of executing the transaction, serialize the bcs bytes of the unsigned transaction data
         /// (TransactionData) using base64 encoding, and print out the string.
@@ -248,8 +293,29 @@ pub enum SuiClientCommands {
         alias: String,
         #[clap(long, value_hint = ValueHint::Url)]
         rpc: String,
+        /// An RPC endpoint to fail over to when `rpc` fails, can be repeated
+        #[clap(long = "fallback-rpc", value_hint = ValueHint::Url)]
+        fallback_rpcs: Vec<String>,
         #[clap(long, value_hint = ValueHint::Url)]
         ws: Option<String>,
+        /// The faucet of the network, for `sui client faucet`
+        #[clap(long, value_hint = ValueHint::Url)]
+        faucet: Option<String>,
+        /// The HTTP or SOCKS5 proxy to send the RPC requests through, e.g. socks5h://127.0.0.1:9050
+        #[clap(long, value_hint = ValueHint::Url)]
+        proxy: Option<String>,
+        /// A header to add to every RPC request, as NAME=VALUE, e.g. the API key of the RPC provider
+        #[clap(long = "header", value_parser = parse_header)]
+        headers: Vec<(String, String)>,
+        /// The bearer token authenticating the RPC requests to the RPC provider
+        #[clap(long)]
+        bearer_token: Option<String>,
+        /// The gas budget of transactions that are not given one
+        #[clap(long)]
+        default_gas_budget: Option<u64>,
+        /// The gas price of transactions, instead of the reference gas price of the network
+        #[clap(long)]
+        default_gas_price: Option<u64>,
     },
 
     /// Get object info
@@ -293,9 +359,10 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
 
-        /// Gas budget for this transaction
+        /// Gas budget for this transaction.
+        /// Defaults to the default gas budget of the active environment.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
 
         /// Instead of executing the transaction, serialize the bcs bytes of the unsigned transaction data
         /// (TransactionData) using base64 encoding, and print out the string.
@@ -319,9 +386,10 @@ pub enum SuiClientCommands {
         #[clap(long)]
         recipient: KeyIdentity,
 
-        /// Gas budget for this transaction
+        /// Gas budget for this transaction.
+        /// Defaults to the default gas budget of the active environment.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
 
         /// Instead of executing the transaction, serialize the bcs bytes of the unsigned transaction data
         /// (TransactionData) using base64 encoding, and print out the string.
@@ -351,9 +419,10 @@ pub enum SuiClientCommands {
         #[clap(long, num_args(1..))]
         amounts: Vec<u64>,
 
-        /// Gas budget for this transaction
+        /// Gas budget for this transaction.
+        /// Defaults to the default gas budget of the active environment.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
 
         /// Instead of executing the transaction, serialize the bcs bytes of the unsigned transaction data
         /// (TransactionData) using base64 encoding, and print out the string.
@@ -382,9 +451,10 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
 
-        /// Gas budget for running module initializers
+        /// Gas budget for running module initializers.
+        /// Defaults to the default gas budget of the active environment.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
 
         /// Publish the package without checking whether compiling dependencies from source results
         /// in bytecode matching the dependencies found on-chain.
@@ -406,6 +476,33 @@ pub enum SuiClientCommands {
         serialize_signed_transaction: bool,
     },
 
+    /// Replace the key of an address with a new key, keeping its alias, in one guided flow: the
+    /// new key is generated and takes over the alias of the address, the dWallets the address
+    /// owns are transferred to the new address, their encrypted user shares are encrypted again
+    /// for the new key, and the active addresses and address aliases of the config are pointed to
+    /// the new address. The old key is kept, renamed `<alias>-retired`, with its other objects.
+    #[clap(name = "rotate-key")]
+    RotateKey {
+        /// The address (or its alias) whose key to rotate.
+        /// Defaults to the active address.
+        #[clap(long)]
+        address: Option<KeyIdentity>,
+        /// The scheme of the new key {ed25519 | secp256k1 | secp256r1}.
+        #[clap(long, default_value = "ed25519")]
+        key_scheme: SignatureScheme,
+        /// The word length of the recovery phrase of the new key
+        /// { word12 | word15 | word18 | word21 | word24}, word12 if not specified.
+        #[clap(long)]
+        word_length: Option<String>,
+        /// The derivation path of the new key, the default path of its scheme if not specified.
+        #[clap(long)]
+        derivation_path: Option<DerivationPath>,
+        /// Gas budget for the transfer of the dWallets.
+        /// Defaults to the default gas budget of the active environment, or else to an estimate.
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
+
     /// Split a coin object into multiple coins.
     #[clap(group(ArgGroup::new("split").required(true).args(&["amounts", "count"])))]
     SplitCoin {
@@ -422,9 +519,10 @@ pub enum SuiClientCommands {
         /// If not provided, a gas object with at least gas_budget value will be selected
         #[clap(long)]
         gas: Option<ObjectID>,
-        /// Gas budget for this call
+        /// Gas budget for this call.
+        /// Defaults to the default gas budget of the active environment.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
 
         /// Instead of executing the transaction, serialize the bcs bytes of the unsigned transaction data
         /// (TransactionData) using base64 encoding, and print out the string.
@@ -441,14 +539,15 @@ pub enum SuiClientCommands {
     #[clap(name = "switch")]
     Switch {
         /// An address to be used as the active address for subsequent
-        /// commands. It accepts also the alias of the address.
+        /// commands in the active environment, or in `env` if given. It accepts also the alias of
+        /// the address.
         #[clap(long)]
         address: Option<KeyIdentity>,
         /// The RPC server URL (e.g., local rpc server, devnet rpc server, etc) to be
         /// used for subsequent commands.
         #[clap(long)]
         env: Option<String>,
-        /// The dWallet alias to be used for subsequent commands.
+        /// The alias or ID of the dWallet to be used for subsequent commands.
         #[clap(long)]
         dwallet: Option<String>,
     },
@@ -477,9 +576,10 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
 
-        /// Gas budget for this transfer
+        /// Gas budget for this transfer.
+        /// Defaults to the default gas budget of the active environment.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
 
         /// Instead of executing the transaction, serialize the bcs bytes of the unsigned transaction data
         /// (TransactionData) using base64 encoding, and print out the string.
@@ -505,9 +605,10 @@ pub enum SuiClientCommands {
         #[clap(long)]
         sui_coin_object_id: ObjectID,
 
-        /// Gas budget for this transfer
+        /// Gas budget for this transfer.
+        /// Defaults to the default gas budget of the active environment.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
 
         /// The amount to transfer, if not specified, the entire coin object will be transferred.
         #[clap(long)]
@@ -544,9 +645,10 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
 
-        /// Gas budget for running module initializers
+        /// Gas budget for running module initializers.
+        /// Defaults to the default gas budget of the active environment.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
 
         /// Publish the package without checking whether compiling dependencies from source results
         /// in bytecode matching the dependencies found on-chain.
@@ -642,11 +744,38 @@ pub enum SuiClientCommands {
     },
 
     /// dWallet subcommands.
+    /// With `--json`, they print their result as JSON, with camelCase field names. Previews and
+    /// confirmation prompts are printed to stderr, for stdout to hold the result only.
     #[command(name = "dwallet")]
     DWallet {
+        #[clap(flatten)]
+        wait: DWalletWaitOptions,
         #[clap(subcommand)]
         cmd: Option<SuiDWalletCommands>,
     },
+
+    /// View and edit the settings of the environments in the client config.
+    #[command(name = "env")]
+    Env {
+        #[clap(subcommand)]
+        cmd: SuiEnvCommands,
+    },
+
+    /// Manage the profiles of the client config, named bundles of an environment, an active
+    /// address, dWallets and gas settings, switched between with `sui client --profile`.
+    #[command(name = "profile")]
+    Profile {
+        #[clap(subcommand)]
+        cmd: SuiProfileCommands,
+    },
+
+    /// Initialize, update and inspect the on-chain light clients of the Ethereum networks of the
+    /// active environment.
+    #[command(name = "eth-lightclient")]
+    EthLightClient {
+        #[clap(subcommand)]
+        cmd: SuiEthLightClientCommands,
+    },
 }
 
 impl SuiClientCommands {
@@ -714,6 +843,19 @@ impl SuiClientCommands {
                 };
                 SuiClientCommandResult::Addresses(output)
             }
+            SuiClientCommands::AddAddressAlias { alias, address } => {
+                context.config.add_address_alias(alias, address)?;
+                context.config.save()?;
+                SuiClientCommandResult::AddressAliases(context.config.address_aliases.clone())
+            }
+            SuiClientCommands::AddressAliases => {
+                SuiClientCommandResult::AddressAliases(context.config.address_aliases.clone())
+            }
+            SuiClientCommands::RemoveAddressAlias { alias } => {
+                context.config.remove_address_alias(&alias)?;
+                context.config.save()?;
+                SuiClientCommandResult::AddressAliases(context.config.address_aliases.clone())
+            }
             SuiClientCommands::DynamicFieldQuery { id, cursor, limit } => {
                 let client = context.get_client().await?;
                 let df_read = client
@@ -734,6 +876,7 @@ impl SuiClientCommands {
                 serialize_unsigned_transaction,
                 serialize_signed_transaction,
             } => {
+                let gas_budget = context.gas_budget(gas_budget)?;
                 let sender = context.try_get_object_owner(&gas).await?;
                 let sender = sender.unwrap_or(context.active_address()?);
 
@@ -821,6 +964,7 @@ impl SuiClientCommands {
                 serialize_unsigned_transaction,
                 serialize_signed_transaction,
             } => {
+                let gas_budget = context.gas_budget(gas_budget)?;
                 if build_config.test_mode {
                     return Err(SuiError::ModulePublishFailure {
                         error:
@@ -948,6 +1092,7 @@ impl SuiClientCommands {
                 serialize_unsigned_transaction,
                 serialize_signed_transaction,
             } => {
+                let gas_budget = context.gas_budget(gas_budget)?;
                 let tx_data = construct_move_call_transaction(
                     package, &module, &function, type_args, gas, gas_budget, args, context,
                 )
@@ -969,6 +1114,7 @@ impl SuiClientCommands {
                 serialize_unsigned_transaction,
                 serialize_signed_transaction,
             } => {
+                let gas_budget = context.gas_budget(gas_budget)?;
                 let from = context.get_object_owner(&object_id).await?;
                 let to = get_identity_address(Some(to), context)?;
                 let client = context.get_client().await?;
@@ -993,6 +1139,7 @@ impl SuiClientCommands {
                 serialize_unsigned_transaction,
                 serialize_signed_transaction,
             } => {
+                let gas_budget = context.gas_budget(gas_budget)?;
                 let from = context.get_object_owner(&object_id).await?;
                 let to = get_identity_address(Some(to), context)?;
                 let client = context.get_client().await?;
@@ -1018,6 +1165,7 @@ impl SuiClientCommands {
                 serialize_unsigned_transaction,
                 serialize_signed_transaction,
             } => {
+                let gas_budget = context.gas_budget(gas_budget)?;
                 ensure!(
                     !input_coins.is_empty(),
                     "Pay transaction requires a non-empty list of input coins"
@@ -1062,6 +1210,7 @@ impl SuiClientCommands {
                 serialize_unsigned_transaction,
                 serialize_signed_transaction,
             } => {
+                let gas_budget = context.gas_budget(gas_budget)?;
                 ensure!(
                     !input_coins.is_empty(),
                     "PaySui transaction requires a non-empty list of input coins"
@@ -1105,6 +1254,7 @@ impl SuiClientCommands {
                 serialize_unsigned_transaction,
                 serialize_signed_transaction,
             } => {
+                let gas_budget = context.gas_budget(gas_budget)?;
                 ensure!(
                     !input_coins.is_empty(),
                     "PayAllSui transaction requires a non-empty list of input coins"
@@ -1179,6 +1329,90 @@ impl SuiClientCommands {
                     recovery_phrase: phrase,
                 })
             }
+            SuiClientCommands::RotateKey {
+                address,
+                key_scheme,
+                word_length,
+                derivation_path,
+                gas_budget,
+            } => {
+                let old_address = get_identity_address(address, context)?;
+                let (address, phrase, scheme) = context.config.keystore.rotate_key(
+                    &old_address,
+                    key_scheme,
+                    derivation_path,
+                    word_length,
+                )?;
+                let alias = context.config.keystore.get_alias_by_address(&address)?;
+
+                let gas_budget = gas_budget.or(context.config.get_active_env()?.default_gas_budget);
+                let mut client = DWalletClient::new(context, old_address);
+                if let Some(gas_budget) = gas_budget {
+                    client = client.with_gas_budget(gas_budget);
+                }
+                let (dwallets, response) = client
+                    .transfer_all_dwallets(address)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "Rotated the key of [{old_address}] to [{address}], but cannot transfer its dWallets"
+                        )
+                    })?;
+                let dwallet_ids: Vec<_> =
+                    dwallets.iter().map(|dwallet| dwallet.dwallet_id).collect();
+
+                // The encrypted user shares of the transferred dWallets follow the key of their owner.
+                if context.config.dwallets.iter().any(|dwallet| {
+                    dwallet.encrypted_share_path.is_some()
+                        && dwallet_ids.contains(&dwallet.dwallet_id)
+                }) {
+                    let old_key =
+                        ShareEncryptionKey::derive(context.config.keystore.get_key(&old_address)?);
+                    let new_key =
+                        ShareEncryptionKey::derive(context.config.keystore.get_key(&address)?);
+                    for dwallet in context
+                        .config
+                        .dwallets
+                        .iter_mut()
+                        .filter(|dwallet| dwallet_ids.contains(&dwallet.dwallet_id))
+                    {
+                        dwallet.reencrypt_share(&old_key, &new_key)?;
+                    }
+                }
+                context.config.replace_address(old_address, address);
+                context.config.save()?;
+
+                SuiClientCommandResult::RotateKey(RotateKeyOutput {
+                    alias,
+                    old_address,
+                    address,
+                    key_scheme: scheme,
+                    recovery_phrase: phrase,
+                    transferred_dwallets: dwallet_ids,
+                    digest: response.map(|response| response.digest),
+                })
+            }
+            SuiClientCommands::Faucet { address, url } => {
+                let address = get_identity_address(address, context)?;
+                let url = match url {
+                    Some(url) => url,
+                    None => context.config.get_active_env()?.faucet_url()?.to_string(),
+                };
+                let faucet = FaucetClient::new(&url);
+                let task = faucet.submit_request(address).await?;
+                let spinner = ProgressBar::new_spinner().with_finish(ProgressFinish::AndClear);
+                spinner.set_message(format!("Waiting for the faucet to serve request [{task}]"));
+                spinner.enable_steady_tick(Duration::from_millis(100));
+                let coins = faucet.wait_for_request(&task).await?;
+                drop(spinner);
+
+                SuiClientCommandResult::Faucet(FaucetOutput {
+                    address,
+                    url,
+                    amount: coins.iter().map(|coin| coin.amount).sum(),
+                    coins,
+                })
+            }
             SuiClientCommands::Gas { address } => {
                 let address = get_identity_address(address, context)?;
                 let coins = context
@@ -1208,6 +1442,7 @@ impl SuiClientCommands {
                 serialize_unsigned_transaction,
                 serialize_signed_transaction,
             } => {
+                let gas_budget = context.gas_budget(gas_budget)?;
                 let signer = context.get_object_owner(&coin_id).await?;
                 let client = context.get_client().await?;
                 let data = match (amounts, count) {
@@ -1246,6 +1481,7 @@ impl SuiClientCommands {
                 serialize_unsigned_transaction,
                 serialize_signed_transaction,
             } => {
+                let gas_budget = context.gas_budget(gas_budget)?;
                 let client = context.get_client().await?;
                 let signer = context.get_object_owner(&primary_coin).await?;
                 let data = client
@@ -1269,19 +1505,20 @@ impl SuiClientCommands {
                     ));
                 }
 
+                // The address is switched in the new environment.
+                if let Some(ref env) = env {
+                    Self::switch_env(&mut context.config, env)?;
+                }
+
                 if let Some(address) = address.clone() {
                     let address = get_identity_address(Some(address), context)?;
                     if !context.config.keystore.addresses().contains(&address) {
                         return Err(anyhow!("Address {} not managed by wallet", address));
                     }
-                    context.config.active_address = Some(address);
+                    context.config.set_active_address(address);
                     addr = Some(address.to_string());
                 }
 
-                if let Some(ref env) = env {
-                    Self::switch_env(&mut context.config, env)?;
-                }
-
                 if let Some(ref dwallet) = dwallet {
                     Self::switch_dwallet(&mut context.config, dwallet)?;
                 }
@@ -1320,13 +1557,49 @@ impl SuiClientCommands {
                 let response = context.execute_transaction_may_fail(transaction).await?;
                 SuiClientCommandResult::ExecuteSignedTx(response)
             }
-            SuiClientCommands::NewEnv { alias, rpc, ws } => {
+            SuiClientCommands::NewEnv {
+                alias,
+                rpc,
+                fallback_rpcs,
+                ws,
+                faucet,
+                proxy,
+                headers,
+                bearer_token,
+                default_gas_budget,
+                default_gas_price,
+            } => {
                 if context.config.envs.iter().any(|env| env.alias == alias) {
                     return Err(anyhow!(
                         "Environment config with name [{alias}] already exists."
                     ));
                 }
-                let env = SuiEnv { alias, rpc, ws };
+                let mut builder = SuiEnv::builder(alias, rpc);
+                for fallback_rpc in fallback_rpcs {
+                    builder = builder.fallback_rpc(fallback_rpc);
+                }
+                if let Some(ws) = ws {
+                    builder = builder.ws(ws);
+                }
+                if let Some(faucet) = faucet {
+                    builder = builder.faucet(faucet);
+                }
+                if let Some(proxy) = proxy {
+                    builder = builder.proxy(proxy);
+                }
+                for (name, value) in headers {
+                    builder = builder.header(name, value);
+                }
+                if let Some(bearer_token) = bearer_token {
+                    builder = builder.bearer_token(bearer_token);
+                }
+                if let Some(gas_budget) = default_gas_budget {
+                    builder = builder.default_gas_budget(gas_budget);
+                }
+                if let Some(gas_price) = default_gas_price {
+                    builder = builder.default_gas_price(gas_price);
+                }
+                let env = builder.build()?;
 
                 // Check urls are valid and server is reachable
                 env.create_rpc_client(None, None).await?;
@@ -1380,10 +1653,11 @@ impl SuiClientCommands {
                 SuiClientCommandResult::VerifySource
             }
             SuiClientCommands::DWallet {
+                wait,
                 cmd,
             } => {
                 if let Some(cmd) = cmd {
-                    cmd.execute(context).await?
+                    cmd.execute(context, &wait).await?
                 } else {
                     // Print help
                     let mut app: Command = SuiCommand::command();
@@ -1394,6 +1668,9 @@ impl SuiClientCommands {
                     );
                 }
             }
+            SuiClientCommands::Env { cmd } => cmd.execute(context).await?,
+            SuiClientCommands::EthLightClient { cmd } => cmd.execute(context).await?,
+            SuiClientCommands::Profile { cmd } => cmd.execute(context).await?,
         });
         ret
     }
@@ -1406,9 +1683,8 @@ impl SuiClientCommands {
     }
 
     pub fn switch_dwallet(config: &mut SuiClientConfig, dwallet: &str) -> Result<(), anyhow::Error> {
-        let dwallet = Some(dwallet.into());
-        ensure!(config.get_env(&dwallet).is_some(), "dWallet config not found for [{dwallet:?}], create a new dWallet using the `sui client dwallet create` command.");
-        config.active_dwallet = dwallet;
+        let dwallet = config.resolve_dwallet(dwallet).map_err(|e| anyhow!("{e}, create a new dWallet using the `sui client dwallet create` command."))?;
+        config.active_dwallet = Some(dwallet.alias.clone());
         Ok(())
     }
 }
@@ -1534,6 +1810,16 @@ impl Display for SuiClientCommandResult {
                 table.with(style);
                 write!(f, "{}", table)?
             }
+            SuiClientCommandResult::AddressAliases(aliases) => {
+                let mut builder = TableBuilder::default();
+                builder.set_header(["alias", "address"]);
+                for (alias, address) in aliases {
+                    builder.push_record([alias.to_string(), address.to_string()]);
+                }
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                write!(f, "{}", table)?
+            }
             SuiClientCommandResult::DynamicFieldQuery(df_refs) => {
                 let df_refs = DynamicFieldOutput {
                     has_next_page: df_refs.has_next_page,
@@ -1618,11 +1904,47 @@ impl Display for SuiClientCommandResult {
 
                 write!(f, "{}", table)?
             }
+            SuiClientCommandResult::RotateKey(rotated) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec!["alias", rotated.alias.as_str()]);
+                builder.push_record(vec!["oldAddress", rotated.old_address.to_string().as_str()]);
+                builder.push_record(vec!["address", rotated.address.to_string().as_str()]);
+                builder.push_record(vec!["keyScheme", rotated.key_scheme.to_string().as_str()]);
+                builder.push_record(vec!["recoveryPhrase", rotated.recovery_phrase.as_str()]);
+                for dwallet_id in &rotated.transferred_dwallets {
+                    builder
+                        .push_record(vec!["transferredDWallet", dwallet_id.to_string().as_str()]);
+                }
+                if let Some(digest) = &rotated.digest {
+                    builder.push_record(vec!["transactionDigest", digest.to_string().as_str()]);
+                }
+
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                table.with(TablePanel::header(
+                    "Rotated the key of the address and saved the new keypair to keystore.",
+                ));
+
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_bottom_right('┬')),
+                );
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_top_right('─')),
+                );
+
+                write!(f, "{}", table)?
+            }
             SuiClientCommandResult::NewDWallet(new_dwallet) => {
                 let mut builder = TableBuilder::default();
                 builder.push_record(vec!["alias", new_dwallet.alias.as_str()]);
                 builder.push_record(vec!["dwallet_id", new_dwallet.dwallet_id.to_string().as_str()]);
                 builder.push_record(vec!["dwallet_cap_id", new_dwallet.dwallet_cap_id.to_string().as_str()]);
+                for (chain, address) in &new_dwallet.external_addresses {
+                    builder.push_record(vec![format!("{chain} address").as_str(), address.as_str()]);
+                }
+                builder.push_record(vec!["user_share", new_dwallet.user_share.as_str()]);
 
                 let mut table = builder.build();
                 table.with(TableStyle::rounded());
@@ -1641,20 +1963,72 @@ impl Display for SuiClientCommandResult {
 
                 write!(f, "{}", table)?
             }
-            SuiClientCommandResult::NewSignOutput(sign_output) => {
+            SuiClientCommandResult::DWalletAddresses(output) => {
                 let mut builder = TableBuilder::default();
-                builder.push_record(vec!["dwallet_id", sign_output.dwallet_id.to_string().as_str()]);
-                builder.push_record(vec!["sign_output_id", sign_output.sign_output_id.to_string().as_str()]);
-                builder.push_record(vec!["signatures:", ""]);
+                builder.push_record(vec!["dwallet_id", output.dwallet_id.to_string().as_str()]);
+                for (chain, address) in &output.addresses {
+                    builder.push_record(vec![format!("{chain} address").as_str(), address.as_str()]);
+                }
 
-                for signature in &sign_output.signatures {
-                    builder.push_record(vec!["", signature.as_str()]);
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::DWalletBench(bench) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec!["dwallets", bench.dwallets.to_string().as_str()]);
+                builder.push_record(vec!["requests", bench.requests.to_string().as_str()]);
+                builder.push_record(vec!["messages_per_request", bench.messages_per_request.to_string().as_str()]);
+                builder.push_record(vec!["failures", bench.failures.to_string().as_str()]);
+                if let Some(error) = &bench.first_error {
+                    builder.push_record(vec!["first_error", error.as_str()]);
+                }
+                builder.push_record(vec!["duration_ms", bench.duration_ms.to_string().as_str()]);
+                builder.push_record(vec!["throughput", format!("{:.2}/s", bench.throughput).as_str()]);
+                let latencies = [("dkg", &bench.dkg_latency), ("sign", &bench.sign_latency)];
+                for (operation, latency) in latencies {
+                    builder.push_record(vec![
+                        format!("{operation} latency").as_str(),
+                        format!(
+                            "mean {}ms, p50 {}ms, p90 {}ms, p99 {}ms, max {}ms ({} samples)",
+                            latency.mean_ms,
+                            latency.p50_ms,
+                            latency.p90_ms,
+                            latency.p99_ms,
+                            latency.max_ms,
+                            latency.samples
+                        )
+                        .as_str(),
+                    ]);
                 }
 
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                table.with(TablePanel::header("Benchmarked signing with dWallets."));
+
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_bottom_right('┬')),
+                );
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_top_right('─')),
+                );
+
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::DWalletBinding(binding) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec!["binding_id", binding.binding_id.to_string().as_str()]);
+                builder.push_record(vec!["dwallet_id", binding.dwallet_id.to_string().as_str()]);
+                builder.push_record(vec!["chain", binding.chain.as_str()]);
+                builder.push_record(vec!["external_address", binding.external_address.as_str()]);
+                builder.push_record(vec!["digest", binding.digest.to_string().as_str()]);
+
                 let mut table = builder.build();
                 table.with(TableStyle::rounded());
                 table.with(TablePanel::header(
-                    "MPC completed and sign output object was generated (signatures in base64).",
+                    "Bound the dWallet to its address on the external chain.",
                 ));
 
                 table.with(
@@ -1668,88 +2042,698 @@ impl Display for SuiClientCommandResult {
 
                 write!(f, "{}", table)?
             }
-            SuiClientCommandResult::Object(object_read) => match object_read.object() {
-                Ok(obj) => {
-                    let object = ObjectOutput::from(obj);
-                    let json_obj = json!(&object);
-                    let mut table = json_to_table(&json_obj);
-                    table.with(TableStyle::rounded().horizontals([]));
-                    writeln!(f, "{}", table)?
+            SuiClientCommandResult::DWallets(output) => {
+                if output.dwallets.is_empty() {
+                    writeln!(f, "No dWallets owned by [{}]", output.owner)?;
                 }
-                Err(e) => writeln!(f, "Internal error, cannot read the object: {e}")?,
-            },
-            SuiClientCommandResult::Objects(object_refs) => {
-                if object_refs.is_empty() {
-                    writeln!(f, "This address has no owned objects.")?
-                } else {
-                    let objects = ObjectsOutput::from_vec(object_refs.to_vec());
-                    match objects {
-                        Ok(objs) => {
-                            let json_obj = json!(objs);
-                            let mut table = json_to_table(&json_obj);
-                            table.with(TableStyle::rounded().horizontals([]));
-                            writeln!(f, "{}", table)?
-                        }
-                        Err(e) => write!(f, "Internal error: {e}")?,
+                for dwallet in &output.dwallets {
+                    let mut builder = TableBuilder::default();
+                    builder.push_record(vec!["alias", dwallet.alias.as_deref().unwrap_or("")]);
+                    builder.push_record(vec!["dwallet_id", dwallet.dwallet_id.to_string().as_str()]);
+                    builder.push_record(vec!["dwallet_cap_id", dwallet.dwallet_cap_id.to_string().as_str()]);
+                    builder.push_record(vec!["curve", dwallet.curve.to_string().as_str()]);
+                    builder.push_record(vec!["creation_epoch", dwallet.creation_epoch.to_string().as_str()]);
+                    builder.push_record(vec!["presigns", dwallet.presigns.to_string().as_str()]);
+                    for (chain, address) in &dwallet.external_addresses {
+                        builder.push_record(vec![format!("{chain} address").as_str(), address.as_str()]);
                     }
+
+                    let mut table = builder.build();
+                    table.with(TableStyle::rounded());
+                    writeln!(f, "{}", table)?
                 }
             }
-            SuiClientCommandResult::Upgrade(response)
-            | SuiClientCommandResult::Publish(response) => {
-                write!(writer, "{}", response)?;
-            }
-            SuiClientCommandResult::TransactionBlock(response) => {
-                write!(writer, "{}", response)?;
-            }
-            SuiClientCommandResult::RawObject(raw_object_read) => {
-                let raw_object = match raw_object_read.object() {
-                    Ok(v) => match &v.bcs {
-                        Some(SuiRawData::MoveObject(o)) => {
-                            format!("{:?}\nNumber of bytes: {}", o.bcs_bytes, o.bcs_bytes.len())
-                        }
-                        Some(SuiRawData::Package(p)) => {
-                            let mut temp = String::new();
-                            let mut bcs_bytes = 0usize;
-                            for m in &p.module_map {
-                                temp.push_str(&format!("{:?}\n", m));
-                                bcs_bytes += m.1.len()
-                            }
-                            format!("{}Number of bytes: {}", temp, bcs_bytes)
-                        }
-                        None => "Bcs field is None".to_string().red().to_string(),
-                    },
-                    Err(err) => format!("{err}").red().to_string(),
-                };
-                writeln!(writer, "{}", raw_object)?;
-            }
-            SuiClientCommandResult::Call(response) => {
-                write!(writer, "{}", response)?;
-            }
-            SuiClientCommandResult::SerializedUnsignedTransaction(tx_data) => {
-                writeln!(
-                    writer,
-                    "{}",
-                    fastcrypto::encoding::Base64::encode(bcs::to_bytes(tx_data).unwrap())
-                )?;
-            }
-            SuiClientCommandResult::SerializedSignedTransaction(sender_signed_tx) => {
+            SuiClientCommandResult::DWalletWatch(watch) => {
                 writeln!(
-                    writer,
-                    "{}",
-                    fastcrypto::encoding::Base64::encode(bcs::to_bytes(sender_signed_tx).unwrap())
+                    f,
+                    "Stopped watching dWallet [{}] after {} event(s).",
+                    watch.dwallet_id, watch.events
                 )?;
             }
-            SuiClientCommandResult::Transfer(response) => {
-                write!(writer, "{}", response)?;
-            }
-            SuiClientCommandResult::TransferSui(response) => {
-                write!(writer, "{}", response)?;
-            }
-            SuiClientCommandResult::Pay(response) => {
-                write!(writer, "{}", response)?;
+            SuiClientCommandResult::DWalletTransfer(transfer) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec!["dwallet_id", transfer.dwallet_id.to_string().as_str()]);
+                builder.push_record(vec!["recipient", transfer.recipient.to_string().as_str()]);
+                builder.push_record(vec!["encrypted_user_share_id", transfer.encrypted_user_share_id.to_string().as_str()]);
+                builder.push_record(vec!["encryption_key_id", transfer.encryption_key_id.to_string().as_str()]);
+                builder.push_record(vec!["decryption_verified", transfer.decryption_verified.to_string().as_str()]);
+                builder.push_record(vec!["digest", transfer.digest.to_string().as_str()]);
+
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                table.with(TablePanel::header(
+                    "Transferred the dWallet with its user share, encrypted to the recipient.",
+                ));
+
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_bottom_right('┬')),
+                );
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_top_right('─')),
+                );
+
+                write!(f, "{}", table)?
             }
-            SuiClientCommandResult::PaySui(response) => {
-                write!(writer, "{}", response)?;
+            SuiClientCommandResult::EncryptionKey(encryption_key) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec!["owner", encryption_key.owner.to_string().as_str()]);
+                builder.push_record(vec!["encryption_key_id", encryption_key.encryption_key_id.to_string().as_str()]);
+                builder.push_record(vec!["encryption_key", encryption_key.encryption_key.as_str()]);
+
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                table.with(TablePanel::header(
+                    "Published the encryption key of the address.",
+                ));
+
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_bottom_right('┬')),
+                );
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_top_right('─')),
+                );
+
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::EthLightClientInit(init) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec!["network", init.network.as_str()]);
+                builder.push_record(vec!["chain_id", init.chain_id.to_string().as_str()]);
+                builder.push_record(vec!["state_object_id", init.state_object_id.to_string().as_str()]);
+                builder.push_record(vec!["checkpoint_epoch", init.checkpoint_epoch.to_string().as_str()]);
+                builder.push_record(vec!["finalized_slot", init.finalized_slot.to_string().as_str()]);
+                builder.push_record(vec!["latest_block_number", init.latest_block_number.to_string().as_str()]);
+                builder.push_record(vec!["digest", init.digest.to_string().as_str()]);
+
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                table.with(TablePanel::header(
+                    "Created the Ethereum light client, and recorded its state object in the config.",
+                ));
+
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_bottom_right('┬')),
+                );
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_top_right('─')),
+                );
+
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::EthLightClientStatus(status) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec!["network", status.network.as_str()]);
+                builder.push_record(vec!["chain_id", status.chain_id.to_string().as_str()]);
+                builder.push_record(vec!["state_object_id", status.state_object_id.to_string().as_str()]);
+                builder.push_record(vec!["finalized_slot", status.finalized_slot.to_string().as_str()]);
+                builder.push_record(vec!["latest_block_number", status.latest_block_number.to_string().as_str()]);
+                builder.push_record(vec!["sync_committee_period", status.sync_committee_period.to_string().as_str()]);
+                builder.push_record(vec!["next_sync_committee_known", status.next_sync_committee_known.to_string().as_str()]);
+                if let (Some(chain_head_slot), Some(slots_behind)) = (status.chain_head_slot, status.slots_behind) {
+                    builder.push_record(vec!["chain_head_slot", chain_head_slot.to_string().as_str()]);
+                    builder.push_record(vec!["slots_behind", slots_behind.to_string().as_str()]);
+                }
+
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                table.with(TablePanel::header(
+                    "Ethereum light client status.",
+                ));
+
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_bottom_right('┬')),
+                );
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_top_right('─')),
+                );
+
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::EthLightClientUpdate(update) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec!["network", update.network.as_str()]);
+                builder.push_record(vec!["state_object_id", update.state_object_id.to_string().as_str()]);
+                builder.push_record(vec!["submitted_updates", update.submitted_updates.to_string().as_str()]);
+                builder.push_record(vec!["finalized_slot", update.finalized_slot.to_string().as_str()]);
+                builder.push_record(vec!["latest_block_number", update.latest_block_number.to_string().as_str()]);
+                builder.push_record(vec!["chain_head_slot", update.chain_head_slot.to_string().as_str()]);
+                for digest in &update.digests {
+                    builder.push_record(vec!["digest", digest.to_string().as_str()]);
+                }
+
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                table.with(TablePanel::header(if update.submitted_updates == 0 {
+                    "The Ethereum light client is up to date."
+                } else {
+                    "Submitted the verified Ethereum light client updates."
+                }));
+
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_bottom_right('┬')),
+                );
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_top_right('─')),
+                );
+
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::EthNetworks(output) => {
+                if output.networks.is_empty() {
+                    writeln!(f, "No Ethereum networks configured for env [{}]", output.env)?;
+                }
+                for network in &output.networks {
+                    let mut builder = TableBuilder::default();
+                    builder.push_record(vec!["chain_id", network.chain_id.to_string().as_str()]);
+                    builder.push_record(vec!["execution_rpc", network.execution_rpc.as_str()]);
+                    if let Some(consensus_rpc) = &network.consensus_rpc {
+                        builder.push_record(vec!["consensus_rpc", consensus_rpc.as_str()]);
+                    }
+                    if let Some(requests_per_second) = network.consensus_rpc_requests_per_second {
+                        builder.push_record(vec!["consensus_rpc_requests_per_second", requests_per_second.to_string().as_str()]);
+                    }
+                    if let Some(state_object_id) = network.state_object_id {
+                        builder.push_record(vec!["state_object_id", state_object_id.to_string().as_str()]);
+                    }
+                    if let Some(finality) = network.finality {
+                        let finality = match finality {
+                            EthFinality::Finalized => "finalized".to_string(),
+                            EthFinality::Depth(depth) => format!("{depth} blocks deep"),
+                        };
+                        builder.push_record(vec!["finality", finality.as_str()]);
+                    }
+                    if let Some(explorer_url) = &network.explorer_url {
+                        builder.push_record(vec!["explorer_url", explorer_url.as_str()]);
+                    }
+                    if let Some(checkpoint_epoch) = network.checkpoint_epoch {
+                        builder.push_record(vec!["checkpoint_epoch", checkpoint_epoch.to_string().as_str()]);
+                    }
+
+                    let mut table = builder.build();
+                    table.with(TableStyle::rounded());
+                    table.with(TablePanel::header(format!(
+                        "Ethereum network [{}] of env [{}]",
+                        network.name, output.env
+                    )));
+
+                    table.with(
+                        TableModify::new(TableCell::new(0, 0))
+                            .with(TableBorder::default().corner_bottom_right('┬')),
+                    );
+                    table.with(
+                        TableModify::new(TableCell::new(0, 0))
+                            .with(TableBorder::default().corner_top_right('─')),
+                    );
+
+                    writeln!(f, "{}", table)?
+                }
+            }
+            SuiClientCommandResult::Faucet(output) => {
+                let mut builder = TableBuilder::default();
+                builder.set_header(["coin_id", "amount", "transfer_tx_digest"]);
+                for coin in &output.coins {
+                    builder.push_record(vec![
+                        coin.id.to_string(),
+                        coin.amount.to_string(),
+                        coin.transfer_tx_digest.to_string(),
+                    ]);
+                }
+
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                table.with(TablePanel::header(format!(
+                    "Received {} MIST from the faucet for [{}].",
+                    output.amount, output.address
+                )));
+
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_bottom_right('┬')),
+                );
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_top_right('─')),
+                );
+
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::FutureSign(future_sign) => {
+                let mut builder = TableBuilder::default();
+                push_future_sign_records(&mut builder, future_sign);
+
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                table.with(TablePanel::header(
+                    "Approved the messages for the dWallet to sign later.",
+                ));
+
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_bottom_right('┬')),
+                );
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_top_right('─')),
+                );
+
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::FutureSigns(output) => {
+                if output.future_signs.is_empty() {
+                    writeln!(f, "No future-sign approvals owned by [{}]", output.owner)?;
+                }
+                for future_sign in &output.future_signs {
+                    let mut builder = TableBuilder::default();
+                    push_future_sign_records(&mut builder, future_sign);
+
+                    let mut table = builder.build();
+                    table.with(TableStyle::rounded());
+                    writeln!(f, "{}", table)?
+                }
+            }
+            SuiClientCommandResult::SigningPolicy(policy) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec!["policy_id", policy.policy_id.to_string().as_str()]);
+                builder.push_record(vec!["alias", policy.alias.as_deref().unwrap_or("")]);
+                builder.push_record(vec!["owner", policy.owner.to_string().as_str()]);
+                builder.push_record(vec!["dwallet_cap_id", policy.dwallet_cap_id.to_string().as_str()]);
+                let signers = if policy.allowlist.is_empty() {
+                    "the owner only".to_string()
+                } else {
+                    format!("the owner and {} allowlisted addresses", policy.allowlist.len())
+                };
+                builder.push_record(vec!["signers", signers.as_str()]);
+                for address in &policy.allowlist {
+                    builder.push_record(vec!["allowed", address.to_string().as_str()]);
+                }
+                let rate_limit = if policy.max_messages == 0 {
+                    "none".to_string()
+                } else {
+                    let window = Duration::from_millis(policy.window_ms);
+                    format!("{} messages per {window:?}", policy.max_messages)
+                };
+                builder.push_record(vec!["rate_limit", rate_limit.as_str()]);
+                let co_approval = if policy.co_approval_threshold == 0 {
+                    "none".to_string()
+                } else {
+                    format!(
+                        "{} of {} co-approvers",
+                        policy.co_approval_threshold,
+                        policy.co_approvers.len()
+                    )
+                };
+                builder.push_record(vec!["co_approval", co_approval.as_str()]);
+                for address in &policy.co_approvers {
+                    builder.push_record(vec!["co_approver", address.to_string().as_str()]);
+                }
+                builder.push_record(vec!["pending_requests", policy.pending_requests.to_string().as_str()]);
+                if let Some(digest) = policy.digest {
+                    builder.push_record(vec!["digest", digest.to_string().as_str()]);
+                }
+
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                if policy.digest.is_some() {
+                    table.with(TablePanel::header("Set the signing policy of the dWallet."));
+                    table.with(
+                        TableModify::new(TableCell::new(0, 0))
+                            .with(TableBorder::default().corner_bottom_right('┬')),
+                    );
+                    table.with(
+                        TableModify::new(TableCell::new(0, 0))
+                            .with(TableBorder::default().corner_top_right('─')),
+                    );
+                }
+
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::SignBatch(output) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec!["dwallet_id", output.dwallet_id.to_string().as_str()]);
+                builder.push_record(vec!["signed_messages", output.signed_messages.to_string().as_str()]);
+                builder.push_record(vec!["output", output.output.display().to_string().as_str()]);
+                for session in &output.sessions {
+                    builder.push_record(vec![
+                        format!("session {}", session.session_id).as_str(),
+                        format!("{} {} messages, sign output {}", session.messages, session.hash, session.sign_output_id).as_str(),
+                    ]);
+                }
+
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                table.with(TablePanel::header(
+                    "Signed the messages of the manifest.",
+                ));
+
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_bottom_right('┬')),
+                );
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_top_right('─')),
+                );
+
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::SignDryRun(dry_runs) => {
+                for dry_run in dry_runs {
+                    let mut builder = TableBuilder::default();
+                    builder.push_record(vec!["dwallet_id", dry_run.dwallet_id.to_string().as_str()]);
+                    builder.push_record(vec!["messages", dry_run.messages.to_string().as_str()]);
+                    builder.push_record(vec!["hash", dry_run.hash.as_str()]);
+                    let messages_size = match dry_run.max_messages_size {
+                        Some(max_messages_size) => format!("{} / {max_messages_size} bytes", dry_run.messages_size),
+                        None => format!("{} bytes", dry_run.messages_size),
+                    };
+                    builder.push_record(vec!["messages_size", messages_size.as_str()]);
+                    if let Some(presign_gas_budget) = dry_run.presign_gas_budget {
+                        builder.push_record(vec!["presign_gas_budget", presign_gas_budget.to_string().as_str()]);
+                    }
+                    builder.push_record(vec!["unused_presigns", dry_run.unused_presigns.to_string().as_str()]);
+                    for error in &dry_run.errors {
+                        builder.push_record(vec!["error", error.as_str()]);
+                    }
+                    for warning in &dry_run.warnings {
+                        builder.push_record(vec!["warning", warning.as_str()]);
+                    }
+
+                    let mut table = builder.build();
+                    table.with(TableStyle::rounded());
+                    table.with(TablePanel::header(if dry_run.would_succeed {
+                        "Dry run: the sign request would be made."
+                    } else {
+                        "Dry run: the sign request would fail."
+                    }));
+
+                    table.with(
+                        TableModify::new(TableCell::new(0, 0))
+                            .with(TableBorder::default().corner_bottom_right('┬')),
+                    );
+                    table.with(
+                        TableModify::new(TableCell::new(0, 0))
+                            .with(TableBorder::default().corner_top_right('─')),
+                    );
+
+                    writeln!(f, "{}", table)?
+                }
+            }
+            SuiClientCommandResult::SignProof(proof) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec!["session_id", proof.session_id.to_string().as_str()]);
+                builder.push_record(vec!["dwallet_id", proof.dwallet_id.to_string().as_str()]);
+                builder.push_record(vec!["public_key", proof.public_key.as_str()]);
+                builder.push_record(vec!["hash", proof.hash.as_str()]);
+                for (i, (message, signature)) in proof.messages.iter().zip(&proof.signatures).enumerate() {
+                    builder.push_record(vec![format!("message {i}").as_str(), message.as_str()]);
+                    builder.push_record(vec![format!("signature {i}").as_str(), signature.as_str()]);
+                }
+                for (name, checkpoint) in ["session", "sign_output", "dwallet"].iter().zip(&proof.checkpoints) {
+                    let checkpoint = format!(
+                        "{} (epoch {}, digest {})",
+                        checkpoint.sequence_number, checkpoint.epoch, checkpoint.digest
+                    );
+                    builder.push_record(vec![format!("{name} checkpoint").as_str(), checkpoint.as_str()]);
+                }
+                if let Some(output) = &proof.output {
+                    builder.push_record(vec!["output", output.display().to_string().as_str()]);
+                }
+
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                table.with(TablePanel::header(if proof.output.is_some() {
+                    "Exported the proof of the sign session."
+                } else {
+                    "The proof is valid: the network signed the messages with the dWallet."
+                }));
+
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_bottom_right('┬')),
+                );
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_top_right('─')),
+                );
+
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::SignRetry(retry) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec!["dwallet_id", retry.dwallet_id.to_string().as_str()]);
+                builder.push_record(vec!["retried_session_id", retry.retried_session_id.to_string().as_str()]);
+                builder.push_record(vec!["session_id", retry.session_id.to_string().as_str()]);
+                builder.push_record(vec!["sign_output_id", retry.sign_output_id.to_string().as_str()]);
+                builder.push_record(vec!["signatures:", ""]);
+                for signature in &retry.signatures {
+                    builder.push_record(vec!["", signature.as_str()]);
+                }
+
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                table.with(TablePanel::header(
+                    "Retried the sign session and the network signed its messages.",
+                ));
+
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_bottom_right('┬')),
+                );
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_top_right('─')),
+                );
+
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::SignSessions(output) => {
+                if output.sessions.is_empty() {
+                    writeln!(f, "No sign sessions of dWallet [{}]", output.dwallet_id)?;
+                }
+                for session in &output.sessions {
+                    let mut builder = TableBuilder::default();
+                    builder.push_record(vec!["session_id", session.session_id.to_string().as_str()]);
+                    builder.push_record(vec!["status", session.status.as_str()]);
+                    builder.push_record(vec!["sender", session.sender.to_string().as_str()]);
+                    for (i, digest) in session.message_digests.iter().enumerate() {
+                        builder.push_record(vec![format!("message {i} sha256").as_str(), digest.as_str()]);
+                    }
+                    if let Some(requested_at_ms) = session.requested_at_ms {
+                        builder.push_record(vec!["requested_at_ms", requested_at_ms.to_string().as_str()]);
+                    }
+                    if let Some(sign_output_id) = session.sign_output_id {
+                        builder.push_record(vec!["sign_output_id", sign_output_id.to_string().as_str()]);
+                    }
+                    if let Some(signed_at_ms) = session.signed_at_ms {
+                        builder.push_record(vec!["signed_at_ms", signed_at_ms.to_string().as_str()]);
+                    }
+                    if let Some(retry_of) = session.retry_of {
+                        builder.push_record(vec!["retry_of", retry_of.to_string().as_str()]);
+                    }
+                    if let Some(retried_by) = session.retried_by {
+                        builder.push_record(vec!["retried_by", retried_by.to_string().as_str()]);
+                    }
+
+                    let mut table = builder.build();
+                    table.with(TableStyle::rounded());
+                    writeln!(f, "{}", table)?
+                }
+            }
+            SuiClientCommandResult::SignedBtcPsbt(signed) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec!["dwallet_id", signed.dwallet_id.to_string().as_str()]);
+                builder.push_record(vec!["sign_output_id", signed.sign_output_id.to_string().as_str()]);
+                let signed_inputs = signed.signed_inputs.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+                builder.push_record(vec!["signed_inputs", signed_inputs.join(", ").as_str()]);
+                builder.push_record(vec!["txid", signed.txid.as_str()]);
+                builder.push_record(vec!["output", signed.output.display().to_string().as_str()]);
+
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                table.with(TablePanel::header(if signed.raw_transaction.is_some() {
+                    "Signed the PSBT and wrote the raw transaction."
+                } else {
+                    "Signed and finalized the PSBT."
+                }));
+
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_bottom_right('┬')),
+                );
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_top_right('─')),
+                );
+
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::SignedEthTransaction(signed) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec!["dwallet_id", signed.dwallet_id.to_string().as_str()]);
+                builder.push_record(vec!["sign_output_id", signed.sign_output_id.to_string().as_str()]);
+                builder.push_record(vec!["transaction_hash", signed.transaction_hash.as_str()]);
+                builder.push_record(vec!["raw_transaction", signed.raw_transaction.as_str()]);
+
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                table.with(TablePanel::header(
+                    "Signed the Ethereum transaction, broadcast it with eth_sendRawTransaction.",
+                ));
+
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_bottom_right('┬')),
+                );
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_top_right('─')),
+                );
+
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::VerifiedSignature(verified) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec!["dwallet_id", verified.dwallet_id.to_string().as_str()]);
+                builder.push_record(vec!["public_key", verified.public_key.as_str()]);
+                builder.push_record(vec!["hash", verified.hash.as_str()]);
+                builder.push_record(vec!["message_digest", verified.message_digest.as_str()]);
+
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                table.with(TablePanel::header(
+                    "The signature was made by the dWallet.",
+                ));
+
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_bottom_right('┬')),
+                );
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_top_right('─')),
+                );
+
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::NewSignOutput(sign_output) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec!["dwallet_id", sign_output.dwallet_id.to_string().as_str()]);
+                builder.push_record(vec!["sign_output_id", sign_output.sign_output_id.to_string().as_str()]);
+                builder.push_record(vec!["signatures:", ""]);
+
+                for signature in &sign_output.signatures {
+                    builder.push_record(vec!["", signature.as_str()]);
+                }
+
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                table.with(TablePanel::header(
+                    "MPC completed and sign output object was generated.",
+                ));
+
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_bottom_right('┬')),
+                );
+                table.with(
+                    TableModify::new(TableCell::new(0, 0))
+                        .with(TableBorder::default().corner_top_right('─')),
+                );
+
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::Object(object_read) => match object_read.object() {
+                Ok(obj) => {
+                    let object = ObjectOutput::from(obj);
+                    let json_obj = json!(&object);
+                    let mut table = json_to_table(&json_obj);
+                    table.with(TableStyle::rounded().horizontals([]));
+                    writeln!(f, "{}", table)?
+                }
+                Err(e) => writeln!(f, "Internal error, cannot read the object: {e}")?,
+            },
+            SuiClientCommandResult::Objects(object_refs) => {
+                if object_refs.is_empty() {
+                    writeln!(f, "This address has no owned objects.")?
+                } else {
+                    let objects = ObjectsOutput::from_vec(object_refs.to_vec());
+                    match objects {
+                        Ok(objs) => {
+                            let json_obj = json!(objs);
+                            let mut table = json_to_table(&json_obj);
+                            table.with(TableStyle::rounded().horizontals([]));
+                            writeln!(f, "{}", table)?
+                        }
+                        Err(e) => write!(f, "Internal error: {e}")?,
+                    }
+                }
+            }
+            SuiClientCommandResult::Upgrade(response)
+            | SuiClientCommandResult::Publish(response) => {
+                write!(writer, "{}", response)?;
+            }
+            SuiClientCommandResult::TransactionBlock(response) => {
+                write!(writer, "{}", response)?;
+            }
+            SuiClientCommandResult::RawObject(raw_object_read) => {
+                let raw_object = match raw_object_read.object() {
+                    Ok(v) => match &v.bcs {
+                        Some(SuiRawData::MoveObject(o)) => {
+                            format!("{:?}\nNumber of bytes: {}", o.bcs_bytes, o.bcs_bytes.len())
+                        }
+                        Some(SuiRawData::Package(p)) => {
+                            let mut temp = String::new();
+                            let mut bcs_bytes = 0usize;
+                            for m in &p.module_map {
+                                temp.push_str(&format!("{:?}\n", m));
+                                bcs_bytes += m.1.len()
+                            }
+                            format!("{}Number of bytes: {}", temp, bcs_bytes)
+                        }
+                        None => "Bcs field is None".to_string().red().to_string(),
+                    },
+                    Err(err) => format!("{err}").red().to_string(),
+                };
+                writeln!(writer, "{}", raw_object)?;
+            }
+            SuiClientCommandResult::Call(response) => {
+                write!(writer, "{}", response)?;
+            }
+            SuiClientCommandResult::SerializedUnsignedTransaction(tx_data) => {
+                writeln!(
+                    writer,
+                    "{}",
+                    fastcrypto::encoding::Base64::encode(bcs::to_bytes(tx_data).unwrap())
+                )?;
+            }
+            SuiClientCommandResult::SerializedSignedTransaction(sender_signed_tx) => {
+                writeln!(
+                    writer,
+                    "{}",
+                    fastcrypto::encoding::Base64::encode(bcs::to_bytes(sender_signed_tx).unwrap())
+                )?;
+            }
+            SuiClientCommandResult::Transfer(response) => {
+                write!(writer, "{}", response)?;
+            }
+            SuiClientCommandResult::TransferSui(response) => {
+                write!(writer, "{}", response)?;
+            }
+            SuiClientCommandResult::Pay(response) => {
+                write!(writer, "{}", response)?;
+            }
+            SuiClientCommandResult::PaySui(response) => {
+                write!(writer, "{}", response)?;
             }
             SuiClientCommandResult::PayAllSui(response) => {
                 write!(writer, "{}", response)?;
@@ -1800,6 +2784,34 @@ impl Display for SuiClientCommandResult {
                 table.with(TableStyle::rounded());
                 write!(f, "{}", table)?
             }
+            SuiClientCommandResult::Profiles(output) => {
+                let optional = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+                let mut builder = TableBuilder::default();
+                builder.set_header(["name", "env", "address", "dwallets", "active dwallet", "gas budget", "gas price", "active"]);
+                for profile in &output.profiles {
+                    builder.push_record(vec![
+                        profile.name.clone(),
+                        optional(profile.env.clone()),
+                        optional(profile.active_address.map(|address| address.to_string())),
+                        if profile.dwallets.is_empty() {
+                            "all".to_string()
+                        } else {
+                            profile.dwallets.join(", ")
+                        },
+                        optional(profile.active_dwallet.clone()),
+                        optional(profile.gas_budget.map(|gas_budget| gas_budget.to_string())),
+                        optional(profile.gas_price.map(|gas_price| gas_price.to_string())),
+                        if output.active_profile.as_ref() == Some(&profile.name) {
+                            "*".to_string()
+                        } else {
+                            "".to_string()
+                        },
+                    ]);
+                }
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                write!(f, "{}", table)?
+            }
             SuiClientCommandResult::VerifySource => {
                 writeln!(writer, "Source verification succeeded!")?;
             }
@@ -1886,6 +2898,21 @@ fn convert_number_to_string(value: Value) -> Value {
     }
 }
 
+fn push_future_sign_records(builder: &mut TableBuilder, future_sign: &FutureSignOutput) {
+    builder.push_record(vec![
+        "approvals_id",
+        future_sign.approvals_id.to_string().as_str(),
+    ]);
+    builder.push_record(vec!["alias", future_sign.alias.as_deref().unwrap_or("")]);
+    builder.push_record(vec![
+        "dwallet_cap_id",
+        future_sign.dwallet_cap_id.to_string().as_str(),
+    ]);
+    for (i, message) in future_sign.messages.iter().enumerate() {
+        builder.push_record(vec![format!("message {i}").as_str(), message.as_str()]);
+    }
+}
+
 impl Debug for SuiClientCommandResult {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let s = unwrap_err_to_string(|| match self {
@@ -1910,6 +2937,13 @@ impl Debug for SuiClientCommandResult {
     }
 }
 
+fn parse_header(header: &str) -> Result<(String, String), anyhow::Error> {
+    let (name, value) = header
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Invalid header [{header}], expected NAME=VALUE"))?;
+    Ok((name.trim().to_string(), value.trim().to_string()))
+}
+
 fn unwrap_err_to_string<T: Display, F: FnOnce() -> Result<T, anyhow::Error>>(func: F) -> String {
     match func() {
         Ok(s) => format!("{s}"),
@@ -1982,6 +3016,243 @@ pub struct NewDWalletOutput {
     pub alias: String,
     pub dwallet_id: ObjectID,
     pub dwallet_cap_id: ObjectID,
+    /// The addresses of the dWallet on external chains, by chain.
+    pub external_addresses: BTreeMap<String, String>,
+    /// Where the user share of the dWallet is kept.
+    pub user_share: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DWalletAddressesOutput {
+    pub dwallet_id: ObjectID,
+    /// The addresses of the dWallet on the requested chains, by chain.
+    pub addresses: BTreeMap<String, String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DWalletBenchOutput {
+    pub dwallets: usize,
+    pub requests: usize,
+    pub messages_per_request: usize,
+    pub failures: usize,
+    /// The error of the first failed sign request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_error: Option<String>,
+    /// The time spent making the sign requests, from the first to the last signatures.
+    pub duration_ms: u64,
+    /// The completed sign requests per second.
+    pub throughput: f64,
+    pub dkg_latency: LatencyOutput,
+    pub sign_latency: LatencyOutput,
+}
+
+/// The distribution of the latencies of an operation, in milliseconds.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyOutput {
+    pub samples: usize,
+    pub mean_ms: u64,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    pub max_ms: u64,
+}
+
+impl LatencyOutput {
+    /// The distribution of `latencies`, with nearest-rank percentiles, all zero without latencies.
+    pub fn new(mut latencies: Vec<Duration>) -> Self {
+        latencies.sort();
+        let percentile = |p: usize| {
+            let rank = (latencies.len() * p).div_ceil(100).max(1);
+            latencies
+                .get(rank - 1)
+                .map_or(0, |latency| latency.as_millis() as u64)
+        };
+        let total: Duration = latencies.iter().sum();
+        Self {
+            samples: latencies.len(),
+            mean_ms: total
+                .checked_div(latencies.len() as u32)
+                .map_or(0, |mean| mean.as_millis() as u64),
+            p50_ms: percentile(50),
+            p90_ms: percentile(90),
+            p99_ms: percentile(99),
+            max_ms: latencies.last().map_or(0, |max| max.as_millis() as u64),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DWalletBindingOutput {
+    pub binding_id: ObjectID,
+    pub dwallet_id: ObjectID,
+    /// The name of the bound address, e.g. `ethereum` or `bitcoin-mainnet`.
+    pub chain: String,
+    pub external_address: String,
+    pub digest: TransactionDigest,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DWalletsOutput {
+    pub owner: SuiAddress,
+    pub dwallets: Vec<DWalletOutput>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DWalletOutput {
+    /// The alias of the dWallet in the client config, if it is there.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    pub dwallet_id: ObjectID,
+    pub dwallet_cap_id: ObjectID,
+    pub curve: DWalletCurve,
+    pub creation_epoch: EpochId,
+    /// The number of unused presigns of the dWallet.
+    pub presigns: usize,
+    /// The addresses of the dWallet on external chains, by chain.
+    pub external_addresses: BTreeMap<String, String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DWalletWatchOutput {
+    pub dwallet_id: ObjectID,
+    /// The number of events printed while watching.
+    pub events: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DWalletTransferOutput {
+    pub dwallet_id: ObjectID,
+    pub recipient: SuiAddress,
+    pub encrypted_user_share_id: ObjectID,
+    pub encryption_key_id: ObjectID,
+    /// Whether the user share was decrypted with the key of the recipient, which is only possible
+    /// when it is in the keystore.
+    pub decryption_verified: bool,
+    pub digest: TransactionDigest,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptionKeyOutput {
+    pub owner: SuiAddress,
+    pub encryption_key_id: ObjectID,
+    /// The compressed secp256k1 public key, hex encoded.
+    pub encryption_key: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FaucetOutput {
+    pub address: SuiAddress,
+    pub url: String,
+    /// The total MIST of the coins sent.
+    pub amount: u64,
+    pub coins: Vec<FaucetCoin>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthLightClientInitOutput {
+    pub network: String,
+    pub chain_id: u64,
+    /// The shared object of the light client, recorded in the config of the network.
+    pub state_object_id: ObjectID,
+    /// The epoch of the checkpoint the light client was bootstrapped from.
+    pub checkpoint_epoch: u64,
+    pub finalized_slot: u64,
+    pub latest_block_number: u64,
+    pub digest: TransactionDigest,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthLightClientUpdateOutput {
+    pub network: String,
+    pub state_object_id: ObjectID,
+    /// The number of verified light client updates submitted.
+    pub submitted_updates: usize,
+    pub finalized_slot: u64,
+    pub latest_block_number: u64,
+    /// The latest slot of the chain, attested by its sync committee.
+    pub chain_head_slot: u64,
+    /// The transactions submitting the updates, in order.
+    pub digests: Vec<TransactionDigest>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthLightClientStatusOutput {
+    pub network: String,
+    pub chain_id: u64,
+    pub state_object_id: ObjectID,
+    pub finalized_slot: u64,
+    pub latest_block_number: u64,
+    pub sync_committee_period: u64,
+    /// Whether the light client knows the sync committee of the next period, without which it
+    /// cannot follow the chain into that period.
+    pub next_sync_committee_known: bool,
+    /// The latest slot of the chain, if the network has a consensus RPC configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_head_slot: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slots_behind: Option<u64>,
+}
+
+/// The Ethereum networks of an environment, as configured in the client config.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthNetworksOutput {
+    pub env: String,
+    pub networks: Vec<EthNetworkOutput>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthNetworkOutput {
+    pub name: String,
+    pub chain_id: u64,
+    pub execution_rpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consensus_rpc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consensus_rpc_requests_per_second: Option<NonZeroU32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_object_id: Option<ObjectID>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finality: Option<EthFinality>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explorer_url: Option<String>,
+    /// The epoch of the checkpoint the light client of the network is bootstrapped from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoint_epoch: Option<u64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FutureSignsOutput {
+    pub owner: SuiAddress,
+    pub future_signs: Vec<FutureSignOutput>,
+}
+
+/// Messages approved for a dWallet to sign later, kept in an `ApprovalsHolder`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FutureSignOutput {
+    pub approvals_id: ObjectID,
+    /// The alias of the dWallet in the client config, if it is there.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    pub dwallet_cap_id: ObjectID,
+    /// The approved messages, hex encoded, in the order they are signed in.
+    pub messages: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -1992,6 +3263,208 @@ pub struct NewSignOutput {
     pub signatures: Vec<String>,
 }
 
+/// The signing policy of a dWallet, holding its capability.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningPolicyOutput {
+    pub policy_id: ObjectID,
+    /// The alias of the dWallet in the client config, if it is there.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    pub owner: SuiAddress,
+    pub dwallet_cap_id: ObjectID,
+    /// The addresses that may have messages approved, besides the owner.
+    pub allowlist: Vec<SuiAddress>,
+    /// The messages approved per window of `window_ms` milliseconds at most, `0` for no limit.
+    pub max_messages: u64,
+    pub window_ms: u64,
+    pub co_approvers: Vec<SuiAddress>,
+    pub co_approval_threshold: u64,
+    /// The requests waiting for co-approvals.
+    pub pending_requests: usize,
+    /// The transaction that set the policy, for `dwallet policy set`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest: Option<TransactionDigest>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignBatchOutput {
+    pub dwallet_id: ObjectID,
+    /// The output manifest.
+    pub output: PathBuf,
+    pub signed_messages: usize,
+    pub sessions: Vec<SignBatchSessionOutput>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignBatchSessionOutput {
+    pub session_id: ObjectID,
+    pub sign_output_id: ObjectID,
+    pub hash: String,
+    pub messages: usize,
+}
+
+/// A sign request checked without being made, see `dwallet sign --dry-run`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignDryRunOutput {
+    pub dwallet_id: ObjectID,
+    pub messages: usize,
+    pub hash: String,
+    pub would_succeed: bool,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+    pub messages_size: usize,
+    pub max_messages_size: Option<u64>,
+    /// The estimated gas budget of the presign transaction, the first of the request.
+    pub presign_gas_budget: Option<u64>,
+    pub unused_presigns: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignProofOutput {
+    pub session_id: ObjectID,
+    pub dwallet_id: ObjectID,
+    /// The compressed SEC1 public key of the dWallet, hex encoded.
+    pub public_key: String,
+    pub hash: String,
+    /// The signed messages, hex encoded.
+    pub messages: Vec<String>,
+    /// The signatures of the messages, hex encoded.
+    pub signatures: Vec<String>,
+    /// The checkpoints of the session, of its output and of the dWallet, to compare with the
+    /// checkpoints of the network.
+    pub checkpoints: Vec<ProvenCheckpointOutput>,
+    /// The file the proof was written to, if it was exported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<PathBuf>,
+}
+
+/// The signatures of the messages of a sign session the network did not sign, requested again in
+/// a new session.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignRetryOutput {
+    pub dwallet_id: ObjectID,
+    pub retried_session_id: ObjectID,
+    pub session_id: ObjectID,
+    pub sign_output_id: ObjectID,
+    pub signatures: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenCheckpointOutput {
+    pub epoch: EpochId,
+    pub sequence_number: CheckpointSequenceNumber,
+    pub digest: CheckpointDigest,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignSessionsOutput {
+    pub dwallet_id: ObjectID,
+    /// The most recent sessions first.
+    pub sessions: Vec<SignSessionOutput>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignSessionOutput {
+    pub session_id: ObjectID,
+    /// "signed", "pending" while the network has not signed the messages, or "retried" if a
+    /// pending session was retried with `dwallet retry`.
+    pub status: String,
+    pub sender: SuiAddress,
+    /// The SHA-256 digests of the messages of the session, hex encoded.
+    pub message_digests: Vec<String>,
+    /// Timestamps are in milliseconds since the Unix epoch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requested_at_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sign_output_id: Option<ObjectID>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signed_at_ms: Option<u64>,
+    /// The session this session retried, as recorded in the client config.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_of: Option<ObjectID>,
+    /// The session that retried this session, as recorded in the client config.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retried_by: Option<ObjectID>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedBtcPsbtOutput {
+    pub dwallet_id: ObjectID,
+    pub sign_output_id: ObjectID,
+    /// The indexes of the inputs signed by the dWallet.
+    pub signed_inputs: Vec<usize>,
+    pub txid: String,
+    /// The file the finalized PSBT, or the raw transaction, was written to.
+    pub output: PathBuf,
+    /// The signed transaction, hex encoded, if it was extracted from the PSBT.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_transaction: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedEthTransactionOutput {
+    pub dwallet_id: ObjectID,
+    pub sign_output_id: ObjectID,
+    /// The signed transaction, hex encoded, to broadcast with `eth_sendRawTransaction`.
+    pub raw_transaction: String,
+    pub transaction_hash: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiedSignatureOutput {
+    pub dwallet_id: ObjectID,
+    /// The compressed SEC1 public key of the dWallet, hex encoded.
+    pub public_key: String,
+    pub hash: String,
+    /// The SHA-256 digest of the verified message, hex encoded.
+    pub message_digest: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfilesOutput {
+    pub active_profile: Option<String>,
+    pub profiles: Vec<ProfileOutput>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileOutput {
+    pub name: String,
+    pub env: Option<String>,
+    pub active_address: Option<SuiAddress>,
+    /// The aliases of the dWallets usable in the profile, all of them if empty.
+    pub dwallets: Vec<String>,
+    pub active_dwallet: Option<String>,
+    pub gas_budget: Option<u64>,
+    pub gas_price: Option<u64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RotateKeyOutput {
+    pub alias: String,
+    pub old_address: SuiAddress,
+    pub address: SuiAddress,
+    pub key_scheme: SignatureScheme,
+    pub recovery_phrase: String,
+    pub transferred_dwallets: Vec<ObjectID>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest: Option<TransactionDigest>,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ObjectOutput {
@@ -2099,11 +3572,26 @@ pub enum SuiClientCommandResult {
     ActiveAddress(Option<SuiAddress>),
     ActiveEnv(Option<String>),
     Addresses(AddressesOutput),
+    AddressAliases(BTreeMap<String, SuiAddress>),
     Call(SuiTransactionBlockResponse),
     ChainIdentifier(String),
+    DWalletAddresses(DWalletAddressesOutput),
+    DWalletBench(DWalletBenchOutput),
+    DWalletBinding(DWalletBindingOutput),
+    DWallets(DWalletsOutput),
+    DWalletTransfer(DWalletTransferOutput),
+    DWalletWatch(DWalletWatchOutput),
     DynamicFieldQuery(DynamicFieldPage),
+    EncryptionKey(EncryptionKeyOutput),
     Envs(Vec<SuiEnv>, Option<String>),
+    EthLightClientInit(EthLightClientInitOutput),
+    EthLightClientStatus(EthLightClientStatusOutput),
+    EthLightClientUpdate(EthLightClientUpdateOutput),
+    EthNetworks(EthNetworksOutput),
     ExecuteSignedTx(SuiTransactionBlockResponse),
+    Faucet(FaucetOutput),
+    FutureSign(FutureSignOutput),
+    FutureSigns(FutureSignsOutput),
     Gas(Vec<GasCoin>),
     MergeCoin(SuiTransactionBlockResponse),
     NewAddress(NewAddressOutput),
@@ -2115,10 +3603,20 @@ pub enum SuiClientCommandResult {
     Pay(SuiTransactionBlockResponse),
     PayAllSui(SuiTransactionBlockResponse),
     PaySui(SuiTransactionBlockResponse),
+    Profiles(ProfilesOutput),
     Publish(SuiTransactionBlockResponse),
     RawObject(SuiObjectResponse),
+    RotateKey(RotateKeyOutput),
     SerializedSignedTransaction(SenderSignedData),
     SerializedUnsignedTransaction(TransactionData),
+    SignBatch(SignBatchOutput),
+    SignDryRun(Vec<SignDryRunOutput>),
+    SignProof(SignProofOutput),
+    SignRetry(SignRetryOutput),
+    SignedBtcPsbt(SignedBtcPsbtOutput),
+    SignSessions(SignSessionsOutput),
+    SignedEthTransaction(SignedEthTransactionOutput),
+    SigningPolicy(SigningPolicyOutput),
     SplitCoin(SuiTransactionBlockResponse),
     Switch(SwitchResponse),
     SyncClientState,
@@ -2126,6 +3624,7 @@ pub enum SuiClientCommandResult {
     Transfer(SuiTransactionBlockResponse),
     TransferSui(SuiTransactionBlockResponse),
     Upgrade(SuiTransactionBlockResponse),
+    VerifiedSignature(VerifiedSignatureOutput),
     VerifyBytecodeMeter {
         max_module_ticks: u128,
         max_function_ticks: u128,
@@ -0,0 +1,88 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+//! Shell completions of the CLI, generated for bash, zsh, fish, elvish and PowerShell by
+//! `sui completions`, and served by the interactive console.
+//!
+//! Besides the subcommands and their flags, the values of some flags are completed from the
+//! client config: the dWallet aliases of `--dwallet`, in the active profile, the environments of
+//! `--env`, the profiles of `--profile` and the Ethereum networks of the active environment of
+//! `--network`. The generated scripts hold the values of the config they were generated from, so
+//! they are to be regenerated once dWallets are added.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use clap::builder::PossibleValuesParser;
+use clap::Command;
+use clap_complete::Shell;
+use sui_sdk::sui_client_config::SuiClientConfig;
+
+/// The values of the flags completed from `config`, by the long name of the flag.
+pub fn config_completions(config: &SuiClientConfig) -> BTreeMap<&'static str, Vec<String>> {
+    let mut completions = BTreeMap::from([
+        (
+            "dwallet",
+            config
+                .profile_dwallets()
+                .map(|dwallet| dwallet.alias.clone())
+                .collect(),
+        ),
+        (
+            "env",
+            config.envs.iter().map(|env| env.alias.clone()).collect(),
+        ),
+        ("profile", config.profiles.keys().cloned().collect()),
+    ]);
+    if let Ok(env) = config.get_active_env() {
+        completions.insert("network", env.eth_networks.keys().cloned().collect());
+    }
+    completions.retain(|_, values| !values.is_empty());
+    completions
+}
+
+/// `command` with the flags of `completions`, in it and all its subcommands, taking their values
+/// as possible values, for the generated completions to list them.
+/// The flags still accept any value, as `command` is only used to generate the completions.
+pub fn with_config_completions(
+    mut command: Command,
+    completions: &BTreeMap<&'static str, Vec<String>>,
+) -> Command {
+    let flags: Vec<_> = command
+        .get_arguments()
+        .filter(|arg| {
+            arg.get_long()
+                .is_some_and(|long| completions.contains_key(long))
+        })
+        .map(|arg| arg.get_id().clone())
+        .collect();
+    for flag in flags {
+        command = command.mut_arg(flag, |arg| {
+            let values = completions[arg.get_long().expect("the flag is long")].clone();
+            arg.value_parser(PossibleValuesParser::new(values))
+        });
+    }
+
+    let subcommands: Vec<_> = command
+        .get_subcommands()
+        .map(|subcommand| subcommand.get_name().to_string())
+        .collect();
+    for subcommand in subcommands {
+        command = command.mut_subcommand(subcommand, |subcommand| {
+            with_config_completions(subcommand, completions)
+        });
+    }
+    command
+}
+
+/// Write the completions of `command`, run as `bin_name`, for `shell` to `out`.
+pub fn generate_completions(
+    shell: Shell,
+    command: Command,
+    bin_name: &str,
+    completions: &BTreeMap<&'static str, Vec<String>>,
+    out: &mut dyn Write,
+) {
+    let mut command = with_config_completions(command, completions);
+    clap_complete::generate(shell, &mut command, bin_name, out);
+}
@@ -14,6 +14,7 @@ use sui_sdk::wallet_context::WalletContext;
 
 use crate::client_commands::SwitchResponse;
 use crate::client_commands::{SuiClientCommandResult, SuiClientCommands};
+use crate::completions::config_completions;
 use crate::shell::{
     install_shell_plugins, AsyncHandler, CacheKey, CommandStructure, CompletionCache, Shell,
 };
@@ -78,12 +79,17 @@ pub async fn start_console(
     writeln!(out, "Welcome to the Sui interactive console.")?;
     writeln!(out)?;
 
+    let completions = config_completions(&context.config)
+        .into_iter()
+        .map(|(flag, values)| (CacheKey::flag(&format!("--{flag}")), values))
+        .collect::<Vec<_>>();
     let mut shell = Shell::new(
         "sui>-$ ",
         context,
         ClientCommandHandler,
         CommandStructure::from_clap(&install_shell_plugins(app)),
-    );
+    )
+    .with_completions(completions);
 
     shell.run_async(out, err).await
 }
@@ -145,6 +151,12 @@ async fn handle_command(
                 cache.insert(CacheKey::flag("--gas"), objects.clone());
                 cache.insert(CacheKey::flag("--coin-object-id"), objects);
             }
+            SuiClientCommandResult::NewDWallet(ref dwallet) => {
+                let aliases = cache.entry(CacheKey::flag("--dwallet")).or_default();
+                if !aliases.contains(&dwallet.alias) {
+                    aliases.push(dwallet.alias.clone());
+                }
+            }
             _ => {}
         }
     }
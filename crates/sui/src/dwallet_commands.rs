@@ -1,47 +1,113 @@
 // Copyright (c) dWallet Labs, Ltd.
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
-use std::{
-    fmt::{Debug, Display, Formatter, Write},
-};
-use std::time::Duration;
-
-use anyhow::{anyhow};
-use bip32::secp256k1::elliptic_curve::rand_core::OsRng;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Context};
 use clap::*;
 use fastcrypto::{
-    encoding::{Base64},
+    encoding::{Base64, Hex},
     traits::ToFromBytes,
 };
 use fastcrypto::encoding::Encoding;
-use move_core_types::language_storage::TypeTag;
-
-use serde_json::{json, Number, Value};
-
-use shared_crypto::intent::Intent;
-use sui_json::SuiJsonValue;
-use sui_json_rpc_types::{ObjectChange, RPCTransactionRequestParams, SuiData, SuiObjectData, SuiObjectDataFilter, SuiObjectResponse, SuiObjectResponseQuery, SuiParsedData, SuiRawData, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions, TransactionFilter};
-use sui_json_rpc_types::{SuiExecutionStatus, SuiObjectDataOptions};
-use sui_keys::keystore::AccountKeystore;
-use sui_sdk::sui_client_config::{DWalletSecretShare, SuiClientConfig, SuiEnv};
+use fastcrypto::hash::{HashFunction, Sha256};
+use futures::future::{join_all, try_join_all};
+use futures::{StreamExt, TryStreamExt};
+use indicatif::{ProgressBar, ProgressFinish};
+use serde::{Deserialize, Serialize};
+
+use sui_json_rpc_types::{SuiExecutionStatus, SuiObjectDataOptions, SuiTransactionBlockEffectsAPI};
+use sui_keys::encryption::{ShareEncryptionKey, ShareTransferKey};
+use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_sdk::btc::bitcoin::psbt::Psbt;
+use sui_sdk::btc::bitcoin::PublicKey as BitcoinPublicKey;
+use sui_sdk::apis::SignSessionStatus;
+use sui_sdk::btc::{self, SighashRequest};
+use sui_sdk::dwallet::{external_addresses_by_chain, DWalletClient, DWalletProgress};
+use sui_sdk::dwallet_events::DWalletEvent;
+use sui_sdk::dwallet_signer::verify_signature;
+use sui_sdk::error::Error as SdkError;
+use sui_sdk::eth::{self, Eip1559Transaction};
+use sui_sdk::evm_chains::evm_chain;
+use sui_sdk::sign_proof::{SignProof, TransactionProof, VerifiedSignProof};
+use sui_sdk::sui_client_config::{DWalletCurve, DWalletSecretShare};
 use sui_sdk::wallet_context::WalletContext;
-use sui_types::{base_types::{ObjectID,}, SUI_SYSTEM_PACKAGE_ID, transaction::{SenderSignedData, Transaction, TransactionData, TransactionDataAPI}};
-
-use tokio::time::sleep;
-use signature_mpc::twopc_mpc_protocols::{initiate_centralized_party_dkg, ProtocolContext, SecretKeyShareEncryptionAndProof, initiate_centralized_party_presign, PresignDecentralizedPartyOutput, initiate_centralized_party_sign, message_digest};
-use sui_types::base_types::ObjectRef;
+use sui_sdk::SuiClient;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use sui_types::external_address::{ethereum_address, BitcoinNetwork, ExternalChain};
+use sui_types::object::Owner;
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
-use sui_types::signature_mpc::{APPROVE_MESSAGES_FUNC_NAME, CREATE_DKG_SESSION_FUNC_NAME, CREATE_DWALLET_FUNC_NAME, CREATE_PRESIGN_SESSION_FUNC_NAME, DKG_SESSION_OUTPUT_STRUCT_NAME, DKG_SESSION_STRUCT_NAME, DKGSessionOutput, DWallet, DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME, DWALLET_MODULE_NAME, DWALLET_STRUCT_NAME, PRESIGN_SESSION_STRUCT_NAME, PresignSessionOutput, Presign, SignOutput, SIGN_SESSION_STRUCT_NAME, SIGN_MESSAGES_FUNC_NAME, CREATE_SIGN_MESSAGES_FUNC_NAME, SignData};
-use sui_types::transaction::{Argument, CallArg, ObjectArg, TransactionKind};
-use crate::client_commands::{construct_move_call_transaction, NewDWalletOutput, NewSignOutput, SuiClientCommandResult};
-use crate::serialize_or_execute;
+use sui_types::signature_mpc::{SigningPolicy, SigningPolicyRules};
+use sui_types::transaction::{TransactionData, TransactionKind};
+
+use crate::client_commands::{
+    DWalletAddressesOutput, DWalletBenchOutput, DWalletBindingOutput, DWalletOutput,
+    DWalletTransferOutput, DWalletWatchOutput, DWalletsOutput, EncryptionKeyOutput,
+    FutureSignOutput, FutureSignsOutput, LatencyOutput, NewDWalletOutput, NewSignOutput,
+    ProvenCheckpointOutput, SignBatchOutput, SignBatchSessionOutput, SignDryRunOutput,
+    SignProofOutput, SignRetryOutput, SignSessionOutput, SignSessionsOutput, SignedBtcPsbtOutput,
+    SignedEthTransactionOutput, SigningPolicyOutput, SuiClientCommandResult,
+    VerifiedSignatureOutput,
+};
+use crate::key_identity::{get_identity_address, KeyIdentity};
+use crate::sui_commands::read_line;
+
+/// The directory, next to the client config, of the encrypted user shares of dWallets.
+const DWALLET_SHARES_DIR: &str = "dwallet_shares";
+/// The first bytes of binary PSBTs (BIP-174).
+const PSBT_MAGIC: &[u8] = b"psbt\xff";
+/// The exit code of dWallet operations timing out, as of `timeout(1)`.
+pub const DWALLET_TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// How dWallet operations wait for the network to run the rounds of the MPC protocols.
+#[derive(Args, Clone, Debug, Default)]
+pub struct DWalletWaitOptions {
+    /// Show the progress of the operation on stderr while it waits for the network: the executed
+    /// transactions, and the protocol round waited for with the time spent on it.
+    /// The network publishes the output of a round, not the shares of the individual validators,
+    /// so progress is shown per round.
+    #[clap(long, global = true)]
+    pub watch: bool,
+
+    /// How long to wait for the network to answer each round, in seconds. Defaults to 60.
+    /// An operation timing out exits with code 124.
+    #[clap(long, global = true)]
+    pub timeout: Option<u64>,
+}
+
+/// Whether `error` is a dWallet operation timing out waiting for the network.
+pub fn is_dwallet_timeout(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<SdkError>(),
+            Some(SdkError::DWalletRoundTimeout { .. } | SdkError::SignSessionTimeout { .. })
+        )
+    })
+}
 
-#[derive(ValueEnum, Clone, Debug)]
+#[derive(ValueEnum, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
 pub enum Hash {
     KECCAK256,
     SHA256
 }
 
+impl Display for Hash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Hash::KECCAK256 => "keccak256",
+            Hash::SHA256 => "sha256",
+        })
+    }
+}
+
 impl From<Hash> for signature_mpc::twopc_mpc_protocols::Hash {
     fn from(value: Hash) -> Self {
         match value {
@@ -51,10 +117,130 @@ impl From<Hash> for signature_mpc::twopc_mpc_protocols::Hash {
     }
 }
 
+impl From<signature_mpc::twopc_mpc_protocols::Hash> for Hash {
+    fn from(value: signature_mpc::twopc_mpc_protocols::Hash) -> Self {
+        match value {
+            signature_mpc::twopc_mpc_protocols::Hash::KECCAK256 => Self::KECCAK256,
+            signature_mpc::twopc_mpc_protocols::Hash::SHA256 => Self::SHA256,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum SignatureEncoding {
+    Base64,
+    Hex,
+}
+
+impl SignatureEncoding {
+    fn encode(&self, signature: &[u8]) -> String {
+        match self {
+            Self::Base64 => Base64::encode(signature),
+            Self::Hex => Hex::encode(signature),
+        }
+    }
+
+    fn decode(&self, signature: &str) -> Result<Vec<u8>, anyhow::Error> {
+        match self {
+            Self::Base64 => Base64::decode(signature),
+            Self::Hex => Hex::decode(signature.strip_prefix("0x").unwrap_or(signature)),
+        }
+        .map_err(|e| anyhow!("Invalid {self:?} signature [{signature}]: {e}"))
+    }
+}
+
 #[derive(Parser)]
 #[clap(rename_all = "kebab-case")]
 pub enum SuiDWalletCommands {
-    /// Create a new dWallet.
+    /// Print the addresses of a dWallet on external chains, to deposit funds to it.
+    /// The addresses are derived from the public key of the dWallet read from the network.
+    #[command(name = "address")]
+    Address {
+        /// The alias or ID of the dWallet, which need not be in the client config.
+        /// Defaults to the active dWallet.
+        #[clap(long)]
+        dwallet: Option<String>,
+
+        /// The chain to print the addresses on: "eth", "btc" or "btc-<network>" for the mainnet,
+        /// testnet or regtest, and "cosmos" or "cosmos:<prefix>" for another Cosmos SDK chain,
+        /// e.g. "cosmos:osmo". Repeat for several chains.
+        /// Defaults to Ethereum, the Bitcoin networks and the Cosmos Hub.
+        #[clap(long)]
+        chain: Vec<ExternalChain>,
+    },
+
+    /// Benchmark signing with dWallets: create dWallets, then make sign requests with them at a
+    /// steady rate, and report the throughput and the latency percentiles of the DKG and of the
+    /// sign requests, from the presign to the signatures.
+    /// Each dWallet makes one request at a time, with a gas coin of its own split from the gas of
+    /// the active address, for the requests of different dWallets not to contend for gas. The
+    /// dWallets are not added to the client config and their user shares are dropped: run it on a
+    /// localnet or a devnet.
+    #[command(name = "bench")]
+    Bench {
+        /// The number of dWallets to create, which is the most sign requests in flight.
+        #[clap(long, default_value_t = 4)]
+        dwallets: usize,
+
+        /// The number of sign requests to make.
+        #[clap(long, default_value_t = 100)]
+        requests: usize,
+
+        /// The rate of the sign requests, per second. A request waits for a dWallet to be idle, so
+        /// the achieved rate is lower when the network falls behind.
+        #[clap(long, default_value_t = 1.0)]
+        rate: f64,
+
+        /// The number of random messages signed by each request.
+        #[clap(long, default_value_t = 1)]
+        messages: usize,
+
+        /// The SUI, in MIST, split to each dWallet for the gas of its transactions.
+        #[clap(long, default_value_t = 5_000_000_000)]
+        gas_per_dwallet: u64,
+
+        /// Gas budget for each transaction of the benchmark.
+        /// Defaults to the default gas budget of the active environment, or else to an estimate of
+        /// the cost of each transaction.
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
+
+    /// Bind a dWallet to its address on an external chain, publishing an immutable
+    /// `DWalletBinding` object for contracts to discover which dWallet controls the address.
+    /// The address is checked to be derived from the public key of the dWallet, as only Ethereum
+    /// addresses are checked on chain. The sender must own the capability of the dWallet.
+    #[command(name = "bind")]
+    Bind {
+        /// The alias or ID of the dWallet, which need not be in the client config.
+        /// Defaults to the active dWallet.
+        #[clap(long)]
+        dwallet: Option<String>,
+
+        /// The chain of the address: "eth", "btc" or "btc-<network>", or "cosmos" or
+        /// "cosmos:<prefix>", as for `dwallet address`.
+        #[clap(long)]
+        chain: ExternalChain,
+
+        /// The address of the dWallet on the chain, e.g. its Ethereum address `0x…` or its
+        /// segwit address on Bitcoin.
+        #[clap(long)]
+        address: String,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
+
+    /// Create a new dWallet, running the DKG protocol with the network, and print its ID and its
+    /// addresses on external chains.
+    /// Its user share is kept in the keyring with a keyring keystore, or else encrypted to a file
+    /// next to the client config.
     #[command(name = "create")]
     Create {
         #[clap(long)]
@@ -64,9 +250,11 @@ pub enum SuiDWalletCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
 
-        /// Gas budget for this transfer
+        /// Gas budget for each transaction of the operation.
+        /// Defaults to the default gas budget of the active environment, or else to an estimate of
+        /// the cost of each transaction.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
 
         /// Instead of executing the transaction, serialize the bcs bytes of the unsigned transaction data
         /// (TransactionData) using base64 encoding, and print out the string.
@@ -79,26 +267,156 @@ pub enum SuiDWalletCommands {
         serialize_signed_transaction: bool,
     },
 
-    /// Create a new dWallet.
+    /// Export the proof that the network signed the messages of a completed sign session, for
+    /// third parties to verify offline with `dwallet verify-proof`.
+    /// The proof holds the messages, the signatures, and the transactions that created the
+    /// session, its output and the dWallet, with the checkpoints that include them and the
+    /// committees that certified the checkpoints.
+    #[command(name = "export-proof")]
+    ExportProof {
+        /// The ID of the sign session.
+        #[clap(long)]
+        session: ObjectID,
+
+        /// The file to write the proof to.
+        #[clap(long)]
+        output: PathBuf,
+
+        /// The REST API of the fullnode, to download the checkpoints from. The fullnode must
+        /// enable it with `enable-experimental-rest-api`.
+        /// Defaults to `/rest` on the RPC endpoint of the active environment.
+        #[clap(long, value_hint = ValueHint::Url)]
+        rest_url: Option<String>,
+    },
+
+    /// Approve messages for a dWallet to sign later, and list, sign or cancel these future-sign
+    /// approvals.
+    #[command(name = "future-sign")]
+    FutureSign {
+        #[clap(subcommand)]
+        cmd: SuiFutureSignCommands,
+    },
+
+    /// List the dWallets owned by the active address, with their curve, creation epoch, number of
+    /// unused presigns, and addresses on external chains.
+    #[command(name = "list")]
+    List,
+
+    /// Set, show or remove the signing policy of a dWallet: the addresses that may sign with it,
+    /// the rate limit of its messages and the co-approvals they need.
+    #[command(name = "policy")]
+    Policy {
+        #[clap(subcommand)]
+        cmd: SuiDWalletPolicyCommands,
+    },
+
+    /// Publish the encryption key of the active address, for the dWallets transferred to it with
+    /// `dwallet transfer` to come with their user share.
+    /// The key is derived from the key of the address, for the shares sent to it to be decrypted
+    /// with its mnemonic alone.
+    #[command(name = "publish-encryption-key")]
+    PublishEncryptionKey {
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
+
+    /// Request again the signatures of the messages of a sign session the network did not sign,
+    /// e.g. as it timed out or identified malicious parties, with a fresh presign.
+    /// The messages are signed with the hash function of the session, and the new session is
+    /// recorded in the client config as the retry of the old one, see `dwallet sessions`.
+    #[command(name = "retry")]
+    Retry {
+        /// The ID of the sign session to retry.
+        #[clap(long)]
+        session: ObjectID,
+
+        /// The alias or ID of the dWallet of the session.
+        /// Defaults to the dWallet of the session in the client config.
+        #[clap(long)]
+        dwallet: Option<String>,
+
+        /// The encoding of the printed signatures, either "base64" (default) or "hex".
+        #[clap(long, value_enum, default_value_t=SignatureEncoding::Base64)]
+        encoding: SignatureEncoding,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for each transaction of the operation.
+        /// Defaults to the default gas budget of the active environment, or else to an estimate of
+        /// the cost of each transaction.
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
+
+    /// List the most recent sign sessions of a dWallet, with their status: signed, or pending
+    /// while the network has not signed their messages, or retried with `dwallet retry`.
+    /// The network records no failure of a session on chain, so a failed session stays pending.
+    #[command(name = "sessions")]
+    Sessions {
+        /// The alias or ID of the dWallet.
+        /// Defaults to the active dWallet.
+        #[clap(long)]
+        dwallet: Option<String>,
+
+        /// The number of sessions to list.
+        #[clap(long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Sign messages with a dWallet.
     #[command(name = "sign")]
     Sign {
+        /// The alias or ID of the dWallet to sign with.
+        /// Defaults to the active dWallet.
+        #[clap(long)]
+        dwallet: Option<String>,
+
+        /// A message to sign, hex encoded, or `@<path>` for the contents of a file.
+        /// Repeat to sign a batch of messages with one request.
+        #[clap(long)]
+        message: Vec<String>,
 
         /// A list of Base64 encoded messages to sign.
         #[clap(long)]
         messages: Vec<String>,
 
-        /// The hash function, either "KECCAK256" (default) or "SHA256".
+        /// A file listing messages to sign, one per line, in the format of `--message`.
+        #[clap(long)]
+        messages_file: Option<PathBuf>,
+
+        /// The hash function, either "keccak256" (default) or "sha256".
         #[clap(long, value_enum, default_value_t=Hash::KECCAK256)]
         hash: Hash,
 
+        /// The encoding of the printed signatures, either "base64" (default) or "hex".
+        #[clap(long, value_enum, default_value_t=SignatureEncoding::Base64)]
+        encoding: SignatureEncoding,
+
+        /// Check the sign request without making it, and report what would make it fail and the
+        /// estimated gas of its presign transaction. No presign is consumed and no session is
+        /// started.
+        #[clap(long)]
+        dry_run: bool,
+
         /// ID of the gas object for gas payment, in 20 bytes Hex string
         /// If not provided, a gas object with at least gas_budget value will be selected
         #[clap(long)]
         gas: Option<ObjectID>,
 
-        /// Gas budget for this transfer
+        /// Gas budget for each transaction of the operation.
+        /// Defaults to the default gas budget of the active environment, or else to an estimate of
+        /// the cost of each transaction.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
 
         /// Instead of executing the transaction, serialize the bcs bytes of the unsigned transaction data
         /// (TransactionData) using base64 encoding, and print out the string.
@@ -110,578 +428,2378 @@ pub enum SuiDWalletCommands {
         #[clap(long, required = false)]
         serialize_signed_transaction: bool,
     },
-}
 
-impl SuiDWalletCommands {
-    pub async fn execute(
-        self,
-        context: &mut WalletContext,
-    ) -> Result<SuiClientCommandResult, anyhow::Error> {
-        let ret = Ok(match self {
-            SuiDWalletCommands::Create {
-                alias,
-                gas,
-                gas_budget,
-                serialize_unsigned_transaction,
-                serialize_signed_transaction,
-            } => {
-                if context.config.dwallets.iter().any(|d| d.alias == alias) {
-                    return Err(anyhow!(
-                        "dWallet config with name [{alias}] already exists."
-                    ));
-                }
+    /// Sign the messages of a manifest with a dWallet, in batches of one sign session each, and
+    /// write the signatures to an output manifest, by the reference ids of the messages.
+    /// The input manifest is a JSON array, or a CSV file with a header, of entries with an `id`,
+    /// a `message` in the format of `dwallet sign --message`, and optionally a `hash`.
+    /// The output manifest, JSON or CSV after its extension, lists the `id`, `hash`, `sessionId`,
+    /// `signOutputId` and `signature` of every message. It is written after each batch, for the
+    /// signatures of the completed batches to be kept if a later one fails.
+    #[command(name = "sign-batch")]
+    SignBatch {
+        /// The alias or ID of the dWallet to sign with.
+        /// Defaults to the active dWallet.
+        #[clap(long)]
+        dwallet: Option<String>,
 
-                // TODO: handle Errors instead of `unwrap`
-                let centralized_party_commitment_round_party = initiate_centralized_party_dkg().unwrap();
+        /// The input manifest, `.csv` for CSV or else JSON.
+        #[clap(long)]
+        input: PathBuf,
 
-                let (
-                    commitment_to_centralized_party_secret_key_share,
-                    centralized_party_decommitment_round_party,
-                ) = centralized_party_commitment_round_party
-                    .sample_commit_and_prove_secret_key_share(&mut OsRng)
-                    .unwrap();
+        /// The file to write the output manifest to, `.csv` for CSV or else JSON, which must not
+        /// exist.
+        #[clap(long)]
+        output: PathBuf,
 
-                let commitment = bcs::to_bytes(&commitment_to_centralized_party_secret_key_share).unwrap();
+        /// The hash function of the messages without one, either "keccak256" (default) or
+        /// "sha256". A sign session hashes its messages with one function, so messages with
+        /// different ones are signed in different sessions.
+        #[clap(long, value_enum, default_value_t=Hash::KECCAK256)]
+        hash: Hash,
 
-                let commitment = commitment.iter().map(|v| Value::Number(Number::from(*v))).collect();
+        /// The encoding of the signatures, either "base64" (default) or "hex".
+        #[clap(long, value_enum, default_value_t=SignatureEncoding::Base64)]
+        encoding: SignatureEncoding,
 
-                let commitment = SuiJsonValue::new(Value::Array(commitment)).unwrap();
+        /// The most messages signed by one sign session.
+        #[clap(long, default_value_t = 32)]
+        batch_size: usize,
 
-                let tx_data = construct_move_call_transaction(
-                    SUI_SYSTEM_PACKAGE_ID, DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME.as_str(), &CREATE_DKG_SESSION_FUNC_NAME.as_str(), Vec::new(), gas, gas_budget, Vec::from([commitment]), context,
-                ).await?;
+        /// Check the sign request of each batch without making it, and report what would make it
+        /// fail and the estimated gas of its presign transaction. No presign is consumed, no
+        /// session is started and no output manifest is written.
+        #[clap(long)]
+        dry_run: bool,
 
-                let session_response = serialize_or_execute!(
-                    tx_data,
-                    serialize_unsigned_transaction,
-                    serialize_signed_transaction,
-                    context,
-                    Call
-                );
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
 
-                let SuiClientCommandResult::Call(session) = session_response else {
-                    return Err(anyhow!(
-                            "Can't get create dkg session response."
-                        ));
-                };
+        /// Gas budget for each transaction of the operation.
+        /// Defaults to the default gas budget of the active environment, or else to an estimate of
+        /// the cost of each transaction.
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
 
-                let session_id = session.object_changes.unwrap().iter().find_map(|o| {
-                    if let ObjectChange::Created {
-                        object_id,
-                        object_type,
-                        ..
-                    } = o {
-                        if object_type.address == SUI_SYSTEM_PACKAGE_ID.into() &&
-                            object_type.module == DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME.into() &&
-                            object_type.name == DKG_SESSION_STRUCT_NAME.into() {
-                        }
-                        return Some(object_id)
-                    }
-                    None
-                }).unwrap().clone();
-
-                sleep(Duration::from_millis(500)).await;
-
-                let mut cursor = None;
-                let mut output: Option<DKGSessionOutput> = None;
-                loop {
-                    let client = context.get_client().await?;
-                    let response = client
-                        .read_api()
-                        .get_owned_objects(
-                            context.active_address()?,
-                            Some(SuiObjectResponseQuery::new(
-                                Some(SuiObjectDataFilter::StructType(DKGSessionOutput::type_())),
-                                Some(SuiObjectDataOptions::bcs_lossless()),
-                            )),
-                            cursor,
-                            None,
-                        )
-                        .await?;
+    /// Sign the inputs of a Bitcoin PSBT spent from a dWallet, and write back the finalized PSBT,
+    /// or the raw transaction.
+    /// Native segwit, nested segwit and legacy inputs are signed. Taproot key-path inputs need
+    /// Schnorr signatures, which ECDSA dWallets cannot produce, and are refused.
+    /// The outputs and fee of the PSBT are shown for confirmation before it is signed.
+    #[command(name = "sign-btc-psbt")]
+    SignBtcPsbt {
+        /// The alias or ID of the dWallet to sign with.
+        /// Defaults to the active dWallet.
+        #[clap(long)]
+        dwallet: Option<String>,
 
-                    output = response.data.iter().find_map(|o| {
-                        let move_object = o.move_object_bcs().unwrap();
-                        let output = DKGSessionOutput::from_bcs_bytes(move_object).unwrap();
-                        if output.session_id.bytes == session_id {
-                            Some(output)
-                        } else {
-                            None
-                        }
-                    });
+        /// The PSBT file, binary or Base64 encoded.
+        #[clap(long)]
+        psbt: PathBuf,
 
-                    if output.is_some() {
-                        break;
-                    } else if response.has_next_page {
-                        cursor = response.next_cursor;
-                    } else {
-                        cursor = None;
-                    }
-                }
-                let output = output.unwrap();
-
-
-                // let mut stream = client.read_api().subscribe_transaction(TransactionFilter::ToAddress(context.active_address()?)).await?;
-                //
-                // let mut output: Option<DKGSessionOutput> = None;
-                // while let Some(effects) = stream.next().await {
-                //     if let SuiTransactionBlockEffects::V1(effects) = effects? {
-                //         let obj_ref = &effects.created[0];
-                //
-                //         let response = client
-                //             .read_api()
-                //             .get_object_with_options(
-                //                 obj_ref.object_id(),
-                //                 SuiObjectDataOptions::bcs_lossless(),
-                //             )
-                //             .await?;
-                //
-                //         output = response.data.iter().find_map(|o| {
-                //             if let Some(bcs_object) = &o.bcs {
-                //                 let move_object = bcs_object.try_as_move();
-                //                 let output = move_object.map(|o| DKGSessionOutput::from_bcs_bytes(&o.bcs_bytes).ok()).flatten();
-                //                 output.filter(|o| o.session_id.bytes == session_id)
-                //             } else {
-                //                 None
-                //             }
-                //         });
-                //         if output.is_some() {
-                //             break;
-                //         }
-                //     }
-                // }
-                // let output = output.unwrap();
-
-                let secret_key_share_encryption_and_proof = bcs::from_bytes::<SecretKeyShareEncryptionAndProof<ProtocolContext>>(&output.secret_key_share_encryption_and_proof)?;
-
-                let (
-                    centralized_party_public_key_share_decommitment_and_proof,
-                    centralized_party_dkg_output,
-                ) = centralized_party_decommitment_round_party
-                    .decommit_proof_public_key_share(secret_key_share_encryption_and_proof, &mut OsRng)
-                    .unwrap();
-
-                let public_key_share_decommitment_and_proof = bcs::to_bytes(&centralized_party_public_key_share_decommitment_and_proof).unwrap();
-
-                let public_key_share_decommitment_and_proof = public_key_share_decommitment_and_proof.iter().map(|v| Value::Number(Number::from(*v))).collect();
-
-                let centralized_party_public_key_share_decommitment_and_proofs = SuiJsonValue::new(Value::Array(public_key_share_decommitment_and_proof)).unwrap();
-
-                let tx_data = construct_move_call_transaction(
-                    SUI_SYSTEM_PACKAGE_ID, DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME.as_str(), &CREATE_DWALLET_FUNC_NAME.as_str(), Vec::new(), gas, gas_budget, Vec::from([SuiJsonValue::from_object_id(*output.id.object_id()), centralized_party_public_key_share_decommitment_and_proofs]), context,
-                ).await?;
-
-                let dwallet_response = serialize_or_execute!(
-                    tx_data,
-                    serialize_unsigned_transaction,
-                    serialize_signed_transaction,
-                    context,
-                    Call
-                );
+        /// The file to write the finalized PSBT, Base64 encoded, or the raw transaction to.
+        /// Defaults to the PSBT file.
+        #[clap(long)]
+        output: Option<PathBuf>,
 
-                let SuiClientCommandResult::Call(dwallet) = dwallet_response else {
-                    return Err(anyhow!(
-                            "Can't get response."
-                        ));
-                };
+        /// Write the raw transaction, hex encoded, instead of the finalized PSBT.
+        /// Every input of the PSBT must then be spent from the dWallet, or already finalized.
+        #[clap(long)]
+        extract: bool,
 
-                let dwallet_id = dwallet.object_changes.unwrap().iter().find_map(|o| {
-                    if let ObjectChange::Created {
-                        object_id,
-                        object_type,
-                        ..
-                    } = o {
-                        if object_type.address == SUI_SYSTEM_PACKAGE_ID.into() &&
-                            object_type.module == DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME.into() &&
-                            object_type.name == DWALLET_STRUCT_NAME.into() {
-                        }
-                        return Some(object_id)
-                    }
-                    None
-                }).unwrap().clone();
+        /// The Bitcoin network of the PSBT, for the addresses shown for confirmation.
+        #[clap(long, default_value_t = BitcoinNetwork::Mainnet)]
+        network: BitcoinNetwork,
 
-                let resp = context
-                    .get_client()
-                    .await?
-                    .read_api()
-                    .get_object_with_options(
-                        dwallet_id,
-                        SuiObjectDataOptions::default().with_bcs().with_owner(),
-                    )
-                    .await?;
+        /// Sign inputs with sighash types other than SIGHASH_ALL, which let the rest of the
+        /// transaction be changed after it is signed.
+        #[clap(long)]
+        allow_any_sighash: bool,
 
-                let Some(data) = resp.data else {
-                    return Err(anyhow!(
-                        "Could not find dwallet at {dwallet_id}"
-                    ));
-                };
+        /// Sign without asking for confirmation.
+        #[clap(short = 'y', long)]
+        yes: bool,
 
-                let dwallet: DWallet = data
-                    .bcs
-                    .ok_or_else(|| {
-                        anyhow!("Fetch dwallet object but no data was returned")
-                    })?
-                    .try_as_move()
-                    .ok_or_else(|| anyhow!("dwallet is not a Move Object"))?
-                    .deserialize()?;
-
-                context.config.add_dwallet(
-                    DWalletSecretShare {
-                        alias: alias.clone(),
-                        dwallet_id,
-                        dwallet_cap_id: dwallet.dwallet_cap_id.bytes.clone(),
-                        dkg_output: centralized_party_dkg_output,
-                    }
-                );
-                context.config.save()?;
+        /// Check the sign request without making it or asking for confirmation, and report what
+        /// would make it fail and the estimated gas of its presign transaction. No presign is
+        /// consumed and no session is started.
+        #[clap(long)]
+        dry_run: bool,
 
-                SuiClientCommandResult::NewDWallet(NewDWalletOutput {
-                    alias,
-                    dwallet_id,
-                    dwallet_cap_id: dwallet.dwallet_cap_id.bytes,
-                })
-            }
-            SuiDWalletCommands::Sign {
-                messages,
-                gas,
-                gas_budget,
-                serialize_unsigned_transaction,
-                serialize_signed_transaction,
-                hash
-            } => {
-                let hash: signature_mpc::twopc_mpc_protocols::Hash = hash.into();
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
 
-                let DWalletSecretShare { alias: _, dkg_output, dwallet_id, dwallet_cap_id } = context.config.get_active_dwallet()?.clone();
-                let resp = context
-                    .get_client()
-                    .await?
-                    .read_api()
-                    .get_object_with_options(
-                        dwallet_id,
-                        SuiObjectDataOptions::default().with_bcs().with_owner(),
-                    )
-                    .await?;
+        /// Gas budget for each transaction of the operation.
+        /// Defaults to the default gas budget of the active environment, or else to an estimate of
+        /// the cost of each transaction.
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
 
+    /// Sign an EIP-1559 Ethereum transaction with a dWallet, and print the raw transaction to
+    /// broadcast with `eth_sendRawTransaction`.
+    /// The decoded transaction is shown for confirmation before it is signed.
+    #[command(name = "sign-eth-tx")]
+    SignEthTx {
+        /// The alias or ID of the dWallet to sign with.
+        /// Defaults to the active dWallet.
+        #[clap(long)]
+        dwallet: Option<String>,
 
-                let Some(data) = resp.data else {
-                    return Err(anyhow!(
-                        "Could not find dwallet at {dwallet_id}"
-                    ));
-                };
+        /// The recipient of the transaction, hex encoded.
+        /// Omit to create a contract.
+        #[clap(long)]
+        to: Option<String>,
 
-                let dwallet_ref = data.object_ref();
+        /// The amount to transfer, in wei.
+        #[clap(long, default_value_t = 0)]
+        value: u128,
 
-                let mut messages_vec = Vec::new();
-                for m in messages {
-                    messages_vec.push(
-                        Base64::try_from(m)
-                            .map_err(|e| anyhow!(e))?
-                            .to_vec()
-                            .map_err(|e| anyhow!(e))?,
-                    );
-                }
+        /// The call data of the transaction, hex encoded, or `@<path>` for the contents of a file.
+        #[clap(long)]
+        data: Option<String>,
 
-                let centralized_party_commitment_round_party = initiate_centralized_party_presign(dkg_output.clone()).unwrap();
+        /// The ID of the EVM chain, e.g. 1 for Ethereum mainnet.
+        #[clap(long)]
+        chain_id: u64,
 
-                let (
-                    centralized_party_nonce_shares_commitments_and_batched_proof,
-                    centralized_party_proof_verification_round_party,
-                ) = centralized_party_commitment_round_party
-                    .sample_commit_and_prove_signature_nonce_share(messages_vec.len(), &mut OsRng)
-                    .unwrap();
+        /// The nonce of the Ethereum address of the dWallet.
+        #[clap(long)]
+        nonce: u64,
 
-                let centralized_party_nonce_shares_commitments_and_batched_proof = bcs::to_bytes(&centralized_party_nonce_shares_commitments_and_batched_proof).unwrap();
+        #[clap(long, default_value_t = 21_000)]
+        gas_limit: u64,
 
-                let centralized_party_nonce_shares_commitments_and_batched_proof = centralized_party_nonce_shares_commitments_and_batched_proof.iter().map(|v| Value::Number(Number::from(*v))).collect();
+        /// The maximum fee per gas, in wei.
+        #[clap(long)]
+        max_fee_per_gas: u128,
 
-                let centralized_party_nonce_shares_commitments_and_batched_proof = SuiJsonValue::new(Value::Array(centralized_party_nonce_shares_commitments_and_batched_proof)).unwrap();
+        /// The maximum priority fee per gas, in wei.
+        #[clap(long)]
+        max_priority_fee_per_gas: u128,
 
-                let messages_vec_input = messages_vec.iter().map(|v| Value::Array(v.iter().map(|v| Value::Number(Number::from(*v))).collect::<Vec<_>>())).collect::<Vec<_>>();
+        /// Sign without asking for confirmation.
+        #[clap(short = 'y', long)]
+        yes: bool,
 
+        /// Check the sign request without making it or asking for confirmation, and report what
+        /// would make it fail and the estimated gas of its presign transaction. No presign is
+        /// consumed and no session is started.
+        #[clap(long)]
+        dry_run: bool,
 
-                let messages_vec_input = SuiJsonValue::new(Value::Array(messages_vec_input)).unwrap();
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
 
-                let gas_owner = context.try_get_object_owner(&gas).await?;
-                let sender = gas_owner.unwrap_or(context.active_address()?);
+        /// Gas budget for each transaction of the operation.
+        /// Defaults to the default gas budget of the active environment, or else to an estimate of
+        /// the cost of each transaction.
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
 
-                let client = context.get_client().await?;
+    /// Transfer a dWallet to another address, with its user share encrypted to the encryption key
+    /// published by the recipient with `dwallet publish-encryption-key`.
+    /// The transfer is shown for confirmation before it is sent.
+    #[command(name = "transfer")]
+    Transfer {
+        /// The alias or ID of the dWallet to transfer.
+        /// Defaults to the active dWallet.
+        #[clap(long)]
+        dwallet: Option<String>,
 
-                let hash_num: u8 = hash.clone().into();
-                let mut pt_builder = ProgrammableTransactionBuilder::new();
-                client.transaction_builder().single_move_call(
-                    &mut pt_builder,
-                    SUI_SYSTEM_PACKAGE_ID,
-                    DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME.as_str(),
-                    CREATE_PRESIGN_SESSION_FUNC_NAME.as_str(),
-                    Vec::new(),
-                    Vec::from([SuiJsonValue::from_object_id(dwallet_id), messages_vec_input.clone(), centralized_party_nonce_shares_commitments_and_batched_proof, SuiJsonValue::new(Value::Number(Number::from(hash_num))).unwrap()])
-                ).await?;
-
-                // let dwallet_arg = pt_builder.input(CallArg::Object(ObjectArg::ImmOrOwnedObject(dwallet_ref))).unwrap();
-                // let centralized_party_nonce_shares_commitments_and_batched_proof_arg = pt_builder.input(CallArg::from(&centralized_party_nonce_shares_commitments_and_batched_proof)).unwrap();
-                // let hash = pt_builder.input(CallArg::from(1u8)).unwrap();
-                // pt_builder.programmable_move_call(
-                //     SUI_SYSTEM_PACKAGE_ID,
-                //     DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME.into(),
-                //     CREATE_PRESIGN_SESSION_FUNC_NAME.into(),
-                //     Vec::new(),
-                //     Vec::from([dwallet_arg, Argument::Result(0), centralized_party_nonce_shares_commitments_and_batched_proof_arg, hash]),
-                // );
-
-                let tx_data = client
-                    .transaction_builder().
-                    finish_programmable_transaction(
-                        sender,
-                        pt_builder,
-                        gas,
-                        gas_budget
-                    ).await?;
-
-                let session_response = serialize_or_execute!(
-                    tx_data,
-                    serialize_unsigned_transaction,
-                    serialize_signed_transaction,
-                    context,
-                    Call
-                );
+        /// Recipient address (or its alias if it's an address in the keystore)
+        #[clap(long)]
+        to: KeyIdentity,
 
-                let SuiClientCommandResult::Call(session) = session_response else {
-                    return Err(anyhow!(
-                            "Can't get response."
-                        ));
-                };
+        /// Transfer without asking for confirmation.
+        #[clap(short = 'y', long)]
+        yes: bool,
 
-                let (session_id, session_ref) = session.object_changes.unwrap().iter().find_map(|o| {
-                    if let ObjectChange::Created {
-                        object_id,
-                        object_type,
-                        ..
-                    } = o {
-                        if object_type.address == SUI_SYSTEM_PACKAGE_ID.into() &&
-                            object_type.module == DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME.into() &&
-                            object_type.name == PRESIGN_SESSION_STRUCT_NAME.into() {
-                        }
-                        return Some((*object_id, o.object_ref()))
-                    }
-                    None
-                }).unwrap().clone();
-
-                sleep(Duration::from_millis(500)).await;
-
-                let mut cursor = None;
-                let mut output: Option<(PresignSessionOutput, ObjectRef)> = None;
-                loop {
-                    let client = context.get_client().await?;
-                    let response = client
-                        .read_api()
-                        .get_owned_objects(
-                            context.active_address()?,
-                            Some(SuiObjectResponseQuery::new(
-                                Some(SuiObjectDataFilter::StructType(PresignSessionOutput::type_())),
-                                Some(SuiObjectDataOptions::bcs_lossless()),
-                            )),
-                            cursor,
-                            None,
-                        )
-                        .await?;
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
 
-                    output = response.data.iter().find_map(|o| {
-                        let move_object = o.move_object_bcs().unwrap();
-                        let output = PresignSessionOutput::from_bcs_bytes(move_object).unwrap();
-                        if output.session_id.bytes == session_id {
-                            Some((output, o.object_ref_if_exists().unwrap()))
-                        } else {
-                            None
-                        }
-                    });
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
 
-                    if output.is_some() {
-                        break;
-                    } else if response.has_next_page {
-                        cursor = response.next_cursor;
-                    } else {
-                        cursor = None;
-                    }
-                }
-                let (presign_output, presign_ref) = output.unwrap();
-
-                let presign_output = bcs::from_bytes::<PresignDecentralizedPartyOutput<ProtocolContext>>(&presign_output.output)?;
-
-
-                let centralized_party_presigns = centralized_party_proof_verification_round_party
-                    .verify_presign_output(presign_output, &mut OsRng)
-                    .unwrap();
-
-                let centralized_party_sign_round_parties = initiate_centralized_party_sign(dkg_output, centralized_party_presigns).unwrap();
-                
-                let (public_nonce_encrypted_partial_signature_and_proofs, signature_verification_round_parties): (Vec<_>, Vec<_>) = messages_vec.into_iter().zip(centralized_party_sign_round_parties.into_iter()).map(|(message, party)| {
-                    let m = message_digest(&message, &hash);
-                    party
-                        .evaluate_encrypted_partial_signature_prehash(m, &mut OsRng)
-                        .unwrap()
-                }).collect::<Vec<_>>().into_iter().unzip();
-
-                let public_nonce_encrypted_partial_signature_and_proofs = bcs::to_bytes(&public_nonce_encrypted_partial_signature_and_proofs).unwrap();
-
-                // let public_nonce_encrypted_partial_signature_and_proofs = public_nonce_encrypted_partial_signature_and_proofs.iter().map(|v| Value::Number(Number::from(*v))).collect();
-                //
-                // let public_nonce_encrypted_partial_signature_and_proofs = SuiJsonValue::new(Value::Array(public_nonce_encrypted_partial_signature_and_proofs)).unwrap();
-
-                sleep(Duration::from_millis(500)).await;
-                let mut cursor = None;
-                let mut decentralized_presign: Option<(Presign, ObjectRef)> = None;
-                loop {
-                    let client = context.get_client().await?;
-                    let response = client
-                        .read_api()
-                        .get_owned_objects(
-                            context.active_address()?,
-                            Some(SuiObjectResponseQuery::new(
-                                Some(SuiObjectDataFilter::StructType(Presign::type_())),
-                                Some(SuiObjectDataOptions::bcs_lossless()),
-                            )),
-                            cursor,
-                            None,
-                        )
-                        .await?;
+    /// Verify a signature of a message by a dWallet locally, against the public key of the dWallet
+    /// read from the network, e.g. a signature received out of band.
+    /// Fails if the signature does not verify.
+    #[command(name = "verify")]
+    Verify {
+        /// The alias or ID of the dWallet, which need not be in the client config.
+        /// Defaults to the active dWallet.
+        #[clap(long)]
+        dwallet: Option<String>,
 
-                    decentralized_presign = response.data.iter().find_map(|o| {
-                        let move_object = o.move_object_bcs().unwrap();
-                        let decentralized_presign = Presign::from_bcs_bytes(move_object).unwrap();
-                        if decentralized_presign.session_id.bytes == session_id {
+        /// The signed message, hex encoded, or `@<path>` for the contents of a file.
+        #[clap(long)]
+        message: String,
 
-                            Some((decentralized_presign, o.object_ref_if_exists().unwrap()))
-                        } else {
-                            None
-                        }
-                    });
+        /// The signature, 64 bytes `r || s`.
+        #[clap(long)]
+        signature: String,
 
-                    if decentralized_presign.is_some() {
-                        break;
-                    } else if response.has_next_page {
-                        cursor = response.next_cursor;
-                    } else {
-                        cursor = None;
-                    }
-                }
-                let (decentralized_presign, decentralized_presign_ref) = decentralized_presign.unwrap();
-
-                let mut pt_builder = ProgrammableTransactionBuilder::new();
-                client.transaction_builder().single_move_call(
-                    &mut pt_builder,
-                    SUI_SYSTEM_PACKAGE_ID,
-                    DWALLET_MODULE_NAME.as_str(),
-                    APPROVE_MESSAGES_FUNC_NAME.as_str(),
-                    Vec::new(),
-                    Vec::from([SuiJsonValue::from_object_id(dwallet_cap_id), messages_vec_input])
-                ).await?;
-
-                let dwallet_arg = pt_builder.input(CallArg::Object(ObjectArg::ImmOrOwnedObject(dwallet_ref))).unwrap();
-                let session_arg = pt_builder.input(CallArg::Object(ObjectArg::ImmOrOwnedObject(session_ref))).unwrap();
-                let presign_arg = pt_builder.input(CallArg::Object(ObjectArg::ImmOrOwnedObject(presign_ref))).unwrap();
-                let decentralized_presign_arg = pt_builder.input(CallArg::Object(ObjectArg::ImmOrOwnedObject(decentralized_presign_ref))).unwrap();
-                let public_nonce_encrypted_partial_signature_and_proofs = pt_builder.input(CallArg::from(&public_nonce_encrypted_partial_signature_and_proofs)).unwrap();
-                pt_builder.programmable_move_call(
-                    SUI_SYSTEM_PACKAGE_ID,
-                    DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME.into(),
-                    CREATE_SIGN_MESSAGES_FUNC_NAME.into(),
-                    Vec::new(),
-                    Vec::from([dwallet_arg, session_arg, presign_arg, decentralized_presign_arg, public_nonce_encrypted_partial_signature_and_proofs]),
-                );
-                pt_builder.programmable_move_call(
-                    SUI_SYSTEM_PACKAGE_ID,
-                    DWALLET_MODULE_NAME.into(),
-                    SIGN_MESSAGES_FUNC_NAME.into(),
-                    Vec::from([TypeTag::Struct(Box::new(SignData::type_()))]),
-                    Vec::from([Argument::Result(1), Argument::Result(0)]),
-                );
+        /// The encoding of the signature, either "hex" (default) or "base64".
+        #[clap(long, value_enum, default_value_t=SignatureEncoding::Hex)]
+        encoding: SignatureEncoding,
 
-                let tx_data = client
-                    .transaction_builder().
-                    finish_programmable_transaction(
-                        sender,
-                        pt_builder,
-                        gas,
-                        gas_budget
-                    ).await?;
-
-                let session_response = serialize_or_execute!(
-                    tx_data,
-                    serialize_unsigned_transaction,
-                    serialize_signed_transaction,
-                    context,
-                    Call
-                );
+        /// The hash function the message was signed with, either "keccak256" (default) or
+        /// "sha256".
+        #[clap(long, value_enum, default_value_t=Hash::KECCAK256)]
+        hash: Hash,
+    },
 
-                let SuiClientCommandResult::Call(session) = session_response else {
-                    return Err(anyhow!(
-                            "Can't get response."
-                        ));
-                };
+    /// Verify a proof exported with `dwallet export-proof` offline, and show the messages it
+    /// proves the network signed with the dWallet.
+    /// The proof is trusted as far as its committees are: compare the checkpoints it relies on
+    /// with the checkpoints of the network.
+    #[command(name = "verify-proof")]
+    VerifyProof {
+        /// The proof file.
+        #[clap(long)]
+        proof: PathBuf,
+    },
 
-                let session_id = session.object_changes.unwrap().iter().find_map(|o| {
-                    if let ObjectChange::Created {
-                        object_id,
-                        object_type,
-                        ..
-                    } = o {
-                        if object_type.address == SUI_SYSTEM_PACKAGE_ID.into() &&
-                            object_type.module == DWALLET_2PC_MPC_ECDSA_K1_MODULE_NAME.into() &&
-                            object_type.name == SIGN_SESSION_STRUCT_NAME.into() {
-                        }
-                        return Some(object_id)
-                    }
-                    None
-                }).unwrap().clone();
-
-
-                sleep(Duration::from_millis(500)).await;
-                let mut cursor = None;
-                let mut sign_output: Option<SignOutput> = None;
-                loop {
-                    let client = context.get_client().await?;
-                    let response = client
-                        .read_api()
-                        .get_owned_objects(
-                            context.active_address()?,
-                            Some(SuiObjectResponseQuery::new(
-                                Some(SuiObjectDataFilter::StructType(SignOutput::type_())),
-                                Some(SuiObjectDataOptions::bcs_lossless()),
-                            )),
-                            cursor,
-                            None,
-                        )
-                        .await?;
+    /// Watch the lifecycle events of a dWallet as they happen: its presign and sign sessions, and
+    /// the transfers of its capability, until interrupted.
+    /// Events are subscribed to over the WebSocket endpoint of the active environment, which is
+    /// reconnected when it drops; events emitted while reconnecting are missed.
+    #[command(name = "watch")]
+    Watch {
+        /// The alias or ID of the dWallet, which need not be in the client config.
+        /// Defaults to the active dWallet.
+        #[clap(long)]
+        dwallet: Option<String>,
 
-                    sign_output = response.data.iter().find_map(|o| {
-                        let move_object = o.move_object_bcs().unwrap();
-                        let sign_output = SignOutput::from_bcs_bytes(move_object).unwrap();
-                        if sign_output.session_id.bytes == session_id {
-                            Some(sign_output)
-                        } else {
-                            None
-                        }
-                    });
+        /// Print one JSON object per event and line, for piping into monitoring.
+        #[clap(long)]
+        jsonl: bool,
+
+        /// Report a sign session as failed when it is not signed within this many seconds. The
+        /// network records no failure of a session on chain.
+        #[clap(long, default_value_t = 300)]
+        fail_after: u64,
+    },
+}
+
+/// Future-sign approvals are messages approved ahead of time for a dWallet to sign, kept in an
+/// `ApprovalsHolder` owned by the address that approved them.
+#[derive(Parser)]
+pub enum SuiFutureSignCommands {
+    /// Approve messages for a dWallet to sign later with `future-sign sign`, which no longer needs
+    /// the capability of the dWallet, e.g. once it has been transferred.
+    /// Only given messages can be approved: the dWallet module has no message templates or
+    /// conditions.
+    #[command(name = "approve")]
+    Approve {
+        /// The alias or ID of the dWallet to approve the messages for.
+        /// Defaults to the active dWallet.
+        #[clap(long)]
+        dwallet: Option<String>,
+
+        /// A message to approve, hex encoded, or `@<path>` for the contents of a file.
+        /// Repeat to approve a batch of messages, signed together.
+        #[clap(long, required = true)]
+        message: Vec<String>,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
 
-                    if sign_output.is_some() {
-                        break;
-                    } else if response.has_next_page {
-                        cursor = response.next_cursor;
-                    } else {
-                        cursor = None;
+    /// List the future-sign approvals owned by the active address.
+    #[command(name = "list")]
+    List {
+        /// Only list the approvals of this dWallet, by alias or ID.
+        #[clap(long)]
+        dwallet: Option<String>,
+    },
+
+    /// Sign the messages of future-sign approvals, which are consumed.
+    #[command(name = "sign")]
+    Sign {
+        /// The ID of the approvals, as printed by `future-sign approve`.
+        #[clap(long)]
+        approvals: ObjectID,
+
+        /// The alias or ID of the dWallet the messages were approved for.
+        /// Defaults to the active dWallet.
+        #[clap(long)]
+        dwallet: Option<String>,
+
+        /// The hash function, either "keccak256" (default) or "sha256".
+        #[clap(long, value_enum, default_value_t=Hash::KECCAK256)]
+        hash: Hash,
+
+        /// The encoding of the printed signatures, either "base64" (default) or "hex".
+        #[clap(long, value_enum, default_value_t=SignatureEncoding::Base64)]
+        encoding: SignatureEncoding,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for each transaction of the operation.
+        /// Defaults to the default gas budget of the active environment, or else to an estimate of
+        /// the cost of each transaction.
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
+
+    /// Cancel future-sign approvals, whose messages can then no longer be signed.
+    #[command(name = "cancel")]
+    Cancel {
+        /// The ID of the approvals, as printed by `future-sign approve`.
+        #[clap(long)]
+        approvals: ObjectID,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
+}
+
+/// A signing policy takes custody of the capability of a dWallet, in a shared `SigningPolicy`
+/// owned by the address that set it, and approves the messages of the dWallet within its rules.
+/// The policy is recorded in the client config, for `dwallet sign` to approve the messages
+/// through it.
+#[derive(Parser)]
+pub enum SuiDWalletPolicyCommands {
+    /// Set the signing policy of a dWallet, replacing its rules if it already has one.
+    /// Setting a first policy moves the capability of the dWallet, which the sender must own, into
+    /// the policy.
+    #[command(name = "set")]
+    Set {
+        /// The alias or ID of the dWallet.
+        /// Defaults to the active dWallet.
+        #[clap(long)]
+        dwallet: Option<String>,
+
+        /// An address or alias that may have messages approved, besides the owner of the policy.
+        /// Repeat to allow several addresses.
+        #[clap(long)]
+        allow: Vec<KeyIdentity>,
+
+        /// The messages approved per window at most. No limit if not provided.
+        #[clap(long, requires = "window_secs")]
+        max_messages: Option<u64>,
+
+        /// The window of the rate limit, in seconds.
+        #[clap(long)]
+        window_secs: Option<u64>,
+
+        /// An address or alias that co-approves the messages before they are approved.
+        /// Repeat to add several co-approvers.
+        #[clap(long)]
+        co_approver: Vec<KeyIdentity>,
+
+        /// The co-approvers that must co-approve the messages.
+        /// Defaults to all the co-approvers.
+        #[clap(long)]
+        threshold: Option<u64>,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
+
+    /// Show the signing policy of a dWallet.
+    #[command(name = "show")]
+    Show {
+        /// The alias or ID of the dWallet.
+        /// Defaults to the active dWallet.
+        #[clap(long)]
+        dwallet: Option<String>,
+
+        /// The ID of the policy, for a dWallet whose policy is not recorded in the client config.
+        #[clap(long, conflicts_with = "dwallet")]
+        policy: Option<ObjectID>,
+    },
+
+    /// Remove the signing policy of a dWallet, sending its capability back to the sender, which
+    /// must own the policy.
+    #[command(name = "remove")]
+    Remove {
+        /// The alias or ID of the dWallet.
+        /// Defaults to the active dWallet.
+        #[clap(long)]
+        dwallet: Option<String>,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
+}
+
+impl SuiDWalletCommands {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+        wait: &DWalletWaitOptions,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        let ret = Ok(match self {
+            SuiDWalletCommands::Address { dwallet, chain } => {
+                let dwallet_id = match dwallet {
+                    Some(dwallet) => context.config.resolve_dwallet_id(&dwallet)?,
+                    None => context.config.get_active_dwallet()?.dwallet_id,
+                };
+                let public_key = context
+                    .get_client()
+                    .await?
+                    .dwallet_api()
+                    .get_dwallet(dwallet_id)
+                    .await?
+                    .public_key_sec1()
+                    .map_err(|e| anyhow!("Invalid public key of dWallet {dwallet_id}: {e}"))?;
+                let chains = if chain.is_empty() {
+                    ExternalChain::all()
+                } else {
+                    chain
+                };
+                let mut addresses = BTreeMap::new();
+                for chain in &chains {
+                    addresses.extend(chain.addresses(&public_key)?);
+                }
+
+                SuiClientCommandResult::DWalletAddresses(DWalletAddressesOutput {
+                    dwallet_id,
+                    addresses,
+                })
+            }
+            SuiDWalletCommands::Bench {
+                dwallets,
+                requests,
+                rate,
+                messages,
+                gas_per_dwallet,
+                gas_budget,
+            } => {
+                if dwallets == 0 || requests == 0 || messages == 0 {
+                    bail!("The dWallets, requests and messages of the benchmark must be positive");
+                }
+                if !(rate.is_finite() && rate > 0.0) {
+                    bail!(
+                        "Invalid rate [{rate}], expected a positive number of requests per second"
+                    );
+                }
+                let sender = context.active_address()?;
+                let context = &*context;
+                let coins =
+                    split_gas_coins(context, sender, dwallets, gas_per_dwallet, gas_budget).await?;
+                // Concurrent operations would draw over each other's spinners.
+                let wait = &DWalletWaitOptions {
+                    watch: false,
+                    timeout: wait.timeout,
+                };
+
+                let clients = coins
+                    .iter()
+                    .map(|coin| dwallet_client(context, sender, Some(*coin), gas_budget, wait))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let created =
+                    try_join_all(clients.iter().enumerate().map(|(i, client)| async move {
+                        let start = Instant::now();
+                        let dwallet = client.create_dwallet(format!("bench-{i}")).await?;
+                        Ok::<_, anyhow::Error>((dwallet, start.elapsed()))
+                    }))
+                    .await?;
+                let dkg_latencies = created.iter().map(|(_, latency)| *latency).collect();
+
+                // The requests left to make, and the ticks of their rate, shared by the dWallets.
+                let remaining = AtomicUsize::new(requests);
+                let mut ticks = tokio::time::interval(Duration::from_secs_f64(1.0 / rate));
+                ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                let ticks = tokio::sync::Mutex::new(ticks);
+                let start = Instant::now();
+                let results =
+                    join_all(clients.iter().zip(&created).map(|(client, (dwallet, _))| {
+                        bench_sign_requests(client, dwallet, messages, &remaining, &ticks)
+                    }))
+                    .await;
+                let duration = start.elapsed();
+
+                let mut sign_latencies = vec![];
+                let mut failures = 0;
+                let mut first_error = None;
+                for result in results.into_iter().flatten() {
+                    match result {
+                        Ok(latency) => sign_latencies.push(latency),
+                        Err(e) => {
+                            failures += 1;
+                            first_error.get_or_insert_with(|| format!("{e:#}"));
+                        }
                     }
                 }
-                let sign_output = sign_output.unwrap();
 
-                let signatures = sign_output.signatures.iter().map(|s| Base64::encode(s)).collect::<Vec<_>>();
+                SuiClientCommandResult::DWalletBench(DWalletBenchOutput {
+                    dwallets,
+                    requests,
+                    messages_per_request: messages,
+                    failures,
+                    first_error,
+                    duration_ms: duration.as_millis() as u64,
+                    throughput: sign_latencies.len() as f64 / duration.as_secs_f64(),
+                    dkg_latency: LatencyOutput::new(dkg_latencies),
+                    sign_latency: LatencyOutput::new(sign_latencies),
+                })
+            }
+            SuiDWalletCommands::Bind {
+                dwallet,
+                chain,
+                address,
+                gas,
+                gas_budget,
+            } => {
+                let dwallet_id = match dwallet {
+                    Some(dwallet) => context.config.resolve_dwallet_id(&dwallet)?,
+                    None => context.config.get_active_dwallet()?.dwallet_id,
+                };
+                let bindings = context
+                    .get_client()
+                    .await?
+                    .dwallet_api()
+                    .get_dwallet_bindings(dwallet_id)
+                    .await?;
+                if let Some(binding) = bindings.iter().find(|binding| {
+                    binding
+                        .external_address
+                        .eq_ignore_ascii_case(address.as_bytes())
+                }) {
+                    bail!(
+                        "dWallet [{dwallet_id}] is already bound to [{address}] by binding [{}]",
+                        binding.binding_id.bytes
+                    );
+                }
 
-                SuiClientCommandResult::NewSignOutput(NewSignOutput {
+                let sender = dwallet_sender(context, gas).await?;
+                let (binding_id, chain, external_address, response) =
+                    dwallet_client(context, sender, gas, gas_budget, wait)?
+                        .bind_dwallet(dwallet_id, &chain, &address)
+                        .await?;
+
+                SuiClientCommandResult::DWalletBinding(DWalletBindingOutput {
+                    binding_id,
+                    dwallet_id,
+                    chain,
+                    external_address,
+                    digest: response.digest,
+                })
+            }
+            SuiDWalletCommands::Create {
+                alias,
+                gas,
+                gas_budget,
+                serialize_unsigned_transaction,
+                serialize_signed_transaction,
+            } => {
+                if context.config.dwallets.iter().any(|d| d.alias == alias) {
+                    return Err(anyhow!(
+                        "dWallet config with name [{alias}] already exists."
+                    ));
+                }
+                ensure_not_serialized(serialize_unsigned_transaction, serialize_signed_transaction)?;
+
+                let sender = dwallet_sender(context, gas).await?;
+                let mut dwallet = dwallet_client(context, sender, gas, gas_budget, wait)?
+                    .create_dwallet(alias.clone())
+                    .await?;
+                let user_share = store_user_share(context, sender, &mut dwallet)?;
+                let (dwallet_id, dwallet_cap_id) = (dwallet.dwallet_id, dwallet.dwallet_cap_id);
+                let external_addresses = dwallet.external_addresses.clone();
+
+                context.config.add_dwallet(dwallet);
+                context.config.save()?;
+
+                SuiClientCommandResult::NewDWallet(NewDWalletOutput {
+                    alias,
+                    dwallet_id,
+                    dwallet_cap_id,
+                    external_addresses,
+                    user_share,
+                })
+            }
+            SuiDWalletCommands::ExportProof {
+                session,
+                output,
+                rest_url,
+            } => {
+                let rest_url = match rest_url {
+                    Some(rest_url) => rest_url,
+                    None => format!(
+                        "{}/rest",
+                        context.config.get_active_env()?.rpc.trim_end_matches('/')
+                    ),
+                };
+                let proof = export_sign_proof(context, session, &rest_url).await?;
+                let verified = proof.verify()?;
+                fs::write(&output, proof.to_bytes())
+                    .with_context(|| format!("Cannot write to {}", output.display()))?;
+
+                SuiClientCommandResult::SignProof(sign_proof_output(verified, Some(output)))
+            }
+            SuiDWalletCommands::FutureSign { cmd } => cmd.execute(context, wait).await?,
+            SuiDWalletCommands::Policy { cmd } => cmd.execute(context, wait).await?,
+            SuiDWalletCommands::List => {
+                let owner = context.active_address()?;
+                let client = context.get_client().await?;
+                let dwallet_api = client.dwallet_api();
+                let owned: Vec<_> = dwallet_api
+                    .get_owned_dwallets_stream(owner)
+                    .try_collect()
+                    .await?;
+                let presigns: Vec<_> = dwallet_api
+                    .get_owned_presigns_stream(owner)
+                    .try_collect()
+                    .await?;
+
+                let dwallets = try_join_all(owned.iter().map(|owned| async {
+                    let dwallet_id = owned.dwallet_id;
+                    let dwallet = dwallet_api.get_dwallet(dwallet_id).await?;
+                    let external_addresses = dwallet
+                        .external_addresses()
+                        .with_context(|| format!("Invalid public key of dWallet [{dwallet_id}]"))?;
+                    Ok::<_, anyhow::Error>(DWalletOutput {
+                        alias: context
+                            .config
+                            .dwallets
+                            .iter()
+                            .find(|dwallet| dwallet.dwallet_id == dwallet_id)
+                            .map(|dwallet| dwallet.alias.clone()),
+                        dwallet_id,
+                        dwallet_cap_id: owned.dwallet_cap.0,
+                        // All dWallets are 2PC-MPC ECDSA secp256k1 dWallets.
+                        curve: DWalletCurve::Secp256k1,
+                        creation_epoch: dwallet_api.get_dwallet_creation_epoch(dwallet_id).await?,
+                        presigns: presigns
+                            .iter()
+                            .filter(|presign| presign.dwallet_id.bytes == dwallet_id)
+                            .count(),
+                        external_addresses: external_addresses_by_chain(&external_addresses),
+                    })
+                }))
+                .await?;
+
+                SuiClientCommandResult::DWallets(DWalletsOutput { owner, dwallets })
+            }
+            SuiDWalletCommands::PublishEncryptionKey { gas, gas_budget } => {
+                let sender = dwallet_sender(context, gas).await?;
+                let encryption_key =
+                    ShareTransferKey::derive(context.config.keystore.get_key(&sender)?)
+                        .public_key();
+                let published = context
+                    .get_client()
+                    .await?
+                    .dwallet_api()
+                    .get_encryption_key(sender)
+                    .await?
+                    .filter(|published| published.encryption_key == encryption_key);
+                let encryption_key_id = match published {
+                    Some(published) => *published.id(),
+                    None => {
+                        dwallet_client(context, sender, gas, gas_budget, wait)?
+                            .publish_encryption_key(&encryption_key)
+                            .await?
+                    }
+                };
+
+                SuiClientCommandResult::EncryptionKey(EncryptionKeyOutput {
+                    owner: sender,
+                    encryption_key_id,
+                    encryption_key: Hex::encode(encryption_key),
+                })
+            }
+            SuiDWalletCommands::Retry {
+                session,
+                dwallet,
+                encoding,
+                gas,
+                gas_budget,
+            } => {
+                let sender = dwallet_sender(context, gas).await?;
+                let alias = match dwallet {
+                    Some(dwallet) => context.config.resolve_dwallet(&dwallet)?.alias.clone(),
+                    None => {
+                        let retried = context
+                            .get_client()
+                            .await?
+                            .dwallet_api()
+                            .get_sign_session(session)
+                            .await?
+                            .ok_or_else(|| anyhow!("There is no sign session [{session}]"))?;
+                        let dwallet_id = retried.dwallet_id.bytes;
+                        context
+                            .config
+                            .dwallets
+                            .iter()
+                            .find(|dwallet| dwallet.dwallet_id == dwallet_id)
+                            .ok_or_else(|| {
+                                anyhow!(
+                                    "The dWallet [{dwallet_id}] of sign session [{session}] is not \
+                                     in the client config, pass it with --dwallet"
+                                )
+                            })?
+                            .alias
+                            .clone()
+                    }
+                };
+                let dwallet = signing_dwallet(context, Some(&alias), sender)?;
+                let session_id = dwallet_client(context, sender, gas, gas_budget, wait)?
+                    .retry_sign(&dwallet, session)
+                    .await?;
+                // Recorded before waiting, for the link to be kept if the retry fails too.
+                set_session_retry(context, &alias, session, session_id)?;
+                let sign_output = dwallet_client(context, sender, gas, gas_budget, wait)?
+                    .wait_for_signatures(session_id)
+                    .await?;
+
+                SuiClientCommandResult::SignRetry(SignRetryOutput {
+                    dwallet_id: dwallet.dwallet_id,
+                    retried_session_id: session,
+                    session_id,
+                    sign_output_id: *sign_output.id.object_id(),
+                    signatures: sign_output
+                        .signatures
+                        .iter()
+                        .map(|s| encoding.encode(s))
+                        .collect(),
+                })
+            }
+            SuiDWalletCommands::Sessions { dwallet, limit } => {
+                let dwallet_id = match dwallet {
+                    Some(dwallet) => context.config.resolve_dwallet_id(&dwallet)?,
+                    None => context.config.get_active_dwallet()?.dwallet_id,
+                };
+                let session_retries = context
+                    .config
+                    .dwallets
+                    .iter()
+                    .find(|dwallet| dwallet.dwallet_id == dwallet_id)
+                    .map(|dwallet| dwallet.session_retries.clone())
+                    .unwrap_or_default();
+                let sessions = context
+                    .get_client()
+                    .await?
+                    .dwallet_api()
+                    .get_sign_session_statuses(dwallet_id, limit)
+                    .await?;
+
+                let sessions = sessions
+                    .into_iter()
+                    .map(|info| {
+                        let (sign_output_id, signed_at_ms) = match info.status {
+                            SignSessionStatus::Pending => (None, None),
+                            SignSessionStatus::Signed {
+                                sign_output_id,
+                                signed_at_ms,
+                            } => (Some(sign_output_id), signed_at_ms),
+                        };
+                        let session_id = info.session.session_id.bytes;
+                        let retried_by = session_retries.get(&session_id).copied();
+                        let retry_of = session_retries
+                            .iter()
+                            .find(|(_, retry)| **retry == session_id)
+                            .map(|(retried, _)| *retried);
+                        let status = match (sign_output_id, retried_by) {
+                            (Some(_), _) => "signed",
+                            (None, Some(_)) => "retried",
+                            (None, None) => "pending",
+                        };
+                        SignSessionOutput {
+                            session_id,
+                            status: status.to_string(),
+                            message_digests: info
+                                .session
+                                .messages
+                                .iter()
+                                .map(|message| Hex::encode(Sha256::digest(message).digest))
+                                .collect(),
+                            sender: info.session.sender,
+                            requested_at_ms: info.requested_at_ms,
+                            sign_output_id,
+                            signed_at_ms,
+                            retry_of,
+                            retried_by,
+                        }
+                    })
+                    .collect();
+
+                SuiClientCommandResult::SignSessions(SignSessionsOutput {
                     dwallet_id,
-                    sign_output_id: sign_output.id.object_id().clone(),
+                    sessions,
+                })
+            }
+            SuiDWalletCommands::Sign {
+                dwallet,
+                message,
+                messages,
+                messages_file,
+                encoding,
+                dry_run,
+                gas,
+                gas_budget,
+                serialize_unsigned_transaction,
+                serialize_signed_transaction,
+                hash
+            } => {
+                ensure_not_serialized(serialize_unsigned_transaction, serialize_signed_transaction)?;
+
+                let mut messages_vec = message
+                    .iter()
+                    .map(|message| parse_message(message))
+                    .collect::<Result<Vec<_>, _>>()?;
+                for m in messages {
+                    messages_vec.push(
+                        Base64::try_from(m)
+                            .map_err(|e| anyhow!(e))?
+                            .to_vec()
+                            .map_err(|e| anyhow!(e))?,
+                    );
+                }
+                if let Some(path) = messages_file {
+                    let file = fs::read_to_string(&path).with_context(|| {
+                        format!("Cannot read the messages from {}", path.display())
+                    })?;
+                    for line in file.lines().map(str::trim).filter(|line| !line.is_empty()) {
+                        messages_vec.push(parse_message(line)?);
+                    }
+                }
+
+                let sender = dwallet_sender(context, gas).await?;
+                let dwallet = signing_dwallet(context, dwallet.as_deref(), sender)?;
+                let client = dwallet_client(context, sender, gas, gas_budget, wait)?;
+                if dry_run {
+                    return Ok(SuiClientCommandResult::SignDryRun(vec![
+                        dry_run_sign(&client, &dwallet, &messages_vec, hash).await?,
+                    ]));
+                }
+                let session_id = client
+                    .request_sign(&dwallet, messages_vec, hash.into())
+                    .await?;
+                let sign_output = client.wait_for_signatures(session_id).await?;
+
+                let signatures = sign_output
+                    .signatures
+                    .iter()
+                    .map(|s| encoding.encode(s))
+                    .collect::<Vec<_>>();
+
+                SuiClientCommandResult::NewSignOutput(NewSignOutput {
+                    dwallet_id: dwallet.dwallet_id,
+                    sign_output_id: *sign_output.id.object_id(),
                     signatures,
                 })
+            }
+            SuiDWalletCommands::SignBatch {
+                dwallet,
+                input,
+                output,
+                hash,
+                encoding,
+                batch_size,
+                dry_run,
+                gas,
+                gas_budget,
+            } => {
+                if batch_size == 0 {
+                    bail!("The batch size must be positive");
+                }
+                if output.exists() && !dry_run {
+                    bail!("{} already exists", output.display());
+                }
+
+                // The messages are batched in order, each with the messages hashed alike.
+                let mut batches: Vec<(Hash, Vec<(String, Vec<u8>)>)> = vec![];
+                for request in read_sign_batch_manifest(&input)? {
+                    let message = parse_message(&request.message)
+                        .with_context(|| format!("Invalid message [{}]", request.id))?;
+                    let message_hash = request.hash.unwrap_or(hash.clone());
+                    let batch = batches.iter_mut().find(|(batch_hash, messages)| {
+                        *batch_hash == message_hash && messages.len() < batch_size
+                    });
+                    match batch {
+                        Some((_, messages)) => messages.push((request.id, message)),
+                        None => batches.push((message_hash, vec![(request.id, message)])),
+                    }
+                }
+
+                let sender = dwallet_sender(context, gas).await?;
+                let dwallet = signing_dwallet(context, dwallet.as_deref(), sender)?;
+                let client = dwallet_client(context, sender, gas, gas_budget, wait)?;
+                if dry_run {
+                    let mut dry_runs = vec![];
+                    for (hash, messages) in batches {
+                        let messages: Vec<_> =
+                            messages.into_iter().map(|(_, message)| message).collect();
+                        dry_runs.push(dry_run_sign(&client, &dwallet, &messages, hash).await?);
+                    }
+                    return Ok(SuiClientCommandResult::SignDryRun(dry_runs));
+                }
+                let total = batches.len();
+                let mut signatures = vec![];
+                let mut sessions = vec![];
+                for (i, (hash, messages)) in batches.into_iter().enumerate() {
+                    let (ids, messages): (Vec<_>, Vec<_>) = messages.into_iter().unzip();
+                    let signed = async {
+                        let session_id = client
+                            .request_sign(&dwallet, messages, hash.clone().into())
+                            .await?;
+                        let sign_output = client.wait_for_signatures(session_id).await?;
+                        Ok::<_, anyhow::Error>((session_id, sign_output))
+                    }
+                    .await;
+                    let (session_id, sign_output) = match signed {
+                        Ok(signed) => signed,
+                        Err(e) if signatures.is_empty() => return Err(e),
+                        Err(e) => {
+                            return Err(e.context(format!(
+                                "Signing batch {} of {total} failed, the signatures of the previous batches are in {}",
+                                i + 1,
+                                output.display()
+                            )))
+                        }
+                    };
+
+                    let sign_output_id = *sign_output.id.object_id();
+                    sessions.push(SignBatchSessionOutput {
+                        session_id,
+                        sign_output_id,
+                        hash: hash.to_string(),
+                        messages: ids.len(),
+                    });
+                    signatures.extend(ids.into_iter().zip(&sign_output.signatures).map(
+                        |(id, signature)| SignBatchSignature {
+                            id,
+                            hash: hash.to_string(),
+                            session_id,
+                            sign_output_id,
+                            signature: encoding.encode(signature),
+                        },
+                    ));
+                    write_sign_batch_manifest(&output, &signatures)?;
+                }
+
+                SuiClientCommandResult::SignBatch(SignBatchOutput {
+                    dwallet_id: dwallet.dwallet_id,
+                    output,
+                    signed_messages: signatures.len(),
+                    sessions,
+                })
+            }
+            SuiDWalletCommands::SignBtcPsbt {
+                dwallet,
+                psbt: psbt_path,
+                output,
+                extract,
+                network,
+                allow_any_sighash,
+                yes,
+                dry_run,
+                gas,
+                gas_budget,
+            } => {
+                let mut psbt = read_psbt(&psbt_path)?;
+
+                let sender = dwallet_sender(context, gas).await?;
+                let dwallet = signing_dwallet(context, dwallet.as_deref(), sender)?;
+                let client = dwallet_client(context, sender, gas, gas_budget, wait)?;
+                let public_key = client
+                    .get_dwallet(dwallet.dwallet_id)
+                    .await?
+                    .public_key_sec1()
+                    .ok()
+                    .and_then(|public_key| BitcoinPublicKey::from_slice(&public_key).ok())
+                    .ok_or_else(|| anyhow!("Invalid public key of dWallet [{}]", dwallet.alias))?;
+
+                let requests = btc::sighash_requests(&psbt, &public_key, allow_any_sighash)?;
+                if requests.is_empty() {
+                    bail!(
+                        "No input of {} is spent from dWallet [{}]",
+                        psbt_path.display(),
+                        dwallet.alias
+                    );
+                }
+                let messages: Vec<_> = requests
+                    .iter()
+                    .map(SighashRequest::dwallet_message)
+                    .collect();
+                eprintln!("{}", btc_psbt_preview(&psbt, &public_key, network)?);
+                if dry_run {
+                    return Ok(SuiClientCommandResult::SignDryRun(vec![
+                        dry_run_sign(&client, &dwallet, &messages, Hash::SHA256).await?,
+                    ]));
+                }
+                if !yes {
+                    eprint!(
+                        "Sign {} input(s) of this PSBT with dWallet [{}] [y/N]? ",
+                        requests.len(),
+                        dwallet.alias
+                    );
+                    if !matches!(read_line(), Ok(line) if line.trim().to_lowercase() == "y") {
+                        bail!("Signing cancelled");
+                    }
+                }
+                let session_id = client
+                    .request_sign(&dwallet, messages, Hash::SHA256.into())
+                    .await?;
+                let sign_output = client.wait_for_signatures(session_id).await?;
+                let sign_output_id = *sign_output.id.object_id();
+                btc::add_signatures(&mut psbt, &public_key, &requests, sign_output.signatures)?;
+                btc::finalize_psbt(&mut psbt, &public_key)?;
+
+                let output = output.unwrap_or(psbt_path);
+                let raw_transaction = if extract {
+                    Some(Hex::encode(btc::extract_transaction(&psbt)?))
+                } else {
+                    None
+                };
+                let contents = match &raw_transaction {
+                    Some(raw_transaction) => raw_transaction.clone(),
+                    None => btc::psbt_to_base64(&psbt),
+                };
+                fs::write(&output, contents)
+                    .with_context(|| format!("Cannot write to {}", output.display()))?;
+
+                SuiClientCommandResult::SignedBtcPsbt(SignedBtcPsbtOutput {
+                    dwallet_id: dwallet.dwallet_id,
+                    sign_output_id,
+                    signed_inputs: requests.iter().map(|request| request.input_index).collect(),
+                    txid: psbt.unsigned_tx.txid().to_string(),
+                    output,
+                    raw_transaction,
+                })
+            }
+            SuiDWalletCommands::SignEthTx {
+                dwallet,
+                to,
+                value,
+                data,
+                chain_id,
+                nonce,
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                yes,
+                dry_run,
+                gas,
+                gas_budget,
+            } => {
+                let transaction = Eip1559Transaction {
+                    chain_id,
+                    nonce,
+                    max_priority_fee_per_gas,
+                    max_fee_per_gas,
+                    gas_limit,
+                    to: to.as_deref().map(eth::address_from_hex).transpose()?,
+                    value,
+                    data: data
+                        .as_deref()
+                        .map(parse_message)
+                        .transpose()?
+                        .unwrap_or_default(),
+                    access_list: vec![],
+                };
+
+                let sender = dwallet_sender(context, gas).await?;
+                let dwallet = signing_dwallet(context, dwallet.as_deref(), sender)?;
+                let client = dwallet_client(context, sender, gas, gas_budget, wait)?;
+                let public_key = client
+                    .get_dwallet(dwallet.dwallet_id)
+                    .await?
+                    .public_key_sec1()
+                    .with_context(|| {
+                        format!("Invalid public key of dWallet [{}]", dwallet.alias)
+                    })?;
+
+                eprintln!("{}", eth_transaction_preview(&transaction, &public_key)?);
+                if dry_run {
+                    let messages = [transaction.signing_payload()];
+                    return Ok(SuiClientCommandResult::SignDryRun(vec![
+                        dry_run_sign(&client, &dwallet, &messages, Hash::KECCAK256).await?,
+                    ]));
+                }
+                if !yes {
+                    eprint!(
+                        "Sign this transaction with dWallet [{}] [y/N]? ",
+                        dwallet.alias
+                    );
+                    if !matches!(read_line(), Ok(line) if line.trim().to_lowercase() == "y") {
+                        bail!("Signing cancelled");
+                    }
+                }
+
+                let session_id = client
+                    .request_sign(
+                        &dwallet,
+                        vec![transaction.signing_payload()],
+                        Hash::KECCAK256.into(),
+                    )
+                    .await?;
+                let sign_output = client.wait_for_signatures(session_id).await?;
+                let signature = sign_output
+                    .signatures
+                    .first()
+                    .ok_or_else(|| anyhow!("No signature in sign output [{session_id}]"))?;
+                let raw_transaction = transaction.encode_signed(&public_key, signature)?;
+
+                SuiClientCommandResult::SignedEthTransaction(SignedEthTransactionOutput {
+                    dwallet_id: dwallet.dwallet_id,
+                    sign_output_id: *sign_output.id.object_id(),
+                    transaction_hash: format!(
+                        "0x{}",
+                        Hex::encode(eth::transaction_hash(&raw_transaction))
+                    ),
+                    raw_transaction: format!("0x{}", Hex::encode(raw_transaction)),
+                })
+            }
+            SuiDWalletCommands::Transfer {
+                dwallet,
+                to,
+                yes,
+                gas,
+                gas_budget,
+            } => {
+                let recipient = get_identity_address(Some(to), context)?;
+                let sender = dwallet_sender(context, gas).await?;
+                let dwallet = signing_dwallet(context, dwallet.as_deref(), sender)?;
+                let encryption_key = context
+                    .get_client()
+                    .await?
+                    .dwallet_api()
+                    .get_encryption_key(recipient)
+                    .await?
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "[{recipient}] has not published an encryption key, it must run `dwallet publish-encryption-key` first"
+                        )
+                    })?;
+                // The key of the recipient is in the keystore when transferring between own
+                // addresses, and the share can then be checked to decrypt.
+                let recipient_key = context
+                    .config
+                    .keystore
+                    .get_key(&recipient)
+                    .ok()
+                    .map(ShareTransferKey::derive);
+                if recipient_key
+                    .as_ref()
+                    .is_some_and(|key| key.public_key() != encryption_key.encryption_key)
+                {
+                    bail!(
+                        "The encryption key published by [{recipient}] is not derived from its key in the keystore"
+                    );
+                }
+
+                eprintln!(
+                    "Transferring dWallet [{}] {} to [{recipient}], with its user share encrypted to the encryption key {}.",
+                    dwallet.alias,
+                    dwallet.dwallet_id,
+                    encryption_key.id()
+                );
+                if !yes {
+                    eprint!(
+                        "The recipient will own the dWallet, and [{sender}] will no longer be able to sign with it. Transfer [y/N]? "
+                    );
+                    if !matches!(read_line(), Ok(line) if line.trim().to_lowercase() == "y") {
+                        bail!("Transfer cancelled");
+                    }
+                }
+
+                let (encrypted_user_share_id, response) =
+                    dwallet_client(context, sender, gas, gas_budget, wait)?
+                        .transfer_dwallet_with_share(&dwallet, recipient, &encryption_key)
+                        .await?;
+
+                let decryption_verified = match &recipient_key {
+                    Some(key) => {
+                        let (sent, _) = context
+                            .get_client()
+                            .await?
+                            .dwallet_api()
+                            .get_encrypted_user_share(encrypted_user_share_id)
+                            .await?;
+                        let share = key
+                            .decrypt(&sent.encrypted_user_share, dwallet.dwallet_id.as_ref())
+                            .with_context(|| {
+                                format!(
+                                    "[{recipient}] cannot decrypt the user share {encrypted_user_share_id}"
+                                )
+                            })?;
+                        if *share != serde_json::to_vec(&dwallet.dkg_output()?)? {
+                            bail!(
+                                "The user share {encrypted_user_share_id} decrypted by [{recipient}] is not the one of dWallet [{}]",
+                                dwallet.alias
+                            );
+                        }
+                        true
+                    }
+                    None => false,
+                };
+
+                SuiClientCommandResult::DWalletTransfer(DWalletTransferOutput {
+                    dwallet_id: dwallet.dwallet_id,
+                    recipient,
+                    encrypted_user_share_id,
+                    encryption_key_id: *encryption_key.id(),
+                    decryption_verified,
+                    digest: response.digest,
+                })
+            }
+            SuiDWalletCommands::Verify {
+                dwallet,
+                message,
+                signature,
+                encoding,
+                hash,
+            } => {
+                let dwallet_id = match dwallet {
+                    Some(dwallet) => context.config.resolve_dwallet_id(&dwallet)?,
+                    None => context.config.get_active_dwallet()?.dwallet_id,
+                };
+                let message = parse_message(&message)?;
+                let signature = encoding.decode(&signature)?;
+                let dwallet = context
+                    .get_client()
+                    .await?
+                    .dwallet_api()
+                    .get_dwallet(dwallet_id)
+                    .await?;
+                let public_key = dwallet
+                    .public_key_sec1()
+                    .map_err(|e| anyhow!("Invalid public key of dWallet {dwallet_id}: {e}"))?;
+                verify_signature(&public_key, &message, hash.clone().into(), &signature)
+                    .with_context(|| format!("Invalid signature by dWallet {dwallet_id}"))?;
+
+                SuiClientCommandResult::VerifiedSignature(VerifiedSignatureOutput {
+                    dwallet_id,
+                    public_key: Hex::encode(public_key),
+                    hash: hash.to_string(),
+                    message_digest: Hex::encode(Sha256::digest(&message).digest),
+                })
+            }
+            SuiDWalletCommands::VerifyProof { proof } => {
+                let bytes = fs::read(&proof)
+                    .with_context(|| format!("Cannot read the proof from {}", proof.display()))?;
+                let verified = SignProof::from_bytes(&bytes)?
+                    .verify()
+                    .with_context(|| format!("Invalid proof {}", proof.display()))?;
+
+                SuiClientCommandResult::SignProof(sign_proof_output(verified, None))
+            }
+            SuiDWalletCommands::Watch {
+                dwallet,
+                jsonl,
+                fail_after,
+            } => {
+                let dwallet_id = match dwallet {
+                    Some(dwallet) => context.config.resolve_dwallet_id(&dwallet)?,
+                    None => context.config.get_active_dwallet()?.dwallet_id,
+                };
+                let env = context.config.get_active_env()?;
+                if env.ws.is_none() {
+                    bail!(
+                        "Environment [{}] has no WebSocket endpoint to watch dWallets with, add one with `sui client new-env --ws`",
+                        env.alias
+                    );
+                }
+                let events =
+                    watch_dwallet(context, dwallet_id, jsonl, Duration::from_secs(fail_after))
+                        .await?;
 
+                SuiClientCommandResult::DWalletWatch(DWalletWatchOutput { dwallet_id, events })
             }
         });
         ret
     }
 }
+
+impl SuiFutureSignCommands {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+        wait: &DWalletWaitOptions,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        Ok(match self {
+            SuiFutureSignCommands::Approve {
+                dwallet,
+                message,
+                gas,
+                gas_budget,
+            } => {
+                let messages = message
+                    .iter()
+                    .map(|message| parse_message(message))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let sender = dwallet_sender(context, gas).await?;
+                let dwallet = match dwallet {
+                    Some(dwallet) => context.config.resolve_dwallet(&dwallet)?,
+                    None => context.config.get_active_dwallet()?,
+                };
+                let approvals_id = dwallet_client(context, sender, gas, gas_budget, wait)?
+                    .approve_future_sign(dwallet, &messages)
+                    .await?;
+
+                SuiClientCommandResult::FutureSign(FutureSignOutput {
+                    approvals_id,
+                    alias: Some(dwallet.alias.clone()),
+                    dwallet_cap_id: dwallet.dwallet_cap_id,
+                    messages: messages.iter().map(Hex::encode).collect(),
+                })
+            }
+            SuiFutureSignCommands::List { dwallet } => {
+                let dwallet_cap_id = dwallet
+                    .map(|dwallet| {
+                        context
+                            .config
+                            .resolve_dwallet(&dwallet)
+                            .map(|dwallet| dwallet.dwallet_cap_id)
+                    })
+                    .transpose()?;
+                let owner = context.active_address()?;
+                let holders: Vec<_> = context
+                    .get_client()
+                    .await?
+                    .dwallet_api()
+                    .get_owned_approvals_holders_stream(owner)
+                    .try_collect()
+                    .await?;
+
+                let future_signs = holders
+                    .iter()
+                    .filter_map(|holder| {
+                        // `approve` never creates holders without approvals.
+                        let cap_id = holder.message_approvals.first()?.dwallet_cap_id.bytes;
+                        if dwallet_cap_id.is_some_and(|dwallet_cap_id| dwallet_cap_id != cap_id) {
+                            return None;
+                        }
+                        Some(FutureSignOutput {
+                            approvals_id: *holder.id(),
+                            alias: context
+                                .config
+                                .dwallets
+                                .iter()
+                                .find(|dwallet| dwallet.dwallet_cap_id == cap_id)
+                                .map(|dwallet| dwallet.alias.clone()),
+                            dwallet_cap_id: cap_id,
+                            messages: holder.messages().iter().map(Hex::encode).collect(),
+                        })
+                    })
+                    .collect();
+
+                SuiClientCommandResult::FutureSigns(FutureSignsOutput {
+                    owner,
+                    future_signs,
+                })
+            }
+            SuiFutureSignCommands::Sign {
+                approvals,
+                dwallet,
+                hash,
+                encoding,
+                gas,
+                gas_budget,
+            } => {
+                let sender = dwallet_sender(context, gas).await?;
+                let dwallet = signing_dwallet(context, dwallet.as_deref(), sender)?;
+                let client = dwallet_client(context, sender, gas, gas_budget, wait)?;
+                let session_id = client
+                    .request_future_sign(&dwallet, approvals, hash.into())
+                    .await?;
+                let sign_output = client.wait_for_signatures(session_id).await?;
+
+                SuiClientCommandResult::NewSignOutput(NewSignOutput {
+                    dwallet_id: dwallet.dwallet_id,
+                    sign_output_id: *sign_output.id.object_id(),
+                    signatures: sign_output
+                        .signatures
+                        .iter()
+                        .map(|s| encoding.encode(s))
+                        .collect(),
+                })
+            }
+            SuiFutureSignCommands::Cancel {
+                approvals,
+                gas,
+                gas_budget,
+            } => {
+                let sender = dwallet_sender(context, gas).await?;
+                let response = dwallet_client(context, sender, gas, gas_budget, wait)?
+                    .cancel_future_sign(approvals)
+                    .await?;
+                SuiClientCommandResult::TransactionBlock(response)
+            }
+        })
+    }
+}
+
+impl SuiDWalletPolicyCommands {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+        wait: &DWalletWaitOptions,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        Ok(match self {
+            SuiDWalletPolicyCommands::Set {
+                dwallet,
+                allow,
+                max_messages,
+                window_secs,
+                co_approver,
+                threshold,
+                gas,
+                gas_budget,
+            } => {
+                let allowlist = allow
+                    .into_iter()
+                    .map(|address| get_identity_address(Some(address), context))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let co_approvers = co_approver
+                    .into_iter()
+                    .map(|address| get_identity_address(Some(address), context))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let co_approval_threshold = threshold.unwrap_or(co_approvers.len() as u64);
+                if co_approval_threshold > co_approvers.len() as u64 {
+                    bail!(
+                        "A threshold of {co_approval_threshold} exceeds the {} co-approvers",
+                        co_approvers.len()
+                    );
+                }
+                let window_ms = window_secs.unwrap_or_default().saturating_mul(1000);
+                if max_messages.is_some_and(|max_messages| max_messages > 0) && window_ms == 0 {
+                    bail!("The window of the rate limit must not be empty");
+                }
+                let rules = SigningPolicyRules {
+                    allowlist,
+                    max_messages: max_messages.unwrap_or_default(),
+                    window_ms,
+                    co_approvers,
+                    co_approval_threshold,
+                };
+
+                let sender = dwallet_sender(context, gas).await?;
+                let dwallet = policy_dwallet(context, dwallet.as_deref())?.clone();
+                let client = dwallet_client(context, sender, gas, gas_budget, wait)?;
+                let (policy_id, response) = client.set_signing_policy(&dwallet, &rules).await?;
+                let (policy, _) = client.get_signing_policy(policy_id).await?;
+                set_signing_policy_id(context, &dwallet.alias, Some(policy_id))?;
+
+                SuiClientCommandResult::SigningPolicy(signing_policy_output(
+                    &policy,
+                    Some(dwallet.alias),
+                    Some(response.digest),
+                ))
+            }
+            SuiDWalletPolicyCommands::Show { dwallet, policy } => {
+                let (policy_id, alias) = match policy {
+                    Some(policy_id) => (policy_id, None),
+                    None => {
+                        let dwallet = policy_dwallet(context, dwallet.as_deref())?;
+                        let policy_id = dwallet.signing_policy_id.ok_or_else(|| {
+                            anyhow!(
+                                "No signing policy recorded for dWallet [{}], pass its ID with --policy",
+                                dwallet.alias
+                            )
+                        })?;
+                        (policy_id, Some(dwallet.alias.clone()))
+                    }
+                };
+                let (policy, _) = context
+                    .get_client()
+                    .await?
+                    .dwallet_api()
+                    .get_signing_policy(policy_id)
+                    .await?;
+
+                SuiClientCommandResult::SigningPolicy(signing_policy_output(&policy, alias, None))
+            }
+            SuiDWalletPolicyCommands::Remove {
+                dwallet,
+                gas,
+                gas_budget,
+            } => {
+                let sender = dwallet_sender(context, gas).await?;
+                let dwallet = policy_dwallet(context, dwallet.as_deref())?.clone();
+                let response = dwallet_client(context, sender, gas, gas_budget, wait)?
+                    .remove_signing_policy(&dwallet)
+                    .await?;
+                set_signing_policy_id(context, &dwallet.alias, None)?;
+                SuiClientCommandResult::TransactionBlock(response)
+            }
+        })
+    }
+}
+
+/// The dWallet `dwallet` of the client config, or else the active dWallet.
+fn policy_dwallet<'a>(
+    context: &'a WalletContext,
+    dwallet: Option<&str>,
+) -> Result<&'a DWalletSecretShare, anyhow::Error> {
+    match dwallet {
+        Some(dwallet) => context.config.resolve_dwallet(dwallet),
+        None => context.config.get_active_dwallet(),
+    }
+}
+
+/// Record `policy_id` as the signing policy of the dWallet `alias` in the client config.
+fn set_signing_policy_id(
+    context: &mut WalletContext,
+    alias: &str,
+    policy_id: Option<ObjectID>,
+) -> Result<(), anyhow::Error> {
+    if let Some(dwallet) = context
+        .config
+        .dwallets
+        .iter_mut()
+        .find(|dwallet| dwallet.alias == alias)
+    {
+        dwallet.signing_policy_id = policy_id;
+    }
+    context.config.save()
+}
+
+/// Record in the client config that the sign session `retry` of the dWallet `alias` retried the
+/// session `retried`.
+fn set_session_retry(
+    context: &mut WalletContext,
+    alias: &str,
+    retried: ObjectID,
+    retry: ObjectID,
+) -> Result<(), anyhow::Error> {
+    if let Some(dwallet) = context
+        .config
+        .dwallets
+        .iter_mut()
+        .find(|dwallet| dwallet.alias == alias)
+    {
+        dwallet.session_retries.insert(retried, retry);
+    }
+    context.config.save()
+}
+
+fn signing_policy_output(
+    policy: &SigningPolicy,
+    alias: Option<String>,
+    digest: Option<TransactionDigest>,
+) -> SigningPolicyOutput {
+    SigningPolicyOutput {
+        policy_id: *policy.id(),
+        alias,
+        owner: policy.owner,
+        dwallet_cap_id: *policy.dwallet_cap.id(),
+        allowlist: policy.allowlist.clone(),
+        max_messages: policy.max_messages,
+        window_ms: policy.window_ms,
+        co_approvers: policy.co_approvers.clone(),
+        co_approval_threshold: policy.co_approval_threshold,
+        pending_requests: policy.requests.len(),
+        digest,
+    }
+}
+
+/// The PSBT in the file at `path`, binary or Base64 encoded.
+fn read_psbt(path: &Path) -> Result<Psbt, anyhow::Error> {
+    let contents =
+        fs::read(path).with_context(|| format!("Cannot read the PSBT from {}", path.display()))?;
+    if contents.starts_with(PSBT_MAGIC) {
+        return Psbt::deserialize(&contents)
+            .map_err(|e| anyhow!("Invalid PSBT in {}: {e}", path.display()));
+    }
+    let contents = String::from_utf8_lossy(&contents);
+    btc::psbt_from_base64(contents.trim())
+        .with_context(|| format!("Invalid PSBT in {}", path.display()))
+}
+
+/// The dWallet `dwallet` of the client config, or else the active dWallet, with its user share
+/// decrypted with the key of `sender` if it is held encrypted.
+fn signing_dwallet(
+    context: &WalletContext,
+    dwallet: Option<&str>,
+    sender: SuiAddress,
+) -> Result<DWalletSecretShare, anyhow::Error> {
+    let mut dwallet = match dwallet {
+        Some(dwallet) => context.config.resolve_dwallet(dwallet)?,
+        None => context.config.get_active_dwallet()?,
+    }
+    .clone();
+    if dwallet.encrypted_share_path.is_some() {
+        let key = ShareEncryptionKey::derive(context.config.keystore.get_key(&sender)?);
+        dwallet.dkg_output = Some(dwallet.decrypt_share(&key)?);
+    }
+    Ok(dwallet)
+}
+
+/// The fields of `transaction`, sent from the dWallet with the public key `public_key`, with the
+/// amounts in the native currency of its chain.
+fn eth_transaction_preview(
+    transaction: &Eip1559Transaction,
+    public_key: &[u8],
+) -> Result<String, anyhow::Error> {
+    let chain = evm_chain(transaction.chain_id);
+    let (symbol, decimals) = chain.map_or(("ETH", 18), |chain| {
+        (
+            chain.native_currency.symbol.as_str(),
+            chain.native_currency.decimals,
+        )
+    });
+    let amount = |wei: u128| format!("{} {symbol} ({wei} wei)", format_units(wei, decimals));
+
+    let to = match &transaction.to {
+        Some(to) => format!("0x{}", Hex::encode(to)),
+        None => "(contract creation)".to_string(),
+    };
+    // The selector of the called function, unless the transaction is a plain transfer.
+    let selector = match (&transaction.to, transaction.data.get(..4)) {
+        (None, _) => "(none, contract creation)".to_string(),
+        (Some(_), None) if transaction.data.is_empty() => "(none, transfer)".to_string(),
+        (Some(_), None) => "(none, call data shorter than a selector)".to_string(),
+        (Some(_), Some(selector)) => format!("0x{}", Hex::encode(selector)),
+    };
+    let max_fee = transaction
+        .max_fee_per_gas
+        .saturating_mul(transaction.gas_limit as u128);
+    let fields = [
+        (
+            "chain",
+            match chain {
+                Some(chain) => format!("{} ({})", chain.name, transaction.chain_id),
+                None => transaction.chain_id.to_string(),
+            },
+        ),
+        ("from", ethereum_address(public_key)?),
+        ("to", to),
+        ("value", amount(transaction.value)),
+        ("max fee", amount(max_fee)),
+        ("selector", selector),
+        ("data", format!("0x{}", Hex::encode(&transaction.data))),
+        ("nonce", transaction.nonce.to_string()),
+        ("gas limit", transaction.gas_limit.to_string()),
+        (
+            "max fee per gas",
+            format!("{} wei", transaction.max_fee_per_gas),
+        ),
+        (
+            "max priority fee per gas",
+            format!("{} wei", transaction.max_priority_fee_per_gas),
+        ),
+    ];
+    Ok(fields
+        .iter()
+        .map(|(name, value)| format!("{name:<24} : {value}"))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// The outputs, amounts and fee of `psbt`, spent from the dWallet with the public key
+/// `public_key`.
+fn btc_psbt_preview(
+    psbt: &Psbt,
+    public_key: &BitcoinPublicKey,
+    network: BitcoinNetwork,
+) -> Result<String, anyhow::Error> {
+    let summary = btc::summarize_psbt(psbt, public_key, network)?;
+    let unverified = format!(
+        "(unverified, input(s) {} give their amount without their previous transaction)",
+        summary
+            .unverified_inputs
+            .iter()
+            .map(|input_index| input_index.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let mut fields = vec![
+        ("network", network.to_string()),
+        ("txid", psbt.unsigned_tx.txid().to_string()),
+        (
+            "spent from the dWallet",
+            if summary.spent_unverified {
+                format!("{} {unverified}", summary.spent)
+            } else {
+                summary.spent.to_string()
+            },
+        ),
+    ];
+    for output in &summary.outputs {
+        let name = if output.change { "change" } else { "to" };
+        fields.push((name, format!("{} {}", output.recipient, output.amount)));
+    }
+    fields.push((
+        "fee",
+        match summary.fee {
+            Some(fee) if summary.unverified_inputs.is_empty() => fee.to_string(),
+            Some(fee) => format!("{fee} {unverified}"),
+            None => "(unknown, an input is missing its spent output)".to_string(),
+        },
+    ));
+    if summary.foreign_inputs > 0 {
+        fields.push((
+            "other inputs",
+            format!("{} not spent from the dWallet", summary.foreign_inputs),
+        ));
+    }
+    Ok(fields
+        .iter()
+        .map(|(name, value)| format!("{name:<24} : {value}"))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// `amount` of the smallest unit of a currency with `decimals` decimals, in whole units.
+fn format_units(amount: u128, decimals: u8) -> String {
+    let unit = 10u128.pow(decimals as u32);
+    let fraction = format!("{:0width$}", amount % unit, width = decimals as usize);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        (amount / unit).to_string()
+    } else {
+        format!("{}.{fraction}", amount / unit)
+    }
+}
+
+/// A message given on the command line: hex encoded, with or without `0x`, or `@<path>` for the
+/// contents of a file.
+fn parse_message(message: &str) -> Result<Vec<u8>, anyhow::Error> {
+    match message.strip_prefix('@') {
+        Some(path) => {
+            fs::read(path).with_context(|| format!("Cannot read the message from {path}"))
+        }
+        None => Hex::decode(message.strip_prefix("0x").unwrap_or(message))
+            .map_err(|e| anyhow!("Invalid hex message [{message}]: {e}")),
+    }
+}
+
+/// The dry run of the sign request of `messages` by `dwallet`, each hashed with `hash`.
+async fn dry_run_sign(
+    client: &DWalletClient<'_>,
+    dwallet: &DWalletSecretShare,
+    messages: &[Vec<u8>],
+    hash: Hash,
+) -> Result<SignDryRunOutput, anyhow::Error> {
+    let dry_run = client
+        .dry_run_sign(dwallet, messages, hash.clone().into())
+        .await?;
+    Ok(SignDryRunOutput {
+        dwallet_id: dwallet.dwallet_id,
+        messages: messages.len(),
+        hash: hash.to_string(),
+        would_succeed: dry_run.errors.is_empty(),
+        errors: dry_run.errors,
+        warnings: dry_run.warnings,
+        messages_size: dry_run.messages_size,
+        max_messages_size: dry_run.max_messages_size,
+        presign_gas_budget: dry_run.presign_gas_budget,
+        unused_presigns: dry_run.unused_presigns,
+    })
+}
+
+/// Make sign requests of `messages` random messages with `dwallet`, one at a time, while
+/// `remaining` requests are left, each at the next of `ticks`. Returns the latency or the error of
+/// every request.
+async fn bench_sign_requests(
+    client: &DWalletClient<'_>,
+    dwallet: &DWalletSecretShare,
+    messages: usize,
+    remaining: &AtomicUsize,
+    ticks: &tokio::sync::Mutex<tokio::time::Interval>,
+) -> Vec<Result<Duration, anyhow::Error>> {
+    let mut results = vec![];
+    while remaining
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+        .is_ok()
+    {
+        ticks.lock().await.tick().await;
+        let messages = (0..messages)
+            .map(|_| rand::random::<[u8; 32]>().to_vec())
+            .collect();
+        let start = Instant::now();
+        let signed = match client
+            .request_sign(dwallet, messages, Hash::KECCAK256.into())
+            .await
+        {
+            Ok(session_id) => client.wait_for_signatures(session_id).await,
+            Err(e) => Err(e),
+        };
+        results.push(signed.map(|_| start.elapsed()));
+    }
+    results
+}
+
+/// Split `count` gas coins of `amount` MIST each from a gas coin of `sender`, and return them.
+async fn split_gas_coins(
+    context: &WalletContext,
+    sender: SuiAddress,
+    count: usize,
+    amount: u64,
+    gas_budget: Option<u64>,
+) -> Result<Vec<ObjectID>, anyhow::Error> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.pay_sui(vec![sender; count], vec![amount; count])?;
+    let client = context.get_client().await?;
+    let gas_price = context.get_reference_gas_price().await?;
+    let gas_budget = match gas_budget.or(context.config.get_active_env()?.default_gas_budget) {
+        Some(gas_budget) => gas_budget,
+        None => {
+            let tx = TransactionKind::programmable(builder.clone().finish());
+            client.estimate_gas(sender, tx, Some(gas_price)).await?
+        }
+    };
+    let total = amount
+        .checked_mul(count as u64)
+        .and_then(|total| total.checked_add(gas_budget))
+        .ok_or_else(|| anyhow!("The gas of {count} dWallets of {amount} MIST overflows"))?;
+    let (_, gas) = context
+        .gas_for_owner_budget(sender, total, BTreeSet::new())
+        .await
+        .with_context(|| format!("[{sender}] has no gas coin of {total} MIST to split"))?;
+
+    let tx_data = TransactionData::new_programmable(
+        sender,
+        vec![gas.object_ref()],
+        builder.finish(),
+        gas_budget,
+        gas_price,
+    );
+    let transaction = context.try_sign_transaction(&tx_data).await?;
+    let response = context.execute_transaction_may_fail(transaction).await?;
+    let effects = response
+        .effects
+        .as_ref()
+        .ok_or_else(|| anyhow!("No effects in the response of [{}]", response.digest))?;
+    if let SuiExecutionStatus::Failure { error } = effects.status() {
+        bail!(
+            "Cannot split the gas coins, transaction [{}] failed: {error}",
+            response.digest
+        );
+    }
+    Ok(effects
+        .created()
+        .iter()
+        .map(|created| created.reference.object_id)
+        .collect())
+}
+
+/// The proof of the completed sign session `session_id`, with the checkpoints downloaded from the
+/// fullnode REST API `rest_url`.
+async fn export_sign_proof(
+    context: &WalletContext,
+    session_id: ObjectID,
+    rest_url: &str,
+) -> Result<SignProof, anyhow::Error> {
+    let client = context.get_client().await?;
+    let rest_client = sui_rest_api::Client::new(rest_url);
+    let session = client
+        .dwallet_api()
+        .get_sign_session(session_id)
+        .await?
+        .ok_or_else(|| anyhow!("Sign session [{session_id}] not found"))?;
+    let sign_output = client
+        .get_sign_output(session_id)
+        .await?
+        .ok_or_else(|| anyhow!("Sign session [{session_id}] is not signed yet"))?;
+
+    let transaction_proof = |object_id: ObjectID| {
+        let client = &client;
+        let rest_client = &rest_client;
+        async move {
+            let (digest, checkpoint) = client
+                .dwallet_api()
+                .get_creation_transaction(object_id)
+                .await?
+                .ok_or_else(|| anyhow!("No transaction created object [{object_id}]"))?;
+            let checkpoint = checkpoint.ok_or_else(|| {
+                anyhow!("Transaction [{digest}] is not checkpointed yet, try again later")
+            })?;
+            let checkpoint = rest_client
+                .get_full_checkpoint(checkpoint)
+                .await
+                .with_context(|| {
+                    format!("Cannot download checkpoint {checkpoint} from {rest_url}")
+                })?;
+            let committee = client
+                .governance_api()
+                .get_committee_info(Some(checkpoint.checkpoint_summary.epoch.into()))
+                .await?;
+            Ok::<_, anyhow::Error>(TransactionProof::new(
+                committee.validators.into_iter().collect(),
+                checkpoint,
+                digest,
+            )?)
+        }
+    };
+    let session_proof = transaction_proof(session_id).await?;
+    let sign_output_proof = transaction_proof(*sign_output.id.object_id()).await?;
+    let dwallet_proof = transaction_proof(session.dwallet_id.bytes).await?;
+
+    // The hash of the messages is not recorded on chain: it is the one the signatures verify with.
+    let mut proof = SignProof {
+        session_id,
+        hash: signature_mpc::twopc_mpc_protocols::Hash::KECCAK256.into(),
+        session: session_proof,
+        sign_output: sign_output_proof,
+        dwallet: dwallet_proof,
+    };
+    if proof.verify().is_err() {
+        proof.hash = signature_mpc::twopc_mpc_protocols::Hash::SHA256.into();
+    }
+    Ok(proof)
+}
+
+fn sign_proof_output(verified: VerifiedSignProof, output: Option<PathBuf>) -> SignProofOutput {
+    SignProofOutput {
+        session_id: verified.session_id,
+        dwallet_id: verified.dwallet_id,
+        public_key: Hex::encode(verified.public_key),
+        hash: Hash::from(verified.hash).to_string(),
+        messages: verified.messages.iter().map(Hex::encode).collect(),
+        signatures: verified.signatures.iter().map(Hex::encode).collect(),
+        checkpoints: verified
+            .checkpoints
+            .into_iter()
+            .map(|checkpoint| ProvenCheckpointOutput {
+                epoch: checkpoint.epoch,
+                sequence_number: checkpoint.sequence_number,
+                digest: checkpoint.digest,
+            })
+            .collect(),
+        output,
+    }
+}
+
+/// How often `dwallet watch` checks the owner of the capability of the dWallet, and its sign
+/// sessions that are not signed in time.
+const WATCH_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A lifecycle event of a dWallet, as printed by `dwallet watch`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WatchLine {
+    dwallet_id: ObjectID,
+    /// When the event was received, in milliseconds since the Unix epoch.
+    observed_at_ms: u64,
+    #[serde(flatten)]
+    event: WatchEvent,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+enum WatchEvent {
+    #[serde(rename_all = "camelCase")]
+    PresignRequested { session_id: ObjectID },
+    #[serde(rename_all = "camelCase")]
+    PresignCreated {
+        session_id: ObjectID,
+        presign_id: ObjectID,
+    },
+    #[serde(rename_all = "camelCase")]
+    SignRequested {
+        session_id: ObjectID,
+        sender: SuiAddress,
+        /// The SHA-256 digests of the messages, hex encoded.
+        message_digests: Vec<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    SignCompleted {
+        session_id: ObjectID,
+        sign_output_id: ObjectID,
+        signatures: usize,
+    },
+    #[serde(rename_all = "camelCase")]
+    SignFailed {
+        session_id: ObjectID,
+        reason: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    OwnershipTransferred {
+        from: Option<SuiAddress>,
+        to: Option<SuiAddress>,
+        transaction_digest: Option<TransactionDigest>,
+    },
+}
+
+impl Display for WatchEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let address = |address: &Option<SuiAddress>| match address {
+            Some(address) => address.to_string(),
+            None => "(not an address)".to_string(),
+        };
+        match self {
+            WatchEvent::PresignRequested { session_id } => {
+                write!(f, "presign requested: session {session_id}")
+            }
+            WatchEvent::PresignCreated {
+                session_id,
+                presign_id,
+            } => write!(f, "presign created: {presign_id} of session {session_id}"),
+            WatchEvent::SignRequested {
+                session_id,
+                sender,
+                message_digests,
+            } => write!(
+                f,
+                "sign requested: session {session_id} by {sender}, {} message(s) with SHA-256 {}",
+                message_digests.len(),
+                message_digests.join(", ")
+            ),
+            WatchEvent::SignCompleted {
+                session_id,
+                sign_output_id,
+                signatures,
+            } => write!(
+                f,
+                "sign completed: session {session_id}, {signatures} signature(s) in {sign_output_id}"
+            ),
+            WatchEvent::SignFailed { session_id, reason } => {
+                write!(f, "sign failed: session {session_id}, {reason}")
+            }
+            WatchEvent::OwnershipTransferred {
+                from,
+                to,
+                transaction_digest,
+            } => {
+                write!(f, "ownership transferred: {} -> {}", address(from), address(to))?;
+                if let Some(digest) = transaction_digest {
+                    write!(f, " in transaction {digest}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The owner of the capability `dwallet_cap_id`, if it is owned by an address, and the last
+/// transaction that changed it.
+async fn dwallet_cap_owner(
+    client: &SuiClient,
+    dwallet_cap_id: ObjectID,
+) -> Result<(Option<SuiAddress>, Option<TransactionDigest>), anyhow::Error> {
+    let data = client
+        .read_api()
+        .get_object_with_options(
+            dwallet_cap_id,
+            SuiObjectDataOptions::new()
+                .with_owner()
+                .with_previous_transaction(),
+        )
+        .await?
+        .data
+        .ok_or_else(|| anyhow!("DWalletCap [{dwallet_cap_id}] not found"))?;
+    let owner = match data.owner {
+        Some(Owner::AddressOwner(owner)) => Some(owner),
+        _ => None,
+    };
+    Ok((owner, data.previous_transaction))
+}
+
+/// Print the lifecycle events of the dWallet `dwallet_id` until interrupted, as JSON lines if
+/// `jsonl`, reporting the sign sessions not signed within `fail_after` as failed. Returns the
+/// number of printed events.
+async fn watch_dwallet(
+    context: &WalletContext,
+    dwallet_id: ObjectID,
+    jsonl: bool,
+    fail_after: Duration,
+) -> Result<usize, anyhow::Error> {
+    let client = context.get_client().await?;
+    let dwallet_cap_id = client
+        .dwallet_api()
+        .get_dwallet(dwallet_id)
+        .await?
+        .dwallet_cap_id
+        .bytes;
+    let (mut owner, _) = dwallet_cap_owner(&client, dwallet_cap_id).await?;
+    let mut events = Box::pin(client.subscribe_dwallet_events(dwallet_id).await?);
+    eprintln!("Watching dWallet [{dwallet_id}], press Ctrl-C to stop.");
+
+    let mut printed = 0;
+    let mut print = |event: WatchEvent| -> Result<(), anyhow::Error> {
+        let observed_at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        if jsonl {
+            let line = WatchLine {
+                dwallet_id,
+                observed_at_ms,
+                event,
+            };
+            println!("{}", serde_json::to_string(&line)?);
+        } else {
+            println!("[{observed_at_ms}] {event}");
+        }
+        printed += 1;
+        Ok(())
+    };
+    // The sign sessions not signed yet, with when they were requested.
+    let mut pending: BTreeMap<ObjectID, Instant> = BTreeMap::new();
+    let mut poll = tokio::time::interval(WATCH_POLL_INTERVAL);
+    loop {
+        tokio::select! {
+            event = events.next() => {
+                let Some(event) = event else {
+                    break;
+                };
+                let event = match event? {
+                    DWalletEvent::DWalletCreated(_) => continue,
+                    DWalletEvent::PresignSessionCreated(event) => WatchEvent::PresignRequested {
+                        session_id: event.session_id.bytes,
+                    },
+                    DWalletEvent::PresignCreated(event) => WatchEvent::PresignCreated {
+                        session_id: event.session_id.bytes,
+                        presign_id: event.presign_id.bytes,
+                    },
+                    DWalletEvent::SignSessionCreated(event) => {
+                        pending.insert(event.session_id.bytes, Instant::now());
+                        WatchEvent::SignRequested {
+                            session_id: event.session_id.bytes,
+                            sender: event.sender,
+                            message_digests: event
+                                .messages
+                                .iter()
+                                .map(|message| Hex::encode(Sha256::digest(message).digest))
+                                .collect(),
+                        }
+                    }
+                    DWalletEvent::SignOutputCreated(event) => {
+                        pending.remove(&event.session_id.bytes);
+                        WatchEvent::SignCompleted {
+                            session_id: event.session_id.bytes,
+                            sign_output_id: event.sign_output_id.bytes,
+                            signatures: event.signatures.len(),
+                        }
+                    }
+                };
+                print(event)?;
+            }
+            _ = poll.tick() => {
+                let (new_owner, transaction_digest) =
+                    dwallet_cap_owner(&client, dwallet_cap_id).await?;
+                if new_owner != owner {
+                    print(WatchEvent::OwnershipTransferred {
+                        from: owner,
+                        to: new_owner,
+                        transaction_digest,
+                    })?;
+                    owner = new_owner;
+                }
+                let failed: Vec<_> = pending
+                    .iter()
+                    .filter(|(_, requested_at)| requested_at.elapsed() >= fail_after)
+                    .map(|(session_id, _)| *session_id)
+                    .collect();
+                for session_id in failed {
+                    pending.remove(&session_id);
+                    print(WatchEvent::SignFailed {
+                        session_id,
+                        reason: format!("not signed within {}s", fail_after.as_secs()),
+                    })?;
+                }
+            }
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+    Ok(printed)
+}
+
+/// A message of the input manifest of `dwallet sign-batch`.
+#[derive(Deserialize)]
+struct SignBatchRequest {
+    /// The reference id of the message, unique in the manifest.
+    id: String,
+    /// In the format of `dwallet sign --message`.
+    message: String,
+    #[serde(default)]
+    hash: Option<Hash>,
+}
+
+/// A signed message of the output manifest of `dwallet sign-batch`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SignBatchSignature {
+    id: String,
+    hash: String,
+    session_id: ObjectID,
+    sign_output_id: ObjectID,
+    signature: String,
+}
+
+/// Whether the manifest at `path` is a CSV file rather than JSON.
+fn is_csv_manifest(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"))
+}
+
+/// The messages of the input manifest at `path`, failing if two of them have the same id.
+fn read_sign_batch_manifest(path: &Path) -> Result<Vec<SignBatchRequest>, anyhow::Error> {
+    let requests: Vec<SignBatchRequest> = if is_csv_manifest(path) {
+        csv::Reader::from_path(path)
+            .with_context(|| format!("Cannot read the manifest {}", path.display()))?
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("Invalid manifest {}", path.display()))?
+    } else {
+        let manifest = fs::read_to_string(path)
+            .with_context(|| format!("Cannot read the manifest {}", path.display()))?;
+        serde_json::from_str(&manifest)
+            .with_context(|| format!("Invalid manifest {}", path.display()))?
+    };
+    if requests.is_empty() {
+        bail!("No messages to sign in {}", path.display());
+    }
+    let mut ids = BTreeSet::new();
+    if let Some(duplicate) = requests.iter().find(|request| !ids.insert(&request.id)) {
+        bail!("Duplicate id [{}] in {}", duplicate.id, path.display());
+    }
+    Ok(requests)
+}
+
+fn write_sign_batch_manifest(
+    path: &Path,
+    signatures: &[SignBatchSignature],
+) -> Result<(), anyhow::Error> {
+    let write = || -> Result<(), anyhow::Error> {
+        if is_csv_manifest(path) {
+            let mut writer = csv::Writer::from_path(path)?;
+            for signature in signatures {
+                writer.serialize(signature)?;
+            }
+            writer.flush()?;
+        } else {
+            fs::write(path, serde_json::to_string_pretty(signatures)?)?;
+        }
+        Ok(())
+    };
+    write().with_context(|| format!("Cannot write the signatures to {}", path.display()))
+}
+
+/// dWallet operations take several transactions, depending on the responses of the network.
+fn ensure_not_serialized(
+    serialize_unsigned_transaction: bool,
+    serialize_signed_transaction: bool,
+) -> Result<(), anyhow::Error> {
+    if serialize_unsigned_transaction || serialize_signed_transaction {
+        return Err(anyhow!(
+            "dWallet operations take several transactions, which cannot be serialized"
+        ));
+    }
+    Ok(())
+}
+
+/// The sender of the transactions of dWallet operations: the owner of `gas`, or else the active
+/// address.
+async fn dwallet_sender(
+    context: &mut WalletContext,
+    gas: Option<ObjectID>,
+) -> Result<SuiAddress, anyhow::Error> {
+    match context.try_get_object_owner(&gas).await? {
+        Some(owner) => Ok(owner),
+        None => context.active_address(),
+    }
+}
+
+/// A dWallet client sending transactions from `sender`, waiting for the network as set by `wait`.
+fn dwallet_client<'a>(
+    context: &'a WalletContext,
+    sender: SuiAddress,
+    gas: Option<ObjectID>,
+    gas_budget: Option<u64>,
+    wait: &DWalletWaitOptions,
+) -> Result<DWalletClient<'a>, anyhow::Error> {
+    let gas_budget = gas_budget.or(context.config.get_active_env()?.default_gas_budget);
+    let mut client = DWalletClient::new(context, sender);
+    if let Some(gas) = gas {
+        client = client.with_gas(gas);
+    }
+    if let Some(gas_budget) = gas_budget {
+        client = client.with_gas_budget(gas_budget);
+    }
+    if let Some(timeout) = wait.timeout {
+        client = client.with_timeout(Duration::from_secs(timeout));
+    }
+    if wait.watch {
+        // The spinner is cleared once the client, holding the last handle to it, is dropped.
+        let spinner = ProgressBar::new_spinner().with_finish(ProgressFinish::AndClear);
+        client = client.with_progress(Arc::new(move |progress: &DWalletProgress| {
+            show_progress(&spinner, progress)
+        }));
+    }
+    Ok(client)
+}
+
+/// Show `progress` on `spinner`, which is drawn on stderr, for stdout to hold the result only.
+fn show_progress(spinner: &ProgressBar, progress: &DWalletProgress) {
+    match progress {
+        DWalletProgress::Executed { digest } => {
+            spinner.println(format!("Executed transaction {digest}"))
+        }
+        DWalletProgress::Waiting {
+            round,
+            elapsed,
+            timeout,
+        } => {
+            spinner.set_message(format!(
+                "Waiting for the network to answer the {round} round: {}s / {}s",
+                elapsed.as_secs(),
+                timeout.as_secs()
+            ));
+            spinner.tick();
+        }
+    }
+}
+
+/// Move the user share of the new `dwallet` out of the config file: to the keyring with a keyring
+/// keystore, or else to a file next to the config, encrypted with the share encryption key of
+/// `sender`. The share stays in the config if the keystore does not hold the key of `sender`, e.g.
+/// a Ledger account. Returns where the share is kept.
+pub(crate) fn store_user_share(
+    context: &WalletContext,
+    sender: SuiAddress,
+    dwallet: &mut DWalletSecretShare,
+) -> Result<String, anyhow::Error> {
+    let keystore = &context.config.keystore;
+    if let Keystore::Keyring(keystore) = keystore {
+        dwallet.move_share_to_keyring(keystore.service())?;
+        return Ok(format!("keyring service [{}]", keystore.service()));
+    }
+    let Ok(key) = keystore.get_key(&sender) else {
+        return Ok(format!("client config {}", context.config.path().display()));
+    };
+    let dir = context.config.path().with_file_name(DWALLET_SHARES_DIR);
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Cannot create the directory {}", dir.display()))?;
+    let path = dir.join(format!("{}.share", dwallet.dwallet_id));
+    dwallet.encrypt_share(&ShareEncryptionKey::derive(key), path.clone())?;
+    Ok(format!("encrypted in {}", path.display()))
+}
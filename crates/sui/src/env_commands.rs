@@ -0,0 +1,222 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use std::num::NonZeroU32;
+
+use anyhow::{anyhow, bail};
+use clap::*;
+use sui_sdk::eth_client::EthFinality;
+use sui_sdk::sui_client_config::{EthNetworkConfig, SuiEnv};
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::base_types::ObjectID;
+
+use crate::client_commands::{EthNetworkOutput, EthNetworksOutput, SuiClientCommandResult};
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum SuiEnvCommands {
+    /// Add an Ethereum network to an environment, or change the settings of one: its endpoints,
+    /// chain id and state object.
+    /// A new network needs a chain id and an execution RPC. The settings are validated before the
+    /// client config is saved.
+    #[command(name = "set-eth")]
+    SetEth {
+        /// The name of the Ethereum network, e.g. `mainnet` or `sepolia`.
+        #[clap(long)]
+        network: String,
+
+        /// The environment of the network. Defaults to the active environment.
+        #[clap(long)]
+        env: Option<String>,
+
+        #[clap(long)]
+        chain_id: Option<u64>,
+
+        /// Execution layer JSON-RPC endpoint.
+        #[clap(long, value_hint = ValueHint::Url)]
+        execution_rpc: Option<String>,
+
+        /// Consensus layer (Beacon API) endpoint, followed by the light client of the network.
+        #[clap(long, value_hint = ValueHint::Url)]
+        consensus_rpc: Option<String>,
+
+        /// The request rate limit of the consensus layer endpoint's provider.
+        #[clap(long)]
+        consensus_rpc_requests_per_second: Option<NonZeroU32>,
+
+        /// The object holding the verified state of the network, as created by
+        /// `sui client eth-lightclient init`.
+        #[clap(long)]
+        state_object_id: Option<ObjectID>,
+
+        /// When execution layer data is final: `finalized`, or a number of blocks deep.
+        #[clap(long, value_parser = parse_finality)]
+        finality: Option<EthFinality>,
+
+        #[clap(long, value_hint = ValueHint::Url)]
+        explorer_url: Option<String>,
+
+        /// Verify the bootstrap checkpoint of the network against its configured known
+        /// checkpoints too, and not only against the compiled-in ones. Only pass this if they come
+        /// from a source you trust.
+        #[clap(long)]
+        trust_configured_checkpoints: bool,
+    },
+
+    /// Show the Ethereum networks of an environment.
+    #[command(name = "show-eth")]
+    ShowEth {
+        /// Show only this network.
+        #[clap(long)]
+        network: Option<String>,
+
+        /// The environment of the networks. Defaults to the active environment.
+        #[clap(long)]
+        env: Option<String>,
+    },
+}
+
+impl SuiEnvCommands {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        Ok(match self {
+            SuiEnvCommands::SetEth {
+                network: name,
+                env,
+                chain_id,
+                execution_rpc,
+                consensus_rpc,
+                consensus_rpc_requests_per_second,
+                state_object_id,
+                finality,
+                explorer_url,
+                trust_configured_checkpoints,
+            } => {
+                let mut env = env_mut(context, env.as_deref())?.clone();
+                let mut builder = match env.eth_networks.get(&name) {
+                    Some(network) => network.clone().into_builder(),
+                    None => {
+                        let (Some(chain_id), Some(execution_rpc)) = (chain_id, &execution_rpc)
+                        else {
+                            bail!(
+                                "Ethereum network [{name}] is not configured for env [{}], pass --chain-id and --execution-rpc to add it",
+                                env.alias
+                            );
+                        };
+                        EthNetworkConfig::builder(chain_id, execution_rpc)
+                    }
+                };
+                if let Some(chain_id) = chain_id {
+                    builder = builder.chain_id(chain_id);
+                }
+                if let Some(execution_rpc) = execution_rpc {
+                    builder = builder.execution_rpc(execution_rpc);
+                }
+                if let Some(consensus_rpc) = consensus_rpc {
+                    builder = builder.consensus_rpc(consensus_rpc);
+                }
+                if let Some(requests_per_second) = consensus_rpc_requests_per_second {
+                    builder = builder.consensus_rpc_requests_per_second(requests_per_second);
+                }
+                if let Some(state_object_id) = state_object_id {
+                    builder = builder.state_object_id(state_object_id);
+                }
+                if let Some(finality) = finality {
+                    builder = builder.finality(finality);
+                }
+                if let Some(explorer_url) = explorer_url {
+                    builder = builder.explorer_url(explorer_url);
+                }
+                if trust_configured_checkpoints {
+                    builder = builder.trust_configured_checkpoints();
+                }
+                let network = builder.build()?;
+
+                env.eth_networks.insert(name.clone(), network.clone());
+                env.validate()?;
+                let output = EthNetworksOutput {
+                    env: env.alias.clone(),
+                    networks: vec![eth_network_output(name, &network)],
+                };
+                // Only replace the environment once it is valid.
+                *env_mut(context, Some(&output.env))? = env;
+                context.config.save()?;
+
+                SuiClientCommandResult::EthNetworks(output)
+            }
+            SuiEnvCommands::ShowEth { network, env } => {
+                let env = match &env {
+                    Some(alias) => context
+                        .config
+                        .get_env(&Some(alias.clone()))
+                        .ok_or_else(|| env_not_found(alias))?,
+                    None => context.config.get_active_env()?,
+                };
+                let networks = match network {
+                    Some(name) => vec![eth_network_output(name.clone(), env.eth_network(&name)?)],
+                    None => env
+                        .eth_networks
+                        .iter()
+                        .map(|(name, network)| eth_network_output(name.clone(), network))
+                        .collect(),
+                };
+
+                SuiClientCommandResult::EthNetworks(EthNetworksOutput {
+                    env: env.alias.clone(),
+                    networks,
+                })
+            }
+        })
+    }
+}
+
+/// The environment `alias` of the client config, or the active one.
+fn env_mut<'a>(
+    context: &'a mut WalletContext,
+    alias: Option<&str>,
+) -> Result<&'a mut SuiEnv, anyhow::Error> {
+    match alias {
+        Some(alias) => context
+            .config
+            .envs
+            .iter_mut()
+            .find(|env| env.alias == alias)
+            .ok_or_else(|| env_not_found(alias)),
+        None => context.config.get_active_env_mut(),
+    }
+}
+
+fn env_not_found(alias: &str) -> anyhow::Error {
+    anyhow!(
+        "Environment config not found for [{alias}], add new environment config using the `sui client new-env` command."
+    )
+}
+
+fn eth_network_output(name: String, network: &EthNetworkConfig) -> EthNetworkOutput {
+    EthNetworkOutput {
+        name,
+        chain_id: network.chain_id,
+        execution_rpc: network.execution_rpc.clone(),
+        consensus_rpc: network.consensus_rpc.clone(),
+        consensus_rpc_requests_per_second: network.consensus_rpc_requests_per_second,
+        state_object_id: network.state_object_id,
+        finality: network.finality,
+        explorer_url: network.explorer_url.clone(),
+        checkpoint_epoch: network
+            .checkpoint
+            .as_ref()
+            .map(|checkpoint| checkpoint.epoch),
+    }
+}
+
+/// `finalized`, or a number of blocks deep.
+fn parse_finality(finality: &str) -> Result<EthFinality, anyhow::Error> {
+    if finality == "finalized" {
+        return Ok(EthFinality::Finalized);
+    }
+    finality.parse().map(EthFinality::Depth).map_err(|_| {
+        anyhow!("Invalid finality [{finality}], expected `finalized` or a number of blocks")
+    })
+}
@@ -0,0 +1,306 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use anyhow::{anyhow, bail};
+use clap::*;
+use sui_json_rpc_types::{
+    ObjectChange, SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+};
+use sui_sdk::eth_light_client::{
+    batch_updates, create_light_client, fetch_bootstrap, fetch_updates, get_light_client,
+    is_stale_update, submit_updates, verify_bootstrap_config,
+};
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::base_types::ObjectID;
+use sui_types::eth_light_client::{sync_committee_period, EthereumLightClient};
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::TransactionKind;
+
+use crate::client_commands::{
+    EthLightClientInitOutput, EthLightClientStatusOutput, EthLightClientUpdateOutput,
+    SuiClientCommandResult,
+};
+
+/// The light client updates submitted in a single transaction, at most.
+const MAX_UPDATES_PER_TRANSACTION: usize = 16;
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum SuiEthLightClientCommands {
+    /// Create the on-chain light client of an Ethereum network of the active environment, from the
+    /// bootstrap of the checkpoint configured for the network, and record its state object in the
+    /// client config.
+    /// The genesis validators root and the checkpoint are verified against the known-good ones
+    /// first, and so is the bootstrap served by the consensus RPC.
+    #[command(name = "init")]
+    Init {
+        /// The name of the Ethereum network in the config of the active environment.
+        #[clap(long)]
+        network: String,
+
+        /// Create a new light client even if the network already has a state object.
+        #[clap(long)]
+        force: bool,
+
+        /// Accept a checkpoint vouched for by the known checkpoints of the network's config, and
+        /// not only by the compiled-in ones. Only pass this if they come from a source you trust.
+        #[clap(long)]
+        trust_configured_checkpoints: bool,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
+
+    /// Advance the on-chain light client of an Ethereum network to its latest finalized header,
+    /// submitting the light client updates it is missing, as the `eth-state-updater` relayer does.
+    /// The updates are verified locally first, and the invalid ones are dropped.
+    #[command(name = "update")]
+    Update {
+        /// The name of the Ethereum network in the config of the active environment.
+        #[clap(long)]
+        network: String,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for each transaction
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
+
+    /// Show how far the on-chain light client of an Ethereum network is synced, and how far it
+    /// lags behind the chain when the network has a consensus RPC configured.
+    #[command(name = "status")]
+    Status {
+        /// The name of the Ethereum network in the config of the active environment.
+        #[clap(long)]
+        network: String,
+    },
+}
+
+impl SuiEthLightClientCommands {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        Ok(match self {
+            SuiEthLightClientCommands::Init {
+                network: network_name,
+                force,
+                trust_configured_checkpoints,
+                gas,
+                gas_budget,
+            } => {
+                let env = context.config.get_active_env()?;
+                let network = env.eth_network(&network_name)?.clone();
+                if let Some(state_object_id) = network.state_object_id {
+                    if !force {
+                        bail!(
+                            "Ethereum network [{network_name}] already has the state object {state_object_id}, pass --force to create a new one"
+                        );
+                    }
+                }
+                let beacon_client = env.create_beacon_client(&network_name)?;
+                let checkpoint = verify_bootstrap_config(&network, trust_configured_checkpoints)?;
+                let bootstrap =
+                    fetch_bootstrap(&network, &beacon_client, trust_configured_checkpoints)
+                        .await?
+                        .data;
+
+                let mut builder = ProgrammableTransactionBuilder::new();
+                create_light_client(&mut builder, &network, &bootstrap)?;
+                let response = execute(context, builder, gas, gas_budget).await?;
+                let state_object_id = response
+                    .object_changes
+                    .iter()
+                    .flatten()
+                    .find_map(|change| match change {
+                        ObjectChange::Created { object_type, .. }
+                            if EthereumLightClient::is_type(object_type) =>
+                        {
+                            Some(change.object_id())
+                        }
+                        _ => None,
+                    })
+                    .ok_or_else(|| {
+                        anyhow!("No EthereumLightClient created by {}", response.digest)
+                    })?;
+
+                context
+                    .config
+                    .get_active_env_mut()?
+                    .eth_networks
+                    .get_mut(&network_name)
+                    .expect("the network is configured")
+                    .state_object_id = Some(state_object_id);
+                context.config.save()?;
+
+                SuiClientCommandResult::EthLightClientInit(EthLightClientInitOutput {
+                    network: network_name,
+                    chain_id: network.chain_id,
+                    state_object_id,
+                    checkpoint_epoch: checkpoint.epoch,
+                    finalized_slot: bootstrap.header.beacon.slot,
+                    latest_block_number: bootstrap.header.execution.block_number,
+                    digest: response.digest,
+                })
+            }
+            SuiEthLightClientCommands::Update {
+                network: network_name,
+                gas,
+                gas_budget,
+            } => {
+                let env = context.config.get_active_env()?;
+                let state_object_id = state_object_id(context, &network_name)?;
+                let beacon_client = env.create_beacon_client(&network_name)?;
+                let client = context.get_client().await?;
+                let (light_client, initial_shared_version) =
+                    get_light_client(&client, state_object_id).await?;
+
+                let mut store = light_client.store()?;
+                let (updates, chain_head) = fetch_updates(&beacon_client, &store).await?;
+                let mut verified = vec![];
+                for update in updates {
+                    if is_stale_update(&store, &update) {
+                        continue;
+                    }
+                    match store.apply_update(&update) {
+                        Ok(()) => verified.push(update),
+                        Err(e) => eprintln!(
+                            "Dropping the invalid light client update of slot {}: {e}",
+                            update.finalized_header().beacon.slot
+                        ),
+                    }
+                }
+
+                let mut digests = vec![];
+                if !verified.is_empty() {
+                    for batch in batch_updates(&verified, MAX_UPDATES_PER_TRANSACTION)? {
+                        let mut builder = ProgrammableTransactionBuilder::new();
+                        submit_updates(
+                            &mut builder,
+                            state_object_id,
+                            initial_shared_version,
+                            batch,
+                        )?;
+                        digests.push(execute(context, builder, gas, gas_budget).await?.digest);
+                    }
+                }
+
+                SuiClientCommandResult::EthLightClientUpdate(EthLightClientUpdateOutput {
+                    network: network_name,
+                    state_object_id,
+                    submitted_updates: verified.len(),
+                    finalized_slot: store.finalized_header.beacon.slot,
+                    latest_block_number: store.finalized_header.execution.block_number,
+                    chain_head_slot: chain_head,
+                    digests,
+                })
+            }
+            SuiEthLightClientCommands::Status {
+                network: network_name,
+            } => {
+                let network = context
+                    .config
+                    .get_active_env()?
+                    .eth_network(&network_name)?
+                    .clone();
+                let state_object_id = state_object_id(context, &network_name)?;
+                let client = context.get_client().await?;
+                let (light_client, _) = get_light_client(&client, state_object_id).await?;
+                let store = light_client.store()?;
+
+                // The chain head is only known with a consensus RPC, which reading the on-chain
+                // light client does not need.
+                let chain_head_slot = match network.build_beacon_client() {
+                    Some(beacon_client) => Some(
+                        beacon_client
+                            .finality_update()
+                            .await?
+                            .data
+                            .attested_header
+                            .beacon
+                            .slot,
+                    ),
+                    None => None,
+                };
+
+                SuiClientCommandResult::EthLightClientStatus(EthLightClientStatusOutput {
+                    network: network_name,
+                    chain_id: light_client.chain_id,
+                    state_object_id,
+                    finalized_slot: light_client.finalized_slot,
+                    latest_block_number: light_client.latest_block_number,
+                    sync_committee_period: sync_committee_period(light_client.finalized_slot),
+                    next_sync_committee_known: store.next_sync_committee.is_some(),
+                    chain_head_slot,
+                    slots_behind: chain_head_slot
+                        .map(|head| head.saturating_sub(light_client.finalized_slot)),
+                })
+            }
+        })
+    }
+}
+
+/// The state object of the light client of the Ethereum network `network` of the active
+/// environment.
+fn state_object_id(context: &WalletContext, network: &str) -> Result<ObjectID, anyhow::Error> {
+    context
+        .config
+        .get_active_env()?
+        .eth_network(network)?
+        .state_object_id
+        .ok_or_else(|| {
+            anyhow!(
+                "No state object configured for Ethereum network [{network}], create one with `sui client eth-lightclient init`"
+            )
+        })
+}
+
+/// Execute the transaction of `builder`, sent by the owner of `gas` or else the active address,
+/// failing if its execution fails. The gas budget is estimated when none is given nor configured.
+async fn execute(
+    context: &mut WalletContext,
+    builder: ProgrammableTransactionBuilder,
+    gas: Option<ObjectID>,
+    gas_budget: Option<u64>,
+) -> Result<SuiTransactionBlockResponse, anyhow::Error> {
+    let sender = match context.try_get_object_owner(&gas).await? {
+        Some(owner) => owner,
+        None => context.active_address()?,
+    };
+    let client = context.get_client().await?;
+    let env = context.config.get_active_env()?;
+    let gas_budget = match gas_budget.or(env.default_gas_budget) {
+        Some(gas_budget) => gas_budget,
+        None => {
+            let tx = TransactionKind::programmable(builder.clone().finish());
+            client
+                .estimate_gas(sender, tx, env.default_gas_price)
+                .await?
+        }
+    };
+    let tx_data = client
+        .transaction_builder()
+        .finish_programmable_transaction(sender, builder, gas, gas_budget)
+        .await?;
+    let tx_data = context.with_default_gas_price(tx_data)?;
+    let transaction = context.try_sign_transaction(&tx_data).await?;
+    let response = context.execute_transaction_may_fail(transaction).await?;
+    let effects = response
+        .effects
+        .as_ref()
+        .ok_or_else(|| anyhow!("No effects in the response of {}", response.digest))?;
+    if let SuiExecutionStatus::Failure { error } = effects.status() {
+        bail!("Transaction {} failed: {error}", response.digest);
+    }
+    Ok(response)
+}
@@ -9,7 +9,7 @@ use sui_keys::keystore::{AccountKeystore, Keystore};
 use sui_sdk::wallet_context::WalletContext;
 use sui_types::base_types::SuiAddress;
 
-/// An address or an alias associated with a key in the wallet
+/// An address, or an alias associated with a key in the wallet or with an address in the config.
 /// This is used to distinguish between an address or an alias,
 /// enabling a user to use an alias for any command that requires an address.
 #[derive(Serialize, Clone)]
@@ -39,16 +39,17 @@ impl Display for KeyIdentity {
     }
 }
 
-/// Get the SuiAddress corresponding to this key identity.
+/// Get the SuiAddress corresponding to this key identity, which can also be an address alias of
+/// the client config.
 /// If no string is provided, then the curernt active address is returned.
 pub fn get_identity_address(
     input: Option<KeyIdentity>,
     ctx: &mut WalletContext,
 ) -> Result<SuiAddress, Error> {
-    if let Some(addr) = input {
-        get_identity_address_from_keystore(addr, &ctx.config.keystore)
-    } else {
-        Ok(ctx.active_address()?)
+    match input {
+        Some(KeyIdentity::Address(x)) => Ok(x),
+        Some(KeyIdentity::Alias(x)) => ctx.config.resolve_address(&x),
+        None => Ok(ctx.active_address()?),
     }
 }
 
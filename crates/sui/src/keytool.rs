@@ -1,8 +1,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: BSD-3-Clause-Clear
-use crate::key_identity::{get_identity_address_from_keystore, KeyIdentity};
+use crate::dwallet_commands::store_user_share;
+use crate::key_identity::{get_identity_address, get_identity_address_from_keystore, KeyIdentity};
 use crate::zklogin_commands_util::{perform_zk_login_test_tx, read_cli_line};
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
 use bip32::DerivationPath;
 use clap::*;
 use fastcrypto::ed25519::Ed25519KeyPair;
@@ -27,16 +28,25 @@ use rusoto_kms::{Kms, KmsClient, SignRequest};
 use serde::Serialize;
 use serde_json::json;
 use shared_crypto::intent::{Intent, IntentMessage, IntentScope, PersonalMessage};
+use signature_mpc::twopc_mpc_protocols::DKGCentralizedPartyOutput;
 use std::fmt::{Debug, Display, Formatter};
 use std::fs;
 use std::path::{Path, PathBuf};
-use sui_keys::key_derive::generate_new_key;
+use sui_config::{sui_config_dir, SUI_CLIENT_CONFIG};
+use sui_keys::encryption::{
+    read_new_passphrase, read_new_share_passphrase, read_share_passphrase, ShareEncryptionKey,
+    ShareTransferKey,
+};
+use sui_keys::key_derive::{account_derivation_path, generate_new_key, validate_path};
 use sui_keys::keypair_file::{
     read_authority_keypair_from_file, read_keypair_from_file, write_authority_keypair_to_file,
     write_keypair_to_file,
 };
 use sui_keys::keystore::{AccountKeystore, Keystore};
-use sui_types::base_types::SuiAddress;
+use sui_keys::shamir::{self, Fragment, SecretKind};
+use sui_sdk::sui_client_config::{DWalletSecretShare, ExportedDWalletShare, ShareExportEncryption};
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::committee::EpochId;
 use sui_types::crypto::{
     get_authority_key_pair, EncodeDecodeBase64, Signature, SignatureScheme, SuiKeyPair,
@@ -53,6 +63,7 @@ use tabled::builder::Builder;
 use tabled::settings::Rotate;
 use tabled::settings::{object::Rows, Modify, Width};
 use tracing::info;
+use zeroize::Zeroizing;
 #[cfg(test)]
 #[path = "unit_tests/keytool_tests.rs"]
 mod keytool_tests;
@@ -85,6 +96,21 @@ pub enum KeyToolCommand {
         #[clap(long)]
         tx_bytes: Option<String>,
     },
+    /// Manage the user shares of the dWallets of the client config: show where they are held, and
+    /// export or import them encrypted with a passphrase or to the public key of another account.
+    /// A dWallet cannot sign without its user share, which cannot be recovered once lost.
+    #[clap(name = "dwallet-share")]
+    DWalletShare {
+        /// The client config holding the registry of dWallets.
+        #[clap(long = "client.config")]
+        config: Option<PathBuf>,
+        #[clap(subcommand)]
+        cmd: DWalletShareCommand,
+    },
+    /// Encrypt sui.keystore, which holds the private keys in the clear, with a passphrase read from
+    /// the SUI_KEYSTORE_PASSPHRASE environment variable or else asked interactively. The keystore
+    /// is then unlocked with the passphrase whenever it is loaded.
+    EncryptKeystore,
     /// Generate a new keypair with key scheme flag {ed25519 | secp256k1 | secp256r1}
     /// with optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or
     /// m/54'/784'/0'/0/0 for secp256k1 or m/74'/784'/0'/0/0 for secp256r1. Word
@@ -106,6 +132,11 @@ pub enum KeyToolCommand {
     /// or m/74'/784'/0'/0/0 for secp256r1. Supports mnemonic phrase of word length 12, 15, 18`, 21, 24.
     /// Set an alias for the key with the --alias flag. If no alias is provided,
     /// the tool will automatically generate one.
+    ///
+    /// Other accounts of a mnemonic are imported with --account-index, at the default path with
+    /// the given address index, and keys of other wallets with --allow-custom-path, which accepts
+    /// any derivation path valid for the key scheme. The user shares of the dWallets of the account
+    /// are encrypted with a key derived from its private key, so that they are restored along.
     Import {
         /// Sets an alias for this address. The alias must start with a letter and can contain only letters, digits, hyphens (-), or underscores (_).
         #[clap(long)]
@@ -113,6 +144,24 @@ pub enum KeyToolCommand {
         input_string: String,
         key_scheme: SignatureScheme,
         derivation_path: Option<DerivationPath>,
+        /// The index of the account of the mnemonic to import, at the default derivation path.
+        #[clap(long, conflicts_with = "derivation_path")]
+        account_index: Option<u32>,
+        /// Accept a derivation path outside the conventions of Sui, e.g. the one of an account of
+        /// another wallet.
+        #[clap(long)]
+        allow_custom_path: bool,
+    },
+    /// Add the ed25519 account of a Ledger device to sui.keystore, with an optional derivation path,
+    /// default to m/44'/784'/0'/0'/0'. The device must be connected over USB, unlocked, with the Sui
+    /// app open. The key stays on the device, which shows the transactions of the address and signs
+    /// them once approved. Set an alias for the address with the --alias flag. If no alias is
+    /// provided, the tool will automatically generate one.
+    ImportLedger {
+        /// Sets an alias for this address. The alias must start with a letter and can contain only letters, digits, hyphens (-), or underscores (_).
+        #[clap(long)]
+        alias: Option<String>,
+        derivation_path: Option<DerivationPath>,
     },
     /// List all keys by its Sui address, Base64 encoded public key, key scheme name in
     /// sui.keystore.
@@ -162,6 +211,51 @@ pub enum KeyToolCommand {
         threshold: ThresholdUnit,
     },
 
+    /// Restore a private key to the keystore, or a dWallet with its user share to the registry of
+    /// the client config, from Shamir fragments written by `shamir-split`, as many as the threshold
+    /// of the split.
+    ShamirRestore {
+        /// The fragments, or the files holding one each.
+        #[clap(required = true)]
+        fragments: Vec<String>,
+        /// The alias of the restored key or dWallet, instead of a random one for a key, or the
+        /// alias of the split dWallet.
+        #[clap(long)]
+        alias: Option<String>,
+        /// The address or alias of the account whose key encrypts the user share of a restored
+        /// dWallet: by default, the active address.
+        #[clap(long)]
+        owner: Option<KeyIdentity>,
+        /// The client config holding the registry of dWallets.
+        #[clap(long = "client.config")]
+        config: Option<PathBuf>,
+    },
+    /// Split a private key of the keystore, or a dWallet of the client config with its user share,
+    /// into Shamir fragments for custodians to hold, any `threshold` of which restore it with
+    /// `shamir-restore` while fewer reveal nothing about it.
+    /// Each fragment is checksummed, and tied to its split so that fragments of different splits
+    /// are not mixed up.
+    ShamirSplit {
+        /// The address or alias of the key to split.
+        #[clap(long, required_unless_present = "dwallet", conflicts_with = "dwallet")]
+        key: Option<KeyIdentity>,
+        /// The alias or ID of the dWallet to split.
+        #[clap(long)]
+        dwallet: Option<String>,
+        /// The number of fragments that restore the secret, at least 2.
+        #[clap(long)]
+        threshold: u8,
+        /// The number of fragments to split the secret into.
+        #[clap(long)]
+        shares: u8,
+        /// Write each fragment to a file of this directory instead of printing them.
+        #[clap(long)]
+        output_dir: Option<PathBuf>,
+        /// The client config holding the registry of dWallets.
+        #[clap(long = "client.config")]
+        config: Option<PathBuf>,
+    },
+
     /// Read the content at the provided file path. The accepted format can be
     /// [enum SuiKeyPair] (Base64 encoded of 33-byte `flag || privkey`) or `type AuthorityKeyPair`
     /// (Base64 encoded `privkey`). It prints its Base64 encoded public key and the key scheme flag.
@@ -273,6 +367,92 @@ pub struct AliasUpdate {
     new_alias: String,
 }
 
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum DWalletShareCommand {
+    /// List the dWallets of the client config with where their user share is held: in the
+    /// keyring, encrypted in a file, or in the clear in the client config.
+    List,
+    /// Export a dWallet with its user share to a file, encrypted with a passphrase read from the
+    /// SUI_DWALLET_SHARE_PASSPHRASE environment variable or else asked interactively, or to the
+    /// public key of another account.
+    Export {
+        /// The alias or ID of the dWallet.
+        dwallet: String,
+        /// The file to write the dWallet to, which must not exist.
+        #[clap(long)]
+        output: PathBuf,
+        /// Encrypt the user share to this hex encoded public key, the encryption key published by
+        /// the account to import it with `sui client dwallet publish-encryption-key`, instead of
+        /// a passphrase.
+        #[clap(long)]
+        to: Option<String>,
+    },
+    /// Import a dWallet with its user share from a file written by `export`, adding it to the
+    /// registry of the client config. The user share is then held as the ones of new dWallets.
+    Import {
+        file: PathBuf,
+        /// The address or alias of the account whose key encrypts the imported user share: by
+        /// default, the account the share was exported to, or else the active address.
+        #[clap(long)]
+        owner: Option<KeyIdentity>,
+        /// The alias of the dWallet in the registry, instead of the exported one.
+        #[clap(long)]
+        alias: Option<String>,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DWalletShareOutput {
+    alias: String,
+    dwallet_id: ObjectID,
+    /// Where the user share is held.
+    user_share: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportDWalletShareOutput {
+    alias: String,
+    dwallet_id: ObjectID,
+    path: PathBuf,
+    /// `passphrase`, or the public key the user share is encrypted to.
+    encrypted_to: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportDWalletShareOutput {
+    alias: String,
+    dwallet_id: ObjectID,
+    dwallet_cap_id: ObjectID,
+    owner: SuiAddress,
+    /// Where the user share is held.
+    user_share: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShamirSplitOutput {
+    kind: SecretKind,
+    /// The address of the split key, or the alias of the split dWallet.
+    secret: String,
+    threshold: u8,
+    /// The fragments, unless they are written to `paths`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fragments: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    paths: Vec<PathBuf>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptKeystoreOutput {
+    path: PathBuf,
+    keys: usize,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DecodedMultiSig {
@@ -410,15 +590,20 @@ pub enum CommandOutput {
     Convert(ConvertOutput),
     DecodeMultiSig(DecodedMultiSigOutput),
     DecodeTxBytes(TransactionData),
+    DWalletShares(Vec<DWalletShareOutput>),
+    EncryptKeystore(EncryptKeystoreOutput),
     Error(String),
+    ExportDWalletShare(ExportDWalletShareOutput),
     Generate(Key),
     Import(Key),
+    ImportDWalletShare(ImportDWalletShareOutput),
     List(Vec<Key>),
     LoadKeypair(KeypairData),
     MultiSigAddress(MultiSigAddress),
     MultiSigCombinePartialSig(MultiSigCombinePartialSig),
     MultiSigCombinePartialSigLegacy(MultiSigCombinePartialSigLegacyOutput),
     PrivateKeyBase64(PrivateKeyBase64),
+    ShamirSplit(ShamirSplitOutput),
     Show(Key),
     Sign(SignData),
     SignKMS(SerializedSig),
@@ -505,6 +690,31 @@ impl KeyToolCommand {
                 CommandOutput::DecodeTxBytes(tx_data)
             }
 
+            KeyToolCommand::DWalletShare { config, cmd } => {
+                cmd.execute(&mut client_context(config).await?)?
+            }
+
+            KeyToolCommand::EncryptKeystore => {
+                let Keystore::File(keystore) = keystore else {
+                    return Err(anyhow!("Only file keystores can be encrypted"));
+                };
+                let path = keystore
+                    .path()
+                    .ok_or_else(|| anyhow!("The keystore has no file"))?
+                    .to_path_buf();
+                if keystore.is_encrypted() {
+                    return Err(anyhow!(
+                        "The keystore {} is already encrypted",
+                        path.display()
+                    ));
+                }
+                keystore.encrypt(&read_new_passphrase(&path)?)?;
+                CommandOutput::EncryptKeystore(EncryptKeystoreOutput {
+                    path,
+                    keys: keystore.key_pairs().len(),
+                })
+            }
+
             KeyToolCommand::Generate {
                 key_scheme,
                 derivation_path,
@@ -540,9 +750,16 @@ impl KeyToolCommand {
                 input_string,
                 key_scheme,
                 derivation_path,
+                account_index,
+                allow_custom_path,
             } => {
                 // check if input is a private key -- should start with 0x
                 if input_string.starts_with("0x") {
+                    if account_index.is_some() {
+                        return Err(anyhow!(
+                            "Account indexes only apply to the import of mnemonic phrases"
+                        ));
+                    }
                     let bytes: Vec<u8> = Hex::decode(&input_string).map_err(|_| {
                         anyhow!("Private key is malformed. Importing private key failed.")
                     })?;
@@ -565,17 +782,43 @@ impl KeyToolCommand {
                     keystore.add_key(alias, skp)?;
                     CommandOutput::Import(key)
                 } else {
-                    let sui_address = keystore.import_from_mnemonic(
-                        &input_string,
-                        key_scheme,
-                        derivation_path,
-                    )?;
+                    let derivation_path = match account_index {
+                        Some(index) => Some(account_derivation_path(&key_scheme, index)?),
+                        None => derivation_path,
+                    };
+                    let sui_address = match derivation_path {
+                        Some(derivation_path) if allow_custom_path => keystore
+                            .import_from_mnemonic_with_custom_path(
+                                &input_string,
+                                key_scheme,
+                                derivation_path,
+                            )?,
+                        derivation_path => keystore.import_from_mnemonic(
+                            &input_string,
+                            key_scheme,
+                            derivation_path,
+                        )?,
+                    };
                     let skp = keystore.get_key(&sui_address)?;
                     let key = Key::from(skp);
                     CommandOutput::Import(key)
                 }
             }
 
+            KeyToolCommand::ImportLedger {
+                alias,
+                derivation_path,
+            } => {
+                let derivation_path = validate_path(&SignatureScheme::ED25519, derivation_path)?;
+                let sui_address = keystore.add_ledger_account(alias, derivation_path)?;
+                let public_key = keystore
+                    .keys()
+                    .into_iter()
+                    .find(|pk| SuiAddress::from(pk) == sui_address)
+                    .ok_or_else(|| anyhow!("Cannot find the public key of [{sui_address}]"))?;
+                CommandOutput::Import(Key::from(public_key))
+            }
+
             KeyToolCommand::List => {
                 let keys = keystore
                     .keys()
@@ -690,6 +933,122 @@ impl KeyToolCommand {
                 )
             }
 
+            KeyToolCommand::ShamirRestore {
+                fragments,
+                alias,
+                owner,
+                config,
+            } => {
+                let fragments = fragments
+                    .iter()
+                    .map(|fragment| read_fragment(fragment))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let (kind, secret) = shamir::restore(&fragments)?;
+                match kind {
+                    SecretKind::Key => {
+                        let skp = std::str::from_utf8(&secret)
+                            .ok()
+                            .and_then(|key| SuiKeyPair::decode_base64(key).ok())
+                            .ok_or_else(|| anyhow!("The restored secret is not a private key"))?;
+                        let key = Key::from(&skp);
+                        keystore.add_key(alias, skp)?;
+                        CommandOutput::Import(key)
+                    }
+                    SecretKind::DWalletShare => {
+                        let mut context = client_context(config).await?;
+                        let mut dwallet: DWalletSecretShare = serde_json::from_slice(&secret)
+                            .map_err(|e| anyhow!("The restored secret is not a dWallet: {e}"))?;
+                        if let Some(alias) = alias {
+                            dwallet.alias = alias;
+                        }
+                        check_new_dwallet(&context, dwallet.dwallet_id, &dwallet.alias)?;
+                        let owner = match owner {
+                            Some(owner) => get_identity_address(Some(owner), &mut context)?,
+                            None => context.active_address()?,
+                        };
+                        let user_share = store_user_share(&context, owner, &mut dwallet)?;
+                        let output = ImportDWalletShareOutput {
+                            alias: dwallet.alias.clone(),
+                            dwallet_id: dwallet.dwallet_id,
+                            dwallet_cap_id: dwallet.dwallet_cap_id,
+                            owner,
+                            user_share,
+                        };
+                        context.config.add_dwallet(dwallet);
+                        context.config.save()?;
+                        CommandOutput::ImportDWalletShare(output)
+                    }
+                }
+            }
+
+            KeyToolCommand::ShamirSplit {
+                key,
+                dwallet,
+                threshold,
+                shares,
+                output_dir,
+                config,
+            } => {
+                let (kind, name, secret) = match (key, dwallet) {
+                    (Some(key), _) => {
+                        let address = get_identity_address_from_keystore(key, keystore)?;
+                        let key = keystore.get_key(&address)?.encode_base64();
+                        (SecretKind::Key, address.to_string(), Zeroizing::new(key.into_bytes()))
+                    }
+                    (None, Some(dwallet)) => {
+                        let context = client_context(config).await?;
+                        let dwallet = context.config.resolve_dwallet(&dwallet)?;
+                        // The user share is split along with the registry entry of the dWallet,
+                        // wherever it is held.
+                        let mut entry = dwallet.clone();
+                        entry.dkg_output = Some(decrypt_user_share(&context, dwallet)?);
+                        entry.encrypted_share_path = None;
+                        entry.keyring_service = None;
+                        (
+                            SecretKind::DWalletShare,
+                            dwallet.alias.clone(),
+                            Zeroizing::new(serde_json::to_vec(&entry)?),
+                        )
+                    }
+                    (None, None) => bail!("Pass the --key or the --dwallet to split"),
+                };
+                let fragments = shamir::split(kind, &secret, threshold, shares)?;
+
+                let mut output = ShamirSplitOutput {
+                    kind,
+                    secret: name,
+                    threshold,
+                    fragments: vec![],
+                    paths: vec![],
+                };
+                match output_dir {
+                    Some(dir) => {
+                        fs::create_dir_all(&dir)
+                            .map_err(|e| anyhow!("Cannot create {}: {e}", dir.display()))?;
+                        for fragment in &fragments {
+                            let path = dir.join(format!(
+                                "{}-fragment-{}-of-{shares}.txt",
+                                output.secret, fragment.index
+                            ));
+                            if path.exists() {
+                                bail!("{} already exists", path.display());
+                            }
+                            fs::write(&path, fragment.encode().as_bytes()).map_err(|e| {
+                                anyhow!("Cannot write the fragment to {}: {e}", path.display())
+                            })?;
+                            output.paths.push(path);
+                        }
+                    }
+                    None => {
+                        output.fragments = fragments
+                            .iter()
+                            .map(|fragment| fragment.encode().to_string())
+                            .collect()
+                    }
+                }
+                CommandOutput::ShamirSplit(output)
+            }
+
             KeyToolCommand::Show { file } => {
                 let res = read_keypair_from_file(&file);
                 match res {
@@ -1173,6 +1532,212 @@ impl Debug for CommandOutput {
     }
 }
 
+impl DWalletShareCommand {
+    pub fn execute(self, context: &mut WalletContext) -> Result<CommandOutput, anyhow::Error> {
+        Ok(match self {
+            DWalletShareCommand::List => CommandOutput::DWalletShares(
+                context
+                    .config
+                    .dwallets
+                    .iter()
+                    .map(|dwallet| DWalletShareOutput {
+                        alias: dwallet.alias.clone(),
+                        dwallet_id: dwallet.dwallet_id,
+                        user_share: user_share_location(context, dwallet),
+                    })
+                    .collect(),
+            ),
+            DWalletShareCommand::Export {
+                dwallet,
+                output,
+                to,
+            } => {
+                if output.exists() {
+                    bail!("{} already exists", output.display());
+                }
+                let dwallet = context.config.resolve_dwallet(&dwallet)?;
+                let dkg_output = decrypt_user_share(context, dwallet)?;
+                let (exported, encrypted_to) = match to {
+                    Some(transfer_key) => {
+                        let transfer_key =
+                            Hex::decode(transfer_key.strip_prefix("0x").unwrap_or(&transfer_key))
+                                .map_err(|e| anyhow!("Invalid public key [{transfer_key}]: {e}"))?;
+                        (
+                            dwallet.export_share_to(&dkg_output, &transfer_key)?,
+                            format!("0x{}", Hex::encode(&transfer_key)),
+                        )
+                    }
+                    None => (
+                        dwallet.export_share_with_passphrase(
+                            &dkg_output,
+                            &read_new_share_passphrase(&output)?,
+                        )?,
+                        "passphrase".to_string(),
+                    ),
+                };
+                fs::write(&output, serde_json::to_string_pretty(&exported)?).map_err(|e| {
+                    anyhow!("Cannot write the dWallet to {}: {e}", output.display())
+                })?;
+                CommandOutput::ExportDWalletShare(ExportDWalletShareOutput {
+                    alias: dwallet.alias.clone(),
+                    dwallet_id: dwallet.dwallet_id,
+                    path: output,
+                    encrypted_to,
+                })
+            }
+            DWalletShareCommand::Import { file, owner, alias } => {
+                let mut exported: ExportedDWalletShare =
+                    serde_json::from_slice(&fs::read(&file).map_err(|e| {
+                        anyhow!("Cannot read the dWallet from {}: {e}", file.display())
+                    })?)
+                    .map_err(|e| anyhow!("Invalid exported dWallet in {}: {e}", file.display()))?;
+                if let Some(alias) = alias {
+                    exported.alias = alias;
+                }
+                check_new_dwallet(context, exported.dwallet_id, &exported.alias)?;
+
+                let owner = match owner {
+                    Some(owner) => Some(get_identity_address(Some(owner), context)?),
+                    None => None,
+                };
+                let (dkg_output, owner) = match &exported.encryption {
+                    ShareExportEncryption::Passphrase { .. } => {
+                        let owner = match owner {
+                            Some(owner) => owner,
+                            None => context.active_address()?,
+                        };
+                        let passphrase = read_share_passphrase(&file)?;
+                        (exported.decrypt_with_passphrase(&passphrase)?, owner)
+                    }
+                    ShareExportEncryption::TransferKey { transfer_key } => {
+                        let keystore = &context.config.keystore;
+                        // The share is decrypted with the key of the account it was exported
+                        // to, which is the owner unless another one is given.
+                        let recipient = keystore
+                            .addresses()
+                            .into_iter()
+                            .find(|address| {
+                                keystore.get_key(address).is_ok_and(|key| {
+                                    ShareTransferKey::derive(key).public_key() == *transfer_key
+                                })
+                            })
+                            .ok_or_else(|| {
+                                anyhow!(
+                                    "The user share is encrypted to 0x{}, which is not the key of an account of the keystore",
+                                    Hex::encode(transfer_key)
+                                )
+                            })?;
+                        let key = ShareTransferKey::derive(keystore.get_key(&recipient)?);
+                        (
+                            exported.decrypt_with_transfer_key(&key)?,
+                            owner.unwrap_or(recipient),
+                        )
+                    }
+                };
+
+                let mut dwallet = exported.into_dwallet(dkg_output);
+                let user_share = store_user_share(context, owner, &mut dwallet)?;
+                let output = ImportDWalletShareOutput {
+                    alias: dwallet.alias.clone(),
+                    dwallet_id: dwallet.dwallet_id,
+                    dwallet_cap_id: dwallet.dwallet_cap_id,
+                    owner,
+                    user_share,
+                };
+                context.config.add_dwallet(dwallet);
+                context.config.save()?;
+                CommandOutput::ImportDWalletShare(output)
+            }
+        })
+    }
+}
+
+/// The wallet context of the client config `config`, or of the default one.
+async fn client_context(config: Option<PathBuf>) -> Result<WalletContext, anyhow::Error> {
+    let config = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
+    WalletContext::new(&config, None, None).await
+}
+
+/// Fail if the registry holds the dWallet `dwallet_id`, or another dWallet named `alias`.
+fn check_new_dwallet(
+    context: &WalletContext,
+    dwallet_id: ObjectID,
+    alias: &str,
+) -> Result<(), anyhow::Error> {
+    let registry = &context.config.dwallets;
+    if let Some(existing) = registry
+        .iter()
+        .find(|dwallet| dwallet.dwallet_id == dwallet_id)
+    {
+        bail!(
+            "dWallet {dwallet_id} is already in the registry as [{}]",
+            existing.alias
+        );
+    }
+    if registry.iter().any(|dwallet| dwallet.alias == alias) {
+        bail!("The alias [{alias}] is taken, import the dWallet with --alias");
+    }
+    Ok(())
+}
+
+/// The Shamir fragment `fragment`, or the one held in the file `fragment`.
+fn read_fragment(fragment: &str) -> Result<Fragment, anyhow::Error> {
+    if fragment.starts_with("shamir:") {
+        return fragment.parse();
+    }
+    fs::read_to_string(fragment)
+        .map_err(|e| anyhow!("Cannot read the Shamir fragment from {fragment}: {e}"))?
+        .parse()
+        .map_err(|e| anyhow!("Invalid Shamir fragment in {fragment}: {e}"))
+}
+
+/// Where the user share of `dwallet` is held, in the words of [store_user_share].
+fn user_share_location(context: &WalletContext, dwallet: &DWalletSecretShare) -> String {
+    if let Some(service) = &dwallet.keyring_service {
+        format!("keyring service [{service}]")
+    } else if let Some(path) = &dwallet.encrypted_share_path {
+        if path.exists() {
+            format!("encrypted in {}", path.display())
+        } else {
+            format!("missing, expected encrypted in {}", path.display())
+        }
+    } else if dwallet.dkg_output.is_some() {
+        format!("client config {}", context.config.path().display())
+    } else {
+        "none".to_string()
+    }
+}
+
+/// The user share of `dwallet`, decrypted with the key of the first account of the keystore that
+/// opens it if it is held encrypted.
+fn decrypt_user_share(
+    context: &WalletContext,
+    dwallet: &DWalletSecretShare,
+) -> Result<DKGCentralizedPartyOutput, anyhow::Error> {
+    let Some(path) = &dwallet.encrypted_share_path else {
+        return dwallet.dkg_output();
+    };
+    if !path.exists() {
+        bail!(
+            "The user share of dWallet [{}] is missing from {}",
+            dwallet.alias,
+            path.display()
+        );
+    }
+    let keystore = &context.config.keystore;
+    keystore
+        .addresses()
+        .iter()
+        .filter_map(|address| keystore.get_key(address).ok())
+        .find_map(|key| dwallet.decrypt_share(&ShareEncryptionKey::derive(key)).ok())
+        .ok_or_else(|| {
+            anyhow!(
+                "No account of the keystore decrypts the user share of dWallet [{}]",
+                dwallet.alias
+            )
+        })
+}
+
 fn convert_private_key_to_base64(value: String) -> Result<ConvertOutput, anyhow::Error> {
     match Base64::decode(&value) {
         Ok(decoded) => {
@@ -3,14 +3,18 @@
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
 pub mod client_commands;
+pub mod completions;
 pub mod console;
 pub mod fire_drill;
 pub mod genesis_ceremony;
 pub mod genesis_inspector;
 pub mod key_identity;
 pub mod keytool;
+pub mod profile_commands;
 pub mod shell;
 pub mod sui_commands;
 pub mod dwallet_commands;
+pub mod env_commands;
+pub mod eth_light_client_commands;
 pub mod validator_commands;
 pub mod zklogin_commands_util;
@@ -3,6 +3,7 @@
 
 use clap::*;
 use colored::Colorize;
+use sui::dwallet_commands::{is_dwallet_timeout, DWALLET_TIMEOUT_EXIT_CODE};
 use sui::sui_commands::SuiCommand;
 use sui_types::exit_main;
 use tracing::debug;
@@ -47,6 +48,7 @@ async fn main() {
     let _guard = match args.command {
         SuiCommand::Console { .. }
         | SuiCommand::Client { .. }
+        | SuiCommand::Completions { .. }
         | SuiCommand::KeyTool { .. }
         | SuiCommand::Move { .. } => telemetry_subscribers::TelemetryConfig::new()
             .with_log_level("error")
@@ -59,5 +61,12 @@ async fn main() {
 
     debug!("Sui CLI version: {VERSION}");
 
-    exit_main!(args.command.execute().await);
+    let result = args.command.execute().await;
+    if let Err(err) = &result {
+        if is_dwallet_timeout(err) {
+            println!("{}", format!("{err:?}").bold().red());
+            std::process::exit(DWALLET_TIMEOUT_EXIT_CODE);
+        }
+    }
+    exit_main!(result);
 }
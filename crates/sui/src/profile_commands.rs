@@ -0,0 +1,182 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+use anyhow::anyhow;
+use clap::*;
+use sui_sdk::sui_client_config::ClientProfile;
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::base_types::ObjectID;
+
+use crate::client_commands::{ProfileOutput, ProfilesOutput, SuiClientCommandResult};
+use crate::key_identity::{get_identity_address, KeyIdentity};
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum SuiProfileCommands {
+    /// Add a profile, a named bundle of an environment, an active address, dWallets and gas
+    /// settings, or change the settings of one.
+    /// Switch to a profile with `sui client --profile <NAME>` or `sui client profile switch`.
+    #[command(name = "set")]
+    Set {
+        /// The name of the profile, e.g. the name of a custody tenant.
+        name: String,
+
+        /// The environment of the profile.
+        #[clap(long)]
+        env: Option<String>,
+
+        /// The active address of the profile, in the environment of the profile. It accepts also
+        /// the alias of the address.
+        #[clap(long)]
+        address: Option<KeyIdentity>,
+
+        /// The aliases or IDs of the dWallets usable in the profile, replacing the previous ones.
+        /// All the dWallets of the registry are usable in a profile without dWallets.
+        #[clap(long = "dwallet")]
+        dwallets: Vec<String>,
+
+        /// The alias or ID of the active dWallet of the profile.
+        #[clap(long)]
+        active_dwallet: Option<String>,
+
+        /// The default gas budget of the environment in the profile.
+        #[clap(long)]
+        gas_budget: Option<u64>,
+
+        /// The default gas price of the environment in the profile.
+        #[clap(long)]
+        gas_price: Option<u64>,
+    },
+
+    /// List the profiles of the client config.
+    #[command(name = "list")]
+    List,
+
+    /// Remove a profile. The settings it was switched to stay active.
+    #[command(name = "remove")]
+    Remove { name: String },
+
+    /// Switch to a profile, for subsequent commands.
+    #[command(name = "switch")]
+    Switch { name: String },
+}
+
+impl SuiProfileCommands {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        Ok(match self {
+            SuiProfileCommands::Set {
+                name,
+                env,
+                address,
+                dwallets,
+                active_dwallet,
+                gas_budget,
+                gas_price,
+            } => {
+                let mut profile = context
+                    .config
+                    .profiles
+                    .get(&name)
+                    .cloned()
+                    .unwrap_or_default();
+                if env.is_some() {
+                    profile.env = env;
+                }
+                if address.is_some() {
+                    profile.active_address = Some(get_identity_address(address, context)?);
+                }
+                if !dwallets.is_empty() {
+                    profile.dwallets = dwallets
+                        .iter()
+                        .map(|dwallet| dwallet_alias(context, dwallet))
+                        .collect::<Result<_, _>>()?;
+                }
+                if let Some(active_dwallet) = active_dwallet {
+                    profile.active_dwallet = Some(dwallet_alias(context, &active_dwallet)?);
+                }
+                if gas_budget.is_some() {
+                    profile.gas_budget = gas_budget;
+                }
+                if gas_price.is_some() {
+                    profile.gas_price = gas_price;
+                }
+                context.config.validate_profile(&profile)?;
+
+                let output = profile_output(name.clone(), &profile);
+                context.config.profiles.insert(name.clone(), profile);
+                // Apply the new settings right away to the active profile.
+                if context.config.active_profile.as_ref() == Some(&name) {
+                    context.config.switch_profile(&name)?;
+                }
+                context.config.save()?;
+
+                SuiClientCommandResult::Profiles(ProfilesOutput {
+                    active_profile: context.config.active_profile.clone(),
+                    profiles: vec![output],
+                })
+            }
+            SuiProfileCommands::List => SuiClientCommandResult::Profiles(ProfilesOutput {
+                active_profile: context.config.active_profile.clone(),
+                profiles: context
+                    .config
+                    .profiles
+                    .iter()
+                    .map(|(name, profile)| profile_output(name.clone(), profile))
+                    .collect(),
+            }),
+            SuiProfileCommands::Remove { name } => {
+                let profile = context
+                    .config
+                    .profiles
+                    .remove(&name)
+                    .ok_or_else(|| anyhow!("Profile [{name}] not found"))?;
+                if context.config.active_profile.as_ref() == Some(&name) {
+                    context.config.active_profile = None;
+                }
+                context.config.save()?;
+
+                SuiClientCommandResult::Profiles(ProfilesOutput {
+                    active_profile: context.config.active_profile.clone(),
+                    profiles: vec![profile_output(name, &profile)],
+                })
+            }
+            SuiProfileCommands::Switch { name } => {
+                context.config.switch_profile(&name)?;
+                context.config.save()?;
+                let profile = &context.config.profiles[&name];
+
+                SuiClientCommandResult::Profiles(ProfilesOutput {
+                    active_profile: Some(name.clone()),
+                    profiles: vec![profile_output(name, profile)],
+                })
+            }
+        })
+    }
+}
+
+/// The alias of the dWallet of the registry with the alias or ID `dwallet`, in any profile.
+fn dwallet_alias(context: &WalletContext, dwallet: &str) -> Result<String, anyhow::Error> {
+    let dwallet_id = dwallet.parse::<ObjectID>().ok();
+    context
+        .config
+        .dwallets
+        .iter()
+        .find(|entry| entry.alias == dwallet || Some(entry.dwallet_id) == dwallet_id)
+        .map(|entry| entry.alias.clone())
+        .ok_or_else(|| anyhow!("dWallet [{dwallet}] not found in the dWallet registry"))
+}
+
+fn profile_output(name: String, profile: &ClientProfile) -> ProfileOutput {
+    ProfileOutput {
+        name,
+        env: profile.env.clone(),
+        active_address: profile.active_address,
+        dwallets: profile.dwallets.clone(),
+        active_dwallet: profile.active_dwallet.clone(),
+        gas_budget: profile.gas_budget,
+        gas_price: profile.gas_price,
+    }
+}
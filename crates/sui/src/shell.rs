@@ -33,6 +33,7 @@ pub struct Shell<P: Display, S, H> {
     state: S,
     handler: H,
     command: CommandStructure,
+    completion_cache: CompletionCache,
 }
 
 impl<P: Display, S: Send, H: AsyncHandler<S>> Shell<P, S, H> {
@@ -51,9 +52,21 @@ impl<P: Display, S: Send, H: AsyncHandler<S>> Shell<P, S, H> {
             state,
             handler,
             command,
+            completion_cache: Arc::new(RwLock::new(BTreeMap::new())),
         }
     }
 
+    /// Complete the values of the flags of `completions`, e.g. `--dwallet`, from the start.
+    pub fn with_completions(
+        self,
+        completions: impl IntoIterator<Item = (CacheKey, Vec<String>)>,
+    ) -> Self {
+        if let Ok(mut cache) = self.completion_cache.write() {
+            cache.extend(completions);
+        }
+        self
+    }
+
     pub async fn run_async(
         &mut self,
         out: &mut (dyn Write + Send),
@@ -67,7 +80,7 @@ impl<P: Display, S: Send, H: AsyncHandler<S>> Shell<P, S, H> {
 
         let mut rl = Editor::with_config(config);
 
-        let completion_cache = Arc::new(RwLock::new(BTreeMap::new()));
+        let completion_cache = self.completion_cache.clone();
 
         rl.set_helper(Some(ShellHelper {
             command: self.command.clone(),
@@ -270,29 +283,33 @@ pub struct CommandStructure {
 }
 
 impl CommandStructure {
-    /// Create CommandStructure using clap::Command, currently only support 1 level of subcommands
+    /// Create CommandStructure using clap::Command, with the subcommands of its subcommands at
+    /// any depth, e.g. `dwallet future-sign approve`
     pub fn from_clap(app: &Command) -> Self {
         let subcommands = app
             .get_subcommands()
-            .map(|it| {
-                let name = it.get_name();
-                CommandStructure {
-                    name: name.to_string(),
-                    completions: it
-                        .get_opts()
-                        .map(|it| match it.get_long() {
-                            Some(long) => format!("--{}", long),
-                            None => format!("--{}", name),
-                        })
-                        .collect::<Vec<_>>(),
-                    children: vec![],
-                }
-            })
+            .map(Self::from_subcommand)
             .collect::<Vec<_>>();
 
         Self::from_children("", subcommands)
     }
 
+    fn from_subcommand(app: &Command) -> Self {
+        let name = app.get_name();
+        let subcommands = app
+            .get_subcommands()
+            .map(Self::from_subcommand)
+            .collect::<Vec<_>>();
+        let mut command = Self::from_children(name, subcommands);
+        command
+            .completions
+            .extend(app.get_opts().map(|it| match it.get_long() {
+                Some(long) => format!("--{}", long),
+                None => format!("--{}", name),
+            }));
+        command
+    }
+
     fn from_children(name: &str, children: Vec<CommandStructure>) -> Self {
         let completions = children
             .iter()
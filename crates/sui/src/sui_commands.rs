@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: BSD-3-Clause-Clear
 
 use crate::client_commands::SuiClientCommands;
+use crate::completions::{config_completions, generate_completions};
 use crate::console::start_console;
 use crate::fire_drill::{run_fire_drill, FireDrill};
 use crate::genesis_ceremony::{run, Ceremony};
@@ -9,9 +10,11 @@ use crate::keytool::KeyToolCommand;
 use crate::validator_commands::SuiValidatorCommand;
 use anyhow::{anyhow, bail};
 use clap::*;
+use clap_complete::Shell;
 use fastcrypto::traits::KeyPair;
 use move_package::BuildConfig;
 use rand::rngs::OsRng;
+use std::collections::BTreeMap;
 use std::io::{stderr, stdout, Write};
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
@@ -28,7 +31,7 @@ use sui_config::{
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
 use sui_move::{self, execute_move_command};
 use sui_move_build::SuiPackageHooks;
-use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv};
+use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv, SUI_CLIENT_CONFIG_VERSION};
 use sui_sdk::wallet_context::WalletContext;
 use sui_swarm::memory::Swarm;
 use sui_swarm_config::genesis_config::{GenesisConfig, DEFAULT_NUMBER_OF_AUTHORITIES};
@@ -113,6 +116,10 @@ pub enum SuiCommand {
         /// Sets the file storing the state of our user accounts (an empty one will be created if missing)
         #[clap(long = "client.config")]
         config: Option<PathBuf>,
+        /// Switch to this profile of the client config, and its environment, address, dWallets
+        /// and gas settings, before running the command. See `sui client profile`.
+        #[clap(long)]
+        profile: Option<String>,
         #[clap(subcommand)]
         cmd: Option<SuiClientCommands>,
         /// Return command outputs in json format.
@@ -150,6 +157,21 @@ pub enum SuiCommand {
         cmd: sui_move::Command,
     },
 
+    /// Generate the completions of the CLI for a shell, printed to stdout, e.g.
+    /// `sui completions bash > /etc/bash_completion.d/sui`.
+    /// The dWallet aliases, environments and Ethereum networks of the client config are completed
+    /// as of the generation: regenerate the completions once they change.
+    #[clap(name = "completions")]
+    Completions {
+        /// The shell to generate the completions for.
+        #[clap(value_enum)]
+        shell: Shell,
+        /// The client config to complete the dWallet aliases, environments and Ethereum networks
+        /// from.
+        #[clap(long = "client.config")]
+        config: Option<PathBuf>,
+    },
+
     /// Tool for Fire Drill
     FireDrill {
         #[clap(subcommand)]
@@ -278,6 +300,7 @@ impl SuiCommand {
             }
             SuiCommand::Client {
                 config,
+                profile,
                 cmd,
                 json,
                 accept_defaults,
@@ -285,6 +308,10 @@ impl SuiCommand {
                 let config_path = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
                 prompt_if_no_config(&config_path, accept_defaults).await?;
                 let mut context = WalletContext::new(&config_path, None, None).await?;
+                if let Some(profile) = profile {
+                    context.config.switch_profile(&profile)?;
+                    context.config.save()?;
+                }
                 if let Some(cmd) = cmd {
                     cmd.execute(&mut context).await?.print(!json);
                 } else {
@@ -319,6 +346,22 @@ impl SuiCommand {
                 build_config,
                 cmd,
             } => execute_move_command(package_path, build_config, cmd),
+            SuiCommand::Completions { shell, config } => {
+                let config_path = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
+                // Completions are generated without a client config too, e.g. when packaging.
+                let completions = match SuiClientConfig::load(&config_path) {
+                    Ok(config) => config_completions(&config),
+                    Err(_) => BTreeMap::new(),
+                };
+                generate_completions(
+                    shell,
+                    SuiCommand::command().name("sui"),
+                    "sui",
+                    &completions,
+                    &mut stdout(),
+                );
+                Ok(())
+            }
             SuiCommand::FireDrill { fire_drill } => run_fire_drill(fire_drill).await,
         }
     }
@@ -542,11 +585,13 @@ async fn genesis(
     if client_config.active_address.is_none() {
         client_config.active_address = active_address;
     }
-    client_config.add_env(SuiEnv {
-        alias: "localnet".to_string(),
-        rpc: format!("http://{}", fullnode_config.json_rpc_address),
-        ws: None,
-    });
+    client_config.add_env(
+        SuiEnv::builder(
+            "localnet",
+            format!("http://{}", fullnode_config.json_rpc_address),
+        )
+        .build()?,
+    );
     client_config.add_env(SuiEnv::devnet());
 
     if client_config.active_env.is_none() {
@@ -566,11 +611,7 @@ async fn prompt_if_no_config(
     // Prompt user for connect to devnet fullnode if config does not exist.
     if !wallet_conf_path.exists() {
         let env = match std::env::var_os("SUI_CONFIG_WITH_RPC_URL") {
-            Some(v) => Some(SuiEnv {
-                alias: "custom".to_string(),
-                rpc: v.into_string().unwrap(),
-                ws: None,
-            }),
+            Some(v) => Some(SuiEnv::builder("custom", v.into_string().unwrap()).build()?),
             None => {
                 if accept_defaults {
                     print!("Creating config file [{:?}] with default (devnet) Full node server and ed25519 key scheme.", wallet_conf_path);
@@ -601,11 +642,7 @@ async fn prompt_if_no_config(
                         } else {
                             alias
                         };
-                        SuiEnv {
-                            alias,
-                            rpc: url,
-                            ws: None,
-                        }
+                        SuiEnv::builder(alias, url).build()?
                     })
                 } else {
                     None
@@ -638,12 +675,16 @@ async fn prompt_if_no_config(
             println!("Secret Recovery Phrase : [{phrase}]");
             let alias = env.alias.clone();
             SuiClientConfig {
+                version: SUI_CLIENT_CONFIG_VERSION,
                 keystore,
                 envs: vec![env],
                 active_address: Some(new_address),
                 active_env: Some(alias),
                 dwallets: vec![],
                 active_dwallet: None,
+                address_aliases: Default::default(),
+                profiles: Default::default(),
+                active_profile: None,
             }
             .persisted(wallet_conf_path)
             .save()?;
@@ -652,7 +693,7 @@ async fn prompt_if_no_config(
     Ok(())
 }
 
-fn read_line() -> Result<String, anyhow::Error> {
+pub(crate) fn read_line() -> Result<String, anyhow::Error> {
     let mut s = String::new();
     let _ = stdout().flush();
     io::stdin().read_line(&mut s)?;
@@ -224,6 +224,8 @@ async fn test_private_keys_ed25519() -> Result<(), anyhow::Error> {
             input_string: private_key.to_string(),
             key_scheme: SignatureScheme::ED25519,
             derivation_path: None,
+            account_index: None,
+            allow_custom_path: false,
         }
         .execute(&mut keystore)
         .await?;
@@ -241,6 +243,8 @@ async fn test_private_keys_ed25519() -> Result<(), anyhow::Error> {
             input_string: private_key[..25].to_string(),
             key_scheme: SignatureScheme::ED25519,
             derivation_path: None,
+            account_index: None,
+            allow_custom_path: false,
         }
         .execute(&mut keystore)
         .await;
@@ -264,6 +268,8 @@ async fn test_mnemonics_ed25519() -> Result<(), anyhow::Error> {
             input_string: t[0].to_string(),
             key_scheme: SignatureScheme::ED25519,
             derivation_path: None,
+            account_index: None,
+            allow_custom_path: false,
         }
         .execute(&mut keystore)
         .await?;
@@ -289,6 +295,8 @@ async fn test_mnemonics_secp256k1() -> Result<(), anyhow::Error> {
             input_string: t[0].to_string(),
             key_scheme: SignatureScheme::Secp256k1,
             derivation_path: None,
+            account_index: None,
+            allow_custom_path: false,
         }
         .execute(&mut keystore)
         .await?;
@@ -328,6 +336,8 @@ async fn test_mnemonics_secp256r1() -> Result<(), anyhow::Error> {
             input_string: t[0].to_string(),
             key_scheme: SignatureScheme::Secp256r1,
             derivation_path: None,
+            account_index: None,
+            allow_custom_path: false,
         }
         .execute(&mut keystore)
         .await?;
@@ -350,6 +360,8 @@ async fn test_invalid_derivation_path() -> Result<(), anyhow::Error> {
         input_string: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/44'/1'/0'/0/0".parse().unwrap()),
+        account_index: None,
+        allow_custom_path: false,
     }
     .execute(&mut keystore)
     .await
@@ -360,6 +372,8 @@ async fn test_invalid_derivation_path() -> Result<(), anyhow::Error> {
         input_string: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/0'/784'/0'/0/0".parse().unwrap()),
+        account_index: None,
+        allow_custom_path: false,
     }
     .execute(&mut keystore)
     .await
@@ -370,6 +384,8 @@ async fn test_invalid_derivation_path() -> Result<(), anyhow::Error> {
         input_string: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/54'/784'/0'/0/0".parse().unwrap()),
+        account_index: None,
+        allow_custom_path: false,
     }
     .execute(&mut keystore)
     .await
@@ -380,6 +396,8 @@ async fn test_invalid_derivation_path() -> Result<(), anyhow::Error> {
         input_string: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::Secp256k1,
         derivation_path: Some("m/54'/784'/0'/0'/0'".parse().unwrap()),
+        account_index: None,
+        allow_custom_path: false,
     }
     .execute(&mut keystore)
     .await
@@ -390,6 +408,8 @@ async fn test_invalid_derivation_path() -> Result<(), anyhow::Error> {
         input_string: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::Secp256k1,
         derivation_path: Some("m/44'/784'/0'/0/0".parse().unwrap()),
+        account_index: None,
+        allow_custom_path: false,
     }
     .execute(&mut keystore)
     .await
@@ -406,6 +426,8 @@ async fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
         input_string: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/44'/784'/0'/0'/0'".parse().unwrap()),
+        account_index: None,
+        allow_custom_path: false,
     }
     .execute(&mut keystore)
     .await
@@ -416,6 +438,8 @@ async fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
         input_string: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/44'/784'/0'/0'/1'".parse().unwrap()),
+        account_index: None,
+        allow_custom_path: false,
     }
     .execute(&mut keystore)
     .await
@@ -426,6 +450,8 @@ async fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
         input_string: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::ED25519,
         derivation_path: Some("m/44'/784'/1'/0'/1'".parse().unwrap()),
+        account_index: None,
+        allow_custom_path: false,
     }
     .execute(&mut keystore)
     .await
@@ -436,6 +462,8 @@ async fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
         input_string: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::Secp256k1,
         derivation_path: Some("m/54'/784'/0'/0/1".parse().unwrap()),
+        account_index: None,
+        allow_custom_path: false,
     }
     .execute(&mut keystore)
     .await
@@ -446,6 +474,8 @@ async fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
         input_string: TEST_MNEMONIC.to_string(),
         key_scheme: SignatureScheme::Secp256k1,
         derivation_path: Some("m/54'/784'/1'/0/1".parse().unwrap()),
+        account_index: None,
+        allow_custom_path: false,
     }
     .execute(&mut keystore)
     .await
@@ -453,6 +483,58 @@ async fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[test]
+async fn test_account_index_and_custom_derivation_path() -> Result<(), anyhow::Error> {
+    let mut keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(0));
+    KeyToolCommand::Import {
+        alias: None,
+        input_string: TEST_MNEMONIC.to_string(),
+        key_scheme: SignatureScheme::ED25519,
+        derivation_path: None,
+        account_index: Some(1),
+        allow_custom_path: false,
+    }
+    .execute(&mut keystore)
+    .await?;
+    let mut other_keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(0));
+    let address = other_keystore.import_from_mnemonic(
+        TEST_MNEMONIC,
+        SignatureScheme::ED25519,
+        Some("m/44'/784'/0'/0'/1'".parse().unwrap()),
+    )?;
+    assert_eq!(keystore.addresses(), vec![address]);
+
+    // The path of an Ethereum account is only accepted as a custom path.
+    let import_ethereum_account = |allow_custom_path| KeyToolCommand::Import {
+        alias: None,
+        input_string: TEST_MNEMONIC.to_string(),
+        key_scheme: SignatureScheme::Secp256k1,
+        derivation_path: Some("m/44'/60'/0'/0/0".parse().unwrap()),
+        account_index: None,
+        allow_custom_path,
+    };
+    assert!(import_ethereum_account(false)
+        .execute(&mut keystore)
+        .await
+        .is_err());
+    import_ethereum_account(true).execute(&mut keystore).await?;
+    assert_eq!(keystore.addresses().len(), 2);
+
+    // Ed25519 keys are only derived at hardened levels.
+    assert!(KeyToolCommand::Import {
+        alias: None,
+        input_string: TEST_MNEMONIC.to_string(),
+        key_scheme: SignatureScheme::ED25519,
+        derivation_path: Some("m/44'/501'/0'/0".parse().unwrap()),
+        account_index: None,
+        allow_custom_path: true,
+    }
+    .execute(&mut keystore)
+    .await
+    .is_err());
+    Ok(())
+}
+
 #[test]
 async fn test_keytool_bls12381() -> Result<(), anyhow::Error> {
     let mut keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(0));
@@ -521,3 +603,66 @@ async fn test_sign_command() -> Result<(), anyhow::Error> {
     .await?;
     Ok(())
 }
+
+#[test]
+async fn test_shamir_split_restore_key() -> Result<(), anyhow::Error> {
+    let mut keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(1));
+    let address = keystore.addresses()[0];
+    let dir = TempDir::new().unwrap();
+
+    KeyToolCommand::ShamirSplit {
+        key: Some(KeyIdentity::Address(address)),
+        dwallet: None,
+        threshold: 2,
+        shares: 3,
+        output_dir: Some(dir.path().to_path_buf()),
+        config: None,
+    }
+    .execute(&mut keystore)
+    .await?;
+    let fragment = |index| {
+        dir.path()
+            .join(format!("{address}-fragment-{index}-of-3.txt"))
+            .display()
+            .to_string()
+    };
+
+    // Any two of the fragments restore the key.
+    let mut restored = Keystore::from(InMemKeystore::new_insecure_for_tests(0));
+    KeyToolCommand::ShamirRestore {
+        fragments: vec![fragment(3), fragment(1)],
+        alias: Some("restored".to_string()),
+        owner: None,
+        config: None,
+    }
+    .execute(&mut restored)
+    .await?;
+    assert_eq!(restored.addresses(), vec![address]);
+    assert_eq!(restored.get_alias_by_address(&address)?, "restored");
+
+    // One fragment does not.
+    let mut restored = Keystore::from(InMemKeystore::new_insecure_for_tests(0));
+    assert!(KeyToolCommand::ShamirRestore {
+        fragments: vec![fragment(2)],
+        alias: None,
+        owner: None,
+        config: None,
+    }
+    .execute(&mut restored)
+    .await
+    .is_err());
+
+    // The fragments are not overwritten by another split.
+    assert!(KeyToolCommand::ShamirSplit {
+        key: Some(KeyIdentity::Address(address)),
+        dwallet: None,
+        threshold: 2,
+        shares: 3,
+        output_dir: Some(dir.path().to_path_buf()),
+        config: None,
+    }
+    .execute(&mut keystore)
+    .await
+    .is_err());
+    Ok(())
+}
@@ -5,6 +5,7 @@ use std::collections::BTreeMap;
 use std::env;
 use std::sync::Arc;
 
+use clap::{Arg, Command};
 use rustyline::completion::Completer;
 use rustyline::history::History;
 use rustyline::Context;
@@ -233,3 +234,33 @@ fn test_split_line() {
         result
     );
 }
+
+#[test]
+fn test_completer_nested_subcommands() {
+    let app = Command::new("test").subcommand(
+        Command::new("dwallet")
+            .subcommand(Command::new("sign").arg(Arg::new("dwallet").long("dwallet")))
+            .subcommand(Command::new("list")),
+    );
+    let helper = ShellHelper {
+        command: CommandStructure::from_clap(&app),
+        completion_cache: Arc::new(Default::default()),
+    };
+    helper.completion_cache.write().unwrap().insert(
+        CacheKey::flag("--dwallet"),
+        vec!["my-btc-vault".to_string()],
+    );
+
+    let complete = |line: &str| {
+        helper
+            .complete(line, 1, &Context::new(&History::new()))
+            .unwrap()
+            .1
+            .into_iter()
+            .map(|pair| pair.display)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(vec!["sign", "list"], complete("dwallet "));
+    assert_eq!(vec!["--dwallet"], complete("dwallet sign "));
+    assert_eq!(vec!["my-btc-vault"], complete("dwallet sign --dwallet "));
+}
@@ -9,11 +9,13 @@ use std::{
     fs,
     path::PathBuf,
 };
+use sui_config::node::default_admin_interface_port;
 use sui_genesis_builder::validator_info::GenesisValidatorInfo;
 
 use sui_types::{
     base_types::{ObjectID, ObjectRef, SuiAddress},
     crypto::{AuthorityPublicKey, NetworkPublicKey, Signable, DEFAULT_EPOCH_ID},
+    messages_signature_mpc::{SignatureMPCProtocol, SignatureMPCStatusSummary},
     multiaddr::Multiaddr,
     object::Owner,
     sui_system_state::{
@@ -29,7 +31,7 @@ use clap::*;
 use colored::Colorize;
 use fastcrypto::traits::ToFromBytes;
 use fastcrypto::{
-    encoding::{Base64, Encoding},
+    encoding::{Base64, Encoding, Hex},
     traits::KeyPair,
 };
 use serde::Serialize;
@@ -163,6 +165,16 @@ pub enum SuiValidatorCommand {
         #[clap(name = "gas-budget", long)]
         gas_budget: Option<u64>,
     },
+    /// Show the health of the signature MPC service of the validator node running on this machine:
+    /// whether it has its share of the decryption key, the sessions it took part in during the
+    /// current epoch, its presign backlog and its recent failures.
+    /// Read from the admin interface of the node, which only listens on localhost.
+    #[clap(name = "mpc-status")]
+    MpcStatus {
+        /// Port of the admin interface of the node.
+        #[clap(name = "admin-port", long, default_value_t = default_admin_interface_port())]
+        admin_port: u16,
+    },
 }
 
 #[derive(Serialize)]
@@ -181,6 +193,7 @@ pub enum SuiValidatorCommandResponse {
         data: TransactionData,
         serialized_data: String,
     },
+    MpcStatus(SignatureMPCStatusSummary),
 }
 
 fn make_key_files(
@@ -454,6 +467,19 @@ impl SuiValidatorCommand {
                     serialized_data,
                 }
             }
+
+            SuiValidatorCommand::MpcStatus { admin_port } => {
+                let url = format!("http://127.0.0.1:{admin_port}/signature-mpc-status");
+                let status = reqwest::get(&url)
+                    .await
+                    .map_err(|e| {
+                        anyhow!("Cannot reach the admin interface of the node at {url}: {e}")
+                    })?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+                SuiValidatorCommandResponse::MpcStatus(status)
+            }
         });
         ret
     }
@@ -689,6 +715,9 @@ impl Display for SuiValidatorCommandResponse {
                     data, serialized_data
                 )?;
             }
+            SuiValidatorCommandResponse::MpcStatus(status) => {
+                write_mpc_status(&mut writer, status)?;
+            }
         }
         write!(f, "{}", writer.trim_end_matches('\n'))
     }
@@ -715,6 +744,56 @@ pub fn write_transaction_response(
     Ok(writer)
 }
 
+fn write_mpc_status(writer: &mut String, status: &SignatureMPCStatusSummary) -> fmt::Result {
+    let key_share = if status.key_share_present {
+        "present".green()
+    } else {
+        "missing".red()
+    };
+    writeln!(writer, "Key share: {key_share}")?;
+    match (status.epoch, status.party_id) {
+        (Some(epoch), Some(party_id)) => {
+            writeln!(writer, "Epoch: {epoch}, party ID: {party_id}")?;
+        }
+        _ => writeln!(
+            writer,
+            "{}",
+            "The signature MPC service is not running".yellow()
+        )?,
+    }
+    writeln!(writer, "Presign backlog: {}", status.presign.pending)?;
+
+    writeln!(writer, "\n----- Sessions this epoch ----")?;
+    for (protocol, sessions) in [
+        (SignatureMPCProtocol::DKG, &status.dkg),
+        (SignatureMPCProtocol::Presign, &status.presign),
+        (SignatureMPCProtocol::Sign, &status.sign),
+    ] {
+        writeln!(
+            writer,
+            "{protocol:<8} participated: {}, completed: {}, failed: {}, pending: {}",
+            sessions.participated, sessions.completed, sessions.failed, sessions.pending
+        )?;
+    }
+
+    writeln!(writer, "\n----- Recent failures ----")?;
+    if status.recent_failures.is_empty() {
+        writeln!(writer, "None")?;
+    }
+    for failure in status.recent_failures.iter().rev() {
+        writeln!(
+            writer,
+            "{} epoch {} {} session 0x{}: {}",
+            failure.timestamp_ms,
+            failure.epoch,
+            failure.protocol,
+            Hex::encode(failure.session_id.0),
+            failure.error.red()
+        )?;
+    }
+    Ok(())
+}
+
 impl Debug for SuiValidatorCommandResponse {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let string = serde_json::to_string_pretty(self);
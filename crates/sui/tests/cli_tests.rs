@@ -347,7 +347,7 @@ async fn test_gas_command() -> Result<(), anyhow::Error> {
         to: KeyIdentity::Address(SuiAddress::random_for_testing_only()),
         object_id: object_to_send,
         gas: Some(object_id),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
     }
@@ -396,7 +396,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: TEST_ONLY_GAS_UNIT_FOR_PUBLISH * rgp,
+        gas_budget: Some(TEST_ONLY_GAS_UNIT_FOR_PUBLISH * rgp),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
         serialize_unsigned_transaction: false,
@@ -479,7 +479,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         type_args: vec![],
         args,
         gas: None,
-        gas_budget: TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS * rgp,
+        gas_budget: Some(TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS * rgp),
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
     }
@@ -518,7 +518,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         type_args: vec![],
         args: args.to_vec(),
         gas: Some(gas),
-        gas_budget: TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS * rgp,
+        gas_budget: Some(TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS * rgp),
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
     }
@@ -544,7 +544,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         type_args: vec![],
         args: args.to_vec(),
         gas: Some(gas),
-        gas_budget: TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS * rgp,
+        gas_budget: Some(TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS * rgp),
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
     }
@@ -572,7 +572,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         type_args: vec![],
         args: args.to_vec(),
         gas: Some(gas),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS),
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
     }
@@ -617,7 +617,7 @@ async fn test_package_publish_command() -> Result<(), anyhow::Error> {
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
         serialize_unsigned_transaction: false,
@@ -684,7 +684,7 @@ async fn test_delete_shared_object() -> Result<(), anyhow::Error> {
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
         serialize_unsigned_transaction: false,
@@ -718,7 +718,7 @@ async fn test_delete_shared_object() -> Result<(), anyhow::Error> {
         function: "start".to_string(),
         type_args: vec![],
         gas: None,
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         args: vec![],
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
@@ -740,7 +740,7 @@ async fn test_delete_shared_object() -> Result<(), anyhow::Error> {
         function: "delete".to_string(),
         type_args: vec![],
         gas: None,
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         args: vec![SuiJsonValue::from_str(&shared_id.to_string()).unwrap()],
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
@@ -791,7 +791,7 @@ async fn test_receive_argument() -> Result<(), anyhow::Error> {
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
         serialize_unsigned_transaction: false,
@@ -825,7 +825,7 @@ async fn test_receive_argument() -> Result<(), anyhow::Error> {
         function: "start".to_string(),
         type_args: vec![],
         gas: None,
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         args: vec![],
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
@@ -863,7 +863,7 @@ async fn test_receive_argument() -> Result<(), anyhow::Error> {
         function: "receiver".to_string(),
         type_args: vec![],
         gas: None,
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         args: vec![
             SuiJsonValue::from_str(&parent.object_id.to_string()).unwrap(),
             SuiJsonValue::from_str(&child.object_id.to_string()).unwrap(),
@@ -917,7 +917,7 @@ async fn test_receive_argument_by_immut_ref() -> Result<(), anyhow::Error> {
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
         serialize_unsigned_transaction: false,
@@ -951,7 +951,7 @@ async fn test_receive_argument_by_immut_ref() -> Result<(), anyhow::Error> {
         function: "start".to_string(),
         type_args: vec![],
         gas: None,
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         args: vec![],
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
@@ -989,7 +989,7 @@ async fn test_receive_argument_by_immut_ref() -> Result<(), anyhow::Error> {
         function: "invalid_call_immut_ref".to_string(),
         type_args: vec![],
         gas: None,
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         args: vec![
             SuiJsonValue::from_str(&parent.object_id.to_string()).unwrap(),
             SuiJsonValue::from_str(&child.object_id.to_string()).unwrap(),
@@ -1043,7 +1043,7 @@ async fn test_receive_argument_by_mut_ref() -> Result<(), anyhow::Error> {
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
         serialize_unsigned_transaction: false,
@@ -1077,7 +1077,7 @@ async fn test_receive_argument_by_mut_ref() -> Result<(), anyhow::Error> {
         function: "start".to_string(),
         type_args: vec![],
         gas: None,
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         args: vec![],
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
@@ -1115,7 +1115,7 @@ async fn test_receive_argument_by_mut_ref() -> Result<(), anyhow::Error> {
         function: "invalid_call_mut_ref".to_string(),
         type_args: vec![],
         gas: None,
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         args: vec![
             SuiJsonValue::from_str(&parent.object_id.to_string()).unwrap(),
             SuiJsonValue::from_str(&child.object_id.to_string()).unwrap(),
@@ -1171,7 +1171,7 @@ async fn test_package_publish_command_with_unpublished_dependency_succeeds(
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         skip_dependency_verification: false,
         with_unpublished_dependencies,
         serialize_unsigned_transaction: false,
@@ -1239,7 +1239,7 @@ async fn test_package_publish_command_with_unpublished_dependency_fails(
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         skip_dependency_verification: false,
         with_unpublished_dependencies,
         serialize_unsigned_transaction: false,
@@ -1285,7 +1285,7 @@ async fn test_package_publish_command_non_zero_unpublished_dep_fails() -> Result
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         skip_dependency_verification: false,
         with_unpublished_dependencies,
         serialize_unsigned_transaction: false,
@@ -1340,7 +1340,7 @@ async fn test_package_publish_command_failure_invalid() -> Result<(), anyhow::Er
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         skip_dependency_verification: false,
         with_unpublished_dependencies,
         serialize_unsigned_transaction: false,
@@ -1382,7 +1382,7 @@ async fn test_package_publish_nonexistent_dependency() -> Result<(), anyhow::Err
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
         serialize_unsigned_transaction: false,
@@ -1425,7 +1425,7 @@ async fn test_package_publish_test_flag() -> Result<(), anyhow::Error> {
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
         serialize_unsigned_transaction: false,
@@ -1480,7 +1480,7 @@ async fn test_package_upgrade_command() -> Result<(), anyhow::Error> {
         package_path: package_path.clone(),
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
         serialize_unsigned_transaction: false,
@@ -1552,7 +1552,7 @@ async fn test_package_upgrade_command() -> Result<(), anyhow::Error> {
         upgrade_capability: cap.reference.object_id,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
         serialize_unsigned_transaction: false,
@@ -1616,7 +1616,7 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
         gas: Some(gas_obj_id),
         to: KeyIdentity::Address(recipient),
         object_id: obj_id,
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
     }
@@ -1721,7 +1721,7 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
         gas: None,
         to: KeyIdentity::Address(recipient),
         object_id: obj_id,
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
     }
@@ -2040,7 +2040,7 @@ async fn test_merge_coin() -> Result<(), anyhow::Error> {
         primary_coin,
         coin_to_merge,
         gas: Some(gas),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC),
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
     }
@@ -2095,7 +2095,7 @@ async fn test_merge_coin() -> Result<(), anyhow::Error> {
         primary_coin,
         coin_to_merge,
         gas: None,
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC),
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: false,
     }
@@ -2158,7 +2158,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
     // Test with gas specified
     let resp = SuiClientCommands::SplitCoin {
         gas: Some(gas),
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN),
         coin_id: coin,
         amounts: Some(vec![1000, 10]),
         count: None,
@@ -2226,7 +2226,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
     // Test split coin into equal parts
     let resp = SuiClientCommands::SplitCoin {
         gas: None,
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN),
         coin_id: coin,
         amounts: None,
         count: Some(3),
@@ -2297,7 +2297,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
     // Test with no gas specified
     let resp = SuiClientCommands::SplitCoin {
         gas: None,
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN),
         coin_id: coin,
         amounts: Some(vec![1000, 10]),
         count: None,
@@ -2408,7 +2408,7 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     SuiClientCommands::TransferSui {
         to: KeyIdentity::Address(address1),
         sui_coin_object_id: coin,
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
         amount: Some(1),
         serialize_unsigned_transaction: true,
         serialize_signed_transaction: false,
@@ -2419,7 +2419,7 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     SuiClientCommands::TransferSui {
         to: KeyIdentity::Address(address1),
         sui_coin_object_id: coin,
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
         amount: Some(1),
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: true,
@@ -2431,7 +2431,7 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     SuiClientCommands::TransferSui {
         to: KeyIdentity::Alias(alias1),
         sui_coin_object_id: coin,
-        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
         amount: Some(1),
         serialize_unsigned_transaction: false,
         serialize_signed_transaction: true,
@@ -2676,3 +2676,296 @@ async fn key_identity_test() {
         get_identity_address(None, context).unwrap()
     );
 }
+
+#[test]
+fn test_dwallet_json_output() {
+    use sui::client_commands::{
+        DWalletBindingOutput, FutureSignOutput, NewSignOutput, SignBatchOutput,
+        SignBatchSessionOutput, SignSessionOutput, SignSessionsOutput, SigningPolicyOutput,
+        VerifiedSignatureOutput,
+    };
+    use sui_types::digests::TransactionDigest;
+
+    let dwallet_id = ObjectID::random();
+    let sign_output_id = ObjectID::random();
+    let result = SuiClientCommandResult::NewSignOutput(NewSignOutput {
+        dwallet_id,
+        sign_output_id,
+        signatures: vec!["c2lnbmF0dXJl".to_string()],
+    });
+    assert_eq!(
+        json!({
+            "dwalletId": dwallet_id,
+            "signOutputId": sign_output_id,
+            "signatures": ["c2lnbmF0dXJl"],
+        }),
+        serde_json::from_str::<serde_json::Value>(&format!("{result:?}")).unwrap()
+    );
+
+    let approvals_id = ObjectID::random();
+    let dwallet_cap_id = ObjectID::random();
+    let result = SuiClientCommandResult::FutureSign(FutureSignOutput {
+        approvals_id,
+        alias: None,
+        dwallet_cap_id,
+        messages: vec!["00ff".to_string()],
+    });
+    assert_eq!(
+        json!({
+            "approvalsId": approvals_id,
+            "dwalletCapId": dwallet_cap_id,
+            "messages": ["00ff"],
+        }),
+        serde_json::from_str::<serde_json::Value>(&format!("{result:?}")).unwrap()
+    );
+
+    let session_id = ObjectID::random();
+    let retry_id = ObjectID::random();
+    let sender = SuiAddress::random_for_testing_only();
+    let result = SuiClientCommandResult::SignSessions(SignSessionsOutput {
+        dwallet_id,
+        sessions: vec![SignSessionOutput {
+            session_id,
+            status: "retried".to_string(),
+            sender,
+            message_digests: vec!["ab".to_string()],
+            requested_at_ms: Some(1_700_000_000_000),
+            sign_output_id: None,
+            signed_at_ms: None,
+            retry_of: None,
+            retried_by: Some(retry_id),
+        }],
+    });
+    assert_eq!(
+        json!({
+            "dwalletId": dwallet_id,
+            "sessions": [{
+                "sessionId": session_id,
+                "status": "retried",
+                "sender": sender,
+                "messageDigests": ["ab"],
+                "requestedAtMs": 1_700_000_000_000u64,
+                "retriedBy": retry_id,
+            }],
+        }),
+        serde_json::from_str::<serde_json::Value>(&format!("{result:?}")).unwrap()
+    );
+
+    let result = SuiClientCommandResult::SignBatch(SignBatchOutput {
+        dwallet_id,
+        output: PathBuf::from("signatures.json"),
+        signed_messages: 2,
+        sessions: vec![SignBatchSessionOutput {
+            session_id,
+            sign_output_id,
+            hash: "sha256".to_string(),
+            messages: 2,
+        }],
+    });
+    assert_eq!(
+        json!({
+            "dwalletId": dwallet_id,
+            "output": "signatures.json",
+            "signedMessages": 2,
+            "sessions": [{
+                "sessionId": session_id,
+                "signOutputId": sign_output_id,
+                "hash": "sha256",
+                "messages": 2,
+            }],
+        }),
+        serde_json::from_str::<serde_json::Value>(&format!("{result:?}")).unwrap()
+    );
+
+    let result = SuiClientCommandResult::VerifiedSignature(VerifiedSignatureOutput {
+        dwallet_id,
+        public_key: "02ab".to_string(),
+        hash: "keccak256".to_string(),
+        message_digest: "cd".to_string(),
+    });
+    assert_eq!(
+        json!({
+            "dwalletId": dwallet_id,
+            "publicKey": "02ab",
+            "hash": "keccak256",
+            "messageDigest": "cd",
+        }),
+        serde_json::from_str::<serde_json::Value>(&format!("{result:?}")).unwrap()
+    );
+
+    let policy_id = ObjectID::random();
+    let owner = SuiAddress::random_for_testing_only();
+    let result = SuiClientCommandResult::SigningPolicy(SigningPolicyOutput {
+        policy_id,
+        alias: Some("vault".to_string()),
+        owner,
+        dwallet_cap_id,
+        allowlist: vec![],
+        max_messages: 10,
+        window_ms: 60000,
+        co_approvers: vec![owner],
+        co_approval_threshold: 1,
+        pending_requests: 0,
+        digest: None,
+    });
+    assert_eq!(
+        json!({
+            "policyId": policy_id,
+            "alias": "vault",
+            "owner": owner,
+            "dwalletCapId": dwallet_cap_id,
+            "allowlist": [],
+            "maxMessages": 10,
+            "windowMs": 60000,
+            "coApprovers": [owner],
+            "coApprovalThreshold": 1,
+            "pendingRequests": 0,
+        }),
+        serde_json::from_str::<serde_json::Value>(&format!("{result:?}")).unwrap()
+    );
+
+    let binding_id = ObjectID::random();
+    let digest = TransactionDigest::random();
+    let result = SuiClientCommandResult::DWalletBinding(DWalletBindingOutput {
+        binding_id,
+        dwallet_id,
+        chain: "ethereum".to_string(),
+        external_address: "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf".to_string(),
+        digest,
+    });
+    assert_eq!(
+        json!({
+            "bindingId": binding_id,
+            "dwalletId": dwallet_id,
+            "chain": "ethereum",
+            "externalAddress": "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf",
+            "digest": digest,
+        }),
+        serde_json::from_str::<serde_json::Value>(&format!("{result:?}")).unwrap()
+    );
+}
+
+#[test]
+fn test_latency_output() {
+    use std::time::Duration;
+    use sui::client_commands::LatencyOutput;
+
+    let latencies = (1..=100).rev().map(Duration::from_millis).collect();
+    let latency = LatencyOutput::new(latencies);
+    assert_eq!(
+        (latency.samples, latency.mean_ms, latency.max_ms),
+        (100, 50, 100)
+    );
+    assert_eq!(
+        (latency.p50_ms, latency.p90_ms, latency.p99_ms),
+        (50, 90, 99)
+    );
+
+    let latency = LatencyOutput::new(vec![Duration::from_millis(7)]);
+    assert_eq!((latency.p50_ms, latency.p99_ms, latency.max_ms), (7, 7, 7));
+
+    let latency = LatencyOutput::new(vec![]);
+    assert_eq!(
+        (latency.samples, latency.mean_ms, latency.p99_ms),
+        (0, 0, 0)
+    );
+}
+
+#[test]
+fn test_completions() {
+    use clap::CommandFactory;
+    use std::collections::BTreeMap;
+    use sui::completions::generate_completions;
+
+    let completions = BTreeMap::from([
+        ("dwallet", vec!["my-btc-vault".to_string()]),
+        ("network", vec!["sepolia".to_string()]),
+    ]);
+    for shell in [
+        clap_complete::Shell::Bash,
+        clap_complete::Shell::Zsh,
+        clap_complete::Shell::Fish,
+    ] {
+        let mut script = vec![];
+        generate_completions(
+            shell,
+            SuiCommand::command().name("sui"),
+            "sui",
+            &completions,
+            &mut script,
+        );
+        let script = String::from_utf8(script).unwrap();
+        for completion in [
+            "sign-eth-tx",
+            "future-sign",
+            "eth-lightclient",
+            "my-btc-vault",
+            "sepolia",
+        ] {
+            assert!(
+                script.contains(completion),
+                "no {completion} in the {shell} completions"
+            );
+        }
+    }
+}
+
+#[sim_test]
+async fn test_env_set_eth() -> Result<(), anyhow::Error> {
+    use sui::env_commands::SuiEnvCommands;
+
+    let mut cluster = TestClusterBuilder::new().build().await;
+    let context = cluster.wallet_mut();
+    let set_eth = |chain_id, execution_rpc: Option<&str>, state_object_id| SuiClientCommands::Env {
+        cmd: SuiEnvCommands::SetEth {
+            network: "sepolia".to_string(),
+            env: None,
+            chain_id,
+            execution_rpc: execution_rpc.map(str::to_string),
+            consensus_rpc: None,
+            consensus_rpc_requests_per_second: None,
+            state_object_id,
+            finality: None,
+            explorer_url: None,
+            trust_configured_checkpoints: false,
+        },
+    };
+
+    // A new network needs a chain id and an execution RPC, which must be a valid URL.
+    assert!(set_eth(None, None, None).execute(context).await.is_err());
+    assert!(set_eth(Some(11155111), Some("not a url"), None)
+        .execute(context)
+        .await
+        .is_err());
+    assert!(context.config.get_active_env()?.eth_networks.is_empty());
+
+    set_eth(Some(11155111), Some("https://rpc.sepolia.org"), None)
+        .execute(context)
+        .await?;
+    let state_object_id = ObjectID::random();
+    set_eth(None, None, Some(state_object_id))
+        .execute(context)
+        .await?;
+
+    let result = SuiClientCommands::Env {
+        cmd: SuiEnvCommands::ShowEth {
+            network: Some("sepolia".to_string()),
+            env: None,
+        },
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::EthNetworks(output) = result else {
+        panic!("Command failed")
+    };
+    assert_eq!(output.networks.len(), 1);
+    assert_eq!(output.networks[0].chain_id, 11155111);
+    assert_eq!(output.networks[0].execution_rpc, "https://rpc.sepolia.org");
+    assert_eq!(output.networks[0].state_object_id, Some(state_object_id));
+
+    // The settings are saved to the client config.
+    let config = PersistedConfig::<SuiClientConfig>::read(context.config.path())?;
+    let network = config.get_active_env()?.eth_network("sepolia")?;
+    assert_eq!(network.state_object_id, Some(state_object_id));
+    Ok(())
+}
@@ -23,7 +23,7 @@ use sui_json_rpc_types::{
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
 use sui_node::SuiNodeHandle;
 use sui_protocol_config::{ProtocolVersion, SupportedProtocolVersions};
-use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv};
+use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv, SUI_CLIENT_CONFIG_VERSION};
 use sui_sdk::wallet_context::WalletContext;
 use sui_sdk::{SuiClient, SuiClientBuilder};
 use sui_swarm::memory::{Swarm, SwarmBuilder};
@@ -658,6 +658,7 @@ pub struct TestClusterBuilder {
     default_jwks: bool,
     overload_threshold_config: Option<OverloadThresholdConfig>,
     data_ingestion_dir: Option<PathBuf>,
+    signature_mpc_threshold: Option<usize>,
 }
 
 impl TestClusterBuilder {
@@ -679,6 +680,7 @@ impl TestClusterBuilder {
             default_jwks: false,
             overload_threshold_config: None,
             data_ingestion_dir: None,
+            signature_mpc_threshold: None,
         }
     }
 
@@ -828,6 +830,14 @@ impl TestClusterBuilder {
         self
     }
 
+    /// The number of validators, out of [Self::with_num_validators], needed to decrypt in the
+    /// signature MPC protocols. More than two thirds of them by default.
+    pub fn with_signature_mpc_threshold(mut self, threshold: usize) -> Self {
+        assert!(self.network_config.is_none());
+        self.signature_mpc_threshold = Some(threshold);
+        self
+    }
+
     pub async fn build(mut self) -> TestCluster {
         // All test clusters receive a continuous stream of random JWKs.
         // If we later use zklogin authenticated transactions in tests we will need to supply
@@ -869,11 +879,12 @@ impl TestClusterBuilder {
         let fullnode_handle =
             FullNodeHandle::new(fullnode.get_node_handle().unwrap(), json_rpc_address).await;
 
-        wallet_conf.envs.push(SuiEnv {
-            alias: "localnet".to_string(),
-            rpc: fullnode_handle.rpc_url.clone(),
-            ws: Some(fullnode_handle.ws_url.clone()),
-        });
+        wallet_conf.envs.push(
+            SuiEnv::builder("localnet", &fullnode_handle.rpc_url)
+                .ws(&fullnode_handle.ws_url)
+                .build()
+                .unwrap(),
+        );
         wallet_conf.active_env = Some("localnet".to_string());
 
         wallet_conf
@@ -922,6 +933,10 @@ impl TestClusterBuilder {
             builder = builder.with_overload_threshold_config(overload_threshold_config);
         }
 
+        if let Some(threshold) = self.signature_mpc_threshold {
+            builder = builder.with_signature_mpc_threshold(threshold);
+        }
+
         if let Some(fullnode_rpc_port) = self.fullnode_rpc_port {
             builder = builder.with_fullnode_rpc_port(fullnode_rpc_port);
         }
@@ -960,12 +975,16 @@ impl TestClusterBuilder {
 
         // Create wallet config with stated authorities port
         SuiClientConfig {
+            version: SUI_CLIENT_CONFIG_VERSION,
             keystore: Keystore::from(FileBasedKeystore::new(&keystore_path)?),
             envs: Default::default(),
             active_address,
             active_env: Default::default(),
             dwallets: vec![],
             active_dwallet: None,
+            address_aliases: Default::default(),
+            profiles: Default::default(),
+            active_profile: None,
         }
         .save(wallet_path)?;
 
@@ -17,7 +17,7 @@ use signature_mpc::twopc_mpc_protocols::Scalar;
 use signature_mpc::twopc_mpc_protocols::{
     decommitment_round_centralized_party_dkg, initiate_centralized_party_dkg,
     DKGDecommitmentRoundState, ProtocolContext, SecretKeyShareEncryptionAndProof,
-    recovery_id, PublicKeyValue, SignatureK256Secp256k1, Hash
+    public_key_to_sec1_compressed, PublicKeyValue, SignatureK256Secp256k1, Hash
 };
 use wasm_bindgen::prelude::*;
 
@@ -183,34 +183,60 @@ pub fn finalize_sign(
     .and_then(|_| Ok(()))?)
 }
 
+/// The compressed SEC1 encoding of the dWallet public key of `dkg_output`, as used by external
+/// chains to derive addresses and verify signatures.
 #[wasm_bindgen]
-pub fn recovery_id_keccak256(
+pub fn dkg_output_public_key(dkg_output: Vec<u8>) -> Result<Vec<u8>, JsErr> {
+    let dkg_output: DKGCentralizedPartyOutput = bcs::from_bytes(&dkg_output)?;
+    Ok(public_key_to_sec1_compressed(dkg_output.public_key))
+}
+
+/// The recovery id of `signature` over `message` hashed with `hash` (0 for KECCAK256, 1 for
+/// SHA256), needed by chains that recover the signer from the signature.
+#[wasm_bindgen]
+pub fn recovery_id(
     public_key: Vec<u8>,
     message: Vec<u8>,
     signature: Vec<u8>,
+    hash: u8,
 ) -> Result<u8, JsErr> {
+    let hash = match hash {
+        0 => Hash::KECCAK256,
+        1 => Hash::SHA256,
+        _ => {
+            return Err(JsErr {
+                message: format!("Unknown hash {hash}"),
+                display: format!("Unknown hash {hash}"),
+            })
+        }
+    };
     let public_key: PublicKeyValue = bcs::from_bytes(&public_key)?;
     let signature: SignatureK256Secp256k1 = bcs::from_bytes(&signature)?;
 
-    Ok(recovery_id(message, public_key, signature, &Hash::KECCAK256).map_err(|_| JsErr {
-        message: "Can't generate RecoveryId".to_string(),
-        display: "Can't generate RecoveryId".to_string(),
-    })?.into())
+    Ok(signature_mpc::twopc_mpc_protocols::recovery_id(message, public_key, signature, &hash)
+        .map_err(|_| JsErr {
+            message: "Can't generate RecoveryId".to_string(),
+            display: "Can't generate RecoveryId".to_string(),
+        })?
+        .into())
 }
 
 #[wasm_bindgen]
-pub fn recovery_id_sha256(
+pub fn recovery_id_keccak256(
     public_key: Vec<u8>,
     message: Vec<u8>,
     signature: Vec<u8>,
 ) -> Result<u8, JsErr> {
-    let public_key: PublicKeyValue = bcs::from_bytes(&public_key)?;
-    let signature: SignatureK256Secp256k1 = bcs::from_bytes(&signature)?;
+    recovery_id(public_key, message, signature, Hash::KECCAK256.into())
+}
 
-    Ok(recovery_id(message, public_key, signature, &Hash::SHA256).map_err(|_| JsErr {
-        message: "Can't generate RecoveryId".to_string(),
-        display: "Can't generate RecoveryId".to_string(),
-    })?.into())
+#[wasm_bindgen]
+pub fn recovery_id_sha256(
+    public_key: Vec<u8>,
+    message: Vec<u8>,
+    signature: Vec<u8>,
+) -> Result<u8, JsErr> {
+    recovery_id(public_key, message, signature, Hash::SHA256.into())
 }
 
 #[derive(Serialize, Deserialize)]
@@ -0,0 +1,12 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+
+import { describe, expect, it } from 'vitest';
+
+import { recovery_id } from '../pkg';
+
+describe('recovery_id', () => {
+	it('should reject an unknown hash', () => {
+		expect(() => recovery_id(new Uint8Array(), new Uint8Array(), new Uint8Array(), 2)).toThrow();
+	});
+});
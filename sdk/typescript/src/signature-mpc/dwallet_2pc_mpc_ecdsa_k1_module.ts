@@ -15,6 +15,8 @@ import { DWalletClient } from '../client';
 import { Keypair } from '../cryptography';
 
 export {
+	dkg_output_public_key as dkgOutputPublicKey,
+	recovery_id as recoveryId,
 	recovery_id_keccak256 as recoveryIdKeccak256,
 	recovery_id_sha256 as recoveryIdSha256,
 } from '@dwallet-network/signature-mpc-wasm/signature_mpc_wasm';
@@ -0,0 +1,333 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+use crate::NativesCostTable;
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::gas_algebra::InternalGas;
+use move_vm_runtime::{native_charge_gas_early_exit, native_functions::NativeContext};
+use move_vm_types::{
+    loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+};
+use smallvec::{smallvec, SmallVec};
+use std::{collections::VecDeque, ops::Mul};
+use sui_types::eth_light_client::{EthUpdate, Fork, LightClientBootstrap, LightClientStore, Root};
+use sui_types::eth_proofs::{verify_receipt_proof, Log};
+
+pub const INVALID_INPUT: u64 = 0;
+pub const INVALID_BOOTSTRAP: u64 = 1;
+pub const INVALID_UPDATE: u64 = 2;
+
+#[derive(Clone)]
+pub struct EthereumLightClientCostParams {
+    /// Base cost for invoking the `ethereum_bootstrap` function
+    pub ethereum_bootstrap_cost_base: InternalGas,
+    /// Cost per byte of `forks` and `bootstrap`
+    pub ethereum_bootstrap_cost_per_byte: InternalGas,
+    /// Cost per public key of the bootstrapped sync committee
+    pub ethereum_bootstrap_cost_per_pubkey: InternalGas,
+    /// Base cost for invoking the `ethereum_apply_update` function
+    pub ethereum_apply_update_cost_base: InternalGas,
+    /// Cost per byte of `store` and `update`
+    pub ethereum_apply_update_cost_per_byte: InternalGas,
+    /// Cost per public key aggregated or committed to by the update
+    pub ethereum_apply_update_cost_per_pubkey: InternalGas,
+    /// Base cost for invoking the `ethereum_verify_receipt` function
+    pub ethereum_verify_receipt_cost_base: InternalGas,
+    /// Cost per node of `proof`
+    pub ethereum_verify_receipt_cost_per_node: InternalGas,
+    /// Cost per byte of `proof`
+    pub ethereum_verify_receipt_cost_per_byte: InternalGas,
+    /// Base cost for invoking the `ethereum_verify_log` function
+    pub ethereum_verify_log_cost_base: InternalGas,
+    /// Cost per node of `proof`
+    pub ethereum_verify_log_cost_per_node: InternalGas,
+    /// Cost per byte of `proof` and `data`
+    pub ethereum_verify_log_cost_per_byte: InternalGas,
+}
+
+/***************************************************************************************************
+ * native fun ethereum_bootstrap
+ * Implementation of the Move native function `ethereum_light_client::ethereum_bootstrap(genesis_validators_root: vector<u8>, forks: vector<u8>, trusted_block_root: vector<u8>, bootstrap: vector<u8>): (vector<u8>, u64, u64, vector<u8>, vector<u8>, vector<u8>, u64);`
+ *   gas cost: ethereum_bootstrap_cost_base                                     | base cost for function call and fixed opers
+ *              + ethereum_bootstrap_cost_per_byte * (forks.len() + bootstrap.len()) | cost depends on the size of the input
+ *              + ethereum_bootstrap_cost_per_pubkey * committee size                | every public key of the committee is hashed
+ *
+ * Returns the BCS encoded light client store, and the finalized header, see `finalized_header`.
+ **************************************************************************************************/
+pub fn ethereum_bootstrap(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 4);
+
+    let cost_params = &context
+        .extensions()
+        .get::<NativesCostTable>()
+        .ethereum_light_client_cost_params
+        .clone();
+
+    native_charge_gas_early_exit!(context, cost_params.ethereum_bootstrap_cost_base);
+
+    let bootstrap_bytes = pop_arg!(args, Vec<u8>);
+    let trusted_block_root = pop_arg!(args, Vec<u8>);
+    let forks_bytes = pop_arg!(args, Vec<u8>);
+    let genesis_validators_root = pop_arg!(args, Vec<u8>);
+
+    // Charge the input dependent costs before decoding it
+    native_charge_gas_early_exit!(
+        context,
+        cost_params
+            .ethereum_bootstrap_cost_per_byte
+            .mul(((forks_bytes.len() + bootstrap_bytes.len()) as u64).into())
+    );
+
+    let (Ok(genesis_validators_root), Ok(trusted_block_root), Ok(forks), Ok(bootstrap)) = (
+        Root::try_from(genesis_validators_root),
+        Root::try_from(trusted_block_root),
+        bcs::from_bytes::<Vec<Fork>>(&forks_bytes),
+        bcs::from_bytes::<LightClientBootstrap>(&bootstrap_bytes),
+    ) else {
+        return Ok(NativeResult::err(context.gas_used(), INVALID_INPUT));
+    };
+
+    native_charge_gas_early_exit!(
+        context,
+        cost_params
+            .ethereum_bootstrap_cost_per_pubkey
+            .mul((bootstrap.current_sync_committee.pubkeys.len() as u64).into())
+    );
+
+    let cost = context.gas_used();
+
+    let Ok(store) = LightClientStore::new(
+        genesis_validators_root,
+        forks,
+        &trusted_block_root,
+        bootstrap,
+    ) else {
+        return Ok(NativeResult::err(cost, INVALID_BOOTSTRAP));
+    };
+
+    Ok(NativeResult::ok(cost, store_values(&store)))
+}
+
+/***************************************************************************************************
+ * native fun ethereum_apply_update
+ * Implementation of the Move native function `ethereum_light_client::ethereum_apply_update(store: vector<u8>, update: vector<u8>): (vector<u8>, u64, u64, vector<u8>, vector<u8>, vector<u8>, u64);`
+ *   gas cost: ethereum_apply_update_cost_base                                   | base cost for function call and fixed opers
+ *              + ethereum_apply_update_cost_per_byte * (store.len() + update.len()) | cost depends on the size of the input
+ *              + ethereum_apply_update_cost_per_pubkey * public keys               | the participants are aggregated, the next
+ *                                                                                     committee is hashed
+ *
+ * Returns the BCS encoded updated light client store, and its finalized header, see
+ * `finalized_header`.
+ **************************************************************************************************/
+pub fn ethereum_apply_update(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 2);
+
+    let cost_params = &context
+        .extensions()
+        .get::<NativesCostTable>()
+        .ethereum_light_client_cost_params
+        .clone();
+
+    native_charge_gas_early_exit!(context, cost_params.ethereum_apply_update_cost_base);
+
+    let update_bytes = pop_arg!(args, Vec<u8>);
+    let store_bytes = pop_arg!(args, Vec<u8>);
+
+    // Charge the input dependent costs before decoding it
+    native_charge_gas_early_exit!(
+        context,
+        cost_params
+            .ethereum_apply_update_cost_per_byte
+            .mul(((store_bytes.len() + update_bytes.len()) as u64).into())
+    );
+
+    let (Ok(mut store), Ok(update)) = (
+        bcs::from_bytes::<LightClientStore>(&store_bytes),
+        bcs::from_bytes::<EthUpdate>(&update_bytes),
+    ) else {
+        return Ok(NativeResult::err(context.gas_used(), INVALID_INPUT));
+    };
+
+    let pubkeys = match &update {
+        EthUpdate::Update(update) => {
+            update.sync_aggregate.participants() + update.next_sync_committee.pubkeys.len()
+        }
+        EthUpdate::FinalityUpdate(update) => update.sync_aggregate.participants(),
+    };
+    native_charge_gas_early_exit!(
+        context,
+        cost_params
+            .ethereum_apply_update_cost_per_pubkey
+            .mul((pubkeys as u64).into())
+    );
+
+    let cost = context.gas_used();
+
+    if store.apply_update(&update).is_err() {
+        return Ok(NativeResult::err(cost, INVALID_UPDATE));
+    }
+
+    Ok(NativeResult::ok(cost, store_values(&store)))
+}
+
+/***************************************************************************************************
+ * native fun ethereum_verify_receipt
+ * Implementation of the Move native function `ethereum_light_client::ethereum_verify_receipt(receipts_root: vector<u8>, transaction_index: u64, proof: vector<vector<u8>>): (bool, bool);`
+ *   gas cost: ethereum_verify_receipt_cost_base                          | base cost for function call and fixed opers
+ *              + ethereum_verify_receipt_cost_per_node * proof.len()      | every node is hashed and decoded
+ *              + ethereum_verify_receipt_cost_per_byte * proof bytes      | cost depends on the size of the nodes
+ *
+ * Returns whether `proof` proves the receipt of the transaction at `transaction_index`, and if so
+ * whether the transaction succeeded.
+ **************************************************************************************************/
+pub fn ethereum_verify_receipt(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let cost_params = &context
+        .extensions()
+        .get::<NativesCostTable>()
+        .ethereum_light_client_cost_params
+        .clone();
+
+    native_charge_gas_early_exit!(context, cost_params.ethereum_verify_receipt_cost_base);
+
+    let proof = pop_vector_of_bytes(&mut args)?;
+    let transaction_index = pop_arg!(args, u64);
+    let receipts_root = pop_arg!(args, Vec<u8>);
+
+    // Charge the proof dependent costs before decoding it
+    native_charge_gas_early_exit!(
+        context,
+        cost_params
+            .ethereum_verify_receipt_cost_per_node
+            .mul((proof.len() as u64).into())
+            + cost_params
+                .ethereum_verify_receipt_cost_per_byte
+                .mul((proof_bytes(&proof) as u64).into())
+    );
+
+    let cost = context.gas_used();
+
+    let Ok(receipts_root) = Root::try_from(receipts_root) else {
+        return Ok(NativeResult::err(cost, INVALID_INPUT));
+    };
+
+    let (valid, status) = match verify_receipt_proof(&receipts_root, transaction_index, &proof) {
+        Ok(receipt) => (true, receipt.status),
+        Err(_) => (false, false),
+    };
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(valid), Value::bool(status)],
+    ))
+}
+
+/***************************************************************************************************
+ * native fun ethereum_verify_log
+ * Implementation of the Move native function `ethereum_light_client::ethereum_verify_log(receipts_root: vector<u8>, transaction_index: u64, proof: vector<vector<u8>>, log_index: u64, address: vector<u8>, topics: vector<vector<u8>>, data: vector<u8>): bool;`
+ *   gas cost: ethereum_verify_log_cost_base                                  | base cost for function call and fixed opers
+ *              + ethereum_verify_log_cost_per_node * proof.len()              | every node is hashed and decoded
+ *              + ethereum_verify_log_cost_per_byte * (proof bytes + data.len()) | cost depends on the size of the input
+ *
+ * Returns whether `proof` proves the receipt of the transaction at `transaction_index`, and the
+ * log at `log_index` of the receipt is the given one.
+ **************************************************************************************************/
+pub fn ethereum_verify_log(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 7);
+
+    let cost_params = &context
+        .extensions()
+        .get::<NativesCostTable>()
+        .ethereum_light_client_cost_params
+        .clone();
+
+    native_charge_gas_early_exit!(context, cost_params.ethereum_verify_log_cost_base);
+
+    let data = pop_arg!(args, Vec<u8>);
+    let topics = pop_vector_of_bytes(&mut args)?;
+    let address = pop_arg!(args, Vec<u8>);
+    let log_index = pop_arg!(args, u64);
+    let proof = pop_vector_of_bytes(&mut args)?;
+    let transaction_index = pop_arg!(args, u64);
+    let receipts_root = pop_arg!(args, Vec<u8>);
+
+    // Charge the input dependent costs before decoding the proof
+    native_charge_gas_early_exit!(
+        context,
+        cost_params
+            .ethereum_verify_log_cost_per_node
+            .mul((proof.len() as u64).into())
+            + cost_params
+                .ethereum_verify_log_cost_per_byte
+                .mul(((proof_bytes(&proof) + data.len()) as u64).into())
+    );
+
+    let cost = context.gas_used();
+
+    let (Ok(receipts_root), Ok(address), Ok(topics)) = (
+        Root::try_from(receipts_root),
+        <[u8; 20]>::try_from(address),
+        topics
+            .into_iter()
+            .map(<[u8; 32]>::try_from)
+            .collect::<Result<Vec<_>, _>>(),
+    ) else {
+        return Ok(NativeResult::err(cost, INVALID_INPUT));
+    };
+    let expected = Log {
+        address,
+        topics,
+        data,
+    };
+
+    let valid = verify_receipt_proof(&receipts_root, transaction_index, &proof)
+        .and_then(|receipt| receipt.log(log_index).cloned())
+        .is_ok_and(|log| log == expected);
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(valid)]))
+}
+
+fn pop_vector_of_bytes(args: &mut VecDeque<Value>) -> PartialVMResult<Vec<Vec<u8>>> {
+    pop_arg!(args, Vec<Value>)
+        .into_iter()
+        .map(|bytes| bytes.value_as::<Vec<u8>>())
+        .collect()
+}
+
+/// The total size of the nodes of `proof`.
+fn proof_bytes(proof: &[Vec<u8>]) -> usize {
+    proof.iter().map(Vec::len).sum()
+}
+
+/// The BCS encoded `store`, then the slot, block number, block hash, state root, receipts root and
+/// timestamp of its finalized header.
+fn store_values(store: &LightClientStore) -> SmallVec<[Value; 1]> {
+    let header = &store.finalized_header;
+    smallvec![
+        Value::vector_u8(bcs::to_bytes(store).expect("the light client store is serializable")),
+        Value::u64(header.beacon.slot),
+        Value::u64(header.execution.block_number),
+        Value::vector_u8(header.execution.block_hash),
+        Value::vector_u8(header.execution.state_root),
+        Value::vector_u8(header.execution.receipts_root),
+        Value::u64(header.execution.timestamp),
+    ]
+}
@@ -12,4 +12,6 @@ pub mod hmac;
 pub mod zklogin;
 pub mod twopc_mpc;
 pub mod sui_state_proof;
+pub mod tendermint_light_client;
+pub mod ethereum_light_client;
 
@@ -0,0 +1,169 @@
+// Copyright (c) dWallet Labs, Ltd.
+// SPDX-License-Identifier: BSD-3-Clause-Clear
+use crate::NativesCostTable;
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::gas_algebra::InternalGas;
+use move_vm_runtime::{native_charge_gas_early_exit, native_functions::NativeContext};
+use move_vm_types::{
+    loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+};
+use smallvec::smallvec;
+use std::{collections::VecDeque, ops::Mul};
+use sui_types::tendermint::{
+    verify_light_block, verify_membership, ExistenceProof, LightBlock, ValidatorSet,
+};
+
+pub const INVALID_INPUT: u64 = 0;
+pub const INVALID_LIGHT_BLOCK: u64 = 1;
+
+#[derive(Clone)]
+pub struct TendermintLightClientCostParams {
+    /// Base cost for invoking the `tendermint_verify_light_block` function
+    pub tendermint_verify_light_block_cost_base: InternalGas,
+    /// Cost per byte of `chain_id`, `trusted_validators` and `light_block`
+    pub tendermint_verify_light_block_cost_per_byte: InternalGas,
+    /// Cost per commit signature of `light_block`
+    pub tendermint_verify_light_block_cost_per_signature: InternalGas,
+    /// Base cost for invoking the `tendermint_verify_membership` function
+    pub tendermint_verify_membership_cost_base: InternalGas,
+    /// Cost per byte of `proofs`, `path` and `value`
+    pub tendermint_verify_membership_cost_per_byte: InternalGas,
+    /// Cost per node hashed to compute the roots of `proofs`
+    pub tendermint_verify_membership_cost_per_step: InternalGas,
+}
+
+/***************************************************************************************************
+ * native fun tendermint_verify_light_block
+ * Implementation of the Move native function `tendermint_light_client::tendermint_verify_light_block(chain_id: vector<u8>, trusted_height: u64, trusted_validators: vector<u8>, light_block: vector<u8>): (u64, u64, vector<u8>, vector<u8>, vector<u8>);`
+ *   gas cost: tendermint_verify_light_block_cost_base                                   | base cost for function call and fixed opers
+ *              + tendermint_verify_light_block_cost_per_byte * input bytes                  | cost depends on the size of the input
+ *              + tendermint_verify_light_block_cost_per_signature * commit signatures       | every signature may be verified
+ *
+ * Returns the height, the timestamp in milliseconds, the app hash and the next validators hash of the
+ * verified header, and the hash of the trusted validators.
+ **************************************************************************************************/
+pub fn tendermint_verify_light_block(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 4);
+
+    let cost_params = &context
+        .extensions()
+        .get::<NativesCostTable>()
+        .tendermint_light_client_cost_params
+        .clone();
+
+    native_charge_gas_early_exit!(context, cost_params.tendermint_verify_light_block_cost_base);
+
+    let light_block_bytes = pop_arg!(args, Vec<u8>);
+    let trusted_validators_bytes = pop_arg!(args, Vec<u8>);
+    let trusted_height = pop_arg!(args, u64);
+    let chain_id_bytes = pop_arg!(args, Vec<u8>);
+
+    // Charge the input dependent costs before decoding it
+    let input_bytes =
+        chain_id_bytes.len() + trusted_validators_bytes.len() + light_block_bytes.len();
+    native_charge_gas_early_exit!(
+        context,
+        cost_params
+            .tendermint_verify_light_block_cost_per_byte
+            .mul((input_bytes as u64).into())
+    );
+
+    let (Ok(chain_id), Ok(trusted_validators), Ok(light_block)) = (
+        String::from_utf8(chain_id_bytes),
+        bcs::from_bytes::<ValidatorSet>(&trusted_validators_bytes),
+        bcs::from_bytes::<LightBlock>(&light_block_bytes),
+    ) else {
+        return Ok(NativeResult::err(context.gas_used(), INVALID_INPUT));
+    };
+
+    native_charge_gas_early_exit!(
+        context,
+        cost_params
+            .tendermint_verify_light_block_cost_per_signature
+            .mul((light_block.commit.signatures.len() as u64).into())
+    );
+
+    let cost = context.gas_used();
+
+    if verify_light_block(&chain_id, trusted_height, &trusted_validators, &light_block).is_err() {
+        return Ok(NativeResult::err(cost, INVALID_LIGHT_BLOCK));
+    }
+
+    let header = light_block.header;
+    let Ok(time_ms) = header.time.unix_ms() else {
+        return Ok(NativeResult::err(cost, INVALID_LIGHT_BLOCK));
+    };
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![
+            Value::u64(header.height),
+            Value::u64(time_ms),
+            Value::vector_u8(header.app_hash),
+            Value::vector_u8(header.next_validators_hash),
+            Value::vector_u8(trusted_validators.hash()),
+        ],
+    ))
+}
+
+/***************************************************************************************************
+ * native fun tendermint_verify_membership
+ * Implementation of the Move native function `tendermint_light_client::tendermint_verify_membership(app_hash: vector<u8>, proofs: vector<u8>, path: vector<vector<u8>>, value: vector<u8>): bool;`
+ *   gas cost: tendermint_verify_membership_cost_base                     | base cost for function call and fixed opers
+ *              + tendermint_verify_membership_cost_per_byte * input bytes    | cost depends on the size of the input
+ *              + tendermint_verify_membership_cost_per_step * proof nodes    | every leaf and inner node is hashed
+ **************************************************************************************************/
+pub fn tendermint_verify_membership(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 4);
+
+    let cost_params = &context
+        .extensions()
+        .get::<NativesCostTable>()
+        .tendermint_light_client_cost_params
+        .clone();
+
+    native_charge_gas_early_exit!(context, cost_params.tendermint_verify_membership_cost_base);
+
+    let value = pop_arg!(args, Vec<u8>);
+    let path = pop_arg!(args, Vec<Value>)
+        .into_iter()
+        .map(|key| key.value_as::<Vec<u8>>())
+        .collect::<PartialVMResult<Vec<_>>>()?;
+    let proofs_bytes = pop_arg!(args, Vec<u8>);
+    let app_hash = pop_arg!(args, Vec<u8>);
+
+    // Charge the input dependent costs before decoding the proofs
+    let path_bytes: usize = path.iter().map(Vec::len).sum();
+    native_charge_gas_early_exit!(
+        context,
+        cost_params
+            .tendermint_verify_membership_cost_per_byte
+            .mul(((proofs_bytes.len() + path_bytes + value.len()) as u64).into())
+    );
+
+    let Ok(proofs) = bcs::from_bytes::<Vec<ExistenceProof>>(&proofs_bytes) else {
+        return Ok(NativeResult::err(context.gas_used(), INVALID_INPUT));
+    };
+
+    let steps: usize = proofs.iter().map(|proof| 1 + proof.path.len()).sum();
+    native_charge_gas_early_exit!(
+        context,
+        cost_params
+            .tendermint_verify_membership_cost_per_step
+            .mul((steps as u64).into())
+    );
+
+    let cost = context.gas_used();
+
+    let valid = verify_membership(&app_hash, &proofs, &path, &value).is_ok();
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(valid)]))
+}
@@ -33,7 +33,7 @@ use self::{
     types::TypesIsOneTimeWitnessCostParams,
     validator::ValidatorValidateMetadataBcsCostParams,
 };
-use crate::crypto::{twopc_mpc, zklogin, sui_state_proof};
+use crate::crypto::{twopc_mpc, zklogin, sui_state_proof, tendermint_light_client, ethereum_light_client};
 use crate::crypto::zklogin::{CheckZkloginIdCostParams, CheckZkloginIssuerCostParams};
 use better_any::{Tid, TidAble};
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
@@ -58,6 +58,8 @@ use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_ADDRESS};
 use transfer::TransferReceiveObjectInternalCostParams;
 use crate::crypto::twopc_mpc::TwoPCMPCDKGCostParams;
 use crate::crypto::sui_state_proof::SuiStateProofCostParams;
+use crate::crypto::tendermint_light_client::TendermintLightClientCostParams;
+use crate::crypto::ethereum_light_client::EthereumLightClientCostParams;
 
 
 mod address;
@@ -156,6 +158,12 @@ pub struct NativesCostTable {
 
     // sui state proof
     pub sui_state_proof_cost_params: SuiStateProofCostParams,
+
+    // tendermint light client
+    pub tendermint_light_client_cost_params: TendermintLightClientCostParams,
+
+    // ethereum light client
+    pub ethereum_light_client_cost_params: EthereumLightClientCostParams,
 }
 
 impl NativesCostTable {
@@ -521,6 +529,28 @@ impl NativesCostTable {
                 sui_state_proof_verify_link_cap_base: protocol_config.sui_state_proof_verify_link_cap_base().into(),
                 sui_state_proof_verify_transaction_base: protocol_config.sui_state_proof_verify_transaction_base().into(),
             },
+            tendermint_light_client_cost_params: TendermintLightClientCostParams {
+                tendermint_verify_light_block_cost_base: protocol_config.tendermint_verify_light_block_cost_base().into(),
+                tendermint_verify_light_block_cost_per_byte: protocol_config.tendermint_verify_light_block_cost_per_byte().into(),
+                tendermint_verify_light_block_cost_per_signature: protocol_config.tendermint_verify_light_block_cost_per_signature().into(),
+                tendermint_verify_membership_cost_base: protocol_config.tendermint_verify_membership_cost_base().into(),
+                tendermint_verify_membership_cost_per_byte: protocol_config.tendermint_verify_membership_cost_per_byte().into(),
+                tendermint_verify_membership_cost_per_step: protocol_config.tendermint_verify_membership_cost_per_step().into(),
+            },
+            ethereum_light_client_cost_params: EthereumLightClientCostParams {
+                ethereum_bootstrap_cost_base: protocol_config.ethereum_bootstrap_cost_base().into(),
+                ethereum_bootstrap_cost_per_byte: protocol_config.ethereum_bootstrap_cost_per_byte().into(),
+                ethereum_bootstrap_cost_per_pubkey: protocol_config.ethereum_bootstrap_cost_per_pubkey().into(),
+                ethereum_apply_update_cost_base: protocol_config.ethereum_apply_update_cost_base().into(),
+                ethereum_apply_update_cost_per_byte: protocol_config.ethereum_apply_update_cost_per_byte().into(),
+                ethereum_apply_update_cost_per_pubkey: protocol_config.ethereum_apply_update_cost_per_pubkey().into(),
+                ethereum_verify_receipt_cost_base: protocol_config.ethereum_verify_receipt_cost_base().into(),
+                ethereum_verify_receipt_cost_per_node: protocol_config.ethereum_verify_receipt_cost_per_node().into(),
+                ethereum_verify_receipt_cost_per_byte: protocol_config.ethereum_verify_receipt_cost_per_byte().into(),
+                ethereum_verify_log_cost_base: protocol_config.ethereum_verify_log_cost_base().into(),
+                ethereum_verify_log_cost_per_node: protocol_config.ethereum_verify_log_cost_per_node().into(),
+                ethereum_verify_log_cost_per_byte: protocol_config.ethereum_verify_log_cost_per_byte().into(),
+            },
         }
     }
 }
@@ -766,6 +796,36 @@ pub fn all_natives(silent: bool) -> NativeFunctionTable {
             "sui_state_proof_verify_transaction",
             make_native!(sui_state_proof::sui_state_proof_verify_transaction),
         ),
+        (
+            "tendermint_light_client",
+            "tendermint_verify_light_block",
+            make_native!(tendermint_light_client::tendermint_verify_light_block),
+        ),
+        (
+            "tendermint_light_client",
+            "tendermint_verify_membership",
+            make_native!(tendermint_light_client::tendermint_verify_membership),
+        ),
+        (
+            "ethereum_light_client",
+            "ethereum_bootstrap",
+            make_native!(ethereum_light_client::ethereum_bootstrap),
+        ),
+        (
+            "ethereum_light_client",
+            "ethereum_apply_update",
+            make_native!(ethereum_light_client::ethereum_apply_update),
+        ),
+        (
+            "ethereum_light_client",
+            "ethereum_verify_receipt",
+            make_native!(ethereum_light_client::ethereum_verify_receipt),
+        ),
+        (
+            "ethereum_light_client",
+            "ethereum_verify_log",
+            make_native!(ethereum_light_client::ethereum_verify_log),
+        ),
         (
             "dwallet_2pc_mpc_ecdsa_k1",
             "sign_verify_encrypted_signature_parts_prehash",